@@ -0,0 +1,346 @@
+//! Audit log of the operations that modify files.
+//!
+//! When `audit.path` is set, every call of a tool that deletes, moves, writes or
+//! downloads files, and every `read_metadata` call extracting cover art, appends one
+//! JSON line to the log, whether it succeeded or failed:
+//!
+//! ```json
+//! {"timestamp":"2024-03-02T18:11:05Z","tool":"fs_delete","paths":["/music/old.mp3"],"params":{"path":"old.mp3","recursive":false,"dry_run":false},"outcome":"success"}
+//! ```
+//!
+//! Each line is written and flushed to disk before the tool returns its result. A call
+//! answered with a timeout is recorded with the outcome "timeout" when it is answered;
+//! its work carries on, and is recorded again once it completes. The log is only ever
+//! appended to; rotating it is left to the system.
+
+use rmcp::model::{CallToolResult, RawContent};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tracing::warn;
+
+use crate::core::config::Config;
+use crate::core::security::path_validator::resolve_path;
+use crate::core::time::format_time;
+
+/// Maximum number of items recorded from each array of the call parameters.
+const MAX_ARRAY_ITEMS: usize = 100;
+
+/// Serializes the writes of concurrent tool calls, so their lines never interleave.
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Whether an audited call succeeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Outcome {
+    Success,
+    Error,
+    /// Answered with a timeout error while its work carried on
+    Timeout,
+}
+
+/// One line of the audit log.
+#[derive(Debug, Serialize)]
+struct AuditEntry<'a> {
+    /// When the call finished, RFC 3339 in UTC
+    timestamp: String,
+    /// Name of the tool called
+    tool: &'a str,
+    /// Absolute paths the call operated on, with symlinks resolved
+    paths: Vec<String>,
+    /// Parameters of the call, with long strings and arrays truncated
+    params: Value,
+    outcome: Outcome,
+    /// Error returned by the tool
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Record a call of a tool that modifies files, if the audit log is enabled.
+///
+/// `paths` are the paths given in the parameters; they are resolved as they stand
+/// after the call, so a deleted or moved path is resolved through its parent. A log
+/// that cannot be written is reported as a warning and never fails the call.
+pub fn record<P: Serialize>(
+    config: &Config,
+    tool: &str,
+    params: &P,
+    paths: &[&str],
+    result: &CallToolResult,
+) {
+    append_entry(config, tool, params, paths, result, false);
+}
+
+/// Record a call of a tool that modifies files that was answered with `result`, a
+/// timeout error, while its work carried on, if the audit log is enabled.
+///
+/// The work is recorded again by [`record`] once it completes, so the log shows both
+/// what the client was told and what happened to the files.
+pub fn record_timeout<P: Serialize>(
+    config: &Config,
+    tool: &str,
+    params: &P,
+    paths: &[&str],
+    result: &CallToolResult,
+) {
+    append_entry(config, tool, params, paths, result, true);
+}
+
+/// Append the entry of a call to the log, if enabled.
+fn append_entry<P: Serialize>(
+    config: &Config,
+    tool: &str,
+    params: &P,
+    paths: &[&str],
+    result: &CallToolResult,
+    timed_out: bool,
+) {
+    let Some(log_path) = &config.audit.path else {
+        return;
+    };
+
+    let line = entry_line(config, tool, params, paths, result, timed_out);
+    if let Err(e) = append_line(log_path, &line) {
+        warn!(
+            "Failed to write the audit log {:?} for {}: {}",
+            log_path, tool, e
+        );
+    }
+}
+
+/// Build the JSON line recording a call.
+fn entry_line<P: Serialize>(
+    config: &Config,
+    tool: &str,
+    params: &P,
+    paths: &[&str],
+    result: &CallToolResult,
+    timed_out: bool,
+) -> String {
+    let max_bytes = config.audit.max_field_bytes;
+    let params = serde_json::to_value(params)
+        .map(|params| truncate_value(params, max_bytes))
+        .unwrap_or_else(|e| Value::String(format!("<unserializable parameters: {}>", e)));
+
+    let (outcome, error) = if result.is_error.unwrap_or(false) {
+        let text: Vec<&str> = result
+            .content
+            .iter()
+            .filter_map(|content| match &content.raw {
+                RawContent::Text(text) => Some(text.text.as_str()),
+                _ => None,
+            })
+            .collect();
+        (
+            if timed_out {
+                Outcome::Timeout
+            } else {
+                Outcome::Error
+            },
+            Some(truncate_str(&text.join("\n"), max_bytes)),
+        )
+    } else {
+        (Outcome::Success, None)
+    };
+
+    let entry = AuditEntry {
+        timestamp: format_time(SystemTime::now()),
+        tool,
+        paths: paths
+            .iter()
+            .filter(|path| !path.is_empty())
+            .map(|path| match resolve_path(Path::new(path)) {
+                Ok(resolved) => resolved.to_string_lossy().into_owned(),
+                Err(_) => path.to_string(),
+            })
+            .collect(),
+        params,
+        outcome,
+        error,
+    };
+    // An entry holds only strings and JSON values, which always serialize
+    serde_json::to_string(&entry).unwrap()
+}
+
+/// Append a line to the log and flush it to disk.
+fn append_line(log_path: &Path, line: &str) -> std::io::Result<()> {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)?;
+    // A single write, so the line stays whole even with other writers
+    file.write_all(format!("{}\n", line).as_bytes())?;
+    file.sync_data()
+}
+
+/// Truncate every string of a value to `max_bytes`, and every array to
+/// [`MAX_ARRAY_ITEMS`] items.
+fn truncate_value(value: Value, max_bytes: usize) -> Value {
+    match value {
+        Value::String(s) => Value::String(truncate_str(&s, max_bytes)),
+        Value::Array(items) => {
+            let total = items.len();
+            let mut items: Vec<Value> = items
+                .into_iter()
+                .take(MAX_ARRAY_ITEMS)
+                .map(|item| truncate_value(item, max_bytes))
+                .collect();
+            if total > MAX_ARRAY_ITEMS {
+                items.push(Value::String(format!(
+                    "... {} more items",
+                    total - MAX_ARRAY_ITEMS
+                )));
+            }
+            Value::Array(items)
+        }
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, truncate_value(value, max_bytes)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+/// Truncate a string to at most `max_bytes`, on a character boundary, noting how
+/// much was cut.
+fn truncate_str(s: &str, max_bytes: usize) -> String {
+    if s.len() <= max_bytes {
+        return s.to_string();
+    }
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}... [{} bytes truncated]", &s[..end], s.len() - end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::Content;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn audit_config(temp_dir: &TempDir) -> Config {
+        let mut config = Config::default();
+        config.audit.path = Some(temp_dir.path().join("audit.jsonl"));
+        config.audit.max_field_bytes = 8;
+        config
+    }
+
+    fn read_log(config: &Config) -> Vec<Value> {
+        std::fs::read_to_string(config.audit.path.as_ref().unwrap())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_truncate_value() {
+        let value = json!({
+            "content": "0123456789",
+            "title": "Café",
+            "paths": (0..105).collect::<Vec<_>>(),
+            "dry_run": true,
+        });
+        let value = truncate_value(value, 4);
+        assert_eq!(value["content"], "0123... [6 bytes truncated]");
+        // "é" takes two bytes and is not split
+        assert_eq!(value["title"], "Caf... [2 bytes truncated]");
+        assert_eq!(value["paths"].as_array().unwrap().len(), 101);
+        assert_eq!(value["paths"][100], "... 5 more items");
+        assert_eq!(value["dry_run"], true);
+    }
+
+    #[test]
+    fn test_record_appends_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = audit_config(&temp_dir);
+        let file = temp_dir.path().join("song.mp3");
+        let path = file.to_string_lossy().to_string();
+        let params = json!({ "path": path, "content": "a long file content" });
+
+        record(
+            &config,
+            "fs_write_file",
+            &params,
+            &[&path],
+            &CallToolResult::success(vec![Content::text("Wrote 19 bytes")]),
+        );
+        record(
+            &config,
+            "fs_write_file",
+            &params,
+            &[&path],
+            &CallToolResult::error(vec![Content::text("File already exists")]),
+        );
+
+        let entries = read_log(&config);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["tool"], "fs_write_file");
+        assert_eq!(entries[0]["outcome"], "success");
+        assert!(entries[0].get("error").is_none());
+        assert!(entries[0]["timestamp"].as_str().unwrap().ends_with('Z'));
+        // The file does not exist, so it is resolved through its parent
+        let resolved = temp_dir.path().canonicalize().unwrap().join("song.mp3");
+        assert_eq!(entries[0]["paths"][0], resolved.to_string_lossy().as_ref());
+        assert_eq!(
+            entries[0]["params"]["content"],
+            "a long f... [11 bytes truncated]"
+        );
+        assert_eq!(entries[1]["outcome"], "error");
+        assert_eq!(entries[1]["error"], "File alr... [11 bytes truncated]");
+    }
+
+    #[test]
+    fn test_record_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = audit_config(&temp_dir);
+        config.audit.max_field_bytes = 1024;
+        let params = json!({ "path": "Old", "recursive": true });
+
+        record_timeout(
+            &config,
+            "fs_delete",
+            &params,
+            &["Old"],
+            &CallToolResult::error(vec![Content::text("Tool 'fs_delete' timed out")]),
+        );
+        // The detached work completes later
+        record(
+            &config,
+            "fs_delete",
+            &params,
+            &["Old"],
+            &CallToolResult::success(vec![]),
+        );
+
+        let entries = read_log(&config);
+        assert_eq!(entries[0]["outcome"], "timeout");
+        assert_eq!(entries[0]["error"], "Tool 'fs_delete' timed out");
+        assert_eq!(entries[1]["outcome"], "success");
+    }
+
+    #[test]
+    fn test_record_disabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = audit_config(&temp_dir);
+        config.audit.path = None;
+        record(
+            &config,
+            "fs_delete",
+            &json!({}),
+            &[],
+            &CallToolResult::success(vec![]),
+        );
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+}
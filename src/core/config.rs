@@ -3,16 +3,24 @@
 //! This module provides a centralized configuration structure that can be
 //! populated from environment variables, configuration files, or defaults.
 
+use super::error::Error;
+use super::glob::GlobPattern;
+use super::security::AccessKind;
 use super::transport::TransportConfig;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tracing::{info, warn};
 
 /// Main configuration structure for the MCP server.
 ///
 /// This struct contains all configurable aspects of the server, organized
-/// by domain for clarity and maintainability.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// by domain for clarity and maintainability. Each field is a section of the
+/// TOML configuration file (see [`Config::from_file`]).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// Server identification and metadata.
     pub server: ServerConfig,
@@ -34,10 +42,23 @@ pub struct Config {
 
     /// Security and path validation configuration.
     pub security: SecurityConfig,
+
+    /// External tool binaries configuration.
+    pub tools: ToolsConfig,
+
+    /// MusicBrainz API client configuration.
+    pub musicbrainz: MusicBrainzConfig,
+
+    /// Metadata tools configuration.
+    pub metadata: MetadataConfig,
+
+    /// Audit log configuration.
+    pub audit: AuditConfig,
 }
 
 /// Server identification configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ServerConfig {
     /// The name of the server as reported to clients.
     pub name: String,
@@ -48,6 +69,7 @@ pub struct ServerConfig {
 
 /// Configuration for the resources domain.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ResourcesConfig {
     /// Base directory for file resources (if applicable).
     pub base_path: Option<String>,
@@ -56,6 +78,7 @@ pub struct ResourcesConfig {
 
 /// Configuration for the prompts domain.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct PromptsConfig {
     // Prompts are registered in domains/prompts/registry.rs
     // Add prompt-specific configuration here if needed.
@@ -63,6 +86,7 @@ pub struct PromptsConfig {
 
 /// Logging configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct LoggingConfig {
     /// Log level filter (e.g., "info", "debug", "trace").
     pub level: String,
@@ -71,12 +95,29 @@ pub struct LoggingConfig {
     pub with_timestamps: bool,
 }
 
+/// Public AcoustID application key used when none is configured.
+pub const DEFAULT_ACOUSTID_API_KEY: &str = "Kok2GHQlrAg";
+
 /// Configuration for external API credentials.
 #[derive(Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CredentialsConfig {
     /// AcoustID API key for audio fingerprinting.
     /// Get a free key at: https://acoustid.org/api-key
     pub acoustid_api_key: Option<String>,
+
+    /// AcoustID user API key, required to submit fingerprints.
+    /// Find yours at: https://acoustid.org/api-key (after signing in)
+    pub acoustid_user_key: Option<String>,
+}
+
+impl CredentialsConfig {
+    /// Whether a personal AcoustID API key is configured (not the public default).
+    pub fn has_custom_acoustid_key(&self) -> bool {
+        self.acoustid_api_key
+            .as_deref()
+            .is_some_and(|key| !key.is_empty() && key != DEFAULT_ACOUSTID_API_KEY)
+    }
 }
 
 /// Custom Debug implementation to redact secrets from logs.
@@ -87,29 +128,260 @@ impl std::fmt::Debug for CredentialsConfig {
                 "acoustid_api_key",
                 &self.acoustid_api_key.as_ref().map(|_| "[REDACTED]"),
             )
+            .field(
+                "acoustid_user_key",
+                &self.acoustid_user_key.as_ref().map(|_| "[REDACTED]"),
+            )
             .finish()
     }
 }
 
 /// Configuration for security and path validation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SecurityConfig {
-    /// Optional root directory for path operations.
-    /// If None, no path restrictions are enforced.
-    /// All file system operations will be validated against this root.
-    pub root_path: Option<PathBuf>,
+    /// Root directories for path operations: a path is allowed when it is under any
+    /// of them, e.g. a library and a downloads folder.
+    /// If empty, along with `read_paths` and `write_paths`, no path restrictions are enforced.
+    /// All file system operations will be validated against these roots.
+    pub allowed_paths: Vec<PathBuf>,
+
+    /// Root directories that can only be read, e.g. a library that tools may scan and
+    /// identify but never modify.
+    pub read_paths: Vec<PathBuf>,
+
+    /// Root directories that can be read and written, e.g. a staging folder where files
+    /// are tagged, renamed and deleted.
+    pub write_paths: Vec<PathBuf>,
 
     /// Whether to allow symlinks in path validation.
-    /// If true, symlinks are followed and their targets are validated.
-    /// If false, symlinks pointing outside the root are rejected.
+    /// Symlinks leading outside the allowed roots are always rejected.
+    /// If true, symlinks within the roots are followed.
+    /// If false, paths going through a symlink below an allowed root are rejected.
     pub allow_symlinks: bool,
+
+    /// Glob patterns of paths never accessed, even within the allowed roots, e.g. "*.key"
+    /// or ".ssh/**". A pattern matches the end of a path: its name, or its last
+    /// directories and name.
+    pub deny_patterns: Vec<String>,
+}
+
+/// Configuration for external tool binaries (e.g. Chromaprint's fpcalc) and tool limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolsConfig {
+    /// Explicit path to the fpcalc binary.
+    /// If None, fpcalc is looked up on the system PATH.
+    pub fpcalc_path: Option<PathBuf>,
+
+    /// Maximum time in seconds to wait for fpcalc before killing it.
+    pub fpcalc_timeout_secs: u64,
+
+    /// Maximum size in bytes of lyrics embedded by write_metadata.
+    pub max_lyrics_bytes: usize,
+
+    /// Default number of bytes returned by fs_read_file, unless a call asks for fewer or more.
+    pub max_read_bytes: usize,
+
+    /// Maximum size in bytes of the content fs_write_file writes in one call.
+    pub max_write_bytes: usize,
+
+    /// Maximum number of entries fs_list_dir lists in one call.
+    pub fs_max_entries: usize,
+
+    /// Maximum recursion depth of fs_list_dir.
+    pub fs_max_depth: usize,
+
+    /// Glob patterns of entries fs_list_dir and fs_find always leave out, e.g. "@eaDir".
+    pub fs_exclude: Vec<String>,
+
+    /// Whether fs_delete moves items to the trash, unless a call says otherwise.
+    pub fs_prefer_trash: bool,
+
+    /// Maximum number of files fs_checksum hashes in one call for a directory.
+    pub fs_checksum_max_files: usize,
+
+    /// Glob patterns of files fs_empty_dirs counts as junk, e.g. "Thumbs.db": a directory
+    /// holding only junk files is empty.
+    pub fs_junk_files: Vec<String>,
+
+    /// Maximum time in seconds a tool call may run before it is answered with a
+    /// timeout error.
+    pub default_timeout_secs: u64,
+
+    /// Timeouts in seconds of specific tools, by tool name, replacing
+    /// `default_timeout_secs`, e.g. a longer one for mb_identify_directory.
+    pub timeouts: HashMap<String, u64>,
+}
+
+impl ToolsConfig {
+    /// Maximum time a call of a tool may run.
+    pub fn timeout_for(&self, tool: &str) -> Duration {
+        Duration::from_secs(
+            self.timeouts
+                .get(tool)
+                .copied()
+                .unwrap_or(self.default_timeout_secs),
+        )
+    }
+}
+
+impl SecurityConfig {
+    /// Whether paths are restricted at all, i.e. any root directory is configured.
+    pub fn is_restricted(&self) -> bool {
+        !self.allowed_paths.is_empty()
+            || !self.read_paths.is_empty()
+            || !self.write_paths.is_empty()
+    }
+
+    /// The root directories granting an access: every root for reads, `allowed_paths`
+    /// and `write_paths` for writes.
+    pub fn roots_for(&self, access: AccessKind) -> Vec<PathBuf> {
+        let read_paths = match access {
+            AccessKind::Read => self.read_paths.as_slice(),
+            AccessKind::Write => &[],
+        };
+        self.allowed_paths
+            .iter()
+            .chain(read_paths)
+            .chain(&self.write_paths)
+            .cloned()
+            .collect()
+    }
+}
+
+impl ToolsConfig {
+    /// The fpcalc program to invoke: the configured path, or `fpcalc` from PATH.
+    pub fn fpcalc_program(&self) -> &Path {
+        self.fpcalc_path
+            .as_deref()
+            .unwrap_or_else(|| Path::new("fpcalc"))
+    }
+}
+
+/// Configuration for the metadata tools.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetadataConfig {
+    /// Whether write_metadata backs up files before writing, unless a call says otherwise.
+    pub backup_on_write: bool,
+
+    /// Directory for backups.
+    /// If None, backups are written next to the file as `<name>.bak`.
+    pub backup_dir: Option<PathBuf>,
+
+    /// Whether write_metadata restores each file's modification time after writing,
+    /// unless a call says otherwise.
+    pub preserve_mtime: bool,
+
+    /// ID3v2 version of the tags written to MP3 files, unless a call says otherwise.
+    pub id3_version: Id3Version,
+
+    /// JSON file mapping canonical genres to their aliases, used to normalize genres.
+    /// If None, a built-in list of common aliases is used.
+    pub genre_map_path: Option<PathBuf>,
+}
+
+/// Configuration for the audit log of operations that modify files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuditConfig {
+    /// File the audit log is appended to, one JSON line per call of a tool that
+    /// modifies files. If None, no audit log is kept.
+    pub path: Option<PathBuf>,
+
+    /// Maximum size in bytes of each string recorded from the call parameters;
+    /// longer strings, like file contents, are truncated.
+    pub max_field_bytes: usize,
+}
+
+/// ID3v2 version used when saving MP3 tags.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Id3Version {
+    /// ID3v2.3, the only version many car stereos and older players read
+    #[serde(rename = "2.3")]
+    V23,
+    /// ID3v2.4
+    #[default]
+    #[serde(rename = "2.4")]
+    V24,
+}
+
+impl std::str::FromStr for Id3Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().trim_start_matches(['v', 'V']) {
+            "2.3" => Ok(Self::V23),
+            "2.4" => Ok(Self::V24),
+            other => Err(format!(
+                "unknown ID3 version '{}', expected 2.3 or 2.4",
+                other
+            )),
+        }
+    }
+}
+
+/// Public MusicBrainz server used when no mirror is configured.
+///
+/// Plain HTTP, as musicbrainz_rs sends every request to `http://{host}/ws/2/...`.
+pub const DEFAULT_MUSICBRAINZ_BASE_URL: &str = "http://musicbrainz.org";
+
+/// User-Agent sent to MusicBrainz when none is configured.
+///
+/// MusicBrainz asks clients to identify themselves with an application name,
+/// version and contact address.
+pub const DEFAULT_MUSICBRAINZ_USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " ( https://github.com/sebetc4/rust_music_mcp_server )"
+);
+
+/// Configuration for the MusicBrainz API client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MusicBrainzConfig {
+    /// MusicBrainz server, e.g. a self-hosted mirror without rate limits.
+    pub base_url: String,
+
+    /// User-Agent header sent with every MusicBrainz request.
+    pub user_agent: String,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            name: "mcp-server".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            with_timestamps: true,
+        }
+    }
+}
+
+impl Default for MusicBrainzConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_MUSICBRAINZ_BASE_URL.to_string(),
+            user_agent: DEFAULT_MUSICBRAINZ_USER_AGENT.to_string(),
+        }
+    }
 }
 
 impl Default for CredentialsConfig {
     fn default() -> Self {
         Self {
             // Default public key for testing/demo purposes
-            acoustid_api_key: Some("Kok2GHQlrAg".to_string()),
+            acoustid_api_key: Some(DEFAULT_ACOUSTID_API_KEY.to_string()),
+            acoustid_user_key: None,
         }
     }
 }
@@ -118,33 +390,84 @@ impl Default for SecurityConfig {
     fn default() -> Self {
         Self {
             // No root path restriction by default (backwards compatible)
-            root_path: None,
+            allowed_paths: Vec::new(),
+            read_paths: Vec::new(),
+            write_paths: Vec::new(),
             // Allow symlinks by default with validation
             allow_symlinks: true,
+            // Keys and secrets, which no music tool has a reason to touch
+            deny_patterns: [".env", ".env.*", "*.key", "*.pem", ".ssh/**", ".gnupg/**"]
+                .map(String::from)
+                .to_vec(),
         }
     }
 }
 
-impl Default for Config {
+impl Default for ToolsConfig {
     fn default() -> Self {
         Self {
-            server: ServerConfig {
-                name: "mcp-server".to_string(),
-                version: env!("CARGO_PKG_VERSION").to_string(),
-            },
-            resources: ResourcesConfig::default(),
-            prompts: PromptsConfig::default(),
-            logging: LoggingConfig {
-                level: "info".to_string(),
-                with_timestamps: true,
-            },
-            transport: TransportConfig::default(),
-            credentials: CredentialsConfig::default(),
-            security: SecurityConfig::default(),
+            fpcalc_path: None,
+            fpcalc_timeout_secs: 60,
+            max_lyrics_bytes: 64 * 1024,
+            max_read_bytes: 256 * 1024,
+            max_write_bytes: 1024 * 1024,
+            fs_max_entries: 1000,
+            fs_max_depth: 10,
+            fs_exclude: Vec::new(),
+            fs_prefer_trash: false,
+            fs_checksum_max_files: 1000,
+            fs_junk_files: [".DS_Store", "Thumbs.db", "desktop.ini"]
+                .map(String::from)
+                .to_vec(),
+            default_timeout_secs: 600,
+            timeouts: HashMap::new(),
+        }
+    }
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            max_field_bytes: 1024,
+        }
+    }
+}
+
+/// Split a list of paths separated by `;`, or also by `:` outside Windows, where it
+/// would clash with drive letters.
+fn split_path_list(paths: &str) -> Vec<PathBuf> {
+    paths
+        .split(|c| c == ';' || (cfg!(not(windows)) && c == ':'))
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Log levels accepted by `logging.level`.
+const LOG_LEVELS: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+
+/// Problems found by [`Config::validate`], reported together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblems(pub Vec<String>);
+
+impl std::fmt::Display for ConfigProblems {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid configuration, {} problem(s) found:",
+            self.0.len()
+        )?;
+        for problem in &self.0 {
+            write!(f, "\n  - {}", problem)?;
         }
+        Ok(())
     }
 }
 
+impl std::error::Error for ConfigProblems {}
+
 impl Config {
     /// Create a new configuration with default values.
     pub fn new() -> Self {
@@ -159,50 +482,539 @@ impl Config {
         dotenvy::dotenv().ok();
 
         let mut config = Self::default();
+        config.apply_env();
+        config
+    }
 
+    /// Load configuration from a TOML file, then apply environment variables on top.
+    ///
+    /// Every section and field of the file is optional: missing values keep their
+    /// defaults, and an environment variable always wins over the file.
+    ///
+    /// ```toml
+    /// [security]
+    /// allowed_paths = ["/music"]
+    ///
+    /// [tools]
+    /// fs_max_depth = 5
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        dotenvy::dotenv().ok();
+
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            Error::config(format!("Cannot read config file {}: {}", path.display(), e))
+        })?;
+        let mut config: Self = toml::from_str(&contents)
+            .map_err(|e| Error::config(format!("Invalid config file {}: {}", path.display(), e)))?;
+        info!("Configuration loaded from {}", path.display());
+
+        config.apply_env();
+        Ok(config)
+    }
+
+    /// Load configuration from the file at `path` (e.g. given with `--config`), or at
+    /// `MCP_CONFIG_FILE` when no path is given; without either, from the environment only.
+    pub fn load(path: Option<&Path>) -> Result<Self, Error> {
+        dotenvy::dotenv().ok();
+
+        let path = path
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::var_os("MCP_CONFIG_FILE").map(PathBuf::from));
+        match path {
+            Some(path) => Self::from_file(path),
+            None => Ok(Self::from_env()),
+        }
+    }
+
+    /// Check the configuration before the server starts, so a typo'd path or an
+    /// out-of-range value is reported at once rather than at the first tool call.
+    ///
+    /// Every problem is collected, so they can all be fixed in one go. `tool_names` are the
+    /// tools `tools.timeouts` may name.
+    pub fn validate(&self, tool_names: &[&str]) -> Result<(), ConfigProblems> {
+        let mut problems = Vec::new();
+
+        if !LOG_LEVELS.contains(&self.logging.level.to_lowercase().as_str()) {
+            problems.push(format!(
+                "logging.level (MCP_LOG_LEVEL): '{}' is not one of {}",
+                self.logging.level,
+                LOG_LEVELS.join(", ")
+            ));
+        }
+
+        problems.extend(self.transport.problems());
+
+        for (setting, key) in [
+            (
+                "credentials.acoustid_api_key (MCP_ACOUSTID_API_KEY)",
+                &self.credentials.acoustid_api_key,
+            ),
+            (
+                "credentials.acoustid_user_key (MCP_ACOUSTID_USER_KEY)",
+                &self.credentials.acoustid_user_key,
+            ),
+        ] {
+            if key.as_deref().is_some_and(|key| key.trim().is_empty()) {
+                problems.push(format!("{}: is empty; set a key or remove it", setting));
+            }
+        }
+
+        for (setting, roots) in [
+            (
+                "security.allowed_paths (MCP_ALLOWED_PATHS)",
+                &self.security.allowed_paths,
+            ),
+            (
+                "security.read_paths (MCP_READ_PATHS)",
+                &self.security.read_paths,
+            ),
+            (
+                "security.write_paths (MCP_WRITE_PATHS)",
+                &self.security.write_paths,
+            ),
+        ] {
+            for root in roots {
+                if !root.exists() {
+                    problems.push(format!("{}: {} does not exist", setting, root.display()));
+                } else if !root.is_dir() {
+                    problems.push(format!(
+                        "{}: {} is not a directory",
+                        setting,
+                        root.display()
+                    ));
+                }
+            }
+        }
+        if !self.security.is_restricted() && !self.security.allow_symlinks {
+            problems.push(
+                "security.allow_symlinks (MCP_ALLOW_SYMLINKS): false has no effect without \
+                 security.allowed_paths (MCP_ALLOWED_PATHS), read_paths or write_paths, since \
+                 symlinks are only checked below the allowed roots"
+                    .to_string(),
+            );
+        }
+
+        let tools = &self.tools;
+        if let Some(fpcalc) = &tools.fpcalc_path
+            && !fpcalc.is_file()
+        {
+            problems.push(format!(
+                "tools.fpcalc_path (MCP_FPCALC_PATH): {} does not exist or is not a file",
+                fpcalc.display()
+            ));
+        }
+        for (setting, value) in [
+            (
+                "tools.fpcalc_timeout_secs (MCP_FPCALC_TIMEOUT_SECS)",
+                tools.fpcalc_timeout_secs as usize,
+            ),
+            (
+                "tools.max_lyrics_bytes (MCP_MAX_LYRICS_BYTES)",
+                tools.max_lyrics_bytes,
+            ),
+            (
+                "tools.max_read_bytes (MCP_MAX_READ_BYTES)",
+                tools.max_read_bytes,
+            ),
+            (
+                "tools.max_write_bytes (MCP_MAX_WRITE_BYTES)",
+                tools.max_write_bytes,
+            ),
+            (
+                "tools.fs_max_entries (MCP_FS_MAX_ENTRIES)",
+                tools.fs_max_entries,
+            ),
+            (
+                "tools.fs_checksum_max_files (MCP_FS_CHECKSUM_MAX_FILES)",
+                tools.fs_checksum_max_files,
+            ),
+            (
+                "tools.default_timeout_secs (MCP_TOOL_TIMEOUT_SECS)",
+                tools.default_timeout_secs as usize,
+            ),
+        ] {
+            if value == 0 {
+                problems.push(format!("{}: must be greater than 0", setting));
+            }
+        }
+        if !tools.timeouts.is_empty() {
+            let mut timeouts: Vec<_> = tools.timeouts.iter().collect();
+            timeouts.sort();
+            for (tool, secs) in timeouts {
+                if !tool_names.contains(&tool.as_str()) {
+                    problems.push(format!(
+                        "tools.timeouts (MCP_TOOL_TIMEOUTS): '{}' is not a tool name",
+                        tool
+                    ));
+                } else if *secs == 0 {
+                    problems.push(format!(
+                        "tools.timeouts (MCP_TOOL_TIMEOUTS): {}: must be greater than 0",
+                        tool
+                    ));
+                }
+            }
+        }
+        for (setting, patterns) in [
+            (
+                "security.deny_patterns (MCP_DENY_PATTERNS)",
+                &self.security.deny_patterns,
+            ),
+            ("tools.fs_exclude (MCP_FS_EXCLUDE)", &tools.fs_exclude),
+            (
+                "tools.fs_junk_files (MCP_FS_JUNK_FILES)",
+                &tools.fs_junk_files,
+            ),
+        ] {
+            for pattern in patterns {
+                if let Err(e) = GlobPattern::parse(pattern) {
+                    problems.push(format!("{}: {}", setting, e));
+                }
+            }
+        }
+
+        let base_url = self.musicbrainz.base_url.trim();
+        if base_url.starts_with("https://") {
+            // The client would silently send the requests over plain HTTP instead
+            problems.push(format!(
+                "musicbrainz.base_url (MCP_MB_BASE_URL): '{}' uses https://, but the MusicBrainz \
+                 client only sends plain HTTP requests; use an http:// URL",
+                self.musicbrainz.base_url
+            ));
+        } else if base_url.strip_prefix("http://").is_none_or(str::is_empty) {
+            problems.push(format!(
+                "musicbrainz.base_url (MCP_MB_BASE_URL): '{}' is not an http:// URL",
+                self.musicbrainz.base_url
+            ));
+        }
+        if self.musicbrainz.user_agent.trim().is_empty() {
+            problems.push(
+                "musicbrainz.user_agent (MCP_MB_USER_AGENT): is empty; MusicBrainz requires \
+                 clients to identify themselves"
+                    .to_string(),
+            );
+        }
+
+        if let Some(backup_dir) = &self.metadata.backup_dir
+            && backup_dir.exists()
+            && !backup_dir.is_dir()
+        {
+            problems.push(format!(
+                "metadata.backup_dir (MCP_METADATA_BACKUP_DIR): {} is not a directory",
+                backup_dir.display()
+            ));
+        }
+        if let Some(genre_map) = &self.metadata.genre_map_path
+            && !genre_map.is_file()
+        {
+            problems.push(format!(
+                "metadata.genre_map_path (MCP_METADATA_GENRE_MAP): {} does not exist or is not a file",
+                genre_map.display()
+            ));
+        }
+
+        if let Some(audit_path) = &self.audit.path {
+            if audit_path.is_dir() {
+                problems.push(format!(
+                    "audit.path (MCP_AUDIT_LOG): {} is a directory",
+                    audit_path.display()
+                ));
+            } else if let Some(parent) = audit_path.parent()
+                && !parent.as_os_str().is_empty()
+                && !parent.is_dir()
+            {
+                problems.push(format!(
+                    "audit.path (MCP_AUDIT_LOG): directory {} does not exist",
+                    parent.display()
+                ));
+            }
+        }
+        if self.audit.max_field_bytes == 0 {
+            problems.push(
+                "audit.max_field_bytes (MCP_AUDIT_MAX_FIELD_BYTES): must be greater than 0"
+                    .to_string(),
+            );
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigProblems(problems))
+        }
+    }
+
+    /// Override the configuration with the `MCP_*` environment variables that are set.
+    fn apply_env(&mut self) {
         if let Ok(name) = std::env::var("MCP_SERVER_NAME") {
-            config.server.name = name;
+            self.server.name = name;
         }
 
         if let Ok(level) = std::env::var("MCP_LOG_LEVEL") {
-            config.logging.level = level;
+            self.logging.level = level;
         }
 
         if let Ok(base_path) = std::env::var("MCP_RESOURCES_BASE_PATH") {
-            config.resources.base_path = Some(base_path);
+            self.resources.base_path = Some(base_path);
         }
 
         // Load transport configuration from environment
-        config.transport = TransportConfig::from_env();
+        self.transport.apply_env();
 
         // Load AcoustID API key
         if let Ok(api_key) = std::env::var("MCP_ACOUSTID_API_KEY") {
-            config.credentials.acoustid_api_key = Some(api_key);
+            self.credentials.acoustid_api_key = Some(api_key);
             info!("AcoustID API key loaded from environment");
-        } else {
+        } else if !self.credentials.has_custom_acoustid_key() {
             warn!(
                 "Using default AcoustID API key. For higher rate limits, \
                  set MCP_ACOUSTID_API_KEY (get your key at https://acoustid.org/api-key)"
             );
         }
 
+        if let Ok(user_key) = std::env::var("MCP_ACOUSTID_USER_KEY") {
+            self.credentials.acoustid_user_key = Some(user_key);
+            info!("AcoustID user key loaded from environment");
+        }
+
         // Load security configuration
-        if let Ok(root_path) = std::env::var("MCP_ROOT_PATH") {
-            config.security.root_path = Some(PathBuf::from(root_path));
-            info!("Path security enabled: root directory set to {:?}", config.security.root_path);
+        // MCP_ROOT_PATH, a single root, is still read, and combined with MCP_ALLOWED_PATHS
+        let mut allowed_paths = std::env::var("MCP_ALLOWED_PATHS")
+            .map(|paths| split_path_list(&paths))
+            .unwrap_or_default();
+        if let Ok(root_path) = std::env::var("MCP_ROOT_PATH")
+            && !root_path.trim().is_empty()
+        {
+            let root_path = PathBuf::from(root_path.trim());
+            if !allowed_paths.contains(&root_path) {
+                allowed_paths.push(root_path);
+            }
+        }
+        if !allowed_paths.is_empty() {
+            self.security.allowed_paths = allowed_paths;
+        }
+        if let Ok(read_paths) = std::env::var("MCP_READ_PATHS") {
+            self.security.read_paths = split_path_list(&read_paths);
+        }
+        if let Ok(write_paths) = std::env::var("MCP_WRITE_PATHS") {
+            self.security.write_paths = split_path_list(&write_paths);
+        }
+        if self.security.is_restricted() {
+            info!(
+                "Path security enabled: readable root directories {:?}, writable root directories {:?}",
+                self.security.roots_for(AccessKind::Read),
+                self.security.roots_for(AccessKind::Write)
+            );
         } else {
             warn!(
-                "MCP_ROOT_PATH not set - no path restrictions active. \
-                 All filesystem paths will be allowed."
+                "MCP_ALLOWED_PATHS (or security.allowed_paths, read_paths, write_paths) not set - \
+                 no path restrictions active. All filesystem paths will be allowed."
             );
         }
 
         if let Ok(allow_symlinks) = std::env::var("MCP_ALLOW_SYMLINKS") {
-            config.security.allow_symlinks = allow_symlinks.parse().unwrap_or(true);
-            info!("Symlinks allowed: {}", config.security.allow_symlinks);
+            self.security.allow_symlinks = allow_symlinks.parse().unwrap_or(true);
+            info!("Symlinks allowed: {}", self.security.allow_symlinks);
+        }
+        if let Ok(deny_patterns) = std::env::var("MCP_DENY_PATTERNS") {
+            self.security.deny_patterns = deny_patterns
+                .split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(String::from)
+                .collect();
         }
 
-        config
+        // Load external tool configuration
+        if let Ok(fpcalc_path) = std::env::var("MCP_FPCALC_PATH") {
+            let fpcalc_path = PathBuf::from(fpcalc_path);
+            if fpcalc_path.is_file() {
+                info!("Using fpcalc binary at {:?}", fpcalc_path);
+            } else {
+                warn!(
+                    "MCP_FPCALC_PATH points to {:?}, which does not exist or is not a file",
+                    fpcalc_path
+                );
+            }
+            self.tools.fpcalc_path = Some(fpcalc_path);
+        }
+        if let Ok(timeout) = std::env::var("MCP_FPCALC_TIMEOUT_SECS") {
+            match timeout.parse() {
+                Ok(secs) => self.tools.fpcalc_timeout_secs = secs,
+                Err(_) => warn!(
+                    "Invalid MCP_FPCALC_TIMEOUT_SECS '{}', using default of {}s",
+                    timeout, self.tools.fpcalc_timeout_secs
+                ),
+            }
+        }
+        if let Ok(max_bytes) = std::env::var("MCP_MAX_LYRICS_BYTES") {
+            match max_bytes.parse() {
+                Ok(bytes) => self.tools.max_lyrics_bytes = bytes,
+                Err(_) => warn!(
+                    "Invalid MCP_MAX_LYRICS_BYTES '{}', using default of {} bytes",
+                    max_bytes, self.tools.max_lyrics_bytes
+                ),
+            }
+        }
+        if let Ok(max_bytes) = std::env::var("MCP_MAX_READ_BYTES") {
+            match max_bytes.parse() {
+                Ok(bytes) => self.tools.max_read_bytes = bytes,
+                Err(_) => warn!(
+                    "Invalid MCP_MAX_READ_BYTES '{}', using default of {} bytes",
+                    max_bytes, self.tools.max_read_bytes
+                ),
+            }
+        }
+        if let Ok(max_bytes) = std::env::var("MCP_MAX_WRITE_BYTES") {
+            match max_bytes.parse() {
+                Ok(bytes) => self.tools.max_write_bytes = bytes,
+                Err(_) => warn!(
+                    "Invalid MCP_MAX_WRITE_BYTES '{}', using default of {} bytes",
+                    max_bytes, self.tools.max_write_bytes
+                ),
+            }
+        }
+        if let Ok(max_entries) = std::env::var("MCP_FS_MAX_ENTRIES") {
+            match max_entries.parse() {
+                Ok(entries) if entries > 0 => self.tools.fs_max_entries = entries,
+                _ => warn!(
+                    "Invalid MCP_FS_MAX_ENTRIES '{}', using default of {} entries",
+                    max_entries, self.tools.fs_max_entries
+                ),
+            }
+        }
+        if let Ok(max_depth) = std::env::var("MCP_FS_MAX_DEPTH") {
+            match max_depth.parse() {
+                Ok(depth) => self.tools.fs_max_depth = depth,
+                Err(_) => warn!(
+                    "Invalid MCP_FS_MAX_DEPTH '{}', using default of {} levels",
+                    max_depth, self.tools.fs_max_depth
+                ),
+            }
+        }
+        if let Ok(exclude) = std::env::var("MCP_FS_EXCLUDE") {
+            self.tools.fs_exclude = exclude
+                .split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(String::from)
+                .collect();
+        }
+        if let Ok(prefer_trash) = std::env::var("MCP_FS_PREFER_TRASH") {
+            self.tools.fs_prefer_trash = prefer_trash.parse().unwrap_or(false);
+            info!("fs_delete moves to trash: {}", self.tools.fs_prefer_trash);
+        }
+        if let Ok(max_files) = std::env::var("MCP_FS_CHECKSUM_MAX_FILES") {
+            match max_files.parse() {
+                Ok(files) if files > 0 => self.tools.fs_checksum_max_files = files,
+                _ => warn!(
+                    "Invalid MCP_FS_CHECKSUM_MAX_FILES '{}', using default of {} files",
+                    max_files, self.tools.fs_checksum_max_files
+                ),
+            }
+        }
+        if let Ok(junk_files) = std::env::var("MCP_FS_JUNK_FILES") {
+            self.tools.fs_junk_files = junk_files
+                .split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(String::from)
+                .collect();
+        }
+        if let Ok(timeout) = std::env::var("MCP_TOOL_TIMEOUT_SECS") {
+            match timeout.parse() {
+                Ok(secs) if secs > 0 => self.tools.default_timeout_secs = secs,
+                _ => warn!(
+                    "Invalid MCP_TOOL_TIMEOUT_SECS '{}', using default of {}s",
+                    timeout, self.tools.default_timeout_secs
+                ),
+            }
+        }
+        if let Ok(timeouts) = std::env::var("MCP_TOOL_TIMEOUTS") {
+            self.tools.timeouts = timeouts
+                .split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .filter_map(|entry| {
+                    let parsed = entry
+                        .split_once('=')
+                        .and_then(|(tool, secs)| Some((tool.trim(), secs.trim().parse().ok()?)));
+                    if parsed.is_none() {
+                        warn!(
+                            "Ignoring MCP_TOOL_TIMEOUTS entry '{}': expected <tool>=<seconds>",
+                            entry
+                        );
+                    }
+                    parsed.map(|(tool, secs)| (tool.to_string(), secs))
+                })
+                .collect();
+        }
+
+        // Load MusicBrainz client configuration
+        if let Ok(base_url) = std::env::var("MCP_MB_BASE_URL") {
+            info!("Using MusicBrainz server at {}", base_url);
+            self.musicbrainz.base_url = base_url;
+        }
+        if let Ok(user_agent) = std::env::var("MCP_MB_USER_AGENT") {
+            self.musicbrainz.user_agent = user_agent;
+        }
+
+        // Load metadata tools configuration
+        if let Ok(backup_on_write) = std::env::var("MCP_METADATA_BACKUP_ON_WRITE") {
+            self.metadata.backup_on_write = backup_on_write.parse().unwrap_or(false);
+            info!(
+                "Backup before metadata writes: {}",
+                self.metadata.backup_on_write
+            );
+        }
+        if let Ok(backup_dir) = std::env::var("MCP_METADATA_BACKUP_DIR") {
+            info!("Metadata backups go to {}", backup_dir);
+            self.metadata.backup_dir = Some(PathBuf::from(backup_dir));
+        }
+        if let Ok(preserve_mtime) = std::env::var("MCP_METADATA_PRESERVE_MTIME") {
+            self.metadata.preserve_mtime = preserve_mtime.parse().unwrap_or(false);
+            info!(
+                "Preserve modification time on metadata writes: {}",
+                self.metadata.preserve_mtime
+            );
+        }
+        if let Ok(id3_version) = std::env::var("MCP_METADATA_ID3_VERSION") {
+            match id3_version.parse() {
+                Ok(version) => {
+                    self.metadata.id3_version = version;
+                    info!("ID3 version for MP3 writes: {:?}", version);
+                }
+                Err(e) => warn!("Ignoring MCP_METADATA_ID3_VERSION: {}", e),
+            }
+        }
+        if let Ok(genre_map) = std::env::var("MCP_METADATA_GENRE_MAP") {
+            let genre_map = PathBuf::from(genre_map);
+            if genre_map.is_file() {
+                info!("Using genre map at {:?}", genre_map);
+            } else {
+                warn!(
+                    "MCP_METADATA_GENRE_MAP points to {:?}, which does not exist or is not a file",
+                    genre_map
+                );
+            }
+            self.metadata.genre_map_path = Some(genre_map);
+        }
+
+        // Load audit log configuration
+        if let Ok(audit_log) = std::env::var("MCP_AUDIT_LOG")
+            && !audit_log.trim().is_empty()
+        {
+            info!("Recording operations that modify files in {}", audit_log);
+            self.audit.path = Some(PathBuf::from(audit_log.trim()));
+        }
+        if let Ok(max_bytes) = std::env::var("MCP_AUDIT_MAX_FIELD_BYTES") {
+            match max_bytes.parse() {
+                Ok(bytes) if bytes > 0 => self.audit.max_field_bytes = bytes,
+                _ => warn!(
+                    "Invalid MCP_AUDIT_MAX_FIELD_BYTES '{}', using default of {} bytes",
+                    max_bytes, self.audit.max_field_bytes
+                ),
+            }
+        }
     }
 }
 
@@ -211,6 +1023,9 @@ mod tests {
     use super::*;
     use std::sync::Mutex;
 
+    /// Tools `tools.timeouts` may name in these tests.
+    const TOOL_NAMES: &[&str] = &["mb_identify_directory", "fs_checksum"];
+
     // Mutex to ensure env var tests run serially
     static ENV_TEST_LOCK: Mutex<()> = Mutex::new(());
 
@@ -247,10 +1062,12 @@ mod tests {
     fn test_credentials_redacted_in_debug() {
         let creds = CredentialsConfig {
             acoustid_api_key: Some("super_secret_key".to_string()),
+            acoustid_user_key: Some("super_secret_user".to_string()),
         };
         let debug_str = format!("{:?}", creds);
         assert!(debug_str.contains("REDACTED"));
         assert!(!debug_str.contains("super_secret_key"));
+        assert!(!debug_str.contains("super_secret_user"));
     }
 
     #[test]
@@ -258,4 +1075,401 @@ mod tests {
         let config = Config::default();
         assert!(config.credentials.acoustid_api_key.is_some());
     }
+
+    #[test]
+    fn test_has_custom_acoustid_key() {
+        let mut creds = CredentialsConfig::default();
+        assert!(!creds.has_custom_acoustid_key());
+
+        creds.acoustid_api_key = Some("my_own_key".to_string());
+        assert!(creds.has_custom_acoustid_key());
+    }
+
+    #[test]
+    fn test_fpcalc_path_from_env() {
+        let _lock = ENV_TEST_LOCK.lock().unwrap();
+        unsafe {
+            std::env::set_var("MCP_FPCALC_PATH", "/opt/chromaprint/bin/fpcalc");
+        }
+        let config = Config::from_env();
+        assert_eq!(
+            config.tools.fpcalc_program(),
+            Path::new("/opt/chromaprint/bin/fpcalc")
+        );
+        unsafe {
+            std::env::remove_var("MCP_FPCALC_PATH");
+        }
+
+        let config = Config::from_env();
+        assert_eq!(config.tools.fpcalc_program(), Path::new("fpcalc"));
+    }
+
+    #[test]
+    fn test_tool_timeouts_from_env() {
+        let _lock = ENV_TEST_LOCK.lock().unwrap();
+        unsafe {
+            std::env::set_var("MCP_TOOL_TIMEOUT_SECS", "120");
+            std::env::set_var(
+                "MCP_TOOL_TIMEOUTS",
+                "mb_identify_directory=1800, fs_checksum = 900,fs_find",
+            );
+        }
+        let config = Config::from_env();
+        unsafe {
+            std::env::remove_var("MCP_TOOL_TIMEOUT_SECS");
+            std::env::remove_var("MCP_TOOL_TIMEOUTS");
+        }
+        assert_eq!(
+            config.tools.timeout_for("fs_delete"),
+            Duration::from_secs(120)
+        );
+        assert_eq!(
+            config.tools.timeout_for("mb_identify_directory"),
+            Duration::from_secs(1800)
+        );
+        assert_eq!(
+            config.tools.timeout_for("fs_checksum"),
+            Duration::from_secs(900)
+        );
+        // The entry without a timeout is ignored
+        assert_eq!(config.tools.timeouts.len(), 2);
+    }
+
+    #[test]
+    fn test_default_musicbrainz_user_agent() {
+        let config = MusicBrainzConfig::default();
+        assert!(config.user_agent.starts_with(env!("CARGO_PKG_NAME")));
+        assert!(config.user_agent.contains(env!("CARGO_PKG_VERSION")));
+        assert_eq!(config.base_url, "http://musicbrainz.org");
+    }
+
+    #[test]
+    fn test_musicbrainz_from_env() {
+        let _lock = ENV_TEST_LOCK.lock().unwrap();
+        unsafe {
+            std::env::set_var("MCP_MB_BASE_URL", "http://localhost:5000");
+            std::env::set_var("MCP_MB_USER_AGENT", "MyTagger/1.0 ( me@example.com )");
+        }
+        let config = Config::from_env();
+        assert_eq!(config.musicbrainz.base_url, "http://localhost:5000");
+        assert_eq!(config.musicbrainz.user_agent, "MyTagger/1.0 ( me@example.com )");
+        unsafe {
+            std::env::remove_var("MCP_MB_BASE_URL");
+            std::env::remove_var("MCP_MB_USER_AGENT");
+        }
+    }
+
+    #[test]
+    fn test_metadata_config_from_env() {
+        let _lock = ENV_TEST_LOCK.lock().unwrap();
+        let config = Config::from_env();
+        assert!(!config.metadata.backup_on_write);
+        assert!(config.metadata.backup_dir.is_none());
+        assert!(!config.metadata.preserve_mtime);
+        assert_eq!(config.metadata.id3_version, Id3Version::V24);
+        assert!(config.metadata.genre_map_path.is_none());
+
+        unsafe {
+            std::env::set_var("MCP_METADATA_ID3_VERSION", "v2.3");
+            std::env::set_var("MCP_METADATA_BACKUP_ON_WRITE", "true");
+            std::env::set_var("MCP_METADATA_BACKUP_DIR", "/var/backups/music");
+            std::env::set_var("MCP_METADATA_PRESERVE_MTIME", "true");
+            std::env::set_var("MCP_METADATA_GENRE_MAP", "/etc/music/genres.json");
+        }
+        let config = Config::from_env();
+        assert!(config.metadata.backup_on_write);
+        assert!(config.metadata.preserve_mtime);
+        assert_eq!(config.metadata.id3_version, Id3Version::V23);
+        assert_eq!(
+            config.metadata.backup_dir.as_deref(),
+            Some(Path::new("/var/backups/music"))
+        );
+        assert_eq!(
+            config.metadata.genre_map_path.as_deref(),
+            Some(Path::new("/etc/music/genres.json"))
+        );
+        unsafe {
+            std::env::remove_var("MCP_METADATA_BACKUP_ON_WRITE");
+            std::env::remove_var("MCP_METADATA_BACKUP_DIR");
+            std::env::remove_var("MCP_METADATA_PRESERVE_MTIME");
+            std::env::remove_var("MCP_METADATA_ID3_VERSION");
+            std::env::remove_var("MCP_METADATA_GENRE_MAP");
+        }
+    }
+
+    fn write_config_file(contents: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, contents.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_config_from_file() {
+        let _lock = ENV_TEST_LOCK.lock().unwrap();
+        let file = write_config_file(
+            r#"
+            [server]
+            name = "music-library"
+
+            [credentials]
+            acoustid_api_key = "file_key"
+
+            [security]
+            allowed_paths = ["/music"]
+            allow_symlinks = false
+
+            [tools]
+            fs_max_depth = 5
+            fs_exclude = ["@eaDir", ".git"]
+
+            [tools.timeouts]
+            mb_identify_directory = 3600
+
+            [metadata]
+            id3_version = "2.3"
+            "#,
+        );
+
+        let config = Config::from_file(file.path()).unwrap();
+        assert_eq!(config.server.name, "music-library");
+        assert_eq!(
+            config.credentials.acoustid_api_key.as_deref(),
+            Some("file_key")
+        );
+        assert_eq!(config.security.allowed_paths, vec![PathBuf::from("/music")]);
+        assert!(!config.security.allow_symlinks);
+        assert_eq!(config.tools.fs_max_depth, 5);
+        assert_eq!(config.tools.fs_exclude, vec!["@eaDir", ".git"]);
+        assert_eq!(
+            config.tools.timeout_for("mb_identify_directory"),
+            Duration::from_secs(3600)
+        );
+        assert_eq!(
+            config.tools.timeout_for("fs_delete"),
+            Duration::from_secs(600)
+        );
+        assert_eq!(config.metadata.id3_version, Id3Version::V23);
+
+        // Values missing from the file keep their defaults
+        assert_eq!(config.logging.level, "info");
+        assert_eq!(config.tools.fs_max_entries, 1000);
+        assert_eq!(config.musicbrainz.base_url, DEFAULT_MUSICBRAINZ_BASE_URL);
+    }
+
+    #[test]
+    fn test_allowed_paths_from_env() {
+        let _lock = ENV_TEST_LOCK.lock().unwrap();
+        let separated = if cfg!(windows) {
+            "/music;/downloads ; /music/new"
+        } else {
+            "/music:/downloads ; /music/new"
+        };
+        unsafe {
+            std::env::set_var("MCP_ALLOWED_PATHS", separated);
+            std::env::set_var("MCP_ROOT_PATH", "/downloads");
+            std::env::set_var("MCP_READ_PATHS", "/library");
+            std::env::set_var("MCP_WRITE_PATHS", "/staging");
+        }
+        let config = Config::from_env();
+        unsafe {
+            std::env::remove_var("MCP_ALLOWED_PATHS");
+            std::env::remove_var("MCP_ROOT_PATH");
+            std::env::remove_var("MCP_READ_PATHS");
+            std::env::remove_var("MCP_WRITE_PATHS");
+        }
+        assert_eq!(config.security.read_paths, vec![PathBuf::from("/library")]);
+        assert_eq!(
+            config.security.roots_for(AccessKind::Write),
+            vec![
+                PathBuf::from("/music"),
+                PathBuf::from("/downloads"),
+                PathBuf::from("/music/new"),
+                PathBuf::from("/staging")
+            ]
+        );
+        assert_eq!(
+            config.security.allowed_paths,
+            vec![
+                PathBuf::from("/music"),
+                PathBuf::from("/downloads"),
+                PathBuf::from("/music/new")
+            ]
+        );
+
+        let config = Config::from_env();
+        assert!(!config.security.is_restricted());
+    }
+
+    #[test]
+    fn test_env_overrides_config_file() {
+        let _lock = ENV_TEST_LOCK.lock().unwrap();
+        let file = write_config_file(
+            r#"
+            [logging]
+            level = "debug"
+
+            [security]
+            allowed_paths = ["/music"]
+
+            [tools]
+            fs_max_depth = 5
+            "#,
+        );
+
+        unsafe {
+            std::env::set_var("MCP_LOG_LEVEL", "trace");
+            std::env::set_var("MCP_FS_MAX_DEPTH", "3");
+        }
+        let config = Config::from_file(file.path());
+        unsafe {
+            std::env::remove_var("MCP_LOG_LEVEL");
+            std::env::remove_var("MCP_FS_MAX_DEPTH");
+        }
+        let config = config.unwrap();
+        assert_eq!(config.logging.level, "trace");
+        assert_eq!(config.tools.fs_max_depth, 3);
+        assert_eq!(config.security.allowed_paths, vec![PathBuf::from("/music")]);
+    }
+
+    #[test]
+    fn test_load_config_file_from_env() {
+        let _lock = ENV_TEST_LOCK.lock().unwrap();
+        let file = write_config_file("[server]\nname = \"from-env-file\"\n");
+        let other = write_config_file("[server]\nname = \"from-argument\"\n");
+
+        unsafe {
+            std::env::set_var("MCP_CONFIG_FILE", file.path());
+        }
+        let from_env = Config::load(None);
+        let from_argument = Config::load(Some(other.path()));
+        unsafe {
+            std::env::remove_var("MCP_CONFIG_FILE");
+        }
+        assert_eq!(from_env.unwrap().server.name, "from-env-file");
+        assert_eq!(from_argument.unwrap().server.name, "from-argument");
+        assert_eq!(Config::load(None).unwrap().server.name, "mcp-server");
+    }
+
+    #[test]
+    fn test_invalid_config_file() {
+        let _lock = ENV_TEST_LOCK.lock().unwrap();
+        // A misspelled setting is an error rather than silently ignored
+        let file = write_config_file("[tools]\nfs_max_dept = 5\n");
+        let err = Config::from_file(file.path()).unwrap_err().to_string();
+        assert!(err.contains("fs_max_dept"), "{}", err);
+
+        let err = Config::from_file("/nonexistent/config.toml").unwrap_err();
+        assert!(err.to_string().contains("Cannot read config file"));
+    }
+
+    #[test]
+    fn test_validate_default_config() {
+        assert_eq!(Config::default().validate(TOOL_NAMES), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_rejects_https_musicbrainz_url() {
+        let mut config = Config::default();
+        config.musicbrainz.base_url = "https://mb.example.org".to_string();
+        let problems = config.validate(TOOL_NAMES).unwrap_err().0;
+        assert_eq!(problems.len(), 1, "{:?}", problems);
+        assert!(problems[0].contains("uses https://"), "{}", problems[0]);
+
+        config.musicbrainz.base_url = "http://".to_string();
+        let problems = config.validate(TOOL_NAMES).unwrap_err().0;
+        assert!(
+            problems[0].contains("is not an http:// URL"),
+            "{}",
+            problems[0]
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("library.txt");
+        std::fs::write(&file, "not a directory").unwrap();
+
+        let mut config = Config::default();
+        config.logging.level = "verbose".to_string();
+        config.credentials.acoustid_api_key = Some(" ".to_string());
+        config.security.allowed_paths = vec![file];
+        config.tools.fs_max_entries = 0;
+        config
+            .tools
+            .timeouts
+            .insert("mb_identify_dir".to_string(), 3600);
+        config.security.deny_patterns.push("{*.key".to_string());
+        config.tools.fs_exclude = vec!["*.{flac".to_string()];
+        config.musicbrainz.base_url = "musicbrainz.org".to_string();
+        config.audit.path = Some(temp_dir.path().join("logs/audit.jsonl"));
+
+        let problems = config.validate(TOOL_NAMES).unwrap_err().0;
+        assert_eq!(problems.len(), 9, "{:?}", problems);
+        let settings = [
+            "logging.level",
+            "credentials.acoustid_api_key",
+            "security.allowed_paths",
+            "tools.fs_max_entries",
+            "tools.timeouts",
+            "security.deny_patterns",
+            "tools.fs_exclude",
+            "musicbrainz.base_url",
+            "audit.path",
+        ];
+        for (problem, setting) in problems.iter().zip(settings) {
+            assert!(problem.starts_with(setting), "{}", problem);
+        }
+        assert!(problems[2].contains("is not a directory"));
+
+        // A missing root, and symlink rules without any root
+        config = Config::default();
+        config.security.allowed_paths = vec![temp_dir.path().join("missing")];
+        let report = config.validate(TOOL_NAMES).unwrap_err().to_string();
+        assert!(report.starts_with("Invalid configuration, 1 problem(s) found:"));
+        assert!(report.contains("does not exist"));
+
+        config.security.allowed_paths.clear();
+        config.security.allow_symlinks = false;
+        let problems = config.validate(TOOL_NAMES).unwrap_err().0;
+        assert!(problems[0].starts_with("security.allow_symlinks"));
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_transport_from_file_with_env_override() {
+        let _lock = ENV_TEST_LOCK.lock().unwrap();
+        let file = write_config_file(
+            r#"
+            [transport]
+            type = "http"
+            port = 4000
+            enable_cors = false
+            "#,
+        );
+
+        unsafe {
+            std::env::set_var("MCP_HTTP_PORT", "5000");
+        }
+        let config = Config::from_file(file.path());
+        unsafe {
+            std::env::remove_var("MCP_HTTP_PORT");
+        }
+        let mut config = config.unwrap();
+        match &mut config.transport {
+            TransportConfig::Http(http) => {
+                assert_eq!(http.port, 5000);
+                assert_eq!(http.host, "127.0.0.1");
+                assert!(!http.enable_cors);
+
+                http.port = 0;
+                http.rpc_path = "mcp".to_string();
+            }
+            other => panic!("expected HTTP transport, got {:?}", other),
+        }
+        let problems = config.validate(TOOL_NAMES).unwrap_err().0;
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].starts_with("transport.port"));
+        assert!(problems[1].starts_with("transport.rpc_path"));
+    }
 }
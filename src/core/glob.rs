@@ -0,0 +1,110 @@
+//! Glob patterns, matched against relative paths.
+//!
+//! Used by the deny patterns of the path security and by the filesystem tools.
+
+use std::path::Path;
+
+/// A glob pattern, expanded into one alternative per combination of `{a,b}` choices.
+pub struct GlobPattern {
+    /// Lowercased path segments of each alternative
+    alternatives: Vec<Vec<Vec<char>>>,
+}
+
+impl GlobPattern {
+    /// Parse a pattern; it is matched case-insensitively.
+    pub fn parse(glob: &str) -> Result<Self, String> {
+        let glob = glob.trim().trim_start_matches("./").trim_matches('/');
+        if glob.is_empty() {
+            return Err("Glob pattern is empty".to_string());
+        }
+        let alternatives = expand_braces(&glob.to_lowercase())
+            .ok_or_else(|| format!("Invalid glob pattern '{}': unbalanced braces", glob))?
+            .iter()
+            .map(|alternative| {
+                alternative
+                    .split('/')
+                    .filter(|segment| !segment.is_empty())
+                    .map(|segment| segment.chars().collect())
+                    .collect()
+            })
+            .collect();
+        Ok(Self { alternatives })
+    }
+
+    /// Whether a path relative to the root matches one of the alternatives.
+    pub fn matches(&self, relative: &Path) -> bool {
+        let segments: Vec<Vec<char>> = relative
+            .iter()
+            .map(|s| s.to_string_lossy().to_lowercase().chars().collect())
+            .collect();
+        self.alternatives
+            .iter()
+            .any(|pattern| match_segments(pattern, &segments))
+    }
+}
+
+/// Expand `{a,b}` alternatives, e.g. "*.{flac,mp3}" into "*.flac" and "*.mp3".
+/// Returns `None` when braces are unbalanced.
+fn expand_braces(pattern: &str) -> Option<Vec<String>> {
+    let Some(open) = pattern.find('{') else {
+        return (!pattern.contains('}')).then(|| vec![pattern.to_string()]);
+    };
+    let close = open + pattern[open..].find('}')?;
+    let (prefix, suffix) = (&pattern[..open], &pattern[close + 1..]);
+    let mut expanded = Vec::new();
+    for choice in pattern[open + 1..close].split(',') {
+        if choice.contains('{') {
+            return None;
+        }
+        expanded.extend(expand_braces(&format!("{}{}{}", prefix, choice, suffix))?);
+    }
+    Some(expanded)
+}
+
+/// Match path segments against pattern segments, where `**` matches any number of segments.
+fn match_segments(pattern: &[Vec<char>], path: &[Vec<char>]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first.as_slice() == ['*', '*'] => {
+            (0..=path.len()).any(|skip| match_segments(rest, &path[skip..]))
+        }
+        Some((first, rest)) => path
+            .split_first()
+            .is_some_and(|(name, path)| match_name(first, name) && match_segments(rest, path)),
+    }
+}
+
+/// Match a name against a pattern segment with `*` and `?` wildcards.
+fn match_name(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| match_name(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && match_name(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_name(rest, &name[1..]),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_matching() {
+        let pattern = GlobPattern::parse("**/*.{flac,mp3}").unwrap();
+        assert!(pattern.matches(Path::new("a.flac")));
+        assert!(pattern.matches(Path::new("x/y/B.MP3")));
+        assert!(!pattern.matches(Path::new("x/cover.jpg")));
+
+        let pattern = GlobPattern::parse("*/?? - *").unwrap();
+        assert!(pattern.matches(Path::new("Album/01 - Intro.flac")));
+        assert!(!pattern.matches(Path::new("01 - Intro.flac")));
+        assert!(!pattern.matches(Path::new("Album/1 - Intro.flac")));
+
+        assert!(GlobPattern::parse("*.{flac").is_err());
+        assert!(GlobPattern::parse("/").is_err());
+    }
+}
@@ -4,10 +4,13 @@
 //! including error handling, configuration, server lifecycle management,
 //! and transport layer abstractions.
 
+pub mod audit;
 pub mod config;
 pub mod error;
+pub mod glob;
 pub mod security;
 pub mod server;
+pub mod time;
 pub mod transport;
 
 pub use config::Config;
@@ -6,4 +6,6 @@
 
 pub mod path_validator;
 
-pub use path_validator::{validate_path, PathSecurityError};
+pub use path_validator::{
+    AccessKind, PathSecurityError, validate_path, validate_path_for, validate_write_target,
+};
@@ -1,16 +1,54 @@
+use std::ffi::OsStr;
+use std::fmt;
 use std::io;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 use crate::core::config::Config;
+use crate::core::glob::GlobPattern;
+
+/// The access a tool needs to a path, deciding which root directories allow it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessKind {
+    /// Listing, reading and identifying files.
+    Read,
+    /// Creating, modifying, moving and deleting files.
+    Write,
+}
+
+impl fmt::Display for AccessKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessKind::Read => write!(f, "read"),
+            AccessKind::Write => write!(f, "write"),
+        }
+    }
+}
 
 /// Errors that can occur during path validation
 #[derive(Debug, thiserror::Error)]
 pub enum PathSecurityError {
-    #[error("Path '{path}' is outside allowed root directory '{root}'")]
-    OutsideRootDirectory { path: PathBuf, root: PathBuf },
-
-    #[error("Symlink '{path}' points outside allowed root directory")]
-    SymlinkOutsideRoot { path: PathBuf },
+    #[error(
+        "Path '{path}' is outside the root directories allowing {access} access ({})",
+        display_roots(roots)
+    )]
+    OutsideRootDirectory {
+        path: PathBuf,
+        access: AccessKind,
+        roots: Vec<PathBuf>,
+    },
+
+    #[error(
+        "Symlink '{path}' resolves to '{target}', outside the root directories allowing \
+         {access} access"
+    )]
+    SymlinkEscape {
+        path: PathBuf,
+        target: PathBuf,
+        access: AccessKind,
+    },
+
+    #[error("Path '{path}' is a symlink, and symlinks are not allowed")]
+    SymlinkNotAllowed { path: PathBuf },
 
     #[error("Cannot canonicalize path '{path}': {error}")]
     CannotCanonicalize { path: PathBuf, error: io::Error },
@@ -20,18 +58,37 @@ pub enum PathSecurityError {
 
     #[error("IO error for path '{path}': {error}")]
     IoError { path: PathBuf, error: io::Error },
+
+    #[error("Path '{path}' matches deny pattern '{pattern}'")]
+    DeniedByPattern { path: PathBuf, pattern: String },
+}
+
+/// Validates that a given path can be read within the configured security boundaries.
+///
+/// Shorthand for [`validate_path_for`] with [`AccessKind::Read`], used by the tools
+/// that only list, read or identify files.
+pub fn validate_path(input_path: &str, config: &Config) -> Result<PathBuf, PathSecurityError> {
+    validate_path_for(input_path, AccessKind::Read, config)
 }
 
-/// Validates that a given path is within the configured security boundaries.
+/// Validates that a given path is within the configured security boundaries for an access.
+///
+/// Reads are allowed under any root directory, writes only under `allowed_paths` and
+/// `write_paths`, so a library in `read_paths` can be scanned but never modified.
 ///
 /// This function performs the following checks:
-/// 1. Canonicalizes the input path to resolve `.`, `..`, and symlinks
-/// 2. If a root path is configured, ensures the canonical path is within that root
+/// 1. Canonicalizes the input path to resolve `.`, `..`, and symlinks, or its parent
+///    when the path does not exist yet
+/// 2. If root directories are configured, ensures the canonical path is within one of
+///    those granting the access,
+///    reporting a symlink that leads out of them as [`PathSecurityError::SymlinkEscape`]
 /// 3. Handles symlinks according to the configured policy
+/// 4. Ensures the path exists
 ///
 /// # Arguments
 ///
 /// * `input_path` - The path to validate (can be relative or absolute)
+/// * `access` - Whether the path will be read or written
 /// * `config` - The server configuration containing security settings
 ///
 /// # Returns
@@ -43,89 +100,262 @@ pub enum PathSecurityError {
 ///
 /// ```rust,ignore
 /// let config = Config::from_env();
-/// let safe_path = validate_path("/home/user/music/song.mp3", &config)?;
+/// let safe_path = validate_path_for("/home/user/music/song.mp3", AccessKind::Write, &config)?;
 /// ```
-pub fn validate_path(input_path: &str, config: &Config) -> Result<PathBuf, PathSecurityError> {
+pub fn validate_path_for(
+    input_path: &str,
+    access: AccessKind,
+    config: &Config,
+) -> Result<PathBuf, PathSecurityError> {
     let path = Path::new(input_path);
+    let resolved_path = check_path(path, access, config)?;
 
-    // If no root path is configured, only do basic canonicalization
-    let Some(ref root) = config.security.root_path else {
-        // No restrictions - just ensure path exists and canonicalize if possible
-        return canonicalize_path(path);
-    };
-
-    // Canonicalize the root path first
-    let canonical_root = root.canonicalize().map_err(|e| PathSecurityError::IoError {
-        path: root.clone(),
-        error: e,
-    })?;
-
-    // Check if path exists before canonicalization
-    if !path.exists() {
+    // Checked last, so a missing file does not reveal where a symlink leads
+    if !resolved_path.exists() {
         return Err(PathSecurityError::PathNotFound {
             path: path.to_path_buf(),
         });
     }
 
-    // Handle symlinks according to policy
-    if path.is_symlink() && !config.security.allow_symlinks {
-        // Read the symlink target
-        let target = path.read_link().map_err(|e| PathSecurityError::IoError {
-            path: path.to_path_buf(),
-            error: e,
-        })?;
-
-        // Canonicalize the target
-        let canonical_target =
-            canonicalize_path(&target).map_err(|_| PathSecurityError::SymlinkOutsideRoot {
-                path: path.to_path_buf(),
-            })?;
-
-        // Verify the symlink target is within the root
-        if !is_within_root(&canonical_target, &canonical_root) {
-            return Err(PathSecurityError::SymlinkOutsideRoot {
-                path: path.to_path_buf(),
+    Ok(resolved_path)
+}
+
+/// Validates a path about to be written, which may not exist yet.
+///
+/// Runs every check of [`validate_path_for`] with [`AccessKind::Write`] on the path
+/// itself, deny patterns included, but accepts a path that does not exist. Write to the
+/// returned path rather than the input: a symlink is resolved, even a dangling one, so
+/// writing through it cannot create a file outside the roots.
+pub fn validate_write_target(
+    input_path: &str,
+    config: &Config,
+) -> Result<PathBuf, PathSecurityError> {
+    check_path(Path::new(input_path), AccessKind::Write, config)
+}
+
+/// The checks shared by [`validate_path_for`] and [`validate_write_target`]: returns the
+/// resolved path, whether or not it exists.
+fn check_path(
+    path: &Path,
+    access: AccessKind,
+    config: &Config,
+) -> Result<PathBuf, PathSecurityError> {
+    // If no root directories are configured, only the deny patterns apply
+    if !config.security.is_restricted() {
+        let resolved_path = resolve_path(path)?;
+        DenyPatterns::new(config).check(path, &resolved_path)?;
+        return Ok(resolved_path);
+    }
+
+    let roots = config.security.roots_for(access);
+    let canonical_roots = canonicalize_roots(&roots)?;
+
+    // Resolve `..` and symlinks on the filesystem: checking the path as written would let a
+    // symlink inside a root lead anywhere
+    let resolved_path = resolve_path(path)?;
+
+    if !is_within_any_root(&resolved_path, &canonical_roots) {
+        let lexical_path = normalize_lexically(path);
+        let lexical_roots = lexical_roots(&roots, &canonical_roots);
+        // Written inside a root but resolving outside it: a symlink leads out
+        if is_within_any_root(&lexical_path, &lexical_roots) {
+            return Err(PathSecurityError::SymlinkEscape {
+                path: lexical_path,
+                target: resolved_path,
+                access,
             });
         }
+        return Err(PathSecurityError::OutsideRootDirectory {
+            path: resolved_path,
+            access,
+            roots: canonical_roots,
+        });
     }
 
-    // Canonicalize the input path
-    let canonical_path = path.canonicalize().map_err(|e| PathSecurityError::CannotCanonicalize {
-        path: path.to_path_buf(),
-        error: e,
-    })?;
+    // Symlinks within the roots are only followed when allowed
+    if !config.security.allow_symlinks {
+        let lexical_roots = lexical_roots(&roots, &canonical_roots);
+        if let Some(symlink) = find_symlink(&normalize_lexically(path), &lexical_roots) {
+            return Err(PathSecurityError::SymlinkNotAllowed { path: symlink });
+        }
+    }
 
-    // Verify the canonical path is within the root
-    if !is_within_root(&canonical_path, &canonical_root) {
-        return Err(PathSecurityError::OutsideRootDirectory {
-            path: canonical_path,
-            root: canonical_root,
-        });
+    DenyPatterns::new(config).check(path, &resolved_path)?;
+
+    Ok(resolved_path)
+}
+
+/// The configured deny patterns, with the text of each to name it in errors.
+struct DenyPatterns {
+    patterns: Vec<(String, GlobPattern)>,
+}
+
+impl DenyPatterns {
+    /// Parse `security.deny_patterns`; an invalid pattern, reported by config validation,
+    /// is skipped.
+    fn new(config: &Config) -> Self {
+        let patterns = config
+            .security
+            .deny_patterns
+            .iter()
+            .filter_map(|pattern| Some((pattern.clone(), GlobPattern::parse(pattern).ok()?)))
+            .collect();
+        Self { patterns }
+    }
+
+    /// The first pattern matching the end of a path: its name, or its last directories
+    /// and name.
+    fn matching(&self, path: &Path) -> Option<&str> {
+        let segments: Vec<&OsStr> = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(segment) => Some(segment),
+                _ => None,
+            })
+            .collect();
+        self.patterns.iter().find_map(|(pattern, glob)| {
+            (0..segments.len())
+                .any(|start| glob.matches(&segments[start..].iter().collect::<PathBuf>()))
+                .then_some(pattern.as_str())
+        })
     }
 
-    Ok(canonical_path)
+    /// Reject a path when it matches a pattern as written or as resolved, so a symlink
+    /// neither reaches a denied file nor hides one behind an innocent name.
+    fn check(&self, path: &Path, resolved_path: &Path) -> Result<(), PathSecurityError> {
+        for candidate in [normalize_lexically(path).as_path(), resolved_path] {
+            if let Some(pattern) = self.matching(candidate) {
+                return Err(PathSecurityError::DeniedByPattern {
+                    path: candidate.to_path_buf(),
+                    pattern: pattern.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
 }
 
-/// Checks if a path is within (or equal to) a root directory
-fn is_within_root(path: &Path, root: &Path) -> bool {
-    path.starts_with(root)
+/// Resolves a path on the filesystem, following `..` and symlinks.
+///
+/// A leaf that does not exist yet, such as a file about to be written, is kept as
+/// written under its resolved parent. A dangling symlink resolves to its target, the
+/// file that writing through it would create.
+pub(crate) fn resolve_path(path: &Path) -> Result<PathBuf, PathSecurityError> {
+    resolve_links(path, 0)
+}
+
+/// Maximum number of dangling symlinks followed in a chain, which could otherwise loop.
+const MAX_SYMLINK_HOPS: usize = 40;
+
+/// [`resolve_path`], having followed `hops` dangling symlinks.
+fn resolve_links(path: &Path, hops: usize) -> Result<PathBuf, PathSecurityError> {
+    let cannot_canonicalize = |error| PathSecurityError::CannotCanonicalize {
+        path: path.to_path_buf(),
+        error,
+    };
+    match path.canonicalize() {
+        Ok(resolved) => Ok(resolved),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let Some(name) = path.file_name() else {
+                return Err(cannot_canonicalize(e));
+            };
+            let parent = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            let resolved_parent = resolve_links(parent, hops)?;
+            match path.read_link() {
+                Ok(target) if hops < MAX_SYMLINK_HOPS => {
+                    resolve_links(&resolved_parent.join(target), hops + 1)
+                }
+                Ok(_) => Err(cannot_canonicalize(io::Error::other(
+                    "too many levels of symbolic links",
+                ))),
+                Err(_) => Ok(resolved_parent.join(name)),
+            }
+        }
+        Err(e) => Err(cannot_canonicalize(e)),
+    }
 }
 
-/// Attempts to canonicalize a path, returning it as-is if canonicalization fails
-/// (e.g., for non-existent paths)
-fn canonicalize_path(path: &Path) -> Result<PathBuf, PathSecurityError> {
-    path.canonicalize().map_err(|e| {
-        if e.kind() == io::ErrorKind::NotFound {
-            PathSecurityError::PathNotFound {
-                path: path.to_path_buf(),
+/// Makes a path absolute and removes `.` and `..` without touching the filesystem,
+/// giving the location the path is written to rather than the one it resolves to.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
             }
-        } else {
-            PathSecurityError::CannotCanonicalize {
-                path: path.to_path_buf(),
-                error: e,
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// The roots as configured, made absolute, along with their canonical forms, so a
+/// path written under either form is recognised.
+fn lexical_roots(roots: &[PathBuf], canonical_roots: &[PathBuf]) -> Vec<PathBuf> {
+    roots
+        .iter()
+        .map(|root| normalize_lexically(root))
+        .chain(canonical_roots.iter().cloned())
+        .collect()
+}
+
+/// Finds a symlink among the path and its ancestors below the allowed root holding it.
+fn find_symlink(path: &Path, roots: &[PathBuf]) -> Option<PathBuf> {
+    let mut current = path;
+    while !roots.iter().any(|root| current == root) {
+        if current.is_symlink() {
+            return Some(current.to_path_buf());
+        }
+        current = current.parent()?;
+    }
+    None
+}
+
+/// Canonicalizes the allowed root directories.
+///
+/// A root that cannot be resolved, such as an unmounted drive, is skipped so the
+/// others stay usable; it is an error only when none of them can be resolved.
+fn canonicalize_roots(roots: &[PathBuf]) -> Result<Vec<PathBuf>, PathSecurityError> {
+    let mut canonical_roots = Vec::with_capacity(roots.len());
+    let mut first_error = None;
+    for root in roots {
+        match root.canonicalize() {
+            Ok(canonical_root) => canonical_roots.push(canonical_root),
+            Err(e) => {
+                first_error.get_or_insert(PathSecurityError::IoError {
+                    path: root.clone(),
+                    error: e,
+                });
             }
         }
-    })
+    }
+    match first_error {
+        Some(error) if canonical_roots.is_empty() => Err(error),
+        _ => Ok(canonical_roots),
+    }
+}
+
+/// Checks if a path is within (or equal to) any of the root directories
+fn is_within_any_root(path: &Path, roots: &[PathBuf]) -> bool {
+    roots.iter().any(|root| path.starts_with(root))
+}
+
+/// Formats root directories for error messages, e.g. `'/music', '/downloads'`
+fn display_roots(roots: &[PathBuf]) -> String {
+    if roots.is_empty() {
+        return "none".to_string();
+    }
+    roots
+        .iter()
+        .map(|root| format!("'{}'", root.display()))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 #[cfg(test)]
@@ -135,12 +365,17 @@ mod tests {
     use tempfile::TempDir;
 
     fn create_test_config(root: Option<PathBuf>, allow_symlinks: bool) -> Config {
+        create_multi_root_config(root.into_iter().collect(), allow_symlinks)
+    }
+
+    fn create_multi_root_config(roots: Vec<PathBuf>, allow_symlinks: bool) -> Config {
         use crate::core::config::SecurityConfig;
 
         let mut config = Config::default();
         config.security = SecurityConfig {
-            root_path: root,
+            allowed_paths: roots,
             allow_symlinks,
+            ..SecurityConfig::default()
         };
         config
     }
@@ -185,6 +420,179 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_path_within_any_root() {
+        let library = TempDir::new().unwrap();
+        let downloads = TempDir::new().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+        let album = library.path().join("album");
+        fs::create_dir(&album).unwrap();
+        let library_file = library.path().join("library.flac");
+        let album_file = album.join("01.flac");
+        let download_file = downloads.path().join("new.flac");
+        let outside_file = outside_dir.path().join("outside.flac");
+        for file in [&library_file, &album_file, &download_file, &outside_file] {
+            fs::write(file, "test").unwrap();
+        }
+
+        // Overlapping roots: the album is both a root and inside the library root
+        let config = create_multi_root_config(
+            vec![
+                album.clone(),
+                library.path().to_path_buf(),
+                downloads.path().to_path_buf(),
+            ],
+            true,
+        );
+        for file in [&library_file, &album_file, &download_file] {
+            let result = validate_path(file.to_str().unwrap(), &config);
+            assert_eq!(result.unwrap(), file.canonicalize().unwrap());
+        }
+
+        let result = validate_path(outside_file.to_str().unwrap(), &config);
+        match result {
+            Err(PathSecurityError::OutsideRootDirectory { roots, .. }) => {
+                assert_eq!(roots.len(), 3)
+            }
+            other => panic!("expected OutsideRootDirectory, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_read_and_write_roots() {
+        let library = TempDir::new().unwrap();
+        let staging = TempDir::new().unwrap();
+        let library_file = library.path().join("01.flac");
+        let staging_file = staging.path().join("01.flac");
+        fs::write(&library_file, "test").unwrap();
+        fs::write(&staging_file, "test").unwrap();
+
+        let mut config = Config::default();
+        config.security.read_paths = vec![library.path().to_path_buf()];
+        config.security.write_paths = vec![staging.path().to_path_buf()];
+
+        // Reads are allowed in both roots, writes only in the staging one
+        for file in [&library_file, &staging_file] {
+            assert!(validate_path_for(file.to_str().unwrap(), AccessKind::Read, &config).is_ok());
+        }
+        assert!(
+            validate_path_for(staging_file.to_str().unwrap(), AccessKind::Write, &config).is_ok()
+        );
+        let error = validate_path_for(library_file.to_str().unwrap(), AccessKind::Write, &config)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            PathSecurityError::OutsideRootDirectory {
+                access: AccessKind::Write,
+                ..
+            }
+        ));
+        assert!(
+            error.to_string().contains("allowing write access"),
+            "{}",
+            error
+        );
+
+        // Read-only roots alone allow no writes at all
+        config.security.write_paths.clear();
+        let error = validate_path_for(library_file.to_str().unwrap(), AccessKind::Write, &config)
+            .unwrap_err();
+        assert!(
+            error.to_string().ends_with("allowing write access (none)"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_deny_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = temp_dir.path().join(".config/app/settings.json");
+        fs::create_dir_all(settings.parent().unwrap()).unwrap();
+        for file in ["server.key", ".env", "01.flac"] {
+            fs::write(temp_dir.path().join(file), "test").unwrap();
+        }
+        fs::write(&settings, "{}").unwrap();
+
+        let mut config = create_test_config(Some(temp_dir.path().to_path_buf()), true);
+        config.security.deny_patterns.push(".config/**".to_string());
+        for (path, pattern) in [
+            (temp_dir.path().join("server.key"), "*.key"),
+            (temp_dir.path().join(".env"), ".env"),
+            (settings.clone(), ".config/**"),
+            (temp_dir.path().join(".config"), ".config/**"),
+            // Denied before being reported missing
+            (temp_dir.path().join("new.KEY"), "*.key"),
+        ] {
+            let result = validate_path(path.to_str().unwrap(), &config);
+            match result {
+                Err(PathSecurityError::DeniedByPattern {
+                    pattern: matched, ..
+                }) => {
+                    assert_eq!(matched, pattern, "{:?}", path)
+                }
+                other => panic!("expected DeniedByPattern for {:?}, got {:?}", path, other),
+            }
+        }
+        let allowed = temp_dir.path().join("01.flac");
+        assert!(validate_path(allowed.to_str().unwrap(), &config).is_ok());
+
+        // A file about to be written is checked by its own name, not its directory's
+        let new_secret = temp_dir.path().join("exported.key");
+        assert!(matches!(
+            validate_write_target(new_secret.to_str().unwrap(), &config),
+            Err(PathSecurityError::DeniedByPattern { .. })
+        ));
+        let new_track = temp_dir.path().join("02.flac");
+        assert!(validate_write_target(new_track.to_str().unwrap(), &config).is_ok());
+
+        // Applied without root directories too
+        let mut config = create_test_config(None, true);
+        config.security.deny_patterns = vec!["*.key".to_string()];
+        let key = temp_dir.path().join("server.key");
+        assert!(matches!(
+            validate_path(key.to_str().unwrap(), &config),
+            Err(PathSecurityError::DeniedByPattern { .. })
+        ));
+        config.security.deny_patterns.clear();
+        assert!(validate_path(key.to_str().unwrap(), &config).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_to_denied_file() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let key = temp_dir.path().join("server.key");
+        let link = temp_dir.path().join("cover.jpg");
+        fs::write(&key, "secret").unwrap();
+        symlink(&key, &link).unwrap();
+
+        let config = create_test_config(Some(temp_dir.path().to_path_buf()), true);
+        let result = validate_path(link.to_str().unwrap(), &config);
+        assert!(matches!(
+            result,
+            Err(PathSecurityError::DeniedByPattern { path, .. }) if path == key.canonicalize().unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_unavailable_root_is_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("test.txt");
+        fs::write(&test_file, "test").unwrap();
+        let missing = temp_dir.path().join("unmounted");
+
+        let config =
+            create_multi_root_config(vec![missing.clone(), temp_dir.path().to_path_buf()], true);
+        assert!(validate_path(test_file.to_str().unwrap(), &config).is_ok());
+
+        let config = create_multi_root_config(vec![missing], true);
+        let result = validate_path(test_file.to_str().unwrap(), &config);
+        assert!(matches!(result, Err(PathSecurityError::IoError { .. })));
+    }
+
     #[test]
     fn test_path_traversal_blocked() {
         let temp_dir = TempDir::new().unwrap();
@@ -216,7 +624,10 @@ mod tests {
         let config = create_test_config(Some(temp_dir.path().to_path_buf()), true);
         let result = validate_path(nonexistent.to_str().unwrap(), &config);
 
-        assert!(matches!(result, Err(PathSecurityError::PathNotFound { .. })));
+        assert!(matches!(
+            result,
+            Err(PathSecurityError::PathNotFound { .. })
+        ));
     }
 
     #[cfg(unix)]
@@ -256,8 +667,148 @@ mod tests {
 
         assert!(matches!(
             result,
-            Err(PathSecurityError::SymlinkOutsideRoot { .. })
+            Err(PathSecurityError::SymlinkEscape { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_directory_escape_blocked() {
+        use std::os::unix::fs::symlink;
+
+        let root_dir = TempDir::new().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+        fs::write(outside_dir.path().join("passwd"), "secret").unwrap();
+        let escape = root_dir.path().join("escape");
+        symlink(outside_dir.path(), &escape).unwrap();
+
+        let config = create_test_config(Some(root_dir.path().to_path_buf()), true);
+        for path in [
+            escape.clone(),
+            escape.join("passwd"),
+            // Not created yet: resolved through its parent, and not reported as missing
+            escape.join("new.txt"),
+            escape.join("missing/new.txt"),
+        ] {
+            let result = validate_path(path.to_str().unwrap(), &config);
+            match result {
+                Err(PathSecurityError::SymlinkEscape { target, .. }) => {
+                    assert!(target.starts_with(outside_dir.path().canonicalize().unwrap()))
+                }
+                other => panic!("expected SymlinkEscape for {:?}, got {:?}", path, other),
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_target_dangling_symlink() {
+        use std::os::unix::fs::symlink;
+
+        let root_dir = TempDir::new().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+        let config = create_test_config(Some(root_dir.path().to_path_buf()), true);
+
+        // A new file is accepted and resolved inside the root
+        let new_file = root_dir.path().join("new.txt");
+        let resolved = validate_write_target(new_file.to_str().unwrap(), &config).unwrap();
+        assert_eq!(
+            resolved,
+            root_dir.path().canonicalize().unwrap().join("new.txt")
+        );
+
+        // A dangling link is followed to where a write would land
+        let escape = root_dir.path().join("escape.txt");
+        symlink(outside_dir.path().join("pwned.txt"), &escape).unwrap();
+        let result = validate_write_target(escape.to_str().unwrap(), &config);
+        assert!(matches!(
+            result,
+            Err(PathSecurityError::SymlinkEscape { .. })
+        ));
+
+        let inside = root_dir.path().join("inside.txt");
+        symlink(root_dir.path().join("real.txt"), &inside).unwrap();
+        let resolved = validate_write_target(inside.to_str().unwrap(), &config).unwrap();
+        assert_eq!(
+            resolved,
+            root_dir.path().canonicalize().unwrap().join("real.txt")
+        );
+
+        // A link cycle is reported instead of looping
+        let a = root_dir.path().join("a");
+        let b = root_dir.path().join("b");
+        symlink(&b, &a).unwrap();
+        symlink(&a, &b).unwrap();
+        let result = validate_write_target(a.to_str().unwrap(), &config);
+        assert!(matches!(
+            result,
+            Err(PathSecurityError::CannotCanonicalize { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_into_root_allowed() {
+        use std::os::unix::fs::symlink;
+
+        let root_dir = TempDir::new().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+        let album = root_dir.path().join("album");
+        fs::create_dir(&album).unwrap();
+        fs::write(album.join("01.flac"), "test").unwrap();
+
+        // A directory symlink inside the root, and one outside it, both leading in
+        let inner_link = root_dir.path().join("latest");
+        let outer_link = outside_dir.path().join("album");
+        symlink(&album, &inner_link).unwrap();
+        symlink(&album, &outer_link).unwrap();
+
+        let config = create_test_config(Some(root_dir.path().to_path_buf()), true);
+        let expected = album.join("01.flac").canonicalize().unwrap();
+        for link in [&inner_link, &outer_link] {
+            let result = validate_path(link.join("01.flac").to_str().unwrap(), &config);
+            assert_eq!(result.unwrap(), expected);
+        }
+
+        let config = create_test_config(Some(root_dir.path().to_path_buf()), false);
+        let result = validate_path(inner_link.join("01.flac").to_str().unwrap(), &config);
+        assert!(matches!(
+            result,
+            Err(PathSecurityError::SymlinkNotAllowed { path }) if path == inner_link
         ));
+        let result = validate_path(album.join("01.flac").to_str().unwrap(), &config);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_dot_dot_traversal() {
+        let root_dir = TempDir::new().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+        let subdir = root_dir.path().join("subdir");
+        fs::create_dir(&subdir).unwrap();
+        fs::write(root_dir.path().join("test.txt"), "test").unwrap();
+        fs::write(outside_dir.path().join("outside.txt"), "test").unwrap();
+
+        let config = create_test_config(Some(root_dir.path().to_path_buf()), true);
+
+        // Leaving a subdirectory but staying in the root
+        let result = validate_path(subdir.join("../test.txt").to_str().unwrap(), &config);
+        assert_eq!(
+            result.unwrap(),
+            root_dir.path().join("test.txt").canonicalize().unwrap()
+        );
+
+        // Climbing out of the root, to an existing file or a missing one
+        let outside_name = outside_dir.path().file_name().unwrap().to_str().unwrap();
+        for name in ["outside.txt", "missing.txt"] {
+            let traversal = subdir.join(format!("../../{}/{}", outside_name, name));
+            let result = validate_path(traversal.to_str().unwrap(), &config);
+            assert!(
+                matches!(result, Err(PathSecurityError::OutsideRootDirectory { .. })),
+                "{:?}",
+                result
+            );
+        }
     }
 
     #[cfg(unix)]
@@ -25,6 +25,7 @@ use super::config::Config;
 use crate::domains::{
     prompts::PromptService, resources::ResourceService, tools::build_tool_router,
 };
+use crate::domains::tools::definitions::mb::common::init_mb_client;
 
 #[cfg(feature = "http")]
 use crate::domains::tools::ToolRegistry;
@@ -53,7 +54,12 @@ impl McpServer {
     pub fn new(config: Config) -> Self {
         let config = Arc::new(config);
 
-        let resource_service = Arc::new(ResourceService::new(config.resources.clone()));
+        // Apply the MusicBrainz server and User-Agent before any tool runs
+        init_mb_client(&config.musicbrainz);
+
+        let resource_service = Arc::new(
+            ResourceService::new(config.resources.clone()).with_security(config.security.clone()),
+        );
         let prompt_service = Arc::new(PromptService::new(config.prompts.clone()));
 
         Self {
@@ -110,7 +116,7 @@ impl McpServer {
         arguments: serde_json::Value,
     ) -> Result<serde_json::Value, String> {
         let registry = ToolRegistry::new(self.config.clone());
-        registry.call_tool(name, arguments)
+        registry.call_tool(name, arguments).await
     }
 
     /// List all available resources (for HTTP transport).
@@ -0,0 +1,57 @@
+//! Timestamp formatting and parsing, shared by the audit log and the filesystem tools.
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use std::time::SystemTime;
+
+/// Format a timestamp as RFC 3339 in UTC to the second, e.g. "2024-03-02T18:11:05Z",
+/// whatever the server's local timezone.
+pub fn format_time(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Parse an RFC 3339 timestamp, or a date taken as midnight UTC.
+///
+/// `name` is the parameter the value came from, for the error message.
+pub fn parse_time(name: &str, value: &str) -> Result<SystemTime, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.into());
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_time(Default::default()).and_utc().into())
+        .map_err(|_| {
+            format!(
+                "Invalid {} '{}': expected RFC 3339 (2024-01-31T12:00:00Z) or a date (2024-01-31)",
+                name, value
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_format_time_is_utc() {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_millis(1_000_000_000_250);
+        assert_eq!(format_time(time), "2001-09-09T01:46:40Z");
+    }
+
+    #[test]
+    fn test_parse_time() {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        assert_eq!(parse_time("t", "2001-09-09T01:46:40Z").unwrap(), time);
+        // Offsets are converted to UTC
+        assert_eq!(parse_time("t", "2001-09-09T03:46:40+02:00").unwrap(), time);
+        assert_eq!(
+            parse_time("t", "2001-09-09").unwrap(),
+            SystemTime::UNIX_EPOCH + Duration::from_secs(999_993_600)
+        );
+        let err = parse_time("modified_after", "yesterday").unwrap_err();
+        assert!(
+            err.starts_with("Invalid modified_after 'yesterday'"),
+            "{}",
+            err
+        );
+    }
+}
@@ -22,6 +22,7 @@ pub enum TransportConfig {
 /// TCP transport configuration.
 #[cfg(feature = "tcp")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TcpConfig {
     /// Port number to listen on.
     pub port: u16,
@@ -34,6 +35,7 @@ pub struct TcpConfig {
 /// HTTP transport configuration.
 #[cfg(feature = "http")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct HttpConfig {
     /// Port number to listen on.
     pub port: u16,
@@ -182,6 +184,89 @@ impl TransportConfig {
         }
     }
 
+    /// Override a transport loaded from a configuration file with environment variables.
+    ///
+    /// `MCP_TRANSPORT` replaces the transport entirely (see [`Self::from_env`]); otherwise
+    /// the variables of the configured transport, such as `MCP_HTTP_PORT`, override its
+    /// fields.
+    pub fn apply_env(&mut self) {
+        if std::env::var("MCP_TRANSPORT").is_ok_and(|transport| !transport.is_empty()) {
+            *self = Self::from_env();
+            return;
+        }
+
+        match self {
+            #[cfg(feature = "stdio")]
+            Self::Stdio => {}
+            #[cfg(feature = "tcp")]
+            Self::Tcp(cfg) => {
+                if let Some(port) = std::env::var("MCP_TCP_PORT")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                {
+                    cfg.port = port;
+                }
+                if let Ok(host) = std::env::var("MCP_TCP_HOST") {
+                    cfg.host = host;
+                }
+            }
+            #[cfg(feature = "http")]
+            Self::Http(cfg) => {
+                if let Some(port) = std::env::var("MCP_HTTP_PORT")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                {
+                    cfg.port = port;
+                }
+                if let Ok(host) = std::env::var("MCP_HTTP_HOST") {
+                    cfg.host = host;
+                }
+                if let Ok(rpc_path) = std::env::var("MCP_HTTP_PATH") {
+                    cfg.rpc_path = rpc_path;
+                }
+                if let Ok(cors) = std::env::var("MCP_HTTP_CORS") {
+                    cfg.enable_cors = cors.to_lowercase() != "false" && cors != "0";
+                }
+            }
+        }
+    }
+
+    /// Problems of this transport's settings, checked by `Config::validate`.
+    pub fn problems(&self) -> Vec<String> {
+        match self {
+            #[cfg(feature = "stdio")]
+            Self::Stdio => Vec::new(),
+            #[cfg(feature = "tcp")]
+            Self::Tcp(cfg) => {
+                let mut problems = Vec::new();
+                if cfg.port == 0 {
+                    problems.push("transport.port (MCP_TCP_PORT): must be 1-65535".to_string());
+                }
+                if cfg.host.trim().is_empty() {
+                    problems.push("transport.host (MCP_TCP_HOST): is empty".to_string());
+                }
+                problems
+            }
+            #[cfg(feature = "http")]
+            Self::Http(cfg) => {
+                let mut problems = Vec::new();
+                if cfg.port == 0 {
+                    problems.push("transport.port (MCP_HTTP_PORT): must be 1-65535".to_string());
+                }
+                if cfg.host.trim().is_empty() {
+                    problems.push("transport.host (MCP_HTTP_HOST): is empty".to_string());
+                }
+                if !cfg.rpc_path.starts_with('/') {
+                    problems.push(format!(
+                        "transport.rpc_path (MCP_HTTP_PATH): '{}' must start with '/'",
+                        cfg.rpc_path
+                    ));
+                }
+                problems
+            }
+        }
+    }
+
     /// Get a description of this transport for logging.
     pub fn description(&self) -> String {
         match self {
@@ -0,0 +1,21 @@
+//! Allowed paths resource: the root directories filesystem operations are limited to.
+
+use super::ResourceDefinition;
+use crate::domains::resources::service::{DynamicResourceType, ResourceContent};
+
+/// Lists the allowed root directories, so clients can tell which paths the tools accept
+/// before calling them.
+pub struct AllowedPathsResource;
+
+impl ResourceDefinition for AllowedPathsResource {
+    const URI: &'static str = "mcp://server/allowed-paths";
+    const NAME: &'static str = "Allowed Paths";
+    const DESCRIPTION: &'static str = "Root directories that file system operations are limited to \
+        (security.allowed_paths / MCP_ALLOWED_PATHS). When 'restricted' is false, every path is \
+        allowed.";
+    const MIME_TYPE: &'static str = "application/json";
+
+    fn content() -> ResourceContent {
+        ResourceContent::Dynamic(DynamicResourceType::AllowedPaths)
+    }
+}
@@ -11,6 +11,10 @@
 //! 3. Export it here
 //! 4. Register in `registry.rs`
 
+pub mod allowed_paths;
+
+pub use allowed_paths::AllowedPathsResource;
+
 use rmcp::model::ResourceContents;
 
 use super::service::ResourceContent;
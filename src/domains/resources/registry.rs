@@ -8,11 +8,10 @@
 
 use rmcp::model::{AnnotateAble, RawResource, RawResourceTemplate, ResourceTemplate};
 
-use super::definitions::ResourceDefinition;
+use super::definitions::{AllowedPathsResource, ResourceDefinition};
 use super::service::ResourceEntry;
 
 /// Helper function to create an annotated resource from a definition.
-#[allow(unused)]
 fn build_resource<R: ResourceDefinition>() -> ResourceEntry {
     let mut raw = RawResource::new(R::URI, R::NAME);
     raw.description = Some(R::DESCRIPTION.to_string());
@@ -29,7 +28,7 @@ fn build_resource<R: ResourceDefinition>() -> ResourceEntry {
 /// This is the central place where all resources are registered.
 /// When adding a new resource, add it here.
 pub fn get_all_resources() -> Vec<ResourceEntry> {
-    vec![]
+    vec![build_resource::<AllowedPathsResource>()]
 }
 
 /// Get all registered resource templates.
@@ -72,7 +71,7 @@ pub fn get_all_resource_templates() -> Vec<ResourceTemplate> {
 
 /// Get the list of all resource URIs.
 pub fn resource_uris() -> Vec<&'static str> {
-    vec![]
+    vec![AllowedPathsResource::URI]
 }
 
 #[cfg(test)]
@@ -8,11 +8,13 @@
 
 use rmcp::model::{ReadResourceResult, Resource, ResourceContents, ResourceTemplate};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use tracing::info;
 
 use super::error::ResourceError;
 use super::registry::{get_all_resource_templates, get_all_resources};
-use crate::core::config::ResourcesConfig;
+use crate::core::config::{ResourcesConfig, SecurityConfig};
+use crate::core::security::AccessKind;
 
 /// Service for managing and accessing resources.
 ///
@@ -22,6 +24,9 @@ pub struct ResourceService {
     /// Configuration for the resources domain.
     config: ResourcesConfig,
 
+    /// Path security settings, reported by the allowed paths resource.
+    security: SecurityConfig,
+
     /// Registry of available resources.
     /// Key: resource URI, Value: resource metadata
     resources: HashMap<String, ResourceEntry>,
@@ -62,6 +67,9 @@ pub enum DynamicResourceType {
     /// File system resource (path relative to base_path).
     File(String),
 
+    /// Allowed root directories of path security.
+    AllowedPaths,
+
     /// Custom dynamic resource.
     Custom(String),
 }
@@ -73,6 +81,7 @@ impl ResourceService {
 
         let mut service = Self {
             config,
+            security: SecurityConfig::default(),
             resources: HashMap::new(),
             templates: Vec::new(),
         };
@@ -84,6 +93,12 @@ impl ResourceService {
         service
     }
 
+    /// Set the path security settings reported by the allowed paths resource.
+    pub fn with_security(mut self, security: SecurityConfig) -> Self {
+        self.security = security;
+        self
+    }
+
     /// Register all resources from the registry.
     fn register_from_registry(&mut self) {
         info!("Registering resources from registry");
@@ -174,6 +189,28 @@ impl ResourceService {
 
                 Ok(ResourceContents::text(content, uri))
             }
+            DynamicResourceType::AllowedPaths => {
+                // Report the roots as validation resolves them, keeping unavailable ones as set
+                let resolve = |roots: Vec<PathBuf>| -> Vec<PathBuf> {
+                    roots
+                        .into_iter()
+                        .map(|root| root.canonicalize().unwrap_or(root))
+                        .collect()
+                };
+                let info = serde_json::json!({
+                    "restricted": self.security.is_restricted(),
+                    "allowed_paths": resolve(self.security.allowed_paths.clone()),
+                    "readable_paths": resolve(self.security.roots_for(AccessKind::Read)),
+                    "writable_paths": resolve(self.security.roots_for(AccessKind::Write)),
+                    "allow_symlinks": self.security.allow_symlinks,
+                });
+
+                Ok(ResourceContents::text(
+                    serde_json::to_string_pretty(&info)
+                        .map_err(|e| ResourceError::internal(e.to_string()))?,
+                    uri,
+                ))
+            }
             DynamicResourceType::Custom(identifier) => Ok(ResourceContents::text(
                 format!("Custom resource: {}", identifier),
                 uri,
@@ -204,6 +241,50 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_read_allowed_paths() {
+        let library = tempfile::TempDir::new().unwrap();
+        let downloads = tempfile::TempDir::new().unwrap();
+        let security = SecurityConfig {
+            allowed_paths: vec![downloads.path().to_path_buf()],
+            read_paths: vec![library.path().to_path_buf()],
+            allow_symlinks: false,
+            ..SecurityConfig::default()
+        };
+        let service = ResourceService::new(ResourcesConfig::default()).with_security(security);
+
+        let result = service
+            .read_resource("mcp://server/allowed-paths")
+            .await
+            .unwrap();
+        let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+            panic!("expected text contents");
+        };
+        let info: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(info["restricted"], true);
+        assert_eq!(info["allow_symlinks"], false);
+        let library = library.path().canonicalize().unwrap();
+        let downloads = downloads.path().canonicalize().unwrap();
+        assert_eq!(info["allowed_paths"], serde_json::json!([downloads]));
+        assert_eq!(
+            info["readable_paths"],
+            serde_json::json!([downloads, library])
+        );
+        assert_eq!(info["writable_paths"], serde_json::json!([downloads]));
+
+        let service = ResourceService::new(ResourcesConfig::default());
+        let result = service
+            .read_resource("mcp://server/allowed-paths")
+            .await
+            .unwrap();
+        let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+            panic!("expected text contents");
+        };
+        let info: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(info["restricted"], false);
+        assert_eq!(info["allowed_paths"], serde_json::json!([]));
+    }
+
     #[tokio::test]
     async fn test_read_nonexistent_resource() {
         let config = ResourcesConfig::default();
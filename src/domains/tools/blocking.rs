@@ -0,0 +1,86 @@
+//! Blocking I/O of the tools, off the async executor.
+
+use rmcp::{ErrorData as McpError, model::CallToolResult};
+
+/// Run a tool's synchronous execution on tokio's blocking thread pool.
+///
+/// Filesystem tools do std::fs I/O, which can take seconds on a large tree or a network
+/// mount; run inline in the route's future it would stall every other request of the
+/// connection.
+pub async fn run_blocking<F>(execute: F) -> Result<CallToolResult, McpError>
+where
+    F: FnOnce() -> CallToolResult + Send + 'static,
+{
+    tokio::task::spawn_blocking(execute)
+        .await
+        .map_err(|e| McpError::internal_error(format!("Task failed: {}", e), None))
+}
+
+/// Run a tool's synchronous execution on a dedicated OS thread.
+///
+/// For the tools using musicbrainz_rs or reqwest::blocking, which start their own
+/// runtime and panic on tokio's threads. The thread is never joined: a call that times
+/// out stops waiting for it, and the thread finishes detached.
+pub async fn run_on_thread<F>(execute: F) -> Result<CallToolResult, McpError>
+where
+    F: FnOnce() -> CallToolResult + Send + 'static,
+{
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    std::thread::spawn(move || {
+        // Nobody is waiting any more once the call timed out
+        let _ = sender.send(execute());
+    });
+    receiver
+        .await
+        .map_err(|_| McpError::internal_error("Thread panicked".to_string(), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::Config;
+    use crate::domains::tools::definitions::ReadMetadataTool;
+    use crate::domains::tools::definitions::fs::list_dir::{FSListDirParams, FsListDirTool};
+    use crate::domains::tools::definitions::metadata::read::ReadMetadataParams;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    // A single-threaded runtime, like a connection's requests interleaved on one thread
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_slow_listing_does_not_delay_other_calls() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("01.flac"), b"not audio").unwrap();
+        let config = Arc::new(Config::default());
+
+        let list_params: FSListDirParams = serde_json::from_value(serde_json::json!({
+            "path": temp_dir.path().to_string_lossy()
+        }))
+        .unwrap();
+        let read_params: ReadMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": temp_dir.path().join("01.flac").to_string_lossy()
+        }))
+        .unwrap();
+
+        let start = Instant::now();
+        let list_config = config.clone();
+        let listing = run_blocking(move || {
+            // Stands in for a deep listing over a slow network mount
+            std::thread::sleep(Duration::from_millis(500));
+            FsListDirTool::execute(&list_params, &list_config)
+        });
+        let read = async {
+            let result = ReadMetadataTool::execute(&read_params, &config);
+            (result, start.elapsed())
+        };
+        let (listed, (_, read_elapsed)) = tokio::join!(listing, read);
+
+        assert!(
+            read_elapsed < Duration::from_millis(250),
+            "read_metadata waited {:?} for the listing",
+            read_elapsed
+        );
+        assert!(listed.unwrap().is_error != Some(true));
+        assert!(start.elapsed() >= Duration::from_millis(500));
+    }
+}
@@ -0,0 +1,557 @@
+//! Checksum tool definition.
+//!
+//! A tool that hashes a file, or every file in a directory, to verify copies and moves.
+
+use futures::FutureExt;
+use md5::Md5;
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool, ToolAnnotations},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_timeout;
+
+/// Size of the chunks files are read in, so large files are never loaded whole.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the checksum tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct FsChecksumParams {
+    /// File to hash, or directory whose files to hash.
+    pub path: String,
+
+    /// Hash algorithms to compute (default: ["sha256"]).
+    #[serde(default)]
+    pub algorithms: Option<Vec<Algorithm>>,
+
+    /// For a directory, also hash the files of its subdirectories.
+    #[serde(default)]
+    pub recursive: bool,
+
+    /// For a directory, maximum number of files to hash, in path order
+    /// (default and maximum: MCP_FS_CHECKSUM_MAX_FILES).
+    #[serde(default)]
+    pub max_files: Option<usize>,
+}
+
+/// Hash algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Algorithm {
+    /// SHA-256
+    #[serde(alias = "sha-256")]
+    Sha256,
+    /// MD5, e.g. to compare with checksums published alongside downloads
+    Md5,
+    /// CRC32, e.g. to compare with EAC/XLD rip logs or SFV files
+    Crc32,
+}
+
+// ============================================================================
+// Output Structure (JSON format for AI agents)
+// ============================================================================
+
+/// Result of a checksum operation
+#[derive(Debug, Serialize, JsonSchema)]
+struct ChecksumResult {
+    /// Path as given in the request
+    path: String,
+    /// One entry per file and algorithm, in path order
+    checksums: Vec<Checksum>,
+    /// Number of files hashed
+    file_count: usize,
+    /// Total size of the files hashed, in bytes
+    total_bytes: u64,
+    /// Whether the directory has more files than were hashed (see max_files)
+    truncated: bool,
+    /// Files that could not be hashed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failures: Vec<ChecksumFailure>,
+    /// Warnings encountered while listing files
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
+}
+
+/// Digest of a file
+#[derive(Debug, Serialize, JsonSchema)]
+struct Checksum {
+    /// Absolute path of the file
+    path: String,
+    algorithm: Algorithm,
+    /// Lowercase hexadecimal digest
+    digest: String,
+    /// Size of the file in bytes
+    size_bytes: u64,
+}
+
+/// A file that could not be hashed.
+#[derive(Debug, Serialize, JsonSchema)]
+struct ChecksumFailure {
+    /// Absolute path of the file
+    path: String,
+    /// Why it was not hashed
+    error: String,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Checksum tool - hashes files.
+pub struct FsChecksumTool;
+
+impl FsChecksumTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "fs_checksum";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Compute checksums of a file, or of every file in a directory (recursive=true for subdirectories too), e.g. to verify that a copy or move did not corrupt anything. SHA-256 by default; MD5 and CRC32 via `algorithms`. Files are read in chunks, so large files are fine; a directory is limited to max_files files.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    #[instrument(skip_all, fields(path = %params.path))]
+    pub fn execute(params: &FsChecksumParams, config: &Config) -> CallToolResult {
+        info!("Checksum tool called: '{}'", params.path);
+
+        match Self::checksum(params, config) {
+            Ok(result) => {
+                let mut summary = if let [checksum] = result.checksums.as_slice() {
+                    format!(
+                        "{} of '{}': {}",
+                        checksum.algorithm.label(),
+                        params.path,
+                        checksum.digest
+                    )
+                } else {
+                    format!(
+                        "Hashed {} file(s) ({} bytes) in '{}'",
+                        result.file_count, result.total_bytes, params.path
+                    )
+                };
+                if !result.failures.is_empty() {
+                    summary.push_str(&format!(
+                        "; {} file(s) could not be read",
+                        result.failures.len()
+                    ));
+                }
+                if result.truncated {
+                    summary.push_str("; more files than max_files, not all were hashed");
+                }
+
+                // Return with text summary + structured content
+                CallToolResult {
+                    content: vec![Content::text(summary)],
+                    structured_content: Some(serde_json::to_value(&result).unwrap()),
+                    is_error: Some(false),
+                    meta: None,
+                }
+            }
+            Err(e) => CallToolResult::error(vec![Content::text(e)]),
+        }
+    }
+
+    /// Validate the path, list the files to hash and hash them.
+    fn checksum(params: &FsChecksumParams, config: &Config) -> Result<ChecksumResult, String> {
+        let path = validate_path(&params.path, config).map_err(|e| {
+            warn!("Path security validation failed: {}", e);
+            format!("Path security validation failed: {}", e)
+        })?;
+
+        let mut algorithms = Vec::new();
+        for algorithm in params
+            .algorithms
+            .clone()
+            .unwrap_or_else(|| vec![Algorithm::Sha256])
+        {
+            if !algorithms.contains(&algorithm) {
+                algorithms.push(algorithm);
+            }
+        }
+        if algorithms.is_empty() {
+            return Err("algorithms is empty".to_string());
+        }
+
+        let mut result = ChecksumResult {
+            path: params.path.clone(),
+            checksums: Vec::new(),
+            file_count: 0,
+            total_bytes: 0,
+            truncated: false,
+            failures: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        let files = if path.is_dir() {
+            let max_files = config.tools.fs_checksum_max_files.max(1);
+            let max_files = params.max_files.unwrap_or(max_files).clamp(1, max_files);
+            let max_depth = if params.recursive {
+                config.tools.fs_max_depth
+            } else {
+                0
+            };
+            let mut files = Vec::new();
+            collect_files(
+                &path,
+                0,
+                max_depth,
+                config,
+                &mut files,
+                &mut result.warnings,
+            );
+            files.sort();
+            result.truncated = files.len() > max_files;
+            files.truncate(max_files);
+            files
+        } else {
+            vec![path]
+        };
+
+        for file in files {
+            match hash_file(&file, &algorithms) {
+                Ok((size, digests)) => {
+                    result.file_count += 1;
+                    result.total_bytes += size;
+                    for (algorithm, digest) in algorithms.iter().zip(digests) {
+                        result.checksums.push(Checksum {
+                            path: file.to_string_lossy().into_owned(),
+                            algorithm: *algorithm,
+                            digest,
+                            size_bytes: size,
+                        });
+                    }
+                }
+                Err(e) => {
+                    warn!("Cannot hash {}: {}", file.display(), e);
+                    result.failures.push(ChecksumFailure {
+                        path: file.to_string_lossy().into_owned(),
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        info!(
+            "Hashed {} file(s), {} bytes, {} failure(s) in '{}'",
+            result.file_count,
+            result.total_bytes,
+            result.failures.len(),
+            params.path
+        );
+        Ok(result)
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: FsChecksumParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Checksum tool (HTTP) called: '{}'", params.path);
+
+        let result = Self::execute(&params, &config);
+
+        // Serialize the full CallToolResult to preserve all fields including structuredContent
+        serde_json::to_value(&result).map_err(|e| e.to_string())
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<FsChecksumParams>(),
+            annotations: Some(ToolAnnotations::new().read_only(true)),
+            output_schema: Some(schema_for_type::<ChecksumResult>()),
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: FsChecksumParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+impl Algorithm {
+    /// Name of the algorithm for the text summary.
+    fn label(self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "SHA-256",
+            Algorithm::Md5 => "MD5",
+            Algorithm::Crc32 => "CRC32",
+        }
+    }
+}
+
+/// A running hash of one of the algorithms.
+enum Hasher {
+    Sha256(Sha256),
+    Md5(Md5),
+    Crc32(crc32fast::Hasher),
+}
+
+impl Hasher {
+    fn new(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            Algorithm::Md5 => Hasher::Md5(Md5::new()),
+            Algorithm::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Md5(hasher) => hasher.update(data),
+            Hasher::Crc32(hasher) => hasher.update(data),
+        }
+    }
+
+    /// Lowercase hexadecimal digest.
+    fn finish(self) -> String {
+        match self {
+            Hasher::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Hasher::Md5(hasher) => format!("{:x}", hasher.finalize()),
+            Hasher::Crc32(hasher) => format!("{:08x}", hasher.finalize()),
+        }
+    }
+}
+
+/// Hash a file with each algorithm in a single pass, reading it in chunks.
+///
+/// Returns the number of bytes read and the digests, in the order of `algorithms`.
+pub(crate) fn hash_file(path: &Path, algorithms: &[Algorithm]) -> io::Result<(u64, Vec<String>)> {
+    let mut file = fs::File::open(path)?;
+    let mut hashers: Vec<Hasher> = algorithms.iter().map(|a| Hasher::new(*a)).collect();
+    let mut buffer = vec![0; CHUNK_SIZE];
+    let mut size = 0;
+    loop {
+        let read = match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        for hasher in &mut hashers {
+            hasher.update(&buffer[..read]);
+        }
+        size += read as u64;
+    }
+    Ok((size, hashers.into_iter().map(Hasher::finish).collect()))
+}
+
+/// Collect the files of `dir`, and of its subdirectories down to `max_depth` levels.
+///
+/// Symlinks to files are kept when their target is within the allowed root; symlinked
+/// directories are not entered.
+fn collect_files(
+    dir: &Path,
+    depth: usize,
+    max_depth: usize,
+    config: &Config,
+    files: &mut Vec<PathBuf>,
+    warnings: &mut Vec<String>,
+) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warnings.push(format!(
+                "Could not read directory '{}': {}",
+                dir.display(),
+                e
+            ));
+            return;
+        }
+    };
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if depth < max_depth {
+                collect_files(&path, depth + 1, max_depth, config, files, warnings);
+            }
+        } else if file_type.is_symlink() {
+            if validate_path(&path.to_string_lossy(), config).is_err() {
+                warnings.push(format!(
+                    "Skipped '{}': security validation failed",
+                    path.display()
+                ));
+            } else if path.is_file() {
+                files.push(path);
+            }
+        } else if file_type.is_file() {
+            files.push(path);
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    fn params(path: &Path) -> FsChecksumParams {
+        FsChecksumParams {
+            path: path.to_string_lossy().to_string(),
+            algorithms: None,
+            recursive: false,
+            max_files: None,
+        }
+    }
+
+    #[test]
+    fn test_checksum_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("track.flac");
+        fs::write(&path, "hello world").unwrap();
+
+        let mut params = params(&path);
+        params.algorithms = Some(vec![Algorithm::Sha256, Algorithm::Md5, Algorithm::Crc32]);
+        let result = FsChecksumTool::checksum(&params, &test_config()).unwrap();
+        assert_eq!(result.file_count, 1);
+        assert_eq!(result.total_bytes, 11);
+        let digests: Vec<_> = result
+            .checksums
+            .iter()
+            .map(|c| (c.algorithm, c.digest.as_str(), c.size_bytes))
+            .collect();
+        assert_eq!(
+            digests,
+            vec![
+                (
+                    Algorithm::Sha256,
+                    "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+                    11
+                ),
+                (Algorithm::Md5, "5eb63bbbe01eeed093cb22bb8f5acdc3", 11),
+                (Algorithm::Crc32, "0d4a1185", 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_checksum_large_file_in_chunks() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("large.wav");
+        let data: Vec<u8> = (0..CHUNK_SIZE * 3 + 17).map(|i| i as u8).collect();
+        fs::write(&path, &data).unwrap();
+
+        let (size, digests) = hash_file(&path, &[Algorithm::Sha256]).unwrap();
+        assert_eq!(size, data.len() as u64);
+        assert_eq!(digests[0], format!("{:x}", Sha256::digest(&data)));
+    }
+
+    #[test]
+    fn test_checksum_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir(root.join("CD2")).unwrap();
+        fs::write(root.join("02.flac"), "two").unwrap();
+        fs::write(root.join("01.flac"), "one").unwrap();
+        fs::write(root.join("CD2/01.flac"), "three").unwrap();
+
+        let mut params = params(root);
+        let result = FsChecksumTool::checksum(&params, &test_config()).unwrap();
+        let paths: Vec<_> = result.checksums.iter().map(|c| c.path.clone()).collect();
+        let path = |name: &str| root.join(name).to_string_lossy().to_string();
+        assert_eq!(paths, vec![path("01.flac"), path("02.flac")]);
+        assert!(!result.truncated);
+
+        params.recursive = true;
+        let result = FsChecksumTool::checksum(&params, &test_config()).unwrap();
+        assert_eq!(result.file_count, 3);
+        assert_eq!(result.total_bytes, 11);
+
+        params.max_files = Some(2);
+        let result = FsChecksumTool::checksum(&params, &test_config()).unwrap();
+        assert_eq!(result.file_count, 2);
+        assert!(result.truncated);
+
+        // The server limit caps max_files
+        let mut config = test_config();
+        config.tools.fs_checksum_max_files = 1;
+        params.max_files = Some(10);
+        let result = FsChecksumTool::checksum(&params, &config).unwrap();
+        assert_eq!(result.file_count, 1);
+        assert!(result.truncated);
+    }
+
+    #[test]
+    fn test_checksum_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("missing.flac");
+        assert!(FsChecksumTool::checksum(&params(&path), &test_config()).is_err());
+
+        let mut params = params(temp_dir.path());
+        params.algorithms = Some(Vec::new());
+        let err = FsChecksumTool::checksum(&params, &test_config()).unwrap_err();
+        assert_eq!(err, "algorithms is empty");
+
+        let parsed: FsChecksumParams = serde_json::from_value(serde_json::json!({
+            "path": "/music",
+            "algorithms": ["sha-256", "crc32"]
+        }))
+        .unwrap();
+        assert_eq!(
+            parsed.algorithms,
+            Some(vec![Algorithm::Sha256, Algorithm::Crc32])
+        );
+        assert!(
+            serde_json::from_value::<FsChecksumParams>(serde_json::json!({
+                "path": "/music",
+                "algorithms": ["sha1"]
+            }))
+            .is_err()
+        );
+    }
+}
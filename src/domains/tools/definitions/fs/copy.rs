@@ -0,0 +1,581 @@
+//! Copy tool definition.
+//!
+//! A tool that copies files and directories.
+
+use futures::FutureExt;
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::audit;
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+
+use crate::domains::tools::blocking::run_blocking;
+use super::rename::validate_destination;
+use crate::domains::tools::timeout::with_audited_timeout;
+
+/// Maximum number of directory levels copied below the source directory.
+pub(super) const MAX_COPY_DEPTH: usize = 32;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the copy tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FsCopyParams {
+    /// Source path (file or directory to copy).
+    pub from: String,
+
+    /// Destination path (path of the copy, not the directory to copy into).
+    pub to: String,
+
+    /// Overwrite the destination if it already exists.
+    /// Copying a directory onto an existing one merges them, replacing files of the same name.
+    #[serde(default)]
+    pub overwrite: bool,
+
+    /// Copy directories and their contents.
+    /// Required to copy a directory.
+    #[serde(default)]
+    pub recursive: bool,
+}
+
+// ============================================================================
+// Output Structure (JSON format for AI agents)
+// ============================================================================
+
+/// Result of a copy operation
+#[derive(Debug, Serialize, JsonSchema)]
+struct CopyResult {
+    /// Source path
+    from: String,
+    /// Destination path
+    to: String,
+    /// Type of item copied ("file" or "directory")
+    item_type: String,
+    /// Whether every entry was copied
+    success: bool,
+    /// Number of files copied
+    files_copied: usize,
+    /// Total size of the files copied, in bytes
+    bytes_copied: u64,
+    /// Whether an existing destination was replaced (or merged into, for a directory)
+    replaced: bool,
+    /// Entries of a directory that could not be copied
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failures: Vec<CopyFailure>,
+}
+
+/// An entry of a directory copy that failed.
+#[derive(Debug, Serialize, JsonSchema)]
+struct CopyFailure {
+    /// Source path of the entry
+    path: String,
+    /// Why it was not copied
+    error: String,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Copy tool - copies files and directories.
+pub struct FsCopyTool;
+
+impl FsCopyTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "fs_copy";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Copy a file or directory to a new path, e.g. a cover image into an album folder or a backup of a file before a risky change. Use recursive=true to copy a directory and its contents, and overwrite=true to replace an existing destination. Entries of a directory that cannot be copied are reported without stopping the copy.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
+    #[instrument(skip_all, fields(from = %params.from, to = %params.to))]
+    pub fn execute(params: &FsCopyParams, config: &Config) -> CallToolResult {
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
+
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &FsCopyParams) -> Vec<&str> {
+        vec![params.from.as_str(), params.to.as_str()]
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &FsCopyParams, config: &Config) -> CallToolResult {
+        info!("Copy tool called: '{}' -> '{}'", params.from, params.to);
+
+        match Self::copy_path(params, config) {
+            Ok(result) => {
+                let mut summary = if result.item_type == "directory" {
+                    format!(
+                        "Copied directory '{}' to '{}': {} file(s), {} bytes",
+                        params.from, params.to, result.files_copied, result.bytes_copied
+                    )
+                } else {
+                    format!(
+                        "Copied file '{}' to '{}' ({} bytes)",
+                        params.from, params.to, result.bytes_copied
+                    )
+                };
+                if result.replaced {
+                    summary.push_str(", replacing the existing destination");
+                }
+                if !result.failures.is_empty() {
+                    summary.push_str(&format!(
+                        "; {} item(s) could not be copied",
+                        result.failures.len()
+                    ));
+                }
+
+                // Return with text summary + structured content
+                CallToolResult {
+                    content: vec![Content::text(summary)],
+                    structured_content: Some(serde_json::to_value(&result).unwrap()),
+                    is_error: Some(false),
+                    meta: None,
+                }
+            }
+            Err(e) => CallToolResult::error(vec![Content::text(e)]),
+        }
+    }
+
+    /// Validate both paths and copy the source to the destination.
+    fn copy_path(params: &FsCopyParams, config: &Config) -> Result<CopyResult, String> {
+        // Validate source path security
+        let from_path = validate_path(&params.from, config).map_err(|e| {
+            warn!("Source path security validation failed: {}", e);
+            format!("Source path security validation failed: {}", e)
+        })?;
+        // Copied to the resolved path, never the one given
+        let to_path = validate_destination(&params.to, config)?;
+
+        let is_directory = from_path.is_dir();
+        if is_directory && !params.recursive {
+            return Err(format!(
+                "Source is a directory: {}. Use recursive=true to copy it and its contents.",
+                params.from
+            ));
+        }
+
+        let destination_exists = to_path.exists();
+        if destination_exists && !params.overwrite {
+            warn!("Destination already exists: {}", params.to);
+            return Err(format!(
+                "Destination already exists: {}. Use overwrite=true to replace it.",
+                params.to
+            ));
+        }
+        if destination_exists && to_path.is_dir() != is_directory {
+            let (from_type, to_type) = if is_directory {
+                ("directory", "file")
+            } else {
+                ("file", "directory")
+            };
+            return Err(format!(
+                "Cannot replace a {} with a {}: {}",
+                to_type, from_type, params.to
+            ));
+        }
+
+        let mut result = CopyResult {
+            from: params.from.clone(),
+            to: params.to.clone(),
+            item_type: if is_directory { "directory" } else { "file" }.to_string(),
+            success: true,
+            files_copied: 0,
+            bytes_copied: 0,
+            replaced: destination_exists,
+            failures: Vec::new(),
+        };
+
+        if is_directory {
+            // A copy inside its own source would keep copying itself
+            if to_path.starts_with(&from_path) {
+                return Err(format!(
+                    "Cannot copy directory '{}' into itself ('{}')",
+                    params.from, params.to
+                ));
+            }
+            Self::copy_directory(&from_path, &to_path, &mut result);
+            result.success = result.failures.is_empty();
+        } else {
+            result.bytes_copied = fs::copy(&from_path, &to_path).map_err(|e| {
+                warn!("Failed to copy '{}' to '{}': {}", params.from, params.to, e);
+                if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    format!(
+                        "Permission denied: Cannot copy '{}' to '{}'",
+                        params.from, params.to
+                    )
+                } else {
+                    format!("Failed to copy '{}' to '{}': {}", params.from, params.to, e)
+                }
+            })?;
+            result.files_copied = 1;
+        }
+
+        info!(
+            "Copied '{}' to '{}': {} file(s), {} bytes, {} failure(s)",
+            params.from,
+            params.to,
+            result.files_copied,
+            result.bytes_copied,
+            result.failures.len()
+        );
+        Ok(result)
+    }
+
+    /// Copy a directory tree, at most [`MAX_COPY_DEPTH`] levels deep, recording the entries
+    /// that fail instead of stopping.
+    ///
+    /// Symlinks are not followed, so a link cannot pull in files from outside the source.
+    fn copy_directory(from: &Path, to: &Path, result: &mut CopyResult) {
+        let mut failed = |path: &Path, error: String| {
+            warn!("Cannot copy {}: {}", path.display(), error);
+            result.failures.push(CopyFailure {
+                path: path.to_string_lossy().into_owned(),
+                error,
+            });
+        };
+        let mut files_copied = 0;
+        let mut bytes_copied = 0;
+        let mut pending: Vec<(PathBuf, PathBuf, usize)> =
+            vec![(from.to_path_buf(), to.to_path_buf(), 0)];
+
+        while let Some((source, destination, depth)) = pending.pop() {
+            if !destination.is_dir()
+                && let Err(e) = fs::create_dir(&destination)
+            {
+                failed(
+                    &source,
+                    format!("Cannot create {}: {}", destination.display(), e),
+                );
+                continue;
+            }
+            let entries = match fs::read_dir(&source) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    failed(&source, format!("Cannot read directory: {}", e));
+                    continue;
+                }
+            };
+            let mut entries: Vec<_> = entries.filter_map(Result::ok).collect();
+            entries.sort_by_key(|entry| entry.file_name());
+
+            for entry in entries {
+                let path = entry.path();
+                let target = destination.join(entry.file_name());
+                let file_type = match entry.file_type() {
+                    Ok(file_type) => file_type,
+                    Err(e) => {
+                        failed(&path, e.to_string());
+                        continue;
+                    }
+                };
+                if file_type.is_symlink() {
+                    failed(&path, "Symlinks are not copied".to_string());
+                } else if file_type.is_dir() {
+                    if depth < MAX_COPY_DEPTH {
+                        pending.push((path, target, depth + 1));
+                    } else {
+                        failed(
+                            &path,
+                            format!("Deeper than {} levels, not copied", MAX_COPY_DEPTH),
+                        );
+                    }
+                } else if target.is_dir() {
+                    failed(&path, format!("{} is a directory", target.display()));
+                } else {
+                    match fs::copy(&path, &target) {
+                        Ok(bytes) => {
+                            files_copied += 1;
+                            bytes_copied += bytes;
+                        }
+                        Err(e) => failed(&path, e.to_string()),
+                    }
+                }
+            }
+        }
+
+        result.files_copied = files_copied;
+        result.bytes_copied = bytes_copied;
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let from = arguments
+            .get("from")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "Missing or invalid 'from' parameter".to_string())?
+            .to_string();
+
+        let to = arguments
+            .get("to")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "Missing or invalid 'to' parameter".to_string())?
+            .to_string();
+
+        let overwrite = arguments
+            .get("overwrite")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let recursive = arguments
+            .get("recursive")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        info!("Copy tool (HTTP) called: '{}' -> '{}'", from, to);
+
+        let params = FsCopyParams {
+            from,
+            to,
+            overwrite,
+            recursive,
+        };
+
+        let result = Self::execute(&params, &config);
+
+        // Serialize the full CallToolResult to preserve all fields including structuredContent
+        serde_json::to_value(&result).map_err(|e| e.to_string())
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<FsCopyParams>(),
+            annotations: None,
+            output_schema: Some(schema_for_type::<CopyResult>()),
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: FsCopyParams = serde_json::from_value(serde_json::Value::Object(args))
+                    .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    fn params(from: &Path, to: &Path, overwrite: bool, recursive: bool) -> FsCopyParams {
+        FsCopyParams {
+            from: from.to_string_lossy().to_string(),
+            to: to.to_string_lossy().to_string(),
+            overwrite,
+            recursive,
+        }
+    }
+
+    fn error_text(result: &CallToolResult) -> String {
+        assert!(result.is_error.unwrap_or(false));
+        match &result.content[0].raw {
+            rmcp::model::RawContent::Text(text) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    #[test]
+    fn test_copy_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("cover.jpg");
+        let copy = temp_dir.path().join("folder.jpg");
+        fs::write(&source, "image").unwrap();
+
+        let result = FsCopyTool::execute(&params(&source, &copy, false, false), &test_config());
+        let structured = result
+            .structured_content
+            .expect("structured_content should exist");
+        assert_eq!(structured["item_type"], "file");
+        assert_eq!(structured["bytes_copied"], 5);
+        assert_eq!(structured["replaced"], false);
+        assert!(source.exists());
+        assert_eq!(fs::read_to_string(&copy).unwrap(), "image");
+    }
+
+    #[test]
+    fn test_copy_file_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("new.txt");
+        let existing = temp_dir.path().join("existing.txt");
+        fs::write(&source, "new").unwrap();
+        fs::write(&existing, "old").unwrap();
+
+        let result = FsCopyTool::execute(&params(&source, &existing, false, false), &test_config());
+        assert!(error_text(&result).contains("already exists"));
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "old");
+
+        let result = FsCopyTool::execute(&params(&source, &existing, true, false), &test_config());
+        assert_eq!(result.structured_content.unwrap()["replaced"], true);
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "new");
+    }
+
+    #[test]
+    fn test_copy_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("album");
+        let copy = temp_dir.path().join("album copy");
+        fs::create_dir_all(source.join("CD1")).unwrap();
+        fs::write(source.join("cover.jpg"), "12345").unwrap();
+        fs::write(source.join("CD1/01.flac"), "123").unwrap();
+
+        let result = FsCopyTool::execute(&params(&source, &copy, false, false), &test_config());
+        assert!(error_text(&result).contains("recursive=true"));
+
+        let result = FsCopyTool::execute(&params(&source, &copy, false, true), &test_config());
+        let structured = result
+            .structured_content
+            .expect("structured_content should exist");
+        assert_eq!(structured["item_type"], "directory");
+        assert_eq!(structured["success"], true);
+        assert_eq!(structured["files_copied"], 2);
+        assert_eq!(structured["bytes_copied"], 8);
+        assert!(structured.get("failures").is_none());
+        assert_eq!(fs::read_to_string(copy.join("CD1/01.flac")).unwrap(), "123");
+    }
+
+    #[test]
+    fn test_copy_directory_merges_with_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("album");
+        let existing = temp_dir.path().join("backup");
+        fs::create_dir(&source).unwrap();
+        fs::create_dir(&existing).unwrap();
+        fs::write(source.join("01.flac"), "new").unwrap();
+        fs::write(existing.join("01.flac"), "old").unwrap();
+        fs::write(existing.join("notes.txt"), "kept").unwrap();
+
+        let result = FsCopyTool::execute(&params(&source, &existing, true, true), &test_config());
+        assert_eq!(result.structured_content.unwrap()["replaced"], true);
+        assert_eq!(fs::read_to_string(existing.join("01.flac")).unwrap(), "new");
+        assert!(existing.join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_copy_directory_into_itself() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("album");
+        fs::create_dir(&source).unwrap();
+
+        let result = FsCopyTool::execute(
+            &params(&source, &source.join("copy"), false, true),
+            &test_config(),
+        );
+        assert!(error_text(&result).contains("into itself"));
+        assert!(!source.join("copy").exists());
+    }
+
+    #[test]
+    fn test_copy_directory_type_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("album");
+        let file = temp_dir.path().join("file.txt");
+        fs::create_dir(&source).unwrap();
+        fs::write(&file, "content").unwrap();
+
+        let result = FsCopyTool::execute(&params(&source, &file, true, true), &test_config());
+        assert!(error_text(&result).contains("Cannot replace a file with a directory"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_directory_reports_failures() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("album");
+        let copy = temp_dir.path().join("copy");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("01.flac"), "audio").unwrap();
+        std::os::unix::fs::symlink("/etc/passwd", source.join("link")).unwrap();
+
+        let result = FsCopyTool::execute(&params(&source, &copy, false, true), &test_config());
+        assert!(!result.is_error.unwrap_or(false));
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["success"], false);
+        assert_eq!(structured["files_copied"], 1);
+        assert_eq!(structured["failures"].as_array().unwrap().len(), 1);
+        assert!(!copy.join("link").exists());
+        assert!(copy.join("01.flac").exists());
+    }
+
+    #[test]
+    fn test_copy_nonexistent_source() {
+        let params = FsCopyParams {
+            from: "/nonexistent/file.txt".to_string(),
+            to: "/some/other/path.txt".to_string(),
+            overwrite: false,
+            recursive: false,
+        };
+
+        let result = FsCopyTool::execute(&params, &test_config());
+        assert!(error_text(&result).contains("does not exist"));
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_copy_http_handler_missing_param() {
+        let args = serde_json::json!({
+            "from": "/some/path.txt"
+        });
+
+        let config = Arc::new(test_config());
+        let result = FsCopyTool::http_handler(args, config);
+        assert!(result.is_err());
+    }
+}
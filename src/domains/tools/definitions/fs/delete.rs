@@ -1,36 +1,56 @@
 //! Delete tool definition.
 //!
-//! A tool that deletes files and directories.
+//! A tool that deletes files and directories, one path or a batch of paths per call.
 
 use futures::FutureExt;
 use rmcp::{
     ErrorData as McpError,
     handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
-    model::{CallToolResult, Content, Tool},
+    model::{CallToolResult, Content, JsonObject, Tool},
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::path::Path;
 use std::sync::Arc;
 use tracing::{info, instrument, warn};
 
+use crate::core::audit;
 use crate::core::config::Config;
-use crate::core::security::validate_path;
+use crate::core::security::{AccessKind, validate_path_for};
+
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_audited_timeout;
 
 // ============================================================================
 // Tool Parameters
 // ============================================================================
 
 /// Parameters for the delete tool.
-#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct FsDeleteParams {
-    /// Path to the file or directory to delete.
-    pub path: String,
+    /// Path to the file or directory to delete (use either path or paths).
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Paths to delete in one call, e.g. junk files found by fs_find. Each path is
+    /// validated and deleted independently: one failure does not stop the others.
+    #[serde(default)]
+    pub paths: Option<Vec<String>>,
 
     /// Recursively delete directories and their contents.
-    /// Required to delete non-empty directories.
+    /// Required to delete non-empty directories. Applies to each path.
     #[serde(default)]
     pub recursive: bool,
+
+    /// Move the item to the system trash instead of deleting it permanently
+    /// (defaults to MCP_FS_PREFER_TRASH). Fails, deleting nothing, if no trash is available.
+    #[serde(default)]
+    pub use_trash: Option<bool>,
+
+    /// If true, delete nothing: list what would be removed, with sizes and a total.
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 // ============================================================================
@@ -46,9 +66,87 @@ struct DeleteResult {
     item_type: String,
     /// Whether the operation succeeded
     success: bool,
+    /// Whether the item was removed from its location; false for a dry run
+    deleted: bool,
     /// Whether recursive deletion was used
     #[serde(skip_serializing_if = "Option::is_none")]
     recursive: Option<bool>,
+    /// Whether the item was moved to the trash rather than deleted permanently
+    trashed: bool,
+    /// Where the item is in the trash, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trash_location: Option<String>,
+    /// Items that would be removed, the target first (dry run only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    would_remove: Option<Vec<RemovedItem>>,
+    /// Number of files and directories that would be removed, the target included
+    /// (dry run only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_items: Option<usize>,
+    /// Total size in bytes of the files that would be removed (dry run only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_bytes: Option<u64>,
+    /// Whether would_remove was cut at the entry limit; the totals still count every item
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    truncated: bool,
+    /// Files (and symlinks) removed with a directory (recursive delete only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files_removed: Option<usize>,
+    /// Directories removed, the deleted directory included (recursive delete only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dirs_removed: Option<usize>,
+    /// Total size in bytes of the files removed (recursive delete only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bytes_freed: Option<u64>,
+    /// Whether part of the tree is deeper than the depth limit or unreadable, so the
+    /// totals and counts only cover what was reached
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    approximate: bool,
+}
+
+/// A file or directory inside the target of a delete
+#[derive(Debug, Serialize, JsonSchema)]
+struct RemovedItem {
+    /// Absolute path
+    path: String,
+    /// Type of item ("file", "directory" or "symlink")
+    item_type: String,
+    /// Size in bytes (files only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size_bytes: Option<u64>,
+}
+
+/// Result of deleting a batch of paths
+#[derive(Debug, Serialize, JsonSchema)]
+struct BatchDeleteResult {
+    /// Outcome of each path, in request order
+    results: Vec<PathDeleteResult>,
+    total_count: usize,
+    success_count: usize,
+    failure_count: usize,
+}
+
+/// Outcome of deleting a single path of the batch
+#[derive(Debug, Serialize, JsonSchema)]
+struct PathDeleteResult {
+    /// Path as given in the request
+    path: String,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<DeleteResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Any of the structured results this tool can return.
+///
+/// Only used to describe the output schema; a call emits one of the result types directly.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum DeleteOutput {
+    Single(DeleteResult),
+    Batch(BatchDeleteResult),
 }
 
 // ============================================================================
@@ -63,32 +161,133 @@ impl FsDeleteTool {
     pub const NAME: &'static str = "fs_delete";
 
     /// Tool description shown to clients.
-    pub const DESCRIPTION: &'static str = "Delete a file or directory. Use recursive=true to delete non-empty directories and their contents.";
+    pub const DESCRIPTION: &'static str = "Delete a file or directory, or several at once with `paths` (each path is deleted independently and reported separately). Use recursive=true to delete non-empty directories and their contents, and use_trash=true to move items to the system trash instead of deleting them permanently. Use dry_run=true first to list what would be removed without deleting anything.";
 
     /// Execute the tool logic (for STDIO/TCP transport via rmcp).
-    #[instrument(skip_all, fields(path = %params.path))]
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
+    #[instrument(skip_all, fields(path = ?params.path))]
     pub fn execute(params: &FsDeleteParams, config: &Config) -> CallToolResult {
-        info!("Delete tool called: '{}'", params.path);
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
 
-        // Validate path security
-        let target_path = match validate_path(&params.path, config) {
-            Ok(p) => p,
-            Err(e) => {
-                warn!("Path security validation failed: {}", e);
-                return CallToolResult::error(vec![Content::text(format!(
-                    "Path security validation failed: {}",
-                    e
-                ))]);
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &FsDeleteParams) -> Vec<&str> {
+        params
+            .path
+            .iter()
+            .chain(params.paths.iter().flatten())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &FsDeleteParams, config: &Config) -> CallToolResult {
+        let use_trash = params.use_trash.unwrap_or(config.tools.fs_prefer_trash);
+
+        match (&params.path, &params.paths) {
+            (Some(_), Some(_)) => CallToolResult::error(vec![Content::text(
+                "Provide either path or paths, not both",
+            )]),
+            (None, None) => {
+                CallToolResult::error(vec![Content::text("Provide a path or a list of paths")])
             }
-        };
+            (None, Some(paths)) if paths.is_empty() => {
+                CallToolResult::error(vec![Content::text("paths is empty")])
+            }
+            (Some(path), None) => {
+                info!("Delete tool called: '{}'", path);
+                match Self::delete(path, params, use_trash, config) {
+                    Ok(result) => CallToolResult {
+                        content: vec![Content::text(Self::summary(&result, use_trash))],
+                        structured_content: Some(serde_json::to_value(&result).unwrap()),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(error_msg) => CallToolResult::error(vec![Content::text(error_msg)]),
+                }
+            }
+            (None, Some(paths)) => {
+                info!("Delete tool called for {} paths", paths.len());
+                let results: Vec<PathDeleteResult> = paths
+                    .iter()
+                    .map(|path| match Self::delete(path, params, use_trash, config) {
+                        Ok(result) => PathDeleteResult {
+                            path: path.clone(),
+                            success: true,
+                            result: Some(result),
+                            error: None,
+                        },
+                        Err(error) => PathDeleteResult {
+                            path: path.clone(),
+                            success: false,
+                            result: None,
+                            error: Some(error),
+                        },
+                    })
+                    .collect();
+                let success_count = results.iter().filter(|r| r.success).count();
+                let result = BatchDeleteResult {
+                    total_count: results.len(),
+                    success_count,
+                    failure_count: results.len() - success_count,
+                    results,
+                };
+
+                let action = if use_trash {
+                    "Moved to the trash"
+                } else {
+                    "Deleted"
+                };
+                let summary = if params.dry_run {
+                    format!(
+                        "DRY RUN: would have {} {}/{} path(s) ({} failed); nothing was deleted",
+                        action.to_lowercase(),
+                        result.success_count,
+                        result.total_count,
+                        result.failure_count
+                    )
+                } else {
+                    format!(
+                        "{} {}/{} path(s) ({} failed)",
+                        action, result.success_count, result.total_count, result.failure_count
+                    )
+                };
+                info!("{}", summary);
+
+                // Failures are reported per path; the call itself succeeded
+                CallToolResult {
+                    content: vec![Content::text(summary)],
+                    structured_content: Some(serde_json::to_value(&result).unwrap()),
+                    is_error: Some(false),
+                    meta: None,
+                }
+            }
+        }
+    }
+
+    /// Validate and delete a single path, move it to the trash, or for a dry run list
+    /// what would be removed.
+    fn delete(
+        path: &str,
+        params: &FsDeleteParams,
+        use_trash: bool,
+        config: &Config,
+    ) -> Result<DeleteResult, String> {
+        let recursive = params.recursive;
+        // Validate path security
+        let target_path = validate_path_for(path, AccessKind::Write, config).map_err(|e| {
+            warn!("Path security validation failed: {}", e);
+            format!("Path security validation failed: {}", e)
+        })?;
 
         // Check if path exists
         if !target_path.exists() {
-            warn!("Path does not exist: {}", params.path);
-            return CallToolResult::error(vec![Content::text(format!(
-                "Path does not exist: {}",
-                params.path
-            ))]);
+            warn!("Path does not exist: {}", path);
+            return Err(format!("Path does not exist: {}", path));
         }
 
         // Determine item type for response (before deletion)
@@ -102,88 +301,165 @@ impl FsDeleteTool {
         };
 
         // Check if directory is non-empty and recursive flag is not set
-        if is_directory && !params.recursive {
+        if is_directory && !recursive {
             // Check if directory is empty
             match fs::read_dir(&target_path) {
                 Ok(mut entries) => {
                     if entries.next().is_some() {
-                        warn!("Directory is not empty and recursive flag is not set: {}", params.path);
-                        return CallToolResult::error(vec![Content::text(format!(
+                        warn!("Directory is not empty and recursive flag is not set: {}", path);
+                        return Err(format!(
                             "Directory is not empty: {}. Use recursive=true to delete it and its contents.",
-                            params.path
-                        ))]);
+                            path
+                        ));
                     }
                 }
                 Err(e) => {
-                    warn!("Failed to read directory '{}': {}", params.path, e);
-                    return CallToolResult::error(vec![Content::text(format!(
-                        "Failed to read directory '{}': {}",
-                        params.path, e
-                    ))]);
+                    warn!("Failed to read directory '{}': {}", path, e);
+                    return Err(format!("Failed to read directory '{}': {}", path, e));
                 }
             }
         }
 
-        // Perform the delete operation
-        let delete_result = if is_directory {
-            if params.recursive {
-                fs::remove_dir_all(&target_path)
-            } else {
-                fs::remove_dir(&target_path)
-            }
-        } else {
-            fs::remove_file(&target_path)
-        };
-
-        match delete_result {
-            Ok(_) => {
-                info!("Successfully deleted '{}' ({})", params.path, item_type);
+        // A dry run stops here, with everything that passed the checks above
+        if params.dry_run {
+            let contents = TreeContents::collect(
+                &target_path,
+                config.tools.fs_max_depth,
+                config.tools.fs_max_entries,
+            );
+            return Ok(DeleteResult {
+                path: path.to_string(),
+                item_type: item_type.to_string(),
+                success: true,
+                deleted: false,
+                recursive: (recursive && is_directory).then_some(true),
+                trashed: false,
+                trash_location: None,
+                would_remove: Some(contents.items),
+                total_items: Some(contents.files + contents.dirs),
+                total_bytes: Some(contents.bytes),
+                truncated: contents.truncated,
+                files_removed: None,
+                dirs_removed: None,
+                bytes_freed: None,
+                approximate: contents.approximate,
+            });
+        }
 
-                // Create human-readable summary
-                let summary = if params.recursive && is_directory {
-                    format!(
-                        "Successfully deleted {} '{}' and all its contents",
-                        item_type, params.path
-                    )
+        // Count what a recursive delete removes while it is still there
+        let removed = (recursive && is_directory)
+            .then(|| TreeContents::collect(&target_path, config.tools.fs_max_depth, 0));
+
+        // Perform the delete operation, or move to the trash; a trash failure is reported
+        // rather than falling back to a permanent delete
+        if use_trash {
+            trash::delete(&target_path).map_err(|e| {
+                warn!("Failed to move '{}' to the trash: {}", path, e);
+                format!(
+                    "Could not move '{}' to the trash: {}. Nothing was deleted; use use_trash=false to delete it permanently.",
+                    path, e
+                )
+            })?;
+            info!("Moved '{}' ({}) to the trash", path, item_type);
+        } else {
+            let result = if is_directory {
+                if recursive {
+                    fs::remove_dir_all(&target_path)
                 } else {
-                    format!("Successfully deleted {} '{}'", item_type, params.path)
-                };
-
-                // Create structured result
-                let result = DeleteResult {
-                    path: params.path.clone(),
-                    item_type: item_type.to_string(),
-                    success: true,
-                    recursive: if params.recursive && is_directory {
-                        Some(true)
-                    } else {
-                        None
-                    },
-                };
-
-                // Return with text summary + structured content
-                CallToolResult {
-                    content: vec![Content::text(summary)],
-                    structured_content: Some(serde_json::to_value(&result).unwrap()),
-                    is_error: Some(false),
-                    meta: None,
+                    fs::remove_dir(&target_path)
                 }
-            }
-            Err(e) => {
-                warn!("Failed to delete '{}': {}", params.path, e);
+            } else {
+                fs::remove_file(&target_path)
+            };
+            result.map_err(|e| {
+                warn!("Failed to delete '{}': {}", path, e);
 
                 // Provide more helpful error messages
-                let error_msg = if e.kind() == std::io::ErrorKind::PermissionDenied {
-                    format!("Permission denied: Cannot delete '{}'", params.path)
+                if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    format!("Permission denied: Cannot delete '{}'", path)
                 } else if e.kind() == std::io::ErrorKind::NotFound {
-                    format!("Path not found: '{}'", params.path)
+                    format!("Path not found: '{}'", path)
                 } else {
-                    format!("Failed to delete '{}': {}", params.path, e)
-                };
+                    format!("Failed to delete '{}': {}", path, e)
+                }
+            })?;
+            info!("Successfully deleted '{}' ({})", path, item_type);
+        }
+
+        Ok(DeleteResult {
+            path: path.to_string(),
+            item_type: item_type.to_string(),
+            success: true,
+            deleted: true,
+            recursive: (recursive && is_directory).then_some(true),
+            trashed: use_trash,
+            trash_location: use_trash.then(|| trash_location(&target_path)).flatten(),
+            would_remove: None,
+            total_items: None,
+            total_bytes: None,
+            truncated: false,
+            files_removed: removed.as_ref().map(|removed| removed.files),
+            dirs_removed: removed.as_ref().map(|removed| removed.dirs),
+            bytes_freed: removed.as_ref().map(|removed| removed.bytes),
+            approximate: removed.is_some_and(|removed| removed.approximate),
+        })
+    }
 
-                CallToolResult::error(vec![Content::text(error_msg)])
+    /// Human-readable summary of a single delete.
+    fn summary(result: &DeleteResult, use_trash: bool) -> String {
+        if !result.deleted {
+            let action = if use_trash {
+                "move to the trash"
+            } else {
+                "delete"
+            };
+            return format!(
+                "DRY RUN: would {} {} '{}'{}: {}{} item(s), {} bytes; nothing was deleted",
+                action,
+                result.item_type,
+                result.path,
+                if result.recursive == Some(true) {
+                    " and all its contents"
+                } else {
+                    ""
+                },
+                if result.approximate { "at least " } else { "" },
+                result.total_items.unwrap_or(0),
+                result.total_bytes.unwrap_or(0)
+            );
+        }
+        let mut summary = if result.trashed {
+            let mut summary = format!("Moved {} '{}' to the trash", result.item_type, result.path);
+            if let Some(location) = &result.trash_location {
+                summary.push_str(&format!(" ('{}')", location));
             }
+            summary
+        } else if result.recursive == Some(true) {
+            format!(
+                "Successfully deleted {} '{}' and all its contents",
+                result.item_type, result.path
+            )
+        } else {
+            format!(
+                "Successfully deleted {} '{}'",
+                result.item_type, result.path
+            )
+        };
+        if let (Some(files), Some(dirs), Some(bytes)) = (
+            result.files_removed,
+            result.dirs_removed,
+            result.bytes_freed,
+        ) {
+            summary.push_str(&format!(
+                ": removed {}{} file(s) and {} director{} totaling {} bytes",
+                if result.approximate { "at least " } else { "" },
+                files,
+                dirs,
+                if dirs == 1 { "y" } else { "ies" },
+                bytes
+            ));
         }
+        summary
     }
 
     /// HTTP handler for this tool (for HTTP transport).
@@ -195,17 +471,43 @@ impl FsDeleteTool {
         let path = arguments
             .get("path")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| "Missing or invalid 'path' parameter".to_string())?
-            .to_string();
+            .map(String::from);
+
+        let paths = arguments
+            .get("paths")
+            .and_then(|v| v.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            });
+
+        if path.is_none() && paths.is_none() {
+            return Err("Missing or invalid 'path' or 'paths' parameter".to_string());
+        }
 
         let recursive = arguments
             .get("recursive")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        info!("Delete tool (HTTP) called: '{}'", path);
+        let use_trash = arguments.get("use_trash").and_then(|v| v.as_bool());
+
+        let dry_run = arguments
+            .get("dry_run")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
-        let params = FsDeleteParams { path, recursive };
+        info!("Delete tool (HTTP) called: {:?} {:?}", path, paths);
+
+        let params = FsDeleteParams {
+            path,
+            paths,
+            recursive,
+            use_trash,
+            dry_run,
+        };
 
         let result = Self::execute(&params, &config);
 
@@ -220,18 +522,26 @@ impl FsDeleteTool {
             description: Some(Self::DESCRIPTION.into()),
             input_schema: schema_for_type::<FsDeleteParams>(),
             annotations: None,
-            output_schema: Some(schema_for_type::<DeleteResult>()),
+            output_schema: Some(Self::output_schema()),
             icons: None,
             meta: None,
             title: None,
         }
     }
 
+    /// Output schema covering both single and batch results.
+    fn output_schema() -> Arc<JsonObject> {
+        let mut schema = (*schema_for_type::<DeleteOutput>()).clone();
+        schema.insert("type".to_string(), serde_json::json!("object"));
+        Arc::new(schema)
+    }
+
     /// Create a ToolRoute for STDIO/TCP transport.
     pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
     where
         S: Send + Sync + 'static,
     {
+        let timeout = config.tools.timeout_for(Self::NAME);
         ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             let config = config.clone();
@@ -239,13 +549,155 @@ impl FsDeleteTool {
                 let params: FsDeleteParams =
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
-                Ok(Self::execute(&params, &config))
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
             }
             .boxed()
         })
     }
 }
 
+/// What a delete removes: the target and, for a directory, its contents.
+///
+/// The walk stops at the configured depth limit, and lists items up to the entry limit
+/// while still counting the rest.
+struct TreeContents {
+    max_depth: usize,
+    max_listed: usize,
+    /// Items found, the target first, then each directory's contents in name order
+    items: Vec<RemovedItem>,
+    files: usize,
+    dirs: usize,
+    /// Total size of the files
+    bytes: u64,
+    /// Whether items were left out of `items`
+    truncated: bool,
+    /// Whether part of the tree was too deep or unreadable
+    approximate: bool,
+}
+
+impl TreeContents {
+    /// Walk the target, without following symlinks, as `remove_dir_all` does.
+    fn collect(target: &Path, max_depth: usize, max_listed: usize) -> Self {
+        let mut contents = Self {
+            max_depth,
+            max_listed,
+            items: Vec::new(),
+            files: 0,
+            dirs: 0,
+            bytes: 0,
+            truncated: false,
+            approximate: false,
+        };
+        contents.visit(target, 0);
+        contents
+    }
+
+    fn visit(&mut self, path: &Path, depth: usize) {
+        let Ok(metadata) = fs::symlink_metadata(path) else {
+            self.approximate = true;
+            return;
+        };
+        let is_dir = metadata.is_dir();
+        let size = metadata.is_file().then_some(metadata.len());
+        if is_dir {
+            self.dirs += 1;
+        } else {
+            self.files += 1;
+            self.bytes += size.unwrap_or(0);
+        }
+        if self.items.len() < self.max_listed {
+            let item_type = if is_dir {
+                "directory"
+            } else if metadata.is_symlink() {
+                "symlink"
+            } else {
+                "file"
+            };
+            self.items.push(RemovedItem {
+                path: path.to_string_lossy().into_owned(),
+                item_type: item_type.to_string(),
+                size_bytes: size,
+            });
+        } else {
+            self.truncated = true;
+        }
+        if !is_dir {
+            return;
+        }
+
+        let Ok(entries) = fs::read_dir(path) else {
+            self.approximate = true;
+            return;
+        };
+        let mut children: Vec<_> = entries.filter_map(|entry| entry.ok()).collect();
+        if depth == self.max_depth {
+            // Too deep to walk: only an empty directory is known exactly
+            self.approximate |= !children.is_empty();
+            return;
+        }
+        children.sort_by_key(|entry| entry.file_name());
+        for child in children {
+            self.visit(&child.path(), depth + 1);
+        }
+    }
+}
+
+/// Where an item just moved to the trash ended up.
+///
+/// The freedesktop trash records each item in an info file, `<trash>/info/<name>.trashinfo`,
+/// next to the item itself in `<trash>/files/<name>`; the newest record of the path is ours.
+#[cfg(all(
+    unix,
+    not(target_os = "macos"),
+    not(target_os = "ios"),
+    not(target_os = "android")
+))]
+fn trash_location(original: &Path) -> Option<String> {
+    let item = trash::os_limited::list()
+        .ok()?
+        .into_iter()
+        .filter(|item| item.original_path() == original)
+        .max_by_key(|item| item.time_deleted)?;
+    trashed_file(Path::new(&item.id)).map(|path| path.to_string_lossy().into_owned())
+}
+
+/// Where an item just moved to the trash ended up (not known on this platform).
+#[cfg(not(all(
+    unix,
+    not(target_os = "macos"),
+    not(target_os = "ios"),
+    not(target_os = "android")
+)))]
+fn trash_location(_original: &Path) -> Option<String> {
+    None
+}
+
+/// The trashed item described by a freedesktop `.trashinfo` file.
+#[cfg(all(
+    unix,
+    not(target_os = "macos"),
+    not(target_os = "ios"),
+    not(target_os = "android")
+))]
+fn trashed_file(info_file: &Path) -> Option<std::path::PathBuf> {
+    let name = info_file
+        .file_name()?
+        .to_str()?
+        .strip_suffix(".trashinfo")?;
+    let trash_dir = info_file.parent()?.parent()?;
+    Some(trash_dir.join("files").join(name))
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -271,8 +723,11 @@ mod tests {
         assert!(test_file.exists());
 
         let params = FsDeleteParams {
-            path: test_file.to_string_lossy().to_string(),
+            path: Some(test_file.to_string_lossy().to_string()),
+            paths: None,
             recursive: false,
+            use_trash: None,
+            dry_run: false,
         };
 
         let config = test_config();
@@ -294,8 +749,11 @@ mod tests {
         assert!(test_dir.exists());
 
         let params = FsDeleteParams {
-            path: test_dir.to_string_lossy().to_string(),
+            path: Some(test_dir.to_string_lossy().to_string()),
+            paths: None,
             recursive: false,
+            use_trash: None,
+            dry_run: false,
         };
 
         let config = test_config();
@@ -317,8 +775,11 @@ mod tests {
         fs::write(test_dir.join("file.txt"), "content").unwrap();
 
         let params = FsDeleteParams {
-            path: test_dir.to_string_lossy().to_string(),
+            path: Some(test_dir.to_string_lossy().to_string()),
+            paths: None,
             recursive: false,
+            use_trash: None,
+            dry_run: false,
         };
 
         let config = test_config();
@@ -350,8 +811,11 @@ mod tests {
         fs::write(sub_dir.join("file2.txt"), "content2").unwrap();
 
         let params = FsDeleteParams {
-            path: test_dir.to_string_lossy().to_string(),
+            path: Some(test_dir.to_string_lossy().to_string()),
+            paths: None,
             recursive: true,
+            use_trash: None,
+            dry_run: false,
         };
 
         let config = test_config();
@@ -362,11 +826,147 @@ mod tests {
         assert!(!test_dir.exists());
     }
 
+    #[test]
+    fn test_delete_recursive_counts() {
+        let temp_dir = TempDir::new().unwrap();
+        let album = temp_dir.path().join("album");
+        fs::create_dir_all(album.join("scans/back")).unwrap();
+        fs::write(album.join("01.flac"), "12345").unwrap();
+        fs::write(album.join("scans/front.jpg"), "123").unwrap();
+        fs::write(album.join("scans/back/back.jpg"), "12").unwrap();
+
+        let params = FsDeleteParams {
+            path: Some(album.to_string_lossy().to_string()),
+            paths: None,
+            recursive: true,
+            use_trash: Some(false),
+            dry_run: false,
+        };
+        let result = FsDeleteTool::execute(&params, &test_config());
+        assert!(!album.exists());
+        let text = match &result.content[0].raw {
+            rmcp::model::RawContent::Text(text) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        assert!(
+            text.ends_with("removed 3 file(s) and 3 directories totaling 10 bytes"),
+            "{}",
+            text
+        );
+        let json = result.structured_content.unwrap();
+        assert_eq!(json["files_removed"], 3);
+        assert_eq!(json["dirs_removed"], 3);
+        assert_eq!(json["bytes_freed"], 10);
+        assert!(json.get("approximate").is_none());
+
+        // Beyond the depth limit the counts are lower bounds
+        fs::create_dir_all(album.join("scans/back")).unwrap();
+        fs::write(album.join("scans/back/back.jpg"), "12").unwrap();
+        let mut config = test_config();
+        config.tools.fs_max_depth = 1;
+        let result = FsDeleteTool::execute(&params, &config);
+        assert!(!album.exists());
+        let json = result.structured_content.unwrap();
+        assert_eq!(json["files_removed"], 0);
+        assert_eq!(json["dirs_removed"], 2);
+        assert_eq!(json["approximate"], true);
+    }
+
+    #[test]
+    fn test_delete_in_read_only_root() {
+        let library = TempDir::new().unwrap();
+        let staging = TempDir::new().unwrap();
+        let library_file = library.path().join("01.flac");
+        let staging_file = staging.path().join("01.flac");
+        fs::write(&library_file, "test").unwrap();
+        fs::write(&staging_file, "test").unwrap();
+
+        let mut config = test_config();
+        config.security.read_paths = vec![library.path().to_path_buf()];
+        config.security.write_paths = vec![staging.path().to_path_buf()];
+
+        let delete = |file: &std::path::Path| {
+            let params = FsDeleteParams {
+                path: Some(file.to_string_lossy().to_string()),
+                paths: None,
+                recursive: false,
+                use_trash: None,
+                dry_run: false,
+            };
+            FsDeleteTool::execute(&params, &config)
+        };
+
+        let result = delete(&library_file);
+        assert!(result.is_error.unwrap_or(false));
+        let text = match &result.content[0].raw {
+            rmcp::model::RawContent::Text(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+        assert!(text.contains("write access"), "{}", text);
+        assert!(library_file.exists());
+
+        let result = delete(&staging_file);
+        assert!(!result.is_error.unwrap_or(false));
+        assert!(!staging_file.exists());
+    }
+
+    #[test]
+    fn test_delete_is_audited() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = temp_dir.path().join("audit.jsonl");
+        let mut config = test_config();
+        config.audit.path = Some(log.clone());
+
+        let test_file = temp_dir.path().join("old.mp3");
+        fs::write(&test_file, "audio").unwrap();
+        let missing = temp_dir.path().join("missing.mp3");
+        let delete = |path: &std::path::Path| {
+            let params = FsDeleteParams {
+                path: Some(path.to_string_lossy().to_string()),
+                paths: None,
+                recursive: false,
+                use_trash: None,
+                dry_run: false,
+            };
+            FsDeleteTool::execute(&params, &config)
+        };
+
+        assert!(!delete(&test_file).is_error.unwrap_or(false));
+        assert!(delete(&missing).is_error.unwrap_or(false));
+
+        let entries: Vec<serde_json::Value> = fs::read_to_string(&log)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        let root = temp_dir.path().canonicalize().unwrap();
+        assert_eq!(entries[0]["tool"], "fs_delete");
+        assert_eq!(entries[0]["outcome"], "success");
+        assert_eq!(
+            entries[0]["paths"][0],
+            root.join("old.mp3").to_string_lossy().as_ref()
+        );
+        assert_eq!(entries[0]["params"]["dry_run"], false);
+
+        // The failed delete is recorded with its error
+        assert_eq!(entries[1]["outcome"], "error");
+        assert_eq!(
+            entries[1]["paths"][0],
+            root.join("missing.mp3").to_string_lossy().as_ref()
+        );
+        let error = entries[1]["error"].as_str().unwrap();
+        assert!(error.contains("does not exist"), "{}", error);
+    }
+
     #[test]
     fn test_delete_nonexistent_path() {
         let params = FsDeleteParams {
-            path: "/nonexistent/path/to/file.txt".to_string(),
+            path: Some("/nonexistent/path/to/file.txt".to_string()),
+            paths: None,
             recursive: false,
+            use_trash: None,
+            dry_run: false,
         };
 
         let config = test_config();
@@ -380,6 +980,201 @@ mod tests {
         assert!(text.contains("does not exist"));
     }
 
+    #[cfg(all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    ))]
+    #[test]
+    fn test_trashed_file() {
+        assert_eq!(
+            trashed_file(Path::new(
+                "/home/u/.local/share/Trash/info/cover.2.jpg.trashinfo"
+            )),
+            Some(std::path::PathBuf::from(
+                "/home/u/.local/share/Trash/files/cover.2.jpg"
+            ))
+        );
+        assert_eq!(trashed_file(Path::new("/tmp/cover.jpg")), None);
+    }
+
+    #[cfg(all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    ))]
+    #[test]
+    #[ignore] // Moves a file to the trash of the user running the tests
+    fn test_delete_to_trash() {
+        let temp_dir = TempDir::new_in(std::env::current_dir().unwrap()).unwrap();
+        let test_file = temp_dir.path().join("trash_me.txt");
+        fs::write(&test_file, "content").unwrap();
+
+        let mut config = test_config();
+        config.tools.fs_prefer_trash = true;
+        let params = FsDeleteParams {
+            path: Some(test_file.to_string_lossy().to_string()),
+            paths: None,
+            recursive: false,
+            use_trash: None,
+            dry_run: false,
+        };
+
+        let result = FsDeleteTool::execute(&params, &config);
+        assert_eq!(result.is_error, Some(false), "{:?}", result.content);
+        assert!(!test_file.exists());
+        let json = result.structured_content.unwrap();
+        assert_eq!(json["trashed"], true);
+        if let Some(location) = json["trash_location"].as_str() {
+            assert!(Path::new(location).exists());
+        }
+
+        // Empty the item from the trash again
+        let trashed: Vec<_> = trash::os_limited::list()
+            .unwrap()
+            .into_iter()
+            .filter(|item| item.name == "trash_me.txt")
+            .collect();
+        trash::os_limited::purge_all(trashed).unwrap();
+    }
+
+    #[test]
+    fn test_delete_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        let thumbs = temp_path.join("Thumbs.db");
+        let ds_store = temp_path.join(".DS_Store");
+        let album = temp_path.join("album");
+        fs::write(&thumbs, "x").unwrap();
+        fs::write(&ds_store, "x").unwrap();
+        fs::create_dir(&album).unwrap();
+        fs::write(album.join("01.flac"), "x").unwrap();
+
+        let path = |p: &std::path::Path| p.to_string_lossy().to_string();
+        let mut params = FsDeleteParams {
+            path: None,
+            paths: Some(vec![
+                path(&thumbs),
+                path(&temp_path.join("missing.jpg")),
+                path(&album),
+                path(&ds_store),
+            ]),
+            recursive: false,
+            use_trash: Some(false),
+            dry_run: false,
+        };
+
+        // A failing path does not stop the others
+        let config = test_config();
+        let result = FsDeleteTool::execute(&params, &config);
+        assert_eq!(result.is_error, Some(false));
+        let json = result.structured_content.unwrap();
+        assert_eq!(json["total_count"], 4);
+        assert_eq!(json["success_count"], 2);
+        assert_eq!(json["failure_count"], 2);
+        assert_eq!(json["results"][0]["success"], true);
+        assert_eq!(json["results"][0]["result"]["item_type"], "file");
+        assert!(
+            json["results"][1]["error"]
+                .as_str()
+                .unwrap()
+                .contains("does not exist")
+        );
+        assert!(
+            json["results"][2]["error"]
+                .as_str()
+                .unwrap()
+                .contains("not empty")
+        );
+        assert_eq!(json["results"][3]["path"], path(&ds_store));
+        assert!(!thumbs.exists() && !ds_store.exists() && album.exists());
+
+        // recursive applies to each path
+        params.paths = Some(vec![path(&album)]);
+        params.recursive = true;
+        let result = FsDeleteTool::execute(&params, &config);
+        let json = result.structured_content.unwrap();
+        assert_eq!(json["success_count"], 1);
+        assert_eq!(json["results"][0]["result"]["recursive"], true);
+        assert!(!album.exists());
+
+        params.path = Some(path(&album));
+        let result = FsDeleteTool::execute(&params, &config);
+        assert_eq!(result.is_error, Some(true));
+
+        params.path = None;
+        params.paths = None;
+        let result = FsDeleteTool::execute(&params, &config);
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_delete_dry_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let album = temp_dir.path().join("album");
+        fs::create_dir_all(album.join("scans")).unwrap();
+        fs::write(album.join("01.flac"), "12345").unwrap();
+        fs::write(album.join("scans/front.jpg"), "123").unwrap();
+
+        let mut params = FsDeleteParams {
+            path: Some(album.to_string_lossy().to_string()),
+            paths: None,
+            recursive: true,
+            use_trash: Some(false),
+            dry_run: true,
+        };
+        let mut config = test_config();
+        let result = FsDeleteTool::execute(&params, &config);
+        assert_eq!(result.is_error, Some(false));
+        let text = match &result.content[0].raw {
+            rmcp::model::RawContent::Text(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+        assert!(text.starts_with("DRY RUN"), "{}", text);
+        assert!(album.exists());
+
+        let json = result.structured_content.unwrap();
+        assert_eq!(json["deleted"], false);
+        assert_eq!(json["total_items"], 4);
+        assert_eq!(json["total_bytes"], 8);
+        let items = json["would_remove"].as_array().unwrap();
+        assert_eq!(items[0]["path"], album.to_string_lossy().as_ref());
+        assert_eq!(items[1]["size_bytes"], 5);
+        assert_eq!(items[3]["item_type"], "file");
+        assert!(json.get("truncated").is_none());
+        assert!(json.get("approximate").is_none());
+
+        // The listing is capped, the walk by the depth limit
+        config.tools.fs_max_entries = 2;
+        config.tools.fs_max_depth = 1;
+        let json = FsDeleteTool::execute(&params, &config)
+            .structured_content
+            .unwrap();
+        assert_eq!(json["would_remove"].as_array().unwrap().len(), 2);
+        assert_eq!(json["truncated"], true);
+        assert_eq!(json["approximate"], true);
+        assert_eq!(json["total_items"], 3);
+
+        // A dry run fails like the delete would
+        params.recursive = false;
+        let result = FsDeleteTool::execute(&params, &config);
+        assert_eq!(result.is_error, Some(true));
+
+        params.path = None;
+        params.paths = Some(vec![album.to_string_lossy().to_string()]);
+        params.recursive = true;
+        let result = FsDeleteTool::execute(&params, &config);
+        let text = match &result.content[0].raw {
+            rmcp::model::RawContent::Text(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+        assert!(text.starts_with("DRY RUN"), "{}", text);
+        assert!(album.exists());
+    }
+
     #[cfg(feature = "http")]
     #[test]
     fn test_delete_http_handler() {
@@ -421,8 +1216,11 @@ mod tests {
         fs::write(&test_file, "test").unwrap();
 
         let params = FsDeleteParams {
-            path: test_file.to_string_lossy().to_string(),
+            path: Some(test_file.to_string_lossy().to_string()),
+            paths: None,
             recursive: false,
+            use_trash: None,
+            dry_run: false,
         };
 
         let config = test_config();
@@ -437,7 +1235,7 @@ mod tests {
         let structured = result.structured_content.unwrap();
 
         // Verify fields
-        assert_eq!(structured["path"], params.path);
+        assert_eq!(structured["path"], params.path.unwrap());
         assert_eq!(structured["item_type"], "file");
         assert_eq!(structured["success"], true);
 
@@ -460,8 +1258,11 @@ mod tests {
         fs::write(test_dir.join("file.txt"), "content").unwrap();
 
         let params = FsDeleteParams {
-            path: test_dir.to_string_lossy().to_string(),
+            path: Some(test_dir.to_string_lossy().to_string()),
+            paths: None,
             recursive: true,
+            use_trash: None,
+            dry_run: false,
         };
 
         let config = test_config();
@@ -483,8 +1284,11 @@ mod tests {
         fs::write(&test_file, "data").unwrap();
 
         let params = FsDeleteParams {
-            path: test_file.to_string_lossy().to_string(),
+            path: Some(test_file.to_string_lossy().to_string()),
+            paths: None,
             recursive: false,
+            use_trash: None,
+            dry_run: false,
         };
 
         let config = test_config();
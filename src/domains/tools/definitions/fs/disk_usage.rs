@@ -0,0 +1,523 @@
+//! Disk usage tool definition.
+//!
+//! A tool that adds up the size of a directory tree, broken down by subdirectory.
+
+use futures::FutureExt;
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool, ToolAnnotations},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+use crate::domains::tools::definitions::mb::identify_directory::MbIdentifyDirectoryTool;
+
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_timeout;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the disk usage tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct FsDiskUsageParams {
+    /// Directory to measure.
+    pub path: String,
+
+    /// Levels of subdirectories to report sizes for (default: 1, e.g. one entry per artist
+    /// folder; 0 for the total only). Limited by MCP_FS_MAX_DEPTH.
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+
+    /// Count only audio files (by extension), e.g. to leave out covers and logs.
+    #[serde(default)]
+    pub audio_only: bool,
+}
+
+fn default_depth() -> usize {
+    1
+}
+
+// ============================================================================
+// Output Structure (JSON format for AI agents)
+// ============================================================================
+
+/// Size of a directory tree
+#[derive(Debug, Serialize, JsonSchema)]
+struct DiskUsageResult {
+    /// Directory measured
+    path: String,
+    /// Total size of the files counted, in bytes
+    total_bytes: u64,
+    /// Total size, human-readable (e.g. "1.5 GiB")
+    total_size: String,
+    /// Number of files counted
+    file_count: usize,
+    /// Number of directories below `path`
+    dir_count: usize,
+    /// Whether only audio files were counted
+    audio_only: bool,
+    /// Subdirectories down to the requested depth, largest first
+    directories: Vec<DirectoryUsage>,
+    /// Whether part of the tree was not counted (too deep, unreadable or a symlink loop)
+    incomplete: bool,
+    /// Warnings encountered while measuring
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
+}
+
+/// Size of a subdirectory, including everything below it
+#[derive(Debug, Serialize, JsonSchema)]
+struct DirectoryUsage {
+    /// Absolute path of the directory
+    path: String,
+    /// Level below the measured directory (1 for its direct subdirectories)
+    depth: usize,
+    /// Size of the files counted, in bytes
+    size_bytes: u64,
+    /// Size, human-readable (e.g. "1.5 GiB")
+    size: String,
+    /// Number of files counted
+    file_count: usize,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Disk usage tool - measures directory trees.
+pub struct FsDiskUsageTool;
+
+impl FsDiskUsageTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "fs_disk_usage";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Measure how much space a directory takes, like du: a grand total plus the size and file count of each subdirectory down to `depth` levels (default 1, e.g. per artist folder), largest first, in bytes and human-readable units. Use audio_only=true to count only audio files.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    #[instrument(skip_all, fields(path = %params.path, depth = %params.depth))]
+    pub fn execute(params: &FsDiskUsageParams, config: &Config) -> CallToolResult {
+        info!(
+            "Disk usage tool called: '{}' (depth {})",
+            params.path, params.depth
+        );
+
+        match Self::measure(params, config) {
+            Ok(result) => {
+                let mut summary = format!(
+                    "'{}': {} ({} bytes) in {} {}file(s) and {} director{}",
+                    params.path,
+                    result.total_size,
+                    result.total_bytes,
+                    result.file_count,
+                    if result.audio_only { "audio " } else { "" },
+                    result.dir_count,
+                    if result.dir_count == 1 { "y" } else { "ies" }
+                );
+                if let Some(largest) = result.directories.first() {
+                    summary.push_str(&format!("; largest: '{}' ({})", largest.path, largest.size));
+                }
+                if result.incomplete {
+                    summary.push_str("; incomplete, see warnings");
+                }
+
+                // Return with text summary + structured content
+                CallToolResult {
+                    content: vec![Content::text(summary)],
+                    structured_content: Some(serde_json::to_value(&result).unwrap()),
+                    is_error: Some(false),
+                    meta: None,
+                }
+            }
+            Err(e) => CallToolResult::error(vec![Content::text(e)]),
+        }
+    }
+
+    /// Validate the path and measure the tree below it.
+    fn measure(params: &FsDiskUsageParams, config: &Config) -> Result<DiskUsageResult, String> {
+        let path = validate_path(&params.path, config).map_err(|e| {
+            warn!("Path security validation failed: {}", e);
+            format!("Path security validation failed: {}", e)
+        })?;
+        if !path.is_dir() {
+            return Err(format!("Path is not a directory: {}", params.path));
+        }
+
+        let max_depth = config.tools.fs_max_depth;
+        let mut usage = Usage {
+            config,
+            audio_only: params.audio_only,
+            report_depth: params.depth.min(max_depth),
+            max_depth,
+            visited_inodes: HashSet::new(),
+            directories: Vec::new(),
+            dir_count: 0,
+            incomplete: false,
+            warnings: Vec::new(),
+        };
+        if params.depth > max_depth {
+            usage.warnings.push(format!(
+                "Depth limited to {} levels for safety (requested {}).",
+                max_depth, params.depth
+            ));
+        }
+        #[cfg(unix)]
+        if let Ok(metadata) = fs::metadata(&path) {
+            use std::os::unix::fs::MetadataExt;
+            usage.visited_inodes.insert(metadata.ino());
+        }
+
+        let (total_bytes, file_count) = usage.walk(&path, 0);
+        let mut directories = usage.directories;
+        directories.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.path.cmp(&b.path)));
+
+        info!(
+            "Measured '{}': {} bytes in {} file(s), {} directories",
+            params.path, total_bytes, file_count, usage.dir_count
+        );
+        Ok(DiskUsageResult {
+            path: params.path.clone(),
+            total_bytes,
+            total_size: format_size(total_bytes),
+            file_count,
+            dir_count: usage.dir_count,
+            audio_only: params.audio_only,
+            directories,
+            incomplete: usage.incomplete,
+            warnings: usage.warnings,
+        })
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: FsDiskUsageParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Disk usage tool (HTTP) called: '{}'", params.path);
+
+        let result = Self::execute(&params, &config);
+
+        // Serialize the full CallToolResult to preserve all fields including structuredContent
+        serde_json::to_value(&result).map_err(|e| e.to_string())
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<FsDiskUsageParams>(),
+            annotations: Some(ToolAnnotations::new().read_only(true)),
+            output_schema: Some(schema_for_type::<DiskUsageResult>()),
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: FsDiskUsageParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// State of a measurement: the whole tree is added up, at most `max_depth` levels deep,
+/// and directories down to `report_depth` are recorded.
+struct Usage<'a> {
+    config: &'a Config,
+    audio_only: bool,
+    report_depth: usize,
+    max_depth: usize,
+    visited_inodes: HashSet<u64>,
+    directories: Vec<DirectoryUsage>,
+    dir_count: usize,
+    incomplete: bool,
+    warnings: Vec<String>,
+}
+
+impl Usage<'_> {
+    /// Add up the files below `dir`, returning their total size and count.
+    ///
+    /// Symlinks are followed when their target is within the allowed root, like in
+    /// fs_list_dir, and a directory already visited is skipped as a symlink loop.
+    fn walk(&mut self, dir: &Path, depth: usize) -> (u64, usize) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to read directory {:?}: {}", dir, e);
+                self.skipped(format!(
+                    "Could not read directory '{}': {}",
+                    dir.display(),
+                    e
+                ));
+                return (0, 0);
+            }
+        };
+
+        let mut bytes = 0;
+        let mut files = 0;
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            // A symlink may point anywhere, so validate it like fs_list_dir does
+            if file_type.is_symlink()
+                && let Err(e) = validate_path(&path.to_string_lossy(), self.config)
+            {
+                warn!("Path validation failed for {:?}: {}", path, e);
+                self.warnings.push(format!(
+                    "Skipped '{}': security validation failed",
+                    path.display()
+                ));
+                continue;
+            }
+            let metadata = match fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    self.skipped(format!(
+                        "Could not read metadata for '{}': {}",
+                        path.display(),
+                        e
+                    ));
+                    continue;
+                }
+            };
+
+            if !metadata.is_dir() {
+                if !self.audio_only || MbIdentifyDirectoryTool::is_audio_file(&path) {
+                    bytes += metadata.len();
+                    files += 1;
+                }
+                continue;
+            }
+
+            // Check for symlink loops using inodes (Unix-like systems)
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::MetadataExt;
+                if !self.visited_inodes.insert(metadata.ino()) {
+                    self.skipped(format!(
+                        "Skipped '{}': symlink loop detected",
+                        path.display()
+                    ));
+                    continue;
+                }
+            }
+
+            self.dir_count += 1;
+            if depth + 1 > self.max_depth {
+                self.skipped(format!(
+                    "Skipped the contents of '{}': deeper than {} levels",
+                    path.display(),
+                    self.max_depth
+                ));
+                continue;
+            }
+            let (dir_bytes, dir_files) = self.walk(&path, depth + 1);
+            bytes += dir_bytes;
+            files += dir_files;
+            if depth < self.report_depth {
+                self.directories.push(DirectoryUsage {
+                    path: path.to_string_lossy().into_owned(),
+                    depth: depth + 1,
+                    size_bytes: dir_bytes,
+                    size: format_size(dir_bytes),
+                    file_count: dir_files,
+                });
+            }
+        }
+        (bytes, files)
+    }
+
+    /// Record that part of the tree was not counted.
+    fn skipped(&mut self, warning: String) {
+        self.incomplete = true;
+        self.warnings.push(warning);
+    }
+}
+
+/// Format a size in binary units, e.g. "512 B", "1.5 KiB" or "3.2 GiB".
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    fn measure(path: &Path, depth: usize, audio_only: bool) -> DiskUsageResult {
+        let params = FsDiskUsageParams {
+            path: path.to_string_lossy().to_string(),
+            depth,
+            audio_only,
+        };
+        FsDiskUsageTool::measure(&params, &test_config()).unwrap()
+    }
+
+    /// Library with two artists: A (one album) and B (two albums).
+    fn library() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("A/Album")).unwrap();
+        fs::create_dir_all(root.join("B/One")).unwrap();
+        fs::create_dir_all(root.join("B/Two")).unwrap();
+        fs::write(root.join("A/Album/01.flac"), "12345").unwrap();
+        fs::write(root.join("A/Album/cover.jpg"), "123").unwrap();
+        fs::write(root.join("B/One/01.mp3"), "1234567890").unwrap();
+        fs::write(root.join("B/Two/01.mp3"), "12").unwrap();
+        fs::write(root.join("notes.txt"), "1").unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_disk_usage_by_artist() {
+        let temp_dir = library();
+        let root = temp_dir.path();
+
+        let result = measure(root, 1, false);
+        assert_eq!(result.total_bytes, 21);
+        assert_eq!(result.total_size, "21 B");
+        assert_eq!(result.file_count, 5);
+        assert_eq!(result.dir_count, 5);
+        assert!(!result.incomplete);
+        let directories: Vec<_> = result
+            .directories
+            .iter()
+            .map(|d| (d.path.clone(), d.size_bytes, d.file_count))
+            .collect();
+        let path = |name: &str| root.join(name).to_string_lossy().to_string();
+        assert_eq!(directories, vec![(path("B"), 12, 2), (path("A"), 8, 2)]);
+
+        let result = measure(root, 2, false);
+        assert_eq!(result.directories.len(), 5);
+        assert_eq!(result.directories[0].path, path("B"));
+        assert_eq!(result.directories[1].path, path("B/One"));
+        assert_eq!(result.directories[1].depth, 2);
+
+        let result = measure(root, 0, true);
+        assert_eq!(result.total_bytes, 17);
+        assert_eq!(result.file_count, 3);
+        assert!(result.directories.is_empty());
+    }
+
+    #[test]
+    fn test_disk_usage_depth_limit() {
+        let temp_dir = library();
+        let mut config = test_config();
+        config.tools.fs_max_depth = 1;
+
+        let params = FsDiskUsageParams {
+            path: temp_dir.path().to_string_lossy().to_string(),
+            depth: 3,
+            audio_only: false,
+        };
+        let result = FsDiskUsageTool::measure(&params, &config).unwrap();
+        // Album folders are counted but not entered
+        assert_eq!(result.total_bytes, 1);
+        assert_eq!(result.dir_count, 5);
+        assert!(result.incomplete);
+        assert_eq!(result.directories.len(), 2);
+        assert!(
+            result
+                .warnings
+                .iter()
+                .any(|w| w.starts_with("Depth limited to 1 levels")),
+            "{:?}",
+            result.warnings
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_disk_usage_symlink_loop() {
+        let temp_dir = library();
+        let root = temp_dir.path();
+        std::os::unix::fs::symlink(root, root.join("A/Album/loop")).unwrap();
+
+        let result = measure(root, 1, false);
+        assert_eq!(result.total_bytes, 21);
+        assert!(result.incomplete);
+        assert!(
+            result.warnings[0].contains("symlink loop detected"),
+            "{:?}",
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn test_disk_usage_not_a_directory() {
+        let temp_dir = library();
+        let params = FsDiskUsageParams {
+            path: temp_dir
+                .path()
+                .join("notes.txt")
+                .to_string_lossy()
+                .to_string(),
+            depth: 1,
+            audio_only: false,
+        };
+        let err = FsDiskUsageTool::measure(&params, &test_config()).unwrap_err();
+        assert!(err.starts_with("Path is not a directory"), "{}", err);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+}
@@ -0,0 +1,476 @@
+//! Empty directories tool definition.
+//!
+//! A tool that finds the directories of a tree holding no files, and optionally removes
+//! them.
+
+use futures::FutureExt;
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool, ToolAnnotations},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::audit;
+use crate::core::config::Config;
+use crate::core::glob::GlobPattern;
+use crate::core::security::{AccessKind, validate_path_for};
+
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_audited_timeout;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the empty directories tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FsEmptyDirsParams {
+    /// Directory to scan; it is never removed itself.
+    pub path: String,
+
+    /// Remove the empty directories, deepest first (default: false, only report them).
+    #[serde(default)]
+    pub remove: bool,
+
+    /// Count directories holding only junk files (MCP_FS_JUNK_FILES, by default
+    /// .DS_Store, Thumbs.db and desktop.ini) as empty; removing them deletes those files.
+    #[serde(default = "default_ignore_junk")]
+    pub ignore_junk: bool,
+}
+
+fn default_ignore_junk() -> bool {
+    true
+}
+
+// ============================================================================
+// Output Structure (JSON format for AI agents)
+// ============================================================================
+
+/// Result of a scan for empty directories
+#[derive(Debug, Serialize, JsonSchema)]
+struct EmptyDirsResult {
+    /// Directory scanned
+    path: String,
+    /// False unless remove was requested: nothing was deleted
+    applied: bool,
+    /// Number of empty directories found
+    found_count: usize,
+    /// Number of directories removed
+    removed_count: usize,
+    /// Empty directories, deepest first: each one after the directories inside it
+    directories: Vec<EmptyDir>,
+    /// Directories that could not be removed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failures: Vec<RemovalFailure>,
+    /// Parts of the tree that were not scanned
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
+}
+
+/// A directory holding no files, other than junk files and empty directories
+#[derive(Debug, Serialize, JsonSchema)]
+struct EmptyDir {
+    /// Absolute path of the directory
+    path: String,
+    /// Names of the junk files in it, deleted along with it
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    junk_files: Vec<String>,
+    /// Whether the directory was removed
+    removed: bool,
+}
+
+/// A directory that could not be removed.
+#[derive(Debug, Serialize, JsonSchema)]
+struct RemovalFailure {
+    /// Absolute path of the directory
+    path: String,
+    /// Why it was not removed
+    error: String,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Empty directories tool - finds and removes empty directories.
+pub struct FsEmptyDirsTool;
+
+impl FsEmptyDirsTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "fs_empty_dirs";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Find the directories of a tree that hold no files, e.g. artist and album folders left behind after moving tracks. A directory holding only empty directories, or only junk files like .DS_Store and Thumbs.db (ignore_junk, default true), is empty too. Reports them by default; with remove=true, removes them deepest first so nested empty folders collapse. The scanned directory itself is never removed.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
+    #[instrument(skip_all, fields(path = %params.path, remove = params.remove))]
+    pub fn execute(params: &FsEmptyDirsParams, config: &Config) -> CallToolResult {
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
+
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &FsEmptyDirsParams) -> Vec<&str> {
+        vec![params.path.as_str()]
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &FsEmptyDirsParams, config: &Config) -> CallToolResult {
+        info!(
+            "Empty directories tool called: '{}' (remove: {})",
+            params.path, params.remove
+        );
+
+        match Self::scan(params, config) {
+            Ok(result) => {
+                let mut summary = format!(
+                    "Found {} empty director{} in '{}'",
+                    result.found_count,
+                    if result.found_count == 1 { "y" } else { "ies" },
+                    params.path
+                );
+                if result.applied {
+                    summary.push_str(&format!("; removed {}", result.removed_count));
+                    if !result.failures.is_empty() {
+                        summary.push_str(&format!(", {} failed", result.failures.len()));
+                    }
+                } else if result.found_count > 0 {
+                    summary.push_str("; nothing was removed (use remove: true)");
+                }
+                if !result.warnings.is_empty() {
+                    summary.push_str("; incomplete, see warnings");
+                }
+
+                // Return with text summary + structured content
+                CallToolResult {
+                    content: vec![Content::text(summary)],
+                    structured_content: Some(serde_json::to_value(&result).unwrap()),
+                    is_error: Some(false),
+                    meta: None,
+                }
+            }
+            Err(e) => CallToolResult::error(vec![Content::text(e)]),
+        }
+    }
+
+    /// Validate the path, find the empty directories below it and remove them if asked.
+    fn scan(params: &FsEmptyDirsParams, config: &Config) -> Result<EmptyDirsResult, String> {
+        // A report only reads the tree; removing needs write access
+        let access = if params.remove {
+            AccessKind::Write
+        } else {
+            AccessKind::Read
+        };
+        let path = validate_path_for(&params.path, access, config).map_err(|e| {
+            warn!("Path security validation failed: {}", e);
+            format!("Path security validation failed: {}", e)
+        })?;
+        if !path.is_dir() {
+            return Err(format!("Path is not a directory: {}", params.path));
+        }
+
+        let junk = if params.ignore_junk {
+            config
+                .tools
+                .fs_junk_files
+                .iter()
+                .map(|pattern| GlobPattern::parse(pattern))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            Vec::new()
+        };
+        let mut scan = Scan {
+            junk,
+            max_depth: config.tools.fs_max_depth,
+            found: Vec::new(),
+            warnings: Vec::new(),
+        };
+        scan.walk(&path, 0);
+
+        let mut directories = Vec::with_capacity(scan.found.len());
+        let mut failures = Vec::new();
+        for (dir, junk_files) in scan.found {
+            let removed = params.remove
+                && match Self::remove(&dir, &junk_files) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        warn!("Failed to remove {:?}: {}", dir, e);
+                        failures.push(RemovalFailure {
+                            path: dir.to_string_lossy().into_owned(),
+                            error: e.to_string(),
+                        });
+                        false
+                    }
+                };
+            directories.push(EmptyDir {
+                path: dir.to_string_lossy().into_owned(),
+                junk_files,
+                removed,
+            });
+        }
+        let removed_count = directories.iter().filter(|d| d.removed).count();
+
+        info!(
+            "Found {} empty directories in '{}', removed {}",
+            directories.len(),
+            params.path,
+            removed_count
+        );
+        Ok(EmptyDirsResult {
+            path: params.path.clone(),
+            applied: params.remove,
+            found_count: directories.len(),
+            removed_count,
+            directories,
+            failures,
+            warnings: scan.warnings,
+        })
+    }
+
+    /// Delete the junk files of an empty directory, then the directory.
+    ///
+    /// The directory is removed with `remove_dir`, which fails rather than deleting
+    /// anything added to it since the scan.
+    fn remove(dir: &Path, junk_files: &[String]) -> std::io::Result<()> {
+        for name in junk_files {
+            fs::remove_file(dir.join(name))?;
+        }
+        fs::remove_dir(dir)
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: FsEmptyDirsParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Empty directories tool (HTTP) called: '{}'", params.path);
+
+        let result = Self::execute(&params, &config);
+
+        // Serialize the full CallToolResult to preserve all fields including structuredContent
+        serde_json::to_value(&result).map_err(|e| e.to_string())
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<FsEmptyDirsParams>(),
+            annotations: Some(ToolAnnotations::new().read_only(false).destructive(true)),
+            output_schema: Some(schema_for_type::<EmptyDirsResult>()),
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: FsEmptyDirsParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// State of a scan: the tree is walked at most `max_depth` levels deep, and empty
+/// directories are recorded after the directories inside them.
+struct Scan {
+    junk: Vec<GlobPattern>,
+    max_depth: usize,
+    found: Vec<(PathBuf, Vec<String>)>,
+    warnings: Vec<String>,
+}
+
+impl Scan {
+    /// Walk the subdirectories of `dir`, returning whether `dir` holds nothing but junk
+    /// files and empty directories.
+    ///
+    /// Symlinks are never followed: a symlink counts as content, so a directory holding
+    /// one is not empty. A directory that cannot be read, or is too deep to be scanned,
+    /// is not empty either.
+    fn walk(&mut self, dir: &Path, depth: usize) -> bool {
+        let mut entries: Vec<_> = match fs::read_dir(dir) {
+            Ok(entries) => entries.filter_map(Result::ok).collect(),
+            Err(e) => {
+                warn!("Failed to read directory {:?}: {}", dir, e);
+                self.warnings.push(format!(
+                    "Could not read directory '{}': {}",
+                    dir.display(),
+                    e
+                ));
+                return false;
+            }
+        };
+        entries.sort_by_key(|entry| entry.file_name());
+
+        let mut empty = true;
+        let mut junk_files = Vec::new();
+        for entry in entries {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                empty = false;
+                continue;
+            };
+
+            if file_type.is_dir() {
+                if depth + 1 > self.max_depth {
+                    self.warnings.push(format!(
+                        "Skipped the contents of '{}': deeper than {} levels",
+                        path.display(),
+                        self.max_depth
+                    ));
+                    empty = false;
+                } else if !self.walk(&path, depth + 1) {
+                    empty = false;
+                }
+            } else if file_type.is_file() && self.is_junk(&entry.file_name()) {
+                junk_files.push(entry.file_name().to_string_lossy().into_owned());
+            } else {
+                empty = false;
+            }
+        }
+
+        // The scanned directory itself is never reported
+        if empty && depth > 0 {
+            self.found.push((dir.to_path_buf(), junk_files));
+        }
+        empty
+    }
+
+    /// Whether a file name matches one of the junk file patterns.
+    fn is_junk(&self, name: &std::ffi::OsStr) -> bool {
+        let name = Path::new(name);
+        self.junk.iter().any(|pattern| pattern.matches(name))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    fn params(path: &Path, remove: bool, ignore_junk: bool) -> FsEmptyDirsParams {
+        FsEmptyDirsParams {
+            path: path.to_string_lossy().to_string(),
+            remove,
+            ignore_junk,
+        }
+    }
+
+    /// Library after a reorganization: an empty artist folder, an old album folder with
+    /// only Thumbs.db left in its disc folder, and a kept album with an empty subfolder.
+    fn library() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("Artist/Album")).unwrap();
+        fs::create_dir_all(root.join("Old/CD1")).unwrap();
+        fs::create_dir_all(root.join("Kept/Scans")).unwrap();
+        fs::write(root.join("Old/CD1/Thumbs.db"), "junk").unwrap();
+        fs::write(root.join("Kept/01.flac"), "audio").unwrap();
+        temp_dir
+    }
+
+    fn relative_paths(result: &EmptyDirsResult, root: &Path) -> Vec<String> {
+        result
+            .directories
+            .iter()
+            .map(|d| {
+                Path::new(&d.path)
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_dirs_report() {
+        let temp_dir = library();
+        let root = temp_dir.path().canonicalize().unwrap();
+
+        let result = FsEmptyDirsTool::scan(&params(&root, false, true), &test_config()).unwrap();
+        assert!(!result.applied);
+        assert_eq!(
+            relative_paths(&result, &root),
+            vec!["Artist/Album", "Artist", "Kept/Scans", "Old/CD1", "Old"]
+        );
+        assert_eq!(result.directories[3].junk_files, vec!["Thumbs.db"]);
+        assert_eq!(result.removed_count, 0);
+        assert!(root.join("Old/CD1/Thumbs.db").exists());
+
+        // Without ignore_junk, Thumbs.db keeps its folders
+        let result = FsEmptyDirsTool::scan(&params(&root, false, false), &test_config()).unwrap();
+        assert_eq!(
+            relative_paths(&result, &root),
+            vec!["Artist/Album", "Artist", "Kept/Scans"]
+        );
+    }
+
+    #[test]
+    fn test_empty_dirs_remove() {
+        let temp_dir = library();
+        let root = temp_dir.path().canonicalize().unwrap();
+
+        let result = FsEmptyDirsTool::scan(&params(&root, true, true), &test_config()).unwrap();
+        assert!(result.applied);
+        assert_eq!(result.found_count, 5);
+        assert_eq!(result.removed_count, 5);
+        assert!(result.failures.is_empty());
+        assert!(!root.join("Artist").exists());
+        assert!(!root.join("Old").exists());
+        assert!(!root.join("Kept/Scans").exists());
+        assert!(root.join("Kept/01.flac").exists());
+
+        // Nothing left to remove, and the scanned directory itself stays
+        let result =
+            FsEmptyDirsTool::scan(&params(&root.join("Kept"), true, true), &test_config()).unwrap();
+        assert_eq!(result.found_count, 0);
+        assert!(root.join("Kept").exists());
+    }
+}
@@ -0,0 +1,65 @@
+//! Exclude lists shared by the filesystem tools.
+
+use std::path::Path;
+
+use crate::core::config::Config;
+use crate::core::glob::GlobPattern;
+
+/// Exclude patterns of a call: the server's defaults plus those given in the call.
+pub struct ExcludePatterns {
+    patterns: Vec<GlobPattern>,
+}
+
+impl ExcludePatterns {
+    /// Parse the configured and requested patterns, or return `None` when there are none.
+    pub fn new(config: &Config, exclude: Option<&[String]>) -> Result<Option<Self>, String> {
+        let patterns = config
+            .tools
+            .fs_exclude
+            .iter()
+            .chain(exclude.into_iter().flatten())
+            .map(|pattern| GlobPattern::parse(pattern))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((!patterns.is_empty()).then_some(Self { patterns }))
+    }
+
+    /// Whether an entry, given by its path relative to the root, is excluded: a pattern
+    /// matches either its name or its relative path.
+    pub fn excludes(&self, relative: &Path) -> bool {
+        let name = relative.file_name().map(Path::new);
+        self.patterns.iter().any(|pattern| {
+            pattern.matches(relative) || name.is_some_and(|name| pattern.matches(name))
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclude_patterns() {
+        let mut config = Config::default();
+        assert!(ExcludePatterns::new(&config, None).unwrap().is_none());
+
+        config.tools.fs_exclude = vec!["@eaDir".to_string()];
+        let exclude = ["node_modules".to_string(), "Artist/*/scans".to_string()];
+        let patterns = ExcludePatterns::new(&config, Some(&exclude))
+            .unwrap()
+            .unwrap();
+        // Names match at any depth, paths from the root
+        assert!(patterns.excludes(Path::new("@eaDir")));
+        assert!(patterns.excludes(Path::new("Artist/Album/@EADIR")));
+        assert!(patterns.excludes(Path::new("site/node_modules")));
+        assert!(patterns.excludes(Path::new("Artist/Album/scans")));
+        assert!(!patterns.excludes(Path::new("Other/Artist/Album/scans")));
+        assert!(!patterns.excludes(Path::new("Artist/Album")));
+
+        let exclude = ["*.{flac".to_string()];
+        assert!(ExcludePatterns::new(&config, Some(&exclude)).is_err());
+    }
+}
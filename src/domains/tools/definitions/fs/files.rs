@@ -0,0 +1,85 @@
+//! File writing shared by the tools that save images and backups.
+
+use std::io::Write;
+use std::path::Path;
+
+/// Check whether `file_path` already holds exactly `bytes`.
+///
+/// The file size is compared first so changed content is usually detected
+/// without reading the existing file.
+pub fn is_identical_file(file_path: &Path, bytes: &[u8]) -> bool {
+    match std::fs::metadata(file_path) {
+        Ok(metadata) if metadata.is_file() && metadata.len() == bytes.len() as u64 => {
+            std::fs::read(file_path).is_ok_and(|existing| existing == bytes)
+        }
+        _ => false,
+    }
+}
+
+/// Write `bytes` to `file_path` through a hidden `.{name}.part` temp file
+/// in the same directory, renaming it into place once fully written.
+///
+/// The temp file is removed if any step fails, so an interrupted write
+/// never leaves a truncated file behind.
+pub fn write_atomic(file_path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let file_name = file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp_path = file_path.with_file_name(format!(".{}.part", file_name));
+
+    let result = (|| {
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, file_path)
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_atomic_leaves_no_temp_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("cover.jpg");
+        std::fs::write(&file_path, b"old").unwrap();
+
+        write_atomic(&file_path, b"new image data").unwrap();
+
+        assert_eq!(std::fs::read(&file_path).unwrap(), b"new image data");
+        assert!(!temp_dir.path().join(".cover.jpg.part").exists());
+    }
+
+    #[test]
+    fn test_write_atomic_cleans_up_on_error() {
+        let temp_dir = TempDir::new().unwrap();
+        // Renaming a file onto an existing directory fails
+        let file_path = temp_dir.path().join("cover.jpg");
+        std::fs::create_dir(&file_path).unwrap();
+        std::fs::write(file_path.join("keep"), b"x").unwrap();
+
+        assert!(write_atomic(&file_path, b"data").is_err());
+        assert!(!temp_dir.path().join(".cover.jpg.part").exists());
+    }
+
+    #[test]
+    fn test_is_identical_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("cover.jpg");
+        assert!(!is_identical_file(&file_path, b"image"));
+
+        std::fs::write(&file_path, b"image").unwrap();
+        assert!(is_identical_file(&file_path, b"image"));
+        assert!(!is_identical_file(&file_path, b"imagf"));
+        assert!(!is_identical_file(&file_path, b"image data"));
+    }
+}
@@ -0,0 +1,646 @@
+//! Find tool definition.
+//!
+//! A tool that searches a directory tree for files and directories matching a glob
+//! pattern and filters, returning a flat, paginated list of paths.
+
+use futures::FutureExt;
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool, ToolAnnotations},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tracing::{info, instrument, warn};
+
+use crate::core::config::Config;
+use crate::core::glob::GlobPattern;
+use crate::core::security::validate_path;
+use crate::core::time::{format_time, parse_time};
+
+use crate::domains::tools::blocking::run_blocking;
+use super::exclude::ExcludePatterns;
+use crate::domains::tools::timeout::with_timeout;
+
+/// Number of results returned per call when `max_results` is not given.
+const DEFAULT_MAX_RESULTS: usize = 100;
+
+/// Upper bound for `max_results`.
+const MAX_RESULTS_LIMIT: usize = 1000;
+
+/// Maximum number of directory levels searched below the root.
+const MAX_FIND_DEPTH: usize = 32;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the find tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct FsFindParams {
+    /// Directory to search.
+    pub root: String,
+
+    /// Glob pattern matched against the path relative to root, case-insensitively:
+    /// `*` and `?` within a name, `**` for any number of directories, `{a,b}` for
+    /// alternatives. E.g. "**/*.flac", "*/*/cover.{jpg,png}". Default: everything.
+    #[serde(default)]
+    pub glob: Option<String>,
+
+    /// Only match names containing this text (case-insensitive).
+    #[serde(default)]
+    pub name_contains: Option<String>,
+
+    /// Only match files of at least this many bytes.
+    #[serde(default)]
+    pub min_size: Option<u64>,
+
+    /// Only match files of at most this many bytes.
+    #[serde(default)]
+    pub max_size: Option<u64>,
+
+    /// Only match items modified after this time (RFC 3339, or a date like "2024-01-31").
+    #[serde(default)]
+    pub modified_after: Option<String>,
+
+    /// Only match files ("file") or directories ("dir").
+    #[serde(default, rename = "type")]
+    pub item_type: Option<FindType>,
+
+    /// Include hidden files and directories (starting with '.').
+    #[serde(default)]
+    pub include_hidden: bool,
+
+    /// Glob patterns of entries to leave out, matched against names and paths relative
+    /// to root (e.g. ["@eaDir", "node_modules"]). Excluded directories are not searched.
+    /// Added to the server's default exclude list.
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+
+    /// Maximum number of results returned (default: 100, max: 1000).
+    #[serde(default)]
+    pub max_results: Option<usize>,
+
+    /// Token from a previous call, to get the next page of results.
+    #[serde(default)]
+    pub continuation_token: Option<String>,
+}
+
+/// Type of item to find.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FindType {
+    /// Regular files
+    File,
+    /// Directories
+    #[serde(alias = "directory")]
+    Dir,
+}
+
+// ============================================================================
+// Output Structure (JSON format for AI agents)
+// ============================================================================
+
+/// Result of a find operation
+#[derive(Debug, Serialize, JsonSchema)]
+struct FindResult {
+    /// Directory that was searched
+    root: String,
+    /// Matching items, in path order
+    matches: Vec<FoundItem>,
+    /// Number of matches returned
+    match_count: usize,
+    /// Whether more matches are available with `continuation_token`
+    truncated: bool,
+    /// Entries left out by exclude patterns in the part of the tree searched by this call
+    /// (only when excluding)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    excluded_count: Option<usize>,
+    /// Token to pass to the next call for the next page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    continuation_token: Option<String>,
+    /// Warnings encountered during the search
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
+}
+
+/// A matching file or directory.
+#[derive(Debug, Serialize, JsonSchema)]
+struct FoundItem {
+    /// Absolute path
+    path: String,
+    /// Type of item ("file" or "directory")
+    item_type: String,
+    /// Size in bytes (files only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size_bytes: Option<u64>,
+    /// Last modification time, RFC 3339
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modified: Option<String>,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Find tool - searches a directory tree with a glob pattern and filters.
+pub struct FsFindTool;
+
+impl FsFindTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "fs_find";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Search a directory tree for files or directories matching a glob pattern (e.g. \"**/*.flac\") and optional filters: name_contains, min_size/max_size, modified_after and type. Returns a flat list of absolute paths with size and modification time. Results are paginated: pass continuation_token from the previous call to get the next page.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    #[instrument(skip_all, fields(root = %params.root))]
+    pub fn execute(params: &FsFindParams, config: &Config) -> CallToolResult {
+        info!(
+            "Find tool called in '{}' with glob {:?}",
+            params.root, params.glob
+        );
+
+        match Self::find(params, config) {
+            Ok(result) => {
+                let mut summary = format!(
+                    "Found {} match(es) in '{}'",
+                    result.match_count, params.root
+                );
+                if let Some(excluded) = result.excluded_count
+                    && excluded > 0
+                {
+                    summary.push_str(&format!("; {} excluded", excluded));
+                }
+                if result.truncated {
+                    summary.push_str("; more available with continuation_token");
+                }
+                if !result.warnings.is_empty() {
+                    summary.push_str(&format!(" ({} warnings)", result.warnings.len()));
+                }
+
+                // Return with text summary + structured content (the paths are in the JSON)
+                CallToolResult {
+                    content: vec![Content::text(summary)],
+                    structured_content: Some(serde_json::to_value(&result).unwrap()),
+                    is_error: Some(false),
+                    meta: None,
+                }
+            }
+            Err(e) => CallToolResult::error(vec![Content::text(e)]),
+        }
+    }
+
+    /// Validate the parameters and search the tree.
+    fn find(params: &FsFindParams, config: &Config) -> Result<FindResult, String> {
+        let root = validate_path(&params.root, config).map_err(|e| {
+            warn!("Path security validation failed: {}", e);
+            format!("Path security validation failed: {}", e)
+        })?;
+        if !root.is_dir() {
+            return Err(format!("Path is not a directory: {}", params.root));
+        }
+
+        if let (Some(min), Some(max)) = (params.min_size, params.max_size)
+            && min > max
+        {
+            return Err(format!(
+                "min_size ({}) is larger than max_size ({})",
+                min, max
+            ));
+        }
+        let modified_after = params
+            .modified_after
+            .as_deref()
+            .map(|value| parse_time("modified_after", value))
+            .transpose()?;
+        let patterns = match &params.glob {
+            Some(glob) => Some(GlobPattern::parse(glob)?),
+            None => None,
+        };
+        let exclude = ExcludePatterns::new(config, params.exclude.as_deref())?;
+
+        let mut finder = Finder {
+            params,
+            config,
+            patterns,
+            exclude,
+            modified_after,
+            name_contains: params.name_contains.as_ref().map(|s| s.to_lowercase()),
+            after: params.continuation_token.as_ref().map(PathBuf::from),
+            max_results: params
+                .max_results
+                .unwrap_or(DEFAULT_MAX_RESULTS)
+                .clamp(1, MAX_RESULTS_LIMIT),
+            matches: Vec::new(),
+            last: None,
+            truncated: false,
+            excluded: 0,
+            warnings: Vec::new(),
+        };
+        finder.walk(&root, Path::new(""), 0);
+
+        let continuation_token = finder
+            .truncated
+            .then(|| finder.last.map(|p| p.to_string_lossy().into_owned()))
+            .flatten();
+        info!(
+            "Found {} match(es) in '{}' (truncated: {})",
+            finder.matches.len(),
+            params.root,
+            finder.truncated
+        );
+        Ok(FindResult {
+            root: params.root.clone(),
+            match_count: finder.matches.len(),
+            matches: finder.matches,
+            truncated: finder.truncated,
+            excluded_count: finder.exclude.is_some().then_some(finder.excluded),
+            continuation_token,
+            warnings: finder.warnings,
+        })
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: FsFindParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Find tool (HTTP) called in '{}'", params.root);
+
+        let result = Self::execute(&params, &config);
+
+        // Serialize the full CallToolResult to preserve all fields including structuredContent
+        serde_json::to_value(&result).map_err(|e| e.to_string())
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<FsFindParams>(),
+            annotations: Some(ToolAnnotations::new().read_only(true)),
+            output_schema: Some(schema_for_type::<FindResult>()),
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: FsFindParams = serde_json::from_value(serde_json::Value::Object(args))
+                    .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// State of a search.
+struct Finder<'a> {
+    params: &'a FsFindParams,
+    config: &'a Config,
+    patterns: Option<GlobPattern>,
+    exclude: Option<ExcludePatterns>,
+    modified_after: Option<SystemTime>,
+    name_contains: Option<String>,
+    /// Relative path of the last match of the previous page
+    after: Option<PathBuf>,
+    max_results: usize,
+    matches: Vec<FoundItem>,
+    /// Relative path of the last match returned
+    last: Option<PathBuf>,
+    truncated: bool,
+    /// Number of entries left out by exclude patterns
+    excluded: usize,
+    warnings: Vec<String>,
+}
+
+impl Finder<'_> {
+    /// Search a directory depth-first in name order, so that relative paths come in
+    /// [`Path`] order and a page can resume after the last path of the previous one.
+    fn walk(&mut self, dir: &Path, relative: &Path, depth: usize) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to read directory {:?}: {}", dir, e);
+                self.warnings.push(format!(
+                    "Could not read directory '{}': {}",
+                    dir.display(),
+                    e
+                ));
+                return;
+            }
+        };
+        let mut entries: Vec<_> = entries.filter_map(|entry| entry.ok()).collect();
+        entries.sort_by_key(|e| e.file_name());
+
+        for entry in entries {
+            if self.truncated {
+                return;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !self.params.include_hidden && name.starts_with('.') {
+                continue;
+            }
+            let path = entry.path();
+            let relative = relative.join(&name);
+
+            // Everything before the token, except the directories leading to it, was
+            // returned by earlier pages
+            if let Some(after) = &self.after
+                && relative <= *after
+                && !after.starts_with(&relative)
+            {
+                continue;
+            }
+
+            // Excluded entries are neither matched nor searched
+            if self
+                .exclude
+                .as_ref()
+                .is_some_and(|exclude| exclude.excludes(&relative))
+            {
+                self.excluded += 1;
+                continue;
+            }
+
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            // Symlinks are matched by what they point to, if it is allowed
+            let metadata = if file_type.is_symlink() {
+                if let Err(e) = validate_path(&path.to_string_lossy(), self.config) {
+                    warn!("Path validation failed for {:?}: {}", path, e);
+                    self.warnings.push(format!(
+                        "Skipped '{}': security validation failed",
+                        path.display()
+                    ));
+                    continue;
+                }
+                fs::metadata(&path)
+            } else {
+                entry.metadata()
+            };
+            let metadata = match metadata {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    self.warnings.push(format!(
+                        "Could not read metadata for '{}': {}",
+                        path.display(),
+                        e
+                    ));
+                    continue;
+                }
+            };
+
+            let is_new = self.after.as_ref().is_none_or(|after| relative > *after);
+            if is_new && self.matches(&name, &relative, &metadata) {
+                if self.matches.len() == self.max_results {
+                    self.truncated = true;
+                    return;
+                }
+                self.matches.push(FoundItem {
+                    path: path.to_string_lossy().into_owned(),
+                    item_type: if metadata.is_dir() {
+                        "directory"
+                    } else {
+                        "file"
+                    }
+                    .to_string(),
+                    size_bytes: metadata.is_file().then_some(metadata.len()),
+                    modified: metadata.modified().ok().map(format_time),
+                });
+                self.last = Some(relative.clone());
+            }
+
+            // Symlinked directories are not entered, so the search cannot loop
+            if metadata.is_dir() && !file_type.is_symlink() {
+                if depth + 1 < MAX_FIND_DEPTH {
+                    self.walk(&path, &relative, depth + 1);
+                } else {
+                    self.warnings.push(format!(
+                        "Not searched below '{}': deeper than {} levels",
+                        path.display(),
+                        MAX_FIND_DEPTH
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Whether an item passes the glob pattern and every filter.
+    fn matches(&self, name: &str, relative: &Path, metadata: &fs::Metadata) -> bool {
+        let is_dir = metadata.is_dir();
+        match self.params.item_type {
+            Some(FindType::File) if is_dir => return false,
+            Some(FindType::Dir) if !is_dir => return false,
+            _ => {}
+        }
+        if (self.params.min_size.is_some() || self.params.max_size.is_some())
+            && (is_dir
+                || self.params.min_size.is_some_and(|min| metadata.len() < min)
+                || self.params.max_size.is_some_and(|max| metadata.len() > max))
+        {
+            return false;
+        }
+        if let Some(text) = &self.name_contains
+            && !name.to_lowercase().contains(text.as_str())
+        {
+            return false;
+        }
+        if let Some(after) = self.modified_after
+            && !metadata.modified().is_ok_and(|modified| modified > after)
+        {
+            return false;
+        }
+        self.patterns
+            .as_ref()
+            .is_none_or(|patterns| patterns.matches(relative))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    fn params(root: &Path, glob: Option<&str>) -> FsFindParams {
+        FsFindParams {
+            root: root.to_string_lossy().to_string(),
+            glob: glob.map(str::to_string),
+            name_contains: None,
+            min_size: None,
+            max_size: None,
+            modified_after: None,
+            item_type: None,
+            include_hidden: false,
+            exclude: None,
+            max_results: None,
+            continuation_token: None,
+        }
+    }
+
+    /// Relative paths of the matches, with '/' separators.
+    fn found(root: &Path, params: &FsFindParams) -> Vec<String> {
+        FsFindTool::find(params, &test_config())
+            .unwrap()
+            .matches
+            .iter()
+            .map(|m| {
+                Path::new(&m.path)
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    fn library() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let album = temp_dir.path().join("Artist/Album");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("01 - Intro.flac"), "a").unwrap();
+        fs::write(album.join("02 - Song.FLAC"), "abcdef").unwrap();
+        fs::write(album.join("cover.jpg"), "abc").unwrap();
+        fs::write(album.join(".hidden.flac"), "a").unwrap();
+        fs::write(temp_dir.path().join("loose.mp3"), "ab").unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_find_glob_and_filters() {
+        let library = library();
+        let root = library.path();
+
+        let mut p = params(root, Some("**/*.flac"));
+        assert_eq!(
+            found(root, &p),
+            [
+                "Artist/Album/01 - Intro.flac",
+                "Artist/Album/02 - Song.FLAC"
+            ]
+        );
+
+        p.min_size = Some(2);
+        assert_eq!(found(root, &p), ["Artist/Album/02 - Song.FLAC"]);
+
+        p.min_size = None;
+        p.name_contains = Some("INTRO".to_string());
+        assert_eq!(found(root, &p), ["Artist/Album/01 - Intro.flac"]);
+
+        let mut p = params(root, None);
+        p.item_type = Some(FindType::Dir);
+        assert_eq!(found(root, &p), ["Artist", "Artist/Album"]);
+
+        p.item_type = None;
+        p.modified_after = Some("2000-01-01".to_string());
+        assert_eq!(found(root, &p).len(), 6);
+        p.modified_after = Some("2999-01-01T00:00:00Z".to_string());
+        assert!(found(root, &p).is_empty());
+
+        p.modified_after = Some("yesterday".to_string());
+        let err = FsFindTool::find(&p, &test_config()).unwrap_err();
+        assert!(err.contains("Invalid modified_after"), "{}", err);
+    }
+
+    #[test]
+    fn test_find_exclude() {
+        let library = library();
+        let root = library.path();
+        fs::create_dir(root.join("Artist/Album/@eaDir")).unwrap();
+        fs::write(root.join("Artist/Album/@eaDir/01 - Intro.flac"), "a").unwrap();
+
+        let mut p = params(root, Some("**/*.flac"));
+        assert_eq!(found(root, &p).len(), 3);
+
+        // Configured patterns apply to every call, and add up with those of the call
+        let mut config = test_config();
+        config.tools.fs_exclude = vec!["@eadir".to_string()];
+        p.exclude = Some(vec!["Artist/*/02*".to_string()]);
+        let result = FsFindTool::find(&p, &config).unwrap();
+        assert_eq!(result.match_count, 1);
+        assert!(result.matches[0].path.ends_with("01 - Intro.flac"));
+        assert_eq!(result.excluded_count, Some(2));
+
+        p.exclude = Some(vec!["{".to_string()]);
+        assert!(FsFindTool::find(&p, &config).is_err());
+    }
+
+    #[test]
+    fn test_find_pagination() {
+        let library = library();
+        let root = library.path();
+        let mut p = params(root, None);
+        p.include_hidden = true;
+        let all = found(root, &p);
+        assert_eq!(all.len(), 7);
+
+        p.max_results = Some(3);
+        let mut pages = Vec::new();
+        loop {
+            let result = FsFindTool::find(&p, &test_config()).unwrap();
+            pages.extend(result.matches.into_iter().map(|m| m.path));
+            if !result.truncated {
+                assert!(result.continuation_token.is_none());
+                break;
+            }
+            p.continuation_token = result.continuation_token;
+        }
+        let pages: Vec<String> = pages
+            .iter()
+            .map(|path| {
+                Path::new(path)
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        assert_eq!(pages, all);
+    }
+
+    #[test]
+    fn test_find_outside_root() {
+        let root = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let mut config = test_config();
+        config.security.allowed_paths = vec![root.path().to_path_buf()];
+
+        let err = FsFindTool::find(&params(outside.path(), None), &config).unwrap_err();
+        assert!(err.contains("security validation failed"), "{}", err);
+    }
+}
@@ -12,12 +12,20 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::SystemTime;
 use tracing::{info, instrument, warn};
 
 use crate::core::config::Config;
-use crate::core::security::validate_path;
+use crate::core::security::{PathSecurityError, validate_path};
+use crate::core::time::{format_time, parse_time};
+use crate::domains::tools::definitions::mb::identify_directory::AUDIO_EXTENSIONS;
+
+use crate::domains::tools::blocking::run_blocking;
+use super::exclude::ExcludePatterns;
+use crate::domains::tools::timeout::with_timeout;
 
 // ============================================================================
 // Tool Parameters
@@ -33,13 +41,108 @@ pub struct FSListDirParams {
     #[serde(default)]
     pub include_hidden: bool,
 
-    /// Show additional details (size, type, permissions)
+    /// Show additional details (size, modified and created times)
     #[serde(default)]
     pub detailed: bool,
 
-    /// Recursion depth: 0 = no recursion (default), positive = levels deep, -1 = unlimited
+    /// Recursion depth: 0 = no recursion (default), positive = levels deep, -1 = unlimited.
+    /// Capped at the server's maximum depth (10 by default).
     #[serde(default)]
     pub recursive_depth: i32,
+
+    /// Only list files with these extensions (e.g. ["flac", "cue"], case-insensitive).
+    /// Directories are always listed and traversed.
+    #[serde(default)]
+    pub extensions: Option<Vec<String>>,
+
+    /// Only list audio files (mp3, flac, m4a, ogg, opus, wav, wma, aac, aiff, ...).
+    /// Combined with extensions, files matching either are listed.
+    #[serde(default)]
+    pub audio_only: bool,
+
+    /// Glob patterns of entries to leave out, matched against names and paths relative
+    /// to path (e.g. ["@eaDir", ".git", "node_modules"]). Excluded directories are not
+    /// traversed. Added to the server's default exclude list.
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+
+    /// Order of the entries within each directory: "name" (default), "size" or "mtime".
+    #[serde(default)]
+    pub sort_by: SortBy,
+
+    /// Sort order: "asc" (default) or "desc".
+    #[serde(default)]
+    pub sort_order: SortOrder,
+
+    /// Number of entries to skip, counted in traversal order (a directory, then its
+    /// contents, then the next entry).
+    #[serde(default)]
+    pub offset: usize,
+
+    /// Maximum number of entries listed (default and max: the server's maximum, 1000 by
+    /// default). Also accepted as "max_entries".
+    #[serde(default, alias = "max_entries")]
+    pub limit: Option<usize>,
+
+    /// Only list entries modified after this time (RFC 3339, or a date like "2024-01-31").
+    /// Directories are kept when they still contain entries.
+    #[serde(default)]
+    pub modified_after: Option<String>,
+
+    /// Add up the size of the listed files under each directory into its total_size.
+    /// Every file down to recursive_depth is sized, including those beyond the page,
+    /// which costs extra I/O on large trees. Directories with contents deeper than
+    /// recursive_depth report null (unknown).
+    #[serde(default)]
+    pub compute_dir_sizes: bool,
+
+    /// List symlinks as what they point to, and traverse symlinked directories (e.g.
+    /// album folders on a NAS mount). Targets must be within the allowed root.
+    #[serde(default)]
+    pub follow_symlinks: bool,
+
+    /// Shape of the entries: "tree" (default) nests directory contents in children,
+    /// "flat" lists every entry in one array with its path relative to path.
+    #[serde(default)]
+    pub output: OutputMode,
+}
+
+/// Key the entries of each directory are sorted by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    /// File name
+    #[default]
+    Name,
+    /// Size in bytes (directories count as 0)
+    Size,
+    /// Last modification time
+    #[serde(alias = "modified")]
+    Mtime,
+}
+
+/// Sort order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Smallest, oldest or first in name order first
+    #[default]
+    #[serde(alias = "ascending")]
+    Asc,
+    /// Largest, newest or last in name order first
+    #[serde(alias = "descending")]
+    Desc,
+}
+
+/// Shape of the listed entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputMode {
+    /// Directories hold their contents in children
+    #[default]
+    Tree,
+    /// One array of entries in traversal order, each with its relative path
+    Flat,
 }
 
 // ============================================================================
@@ -57,6 +160,20 @@ struct ListResult {
     dir_count: usize,
     /// Total count of files
     file_count: usize,
+    /// Number of entries in the whole traversal, including those not listed because of
+    /// offset and limit
+    total_entries: usize,
+    /// Files left out by the extensions/audio_only filter (only when filtering)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skipped_files: Option<usize>,
+    /// Entries left out by exclude patterns, not counted in total_entries (only when
+    /// excluding)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    excluded_count: Option<usize>,
+    /// Size in bytes of all listed files, null when part of the tree is deeper than the
+    /// depth limit (only with compute_dir_sizes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_size: Option<Option<u64>>,
     /// Warnings encountered during traversal
     #[serde(skip_serializing_if = "Vec::is_empty")]
     warnings: Vec<String>,
@@ -67,12 +184,32 @@ struct ListResult {
 struct EntryInfo {
     /// Name of the entry (just the filename, not full path)
     name: String,
-    /// Type of entry: "file", "directory", or "symlink"
+    /// Path relative to the listed directory, with "/" separators (only in flat output)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    /// Type of entry: "file", "directory", or "symlink" (with follow_symlinks, the type
+    /// of the symlink's target)
     #[serde(rename = "type")]
     entry_type: String,
+    /// Path a symlink points to, as stored in the link (only for symlinks)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
     /// Size in bytes (only for files in detailed mode)
     #[serde(skip_serializing_if = "Option::is_none")]
     size: Option<u64>,
+    /// Last modification time, RFC 3339 in UTC (only in detailed mode)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modified: Option<String>,
+    /// Creation time, RFC 3339 in UTC (only in detailed mode, where the filesystem records it)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created: Option<String>,
+    /// Size in bytes of the files under a directory, null when its contents go deeper
+    /// than the depth limit (only for directories with compute_dir_sizes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_size: Option<Option<u64>>,
+    /// Directory listed only to hold entries of this page; it belongs to an earlier page
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    parent_only: bool,
     /// Child entries (only for directories when recursing)
     #[serde(skip_serializing_if = "Vec::is_empty")]
     children: Vec<EntryInfo>,
@@ -90,11 +227,7 @@ impl FsListDirTool {
     pub const NAME: &'static str = "fs_list_dir";
 
     /// Tool description shown to clients.
-    pub const DESCRIPTION: &'static str = "List files and directories in a given path. Supports recursive traversal with configurable depth. Returns JSON format optimized for AI agents.";
-
-    /// Safety limits
-    const MAX_DEPTH_LIMIT: usize = 10;
-    const MAX_ENTRIES_LIMIT: usize = 1000;
+    pub const DESCRIPTION: &'static str = "List files and directories in a given path. Supports recursive traversal with configurable depth, sorting by name, size or mtime, pagination with offset/limit, and a flat output with relative paths. Returns JSON format optimized for AI agents.";
 
     /// Execute the tool logic (for STDIO/TCP transport via rmcp).
     #[instrument(skip_all, fields(path = %params.path, depth = %params.recursive_depth))]
@@ -126,49 +259,90 @@ impl FsListDirTool {
         }
 
         // Determine effective max depth
+        let max_depth_limit = config.tools.fs_max_depth;
         let max_depth = if params.recursive_depth < 0 {
             // -1 or "full" means unlimited, but we cap it at safety limit
-            Self::MAX_DEPTH_LIMIT
+            max_depth_limit
         } else {
-            params.recursive_depth as usize
+            (params.recursive_depth as usize).min(max_depth_limit)
+        };
+
+        let modified_after = match params.modified_after.as_deref() {
+            Some(value) => match parse_time("modified_after", value) {
+                Ok(time) => Some(time),
+                Err(e) => return CallToolResult::error(vec![Content::text(e)]),
+            },
+            None => None,
+        };
+
+        let exclude = match ExcludePatterns::new(config, params.exclude.as_deref()) {
+            Ok(exclude) => exclude,
+            Err(e) => return CallToolResult::error(vec![Content::text(e)]),
         };
 
         // Traverse directory with hierarchical structure
-        let mut warnings = Vec::new();
-        let mut visited_inodes = HashSet::new();
-        let mut total_count = 0;
-        let mut truncated = false;
-
-        let entries = Self::traverse_directory_hierarchical(
-            &path,
-            0,
-            max_depth,
-            params.include_hidden,
-            params.detailed,
+        let offset = params.offset;
+        let max_entries = config.tools.fs_max_entries.max(1);
+        let limit = params.limit.unwrap_or(max_entries).clamp(1, max_entries);
+        let mut traversal = Traversal {
+            params,
             config,
-            &mut warnings,
-            &mut visited_inodes,
-            &mut total_count,
-            &mut truncated,
-        );
+            root: &path,
+            max_depth,
+            extensions: Self::extension_filter(params),
+            exclude,
+            modified_after,
+            page: offset..offset.saturating_add(limit),
+            warnings: Vec::new(),
+            visited_inodes: HashSet::new(),
+            total_entries: 0,
+            listed: 0,
+            skipped_files: 0,
+            excluded: 0,
+        };
+        // A symlink back to the listed directory is a loop too
+        #[cfg(unix)]
+        if let Ok(metadata) = fs::metadata(&path) {
+            use std::os::unix::fs::MetadataExt;
+            traversal.visited_inodes.insert(metadata.ino());
+        }
+        let (entries, total_size) = traversal.traverse(&path, 0);
+        let mut warnings = traversal.warnings;
 
         // Add truncation warning if needed
-        if truncated {
+        if traversal.total_entries > offset + traversal.listed {
+            let next = offset + traversal.listed;
             warnings.push(format!(
-                "Results truncated: exceeded maximum of {} entries. Consider reducing recursive_depth.",
-                Self::MAX_ENTRIES_LIMIT
+                "Results truncated: showing entries {}-{} of {}. Use offset={} for the next page or reduce recursive_depth.",
+                offset + 1,
+                next,
+                traversal.total_entries,
+                next
             ));
         }
 
-        if params.recursive_depth < 0 && max_depth == Self::MAX_DEPTH_LIMIT {
+        if params.recursive_depth < 0 {
             warnings.push(format!(
                 "Depth limited to {} levels for safety (requested unlimited).",
-                Self::MAX_DEPTH_LIMIT
+                max_depth_limit
+            ));
+        } else if params.recursive_depth as usize > max_depth_limit {
+            warnings.push(format!(
+                "Depth limited to {} levels for safety (requested {}).",
+                max_depth_limit, params.recursive_depth
             ));
         }
 
         // Count directories and files recursively
         let (dir_count, file_count) = Self::count_entries(&entries);
+        let entries = match params.output {
+            OutputMode::Tree => entries,
+            OutputMode::Flat => {
+                let mut flat = Vec::new();
+                Self::flatten(entries, "", &mut flat);
+                flat
+            }
+        };
 
         // Build result
         let result = ListResult {
@@ -176,6 +350,13 @@ impl FsListDirTool {
             entries,
             dir_count,
             file_count,
+            total_entries: traversal.total_entries,
+            skipped_files: traversal
+                .extensions
+                .is_some()
+                .then_some(traversal.skipped_files),
+            excluded_count: traversal.exclude.is_some().then_some(traversal.excluded),
+            total_size: params.compute_dir_sizes.then_some(total_size),
             warnings,
         };
 
@@ -187,7 +368,7 @@ impl FsListDirTool {
         );
 
         // Create human-readable text summary
-        let summary = if result.warnings.is_empty() {
+        let mut summary = if result.warnings.is_empty() {
             format!(
                 "Found {} directories and {} files in '{}'",
                 result.dir_count, result.file_count, params.path
@@ -198,6 +379,21 @@ impl FsListDirTool {
                 result.dir_count, result.file_count, params.path, result.warnings.len()
             )
         };
+        if let Some(skipped) = result.skipped_files
+            && skipped > 0
+        {
+            summary.push_str(&format!("; {} other file(s) filtered out", skipped));
+        }
+        if let Some(excluded) = result.excluded_count
+            && excluded > 0
+        {
+            summary.push_str(&format!("; {} excluded", excluded));
+        }
+        match result.total_size {
+            Some(Some(size)) => summary.push_str(&format!("; {} bytes in total", size)),
+            Some(None) => summary.push_str("; total size unknown beyond the depth limit"),
+            None => {}
+        }
 
         // Return with text summary + structured content (avoids duplicating the full hierarchy in text)
         CallToolResult {
@@ -208,154 +404,28 @@ impl FsListDirTool {
         }
     }
 
-    /// Recursively traverse a directory and build hierarchical structure
-    #[allow(clippy::too_many_arguments)]
-    fn traverse_directory_hierarchical(
-        current: &Path,
-        current_depth: usize,
-        max_depth: usize,
-        include_hidden: bool,
-        detailed: bool,
-        config: &Config,
-        warnings: &mut Vec<String>,
-        visited_inodes: &mut HashSet<u64>,
-        total_count: &mut usize,
-        truncated: &mut bool,
-    ) -> Vec<EntryInfo> {
-        // Check if we've hit the entry limit
-        if *total_count >= Self::MAX_ENTRIES_LIMIT {
-            *truncated = true;
-            return Vec::new();
-        }
-
-        // Check if we've exceeded max depth
-        if current_depth > max_depth {
-            return Vec::new();
+    /// Lowercased extensions files must have, or `None` to list every file.
+    fn extension_filter(params: &FSListDirParams) -> Option<Vec<String>> {
+        if params.extensions.is_none() && !params.audio_only {
+            return None;
         }
-
-        // Read directory entries
-        let dir_entries = match fs::read_dir(current) {
-            Ok(entries) => entries,
-            Err(e) => {
-                warn!("Failed to read directory {:?}: {}", current, e);
-                warnings.push(format!(
-                    "Could not read directory '{}': {}",
-                    current.display(),
-                    e
-                ));
-                return Vec::new();
-            }
-        };
-
-        // Collect and sort entries
-        let mut sorted_entries: Vec<_> = dir_entries
-            .filter_map(|entry_result| entry_result.ok())
+        let mut extensions: Vec<String> = params
+            .extensions
+            .iter()
+            .flatten()
+            .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
             .collect();
-        sorted_entries.sort_by_key(|e| e.file_name());
-
-        let mut results = Vec::new();
-
-        for entry in sorted_entries {
-            // Check entry limit again
-            if *total_count >= Self::MAX_ENTRIES_LIMIT {
-                *truncated = true;
-                return results;
-            }
-
-            let file_name = entry.file_name();
-            let name = file_name.to_string_lossy().to_string();
-
-            // Skip hidden files if not requested
-            if !include_hidden && name.starts_with('.') {
-                continue;
-            }
-
-            let entry_path = entry.path();
-
-            // Validate path security for each entry
-            if let Err(e) = validate_path(&entry_path.to_string_lossy(), config) {
-                warn!("Path validation failed for {:?}: {}", entry_path, e);
-                warnings.push(format!(
-                    "Skipped '{}': security validation failed",
-                    entry_path.display()
-                ));
-                continue;
-            }
-
-            let metadata = match entry.metadata() {
-                Ok(m) => m,
-                Err(e) => {
-                    warn!("Failed to get metadata for {:?}: {}", entry_path, e);
-                    warnings.push(format!(
-                        "Could not read metadata for '{}': {}",
-                        entry_path.display(),
-                        e
-                    ));
-                    continue;
-                }
-            };
-
-            // Check for symlink loops using inodes (Unix-like systems)
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::MetadataExt;
-                let inode = metadata.ino();
-                if metadata.is_dir() && !visited_inodes.insert(inode) {
-                    warnings.push(format!(
-                        "Skipped '{}': symlink loop detected",
-                        entry_path.display()
-                    ));
-                    continue;
-                }
-            }
-
-            // Determine entry type
-            let entry_type = if metadata.is_dir() {
-                "directory"
-            } else if metadata.is_symlink() {
-                "symlink"
-            } else {
-                "file"
-            };
-
-            // Get size only for files in detailed mode
-            let size = if detailed && metadata.is_file() {
-                Some(metadata.len())
-            } else {
-                None
-            };
-
-            // Increment total count
-            *total_count += 1;
-
-            // Recursively get children if it's a directory and within depth limit
-            let children = if metadata.is_dir() && current_depth < max_depth {
-                Self::traverse_directory_hierarchical(
-                    &entry_path,
-                    current_depth + 1,
-                    max_depth,
-                    include_hidden,
-                    detailed,
-                    config,
-                    warnings,
-                    visited_inodes,
-                    total_count,
-                    truncated,
-                )
-            } else {
-                Vec::new()
-            };
-
-            // Add entry to results with its children
-            results.push(EntryInfo {
-                name,
-                entry_type: entry_type.to_string(),
-                size,
-                children,
-            });
+        if params.audio_only {
+            extensions.extend(AUDIO_EXTENSIONS.iter().map(|ext| ext.to_string()));
         }
+        Some(extensions)
+    }
 
-        results
+    /// Whether a file has one of the (lowercased) extensions.
+    fn has_extension(path: &Path, extensions: &[String]) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.contains(&ext.to_lowercase()))
     }
 
     /// Recursively count directories and files in hierarchical structure
@@ -366,7 +436,9 @@ impl FsListDirTool {
         for entry in entries {
             match entry.entry_type.as_str() {
                 "directory" => {
-                    dir_count += 1;
+                    if !entry.parent_only {
+                        dir_count += 1;
+                    }
                     // Recursively count children
                     let (child_dirs, child_files) = Self::count_entries(&entry.children);
                     dir_count += child_dirs;
@@ -380,6 +452,25 @@ impl FsListDirTool {
         (dir_count, file_count)
     }
 
+    /// Move the entries of a tree into one list, in traversal order, each with its path
+    /// relative to the listed directory. Directories only there to hold entries of the
+    /// page are left out.
+    fn flatten(entries: Vec<EntryInfo>, prefix: &str, flat: &mut Vec<EntryInfo>) {
+        for mut entry in entries {
+            let path = if prefix.is_empty() {
+                entry.name.clone()
+            } else {
+                format!("{}/{}", prefix, entry.name)
+            };
+            let children = std::mem::take(&mut entry.children);
+            if !entry.parent_only {
+                entry.path = Some(path.clone());
+                flat.push(entry);
+            }
+            Self::flatten(children, &path, flat);
+        }
+    }
+
     /// HTTP handler for this tool (for HTTP transport).
     #[cfg(feature = "http")]
     pub fn http_handler(
@@ -407,6 +498,75 @@ impl FsListDirTool {
             .and_then(|v| v.as_i64())
             .unwrap_or(0) as i32;
 
+        let extensions = arguments
+            .get("extensions")
+            .and_then(|v| v.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            });
+
+        let audio_only = arguments
+            .get("audio_only")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let exclude = arguments
+            .get("exclude")
+            .and_then(|v| v.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            });
+
+        let sort_by = match arguments.get("sort_by") {
+            Some(v) => serde_json::from_value(v.clone())
+                .map_err(|e| format!("Invalid 'sort_by' parameter: {}", e))?,
+            None => SortBy::default(),
+        };
+
+        let sort_order = match arguments.get("sort_order") {
+            Some(v) => serde_json::from_value(v.clone())
+                .map_err(|e| format!("Invalid 'sort_order' parameter: {}", e))?,
+            None => SortOrder::default(),
+        };
+
+        let offset = arguments
+            .get("offset")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as usize;
+
+        let limit = arguments
+            .get("limit")
+            .or_else(|| arguments.get("max_entries"))
+            .and_then(|v| v.as_u64())
+            .map(|v| v as usize);
+
+        let modified_after = arguments
+            .get("modified_after")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        let compute_dir_sizes = arguments
+            .get("compute_dir_sizes")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let follow_symlinks = arguments
+            .get("follow_symlinks")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let output = match arguments.get("output") {
+            Some(v) => serde_json::from_value(v.clone())
+                .map_err(|e| format!("Invalid 'output' parameter: {}", e))?,
+            None => OutputMode::default(),
+        };
+
         info!(
             "List directory tool (HTTP) called for path: {} with recursive_depth: {}",
             path, recursive_depth
@@ -417,6 +577,17 @@ impl FsListDirTool {
             include_hidden,
             detailed,
             recursive_depth,
+            extensions,
+            audio_only,
+            exclude,
+            sort_by,
+            sort_order,
+            offset,
+            limit,
+            modified_after,
+            compute_dir_sizes,
+            follow_symlinks,
+            output,
         };
 
         let result = Self::execute(&params, &config);
@@ -444,6 +615,7 @@ impl FsListDirTool {
     where
         S: Send + Sync + 'static,
     {
+        let timeout = config.tools.timeout_for(Self::NAME);
         ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             let config = config.clone();
@@ -451,13 +623,280 @@ impl FsListDirTool {
                 let params: FSListDirParams =
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
-                Ok(Self::execute(&params, &config))
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
             }
             .boxed()
         })
     }
 }
 
+/// State of a directory traversal.
+///
+/// Entries are numbered in traversal order: each directory's entries are sorted, and a
+/// directory's contents come right after it. Only entries numbered within `page` are
+/// listed, but every entry is counted so clients know how many there are.
+struct Traversal<'a> {
+    params: &'a FSListDirParams,
+    config: &'a Config,
+    /// Directory being listed, that exclude patterns are relative to
+    root: &'a Path,
+    max_depth: usize,
+    /// Extensions files must have, if filtering
+    extensions: Option<Vec<String>>,
+    /// Patterns of entries to leave out, if excluding
+    exclude: Option<ExcludePatterns>,
+    /// Time entries must be modified after, if filtering
+    modified_after: Option<SystemTime>,
+    /// Numbers of the entries to list
+    page: Range<usize>,
+    warnings: Vec<String>,
+    /// Inodes of the directories being traversed, from the listed directory down
+    visited_inodes: HashSet<u64>,
+    /// Number of entries seen, listed or not
+    total_entries: usize,
+    /// Number of entries listed
+    listed: usize,
+    /// Number of files left out by the extension filter
+    skipped_files: usize,
+    /// Number of entries left out by exclude patterns
+    excluded: usize,
+}
+
+impl Traversal<'_> {
+    /// Recursively traverse a directory and build hierarchical structure.
+    ///
+    /// Also returns the size of the files kept by the filters under `current`, or `None`
+    /// when it is unknown because part of the tree could not be read or is too deep.
+    fn traverse(&mut self, current: &Path, current_depth: usize) -> (Vec<EntryInfo>, Option<u64>) {
+        // Check if we've exceeded max depth
+        if current_depth > self.max_depth {
+            return (Vec::new(), None);
+        }
+
+        // Read directory entries
+        let dir_entries = match fs::read_dir(current) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to read directory {:?}: {}", current, e);
+                self.warnings.push(format!(
+                    "Could not read directory '{}': {}",
+                    current.display(),
+                    e
+                ));
+                return (Vec::new(), None);
+            }
+        };
+
+        // Collect entries with their metadata
+        let mut sorted_entries = Vec::new();
+        for entry in dir_entries.filter_map(|entry_result| entry_result.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            // Skip hidden files if not requested
+            if !self.params.include_hidden && name.starts_with('.') {
+                continue;
+            }
+
+            // Skip excluded entries, without traversing excluded directories
+            if let Some(exclude) = &self.exclude
+                && let Ok(relative) = entry.path().strip_prefix(self.root)
+                && exclude.excludes(relative)
+            {
+                self.excluded += 1;
+                continue;
+            }
+
+            match entry.metadata() {
+                Ok(metadata) if metadata.is_symlink() => {
+                    let target = fs::read_link(entry.path())
+                        .ok()
+                        .map(|target| target.to_string_lossy().into_owned());
+                    // Resolved to the target's metadata when following; a broken link
+                    // stays a symlink
+                    let metadata = if self.params.follow_symlinks {
+                        fs::metadata(entry.path()).unwrap_or(metadata)
+                    } else {
+                        metadata
+                    };
+                    sorted_entries.push((name, entry.path(), metadata, target));
+                }
+                Ok(metadata) => sorted_entries.push((name, entry.path(), metadata, None)),
+                Err(e) => {
+                    warn!("Failed to get metadata for {:?}: {}", entry.path(), e);
+                    self.warnings.push(format!(
+                        "Could not read metadata for '{}': {}",
+                        entry.path().display(),
+                        e
+                    ));
+                }
+            }
+        }
+        self.sort(&mut sorted_entries);
+
+        let mut results = Vec::new();
+        let mut dir_size = Some(0);
+
+        for (name, entry_path, metadata, target) in sorted_entries {
+            // Validate path security for each entry; for a symlink this resolves the
+            // target, so links out of the allowed root are skipped
+            if let Err(e) = validate_path(&entry_path.to_string_lossy(), self.config) {
+                warn!("Path validation failed for {:?}: {}", entry_path, e);
+                let reason = match e {
+                    PathSecurityError::DeniedByPattern { pattern, .. } => {
+                        format!("matches deny pattern '{}'", pattern)
+                    }
+                    _ => "security validation failed".to_string(),
+                };
+                self.warnings
+                    .push(format!("Skipped '{}': {}", entry_path.display(), reason));
+                continue;
+            }
+
+            // Check for symlink loops using inodes (Unix-like systems): a directory that
+            // is one of its own ancestors
+            #[cfg(unix)]
+            let inode = {
+                use std::os::unix::fs::MetadataExt;
+                metadata.is_dir().then(|| metadata.ino())
+            };
+            #[cfg(unix)]
+            if let Some(inode) = inode
+                && self.visited_inodes.contains(&inode)
+            {
+                self.warnings.push(format!(
+                    "Skipped '{}': symlink loop detected",
+                    entry_path.display()
+                ));
+                continue;
+            }
+
+            // Determine entry type
+            let entry_type = if metadata.is_dir() {
+                "directory"
+            } else if metadata.is_symlink() {
+                "symlink"
+            } else {
+                "file"
+            };
+
+            // Leave out files the extension filter does not keep
+            if !metadata.is_dir()
+                && let Some(extensions) = &self.extensions
+                && !FsListDirTool::has_extension(&entry_path, extensions)
+            {
+                self.skipped_files += 1;
+                continue;
+            }
+
+            // Leave out files, and directories that are not traversed, modified too early
+            let expanded = metadata.is_dir() && current_depth < self.max_depth;
+            if !expanded
+                && let Some(after) = self.modified_after
+                && !metadata.modified().is_ok_and(|modified| modified > after)
+            {
+                continue;
+            }
+
+            // Get size only for files in detailed mode
+            let size = if self.params.detailed && metadata.is_file() {
+                Some(metadata.len())
+            } else {
+                None
+            };
+            let (modified, created) = if self.params.detailed {
+                (
+                    metadata.modified().ok().map(format_time),
+                    metadata.created().ok().map(format_time),
+                )
+            } else {
+                (None, None)
+            };
+
+            // Number the entry, and list it if it is on the page
+            let on_page = self.page.contains(&self.total_entries);
+            self.total_entries += 1;
+            if on_page {
+                self.listed += 1;
+            }
+
+            // Recursively get children if it's a directory and within depth limit
+            let numbered = self.total_entries;
+            let (children, entry_size) = if expanded {
+                #[cfg(unix)]
+                if let Some(inode) = inode {
+                    self.visited_inodes.insert(inode);
+                }
+                let traversed = self.traverse(&entry_path, current_depth + 1);
+                #[cfg(unix)]
+                if let Some(inode) = inode {
+                    self.visited_inodes.remove(&inode);
+                }
+                traversed
+            } else if metadata.is_dir() {
+                (Vec::new(), None)
+            } else if metadata.is_file() {
+                (Vec::new(), Some(metadata.len()))
+            } else {
+                (Vec::new(), Some(0))
+            };
+
+            // A directory left empty by modified_after is dropped; nothing was numbered
+            // after it, so its number goes to the next entry
+            if expanded && self.modified_after.is_some() && self.total_entries == numbered {
+                self.total_entries -= 1;
+                if on_page {
+                    self.listed -= 1;
+                }
+                continue;
+            }
+
+            dir_size = dir_size.zip(entry_size).map(|(a, b)| a + b);
+            let total_size =
+                (self.params.compute_dir_sizes && metadata.is_dir()).then_some(entry_size);
+
+            // Add entry to results with its children; a directory before the page is
+            // kept to hold those of its children that are on it
+            if on_page || !children.is_empty() {
+                results.push(EntryInfo {
+                    name,
+                    path: None,
+                    entry_type: entry_type.to_string(),
+                    target,
+                    size,
+                    modified,
+                    created,
+                    total_size,
+                    parent_only: !on_page,
+                    children,
+                });
+            }
+        }
+
+        (results, dir_size)
+    }
+
+    /// Sort the entries of a directory by the requested key, names breaking ties.
+    fn sort(&self, entries: &mut [(String, PathBuf, fs::Metadata, Option<String>)]) {
+        match self.params.sort_by {
+            SortBy::Name => entries.sort_by(|a, b| a.0.cmp(&b.0)),
+            // Directories have no size, so they come before files
+            SortBy::Size => entries.sort_by_key(|(name, _, metadata, _)| {
+                (metadata.is_file().then_some(metadata.len()), name.clone())
+            }),
+            SortBy::Mtime => entries
+                .sort_by_key(|(name, _, metadata, _)| (metadata.modified().ok(), name.clone())),
+        }
+        if self.params.sort_order == SortOrder::Desc {
+            entries.reverse();
+        }
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -488,6 +927,17 @@ mod tests {
             include_hidden: false,
             detailed: false,
             recursive_depth: 0,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
         };
 
         let config = test_config();
@@ -520,6 +970,17 @@ mod tests {
             include_hidden: false,
             detailed: false,
             recursive_depth: 0,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
         };
 
         let config = test_config();
@@ -539,6 +1000,17 @@ mod tests {
             include_hidden: false,
             detailed: true,
             recursive_depth: 0,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
         };
 
         let config = test_config();
@@ -573,6 +1045,17 @@ mod tests {
             include_hidden: false,
             detailed: false,
             recursive_depth: 1,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
         };
 
         let config = test_config();
@@ -608,6 +1091,17 @@ mod tests {
             include_hidden: false,
             detailed: false,
             recursive_depth: 2,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
         };
 
         let config = test_config();
@@ -630,6 +1124,61 @@ mod tests {
         assert!(dir2["children"].as_array().unwrap().len() > 0);
     }
 
+    #[test]
+    fn test_list_dir_skips_denied_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("Album/.config/app")).unwrap();
+        fs::write(root.join("Album/01.flac"), "audio").unwrap();
+        fs::write(root.join("Album/server.key"), "secret").unwrap();
+        fs::write(root.join("Album/.config/app/settings"), "secret").unwrap();
+
+        let mut config = test_config();
+        config.security.deny_patterns = vec!["*.key".to_string(), ".config/**".to_string()];
+        let params = FSListDirParams {
+            path: root.to_string_lossy().to_string(),
+            include_hidden: true,
+            detailed: false,
+            recursive_depth: 3,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Flat,
+        };
+
+        let result = FsListDirTool::execute(&params, &config);
+        assert!(result.is_error != Some(true));
+        let json = result.structured_content.unwrap();
+        let paths: Vec<&str> = json["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["path"].as_str().unwrap())
+            .collect();
+        assert_eq!(paths, vec!["Album", "Album/01.flac"]);
+
+        let warnings = json["warnings"].as_array().unwrap();
+        assert_eq!(warnings.len(), 2);
+        let key_warning = format!(
+            "Skipped '{}': matches deny pattern '*.key'",
+            root.join("Album/server.key").display()
+        );
+        assert!(warnings.iter().any(|w| w == key_warning.as_str()));
+
+        // Listing a denied directory itself fails
+        let mut params = params;
+        params.path = root.join("Album/.config").to_string_lossy().to_string();
+        let result = FsListDirTool::execute(&params, &config);
+        assert_eq!(result.is_error, Some(true));
+    }
+
     #[test]
     fn test_list_dir_hidden_files() {
         let temp_dir = TempDir::new().unwrap();
@@ -644,6 +1193,17 @@ mod tests {
             include_hidden: false,
             detailed: false,
             recursive_depth: 0,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
         };
 
         let config = test_config();
@@ -657,6 +1217,17 @@ mod tests {
             include_hidden: true,
             detailed: false,
             recursive_depth: 0,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
         };
 
         let result = FsListDirTool::execute(&params, &config);
@@ -664,6 +1235,520 @@ mod tests {
         assert_eq!(json["file_count"], 2); // Both .hidden and visible.txt
     }
 
+    #[test]
+    fn test_list_dir_extension_filters() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::create_dir(temp_path.join("CD1")).unwrap();
+        fs::write(temp_path.join("CD1/01.FLAC"), "content").unwrap();
+        fs::write(temp_path.join("CD1/rip.log"), "content").unwrap();
+        fs::write(temp_path.join("album.cue"), "content").unwrap();
+        fs::write(temp_path.join("cover.jpg"), "content").unwrap();
+
+        let mut params = FSListDirParams {
+            path: temp_path.to_string_lossy().to_string(),
+            include_hidden: false,
+            detailed: false,
+            recursive_depth: 1,
+            extensions: None,
+            audio_only: true,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
+        };
+
+        let config = test_config();
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        // Directories are still listed and traversed
+        assert_eq!(json["dir_count"], 1);
+        assert_eq!(json["file_count"], 1);
+        assert_eq!(json["skipped_files"], 3);
+        assert_eq!(json["entries"][0]["children"][0]["name"], "01.FLAC");
+
+        // Files matching either filter are kept
+        params.extensions = Some(vec![".cue".to_string()]);
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        assert_eq!(json["file_count"], 2);
+        assert_eq!(json["skipped_files"], 2);
+
+        // No filter, no skipped_files field
+        params.extensions = None;
+        params.audio_only = false;
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        assert_eq!(json["file_count"], 4);
+        assert!(json.get("skipped_files").is_none());
+    }
+
+    #[test]
+    fn test_list_dir_sort() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::write(temp_path.join("a.flac"), "abc").unwrap();
+        fs::write(temp_path.join("b.flac"), "a").unwrap();
+        fs::write(temp_path.join("c.flac"), "ab").unwrap();
+        fs::create_dir(temp_path.join("d")).unwrap();
+
+        let mut params = FSListDirParams {
+            path: temp_path.to_string_lossy().to_string(),
+            include_hidden: false,
+            detailed: false,
+            recursive_depth: 0,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Size,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
+        };
+
+        let config = test_config();
+        let names = |params: &FSListDirParams| {
+            let result = FsListDirTool::execute(params, &config);
+            let json = result.structured_content.expect("Expected structured content");
+            json["entries"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| e["name"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // Directories count as size 0
+        assert_eq!(names(&params), ["d", "b.flac", "c.flac", "a.flac"]);
+
+        params.sort_order = SortOrder::Desc;
+        assert_eq!(names(&params), ["a.flac", "c.flac", "b.flac", "d"]);
+
+        params.sort_by = SortBy::Name;
+        assert_eq!(names(&params), ["d", "c.flac", "b.flac", "a.flac"]);
+    }
+
+    #[test]
+    fn test_list_dir_pagination() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::write(temp_path.join("a.txt"), "content").unwrap();
+        fs::create_dir(temp_path.join("b")).unwrap();
+        fs::write(temp_path.join("b/x.txt"), "content").unwrap();
+        fs::write(temp_path.join("b/y.txt"), "content").unwrap();
+        fs::write(temp_path.join("c.txt"), "content").unwrap();
+
+        // Traversal order: a.txt, b, b/x.txt, b/y.txt, c.txt
+        let mut params = FSListDirParams {
+            path: temp_path.to_string_lossy().to_string(),
+            include_hidden: false,
+            detailed: false,
+            recursive_depth: 1,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: Some(2),
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
+        };
+
+        let config = test_config();
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        assert_eq!(json["total_entries"], 5);
+        assert_eq!(json["dir_count"], 1);
+        assert_eq!(json["file_count"], 1);
+        assert!(json["entries"][1].get("children").is_none());
+        assert!(
+            json["warnings"][0]
+                .as_str()
+                .unwrap()
+                .contains("showing entries 1-2 of 5. Use offset=2")
+        );
+
+        // The directory of the first entry is kept to hold it, but not counted
+        params.offset = 3;
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        assert_eq!(json["dir_count"], 0);
+        assert_eq!(json["file_count"], 2);
+        let entries = json["entries"].as_array().unwrap();
+        assert_eq!(entries[0]["name"], "b");
+        assert_eq!(entries[0]["parent_only"], true);
+        assert_eq!(entries[0]["children"][0]["name"], "y.txt");
+        assert_eq!(entries[1]["name"], "c.txt");
+        assert!(json.get("warnings").is_none());
+    }
+
+    #[test]
+    fn test_list_dir_flat_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::write(temp_path.join("a.txt"), "content").unwrap();
+        fs::create_dir_all(temp_path.join("b/cd1")).unwrap();
+        fs::write(temp_path.join("b/x.txt"), "content").unwrap();
+        fs::write(temp_path.join("b/cd1/y.txt"), "content").unwrap();
+        fs::write(temp_path.join("c.txt"), "content").unwrap();
+
+        let mut params = FSListDirParams {
+            path: temp_path.to_string_lossy().to_string(),
+            include_hidden: false,
+            detailed: false,
+            recursive_depth: 2,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Flat,
+        };
+        let paths = |json: &serde_json::Value| -> Vec<String> {
+            json["entries"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| e["path"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let config = test_config();
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.unwrap();
+        assert_eq!(
+            paths(&json),
+            vec!["a.txt", "b", "b/cd1", "b/cd1/y.txt", "b/x.txt", "c.txt"]
+        );
+        assert!(json["entries"][1].get("children").is_none());
+        assert_eq!(json["entries"][3]["name"], "y.txt");
+
+        // Same counts as the tree, and a page leaves out the directories holding it
+        params.offset = 3;
+        params.limit = Some(2);
+        let flat = FsListDirTool::execute(&params, &config)
+            .structured_content
+            .unwrap();
+        params.output = OutputMode::Tree;
+        let tree = FsListDirTool::execute(&params, &config)
+            .structured_content
+            .unwrap();
+        assert_eq!(paths(&flat), vec!["b/cd1/y.txt", "b/x.txt"]);
+        for field in ["dir_count", "file_count", "total_entries", "warnings"] {
+            assert_eq!(flat[field], tree[field]);
+        }
+        assert!(tree["entries"][0].get("path").is_none());
+    }
+
+    #[test]
+    fn test_list_dir_modified_after() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+        let old = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+        let set_old = |path: &Path| {
+            fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(old)
+                .unwrap();
+        };
+
+        fs::write(temp_path.join("new.txt"), "content").unwrap();
+        fs::write(temp_path.join("old.txt"), "content").unwrap();
+        set_old(&temp_path.join("old.txt"));
+        fs::create_dir(temp_path.join("new_album")).unwrap();
+        fs::write(temp_path.join("new_album/01.flac"), "content").unwrap();
+        fs::create_dir(temp_path.join("old_album")).unwrap();
+        fs::write(temp_path.join("old_album/01.flac"), "content").unwrap();
+        set_old(&temp_path.join("old_album/01.flac"));
+
+        let mut params = FSListDirParams {
+            path: temp_path.to_string_lossy().to_string(),
+            include_hidden: false,
+            detailed: true,
+            recursive_depth: 1,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
+        };
+
+        let config = test_config();
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        // Always UTC, whatever the local timezone
+        assert_eq!(json["entries"][2]["name"], "old.txt");
+        assert_eq!(json["entries"][2]["modified"], "2001-09-09T01:46:40Z");
+
+        // The old album is left empty, so it is dropped too
+        params.modified_after = Some("2010-01-01".to_string());
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        let entries = json["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["name"], "new.txt");
+        assert_eq!(entries[1]["name"], "new_album");
+        assert_eq!(entries[1]["children"][0]["name"], "01.flac");
+        assert_eq!(json["total_entries"], 3);
+
+        params.modified_after = Some("last week".to_string());
+        let result = FsListDirTool::execute(&params, &config);
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_list_dir_configured_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::create_dir_all(temp_path.join("a/b/c")).unwrap();
+        fs::write(temp_path.join("a/b/c/deep.txt"), "content").unwrap();
+        fs::write(temp_path.join("z.txt"), "content").unwrap();
+
+        let mut config = test_config();
+        config.tools.fs_max_entries = 2;
+        config.tools.fs_max_depth = 1;
+
+        // Traversal order within the depth limit: a, a/b, z.txt
+        let params: FSListDirParams = serde_json::from_value(serde_json::json!({
+            "path": temp_path.to_string_lossy(),
+            "recursive_depth": 5,
+            "max_entries": 10
+        }))
+        .unwrap();
+        assert_eq!(params.limit, Some(10));
+
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        assert_eq!(json["total_entries"], 3);
+        assert_eq!(json["entries"][0]["children"][0]["name"], "b");
+        assert!(json["entries"][0]["children"][0].get("children").is_none());
+        let warnings = json["warnings"].as_array().unwrap();
+        assert!(
+            warnings[0]
+                .as_str()
+                .unwrap()
+                .contains("showing entries 1-2 of 3")
+        );
+        assert_eq!(
+            warnings[1],
+            "Depth limited to 1 levels for safety (requested 5)."
+        );
+    }
+
+    #[test]
+    fn test_list_dir_exclude() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::create_dir_all(temp_path.join("album/@eaDir")).unwrap();
+        fs::write(temp_path.join("album/@eaDir/01.flac"), "content").unwrap();
+        fs::write(temp_path.join("album/01.flac"), "content").unwrap();
+        fs::create_dir_all(temp_path.join("node_modules/pkg")).unwrap();
+        fs::write(temp_path.join("notes.txt"), "content").unwrap();
+
+        let mut params = FSListDirParams {
+            path: temp_path.to_string_lossy().to_string(),
+            include_hidden: false,
+            detailed: false,
+            recursive_depth: -1,
+            extensions: None,
+            audio_only: false,
+            exclude: Some(vec!["node_modules".to_string(), "*.txt".to_string()]),
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
+        };
+
+        let mut config = test_config();
+        config.tools.fs_exclude = vec!["@eaDir".to_string()];
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        let entries = json["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["name"], "album");
+        assert_eq!(entries[0]["children"].as_array().unwrap().len(), 1);
+        assert_eq!(entries[0]["children"][0]["name"], "01.flac");
+        // Contents of excluded directories are not counted
+        assert_eq!(json["excluded_count"], 3);
+        assert_eq!(json["total_entries"], 2);
+
+        params.exclude = None;
+        config.tools.fs_exclude.clear();
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        assert!(json.get("excluded_count").is_none());
+        assert_eq!(json["total_entries"], 7);
+    }
+
+    #[test]
+    fn test_list_dir_compute_dir_sizes() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::write(temp_path.join("cover.jpg"), "1234").unwrap();
+        fs::create_dir_all(temp_path.join("album/cd1")).unwrap();
+        fs::write(temp_path.join("album/01.flac"), "123456").unwrap();
+        fs::write(temp_path.join("album/cd1/01.flac"), "12345678").unwrap();
+
+        let mut params = FSListDirParams {
+            path: temp_path.to_string_lossy().to_string(),
+            include_hidden: false,
+            detailed: false,
+            recursive_depth: 2,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: true,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
+        };
+
+        let config = test_config();
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        assert_eq!(json["total_size"], 18);
+        assert_eq!(json["entries"][0]["name"], "album");
+        assert_eq!(json["entries"][0]["total_size"], 14);
+        assert_eq!(json["entries"][0]["children"][1]["total_size"], 8);
+        // Files keep their own size field
+        assert!(json["entries"][1].get("total_size").is_none());
+
+        // Sizes follow the filters and are not limited to the page
+        params.extensions = Some(vec!["flac".to_string()]);
+        params.limit = Some(1);
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        assert_eq!(json["total_size"], 14);
+        assert_eq!(json["entries"][0]["total_size"], 14);
+
+        // Contents beyond the depth limit make the size unknown
+        params.recursive_depth = 1;
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        assert!(json["total_size"].is_null());
+        assert!(json["entries"][0]["total_size"].is_null());
+        assert!(json["entries"][0].get("total_size").is_some());
+
+        params.compute_dir_sizes = false;
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.expect("Expected structured content");
+        assert!(json.get("total_size").is_none());
+        assert!(json["entries"][0].get("total_size").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_list_dir_follow_symlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let root = temp_dir.path().join("music");
+        fs::create_dir_all(root.join("nas/Album")).unwrap();
+        fs::write(root.join("nas/Album/01.flac"), "123").unwrap();
+        std::os::unix::fs::symlink(root.join("nas/Album"), root.join("Album")).unwrap();
+        std::os::unix::fs::symlink(&root, root.join("nas/loop")).unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.join("escape")).unwrap();
+
+        let mut config = test_config();
+        config.security.allowed_paths = vec![root.clone()];
+        let mut params = FSListDirParams {
+            path: root.to_string_lossy().to_string(),
+            include_hidden: false,
+            detailed: false,
+            recursive_depth: 3,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
+        };
+
+        // Symlinks are listed with their target, without children
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.unwrap();
+        let album = &json["entries"][0];
+        assert_eq!(album["name"], "Album");
+        assert_eq!(album["type"], "symlink");
+        assert_eq!(
+            album["target"],
+            root.join("nas/Album").to_string_lossy().as_ref()
+        );
+        assert!(album.get("children").is_none());
+
+        // Followed, a symlinked directory is listed and traversed as one
+        params.follow_symlinks = true;
+        let result = FsListDirTool::execute(&params, &config);
+        let json = result.structured_content.unwrap();
+        let album = &json["entries"][0];
+        assert_eq!(album["type"], "directory");
+        assert!(album.get("target").is_some());
+        assert_eq!(album["children"][0]["name"], "01.flac");
+        let warnings: Vec<&str> = json["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|w| w.as_str().unwrap())
+            .collect();
+        // The link out of the root stays blocked, and the loop back to it is cut
+        let escape = format!(
+            "Skipped '{}': security validation failed",
+            root.join("escape").display()
+        );
+        let looped = format!(
+            "Skipped '{}': symlink loop detected",
+            root.join("nas/loop").display()
+        );
+        assert!(warnings.contains(&escape.as_str()));
+        assert!(warnings.contains(&looped.as_str()));
+        // The real album folder is listed too
+        assert_eq!(json["file_count"], 2);
+    }
+
     #[cfg(feature = "http")]
     #[test]
     fn test_list_dir_http_handler() {
@@ -729,6 +1814,17 @@ mod tests {
             include_hidden: false,
             detailed: false,
             recursive_depth: 0,
+            extensions: None,
+            audio_only: false,
+            exclude: None,
+            sort_by: SortBy::Name,
+            sort_order: SortOrder::Asc,
+            offset: 0,
+            limit: None,
+            modified_after: None,
+            compute_dir_sizes: false,
+            follow_symlinks: false,
+            output: OutputMode::Tree,
         };
 
         let config = test_config();
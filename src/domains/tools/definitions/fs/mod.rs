@@ -1,7 +1,25 @@
+pub mod checksum;
+pub mod copy;
 pub mod delete;
+pub mod disk_usage;
+pub mod empty_dirs;
+pub(crate) mod exclude;
+pub(crate) mod files;
+pub mod find;
 pub mod list_dir;
+pub mod read_file;
 pub mod rename;
+pub mod stat;
+pub mod write_file;
 
+pub use checksum::FsChecksumTool;
+pub use copy::FsCopyTool;
 pub use delete::FsDeleteTool;
+pub use disk_usage::FsDiskUsageTool;
+pub use empty_dirs::FsEmptyDirsTool;
+pub use find::FsFindTool;
 pub use list_dir::FsListDirTool;
+pub use read_file::FsReadFileTool;
 pub use rename::FsRenameTool;
+pub use stat::FsStatTool;
+pub use write_file::FsWriteFileTool;
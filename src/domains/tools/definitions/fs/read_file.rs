@@ -0,0 +1,515 @@
+//! Read file tool definition.
+//!
+//! A tool that reads small text files (cue sheets, playlists, lyrics, notes).
+
+use futures::FutureExt;
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::domains::tools::blocking::run_blocking;
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+use crate::domains::tools::timeout::with_timeout;
+
+/// Upper bound for `max_bytes`, whatever the configured default.
+const MAX_READ_BYTES_LIMIT: usize = 8 * 1024 * 1024;
+
+/// Number of leading bytes searched for NUL bytes when sniffing binary files.
+const SNIFF_BYTES: usize = 8192;
+
+/// Signatures of binary formats likely to sit next to text files in a music library.
+const BINARY_SIGNATURES: &[(&[u8], &str)] = &[
+    (b"fLaC", "FLAC audio"),
+    (b"ID3", "MP3 audio"),
+    (b"OggS", "Ogg audio"),
+    (b"RIFF", "RIFF (WAV) audio"),
+    (b"FORM", "AIFF audio"),
+    (b"\x89PNG", "PNG image"),
+    (b"\xFF\xD8\xFF", "JPEG image"),
+    (b"GIF8", "GIF image"),
+    (b"%PDF", "PDF document"),
+    (b"PK\x03\x04", "ZIP archive"),
+    (b"\x7FELF", "executable"),
+];
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the read file tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct FsReadFileParams {
+    /// Path to the text file to read.
+    pub path: String,
+
+    /// Maximum number of bytes to read (default: MCP_MAX_READ_BYTES, 256 KiB; max: 8 MiB).
+    /// Longer files are truncated.
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
+
+    /// Text encoding: "utf-8" (default; a UTF-16 byte order mark is honored) or "latin-1"
+    /// for older cue sheets and playlists.
+    #[serde(default)]
+    pub encoding: TextEncoding,
+}
+
+/// Encoding used to decode a file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub enum TextEncoding {
+    /// UTF-8; invalid sequences are replaced with U+FFFD
+    #[default]
+    #[serde(rename = "utf-8", alias = "utf8")]
+    Utf8,
+    /// ISO-8859-1, one character per byte
+    #[serde(rename = "latin-1", alias = "latin1", alias = "iso-8859-1")]
+    Latin1,
+}
+
+// ============================================================================
+// Output Structure (JSON format for AI agents)
+// ============================================================================
+
+/// Result of a read file operation
+#[derive(Debug, Serialize, JsonSchema)]
+struct ReadFileResult {
+    /// Path that was read
+    path: String,
+    /// Decoded text
+    content: String,
+    /// Size of the file in bytes
+    size_bytes: u64,
+    /// Number of bytes read
+    bytes_read: usize,
+    /// Whether the file is longer than what was read
+    truncated: bool,
+    /// Number of lines in `content`
+    line_count: usize,
+    /// Encoding the text was decoded with ("utf-8", "utf-16le", "utf-16be" or "latin-1")
+    encoding: String,
+    /// Whether invalid byte sequences were replaced with U+FFFD
+    lossy: bool,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Read file tool - reads small text files.
+pub struct FsReadFileTool;
+
+impl FsReadFileTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "fs_read_file";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Read a small text file, e.g. a .cue sheet, .m3u playlist, .lrc lyrics or notes.txt. Returns the text, truncated to max_bytes, with its line count. Decodes UTF-8 (replacing invalid bytes) or Latin-1; binary files such as audio and images are rejected, use read_metadata for audio files.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    #[instrument(skip_all, fields(path = %params.path))]
+    pub fn execute(params: &FsReadFileParams, config: &Config) -> CallToolResult {
+        info!("Read file tool called: '{}'", params.path);
+
+        match Self::read_file(params, config) {
+            Ok(result) => {
+                // The text itself is the summary, so clients showing only text still get it
+                let mut text = result.content.clone();
+                if result.truncated {
+                    text.push_str(&format!(
+                        "\n[Truncated: {} of {} bytes read, raise max_bytes to read more]",
+                        result.bytes_read, result.size_bytes
+                    ));
+                }
+
+                // Return with text + structured content
+                CallToolResult {
+                    content: vec![Content::text(text)],
+                    structured_content: Some(serde_json::to_value(&result).unwrap()),
+                    is_error: Some(false),
+                    meta: None,
+                }
+            }
+            Err(e) => CallToolResult::error(vec![Content::text(e)]),
+        }
+    }
+
+    /// Validate the path, read up to `max_bytes` and decode them.
+    fn read_file(params: &FsReadFileParams, config: &Config) -> Result<ReadFileResult, String> {
+        let path = validate_path(&params.path, config).map_err(|e| {
+            warn!("Path security validation failed: {}", e);
+            format!("Path security validation failed: {}", e)
+        })?;
+        if !path.is_file() {
+            return Err(format!("Path is not a file: {}", params.path));
+        }
+
+        let max_bytes = params
+            .max_bytes
+            .unwrap_or(config.tools.max_read_bytes)
+            .clamp(1, MAX_READ_BYTES_LIMIT);
+        let mut file = fs::File::open(&path).map_err(|e| {
+            warn!("Failed to open '{}': {}", params.path, e);
+            if e.kind() == std::io::ErrorKind::PermissionDenied {
+                format!("Permission denied: Cannot read '{}'", params.path)
+            } else {
+                format!("Failed to read '{}': {}", params.path, e)
+            }
+        })?;
+        let size_bytes = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let mut bytes = Vec::new();
+        (&mut file)
+            .take(max_bytes as u64)
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to read '{}': {}", params.path, e))?;
+        let truncated = size_bytes > bytes.len() as u64;
+
+        if utf16_bom(&bytes).is_none()
+            && let Some(kind) = binary_kind(&bytes)
+        {
+            return Err(format!(
+                "'{}' looks like a binary file ({}), not text. Use read_metadata for audio files.",
+                params.path, kind
+            ));
+        }
+
+        let decoded = decode(&bytes, params.encoding, truncated);
+        Ok(ReadFileResult {
+            path: params.path.clone(),
+            line_count: decoded.text.lines().count(),
+            content: decoded.text,
+            size_bytes,
+            bytes_read: bytes.len(),
+            truncated,
+            encoding: decoded.encoding.to_string(),
+            lossy: decoded.lossy,
+        })
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: FsReadFileParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Read file tool (HTTP) called: '{}'", params.path);
+
+        let result = Self::execute(&params, &config);
+
+        // Serialize the full CallToolResult to preserve all fields including structuredContent
+        serde_json::to_value(&result).map_err(|e| e.to_string())
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<FsReadFileParams>(),
+            annotations: None,
+            output_schema: Some(schema_for_type::<ReadFileResult>()),
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: FsReadFileParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// Text decoded by [`decode`].
+struct Decoded {
+    text: String,
+    encoding: &'static str,
+    lossy: bool,
+}
+
+/// Whether the bytes start with a UTF-16 byte order mark, and which one.
+fn utf16_bom(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [0xFF, 0xFE, ..] => Some("utf-16le"),
+        [0xFE, 0xFF, ..] => Some("utf-16be"),
+        _ => None,
+    }
+}
+
+/// Name of the binary format the bytes look like: a known signature, or NUL bytes near the
+/// start. Text in UTF-16 must be ruled out first, since it is full of NUL bytes.
+fn binary_kind(bytes: &[u8]) -> Option<&'static str> {
+    if let Some((_, kind)) = BINARY_SIGNATURES
+        .iter()
+        .find(|(signature, _)| bytes.starts_with(signature))
+    {
+        return Some(kind);
+    }
+    if bytes.get(4..8) == Some(b"ftyp") {
+        return Some("MP4 audio/video");
+    }
+    // MPEG audio frame sync without an ID3 tag
+    if let [0xFF, second, ..] = bytes
+        && second & 0xE0 == 0xE0
+    {
+        return Some("MPEG audio");
+    }
+    bytes[..bytes.len().min(SNIFF_BYTES)]
+        .contains(&0)
+        .then_some("binary data")
+}
+
+/// Decode the bytes read. A UTF-16 byte order mark takes precedence over `encoding`; a
+/// UTF-8 character cut by truncation is dropped rather than replaced.
+fn decode(bytes: &[u8], encoding: TextEncoding, truncated: bool) -> Decoded {
+    if let Some(utf16) = utf16_bom(bytes) {
+        let units = bytes[2..].chunks_exact(2).map(|pair| match utf16 {
+            "utf-16le" => u16::from_le_bytes([pair[0], pair[1]]),
+            _ => u16::from_be_bytes([pair[0], pair[1]]),
+        });
+        let mut lossy = false;
+        let text = char::decode_utf16(units)
+            .map(|c| {
+                c.unwrap_or_else(|_| {
+                    lossy = true;
+                    char::REPLACEMENT_CHARACTER
+                })
+            })
+            .collect();
+        return Decoded {
+            text,
+            encoding: utf16,
+            lossy,
+        };
+    }
+
+    match encoding {
+        TextEncoding::Latin1 => Decoded {
+            text: bytes.iter().map(|&b| b as char).collect(),
+            encoding: "latin-1",
+            lossy: false,
+        },
+        TextEncoding::Utf8 => {
+            let mut bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+            if truncated {
+                bytes = trim_partial_char(bytes);
+            }
+            match std::str::from_utf8(bytes) {
+                Ok(text) => Decoded {
+                    text: text.to_string(),
+                    encoding: "utf-8",
+                    lossy: false,
+                },
+                Err(_) => Decoded {
+                    text: String::from_utf8_lossy(bytes).into_owned(),
+                    encoding: "utf-8",
+                    lossy: true,
+                },
+            }
+        }
+    }
+}
+
+/// Drop a UTF-8 character whose last bytes were cut off at the end.
+fn trim_partial_char(bytes: &[u8]) -> &[u8] {
+    let tail_start = bytes.len().saturating_sub(3);
+    let Some(start) = (tail_start..bytes.len())
+        .rev()
+        .find(|&i| bytes[i] & 0xC0 != 0x80)
+    else {
+        return bytes;
+    };
+    let width = match bytes[start] {
+        b if b >= 0xF0 => 4,
+        b if b >= 0xE0 => 3,
+        b if b >= 0xC0 => 2,
+        _ => 1,
+    };
+    if bytes.len() - start < width {
+        &bytes[..start]
+    } else {
+        bytes
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    fn read(
+        path: &std::path::Path,
+        max_bytes: Option<usize>,
+        encoding: TextEncoding,
+    ) -> Result<ReadFileResult, String> {
+        let params = FsReadFileParams {
+            path: path.to_string_lossy().to_string(),
+            max_bytes,
+            encoding,
+        };
+        FsReadFileTool::read_file(&params, &test_config())
+    }
+
+    #[test]
+    fn test_read_text_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("album.cue");
+        fs::write(&path, "\u{FEFF}TITLE \"Björk\"\nFILE \"01.flac\" WAVE\n").unwrap();
+
+        let result = read(&path, None, TextEncoding::Utf8).unwrap();
+        assert_eq!(result.content, "TITLE \"Björk\"\nFILE \"01.flac\" WAVE\n");
+        assert_eq!(result.line_count, 2);
+        assert_eq!(result.encoding, "utf-8");
+        assert!(!result.truncated && !result.lossy);
+    }
+
+    #[test]
+    fn test_read_truncated() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("notes.txt");
+        fs::write(&path, "abcé").unwrap();
+
+        // "é" is two bytes; the first alone is dropped, not replaced
+        let result = read(&path, Some(4), TextEncoding::Utf8).unwrap();
+        assert_eq!(result.content, "abc");
+        assert_eq!(result.bytes_read, 4);
+        assert_eq!(result.size_bytes, 5);
+        assert!(result.truncated && !result.lossy);
+
+        let params = FsReadFileParams {
+            path: path.to_string_lossy().to_string(),
+            max_bytes: Some(2),
+            encoding: TextEncoding::Utf8,
+        };
+        let result = FsReadFileTool::execute(&params, &test_config());
+        let text = match &result.content[0].raw {
+            rmcp::model::RawContent::Text(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+        assert!(text.starts_with("ab\n[Truncated: 2 of 5 bytes"), "{}", text);
+    }
+
+    #[test]
+    fn test_read_max_bytes_from_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("playlist.m3u");
+        fs::write(&path, "#EXTM3U\n01.flac\n").unwrap();
+
+        let mut config = test_config();
+        config.tools.max_read_bytes = 7;
+        let params = FsReadFileParams {
+            path: path.to_string_lossy().to_string(),
+            max_bytes: None,
+            encoding: TextEncoding::Utf8,
+        };
+        let result = FsReadFileTool::read_file(&params, &config).unwrap();
+        assert_eq!(result.content, "#EXTM3U");
+        assert!(result.truncated);
+    }
+
+    #[test]
+    fn test_read_encodings() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("old.cue");
+        fs::write(&path, b"PERFORMER \"Bj\xF6rk\"").unwrap();
+
+        let result = read(&path, None, TextEncoding::Utf8).unwrap();
+        assert_eq!(result.content, "PERFORMER \"Bj\u{FFFD}rk\"");
+        assert!(result.lossy);
+
+        let result = read(&path, None, TextEncoding::Latin1).unwrap();
+        assert_eq!(result.content, "PERFORMER \"Björk\"");
+        assert_eq!(result.encoding, "latin-1");
+        assert!(!result.lossy);
+
+        let mut utf16 = vec![0xFF, 0xFE];
+        utf16.extend("Björk\r\n".encode_utf16().flat_map(u16::to_le_bytes));
+        fs::write(&path, utf16).unwrap();
+        let result = read(&path, None, TextEncoding::Utf8).unwrap();
+        assert_eq!(result.content, "Björk\r\n");
+        assert_eq!(result.encoding, "utf-16le");
+        assert_eq!(result.line_count, 1);
+    }
+
+    #[test]
+    fn test_read_rejects_binary() {
+        let temp_dir = TempDir::new().unwrap();
+        let flac = temp_dir.path().join("01.flac");
+        fs::write(&flac, b"fLaC\0\0\0\x22").unwrap();
+        let err = read(&flac, None, TextEncoding::Utf8).unwrap_err();
+        assert!(
+            err.contains("FLAC audio") && err.contains("read_metadata"),
+            "{}",
+            err
+        );
+
+        let data = temp_dir.path().join("notes.txt");
+        fs::write(&data, b"text\0more").unwrap();
+        let err = read(&data, None, TextEncoding::Utf8).unwrap_err();
+        assert!(err.contains("binary data"), "{}", err);
+    }
+
+    #[test]
+    fn test_read_directory_or_missing() {
+        let temp_dir = TempDir::new().unwrap();
+        let err = read(temp_dir.path(), None, TextEncoding::Utf8).unwrap_err();
+        assert!(err.contains("not a file"), "{}", err);
+
+        let err = read(
+            &temp_dir.path().join("missing.txt"),
+            None,
+            TextEncoding::Utf8,
+        )
+        .unwrap_err();
+        assert!(err.contains("security validation failed"), "{}", err);
+    }
+
+    #[test]
+    fn test_encoding_param() {
+        let params: FsReadFileParams =
+            serde_json::from_value(serde_json::json!({ "path": "a.cue", "encoding": "latin1" }))
+                .unwrap();
+        assert_eq!(params.encoding, TextEncoding::Latin1);
+        let params: FsReadFileParams =
+            serde_json::from_value(serde_json::json!({ "path": "a.cue" })).unwrap();
+        assert_eq!(params.encoding, TextEncoding::Utf8);
+        assert_eq!(params.max_bytes, None);
+    }
+}
@@ -6,34 +6,87 @@ use futures::FutureExt;
 use rmcp::{
     ErrorData as McpError,
     handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
-    model::{CallToolResult, Content, Tool},
+    model::{CallToolResult, Content, JsonObject, Tool},
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fs;
-use std::path::Path;
+use std::io;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 use tracing::{info, instrument, warn};
 
+use crate::core::audit;
 use crate::core::config::Config;
-use crate::core::security::validate_path;
+use crate::core::security::{AccessKind, validate_path_for, validate_write_target};
+
+use crate::domains::tools::blocking::run_blocking;
+use super::copy::MAX_COPY_DEPTH;
+use crate::domains::tools::timeout::with_audited_timeout;
+
+/// Maximum number of numbered names tried by `on_conflict: "rename"`.
+const MAX_CONFLICT_ATTEMPTS: usize = 1000;
 
 // ============================================================================
 // Tool Parameters
 // ============================================================================
 
 /// Parameters for the rename/move tool.
-#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
 pub struct FsRenameParams {
-    /// Source path (file or directory to rename/move).
-    pub from: String,
+    /// Source path (file or directory to rename/move). Use with `to`, or use `renames`.
+    #[serde(default)]
+    pub from: Option<String>,
 
     /// Destination path (new name or location).
-    pub to: String,
+    #[serde(default)]
+    pub to: Option<String>,
 
-    /// Overwrite destination if it already exists.
+    /// Several renames in one call, processed in order, e.g. names computed per file.
+    /// Two mappings to the same destination are rejected before anything is renamed.
+    #[serde(default)]
+    pub renames: Option<Vec<RenameMapping>>,
+
+    /// Overwrite destination if it already exists. Applies to each rename.
+    /// Shorthand for on_conflict="overwrite".
     #[serde(default)]
     pub overwrite: bool,
+
+    /// What to do when the destination already exists: "error" (default, or "overwrite"
+    /// with overwrite=true), "overwrite", or "rename" to use a free numbered name.
+    #[serde(default)]
+    pub on_conflict: Option<OnConflict>,
+
+    /// Create the destination's missing parent directories first.
+    #[serde(default)]
+    pub create_parents: bool,
+
+    /// With `renames`, stop at the first failure instead of continuing with the rest.
+    #[serde(default)]
+    pub abort_on_error: bool,
+}
+
+/// What to do when the destination of a rename already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OnConflict {
+    /// Fail, leaving both items in place
+    Error,
+    /// Replace the existing destination
+    Overwrite,
+    /// Append " (1)", " (2)"... before the extension until the name is free
+    Rename,
+}
+
+/// A single rename of a batch.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct RenameMapping {
+    /// Source path
+    pub from: String,
+    /// Destination path
+    pub to: String,
 }
 
 // ============================================================================
@@ -42,20 +95,63 @@ pub struct FsRenameParams {
 
 /// Result of a rename/move operation
 #[derive(Debug, Serialize, JsonSchema)]
-struct RenameResult {
+pub(crate) struct RenameResult {
     /// Source path (original location)
     from: String,
-    /// Destination path (new location)
+    /// Destination path actually used (new location)
     to: String,
+    /// Destination asked for, when it existed and a numbered name was used instead
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requested_to: Option<String>,
     /// Type of item renamed ("file", "directory", or "item")
     item_type: String,
-    /// Type of operation performed ("renamed" or "moved")
+    /// Type of operation performed ("renamed", "moved", or "moved (copied across filesystems)")
     operation: String,
     /// Whether the operation succeeded
     success: bool,
     /// Whether an existing file was overwritten
     #[serde(skip_serializing_if = "Option::is_none")]
     overwritten: Option<bool>,
+    /// Parent directories created for the destination, outermost first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    parents_created: Vec<String>,
+}
+
+/// Result of a batch of renames
+#[derive(Debug, Serialize, JsonSchema)]
+struct BatchRenameResult {
+    /// Outcome of each mapping, in request order
+    results: Vec<MappingRenameResult>,
+    total_count: usize,
+    success_count: usize,
+    failure_count: usize,
+    /// Whether the batch stopped at a failure (abort_on_error), leaving later mappings undone
+    aborted: bool,
+}
+
+/// Outcome of a single mapping of the batch
+#[derive(Debug, Serialize, JsonSchema)]
+struct MappingRenameResult {
+    /// Source path as given in the request
+    from: String,
+    /// Destination path as given in the request
+    to: String,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<RenameResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Any of the structured results this tool can return.
+///
+/// Only used to describe the output schema; a call emits one of the result types directly.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum RenameOutput {
+    Single(RenameResult),
+    Batch(BatchRenameResult),
 }
 
 // ============================================================================
@@ -70,69 +166,186 @@ impl FsRenameTool {
     pub const NAME: &'static str = "fs_rename";
 
     /// Tool description shown to clients.
-    pub const DESCRIPTION: &'static str = "Rename or move a file or directory from one path to another. Can also be used to move items between directories.";
+    pub const DESCRIPTION: &'static str = "Rename or move a file or directory from one path to another, or several at once with `renames` (a list of {from, to}, each reported separately). Can also be used to move items between directories, creating missing destination directories with create_parents=true.";
 
     /// Execute the tool logic (for STDIO/TCP transport via rmcp).
-    #[instrument(skip_all, fields(from = %params.from, to = %params.to))]
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
+    #[instrument(skip_all, fields(from = ?params.from, to = ?params.to))]
     pub fn execute(params: &FsRenameParams, config: &Config) -> CallToolResult {
-        info!("Rename tool called: '{}' -> '{}'", params.from, params.to);
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
 
-        // Validate source path security
-        let from_path = match validate_path(&params.from, config) {
-            Ok(p) => p,
-            Err(e) => {
-                warn!("Source path security validation failed: {}", e);
-                return CallToolResult::error(vec![Content::text(format!(
-                    "Source path security validation failed: {}",
-                    e
-                ))]);
-            }
-        };
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &FsRenameParams) -> Vec<&str> {
+        params
+            .from
+            .iter()
+            .chain(&params.to)
+            .map(String::as_str)
+            .chain(
+                params
+                    .renames
+                    .iter()
+                    .flatten()
+                    .flat_map(|rename| [rename.from.as_str(), rename.to.as_str()]),
+            )
+            .collect()
+    }
 
-        // Validate destination path security
-        // Note: For destination, we validate the parent directory since the file might not exist yet
-        let to_path = Path::new(&params.to);
-
-        // If destination exists, validate it directly
-        // If it doesn't exist, validate that its parent is within bounds
-        if to_path.exists() {
-            match validate_path(&params.to, config) {
-                Ok(_) => {},
-                Err(e) => {
-                    warn!("Destination path security validation failed: {}", e);
-                    return CallToolResult::error(vec![Content::text(format!(
-                        "Destination path security validation failed: {}",
-                        e
-                    ))]);
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &FsRenameParams, config: &Config) -> CallToolResult {
+        match (&params.from, &params.to, &params.renames) {
+            (Some(from), Some(to), None) => {
+                info!("Rename tool called: '{}' -> '{}'", from, to);
+                match Self::rename_path(from, to, params, config) {
+                    Ok(result) => CallToolResult {
+                        content: vec![Content::text(Self::summary(&result))],
+                        structured_content: Some(serde_json::to_value(&result).unwrap()),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(e) => CallToolResult::error(vec![Content::text(e)]),
                 }
             }
-        } else {
-            // Validate parent directory for non-existent destinations
-            if let Some(parent) = to_path.parent() {
-                let parent_str = parent.to_string_lossy().to_string();
-                match validate_path(&parent_str, config) {
-                    Ok(_) => {},
-                    Err(e) => {
-                        warn!("Destination parent directory security validation failed: {}", e);
-                        return CallToolResult::error(vec![Content::text(format!(
-                            "Destination parent directory security validation failed: {}",
-                            e
-                        ))]);
+            (None, None, Some(renames)) if renames.is_empty() => {
+                CallToolResult::error(vec![Content::text("renames is empty")])
+            }
+            (None, None, Some(renames)) => {
+                info!("Rename tool called for {} mappings", renames.len());
+                match Self::rename_batch(renames, params, config) {
+                    Ok(result) => {
+                        let mut summary = format!(
+                            "Renamed {}/{} item(s) ({} failed)",
+                            result.success_count, result.total_count, result.failure_count
+                        );
+                        if result.aborted {
+                            summary.push_str("; stopped at the first failure");
+                        }
+                        info!("{}", summary);
+
+                        // Failures are reported per mapping; the call itself succeeded
+                        CallToolResult {
+                            content: vec![Content::text(summary)],
+                            structured_content: Some(serde_json::to_value(&result).unwrap()),
+                            is_error: Some(false),
+                            meta: None,
+                        }
                     }
+                    Err(e) => CallToolResult::error(vec![Content::text(e)]),
                 }
             }
+            (_, _, Some(_)) => CallToolResult::error(vec![Content::text(
+                "Provide either from and to, or renames, not both",
+            )]),
+            (_, _, None) => CallToolResult::error(vec![Content::text(
+                "Provide from and to, or a list of renames",
+            )]),
         }
+    }
 
-        // Check if destination already exists (track for result)
-        let destination_exists = to_path.exists();
-        if destination_exists && !params.overwrite {
-            warn!("Destination already exists: {}", params.to);
-            return CallToolResult::error(vec![Content::text(format!(
-                "Destination already exists: {}. Use overwrite=true to replace it.",
-                params.to
-            ))]);
+    /// Human-readable summary of a single rename.
+    fn summary(result: &RenameResult) -> String {
+        let mut summary = format!(
+            "Successfully {} {} from '{}' to '{}'",
+            result.operation, result.item_type, result.from, result.to
+        );
+        if let Some(requested) = &result.requested_to {
+            summary.push_str(&format!(" ('{}' already existed)", requested));
+        }
+        if !result.parents_created.is_empty() {
+            summary.push_str(&format!(
+                " (created {} parent director{})",
+                result.parents_created.len(),
+                if result.parents_created.len() == 1 {
+                    "y"
+                } else {
+                    "ies"
+                }
+            ));
+        }
+        summary
+    }
+
+    /// Rename each mapping in order, after checking that no two target the same destination.
+    fn rename_batch(
+        renames: &[RenameMapping],
+        params: &FsRenameParams,
+        config: &Config,
+    ) -> Result<BatchRenameResult, String> {
+        let mut destinations: HashMap<PathBuf, usize> = HashMap::new();
+        for (index, mapping) in renames.iter().enumerate() {
+            if let Some(first) = destinations.insert(resolve_destination(&mapping.to), index) {
+                return Err(format!(
+                    "renames[{}] and renames[{}] both target '{}'; nothing was renamed",
+                    first, index, mapping.to
+                ));
+            }
         }
 
+        let mut results = Vec::with_capacity(renames.len());
+        let mut aborted = false;
+        for mapping in renames {
+            let outcome = if aborted {
+                Err("Not attempted: stopped after an earlier failure".to_string())
+            } else {
+                Self::rename_path(&mapping.from, &mapping.to, params, config)
+            };
+            aborted |= outcome.is_err() && params.abort_on_error;
+            results.push(match outcome {
+                Ok(result) => MappingRenameResult {
+                    from: mapping.from.clone(),
+                    to: mapping.to.clone(),
+                    success: true,
+                    result: Some(result),
+                    error: None,
+                },
+                Err(error) => MappingRenameResult {
+                    from: mapping.from.clone(),
+                    to: mapping.to.clone(),
+                    success: false,
+                    result: None,
+                    error: Some(error),
+                },
+            });
+        }
+
+        let success_count = results.iter().filter(|r| r.success).count();
+        Ok(BatchRenameResult {
+            total_count: results.len(),
+            success_count,
+            failure_count: results.len() - success_count,
+            aborted,
+            results,
+        })
+    }
+
+    /// Validate both paths and rename/move the source to the destination.
+    ///
+    /// Shared with tools that rename files in bulk (e.g. rename_from_metadata).
+    pub(crate) fn rename_path(
+        from: &str,
+        to: &str,
+        params: &FsRenameParams,
+        config: &Config,
+    ) -> Result<RenameResult, String> {
+        // Validate source path security
+        let from_path = validate_path_for(from, AccessKind::Write, config).map_err(|e| {
+            warn!("Source path security validation failed: {}", e);
+            format!("Source path security validation failed: {}", e)
+        })?;
+
+        let to_path = Path::new(to);
+        let parents_created = if params.create_parents {
+            create_parent_dirs(to_path, config)?
+        } else {
+            Vec::new()
+        };
+        validate_destination(to, config).inspect_err(|_| remove_dirs(&parents_created))?;
+
         // Get source type for response message
         let source_type = if from_path.is_dir() {
             "directory"
@@ -142,68 +355,86 @@ impl FsRenameTool {
             "item"
         };
 
+        // Check if destination already exists (track for result)
+        let on_conflict = params.on_conflict.unwrap_or(if params.overwrite {
+            OnConflict::Overwrite
+        } else {
+            OnConflict::Error
+        });
+        let mut destination_exists = to_path.exists();
+        let mut requested_to = None;
+        let numbered_to;
+        let (to, to_path) = match on_conflict {
+            OnConflict::Error if destination_exists => {
+                warn!("Destination already exists: {}", to);
+                return Err(format!(
+                    "Destination already exists: {}. Use overwrite=true to replace it, or on_conflict=\"rename\" to keep both.",
+                    to
+                ));
+            }
+            OnConflict::Rename if destination_exists => {
+                numbered_to = free_destination(to, source_type == "directory")?;
+                info!("'{}' already exists, using '{}'", to, numbered_to);
+                requested_to = Some(to.to_string());
+                destination_exists = false;
+                (numbered_to.as_str(), Path::new(numbered_to.as_str()))
+            }
+            _ => (to, to_path),
+        };
+
         // Check if this is a move (different parent directory) or just a rename
         let is_move = from_path.parent() != to_path.parent();
         let operation = if is_move { "moved" } else { "renamed" };
 
-        // Perform the rename/move operation
-        match fs::rename(from_path, to_path) {
+        // Perform the rename/move operation, copying when the destination is on another mount
+        let mut performed = operation;
+        let mut renamed = fs::rename(&from_path, to_path);
+        if let Err(e) = &renamed
+            && is_cross_device(e)
+        {
+            info!(
+                "'{}' and '{}' are on different filesystems, copying instead",
+                from, to
+            );
+            performed = "moved (copied across filesystems)";
+            renamed = move_across_filesystems(&from_path, to_path);
+        }
+        match renamed {
             Ok(_) => {
-                info!(
-                    "Successfully {} '{}' to '{}'",
-                    operation, params.from, params.to
-                );
-
-                // Create human-readable summary
-                let summary = format!(
-                    "Successfully {} {} from '{}' to '{}'",
-                    operation, source_type, params.from, params.to
-                );
-
-                // Create structured result
-                let result = RenameResult {
-                    from: params.from.clone(),
-                    to: params.to.clone(),
+                info!("Successfully {} '{}' to '{}'", performed, from, to);
+
+                Ok(RenameResult {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    requested_to,
                     item_type: source_type.to_string(),
-                    operation: operation.to_string(),
+                    operation: performed.to_string(),
                     success: true,
-                    overwritten: if destination_exists && params.overwrite {
+                    overwritten: if destination_exists && on_conflict == OnConflict::Overwrite {
                         Some(true)
                     } else {
                         None
                     },
-                };
-
-                // Return with text summary + structured content
-                CallToolResult {
-                    content: vec![Content::text(summary)],
-                    structured_content: Some(serde_json::to_value(&result).unwrap()),
-                    is_error: Some(false),
-                    meta: None,
-                }
+                    parents_created,
+                })
             }
             Err(e) => {
-                warn!(
-                    "Failed to {} '{}' to '{}': {}",
-                    operation, params.from, params.to, e
-                );
+                warn!("Failed to {} '{}' to '{}': {}", operation, from, to, e);
+                remove_dirs(&parents_created);
 
                 // Provide more helpful error messages
                 let error_msg = if e.kind() == std::io::ErrorKind::PermissionDenied {
                     format!(
                         "Permission denied: Cannot {} '{}' to '{}'",
-                        operation, params.from, params.to
+                        operation, from, to
                     )
                 } else if e.kind() == std::io::ErrorKind::NotFound {
-                    format!("Path not found: '{}'", params.from)
+                    format!("Path not found: '{}'", from)
                 } else {
-                    format!(
-                        "Failed to {} '{}' to '{}': {}",
-                        operation, params.from, params.to, e
-                    )
+                    format!("Failed to {} '{}' to '{}': {}", operation, from, to, e)
                 };
 
-                CallToolResult::error(vec![Content::text(error_msg)])
+                Err(error_msg)
             }
         }
     }
@@ -217,26 +448,54 @@ impl FsRenameTool {
         let from = arguments
             .get("from")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| "Missing or invalid 'from' parameter".to_string())?
-            .to_string();
+            .map(String::from);
 
         let to = arguments
             .get("to")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| "Missing or invalid 'to' parameter".to_string())?
-            .to_string();
+            .map(String::from);
+
+        let renames = arguments
+            .get("renames")
+            .map(|v| serde_json::from_value::<Vec<RenameMapping>>(v.clone()))
+            .transpose()
+            .map_err(|e| format!("Invalid 'renames' parameter: {}", e))?;
+
+        if renames.is_none() && (from.is_none() || to.is_none()) {
+            return Err("Missing or invalid 'from' and 'to' or 'renames' parameter".to_string());
+        }
 
         let overwrite = arguments
             .get("overwrite")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        info!("Rename tool (HTTP) called: '{}' -> '{}'", from, to);
+        let on_conflict = arguments
+            .get("on_conflict")
+            .map(|v| serde_json::from_value::<OnConflict>(v.clone()))
+            .transpose()
+            .map_err(|e| format!("Invalid 'on_conflict' parameter: {}", e))?;
+
+        let create_parents = arguments
+            .get("create_parents")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let abort_on_error = arguments
+            .get("abort_on_error")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        info!("Rename tool (HTTP) called: {:?} -> {:?}", from, to);
 
         let params = FsRenameParams {
             from,
             to,
+            renames,
             overwrite,
+            on_conflict,
+            create_parents,
+            abort_on_error,
         };
 
         let result = Self::execute(&params, &config);
@@ -252,18 +511,26 @@ impl FsRenameTool {
             description: Some(Self::DESCRIPTION.into()),
             input_schema: schema_for_type::<FsRenameParams>(),
             annotations: None,
-            output_schema: Some(schema_for_type::<RenameResult>()),
+            output_schema: Some(Self::output_schema()),
             icons: None,
             meta: None,
             title: None,
         }
     }
 
+    /// Output schema covering both single and batch results.
+    fn output_schema() -> Arc<JsonObject> {
+        let mut schema = (*schema_for_type::<RenameOutput>()).clone();
+        schema.insert("type".to_string(), serde_json::json!("object"));
+        Arc::new(schema)
+    }
+
     /// Create a ToolRoute for STDIO/TCP transport.
     pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
     where
         S: Send + Sync + 'static,
     {
+        let timeout = config.tools.timeout_for(Self::NAME);
         ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             let config = config.clone();
@@ -271,13 +538,274 @@ impl FsRenameTool {
                 let params: FsRenameParams =
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
-                Ok(Self::execute(&params, &config))
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
             }
             .boxed()
         })
     }
 }
 
+/// Validate the security of a destination path that may not exist yet, returning the
+/// resolved path that writing to it reaches.
+///
+/// The destination itself is validated, whether or not it exists, so deny patterns see
+/// its name and a symlink, even a dangling one, cannot lead out of the roots. Shared with
+/// tools that write to a new path (e.g. fs_copy). Its parent directory must exist.
+pub(crate) fn validate_destination(to: &str, config: &Config) -> Result<PathBuf, String> {
+    let resolved = validate_write_target(to, config).map_err(|e| {
+        warn!("Destination path security validation failed: {}", e);
+        format!("Destination path security validation failed: {}", e)
+    })?;
+    if let Some(parent) = resolved.parent()
+        && !parent.is_dir()
+    {
+        return Err(format!(
+            "Destination parent directory does not exist: {}",
+            parent.display()
+        ));
+    }
+    Ok(resolved)
+}
+
+/// Where a destination will end up, to tell whether two destinations are the same path:
+/// its parent is resolved when it exists.
+fn resolve_destination(to: &str) -> PathBuf {
+    let to_path = Path::new(to);
+    match (to_path.parent().map(fs::canonicalize), to_path.file_name()) {
+        (Some(Ok(parent)), Some(name)) => parent.join(name),
+        _ => to_path.to_path_buf(),
+    }
+}
+
+/// The first of `to (1)`, `to (2)`... that does not exist, the number going before the
+/// extension of a file, or an error after [`MAX_CONFLICT_ATTEMPTS`] names.
+fn free_destination(to: &str, is_directory: bool) -> Result<String, String> {
+    let to_path = Path::new(to);
+    let name = to_path.file_name().unwrap_or_default().to_string_lossy();
+    let (stem, ext) = match (is_directory, to_path.file_stem(), to_path.extension()) {
+        (false, Some(stem), Some(ext)) => (
+            stem.to_string_lossy(),
+            format!(".{}", ext.to_string_lossy()),
+        ),
+        _ => (name, String::new()),
+    };
+
+    (1..=MAX_CONFLICT_ATTEMPTS)
+        .map(|number| to_path.with_file_name(format!("{} ({}){}", stem, number, ext)))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .map(|candidate| candidate.to_string_lossy().into_owned())
+        .ok_or_else(|| {
+            format!(
+                "No free name found for '{}' after {} attempts",
+                to, MAX_CONFLICT_ATTEMPTS
+            )
+        })
+}
+
+/// Create the missing parent directories of `to_path`, returning those created.
+///
+/// The nearest existing ancestor is validated before anything is created, and the final
+/// parent once it exists; on failure the directories created are removed again.
+fn create_parent_dirs(to_path: &Path, config: &Config) -> Result<Vec<String>, String> {
+    let Some(parent) = to_path.parent().filter(|p| !p.as_os_str().is_empty()) else {
+        return Ok(Vec::new());
+    };
+    let mut missing: Vec<&Path> = parent
+        .ancestors()
+        .take_while(|p| !p.as_os_str().is_empty() && !p.exists())
+        .collect();
+    if missing.is_empty() {
+        return Ok(Vec::new());
+    }
+    missing.reverse();
+
+    // `..` after a missing directory cannot be resolved, and could climb out of the root
+    if missing
+        .iter()
+        .any(|dir| dir.components().next_back() == Some(Component::ParentDir))
+    {
+        return Err(format!(
+            "Cannot create parent directories of '{}': path contains '..'",
+            to_path.display()
+        ));
+    }
+
+    if let Some(ancestor) = missing[0].parent().filter(|p| !p.as_os_str().is_empty()) {
+        validate_path_for(&ancestor.to_string_lossy(), AccessKind::Write, config).map_err(|e| {
+            warn!("Destination ancestor security validation failed: {}", e);
+            format!("Destination ancestor security validation failed: {}", e)
+        })?;
+    }
+
+    let mut created = Vec::new();
+    for dir in missing {
+        if let Err(e) = fs::create_dir(dir) {
+            remove_dirs(&created);
+            return Err(format!(
+                "Failed to create directory '{}': {}",
+                dir.display(),
+                e
+            ));
+        }
+        created.push(dir.to_string_lossy().into_owned());
+    }
+
+    if let Err(e) = validate_path_for(&parent.to_string_lossy(), AccessKind::Write, config) {
+        warn!(
+            "Destination parent directory security validation failed: {}",
+            e
+        );
+        remove_dirs(&created);
+        return Err(format!(
+            "Destination parent directory security validation failed: {}",
+            e
+        ));
+    }
+
+    info!(
+        "Created {} parent director(ies) for '{}'",
+        created.len(),
+        to_path.display()
+    );
+    Ok(created)
+}
+
+/// Whether a rename failed because the destination is on another filesystem
+/// (`EXDEV` on Unix, `ERROR_NOT_SAME_DEVICE` on Windows).
+fn is_cross_device(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::CrossesDevices
+}
+
+/// Move `from` to `to` by copying it then removing the source, for a destination on
+/// another filesystem.
+///
+/// The copy is made under a temporary name next to the destination and only renamed into
+/// place once complete, so if any part of it fails, the partial copy is removed and the
+/// source is left intact.
+fn move_across_filesystems(from: &Path, to: &Path) -> io::Result<()> {
+    let name = to
+        .file_name()
+        .ok_or_else(|| io::Error::other("destination has no file name"))?;
+    let mut temp_name = OsString::from(".");
+    temp_name.push(name);
+    temp_name.push(format!(".{}.moving", std::process::id()));
+    let temp = to.with_file_name(temp_name);
+
+    if let Err(e) = copy_tree(from, &temp, 0).and_then(|_| fs::rename(&temp, to)) {
+        let cleanup = match fs::symlink_metadata(&temp) {
+            Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(&temp),
+            Ok(_) => fs::remove_file(&temp),
+            Err(_) => Ok(()),
+        };
+        if let Err(cleanup) = cleanup {
+            warn!(
+                "Failed to remove partial copy '{}': {}",
+                temp.display(),
+                cleanup
+            );
+        }
+        return Err(e);
+    }
+
+    let removed = if from.is_dir() {
+        fs::remove_dir_all(from)
+    } else {
+        fs::remove_file(from)
+    };
+    removed.map_err(|e| {
+        io::Error::other(format!(
+            "copied to the destination, but could not remove the source: {}",
+            e
+        ))
+    })
+}
+
+/// Copy a file, symlink or directory tree for [`move_across_filesystems`], stopping at the
+/// first error.
+///
+/// File sizes are checked against the source, and permissions and modification times are
+/// preserved. Symlinks are recreated rather than followed.
+fn copy_tree(from: &Path, to: &Path, depth: usize) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(from)?;
+    if metadata.is_symlink() {
+        return copy_symlink(from, to);
+    }
+
+    if metadata.is_dir() {
+        if depth > MAX_COPY_DEPTH {
+            return Err(io::Error::other(format!(
+                "'{}' is deeper than {} levels",
+                from.display(),
+                MAX_COPY_DEPTH
+            )));
+        }
+        fs::create_dir(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_tree(&entry.path(), &to.join(entry.file_name()), depth + 1)?;
+        }
+        // Directory times can't be set through a handle on every platform
+        if let Ok(modified) = metadata.modified()
+            && let Ok(dir) = fs::File::open(to)
+        {
+            let _ = dir.set_times(fs::FileTimes::new().set_modified(modified));
+        }
+        return fs::set_permissions(to, metadata.permissions());
+    }
+
+    let mut source = fs::File::open(from)?;
+    let mut target = fs::File::create_new(to)?;
+    let copied = io::copy(&mut source, &mut target)?;
+    target.sync_all()?;
+    let written = target.metadata()?.len();
+    if copied != metadata.len() || written != metadata.len() {
+        return Err(io::Error::other(format!(
+            "size mismatch copying '{}': {} bytes expected, {} written",
+            from.display(),
+            metadata.len(),
+            written
+        )));
+    }
+    if let Ok(modified) = metadata.modified() {
+        target.set_times(fs::FileTimes::new().set_modified(modified))?;
+    }
+    target.set_permissions(metadata.permissions())
+}
+
+/// Recreate a symlink with the same target.
+#[cfg(unix)]
+fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(from)?, to)
+}
+
+/// Recreate a symlink with the same target.
+#[cfg(not(unix))]
+fn copy_symlink(from: &Path, _to: &Path) -> io::Result<()> {
+    Err(io::Error::other(format!(
+        "cannot copy symlink '{}' across filesystems",
+        from.display()
+    )))
+}
+
+/// Remove directories created by `create_parent_dirs`, innermost first (best effort).
+fn remove_dirs(created: &[String]) {
+    for dir in created.iter().rev() {
+        if let Err(e) = fs::remove_dir(dir) {
+            warn!("Failed to remove created directory '{}': {}", dir, e);
+        }
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -286,6 +814,7 @@ impl FsRenameTool {
 mod tests {
     use super::*;
     use std::fs;
+    use std::time::{Duration, SystemTime};
     use tempfile::TempDir;
 
     fn test_config() -> Config {
@@ -303,9 +832,10 @@ mod tests {
         fs::write(&old_file, "test content").unwrap();
 
         let params = FsRenameParams {
-            from: old_file.to_string_lossy().to_string(),
-            to: new_file.to_string_lossy().to_string(),
+            from: Some(old_file.to_string_lossy().to_string()),
+            to: Some(new_file.to_string_lossy().to_string()),
             overwrite: false,
+            ..Default::default()
         };
 
         let config = test_config();
@@ -330,9 +860,10 @@ mod tests {
         fs::write(old_dir.join("file.txt"), "content").unwrap();
 
         let params = FsRenameParams {
-            from: old_dir.to_string_lossy().to_string(),
-            to: new_dir.to_string_lossy().to_string(),
+            from: Some(old_dir.to_string_lossy().to_string()),
+            to: Some(new_dir.to_string_lossy().to_string()),
             overwrite: false,
+            ..Default::default()
         };
 
         let config = test_config();
@@ -359,9 +890,10 @@ mod tests {
         fs::create_dir(&dest_dir).unwrap();
 
         let params = FsRenameParams {
-            from: source_file.to_string_lossy().to_string(),
-            to: dest_file.to_string_lossy().to_string(),
+            from: Some(source_file.to_string_lossy().to_string()),
+            to: Some(dest_file.to_string_lossy().to_string()),
             overwrite: false,
+            ..Default::default()
         };
 
         let config = test_config();
@@ -376,9 +908,10 @@ mod tests {
     #[test]
     fn test_rename_nonexistent_source() {
         let params = FsRenameParams {
-            from: "/nonexistent/file.txt".to_string(),
-            to: "/some/other/path.txt".to_string(),
+            from: Some("/nonexistent/file.txt".to_string()),
+            to: Some("/some/other/path.txt".to_string()),
             overwrite: false,
+            ..Default::default()
         };
 
         let config = test_config();
@@ -404,9 +937,10 @@ mod tests {
         fs::write(&file2, "content2").unwrap();
 
         let params = FsRenameParams {
-            from: file1.to_string_lossy().to_string(),
-            to: file2.to_string_lossy().to_string(),
+            from: Some(file1.to_string_lossy().to_string()),
+            to: Some(file2.to_string_lossy().to_string()),
             overwrite: false,
+            ..Default::default()
         };
 
         let config = test_config();
@@ -432,9 +966,10 @@ mod tests {
         fs::write(&file2, "content2").unwrap();
 
         let params = FsRenameParams {
-            from: file1.to_string_lossy().to_string(),
-            to: file2.to_string_lossy().to_string(),
+            from: Some(file1.to_string_lossy().to_string()),
+            to: Some(file2.to_string_lossy().to_string()),
             overwrite: true,
+            ..Default::default()
         };
 
         let config = test_config();
@@ -447,6 +982,295 @@ mod tests {
         assert_eq!(fs::read_to_string(&file2).unwrap(), "content1");
     }
 
+    #[test]
+    fn test_rename_create_parents() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("track.flac");
+        fs::write(&source, "audio").unwrap();
+        let album = temp_dir.path().join("Artist").join("Album");
+        let dest = album.join("01 - Track.flac");
+
+        let from = source.to_string_lossy().to_string();
+        let to = dest.to_string_lossy().to_string();
+        let mut params = FsRenameParams::default();
+        let config = test_config();
+        assert!(FsRenameTool::rename_path(&from, &to, &params, &config).is_err());
+        assert!(!album.exists());
+
+        params.create_parents = true;
+        let result = FsRenameTool::rename_path(&from, &to, &params, &config).unwrap();
+        assert_eq!(
+            result.parents_created,
+            vec![
+                temp_dir.path().join("Artist").to_string_lossy().to_string(),
+                album.to_string_lossy().to_string(),
+            ]
+        );
+        assert!(!source.exists());
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "audio");
+
+        // Nothing to create the second time
+        fs::write(&source, "audio").unwrap();
+        let to = album.join("02 - Track.flac").to_string_lossy().to_string();
+        let result = FsRenameTool::rename_path(&from, &to, &params, &config).unwrap();
+        assert!(result.parents_created.is_empty());
+    }
+
+    #[test]
+    fn test_rename_create_parents_outside_root() {
+        let root = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let source = root.path().join("track.flac");
+        fs::write(&source, "audio").unwrap();
+        let mut config = test_config();
+        config.security.allowed_paths = vec![root.path().to_path_buf()];
+
+        let targets = [
+            format!("{}/Album/track.flac", outside.path().display()),
+            format!("{}/Album/../../escaped/track.flac", root.path().display()),
+        ];
+        let params = FsRenameParams {
+            create_parents: true,
+            ..Default::default()
+        };
+        for to in targets {
+            let from = source.to_string_lossy();
+            assert!(FsRenameTool::rename_path(&from, &to, &params, &config).is_err());
+        }
+        assert!(source.exists());
+        assert!(!outside.path().join("Album").exists());
+        assert!(!root.path().join("Album").exists());
+    }
+
+    #[test]
+    fn test_rename_on_conflict() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("cover.jpg"), "old").unwrap();
+        fs::write(dir.join("cover (1).jpg"), "older").unwrap();
+        fs::write(dir.join("new.jpg"), "new").unwrap();
+        let to = dir.join("cover.jpg").to_string_lossy().to_string();
+
+        let mut params = FsRenameParams {
+            from: Some(dir.join("new.jpg").to_string_lossy().to_string()),
+            to: Some(to.clone()),
+            on_conflict: Some(OnConflict::Error),
+            ..Default::default()
+        };
+        assert_eq!(
+            FsRenameTool::execute(&params, &test_config()).is_error,
+            Some(true)
+        );
+
+        params.on_conflict = Some(OnConflict::Rename);
+        let result = FsRenameTool::execute(&params, &test_config());
+        let json = result.structured_content.unwrap();
+        let used = dir.join("cover (2).jpg");
+        assert_eq!(json["to"], used.to_string_lossy().as_ref());
+        assert_eq!(json["requested_to"], to);
+        assert!(json.get("overwritten").is_none());
+        assert_eq!(fs::read_to_string(&used).unwrap(), "new");
+        assert_eq!(fs::read_to_string(dir.join("cover.jpg")).unwrap(), "old");
+
+        // Directories are numbered after their whole name
+        fs::create_dir(dir.join("Album.2024")).unwrap();
+        fs::create_dir(dir.join("Other")).unwrap();
+        let result = FsRenameTool::rename_path(
+            &dir.join("Other").to_string_lossy(),
+            &dir.join("Album.2024").to_string_lossy(),
+            &params,
+            &test_config(),
+        )
+        .unwrap();
+        assert_eq!(
+            result.to,
+            dir.join("Album.2024 (1)").to_string_lossy().as_ref()
+        );
+
+        // on_conflict takes precedence over overwrite
+        params.from = Some(used.to_string_lossy().to_string());
+        params.overwrite = true;
+        params.on_conflict = Some(OnConflict::Overwrite);
+        let json = FsRenameTool::execute(&params, &test_config())
+            .structured_content
+            .unwrap();
+        assert_eq!(json["to"], to);
+        assert_eq!(json["overwritten"], true);
+        assert_eq!(fs::read_to_string(dir.join("cover.jpg")).unwrap(), "new");
+    }
+
+    fn mapping(from: &Path, to: &Path) -> RenameMapping {
+        RenameMapping {
+            from: from.to_string_lossy().to_string(),
+            to: to.to_string_lossy().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rename_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        for name in ["a.flac", "b.flac", "c.flac"] {
+            fs::write(dir.join(name), name).unwrap();
+        }
+
+        let mut params = FsRenameParams {
+            renames: Some(vec![
+                mapping(&dir.join("a.flac"), &dir.join("01 - A.flac")),
+                mapping(&dir.join("missing.flac"), &dir.join("02 - B.flac")),
+                mapping(&dir.join("c.flac"), &dir.join("03 - C.flac")),
+            ]),
+            ..Default::default()
+        };
+        let result = FsRenameTool::execute(&params, &test_config());
+        assert_eq!(result.is_error, Some(false));
+        let json = result.structured_content.unwrap();
+        assert_eq!(json["total_count"], 3);
+        assert_eq!(json["success_count"], 2);
+        assert_eq!(json["failure_count"], 1);
+        assert_eq!(json["aborted"], false);
+        assert_eq!(json["results"][0]["result"]["operation"], "renamed");
+        assert_eq!(json["results"][1]["success"], false);
+        assert!(dir.join("01 - A.flac").exists());
+        assert!(dir.join("03 - C.flac").exists());
+
+        // Stop at the first failure
+        params.abort_on_error = true;
+        params.renames = Some(vec![
+            mapping(&dir.join("missing.flac"), &dir.join("00 - Intro.flac")),
+            mapping(&dir.join("b.flac"), &dir.join("02 - B.flac")),
+        ]);
+        let json = FsRenameTool::execute(&params, &test_config())
+            .structured_content
+            .unwrap();
+        assert_eq!(json["success_count"], 0);
+        assert_eq!(json["aborted"], true);
+        assert!(
+            json["results"][1]["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("Not attempted")
+        );
+        assert!(dir.join("b.flac").exists());
+    }
+
+    #[test]
+    fn test_rename_batch_duplicate_destination() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("a.flac"), "a").unwrap();
+        fs::write(dir.join("b.flac"), "b").unwrap();
+
+        let params = FsRenameParams {
+            renames: Some(vec![
+                mapping(&dir.join("a.flac"), &dir.join("track.flac")),
+                mapping(&dir.join("b.flac"), &dir.join("./track.flac")),
+            ]),
+            ..Default::default()
+        };
+        let result = FsRenameTool::execute(&params, &test_config());
+        assert_eq!(result.is_error, Some(true));
+        let text = match &result.content[0].raw {
+            rmcp::model::RawContent::Text(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+        assert!(text.contains("renames[0] and renames[1]"), "{}", text);
+        assert!(dir.join("a.flac").exists());
+        assert!(!dir.join("track.flac").exists());
+
+        // A single rename and a batch can't be mixed
+        let params = FsRenameParams {
+            from: Some(dir.join("a.flac").to_string_lossy().to_string()),
+            to: Some(dir.join("c.flac").to_string_lossy().to_string()),
+            renames: Some(Vec::new()),
+            ..Default::default()
+        };
+        let result = FsRenameTool::execute(&params, &test_config());
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_move_across_filesystems() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("Album");
+        fs::create_dir_all(source.join("CD1")).unwrap();
+        fs::write(source.join("CD1/01 - Track.flac"), "audio").unwrap();
+        fs::write(source.join("cover.jpg"), "jpeg").unwrap();
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        fs::File::options()
+            .write(true)
+            .open(source.join("cover.jpg"))
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("cover.jpg", source.join("folder.jpg")).unwrap();
+
+        let dest = temp_dir.path().join("Moved");
+        move_across_filesystems(&source, &dest).unwrap();
+
+        assert!(!source.exists());
+        assert_eq!(
+            fs::read_to_string(dest.join("CD1/01 - Track.flac")).unwrap(),
+            "audio"
+        );
+        let cover = fs::metadata(dest.join("cover.jpg")).unwrap();
+        assert_eq!(cover.len(), 4);
+        assert_eq!(cover.modified().unwrap(), modified);
+        #[cfg(unix)]
+        assert_eq!(
+            fs::read_link(dest.join("folder.jpg")).unwrap(),
+            Path::new("cover.jpg")
+        );
+        // Only the destination is left, no temporary copy
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_move_across_filesystems_failure_keeps_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("Deep");
+        let mut deepest = source.clone();
+        for _ in 0..=MAX_COPY_DEPTH + 1 {
+            deepest.push("d");
+        }
+        fs::create_dir_all(&deepest).unwrap();
+        fs::write(deepest.join("track.flac"), "audio").unwrap();
+        fs::write(source.join("cover.jpg"), "jpeg").unwrap();
+
+        let dest = temp_dir.path().join("Moved");
+        let err = move_across_filesystems(&source, &dest).unwrap_err();
+        assert!(err.to_string().contains("deeper than"), "{}", err);
+
+        assert!(deepest.join("track.flac").exists());
+        assert!(source.join("cover.jpg").exists());
+        assert!(!dest.exists());
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    /// Moves a file from the temporary directory to /dev/shm, a separate tmpfs mount on Linux.
+    #[cfg(target_os = "linux")]
+    #[test]
+    #[ignore]
+    fn test_rename_across_mounts() {
+        let temp_dir = TempDir::new().unwrap();
+        let shm_dir = TempDir::new_in("/dev/shm").unwrap();
+        let source = temp_dir.path().join("track.flac");
+        fs::write(&source, "audio").unwrap();
+        let dest = shm_dir.path().join("track.flac");
+
+        let result = FsRenameTool::rename_path(
+            &source.to_string_lossy(),
+            &dest.to_string_lossy(),
+            &FsRenameParams::default(),
+            &test_config(),
+        )
+        .unwrap();
+        assert_eq!(result.operation, "moved (copied across filesystems)");
+        assert!(!source.exists());
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "audio");
+    }
+
     #[cfg(feature = "http")]
     #[test]
     fn test_rename_http_handler() {
@@ -491,9 +1315,10 @@ mod tests {
         fs::write(&old_file, "test").unwrap();
 
         let params = FsRenameParams {
-            from: old_file.to_string_lossy().to_string(),
-            to: new_file.to_string_lossy().to_string(),
+            from: Some(old_file.to_string_lossy().to_string()),
+            to: Some(new_file.to_string_lossy().to_string()),
             overwrite: false,
+            ..Default::default()
         };
 
         let config = test_config();
@@ -505,8 +1330,8 @@ mod tests {
         let structured = result.structured_content.unwrap();
 
         // Verify fields
-        assert_eq!(structured["from"], params.from);
-        assert_eq!(structured["to"], params.to);
+        assert_eq!(structured["from"], params.from.unwrap());
+        assert_eq!(structured["to"], params.to.unwrap());
         assert_eq!(structured["item_type"], "file");
         assert_eq!(structured["operation"], "renamed");
         assert_eq!(structured["success"], true);
@@ -531,9 +1356,10 @@ mod tests {
         fs::write(&existing_file, "existing").unwrap();
 
         let params = FsRenameParams {
-            from: old_file.to_string_lossy().to_string(),
-            to: existing_file.to_string_lossy().to_string(),
+            from: Some(old_file.to_string_lossy().to_string()),
+            to: Some(existing_file.to_string_lossy().to_string()),
             overwrite: true,
+            ..Default::default()
         };
 
         let config = test_config();
@@ -555,9 +1381,10 @@ mod tests {
         fs::write(&old_file, "data").unwrap();
 
         let params = FsRenameParams {
-            from: old_file.to_string_lossy().to_string(),
-            to: new_file.to_string_lossy().to_string(),
+            from: Some(old_file.to_string_lossy().to_string()),
+            to: Some(new_file.to_string_lossy().to_string()),
             overwrite: false,
+            ..Default::default()
         };
 
         let config = test_config();
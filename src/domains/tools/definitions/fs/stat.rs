@@ -0,0 +1,296 @@
+//! Stat tool definition.
+//!
+//! A tool that returns the details of a single file or directory.
+
+use futures::FutureExt;
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool, ToolAnnotations},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+use crate::core::time::format_time;
+use crate::domains::tools::definitions::mb::identify_directory::MbIdentifyDirectoryTool;
+
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_timeout;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the stat tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct FsStatParams {
+    /// Path of the file or directory.
+    pub path: String,
+}
+
+// ============================================================================
+// Output Structure (JSON format for AI agents)
+// ============================================================================
+
+/// Details of a file or directory
+#[derive(Debug, Serialize, JsonSchema)]
+struct StatResult {
+    /// Path as given in the request
+    path: String,
+    /// Type of item ("file" or "directory"), after following a symlink
+    item_type: String,
+    /// Size in bytes (files only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size_bytes: Option<u64>,
+    /// Creation time, RFC 3339 (not available on every filesystem)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created: Option<String>,
+    /// Last modification time, RFC 3339
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modified: Option<String>,
+    /// Last access time, RFC 3339
+    #[serde(skip_serializing_if = "Option::is_none")]
+    accessed: Option<String>,
+    /// Whether the item is read-only
+    readonly: bool,
+    /// Unix permission bits in octal, e.g. "644" (Unix only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<String>,
+    /// Whether the path is a symlink
+    is_symlink: bool,
+    /// Where the symlink points, as stored in the link
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symlink_target: Option<String>,
+    /// Whether the item is a file with an audio extension
+    is_audio: bool,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Stat tool - returns the details of a single path.
+pub struct FsStatTool;
+
+impl FsStatTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "fs_stat";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Get the details of a single file or directory without listing its parent: type, size, created/modified/accessed times (RFC 3339), permissions, symlink target, and whether it is an audio file.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    #[instrument(skip_all, fields(path = %params.path))]
+    pub fn execute(params: &FsStatParams, config: &Config) -> CallToolResult {
+        info!("Stat tool called: '{}'", params.path);
+
+        match Self::stat(params, config) {
+            Ok(result) => {
+                let mut summary = format!("'{}': {}", params.path, result.item_type);
+                if let Some(size) = result.size_bytes {
+                    summary.push_str(&format!(", {} bytes", size));
+                }
+                if let Some(modified) = &result.modified {
+                    summary.push_str(&format!(", modified {}", modified));
+                }
+                if let Some(target) = &result.symlink_target {
+                    summary.push_str(&format!(", symlink to '{}'", target));
+                }
+
+                // Return with text summary + structured content
+                CallToolResult {
+                    content: vec![Content::text(summary)],
+                    structured_content: Some(serde_json::to_value(&result).unwrap()),
+                    is_error: Some(false),
+                    meta: None,
+                }
+            }
+            Err(e) => CallToolResult::error(vec![Content::text(e)]),
+        }
+    }
+
+    /// Validate the path and collect its details.
+    fn stat(params: &FsStatParams, config: &Config) -> Result<StatResult, String> {
+        let path = validate_path(&params.path, config).map_err(|e| {
+            warn!("Path security validation failed: {}", e);
+            format!("Path security validation failed: {}", e)
+        })?;
+
+        let metadata = fs::metadata(&path)
+            .map_err(|e| format!("Failed to read metadata of '{}': {}", params.path, e))?;
+        // The validated path is resolved, so look at the requested one for the link itself
+        let symlink_target = fs::symlink_metadata(&params.path)
+            .is_ok_and(|m| m.is_symlink())
+            .then(|| fs::read_link(&params.path).ok())
+            .flatten()
+            .map(|target| target.to_string_lossy().into_owned());
+        let item_type = if metadata.is_dir() {
+            "directory"
+        } else {
+            "file"
+        };
+
+        Ok(StatResult {
+            path: params.path.clone(),
+            item_type: item_type.to_string(),
+            size_bytes: metadata.is_file().then_some(metadata.len()),
+            created: metadata.created().ok().map(format_time),
+            modified: metadata.modified().ok().map(format_time),
+            accessed: metadata.accessed().ok().map(format_time),
+            readonly: metadata.permissions().readonly(),
+            mode: unix_mode(&metadata),
+            is_symlink: symlink_target.is_some(),
+            symlink_target,
+            is_audio: metadata.is_file() && MbIdentifyDirectoryTool::is_audio_file(&path),
+        })
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: FsStatParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Stat tool (HTTP) called: '{}'", params.path);
+
+        let result = Self::execute(&params, &config);
+
+        // Serialize the full CallToolResult to preserve all fields including structuredContent
+        serde_json::to_value(&result).map_err(|e| e.to_string())
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<FsStatParams>(),
+            annotations: Some(ToolAnnotations::new().read_only(true)),
+            output_schema: Some(schema_for_type::<StatResult>()),
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: FsStatParams = serde_json::from_value(serde_json::Value::Object(args))
+                    .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// Permission bits in octal, on Unix.
+#[cfg(unix)]
+fn unix_mode(metadata: &fs::Metadata) -> Option<String> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(format!("{:o}", metadata.permissions().mode() & 0o7777))
+}
+
+/// Permission bits in octal, on Unix.
+#[cfg(not(unix))]
+fn unix_mode(_metadata: &fs::Metadata) -> Option<String> {
+    None
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    fn stat(path: &Path) -> Result<StatResult, String> {
+        let params = FsStatParams {
+            path: path.to_string_lossy().to_string(),
+        };
+        FsStatTool::stat(&params, &test_config())
+    }
+
+    #[test]
+    fn test_stat_file_and_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("01 - Track.FLAC");
+        fs::write(&path, "not really audio").unwrap();
+
+        let result = stat(&path).unwrap();
+        assert_eq!(result.item_type, "file");
+        assert_eq!(result.size_bytes, Some(16));
+        assert!(result.is_audio);
+        assert!(!result.is_symlink);
+        let modified = result.modified.unwrap();
+        assert!(modified.ends_with('Z'), "{}", modified);
+        #[cfg(unix)]
+        assert!(result.mode.is_some());
+
+        let result = stat(temp_dir.path()).unwrap();
+        assert_eq!(result.item_type, "directory");
+        assert_eq!(result.size_bytes, None);
+        assert!(!result.is_audio);
+
+        assert!(stat(&temp_dir.path().join("missing.flac")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stat_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("cover.jpg");
+        fs::write(&target, "jpeg").unwrap();
+        let link = temp_dir.path().join("folder.jpg");
+        std::os::unix::fs::symlink("cover.jpg", &link).unwrap();
+
+        let result = stat(&link).unwrap();
+        assert!(result.is_symlink);
+        assert_eq!(result.symlink_target.as_deref(), Some("cover.jpg"));
+        assert_eq!(result.item_type, "file");
+        assert_eq!(result.size_bytes, Some(4));
+        assert!(!result.is_audio);
+    }
+
+    #[test]
+    fn test_stat_outside_root() {
+        let root = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let mut config = test_config();
+        config.security.allowed_paths = vec![root.path().to_path_buf()];
+
+        let params = FsStatParams {
+            path: outside.path().to_string_lossy().to_string(),
+        };
+        let err = FsStatTool::stat(&params, &config).unwrap_err();
+        assert!(err.contains("security validation failed"), "{}", err);
+    }
+}
@@ -0,0 +1,357 @@
+//! Write file tool definition.
+//!
+//! A tool that writes small text files (playlists, album READMEs, notes).
+
+use futures::FutureExt;
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool, ToolAnnotations},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::audit;
+use crate::core::config::Config;
+
+use crate::domains::tools::blocking::run_blocking;
+use super::rename::validate_destination;
+use crate::domains::tools::timeout::with_audited_timeout;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the write file tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FsWriteFileParams {
+    /// Path of the file to write. Its parent directory must exist.
+    pub path: String,
+
+    /// Text to write, as UTF-8 (at most MCP_MAX_WRITE_BYTES, 1 MiB by default).
+    pub content: String,
+
+    /// Replace the file if it already exists.
+    #[serde(default)]
+    pub overwrite: bool,
+
+    /// Add the content to the end of the file if it already exists.
+    /// Cannot be combined with overwrite.
+    #[serde(default)]
+    pub append: bool,
+}
+
+// ============================================================================
+// Output Structure (JSON format for AI agents)
+// ============================================================================
+
+/// Result of a write file operation
+#[derive(Debug, Serialize, JsonSchema)]
+struct WriteFileResult {
+    /// Path that was written
+    path: String,
+    /// What happened to the file ("created", "replaced" or "appended")
+    action: String,
+    /// Number of bytes written
+    bytes_written: usize,
+    /// Size of the file after the write, in bytes
+    size_bytes: u64,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Write file tool - writes small text files.
+pub struct FsWriteFileTool;
+
+impl FsWriteFileTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "fs_write_file";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Write a small UTF-8 text file, e.g. an .m3u playlist, an album README or notes. Creates the file in an existing directory; use overwrite=true to replace an existing file or append=true to add to its end.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
+    #[instrument(skip_all, fields(path = %params.path))]
+    pub fn execute(params: &FsWriteFileParams, config: &Config) -> CallToolResult {
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
+
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &FsWriteFileParams) -> Vec<&str> {
+        vec![params.path.as_str()]
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &FsWriteFileParams, config: &Config) -> CallToolResult {
+        info!("Write file tool called: '{}'", params.path);
+
+        match Self::write_file(params, config) {
+            Ok(result) => {
+                let summary = format!(
+                    "{} file '{}': {} bytes written",
+                    match result.action.as_str() {
+                        "created" => "Created",
+                        "replaced" => "Replaced",
+                        _ => "Appended to",
+                    },
+                    params.path,
+                    result.bytes_written
+                );
+
+                // Return with text summary + structured content
+                CallToolResult {
+                    content: vec![Content::text(summary)],
+                    structured_content: Some(serde_json::to_value(&result).unwrap()),
+                    is_error: Some(false),
+                    meta: None,
+                }
+            }
+            Err(e) => CallToolResult::error(vec![Content::text(e)]),
+        }
+    }
+
+    /// Validate the destination and write the content.
+    fn write_file(params: &FsWriteFileParams, config: &Config) -> Result<WriteFileResult, String> {
+        if params.overwrite && params.append {
+            return Err("overwrite and append cannot be used together".to_string());
+        }
+        let max_bytes = config.tools.max_write_bytes;
+        if params.content.len() > max_bytes {
+            return Err(format!(
+                "Content too large: {} bytes (max {} bytes, see MCP_MAX_WRITE_BYTES)",
+                params.content.len(),
+                max_bytes
+            ));
+        }
+
+        // Written through the resolved path, never the one given
+        let path = validate_destination(&params.path, config)?;
+
+        let exists = path.exists();
+        if path.is_dir() {
+            return Err(format!("Path is a directory: {}", params.path));
+        }
+        if exists && !params.overwrite && !params.append {
+            warn!("File already exists: {}", params.path);
+            return Err(format!(
+                "File already exists: {}. Use overwrite=true to replace it or append=true to add to it.",
+                params.path
+            ));
+        }
+
+        let write = || -> std::io::Result<u64> {
+            let mut file = fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .append(params.append)
+                .truncate(!params.append)
+                .open(&path)?;
+            file.write_all(params.content.as_bytes())?;
+            Ok(file.metadata()?.len())
+        };
+        let size_bytes = write().map_err(|e| {
+            warn!("Failed to write '{}': {}", params.path, e);
+            if e.kind() == std::io::ErrorKind::PermissionDenied {
+                format!("Permission denied: Cannot write '{}'", params.path)
+            } else {
+                format!("Failed to write '{}': {}", params.path, e)
+            }
+        })?;
+
+        let action = match (exists, params.append) {
+            (false, _) => "created",
+            (true, false) => "replaced",
+            (true, true) => "appended",
+        };
+        info!(
+            "Wrote {} bytes to '{}' ({})",
+            params.content.len(),
+            params.path,
+            action
+        );
+        Ok(WriteFileResult {
+            path: params.path.clone(),
+            action: action.to_string(),
+            bytes_written: params.content.len(),
+            size_bytes,
+        })
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: FsWriteFileParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Write file tool (HTTP) called: '{}'", params.path);
+
+        let result = Self::execute(&params, &config);
+
+        // Serialize the full CallToolResult to preserve all fields including structuredContent
+        serde_json::to_value(&result).map_err(|e| e.to_string())
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<FsWriteFileParams>(),
+            // Lets clients that only allow safe tools leave this one out
+            annotations: Some(ToolAnnotations::new().read_only(false).destructive(true)),
+            output_schema: Some(schema_for_type::<WriteFileResult>()),
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: FsWriteFileParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    fn params(path: &Path, content: &str, overwrite: bool, append: bool) -> FsWriteFileParams {
+        FsWriteFileParams {
+            path: path.to_string_lossy().to_string(),
+            content: content.to_string(),
+            overwrite,
+            append,
+        }
+    }
+
+    #[test]
+    fn test_write_create_replace_append() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("album.m3u");
+        let config = test_config();
+
+        let result =
+            FsWriteFileTool::write_file(&params(&path, "01.flac\n", false, false), &config)
+                .unwrap();
+        assert_eq!(result.action, "created");
+        assert_eq!(result.bytes_written, 8);
+
+        // Existing files are kept unless overwrite or append is set
+        let err =
+            FsWriteFileTool::write_file(&params(&path, "x", false, false), &config).unwrap_err();
+        assert!(err.contains("File already exists"));
+
+        let result =
+            FsWriteFileTool::write_file(&params(&path, "02.flac\n", false, true), &config).unwrap();
+        assert_eq!(result.action, "appended");
+        assert_eq!(result.size_bytes, 16);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "01.flac\n02.flac\n");
+
+        let result =
+            FsWriteFileTool::write_file(&params(&path, "03.flac\n", true, false), &config).unwrap();
+        assert_eq!(result.action, "replaced");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "03.flac\n");
+    }
+
+    #[test]
+    fn test_write_rejections() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config();
+        config.tools.max_write_bytes = 4;
+
+        let path = temp_dir.path().join("notes.txt");
+        let err = FsWriteFileTool::write_file(&params(&path, "12345", false, false), &config)
+            .unwrap_err();
+        assert!(err.contains("Content too large"), "{}", err);
+        assert!(!path.exists());
+
+        let err =
+            FsWriteFileTool::write_file(&params(&path, "1", true, true), &config).unwrap_err();
+        assert!(err.contains("cannot be used together"), "{}", err);
+
+        let err = FsWriteFileTool::write_file(&params(temp_dir.path(), "1", true, false), &config)
+            .unwrap_err();
+        assert!(err.contains("Path is a directory"), "{}", err);
+    }
+
+    #[test]
+    fn test_write_outside_root() {
+        let root = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let mut config = test_config();
+        config.security.allowed_paths = vec![root.path().to_path_buf()];
+
+        let path = outside.path().join("notes.txt");
+        let err =
+            FsWriteFileTool::write_file(&params(&path, "x", false, false), &config).unwrap_err();
+        assert!(err.contains("security validation failed"), "{}", err);
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_through_dangling_symlink() {
+        let root = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let mut config = test_config();
+        config.security.allowed_paths = vec![root.path().to_path_buf()];
+
+        let target = outside.path().join("pwned.txt");
+        let link = root.path().join("link.txt");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let err =
+            FsWriteFileTool::write_file(&params(&link, "x", false, false), &config).unwrap_err();
+        assert!(err.contains("security validation failed"), "{}", err);
+        assert!(!target.exists());
+    }
+}
@@ -0,0 +1,440 @@
+//! AcoustID fingerprint submission tool.
+//!
+//! This tool contributes a fingerprint → MusicBrainz recording mapping back to
+//! the AcoustID database, after a file has been identified and tagged.
+
+use futures::FutureExt;
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Tool},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, error, info, instrument};
+
+use super::common::{error_result, extract_mbid, structured_result};
+use super::identify_record::{
+    DEFAULT_FINGERPRINT_LENGTH_SECS, FingerprintData, MbIdentifyRecordTool,
+};
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+use crate::domains::tools::timeout::with_timeout;
+
+// ============================================================================
+// Configuration & Constants
+// ============================================================================
+
+const ACOUSTID_SUBMIT_URL: &str = "https://api.acoustid.org/v2/submit";
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the AcoustID submission tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MbAcoustidSubmitParams {
+    /// Path to the audio file to fingerprint and submit
+    pub file_path: String,
+
+    /// MusicBrainz Recording ID (UUID) the audio corresponds to
+    pub recording_mbid: String,
+
+    /// Track title (optional)
+    #[serde(default)]
+    pub track: Option<String>,
+
+    /// Track artist (optional)
+    #[serde(default)]
+    pub artist: Option<String>,
+
+    /// Album title (optional)
+    #[serde(default)]
+    pub album: Option<String>,
+
+    /// Album artist (optional)
+    #[serde(default)]
+    pub album_artist: Option<String>,
+
+    /// Release year (optional)
+    #[serde(default)]
+    pub year: Option<u32>,
+
+    /// Track number on the disc (optional)
+    #[serde(default)]
+    pub track_number: Option<u32>,
+
+    /// Disc number (optional)
+    #[serde(default)]
+    pub disc_number: Option<u32>,
+}
+
+// ============================================================================
+// Structured Output
+// ============================================================================
+
+/// Structured output for a fingerprint submission.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AcoustidSubmitResult {
+    pub file: String,
+    pub recording_mbid: String,
+    /// Submission ID, usable to check the import status with AcoustID
+    pub submission_id: u64,
+    /// Submission status reported by AcoustID (e.g. "pending", "imported")
+    pub status: String,
+    pub duration: u32,
+}
+
+// ============================================================================
+// AcoustID API Response Structures
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct SubmitResponse {
+    status: String,
+    #[serde(default)]
+    submissions: Vec<SubmitSubmission>,
+    error: Option<SubmitError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmitSubmission {
+    id: u64,
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmitError {
+    message: String,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// AcoustID fingerprint submission tool.
+pub struct MbAcoustidSubmitTool;
+
+impl MbAcoustidSubmitTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "mb_acoustid_submit";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Submit an audio file's fingerprint to AcoustID, linked to a MusicBrainz recording.\n\
+         \n\
+         Use after a file has been identified and verified, to contribute the\n\
+         fingerprint → recording mapping back to the AcoustID database.\n\
+         Requires a personal AcoustID API key (MCP_ACOUSTID_API_KEY) and user key\n\
+         (MCP_ACOUSTID_USER_KEY); the default public key is refused.\n\
+         \n\
+         Returns the submission ID and status (submissions are imported asynchronously).";
+
+    /// Create a new tool instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Execute the tool logic.
+    #[instrument(skip_all, fields(file_path = %params.file_path, mbid = %params.recording_mbid))]
+    pub async fn execute(params: &MbAcoustidSubmitParams, config: &Config) -> CallToolResult {
+        info!("Starting AcoustID submission");
+
+        match Self::submit(params, config).await {
+            Ok(result) => {
+                let summary = format!(
+                    "Submitted fingerprint for {} as recording {} (submission {}, status: {})",
+                    result.file, result.recording_mbid, result.submission_id, result.status
+                );
+                info!("{}", summary);
+                structured_result(summary, result)
+            }
+            Err(e) => {
+                error!("AcoustID submission failed: {}", e);
+                error_result(&e)
+            }
+        }
+    }
+
+    /// Validate inputs, fingerprint the file and submit it.
+    async fn submit(
+        params: &MbAcoustidSubmitParams,
+        config: &Config,
+    ) -> Result<AcoustidSubmitResult, String> {
+        if !config.credentials.has_custom_acoustid_key() {
+            return Err(
+                "Submitting fingerprints requires your own AcoustID API key. \
+                 Set MCP_ACOUSTID_API_KEY (get a key at https://acoustid.org/api-key)"
+                    .to_string(),
+            );
+        }
+        let api_key = config
+            .credentials
+            .acoustid_api_key
+            .as_deref()
+            .unwrap_or_default();
+
+        let user_key = config
+            .credentials
+            .acoustid_user_key
+            .as_deref()
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| {
+                "Submitting fingerprints requires an AcoustID user key. \
+                 Set MCP_ACOUSTID_USER_KEY (shown at https://acoustid.org/api-key after signing in)"
+                    .to_string()
+            })?;
+
+        let recording_mbid = extract_mbid(&params.recording_mbid).ok_or_else(|| {
+            format!(
+                "Invalid recording MBID: '{}'. Expected a UUID.",
+                params.recording_mbid
+            )
+        })?;
+
+        validate_path(&params.file_path, config)
+            .map_err(|e| format!("Path security validation failed: {}", e))?;
+        MbIdentifyRecordTool::validate_file(&params.file_path).map_err(|e| e.to_string())?;
+        MbIdentifyRecordTool::ensure_fpcalc_installed(&config.tools)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let (fingerprint, _) = MbIdentifyRecordTool::fingerprint_with_cache(
+            &params.file_path,
+            DEFAULT_FINGERPRINT_LENGTH_SECS,
+            &config.tools,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let client = MbIdentifyRecordTool::build_http_client().map_err(|e| e.to_string())?;
+        let submission = Self::post_submission(
+            &client,
+            api_key,
+            user_key,
+            &fingerprint,
+            &recording_mbid,
+            params,
+        )
+        .await?;
+
+        Ok(AcoustidSubmitResult {
+            file: params.file_path.clone(),
+            recording_mbid,
+            submission_id: submission.id,
+            status: submission.status,
+            duration: fingerprint.duration,
+        })
+    }
+
+    /// POST a single submission to AcoustID.
+    ///
+    /// Not retried: a submission that reached the server but whose response
+    /// was lost would otherwise be recorded twice.
+    async fn post_submission(
+        client: &reqwest::Client,
+        api_key: &str,
+        user_key: &str,
+        fingerprint: &FingerprintData,
+        recording_mbid: &str,
+        params: &MbAcoustidSubmitParams,
+    ) -> Result<SubmitSubmission, String> {
+        let mut form: Vec<(&str, String)> = vec![
+            ("format", "json".to_string()),
+            ("client", api_key.to_string()),
+            ("user", user_key.to_string()),
+            ("duration.0", fingerprint.duration.to_string()),
+            ("fingerprint.0", fingerprint.fingerprint.clone()),
+            ("mbid.0", recording_mbid.to_string()),
+        ];
+
+        let optional = [
+            ("track.0", params.track.clone()),
+            ("artist.0", params.artist.clone()),
+            ("album.0", params.album.clone()),
+            ("albumartist.0", params.album_artist.clone()),
+            ("year.0", params.year.map(|v| v.to_string())),
+            ("trackno.0", params.track_number.map(|v| v.to_string())),
+            ("discno.0", params.disc_number.map(|v| v.to_string())),
+        ];
+        form.extend(
+            optional
+                .into_iter()
+                .filter_map(|(key, value)| value.map(|v| (key, v))),
+        );
+
+        let response = client
+            .post(ACOUSTID_SUBMIT_URL)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| format!("AcoustID submit request failed: {}", e))?;
+
+        let status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| format!("Failed to read AcoustID response: {}", e))?;
+
+        debug!("Submit response ({}): {} bytes", status, body.len());
+
+        let parsed: SubmitResponse = serde_json::from_slice(&body)
+            .map_err(|e| format!("Invalid AcoustID response ({}): {}", status, e))?;
+
+        if parsed.status != "ok" {
+            let message = parsed
+                .error
+                .map(|e| e.message)
+                .unwrap_or_else(|| format!("HTTP {}", status));
+            return Err(format!("AcoustID rejected the submission: {}", message));
+        }
+
+        parsed
+            .submissions
+            .into_iter()
+            .next()
+            .ok_or_else(|| "AcoustID response contained no submission".to_string())
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: MbAcoustidSubmitParams =
+            serde_json::from_value(arguments).map_err(|e| format!("Invalid parameters: {}", e))?;
+
+        // Run on a dedicated thread with its own runtime: this handler is called
+        // synchronously from within the HTTP server's async context.
+        let handle = std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map(|runtime| runtime.block_on(Self::execute(&params, &config)))
+        });
+
+        let result = handle
+            .join()
+            .map_err(|_| "Submission thread panicked".to_string())?
+            .map_err(|e| format!("Failed to start submission runtime: {}", e))?;
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<MbAcoustidSubmitParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: MbAcoustidSubmitParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+                with_timeout(Self::NAME, timeout, async {
+                    Ok(Self::execute(&params, &config).await)
+                })
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+impl Default for MbAcoustidSubmitTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> MbAcoustidSubmitParams {
+        serde_json::from_value(serde_json::json!({
+            "file_path": "/nonexistent/track.flac",
+            "recording_mbid": "b1a9c0e9-d987-4042-ae91-78d6a3267d69"
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_refuses_default_api_key() {
+        let mut config = Config::default();
+        config.credentials.acoustid_user_key = Some("user_key".to_string());
+
+        let result = MbAcoustidSubmitTool::execute(&params(), &config).await;
+        assert!(result.is_error.unwrap_or(false));
+    }
+
+    #[tokio::test]
+    async fn test_requires_user_key() {
+        let mut config = Config::default();
+        config.credentials.acoustid_api_key = Some("my_app_key".to_string());
+
+        let err = MbAcoustidSubmitTool::submit(&params(), &config)
+            .await
+            .unwrap_err();
+        assert!(err.contains("MCP_ACOUSTID_USER_KEY"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_mbid() {
+        let mut config = Config::default();
+        config.credentials.acoustid_api_key = Some("my_app_key".to_string());
+        config.credentials.acoustid_user_key = Some("user_key".to_string());
+
+        let mut params = params();
+        params.recording_mbid = "not-a-uuid".to_string();
+
+        let err = MbAcoustidSubmitTool::submit(&params, &config)
+            .await
+            .unwrap_err();
+        assert!(err.contains("Invalid recording MBID"));
+    }
+
+    #[test]
+    fn test_parse_submit_response() {
+        let json = r#"{"status": "ok", "submissions": [{"index": "0", "id": 123456, "status": "pending"}]}"#;
+        let response: SubmitResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.status, "ok");
+        assert_eq!(response.submissions[0].id, 123456);
+        assert_eq!(response.submissions[0].status, "pending");
+    }
+}
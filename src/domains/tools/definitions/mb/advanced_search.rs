@@ -0,0 +1,987 @@
+//! MusicBrainz advanced search tool.
+//!
+//! This tool searches any of the main MusicBrainz entity types through a
+//! single interface, returning the same structured results as the
+//! entity-specific search tools.
+
+use futures::FutureExt;
+use musicbrainz_rs::{
+    Search,
+    entity::{
+        artist::{Artist, ArtistSearchQuery},
+        label::{Label, LabelSearchQuery},
+        recording::{Recording, RecordingSearchQuery},
+        release::{Release, ReleaseSearchQuery},
+        release_group::{ReleaseGroup, ReleaseGroupSearchQuery},
+        work::{Work, WorkSearchQuery},
+    },
+};
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, JsonObject, Tool},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info};
+
+use super::artist::{ArtistSearchInfo, ArtistSearchResult};
+use super::common::{
+    best_match_suffix, default_limit, error_result, extract_year, format_duration, get_artist_name,
+    search_with_scores, secondary_type_names, structured_result, validate_limit,
+};
+use super::label::{LabelInfo, LabelSearchResult};
+use super::recording::{RecordingSearchInfo, RecordingSearchResult};
+use super::release::{
+    ReleaseGroupSearchInfo, ReleaseGroupSearchResult, ReleaseSearchInfo, ReleaseSearchResult,
+};
+use super::work::{WorkInfo, WorkSearchResult};
+use crate::core::config::Config;
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::timeout::with_timeout;
+
+/// Parameters for advanced search operations.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MbAdvancedSearchParams {
+    /// The entity type to search.
+    #[schemars(
+        description = "Entity type: 'artist', 'release', 'release_group', 'recording', 'work' or 'label'"
+    )]
+    pub entity: String,
+
+    /// The search query string.
+    #[schemars(
+        description = "Search query, matched against the entity's name or title. With raw=true, a full Lucene expression. May be empty when filters are given"
+    )]
+    #[serde(default)]
+    pub query: String,
+
+    /// Pass the query to MusicBrainz unmodified as a Lucene expression.
+    #[schemars(description = r#"
+        When true, 'query' is sent to MusicBrainz as-is instead of being matched against the name/title field (default: false).
+        Lucene rules apply:
+        - Fields: artist:"Miles Davis", date:[1959 TO 1961], country:US, status:official
+        - Operators AND, OR, NOT must be uppercase; group with parentheses
+        - Quote multi-word values: artist:"Pink Floyd"
+        - Escape these characters with a backslash when they are part of a value: + - && || ! ( ) { } [ ] ^ " ~ * ? : \ /
+          e.g. release:AC\/DC or recording:"What\?"
+        - URL encoding is handled by the server; do not pre-encode
+    "#)]
+    #[serde(default)]
+    pub raw: bool,
+
+    /// Filter: artist name (artist, release, release_group, recording, work).
+    #[schemars(
+        description = "Filter by artist name (entities: artist, release, release_group, recording, work)"
+    )]
+    pub artist: Option<String>,
+
+    /// Filter: release title (release, release_group, recording).
+    #[schemars(
+        description = "Filter by release title (entities: release, release_group, recording)"
+    )]
+    pub release: Option<String>,
+
+    /// Filter: recording title (recording, work).
+    #[schemars(description = "Filter by recording title (entities: recording, work)")]
+    pub recording: Option<String>,
+
+    /// Filter: earliest release date (release, release_group, recording).
+    #[schemars(
+        description = "Earliest release date, YYYY, YYYY-MM or YYYY-MM-DD (entities: release, release_group, recording)"
+    )]
+    pub date_from: Option<String>,
+
+    /// Filter: latest release date (release, release_group, recording).
+    #[schemars(
+        description = "Latest release date, YYYY, YYYY-MM or YYYY-MM-DD (entities: release, release_group, recording)"
+    )]
+    pub date_to: Option<String>,
+
+    /// Filter: ISO country code (artist, release, recording, label).
+    #[schemars(
+        description = "Filter by ISO 3166-1 country code, e.g. 'US' (entities: artist, release, recording, label)"
+    )]
+    pub country: Option<String>,
+
+    /// Filter: medium format (release, recording).
+    #[schemars(
+        description = "Filter by medium format, e.g. 'CD', 'Digital Media' (entities: release, recording)"
+    )]
+    pub format: Option<String>,
+
+    /// Filter: folksonomy tag (all entities).
+    #[schemars(description = "Filter by tag, e.g. 'jazz' (all entities)")]
+    pub tag: Option<String>,
+
+    /// Maximum number of results to return (default: 10, max: 100).
+    #[schemars(description = "Maximum number of results (default: 10, max: 100)")]
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+/// Entity types searchable through this tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchEntity {
+    Artist,
+    Release,
+    ReleaseGroup,
+    Recording,
+    Work,
+    Label,
+}
+
+impl SearchEntity {
+    fn parse(entity: &str) -> Option<Self> {
+        match entity {
+            "artist" => Some(Self::Artist),
+            "release" => Some(Self::Release),
+            "release_group" => Some(Self::ReleaseGroup),
+            "recording" => Some(Self::Recording),
+            "work" => Some(Self::Work),
+            "label" => Some(Self::Label),
+            _ => None,
+        }
+    }
+
+    /// Structured filters accepted for this entity type.
+    fn supported_filters(self) -> &'static [&'static str] {
+        match self {
+            Self::Artist => &["artist", "country", "tag"],
+            Self::Release => &[
+                "artist",
+                "release",
+                "date_from",
+                "date_to",
+                "country",
+                "format",
+                "tag",
+            ],
+            Self::ReleaseGroup => &["artist", "release", "date_from", "date_to", "tag"],
+            Self::Recording => &[
+                "artist",
+                "release",
+                "recording",
+                "date_from",
+                "date_to",
+                "country",
+                "format",
+                "tag",
+            ],
+            Self::Work => &["artist", "recording", "tag"],
+            Self::Label => &["country", "tag"],
+        }
+    }
+
+    /// Lucene field holding the release date, for entities with date filters.
+    fn date_field(self) -> Option<&'static str> {
+        match self {
+            Self::Release | Self::Recording => Some("date"),
+            Self::ReleaseGroup => Some("firstreleasedate"),
+            Self::Artist | Self::Work | Self::Label => None,
+        }
+    }
+}
+
+/// Any of the structured search results this tool can return.
+///
+/// Only used to describe the output schema; each search emits the
+/// entity-specific result type directly.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum AdvancedSearchOutput {
+    Artists(ArtistSearchResult),
+    Releases(ReleaseSearchResult),
+    ReleaseGroups(ReleaseGroupSearchResult),
+    Recordings(RecordingSearchResult),
+    Works(WorkSearchResult),
+    Labels(LabelSearchResult),
+}
+
+/// MusicBrainz Advanced Search Tool implementation.
+#[derive(Debug, Clone)]
+pub struct MbAdvancedSearchTool;
+
+impl MbAdvancedSearchTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "mb_advanced_search";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Search any MusicBrainz entity type (artist, release, release_group, recording, work, label) through one tool. Returns the same structured data as the entity-specific search tools, with MBIDs and key attributes for each match.";
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    pub fn execute(params: &MbAdvancedSearchParams) -> CallToolResult {
+        let limit = validate_limit(params.limit);
+
+        let Some(entity) = SearchEntity::parse(&params.entity) else {
+            return error_result(&format!(
+                "Unknown entity: {}. Use 'artist', 'release', 'release_group', 'recording', 'work' or 'label'",
+                params.entity
+            ));
+        };
+
+        let expression = match Self::build_expression(entity, params) {
+            Ok(expression) => expression,
+            Err(message) => return error_result(&message),
+        };
+
+        let search_query = format!("query={}", Self::encode_query(&expression));
+        let query = expression.as_str();
+        match entity {
+            SearchEntity::Artist => Self::search_artists(search_query, query, limit),
+            SearchEntity::Release => Self::search_releases(search_query, query, limit),
+            SearchEntity::ReleaseGroup => Self::search_release_groups(search_query, query, limit),
+            SearchEntity::Recording => Self::search_recordings(search_query, query, limit),
+            SearchEntity::Work => Self::search_works(search_query, query, limit),
+            SearchEntity::Label => Self::search_labels(search_query, query, limit),
+        }
+    }
+
+    /// Compose the Lucene expression sent to MusicBrainz.
+    ///
+    /// The query and any filters are ANDed through the entity's query
+    /// builder. A raw query is kept verbatim (parenthesised when filters are
+    /// added to it). Date filters become a range on the entity's date field.
+    fn build_expression(
+        entity: SearchEntity,
+        params: &MbAdvancedSearchParams,
+    ) -> Result<String, String> {
+        let filters = Self::present_filters(params);
+        let supported = entity.supported_filters();
+        let unsupported: Vec<&str> = filters
+            .iter()
+            .map(|(name, _)| *name)
+            .filter(|name| !supported.contains(name))
+            .collect();
+        if !unsupported.is_empty() {
+            return Err(format!(
+                "Filter(s) not supported for entity '{}': {}. Supported: {}",
+                params.entity,
+                unsupported.join(", "),
+                supported.join(", ")
+            ));
+        }
+
+        let query = params.query.trim();
+        if query.is_empty() && filters.is_empty() {
+            return Err("Provide a query or at least one filter".to_string());
+        }
+
+        let term = |name: &str| {
+            filters
+                .iter()
+                .find(|(filter, _)| *filter == name)
+                .map(|(_, value)| *value)
+        };
+        let main = (!params.raw && !query.is_empty()).then_some(query);
+
+        // `and()` on an empty builder adds no operator, so every term can be
+        // prefixed with it.
+        let built = match entity {
+            SearchEntity::Artist => {
+                let mut builder = ArtistSearchQuery::query_builder();
+                for value in [main, term("artist")].into_iter().flatten() {
+                    builder.and().artist(value);
+                }
+                if let Some(value) = term("country") {
+                    builder.and().country(value);
+                }
+                if let Some(value) = term("tag") {
+                    builder.and().tag(value);
+                }
+                builder.build()
+            }
+            SearchEntity::Release => {
+                let mut builder = ReleaseSearchQuery::query_builder();
+                for value in [main, term("release")].into_iter().flatten() {
+                    builder.and().release(value);
+                }
+                if let Some(value) = term("artist") {
+                    builder.and().artist(value);
+                }
+                if let Some(value) = term("country") {
+                    builder.and().country(value);
+                }
+                if let Some(value) = term("format") {
+                    builder.and().format(value);
+                }
+                if let Some(value) = term("tag") {
+                    builder.and().tag(value);
+                }
+                builder.build()
+            }
+            SearchEntity::ReleaseGroup => {
+                let mut builder = ReleaseGroupSearchQuery::query_builder();
+                if let Some(value) = main {
+                    builder.and().release_group(value);
+                }
+                if let Some(value) = term("release") {
+                    builder.and().release(value);
+                }
+                if let Some(value) = term("artist") {
+                    builder.and().artist(value);
+                }
+                if let Some(value) = term("tag") {
+                    builder.and().tag(value);
+                }
+                builder.build()
+            }
+            SearchEntity::Recording => {
+                let mut builder = RecordingSearchQuery::query_builder();
+                for value in [main, term("recording")].into_iter().flatten() {
+                    builder.and().recording(value);
+                }
+                if let Some(value) = term("artist") {
+                    builder.and().artist(value);
+                }
+                if let Some(value) = term("release") {
+                    builder.and().release(value);
+                }
+                if let Some(value) = term("country") {
+                    builder.and().country(value);
+                }
+                if let Some(value) = term("format") {
+                    builder.and().format(value);
+                }
+                if let Some(value) = term("tag") {
+                    builder.and().tag(value);
+                }
+                builder.build()
+            }
+            SearchEntity::Work => {
+                let mut builder = WorkSearchQuery::query_builder();
+                if let Some(value) = main {
+                    builder.and().work(value);
+                }
+                if let Some(value) = term("recording") {
+                    builder.and().recording(value);
+                }
+                if let Some(value) = term("artist") {
+                    builder.and().artist(value);
+                }
+                if let Some(value) = term("tag") {
+                    builder.and().tag(value);
+                }
+                builder.build()
+            }
+            SearchEntity::Label => {
+                let mut builder = LabelSearchQuery::query_builder();
+                if let Some(value) = main {
+                    builder.and().label(value);
+                }
+                if let Some(value) = term("country") {
+                    builder.and().country(value);
+                }
+                if let Some(value) = term("tag") {
+                    builder.and().tag(value);
+                }
+                builder.build()
+            }
+        };
+
+        let mut clauses = Vec::new();
+        if params.raw && !query.is_empty() {
+            if filters.is_empty() {
+                return Ok(query.to_string());
+            }
+            clauses.push(format!("({})", query));
+        }
+
+        let built = built.strip_prefix("query=").unwrap_or(&built);
+        if !built.is_empty() {
+            clauses.push(built.to_string());
+        }
+
+        if let Some(field) = entity.date_field() {
+            let from = term("date_from");
+            let to = term("date_to");
+            if from.is_some() || to.is_some() {
+                clauses.push(format!(
+                    "{}:[{} TO {}]",
+                    field,
+                    Self::date_bound(from)?,
+                    Self::date_bound(to)?
+                ));
+            }
+        }
+
+        Ok(clauses.join(" AND "))
+    }
+
+    /// Filters with a non-empty value, by parameter name.
+    fn present_filters(params: &MbAdvancedSearchParams) -> Vec<(&'static str, &str)> {
+        [
+            ("artist", &params.artist),
+            ("release", &params.release),
+            ("recording", &params.recording),
+            ("date_from", &params.date_from),
+            ("date_to", &params.date_to),
+            ("country", &params.country),
+            ("format", &params.format),
+            ("tag", &params.tag),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let value = value.as_deref()?.trim();
+            (!value.is_empty()).then_some((name, value))
+        })
+        .collect()
+    }
+
+    /// Validate a date filter value; an open bound becomes `*`.
+    fn date_bound(date: Option<&str>) -> Result<&str, String> {
+        let Some(date) = date else {
+            return Ok("*");
+        };
+
+        let valid = matches!(date.len(), 4 | 7 | 10)
+            && date.char_indices().all(|(i, c)| {
+                if i == 4 || i == 7 {
+                    c == '-'
+                } else {
+                    c.is_ascii_digit()
+                }
+            });
+        if valid {
+            Ok(date)
+        } else {
+            Err(format!(
+                "Invalid date '{}': use YYYY, YYYY-MM or YYYY-MM-DD",
+                date
+            ))
+        }
+    }
+
+    /// Percent-encode everything but unreserved URL characters.
+    fn encode_query(query: &str) -> String {
+        query
+            .bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                    (b as char).to_string()
+                }
+                _ => format!("%{:02X}", b),
+            })
+            .collect()
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(arguments: serde_json::Value) -> Result<serde_json::Value, String> {
+        let params: MbAdvancedSearchParams =
+            serde_json::from_value(arguments).map_err(|e| format!("Invalid parameters: {}", e))?;
+
+        // Use std::thread::spawn to avoid nested runtime panic.
+        // musicbrainz_rs uses reqwest::blocking which creates its own runtime.
+        let handle = std::thread::spawn(move || Self::execute(&params));
+
+        let result = handle
+            .join()
+            .map_err(|_| "Thread panicked during advanced search".to_string())?;
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        // Include structured_content if present
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<MbAdvancedSearchParams>(),
+            annotations: None,
+            output_schema: Some(Self::output_schema()),
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Output schema: one of the entity search results.
+    ///
+    /// MCP requires the root of an output schema to be an object, which the
+    /// generated `anyOf` schema doesn't state on its own.
+    fn output_schema() -> Arc<JsonObject> {
+        let mut schema = (*schema_for_type::<AdvancedSearchOutput>()).clone();
+        schema.insert("type".to_string(), serde_json::json!("object"));
+        Arc::new(schema)
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            async move {
+                let params: MbAdvancedSearchParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_on_thread(move || Self::execute(&params)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+
+    /// Search for artists.
+    pub fn search_artists(search_query: String, query: &str, limit: usize) -> CallToolResult {
+        info!("Advanced search for artists: {}", query);
+
+        match search_with_scores(&Artist::search(search_query)) {
+            Ok(results) => {
+                let artists: Vec<ArtistSearchInfo> = results
+                    .into_iter()
+                    .take(limit)
+                    .map(|(a, score)| ArtistSearchInfo {
+                        name: a.name,
+                        mbid: a.id,
+                        country: a.country.filter(|c| !c.is_empty()),
+                        area: a.area.map(|area| area.name),
+                        disambiguation: Some(a.disambiguation).filter(|d| !d.is_empty()),
+                        score,
+                    })
+                    .collect();
+                if artists.is_empty() {
+                    return error_result(&format!("No artists found for query: {}", query));
+                }
+
+                let count = artists.len();
+                let best_score = artists.first().and_then(|info| info.score);
+                let structured_data = ArtistSearchResult {
+                    artists,
+                    total_count: count,
+                    query: query.to_string(),
+                };
+
+                let summary = format!(
+                    "Found {} artist(s) matching '{}'{}",
+                    count,
+                    query,
+                    best_match_suffix(best_score)
+                );
+                structured_result(summary, structured_data)
+            }
+            Err(e) => {
+                error!("Artist search failed: {:?}", e);
+                error_result(&format!("Artist search failed: {}", e))
+            }
+        }
+    }
+
+    /// Search for releases.
+    pub fn search_releases(search_query: String, query: &str, limit: usize) -> CallToolResult {
+        info!("Advanced search for releases: {}", query);
+
+        match search_with_scores(&Release::search(search_query)) {
+            Ok(results) => {
+                let releases: Vec<ReleaseSearchInfo> = results
+                    .into_iter()
+                    .take(limit)
+                    .map(|(r, score)| ReleaseSearchInfo {
+                        title: r.title,
+                        mbid: r.id,
+                        artist: get_artist_name(&r.artist_credit),
+                        year: r.date.as_ref().and_then(|d| extract_year(&d.0)),
+                        country: r.country,
+                        barcode: r.barcode.filter(|b| !b.is_empty()),
+                        score,
+                        has_cover_art: None,
+                    })
+                    .collect();
+                if releases.is_empty() {
+                    return error_result(&format!("No releases found for query: {}", query));
+                }
+
+                let count = releases.len();
+                let best_score = releases.first().and_then(|info| info.score);
+                let structured_data = ReleaseSearchResult {
+                    releases,
+                    total_count: count,
+                    query: query.to_string(),
+                };
+
+                let summary = format!(
+                    "Found {} release(s) matching '{}'{}",
+                    count,
+                    query,
+                    best_match_suffix(best_score)
+                );
+                structured_result(summary, structured_data)
+            }
+            Err(e) => {
+                error!("Release search failed: {:?}", e);
+                error_result(&format!("Release search failed: {}", e))
+            }
+        }
+    }
+
+    /// Search for release groups.
+    pub fn search_release_groups(
+        search_query: String,
+        query: &str,
+        limit: usize,
+    ) -> CallToolResult {
+        info!("Advanced search for release groups: {}", query);
+
+        match search_with_scores(&ReleaseGroup::search(search_query)) {
+            Ok(results) => {
+                let release_groups: Vec<ReleaseGroupSearchInfo> = results
+                    .into_iter()
+                    .take(limit)
+                    .map(|(rg, score)| ReleaseGroupSearchInfo {
+                        title: rg.title,
+                        mbid: rg.id,
+                        artist: get_artist_name(&rg.artist_credit),
+                        first_release_year: rg
+                            .first_release_date
+                            .as_ref()
+                            .and_then(|d| extract_year(&d.0)),
+                        primary_type: rg.primary_type.map(|t| format!("{:?}", t)),
+                        secondary_types: secondary_type_names(&rg.secondary_types),
+                        score,
+                    })
+                    .collect();
+                if release_groups.is_empty() {
+                    return error_result(&format!("No release groups found for query: {}", query));
+                }
+
+                let count = release_groups.len();
+                let best_score = release_groups.first().and_then(|info| info.score);
+                let structured_data = ReleaseGroupSearchResult {
+                    release_groups,
+                    total_count: count,
+                    query: query.to_string(),
+                };
+
+                let summary = format!(
+                    "Found {} release group(s) matching '{}'{}",
+                    count,
+                    query,
+                    best_match_suffix(best_score)
+                );
+                structured_result(summary, structured_data)
+            }
+            Err(e) => {
+                error!("Release group search failed: {:?}", e);
+                error_result(&format!("Release group search failed: {}", e))
+            }
+        }
+    }
+
+    /// Search for recordings.
+    pub fn search_recordings(search_query: String, query: &str, limit: usize) -> CallToolResult {
+        info!("Advanced search for recordings: {}", query);
+
+        match search_with_scores(&Recording::search(search_query)) {
+            Ok(results) => {
+                let recordings: Vec<RecordingSearchInfo> = results
+                    .into_iter()
+                    .take(limit)
+                    .map(|(r, score)| RecordingSearchInfo {
+                        title: r.title,
+                        mbid: r.id,
+                        artist: get_artist_name(&r.artist_credit),
+                        duration: r.length.map(|l| format_duration(l as u64)),
+                        disambiguation: r.disambiguation.filter(|d| !d.is_empty()),
+                        score,
+                    })
+                    .collect();
+                if recordings.is_empty() {
+                    return error_result(&format!("No recordings found for query: {}", query));
+                }
+
+                let count = recordings.len();
+                let best_score = recordings.first().and_then(|info| info.score);
+                let structured_data = RecordingSearchResult {
+                    recordings,
+                    total_count: count,
+                    query: query.to_string(),
+                };
+
+                let summary = format!(
+                    "Found {} recording(s) matching '{}'{}",
+                    count,
+                    query,
+                    best_match_suffix(best_score)
+                );
+                structured_result(summary, structured_data)
+            }
+            Err(e) => {
+                error!("Recording search failed: {:?}", e);
+                error_result(&format!("Recording search failed: {}", e))
+            }
+        }
+    }
+
+    /// Search for works.
+    pub fn search_works(search_query: String, query: &str, limit: usize) -> CallToolResult {
+        info!("Advanced search for works: {}", query);
+
+        match search_with_scores(&Work::search(search_query)) {
+            Ok(results) => {
+                let works: Vec<WorkInfo> = results
+                    .into_iter()
+                    .take(limit)
+                    .map(|(w, score)| WorkInfo {
+                        title: w.title,
+                        mbid: w.id,
+                        work_type: w.work_type.map(|t| format!("{:?}", t)),
+                        disambiguation: w.disambiguation.filter(|d| !d.is_empty()),
+                        language: w.language,
+                        score,
+                    })
+                    .collect();
+                if works.is_empty() {
+                    return error_result(&format!("No works found for query: {}", query));
+                }
+
+                let count = works.len();
+                let best_score = works.first().and_then(|info| info.score);
+                let structured_data = WorkSearchResult {
+                    works,
+                    total_count: count,
+                    query: query.to_string(),
+                };
+
+                let summary = format!(
+                    "Found {} work(s) matching '{}'{}",
+                    count,
+                    query,
+                    best_match_suffix(best_score)
+                );
+                structured_result(summary, structured_data)
+            }
+            Err(e) => {
+                error!("Work search failed: {:?}", e);
+                error_result(&format!("Work search failed: {}", e))
+            }
+        }
+    }
+
+    /// Search for labels.
+    pub fn search_labels(search_query: String, query: &str, limit: usize) -> CallToolResult {
+        info!("Advanced search for labels: {}", query);
+
+        match search_with_scores(&Label::search(search_query)) {
+            Ok(results) => {
+                let labels: Vec<LabelInfo> = results
+                    .into_iter()
+                    .take(limit)
+                    .map(|(l, score)| LabelInfo {
+                        name: l.name,
+                        mbid: l.id,
+                        label_type: l.label_type.map(|t| format!("{:?}", t)),
+                        country: l.country,
+                        disambiguation: l.disambiguation.filter(|d| !d.is_empty()),
+                        label_code: l.label_code.map(|c| c as i32),
+                        score,
+                    })
+                    .collect();
+                if labels.is_empty() {
+                    return error_result(&format!("No labels found for query: {}", query));
+                }
+
+                let count = labels.len();
+                let best_score = labels.first().and_then(|info| info.score);
+                let structured_data = LabelSearchResult {
+                    labels,
+                    total_count: count,
+                    query: query.to_string(),
+                };
+
+                let summary = format!(
+                    "Found {} label(s) matching '{}'{}",
+                    count,
+                    query,
+                    best_match_suffix(best_score)
+                );
+                structured_result(summary, structured_data)
+            }
+            Err(e) => {
+                error!("Label search failed: {:?}", e);
+                error_result(&format!("Label search failed: {}", e))
+            }
+        }
+    }
+}
+
+impl Default for MbAdvancedSearchTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(json: serde_json::Value) -> MbAdvancedSearchParams {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn expression(json: serde_json::Value) -> Result<String, String> {
+        let params = params(json);
+        let entity = SearchEntity::parse(&params.entity).unwrap();
+        MbAdvancedSearchTool::build_expression(entity, &params)
+    }
+
+    #[test]
+    fn test_advanced_search_params_default_limit() {
+        let json = r#"{"entity": "release", "query": "OK Computer"}"#;
+        let params: MbAdvancedSearchParams = serde_json::from_str(json).unwrap();
+        assert_eq!(params.entity, "release");
+        assert_eq!(params.limit, 10);
+        assert!(!params.raw);
+        assert!(params.artist.is_none());
+    }
+
+    #[test]
+    fn test_raw_query_bypasses_builder() {
+        let query = r#"artist:"Miles Davis" AND date:[1959 TO 1961] AND country:US"#;
+        let expr = expression(serde_json::json!({
+            "entity": "release", "query": query, "raw": true
+        }));
+        assert_eq!(expr.unwrap(), query);
+    }
+
+    #[test]
+    fn test_encode_query_escapes_url_delimiters() {
+        assert_eq!(
+            MbAdvancedSearchTool::encode_query("AC/DC & R+B #1"),
+            "AC%2FDC%20%26%20R%2BB%20%231"
+        );
+        assert_eq!(
+            MbAdvancedSearchTool::encode_query(r#"date:[1959 TO 1961]"#),
+            "date%3A%5B1959%20TO%201961%5D"
+        );
+    }
+
+    #[test]
+    fn test_default_query_uses_builder() {
+        let expr = expression(serde_json::json!({"entity": "recording", "query": "Hurt"}));
+        assert_eq!(expr.unwrap(), "recording:Hurt");
+    }
+
+    #[test]
+    fn test_filters_are_anded() {
+        let expr = expression(serde_json::json!({
+            "entity": "recording",
+            "query": "Hurt",
+            "artist": "Cash",
+            "date_from": "2002",
+            "date_to": "2002"
+        }))
+        .unwrap();
+        assert_eq!(
+            expr,
+            "recording:Hurt AND artist:Cash AND date:[2002 TO 2002]"
+        );
+    }
+
+    #[test]
+    fn test_filters_without_query() {
+        let expr = expression(serde_json::json!({
+            "entity": "release_group",
+            "artist": "Radiohead",
+            "date_from": "1997-05"
+        }))
+        .unwrap();
+        assert_eq!(expr, "artist:Radiohead AND firstreleasedate:[1997-05 TO *]");
+    }
+
+    #[test]
+    fn test_raw_query_with_filters() {
+        let expr = expression(serde_json::json!({
+            "entity": "release",
+            "query": "status:official OR status:promotion",
+            "raw": true,
+            "country": "GB"
+        }))
+        .unwrap();
+        assert_eq!(expr, "(status:official OR status:promotion) AND country:GB");
+    }
+
+    #[test]
+    fn test_unsupported_filter_is_rejected() {
+        let err = expression(serde_json::json!({
+            "entity": "label", "query": "Warp", "format": "CD"
+        }))
+        .unwrap_err();
+        assert!(err.contains("format"));
+        assert!(err.contains("Supported: country, tag"));
+    }
+
+    #[test]
+    fn test_invalid_date_is_rejected() {
+        let err = expression(serde_json::json!({
+            "entity": "release", "query": "Kind of Blue", "date_from": "1959] OR *:*"
+        }))
+        .unwrap_err();
+        assert!(err.contains("Invalid date"));
+    }
+
+    #[test]
+    fn test_empty_query_without_filters() {
+        let err = expression(serde_json::json!({"entity": "work", "query": "  "})).unwrap_err();
+        assert!(err.contains("at least one filter"));
+    }
+
+    #[test]
+    fn test_unknown_entity() {
+        let result = MbAdvancedSearchTool::execute(&params(serde_json::json!({
+            "entity": "place", "query": "Abbey Road"
+        })));
+        assert!(result.is_error.unwrap_or(false));
+    }
+
+    #[test]
+    fn test_output_schema_is_object() {
+        let tool = MbAdvancedSearchTool::to_tool();
+        let schema = tool.output_schema.expect("output schema");
+        assert_eq!(schema.get("type"), Some(&serde_json::json!("object")));
+        assert!(schema.contains_key("anyOf"));
+    }
+
+    // Integration tests (require network, run with: cargo test -- --ignored)
+    #[ignore]
+    #[test]
+    fn test_advanced_search_recordings() {
+        let result = MbAdvancedSearchTool::execute(&params(serde_json::json!({
+            "entity": "recording",
+            "query": "Hurt",
+            "artist": "Johnny Cash",
+            "limit": 5
+        })));
+        assert!(!result.is_error.unwrap_or(true));
+        let structured = result.structured_content.expect("structured content");
+        assert!(structured["recordings"].as_array().is_some());
+        assert!(structured["query"].as_str().unwrap().contains("artist:"));
+    }
+}
@@ -20,8 +20,13 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info};
 
 use super::common::{
-    default_limit, error_result, extract_year, is_mbid, structured_result, validate_limit,
+    ExecuteWithRetry, best_match_suffix, default_limit, error_result, extract_mbid, extract_year,
+    search_with_scores, structured_result, validate_limit,
 };
+use crate::core::config::Config;
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::timeout::with_timeout;
+use std::sync::Arc;
 
 /// Parameters for artist search operations.
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
@@ -71,6 +76,8 @@ pub struct ArtistSearchInfo {
     pub country: Option<String>,
     pub area: Option<String>,
     pub disambiguation: Option<String>,
+    /// MusicBrainz search relevance (0-100); null when fetched by MBID
+    pub score: Option<u8>,
 }
 
 /// Structured output for artist releases search results.
@@ -186,27 +193,25 @@ impl MbArtistTool {
     }
 
     /// Create a ToolRoute for STDIO/TCP transport.
-    pub fn create_route<S>() -> ToolRoute<S>
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
     where
         S: Send + Sync + 'static,
     {
-        ToolRoute::new_dyn(Self::to_tool(), |ctx: ToolCallContext<'_, S>| {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             async move {
                 let params: MbArtistParams =
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
-                // Use std::thread::spawn to avoid nested runtime panic.
-                // musicbrainz_rs uses reqwest::blocking which creates its own runtime,
-                // so we need a completely separate OS thread.
-                let handle = std::thread::spawn(move || Self::execute(&params));
-
-                let result = handle
-                    .join()
-                    .map_err(|_| McpError::internal_error("Thread panicked".to_string(), None))?;
-
-                Ok(result)
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_on_thread(move || Self::execute(&params)),
+                )
+                .await
             }
             .boxed()
         })
@@ -263,8 +268,8 @@ impl MbArtistTool {
         info!("Searching for artists matching: {}", query);
 
         // If query is an MBID, fetch directly
-        if is_mbid(query) {
-            match Artist::fetch().id(query).execute() {
+        if let Some(mbid) = extract_mbid(query) {
+            match Artist::fetch().id(&mbid).execute_with_retry() {
                 Ok(artist) => {
                     let artist_info = ArtistSearchInfo {
                         name: artist.name.clone(),
@@ -276,6 +281,7 @@ impl MbArtistTool {
                         } else {
                             Some(artist.disambiguation)
                         },
+                        score: None,
                     };
 
                     let structured_data = ArtistSearchResult {
@@ -295,19 +301,20 @@ impl MbArtistTool {
         } else {
             // Search by name
             let search_query = ArtistSearchQuery::query_builder().artist(query).build();
-            let search_result = Artist::search(search_query).execute();
+            let search_result = search_with_scores(&Artist::search(search_query));
 
             match search_result {
-                Ok(result) => {
-                    let artists: Vec<_> = result.entities.into_iter().take(limit).collect();
+                Ok(results) => {
+                    let artists: Vec<_> = results.into_iter().take(limit).collect();
                     if artists.is_empty() {
                         return error_result(&format!("No artists found for query: {}", query));
                     }
 
                     let count = artists.len();
+                    let best_score = artists.first().and_then(|(_, score)| *score);
                     let artist_infos: Vec<ArtistSearchInfo> = artists
                         .into_iter()
-                        .map(|a| ArtistSearchInfo {
+                        .map(|(a, score)| ArtistSearchInfo {
                             name: a.name,
                             mbid: a.id,
                             country: a.country.filter(|c| !c.is_empty()),
@@ -317,6 +324,7 @@ impl MbArtistTool {
                             } else {
                                 Some(a.disambiguation)
                             },
+                            score,
                         })
                         .collect();
 
@@ -326,7 +334,12 @@ impl MbArtistTool {
                         query: query.to_string(),
                     };
 
-                    let summary = format!("Found {} artist(s) matching '{}'", count, query);
+                    let summary = format!(
+                        "Found {} artist(s) matching '{}'{}",
+                        count,
+                        query,
+                        best_match_suffix(best_score)
+                    );
                     structured_result(summary, structured_data)
                 }
                 Err(e) => {
@@ -337,42 +350,47 @@ impl MbArtistTool {
         }
     }
 
-    /// Search for releases by a specific artist (using artist name or MBID).
-    pub fn search_releases_by_artist(query: &str, limit: usize) -> CallToolResult {
-        info!("Searching for releases by artist: {}", query);
+    /// Resolve an artist name or MBID to its MBID and display name.
+    ///
+    /// Names resolve to the best search match.
+    pub(super) fn resolve_artist(query: &str) -> Result<(String, String), String> {
+        if let Some(mbid) = extract_mbid(query) {
+            let name = match Artist::fetch().id(&mbid).execute_with_retry() {
+                Ok(artist) => artist.name,
+                Err(_) => "Unknown Artist".to_string(),
+            };
+            return Ok((mbid, name));
+        }
 
-        // First, find the artist
-        let artist_id = if is_mbid(query) {
-            query.to_string()
-        } else {
-            // Search for artist first
-            debug!("Looking up artist by name: {}", query);
-            let search_query = ArtistSearchQuery::query_builder().artist(query).build();
-            match Artist::search(search_query).execute() {
-                Ok(result) => {
-                    if let Some(artist) = result.entities.first() {
-                        debug!("Found artist: {} ({})", artist.name, artist.id);
-                        artist.id.clone()
-                    } else {
-                        return error_result(&format!("No artist found matching: {}", query));
-                    }
-                }
-                Err(e) => {
-                    error!("Artist lookup failed: {:?}", e);
-                    return error_result(&format!("Artist lookup failed: {}", e));
+        debug!("Looking up artist by name: {}", query);
+        let search_query = ArtistSearchQuery::query_builder().artist(query).build();
+        match Artist::search(search_query).execute_with_retry() {
+            Ok(result) => match result.entities.into_iter().next() {
+                Some(artist) => {
+                    debug!("Found artist: {} ({})", artist.name, artist.id);
+                    Ok((artist.id, artist.name))
                 }
+                None => Err(format!("No artist found matching: {}", query)),
+            },
+            Err(e) => {
+                error!("Artist lookup failed: {:?}", e);
+                Err(format!("Artist lookup failed: {}", e))
             }
-        };
+        }
+    }
+
+    /// Search for releases by a specific artist (using artist name or MBID).
+    pub fn search_releases_by_artist(query: &str, limit: usize) -> CallToolResult {
+        info!("Searching for releases by artist: {}", query);
 
-        // Get artist details first (for display name)
-        let artist_name = match Artist::fetch().id(&artist_id).execute() {
-            Ok(artist) => artist.name.clone(),
-            Err(_) => "Unknown Artist".to_string(),
+        let (artist_id, artist_name) = match Self::resolve_artist(query) {
+            Ok(artist) => artist,
+            Err(e) => return error_result(&e),
         };
 
         // Search for releases by this artist using arid (artist MBID)
         let search_query = ReleaseSearchQuery::query_builder().arid(&artist_id).build();
-        let search_result = Release::search(search_query).execute();
+        let search_result = Release::search(search_query).execute_with_retry();
 
         match search_result {
             Ok(result) => {
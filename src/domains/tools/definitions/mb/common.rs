@@ -3,21 +3,128 @@
 //! This module provides shared functionality like MBID validation,
 //! response formatting, and error handling helpers.
 
+use musicbrainz_rs::{
+    ApiRequest, BrowseQuery, Error as MbError, Fetch, FetchQuery, Search, SearchQuery,
+    client::MusicBrainzClient,
+    entity::{
+        Browsable, BrowseResult,
+        release_group::ReleaseGroupSecondaryType,
+        search::{SearchResult, Searchable},
+    },
+};
+use reqwest::{
+    StatusCode,
+    header::{HeaderMap, RETRY_AFTER},
+};
 use rmcp::model::{CallToolResult, Content};
-use tracing::warn;
+use serde::de::DeserializeOwned;
+use std::cmp::Reverse;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::OnceLock;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::core::config::MusicBrainzConfig;
+
+/// Shared MusicBrainz client, configured once per process.
+static MB_CLIENT: OnceLock<MusicBrainzClient> = OnceLock::new();
+
+/// User-Agent of the shared client, also sent to the Cover Art Archive.
+static MB_USER_AGENT: OnceLock<String> = OnceLock::new();
+
+/// Attempts made for a request MusicBrainz answers with 503 Service Unavailable.
+const MB_MAX_ATTEMPTS: u32 = 4;
+/// Backoff before the second attempt, doubled for each further attempt.
+const MB_RETRY_BASE_DELAY_MS: u64 = 1000;
+/// Upper bound of the random delay added to each backoff.
+const MB_RETRY_MAX_JITTER_MS: u64 = 500;
+/// Longest Retry-After honored, so a single request never stalls a tool call.
+const MB_RETRY_AFTER_MAX_SECS: u64 = 30;
 
 /// UUID format: 8-4-4-4-12 hexadecimal characters
 const MBID_LENGTH: usize = 36;
 const MBID_DASH_COUNT: usize = 4;
 
-/// Check if a string looks like a MusicBrainz ID (UUID format).
+/// Entity types that precede the MBID in musicbrainz.org URLs.
+const MBID_URL_ENTITIES: &[&str] = &[
+    "area",
+    "artist",
+    "event",
+    "instrument",
+    "label",
+    "place",
+    "recording",
+    "release",
+    "release-group",
+    "series",
+    "track",
+    "url",
+    "work",
+];
+
+/// Check if a string identifies a MusicBrainz ID.
 ///
-/// MBIDs are UUIDs in the format: xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+/// Accepts bare UUIDs in any letter case and musicbrainz.org entity URLs,
+/// see [`extract_mbid`].
 /// Example: 5b11f4ce-a62d-471e-81fc-a69a8278c7da
 pub fn is_mbid(query: &str) -> bool {
-    query.len() == MBID_LENGTH
-        && query.chars().filter(|c| *c == '-').count() == MBID_DASH_COUNT
-        && query.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+    extract_mbid(query).is_some()
+}
+
+/// Extract the canonical (lowercase) MBID from a query.
+///
+/// The query may be a bare UUID (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx) in any
+/// letter case, or a musicbrainz.org URL such as
+/// `https://musicbrainz.org/release/65c70b9f-.../cover-art?tab=front`,
+/// including web service URLs (`/ws/2/release/...`).
+pub fn extract_mbid(query: &str) -> Option<String> {
+    let query = query.trim();
+    let candidate = if query.contains('/') {
+        mbid_from_url(query)?
+    } else {
+        query
+    };
+    is_uuid(candidate).then(|| candidate.to_ascii_lowercase())
+}
+
+/// The path segment following the entity type in a musicbrainz.org URL.
+fn mbid_from_url(url: &str) -> Option<&str> {
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+
+    let mut segments = url.split('/').filter(|s| !s.is_empty());
+    let host = segments.next()?.to_ascii_lowercase();
+    if host != "musicbrainz.org" && !host.ends_with(".musicbrainz.org") {
+        return None;
+    }
+
+    let mut entity = segments.next()?;
+    if entity == "ws" {
+        segments.next()?; // API version
+        entity = segments.next()?;
+    }
+    if !MBID_URL_ENTITIES.contains(&entity.to_ascii_lowercase().as_str()) {
+        return None;
+    }
+    segments.next()
+}
+
+/// Check for the 8-4-4-4-12 hexadecimal UUID layout, in any letter case.
+fn is_uuid(s: &str) -> bool {
+    s.len() == MBID_LENGTH
+        && s.chars().filter(|c| *c == '-').count() == MBID_DASH_COUNT
+        && s.char_indices().all(|(i, c)| {
+            if matches!(i, 8 | 13 | 18 | 23) {
+                c == '-'
+            } else {
+                c.is_ascii_hexdigit()
+            }
+        })
 }
 
 /// Format a duration in milliseconds to MM:SS format.
@@ -85,6 +192,265 @@ pub fn get_artist_name(
         .unwrap_or_else(|| "Unknown Artist".to_string())
 }
 
+/// Full artist credit as printed, joining every credited name with its join phrase
+/// (e.g. "Simon & Garfunkel", "Artist feat. Guest").
+pub fn format_artist_credit(
+    artist_credit: &Option<Vec<musicbrainz_rs::entity::artist_credit::ArtistCredit>>,
+) -> Option<String> {
+    let credit: String = artist_credit
+        .iter()
+        .flatten()
+        .map(|a| format!("{}{}", a.name, a.joinphrase.as_deref().unwrap_or_default()))
+        .collect();
+    let credit = credit.trim();
+    (!credit.is_empty()).then(|| credit.to_string())
+}
+
+/// Names of release group secondary types, e.g. "Live" or "Compilation".
+pub fn secondary_type_names(types: &[ReleaseGroupSecondaryType]) -> Vec<String> {
+    types.iter().map(|t| format!("{:?}", t)).collect()
+}
+
+/// Configure the shared MusicBrainz client from the server configuration.
+///
+/// Must run before the first MusicBrainz request to take effect. Returns
+/// `false` when the client was already configured (or already used).
+pub fn init_mb_client(config: &MusicBrainzConfig) -> bool {
+    let applied = init_client_cell(&MB_CLIENT, config);
+    if applied {
+        let _ = MB_USER_AGENT.set(config.user_agent.clone());
+    }
+    applied
+}
+
+/// The shared MusicBrainz client. Falls back to the default configuration
+/// when `init_mb_client` was never called (e.g. in tests).
+pub fn mb_client() -> &'static MusicBrainzClient {
+    MB_CLIENT.get_or_init(|| build_mb_client(&MusicBrainzConfig::default()))
+}
+
+/// The User-Agent configured with `init_mb_client`, for the requests sent to the
+/// Cover Art Archive outside the MusicBrainz client.
+pub fn mb_user_agent() -> &'static str {
+    MB_USER_AGENT.get_or_init(|| MusicBrainzConfig::default().user_agent)
+}
+
+fn init_client_cell(cell: &OnceLock<MusicBrainzClient>, config: &MusicBrainzConfig) -> bool {
+    let mut applied = false;
+    cell.get_or_init(|| {
+        applied = true;
+        build_mb_client(config)
+    });
+    if applied {
+        info!(
+            "MusicBrainz client configured for {} as '{}'",
+            config.base_url, config.user_agent
+        );
+    } else {
+        warn!("MusicBrainz client already configured, ignoring new configuration");
+    }
+    applied
+}
+
+fn build_mb_client(config: &MusicBrainzConfig) -> MusicBrainzClient {
+    let mut client = MusicBrainzClient::default();
+    client.musicbrainz_domain = musicbrainz_domain(&config.base_url);
+    if let Err(e) = client.set_user_agent(&config.user_agent) {
+        warn!(
+            "Invalid MusicBrainz User-Agent '{}': {}",
+            config.user_agent, e
+        );
+    }
+    client
+}
+
+/// Reduce a server URL to the host (and port) musicbrainz_rs expects.
+///
+/// musicbrainz_rs builds request URLs as `http://{domain}/ws/2/...` itself,
+/// so the scheme, a trailing slash and a trailing `/ws/2` are dropped.
+fn musicbrainz_domain(base_url: &str) -> String {
+    let url = base_url.trim();
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let url = url.trim_end_matches('/');
+    url.strip_suffix("/ws/2").unwrap_or(url).to_string()
+}
+
+/// Error of a MusicBrainz request sent through [`ExecuteWithRetry`].
+#[derive(Debug, Error)]
+pub enum MbRequestError {
+    /// Network, HTTP or response error reported by musicbrainz_rs.
+    #[error(transparent)]
+    Api(#[from] MbError),
+
+    /// Every attempt was answered with 503 Service Unavailable.
+    #[error(
+        "MusicBrainz is rate limiting or temporarily unavailable (503 Service Unavailable after {attempts} attempts). Wait a minute before trying again"
+    )]
+    Unavailable { attempts: u32 },
+}
+
+impl From<reqwest::Error> for MbRequestError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Api(MbError::ReqwestError(e))
+    }
+}
+
+/// Execute a musicbrainz_rs query with the shared client, retrying while
+/// MusicBrainz answers 503 Service Unavailable.
+pub trait ExecuteWithRetry {
+    type Output;
+
+    fn execute_with_retry(&self) -> Result<Self::Output, MbRequestError>;
+}
+
+impl<T> ExecuteWithRetry for FetchQuery<T>
+where
+    T: Fetch + DeserializeOwned + Clone,
+{
+    type Output = T;
+
+    fn execute_with_retry(&self) -> Result<T, MbRequestError> {
+        get_with_retry(self.as_api_request(mb_client()))
+    }
+}
+
+impl<T> ExecuteWithRetry for SearchQuery<T>
+where
+    T: Search + Searchable + DeserializeOwned + Clone,
+{
+    type Output = SearchResult<T>;
+
+    fn execute_with_retry(&self) -> Result<SearchResult<T>, MbRequestError> {
+        get_with_retry(self.as_api_request(mb_client()))
+    }
+}
+
+impl<T> ExecuteWithRetry for BrowseQuery<T>
+where
+    T: Fetch + Browsable + DeserializeOwned + Clone,
+{
+    type Output = BrowseResult<T>;
+
+    fn execute_with_retry(&self) -> Result<BrowseResult<T>, MbRequestError> {
+        get_with_retry(self.as_api_request(mb_client()))
+    }
+}
+
+/// Send a request and parse the response into `T`, retrying 503s.
+fn get_with_retry<T: DeserializeOwned>(request: ApiRequest) -> Result<T, MbRequestError> {
+    let url = request.url.clone();
+    let json = get_json_with_retry(request)?;
+    Ok(ApiRequest::parse_json(json, &url)?)
+}
+
+/// Send a request, retrying up to `MB_MAX_ATTEMPTS` times on 503.
+///
+/// The request is sent directly rather than through musicbrainz_rs, whose
+/// own retry loop panics when a 503 comes without a Retry-After header.
+fn get_json_with_retry(mut request: ApiRequest) -> Result<serde_json::Value, MbRequestError> {
+    let client = mb_client();
+
+    for attempt in 1..=MB_MAX_ATTEMPTS {
+        request.tries = attempt - 1;
+        let response = request.send_raw(client)?;
+        if response.status() != StatusCode::SERVICE_UNAVAILABLE {
+            return Ok(response.json()?);
+        }
+
+        if attempt < MB_MAX_ATTEMPTS {
+            let delay = retry_delay(attempt, retry_after_secs(response.headers()), jitter_ms());
+            warn!(
+                "MusicBrainz returned 503 for {} (attempt {}/{}), retrying in {}ms",
+                request.url,
+                attempt,
+                MB_MAX_ATTEMPTS,
+                delay.as_millis()
+            );
+            std::thread::sleep(delay);
+        }
+    }
+
+    Err(MbRequestError::Unavailable {
+        attempts: MB_MAX_ATTEMPTS,
+    })
+}
+
+/// Read a Retry-After header given in seconds (the HTTP-date form is ignored).
+fn retry_after_secs(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+/// Delay before the attempt following `attempt` (1-based): the server's
+/// Retry-After when given, otherwise exponential backoff, plus jitter.
+fn retry_delay(attempt: u32, retry_after_secs: Option<u64>, jitter_ms: u64) -> Duration {
+    let base_ms = match retry_after_secs {
+        Some(secs) => secs.min(MB_RETRY_AFTER_MAX_SECS) * 1000,
+        None => MB_RETRY_BASE_DELAY_MS << (attempt.saturating_sub(1)).min(5),
+    };
+    Duration::from_millis(base_ms + jitter_ms)
+}
+
+/// Random jitter so concurrent tool calls do not retry in lockstep.
+fn jitter_ms() -> u64 {
+    RandomState::new().build_hasher().finish() % (MB_RETRY_MAX_JITTER_MS + 1)
+}
+
+/// Run a search and pair each entity with its MusicBrainz relevance score
+/// (0-100), best match first.
+///
+/// musicbrainz_rs drops the `score` field when deserializing entities, so the
+/// JSON response is read once and scores are matched to entities by position.
+pub fn search_with_scores<T>(query: &SearchQuery<T>) -> Result<Vec<(T, Option<u8>)>, MbRequestError>
+where
+    T: Search + Searchable + DeserializeOwned + Clone,
+{
+    let request = query.as_api_request(mb_client());
+    let url = request.url.clone();
+    let json = get_json_with_retry(request)?;
+
+    let scores = extract_scores(&json, T::ENTITIES_FIELD);
+    let result: SearchResult<T> = ApiRequest::parse_json(json, &url)?;
+
+    let mut scored: Vec<(T, Option<u8>)> = result
+        .entities
+        .into_iter()
+        .zip(scores.into_iter().chain(std::iter::repeat(None)))
+        .collect();
+    // Stable sort keeps MusicBrainz's order for equal scores; unscored last
+    scored.sort_by_key(|(_, score)| Reverse(*score));
+    Ok(scored)
+}
+
+/// Read the `score` of each entity in a search response.
+fn extract_scores(json: &serde_json::Value, entities_field: &str) -> Vec<Option<u8>> {
+    json.get(entities_field)
+        .and_then(|entities| entities.as_array())
+        .map(|entities| {
+            entities
+                .iter()
+                .map(|e| {
+                    e.get("score")
+                        .and_then(|s| s.as_u64())
+                        .map(|s| s.min(100) as u8)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Summary suffix naming the top search score, e.g. " (best match 98%)".
+pub fn best_match_suffix(score: Option<u8>) -> String {
+    score
+        .map(|s| format!(" (best match {}%)", s))
+        .unwrap_or_default()
+}
+
 /// Default limit for search results.
 pub fn default_limit() -> usize {
     10
@@ -120,6 +486,55 @@ mod tests {
         assert!(!is_mbid("5b11f4ce-a62d-471e-81fc")); // too short
         assert!(!is_mbid("5b11f4ce-a62d-471e-81fc-a69a8278c7da-extra")); // too long
         assert!(!is_mbid("5b11f4ce_a62d_471e_81fc_a69a8278c7da")); // wrong separator
+        assert!(!is_mbid("5b11f4cea-62d-471e-81fc-a69a8278c7da")); // misplaced dash
+        assert!(!is_mbid(
+            "https://example.com/release/5b11f4ce-a62d-471e-81fc-a69a8278c7da"
+        ));
+        assert!(!is_mbid(
+            "https://musicbrainz.org/search?query=5b11f4ce-a62d-471e-81fc-a69a8278c7da"
+        ));
+        assert!(!is_mbid("https://musicbrainz.org/release/"));
+    }
+
+    #[test]
+    fn test_extract_mbid_normalizes_case() {
+        assert_eq!(
+            extract_mbid("5B11F4CE-A62D-471E-81FC-A69A8278C7DA").as_deref(),
+            Some("5b11f4ce-a62d-471e-81fc-a69a8278c7da")
+        );
+        assert_eq!(
+            extract_mbid("  5b11f4ce-a62d-471e-81fc-a69a8278c7da\n").as_deref(),
+            Some("5b11f4ce-a62d-471e-81fc-a69a8278c7da")
+        );
+        assert_eq!(extract_mbid("Nirvana"), None);
+    }
+
+    #[test]
+    fn test_extract_mbid_from_entity_urls() {
+        let mbid = "5b11f4ce-a62d-471e-81fc-a69a8278c7da";
+        for entity in MBID_URL_ENTITIES {
+            let url = format!("https://musicbrainz.org/{}/{}", entity, mbid);
+            assert_eq!(extract_mbid(&url).as_deref(), Some(mbid), "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_extract_mbid_url_variants() {
+        let mbid = Some("65c70b9f-4d7e-4f0a-9a3c-1c2d3e4f5a6b");
+        let cases = [
+            "https://musicbrainz.org/release/65c70b9f-4d7e-4f0a-9a3c-1c2d3e4f5a6b/",
+            "https://musicbrainz.org/release/65c70b9f-4d7e-4f0a-9a3c-1c2d3e4f5a6b?tab=tracks",
+            "https://musicbrainz.org/release/65c70b9f-4d7e-4f0a-9a3c-1c2d3e4f5a6b/?tab=tracks",
+            "https://musicbrainz.org/release/65c70b9f-4d7e-4f0a-9a3c-1c2d3e4f5a6b#disc1",
+            "https://musicbrainz.org/release/65c70b9f-4d7e-4f0a-9a3c-1c2d3e4f5a6b/cover-art",
+            "http://musicbrainz.org/release/65C70B9F-4D7E-4F0A-9A3C-1C2D3E4F5A6B",
+            "https://beta.musicbrainz.org/release/65c70b9f-4d7e-4f0a-9a3c-1c2d3e4f5a6b",
+            "musicbrainz.org/release/65c70b9f-4d7e-4f0a-9a3c-1c2d3e4f5a6b",
+            "https://musicbrainz.org/ws/2/release/65c70b9f-4d7e-4f0a-9a3c-1c2d3e4f5a6b?inc=recordings",
+        ];
+        for url in cases {
+            assert_eq!(extract_mbid(url).as_deref(), mbid, "{}", url);
+        }
     }
 
     #[test]
@@ -138,6 +553,94 @@ mod tests {
         assert_eq!(validate_limit(50), 50);
     }
 
+    #[test]
+    fn test_extract_scores() {
+        let json = serde_json::json!({
+            "artists": [{"id": "a", "score": 100}, {"id": "b"}, {"id": "c", "score": 87}]
+        });
+        assert_eq!(
+            extract_scores(&json, "artists"),
+            vec![Some(100), None, Some(87)]
+        );
+        assert!(extract_scores(&json, "releases").is_empty());
+    }
+
+    #[test]
+    fn test_best_match_suffix() {
+        assert_eq!(best_match_suffix(Some(98)), " (best match 98%)");
+        assert_eq!(best_match_suffix(None), "");
+    }
+
+    #[test]
+    fn test_mb_client_configured_once() {
+        let cell = OnceLock::new();
+        let mirror = MusicBrainzConfig {
+            base_url: "http://localhost:5000".to_string(),
+            user_agent: "MyTagger/1.0 ( me@example.com )".to_string(),
+        };
+
+        assert!(init_client_cell(&cell, &mirror));
+        assert!(!init_client_cell(&cell, &MusicBrainzConfig::default()));
+        assert_eq!(cell.get().unwrap().musicbrainz_domain, "localhost:5000");
+    }
+
+    #[test]
+    fn test_musicbrainz_domain() {
+        assert_eq!(
+            musicbrainz_domain("http://musicbrainz.org"),
+            "musicbrainz.org"
+        );
+        assert_eq!(
+            musicbrainz_domain("http://localhost:5000/"),
+            "localhost:5000"
+        );
+        assert_eq!(musicbrainz_domain("mb.example.org/ws/2/"), "mb.example.org");
+        assert_eq!(musicbrainz_domain("musicbrainz.org"), "musicbrainz.org");
+    }
+
+    #[test]
+    fn test_retry_delay_backoff() {
+        assert_eq!(retry_delay(1, None, 0), Duration::from_millis(1000));
+        assert_eq!(retry_delay(2, None, 0), Duration::from_millis(2000));
+        assert_eq!(retry_delay(3, None, 250), Duration::from_millis(4250));
+    }
+
+    #[test]
+    fn test_retry_delay_honors_retry_after() {
+        assert_eq!(retry_delay(1, Some(5), 0), Duration::from_secs(5));
+        assert_eq!(retry_delay(3, Some(0), 100), Duration::from_millis(100));
+        // Capped so one request cannot stall a tool call
+        assert_eq!(retry_delay(1, Some(3600), 0), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_retry_after_secs() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after_secs(&headers), None);
+        headers.insert(RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(retry_after_secs(&headers), Some(7));
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after_secs(&headers), None);
+    }
+
+    #[test]
+    fn test_jitter_within_bounds() {
+        for _ in 0..100 {
+            assert!(jitter_ms() <= MB_RETRY_MAX_JITTER_MS);
+        }
+    }
+
+    #[test]
+    fn test_unavailable_error_message() {
+        let message = MbRequestError::Unavailable { attempts: 4 }.to_string();
+        assert!(message.contains("503"));
+        assert!(message.contains("after 4 attempts"));
+        assert!(message.contains("Wait"));
+    }
+
     #[test]
     fn test_extract_year() {
         assert_eq!(extract_year("1997-06-16"), Some("1997".to_string()));
@@ -145,4 +648,21 @@ mod tests {
         assert_eq!(extract_year("1997"), Some("1997".to_string()));
         assert_eq!(extract_year("97"), None);
     }
+
+    #[test]
+    fn test_format_artist_credit() {
+        let credit: Vec<musicbrainz_rs::entity::artist_credit::ArtistCredit> =
+            serde_json::from_value(serde_json::json!([
+                {"name": "Artist", "joinphrase": " feat. ",
+                 "artist": {"id": "a1", "name": "Artist", "sort-name": "Artist"}},
+                {"name": "Guest", "joinphrase": "",
+                 "artist": {"id": "a2", "name": "Guest", "sort-name": "Guest"}}
+            ]))
+            .unwrap();
+        assert_eq!(
+            format_artist_credit(&Some(credit)).as_deref(),
+            Some("Artist feat. Guest")
+        );
+        assert_eq!(format_artist_credit(&None), None);
+    }
 }
@@ -4,20 +4,30 @@
 //! Supports multiple thumbnail sizes with intelligent fallback strategies.
 
 use futures::FutureExt;
+use musicbrainz_rs::{Fetch, entity::release::Release};
 use rmcp::{
     ErrorData as McpError,
     handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
-    model::{CallToolResult, Content, Tool},
+    model::{CallToolResult, Tool},
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
+use crate::core::audit;
 use crate::core::config::Config;
-use crate::core::security::validate_path;
+use crate::core::security::{AccessKind, validate_path_for, validate_write_target};
 
-use super::common::{error_result, is_mbid, structured_result};
+use super::common::{
+    ExecuteWithRetry, error_result, extract_mbid, extract_year, get_artist_name, mb_user_agent,
+    structured_result,
+};
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::definitions::fs::files::{is_identical_file, write_atomic};
+use crate::domains::tools::timeout::with_audited_timeout;
 
 // ============================================================================
 // Cover Art Archive JSON structures
@@ -26,7 +36,7 @@ use super::common::{error_result, is_mbid, structured_result};
 /// Thumbnail structure that handles both string and numeric IDs.
 /// Supports both legacy format (small/large) and new format (250/500/1200).
 #[derive(Debug, Clone, Deserialize, Serialize)]
-struct Thumbnail {
+pub(super) struct Thumbnail {
     #[serde(default)]
     pub small: Option<String>,
     #[serde(default)]
@@ -41,7 +51,7 @@ struct Thumbnail {
 
 /// Cover art image structure with flexible ID handling.
 #[derive(Debug, Clone, Deserialize, Serialize)]
-struct CoverartImage {
+pub(super) struct CoverartImage {
     pub approved: bool,
     pub back: bool,
     #[serde(default)]
@@ -59,7 +69,7 @@ struct CoverartImage {
 
 /// Cover Art Archive response structure.
 #[derive(Debug, Clone, Deserialize, Serialize)]
-struct Coverart {
+pub(super) struct Coverart {
     pub images: Vec<CoverartImage>,
     #[serde(default)]
     pub release: Option<String>,
@@ -112,32 +122,80 @@ where
 // ============================================================================
 
 /// Parameters for cover art download operations.
-#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct MbCoverDownloadParams {
-    /// MusicBrainz Release ID (UUID format).
-    #[schemars(description = "MusicBrainz Release ID (MBID) in UUID format")]
-    pub mbid: String,
+    /// MusicBrainz Release ID (UUID format). Required unless `items` is given.
+    #[serde(default)]
+    #[schemars(
+        description = "MusicBrainz Release ID (MBID) in UUID format (required unless 'items' is used)"
+    )]
+    pub mbid: Option<String>,
 
-    /// Directory path where cover will be saved.
-    #[schemars(description = "Target directory path (must be within allowed root)")]
-    pub path: String,
+    /// Directory path where cover will be saved. Required unless `items` is given.
+    #[serde(default)]
+    #[schemars(
+        description = "Target directory path (must be within allowed root; required unless 'items' is used)"
+    )]
+    pub path: Option<String>,
 
-    /// Filename without extension (default: "cover").
+    /// Filename without extension (default: "cover"), may contain placeholders.
     #[serde(default = "default_filename")]
-    #[schemars(description = "Output filename without extension (default: 'cover')")]
+    #[schemars(
+        description = "Output filename without extension (default: 'cover'). Supports placeholders {artist}, {album}, {year}, {mbid} and {type}, e.g. '{artist} - {album} ({year}) - {type}'"
+    )]
     pub filename: String,
 
+    /// Batch of releases to download, mutually exclusive with `mbid`/`path`.
+    #[serde(default)]
+    #[schemars(
+        description = "Batch mode: list of {mbid, path, filename?} entries processed sequentially with rate limiting. Mutually exclusive with 'mbid'/'path'. Other options apply to every entry"
+    )]
+    pub items: Option<Vec<CoverDownloadItem>>,
+
     /// Preferred thumbnail size: "250", "500", "1200", or "original".
     #[serde(default = "default_thumbnail_size")]
     #[schemars(description = "Thumbnail size: 250, 500, 1200, or original (default: 500)")]
     pub thumbnail_size: String,
 
+    /// Image type to download: "front", "back", "booklet", "medium", or "any".
+    #[serde(default = "default_image_type")]
+    #[schemars(
+        description = "Image type: front, back, booklet, medium, or any (default: front). 'any' prefers the front cover but falls back to the first available image"
+    )]
+    pub image_type: String,
+
+    /// Whether to fall back to release group art when the release has none.
+    #[serde(default = "default_release_group_fallback")]
+    #[schemars(
+        description = "Fall back to the release group's cover art when the release has none (default: true). Set to false for strict release-only art"
+    )]
+    pub release_group_fallback: bool,
+
     /// Whether to overwrite existing file.
     #[serde(default)]
     #[schemars(description = "Overwrite existing file if present (default: false)")]
     pub overwrite: bool,
 }
 
+/// A single entry of a batch cover download.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct CoverDownloadItem {
+    /// MusicBrainz Release ID (UUID format).
+    #[schemars(description = "MusicBrainz Release ID (MBID) in UUID format")]
+    pub mbid: String,
+
+    /// Directory path where cover will be saved.
+    #[schemars(description = "Target directory path (must be within allowed root)")]
+    pub path: String,
+
+    /// Filename without extension (defaults to the top-level `filename`).
+    #[serde(default)]
+    #[schemars(
+        description = "Output filename without extension, placeholders allowed (default: top-level 'filename')"
+    )]
+    pub filename: Option<String>,
+}
+
 fn default_filename() -> String {
     "cover".to_string()
 }
@@ -146,6 +204,24 @@ fn default_thumbnail_size() -> String {
     "500".to_string()
 }
 
+fn default_image_type() -> String {
+    "front".to_string()
+}
+
+fn default_release_group_fallback() -> bool {
+    true
+}
+
+/// Placeholders supported in the `filename` parameter.
+const FILENAME_PLACEHOLDERS: [&str; 5] = ["artist", "album", "year", "mbid", "type"];
+
+/// Placeholders whose values require fetching the release from MusicBrainz.
+const RELEASE_PLACEHOLDERS: [&str; 3] = ["artist", "album", "year"];
+
+/// Characters replaced in substituted values: path separators and
+/// characters Windows does not allow in filenames.
+const INVALID_FILENAME_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
 // ============================================================================
 // Structured Output
 // ============================================================================
@@ -155,12 +231,45 @@ fn default_thumbnail_size() -> String {
 pub struct CoverDownloadResult {
     pub success: bool,
     pub file_path: String,
+    /// Final filename with extension, after placeholder substitution.
+    pub filename: String,
     pub file_size_bytes: u64,
     pub image_type: String,
     pub thumbnail_size: String,
     pub source_url: String,
+    /// Entity the art was taken from: "release" or "release_group".
+    pub source_entity: String,
+    /// MIME type detected from the image bytes (e.g. "image/jpeg").
+    pub content_type: String,
+    /// True when the target file already held identical bytes and was left untouched.
+    #[serde(default)]
+    pub skipped: bool,
+}
+
+/// Structured output for batch cover downloads.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CoverBatchResult {
+    pub results: Vec<CoverBatchItemResult>,
+    pub total_count: usize,
+    pub success_count: usize,
+    pub failure_count: usize,
 }
 
+/// Outcome of a single batch entry.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CoverBatchItemResult {
+    pub mbid: String,
+    pub path: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<CoverDownloadResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Delay between batch entries to respect MusicBrainz / Cover Art Archive rate limits.
+const BATCH_ITEM_DELAY: std::time::Duration = std::time::Duration::from_millis(1000);
+
 // ============================================================================
 // Tool Implementation
 // ============================================================================
@@ -176,7 +285,9 @@ impl MbCoverDownloadTool {
     /// Tool description shown to clients.
     pub const DESCRIPTION: &'static str = "Download cover art images for music releases from the Cover Art Archive. \
          Supports multiple thumbnail sizes (250, 500, 1200, or original) with intelligent fallback. \
-         Prioritizes Front cover but falls back to other available images. \
+         Downloads the Front cover by default; use image_type to request back, booklet, or medium art, \
+         or 'any' to fall back to the first available image. \
+         Falls back to the release group's art when the release has none. \
          Returns structured data with file path, size, and image metadata.";
 
     pub fn new() -> Self {
@@ -184,34 +295,138 @@ impl MbCoverDownloadTool {
     }
 
     /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
     pub fn execute(params: &MbCoverDownloadParams, config: &Config) -> CallToolResult {
-        info!(
-            "Cover download tool called for MBID: {}, path: {}",
-            params.mbid, params.path
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
+
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &MbCoverDownloadParams) -> Vec<&str> {
+        params
+            .path
+            .iter()
+            .map(String::as_str)
+            .chain(params.items.iter().flatten().map(|item| item.path.as_str()))
+            .collect()
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &MbCoverDownloadParams, config: &Config) -> CallToolResult {
+        match (&params.items, &params.mbid, &params.path) {
+            (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+                error_result("'items' is mutually exclusive with 'mbid' and 'path'")
+            }
+            (Some(items), None, None) => Self::execute_batch(items, params, config),
+            (None, Some(mbid), Some(path)) => {
+                match Self::download(mbid, path, &params.filename, params, config) {
+                    Ok(result) => structured_result(Self::summarize(&result), result),
+                    Err(e) => error_result(&e),
+                }
+            }
+            (None, _, _) => error_result("Provide both 'mbid' and 'path', or a batch of 'items'"),
+        }
+    }
+
+    /// Download covers for every batch entry sequentially.
+    ///
+    /// A failing entry is recorded in its result and does not abort the batch.
+    fn execute_batch(
+        items: &[CoverDownloadItem],
+        params: &MbCoverDownloadParams,
+        config: &Config,
+    ) -> CallToolResult {
+        info!("Batch cover download called for {} item(s)", items.len());
+
+        let mut results = Vec::with_capacity(items.len());
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                std::thread::sleep(BATCH_ITEM_DELAY);
+            }
+
+            let filename = item.filename.as_deref().unwrap_or(&params.filename);
+            let outcome = Self::download(&item.mbid, &item.path, filename, params, config);
+            if let Err(e) = &outcome {
+                warn!("Batch item {} ({}) failed: {}", index + 1, item.mbid, e);
+            }
+
+            results.push(CoverBatchItemResult {
+                mbid: item.mbid.clone(),
+                path: item.path.clone(),
+                success: outcome.is_ok(),
+                error: outcome.as_ref().err().cloned(),
+                result: outcome.ok(),
+            });
+        }
+
+        let success_count = results.iter().filter(|r| r.success).count();
+        let batch = CoverBatchResult {
+            total_count: results.len(),
+            failure_count: results.len() - success_count,
+            success_count,
+            results,
+        };
+
+        let summary = format!(
+            "Downloaded {} of {} cover(s) ({} failed)",
+            batch.success_count, batch.total_count, batch.failure_count
         );
+        info!("{}", summary);
 
-        // 1. Validate MBID format
-        if !is_mbid(&params.mbid) {
-            warn!("Invalid MBID format: {}", params.mbid);
-            return error_result("Invalid MBID format (expected UUID)");
+        structured_result(summary, batch)
+    }
+
+    /// Text summary for a single successful download.
+    fn summarize(result: &CoverDownloadResult) -> String {
+        if result.skipped {
+            return format!(
+                "Skipped {} cover ({}): {} is already up to date ({} bytes)",
+                result.image_type, result.thumbnail_size, result.file_path, result.file_size_bytes
+            );
         }
+        format!(
+            "Downloaded {} cover ({}) from {} to {} ({} bytes)",
+            result.image_type,
+            result.thumbnail_size,
+            result.source_entity.replace('_', " "),
+            result.file_path,
+            result.file_size_bytes
+        )
+    }
+
+    /// Download the cover art of one release into `path`.
+    fn download(
+        mbid: &str,
+        path: &str,
+        filename: &str,
+        params: &MbCoverDownloadParams,
+        config: &Config,
+    ) -> Result<CoverDownloadResult, String> {
+        info!("Cover download called for MBID: {}, path: {}", mbid, path);
+
+        // 1. Validate MBID format
+        let Some(mbid) = extract_mbid(mbid) else {
+            warn!("Invalid MBID format: {}", mbid);
+            return Err("Invalid MBID format (expected UUID)".to_string());
+        };
+        let mbid = mbid.as_str();
 
         // 2. Validate path with security
-        let dir_path = match validate_path(&params.path, config) {
+        let dir_path = match validate_path_for(path, AccessKind::Write, config) {
             Ok(p) => p,
             Err(e) => {
                 warn!("Path security validation failed: {}", e);
-                return CallToolResult::error(vec![Content::text(format!(
-                    "Path security validation failed: {}",
-                    e
-                ))]);
+                return Err(format!("Path security validation failed: {}", e));
             }
         };
 
         // 3. Verify it's a directory
         if !dir_path.is_dir() {
-            warn!("Path is not a directory: {}", params.path);
-            return error_result(&format!("Path is not a directory: {}", params.path));
+            warn!("Path is not a directory: {}", path);
+            return Err(format!("Path is not a directory: {}", path));
         }
 
         // 4. Validate thumbnail_size
@@ -220,36 +435,61 @@ impl MbCoverDownloadTool {
             "250" | "500" | "1200" | "original"
         ) {
             warn!("Invalid thumbnail size: {}", params.thumbnail_size);
-            return error_result("Invalid thumbnail size (use 250, 500, 1200, or original)");
+            return Err("Invalid thumbnail size (use 250, 500, 1200, or original)".to_string());
         }
 
-        // 5. Fetch coverart metadata from Cover Art Archive
-        info!("Fetching cover art metadata for MBID: {}", params.mbid);
-        let coverart = match Self::fetch_coverart(&params.mbid) {
-            Ok(data) => data,
-            Err(e) => {
-                error!("Failed to fetch cover art: {}", e);
-                return error_result(&format!("Failed to fetch cover art: {}", e));
-            }
-        };
+        // 5. Validate image_type
+        if !matches!(
+            params.image_type.as_str(),
+            "front" | "back" | "booklet" | "medium" | "any"
+        ) {
+            warn!("Invalid image type: {}", params.image_type);
+            return Err(
+                "Invalid image type (use front, back, booklet, medium, or any)".to_string(),
+            );
+        }
 
-        // 6. Select the best image (Front prioritized)
-        let selected_image = match Self::select_best_image(&coverart) {
+        // 6. Substitute filename placeholders
+        let filename = &Self::resolve_filename(filename, mbid, &params.image_type)?;
+
+        // 7. Fail fast if a cover with this filename already exists
+        if !params.overwrite
+            && let Some(existing) = Self::find_existing_cover(&dir_path, filename)
+        {
+            warn!("File already exists: {}", existing.display());
+            return Err(format!(
+                "File already exists: {}. Use overwrite=true to replace",
+                existing.display()
+            ));
+        }
+
+        // 8. Fetch coverart metadata from Cover Art Archive
+        info!("Fetching cover art metadata for MBID: {}", mbid);
+        let (coverart, source_entity) =
+            match Self::fetch_coverart_with_fallback(mbid, params.release_group_fallback) {
+                Ok(data) => data,
+                Err(e) => {
+                    error!("Failed to fetch cover art: {}", e);
+                    return Err(format!("Failed to fetch cover art: {}", e));
+                }
+            };
+
+        // 9. Select the image matching the requested type
+        let selected_image = match Self::select_best_image(&coverart, &params.image_type) {
             Ok(img) => img,
             Err(e) => {
                 warn!("No suitable image found: {}", e);
-                return error_result(&format!("No suitable image found: {}", e));
+                return Err(format!("No suitable image found: {}", e));
             }
         };
 
-        // 7. Get URL for requested size with fallback
-        let (image_url, actual_size) =
-            Self::get_image_url(selected_image, &params.thumbnail_size);
+        // 10. Get URL for requested size with fallback
+        let (image_url, actual_size) = Self::get_image_url(selected_image, &params.thumbnail_size);
 
         // Validate URL
         if image_url.is_empty() {
             error!("Empty image URL received from API");
-            return error_result("Invalid image URL received from Cover Art Archive");
+            return Err("Invalid image URL received from Cover Art Archive".to_string());
         }
 
         info!(
@@ -258,8 +498,9 @@ impl MbCoverDownloadTool {
             image_url.chars().take(60).collect::<String>()
         );
 
-        // 8. Download the image with proper HTTP client configuration
+        // 11. Download the image with proper HTTP client configuration
         let client = match reqwest::blocking::Client::builder()
+            .user_agent(mb_user_agent())
             .redirect(reqwest::redirect::Policy::limited(10))
             .timeout(std::time::Duration::from_secs(30))
             .build()
@@ -267,7 +508,7 @@ impl MbCoverDownloadTool {
             Ok(c) => c,
             Err(e) => {
                 error!("Failed to build HTTP client: {:?}", e);
-                return error_result(&format!("Failed to build HTTP client: {}", e));
+                return Err(format!("Failed to build HTTP client: {}", e));
             }
         };
 
@@ -280,89 +521,113 @@ impl MbCoverDownloadTool {
 
         info!("Downloading from: {}", secure_url);
 
-        let image_bytes = match client.get(&secure_url).send() {
+        let (image_bytes, header_content_type) = match client.get(&secure_url).send() {
             Ok(response) => {
                 let status = response.status();
                 if !status.is_success() {
-                    error!("HTTP request failed with status: {} for URL: {}", status, secure_url);
-                    return error_result(&format!(
+                    error!(
+                        "HTTP request failed with status: {} for URL: {}",
+                        status, secure_url
+                    );
+                    return Err(format!(
                         "Failed to download image: HTTP {} - URL: {}",
                         status, secure_url
                     ));
                 }
+                let content_type = response
+                    .headers()
+                    .get(reqwest::header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| v.to_string());
                 match response.bytes() {
                     Ok(bytes) => {
                         if bytes.is_empty() {
                             error!("Received empty response from: {}", secure_url);
-                            return error_result("Failed to download image: Empty response");
+                            return Err("Failed to download image: Empty response".to_string());
                         }
-                        bytes
+                        (bytes, content_type)
                     }
                     Err(e) => {
                         error!("Failed to read response bytes: {:?}", e);
-                        return error_result(&format!("Failed to read image data: {}", e));
+                        return Err(format!("Failed to read image data: {}", e));
                     }
                 }
             }
             Err(e) => {
                 error!("HTTP request failed for URL {}: {:?}", secure_url, e);
-                return error_result(&format!(
+                return Err(format!(
                     "Failed to download image from {}: {}",
                     secure_url, e
                 ));
             }
         };
 
-        // 9. Determine file extension from URL
-        let extension = Self::detect_extension(&image_url);
-        let full_filename = format!("{}.{}", params.filename, extension);
+        // 12. Verify the payload is an image and determine its type from the bytes
+        let (content_type, extension) =
+            match Self::validate_image(&image_bytes, header_content_type.as_deref()) {
+                Ok(detected) => detected,
+                Err(e) => {
+                    error!(
+                        "Downloaded data from {} is not a valid image: {}",
+                        secure_url, e
+                    );
+                    return Err(format!("Downloaded data is not a valid image: {}", e));
+                }
+            };
+        let full_filename = format!("{}.{}", filename, extension);
         let file_path = dir_path.join(&full_filename);
 
-        // 10. Check if file exists
+        // 13. Validate the file itself, as a dangling symlink or a deny pattern
+        // only shows on its name
+        let file_path = match validate_write_target(&file_path.to_string_lossy(), config) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!("Path security validation failed: {}", e);
+                return Err(format!("Path security validation failed: {}", e));
+            }
+        };
+
+        // 14. Check if file exists
         if file_path.exists() && !params.overwrite {
             warn!("File already exists: {}", file_path.display());
-            return error_result(&format!(
+            return Err(format!(
                 "File already exists: {}. Use overwrite=true to replace",
                 file_path.display()
             ));
         }
 
-        // 11. Write the file
-        if let Err(e) = std::fs::write(&file_path, &image_bytes) {
+        // 15. Leave an identical existing file untouched, otherwise write the
+        // file atomically (temp file + rename)
+        let skipped = is_identical_file(&file_path, &image_bytes);
+        if skipped {
+            info!(
+                "Existing file is identical, skipping write: {}",
+                file_path.display()
+            );
+        } else if let Err(e) = write_atomic(&file_path, &image_bytes) {
             error!("Failed to write file: {:?}", e);
-            return error_result(&format!("Failed to write file: {}", e));
+            return Err(format!("Failed to write file: {}", e));
         }
 
-        // 12. Build result
-        let image_type = if selected_image.front {
-            "Front".to_string()
-        } else if selected_image.back {
-            "Back".to_string()
-        } else {
-            selected_image
-                .types
-                .first()
-                .cloned()
-                .unwrap_or_else(|| "Unknown".to_string())
-        };
+        // 16. Build result
+        let image_type = Self::describe_image_type(selected_image, &params.image_type);
 
         let result = CoverDownloadResult {
             success: true,
             file_path: file_path.display().to_string(),
+            filename: full_filename,
             file_size_bytes: image_bytes.len() as u64,
-            image_type: image_type.clone(),
-            thumbnail_size: actual_size.clone(),
+            image_type,
+            thumbnail_size: actual_size,
             source_url: secure_url,
+            source_entity: source_entity.to_string(),
+            content_type: content_type.to_string(),
+            skipped,
         };
 
-        let summary = format!(
-            "Downloaded {} cover ({}) to {} ({} bytes)",
-            image_type, actual_size, file_path.display(), result.file_size_bytes
-        );
-
-        info!("{}", summary);
+        info!("{}", Self::summarize(&result));
 
-        structured_result(summary, result)
+        Ok(result)
     }
 
     /// HTTP handler for this tool (for HTTP transport).
@@ -371,42 +636,8 @@ impl MbCoverDownloadTool {
         arguments: serde_json::Value,
         config: Arc<Config>,
     ) -> Result<serde_json::Value, String> {
-        let mbid = arguments
-            .get("mbid")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| "Missing or invalid 'mbid' parameter".to_string())?
-            .to_string();
-
-        let path = arguments
-            .get("path")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| "Missing or invalid 'path' parameter".to_string())?
-            .to_string();
-
-        let filename = arguments
-            .get("filename")
-            .and_then(|v| v.as_str())
-            .unwrap_or("cover")
-            .to_string();
-
-        let thumbnail_size = arguments
-            .get("thumbnail_size")
-            .and_then(|v| v.as_str())
-            .unwrap_or("500")
-            .to_string();
-
-        let overwrite = arguments
-            .get("overwrite")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
-
-        let params = MbCoverDownloadParams {
-            mbid,
-            path,
-            filename,
-            thumbnail_size,
-            overwrite,
-        };
+        let params: MbCoverDownloadParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
 
         // Use std::thread::spawn to avoid nested runtime panic.
         // musicbrainz_rs and reqwest::blocking both create their own runtime.
@@ -423,10 +654,10 @@ impl MbCoverDownloadTool {
 
         // Include structured_content if present
         if let Some(structured) = result.structured_content {
-            response.as_object_mut().unwrap().insert(
-                "structuredContent".to_string(),
-                structured,
-            );
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
         }
 
         Ok(response)
@@ -451,6 +682,7 @@ impl MbCoverDownloadTool {
     where
         S: Send + Sync + 'static,
     {
+        let timeout = config.tools.timeout_for(Self::NAME);
         ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             let config = config.clone();
@@ -459,15 +691,18 @@ impl MbCoverDownloadTool {
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
-                // Use std::thread::spawn to avoid nested runtime panic.
-                // musicbrainz_rs and reqwest::blocking both create their own runtime.
-                let handle = std::thread::spawn(move || Self::execute(&params, &config));
-
-                let result = handle.join().map_err(|_| {
-                    McpError::internal_error("Thread panicked".to_string(), None)
-                })?;
-
-                Ok(result)
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_on_thread(move || Self::execute(&params, &config)),
+                )
+                .await
             }
             .boxed()
         })
@@ -477,14 +712,68 @@ impl MbCoverDownloadTool {
     // Helper Functions
     // ========================================================================
 
+    /// Fetch release coverart, falling back to the release group's art.
+    ///
+    /// The fallback is used when the release has no Cover Art Archive entry
+    /// (404) or an empty image list. Returns the coverart together with the
+    /// entity it came from ("release" or "release_group").
+    fn fetch_coverart_with_fallback(
+        mbid: &str,
+        allow_fallback: bool,
+    ) -> Result<(Coverart, &'static str), String> {
+        if let Some(coverart) = Self::fetch_coverart("release", mbid)?
+            && !coverart.images.is_empty()
+        {
+            return Ok((coverart, "release"));
+        }
+
+        if !allow_fallback {
+            return Err(format!(
+                "No cover art available for this release (MBID: {}). The release may not have any uploaded cover art in the Cover Art Archive.",
+                mbid
+            ));
+        }
+
+        let release_group_id = Self::resolve_release_group(mbid)?;
+        info!(
+            "No release-level art for {}, trying release group {}",
+            mbid, release_group_id
+        );
+
+        match Self::fetch_coverart("release-group", &release_group_id)? {
+            Some(coverart) if !coverart.images.is_empty() => Ok((coverart, "release_group")),
+            _ => Err(format!(
+                "No cover art available for this release (MBID: {}) or its release group (MBID: {}).",
+                mbid, release_group_id
+            )),
+        }
+    }
+
+    /// Resolve the release group MBID of a release via the MusicBrainz API.
+    fn resolve_release_group(release_mbid: &str) -> Result<String, String> {
+        let release = Release::fetch()
+            .id(release_mbid)
+            .with_release_groups()
+            .execute_with_retry()
+            .map_err(|e| format!("Failed to resolve release group: {}", e))?;
+
+        release
+            .release_group
+            .map(|rg| rg.id)
+            .ok_or_else(|| format!("Release {} has no release group", release_mbid))
+    }
+
     /// Fetch coverart metadata from Cover Art Archive API.
-    fn fetch_coverart(mbid: &str) -> Result<Coverart, String> {
-        let url = format!("https://coverartarchive.org/release/{}", mbid);
+    ///
+    /// `entity` is the Cover Art Archive entity path ("release" or "release-group").
+    /// Returns `Ok(None)` when the archive has no entry for the MBID (HTTP 404).
+    pub(super) fn fetch_coverart(entity: &str, mbid: &str) -> Result<Option<Coverart>, String> {
+        let url = format!("https://coverartarchive.org/{}/{}", entity, mbid);
 
         info!("Fetching cover art from: {}", url);
 
         let client = reqwest::blocking::Client::builder()
-            .user_agent("MusicMCPServer/0.1.0")
+            .user_agent(mb_user_agent())
             .redirect(reqwest::redirect::Policy::limited(10))
             .timeout(std::time::Duration::from_secs(30))
             .build()
@@ -496,15 +785,15 @@ impl MbCoverDownloadTool {
             .map_err(|e| format!("HTTP request failed: {}", e))?;
 
         let status = response.status();
+        if status.as_u16() == 404 {
+            return Ok(None);
+        }
         if !status.is_success() {
-            // Provide more helpful error message for 404
-            if status.as_u16() == 404 {
-                return Err(format!(
-                    "No cover art available for this release (MBID: {}). The release may not have any uploaded cover art in the Cover Art Archive.",
-                    mbid
-                ));
-            }
-            return Err(format!("HTTP {} - {}", status, status.canonical_reason().unwrap_or("Unknown error")));
+            return Err(format!(
+                "HTTP {} - {}",
+                status,
+                status.canonical_reason().unwrap_or("Unknown error")
+            ));
         }
 
         let json_text = response
@@ -513,34 +802,137 @@ impl MbCoverDownloadTool {
 
         info!("Received JSON response ({} bytes)", json_text.len());
 
-        serde_json::from_str(&json_text)
-            .map_err(|e| format!("Failed to parse JSON: {} - Response: {}", e,
-                &json_text.chars().take(200).collect::<String>()))
+        serde_json::from_str(&json_text).map(Some).map_err(|e| {
+            format!(
+                "Failed to parse JSON: {} - Response: {}",
+                e,
+                &json_text.chars().take(200).collect::<String>()
+            )
+        })
     }
 
-    /// Select the best image (Front prioritized, fallback to first available).
-    fn select_best_image(coverart: &Coverart) -> Result<&CoverartImage, &'static str> {
-        if coverart.images.is_empty() {
-            return Err("No images available");
+    /// Check whether a release has a front cover, without downloading it.
+    ///
+    /// Sends a HEAD request for the front image; the archive answers with a
+    /// redirect when one exists. Network errors are logged and count as absent.
+    pub(super) fn has_front_cover(release_mbid: &str) -> bool {
+        let url = format!("https://coverartarchive.org/release/{}/front", release_mbid);
+
+        let client = match reqwest::blocking::Client::builder()
+            .user_agent(mb_user_agent())
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+        {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("Failed to build HTTP client: {}", e);
+                return false;
+            }
+        };
+
+        match client.head(&url).send() {
+            Ok(response) => {
+                let status = response.status();
+                status.is_success() || status.is_redirection()
+            }
+            Err(e) => {
+                warn!("Cover art check failed for {}: {}", release_mbid, e);
+                false
+            }
         }
+    }
 
-        // Priority 1: Image marked as "front"
-        if let Some(img) = coverart.images.iter().find(|img| img.front) {
-            return Ok(img);
+    /// Select the image matching the requested type.
+    ///
+    /// `"any"` prioritizes the Front cover and falls back to the first available
+    /// image. Any other type must be present, otherwise the error lists the
+    /// types that are available for the release.
+    fn select_best_image<'a>(
+        coverart: &'a Coverart,
+        image_type: &str,
+    ) -> Result<&'a CoverartImage, String> {
+        if coverart.images.is_empty() {
+            return Err("No images available".to_string());
         }
 
-        // Priority 2: Image with type "Front"
-        if let Some(img) = coverart.images.iter().find(|img| {
-            img.types.iter().any(|t| t.eq_ignore_ascii_case("front"))
-        }) {
-            return Ok(img);
+        if image_type == "any" {
+            return Ok(coverart
+                .images
+                .iter()
+                .find(|img| Self::image_has_type(img, "front"))
+                .unwrap_or(&coverart.images[0]));
         }
 
-        // Fallback: First available image
         coverart
             .images
-            .first()
-            .ok_or("No images available after fallback")
+            .iter()
+            .find(|img| Self::image_has_type(img, image_type))
+            .ok_or_else(|| {
+                format!(
+                    "No '{}' image available for this release (available types: {})",
+                    image_type,
+                    Self::available_types(coverart).join(", ")
+                )
+            })
+    }
+
+    /// Check whether an image matches a type, using the front/back flags
+    /// as well as the Cover Art Archive `types` list.
+    fn image_has_type(image: &CoverartImage, image_type: &str) -> bool {
+        match image_type {
+            "front" if image.front => true,
+            "back" if image.back => true,
+            _ => image
+                .types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(image_type)),
+        }
+    }
+
+    /// List the distinct image types available in a Cover Art Archive response.
+    fn available_types(coverart: &Coverart) -> Vec<String> {
+        let mut types: Vec<String> = Vec::new();
+        for image in &coverart.images {
+            let flagged = image
+                .front
+                .then(|| "Front".to_string())
+                .into_iter()
+                .chain(image.back.then(|| "Back".to_string()));
+            for t in flagged.chain(image.types.iter().cloned()) {
+                if !types.iter().any(|known| known.eq_ignore_ascii_case(&t)) {
+                    types.push(t);
+                }
+            }
+        }
+        if types.is_empty() {
+            types.push("none".to_string());
+        }
+        types
+    }
+
+    /// Human-readable type of the selected image, preferring the requested type.
+    fn describe_image_type(image: &CoverartImage, requested: &str) -> String {
+        if requested != "any"
+            && let Some(t) = image
+                .types
+                .iter()
+                .find(|t| t.eq_ignore_ascii_case(requested))
+        {
+            return t.clone();
+        }
+
+        if image.front {
+            "Front".to_string()
+        } else if image.back {
+            "Back".to_string()
+        } else {
+            image
+                .types
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "Unknown".to_string())
+        }
     }
 
     /// Get URL for requested size with intelligent fallback.
@@ -618,21 +1010,205 @@ impl MbCoverDownloadTool {
         }
     }
 
-    /// Detect file extension from URL.
-    fn detect_extension(url: &str) -> String {
-        if url.ends_with(".png") {
-            "png"
-        } else if url.ends_with(".jpg") || url.ends_with(".jpeg") {
-            "jpg"
-        } else if url.ends_with(".gif") {
-            "gif"
-        } else if url.ends_with(".webp") {
-            "webp"
+    /// Resolve the placeholders of a filename template for one release.
+    ///
+    /// The release is only fetched when `{artist}`, `{album}` or `{year}` is
+    /// used. `{type}` is the requested image type.
+    fn resolve_filename(template: &str, mbid: &str, image_type: &str) -> Result<String, String> {
+        // Substituted values are sanitized, but the template itself must name a file
+        // in the target directory
+        if template.contains(['/', '\\']) || template.contains("..") {
+            return Err(format!(
+                "Invalid filename '{}': path separators and '..' are not allowed",
+                template
+            ));
+        }
+        let placeholders = Self::filename_placeholders(template)?;
+        if placeholders.is_empty() {
+            return Ok(template.to_string());
+        }
+
+        let mut values =
+            HashMap::from([("mbid", mbid.to_string()), ("type", image_type.to_string())]);
+        if placeholders
+            .iter()
+            .any(|p| RELEASE_PLACEHOLDERS.contains(p))
+        {
+            let release = Release::fetch()
+                .id(mbid)
+                .with_artists()
+                .with_release_groups()
+                .execute_with_retry()
+                .map_err(|e| format!("Failed to fetch release metadata for filename: {}", e))?;
+
+            let year = release
+                .date
+                .as_ref()
+                .or_else(|| {
+                    release
+                        .release_group
+                        .as_ref()
+                        .and_then(|rg| rg.first_release_date.as_ref())
+                })
+                .and_then(|d| extract_year(&d.0))
+                .unwrap_or_else(|| "Unknown".to_string());
+            values.insert("artist", get_artist_name(&release.artist_credit));
+            values.insert("album", release.title);
+            values.insert("year", year);
+        }
+
+        let filename = Self::render_filename(template, &values);
+        if filename.is_empty() {
+            return Err(format!(
+                "Filename '{}' is empty after substitution",
+                template
+            ));
+        }
+        info!("Resolved filename '{}' to '{}'", template, filename);
+        Ok(filename)
+    }
+
+    /// Names of the `{placeholder}`s used in a filename template.
+    ///
+    /// Other braces are kept literally, but a `{name}` with an unknown name
+    /// is an error.
+    fn filename_placeholders(template: &str) -> Result<Vec<&str>, String> {
+        let mut placeholders = Vec::new();
+        let mut rest = template;
+        while let Some((_, name, after)) = Self::next_placeholder(rest) {
+            if !FILENAME_PLACEHOLDERS.contains(&name) {
+                return Err(format!(
+                    "Unknown filename placeholder '{{{}}}' (use {{artist}}, {{album}}, {{year}}, {{mbid}} or {{type}})",
+                    name
+                ));
+            }
+            placeholders.push(name);
+            rest = after;
+        }
+        Ok(placeholders)
+    }
+
+    /// Substitute each `{placeholder}` with its sanitized value.
+    fn render_filename(template: &str, values: &HashMap<&str, String>) -> String {
+        let mut filename = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some((before, name, after)) = Self::next_placeholder(rest) {
+            filename.push_str(before);
+            match values.get(name) {
+                Some(value) => filename.push_str(&Self::sanitize_filename_component(value)),
+                None => filename.push_str(&rest[before.len()..rest.len() - after.len()]),
+            }
+            rest = after;
+        }
+        filename.push_str(rest);
+        filename.trim().to_string()
+    }
+
+    /// Find the next `{name}` (ASCII letters and underscores) in `s`.
+    ///
+    /// Returns the text before it, the name and the text after it.
+    fn next_placeholder(s: &str) -> Option<(&str, &str, &str)> {
+        let mut offset = 0;
+        while let Some(start) = s[offset..].find('{').map(|i| offset + i) {
+            let after = &s[start + 1..];
+            let len = after
+                .find(|c: char| !(c.is_ascii_alphabetic() || c == '_'))
+                .unwrap_or(after.len());
+            if len > 0 && after[len..].starts_with('}') {
+                return Some((&s[..start], &after[..len], &after[len + 1..]));
+            }
+            offset = start + 1;
+        }
+        None
+    }
+
+    /// Make a substituted value safe to use inside a filename.
+    ///
+    /// Path separators, characters invalid on Windows and control characters
+    /// become `_`; leading and trailing dots and spaces are dropped so a value
+    /// cannot produce `..` or a hidden file.
+    fn sanitize_filename_component(value: &str) -> String {
+        value
+            .trim_matches(|c: char| c == '.' || c.is_whitespace())
+            .chars()
+            .map(|c| {
+                if INVALID_FILENAME_CHARS.contains(&c) || c.is_control() {
+                    '_'
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+
+    /// Find an existing cover image named `filename` with any supported extension.
+    fn find_existing_cover(dir: &Path, filename: &str) -> Option<PathBuf> {
+        ["jpg", "png", "gif", "webp"]
+            .iter()
+            .map(|ext| dir.join(format!("{}.{}", filename, ext)))
+            .find(|path| path.exists())
+    }
+
+    /// Detect the image format from its magic bytes.
+    ///
+    /// Returns the MIME type and file extension for JPEG, PNG, GIF and WebP data.
+    fn sniff_image_type(bytes: &[u8]) -> Option<(&'static str, &'static str)> {
+        if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(("image/jpeg", "jpg"))
+        } else if bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+            Some(("image/png", "png"))
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            Some(("image/gif", "gif"))
+        } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            Some(("image/webp", "webp"))
         } else {
-            // Fallback: jpg (most common format)
-            "jpg"
+            None
         }
-        .to_string()
+    }
+
+    /// Validate downloaded bytes against their magic bytes and the Content-Type header.
+    ///
+    /// The header, when present, must be an `image/*` type that agrees with the
+    /// sniffed format. Returns the sniffed MIME type and file extension.
+    fn validate_image(
+        bytes: &[u8],
+        header_content_type: Option<&str>,
+    ) -> Result<(&'static str, &'static str), String> {
+        // Normalize "image/jpg; charset=..." to "image/jpeg"
+        let declared = header_content_type.map(|header| {
+            let mime = header
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            if mime == "image/jpg" {
+                "image/jpeg".to_string()
+            } else {
+                mime
+            }
+        });
+
+        if let Some(declared) = &declared
+            && !declared.starts_with("image/")
+        {
+            return Err(format!("server returned Content-Type '{}'", declared));
+        }
+
+        let (mime, extension) = Self::sniff_image_type(bytes).ok_or_else(|| {
+            "unrecognized image format (expected JPEG, PNG, GIF or WebP)".to_string()
+        })?;
+
+        if let Some(declared) = &declared
+            && declared != mime
+        {
+            return Err(format!(
+                "Content-Type '{}' does not match detected format '{}'",
+                declared, mime
+            ));
+        }
+
+        Ok((mime, extension))
     }
 }
 
@@ -654,8 +1230,15 @@ mod tests {
     fn test_params_defaults() {
         let json = r#"{"mbid": "65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c", "path": "/tmp"}"#;
         let params: MbCoverDownloadParams = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            params.mbid.as_deref(),
+            Some("65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c")
+        );
+        assert!(params.items.is_none());
         assert_eq!(params.filename, "cover");
         assert_eq!(params.thumbnail_size, "500");
+        assert_eq!(params.image_type, "front");
+        assert!(params.release_group_fallback);
         assert_eq!(params.overwrite, false);
     }
 
@@ -666,40 +1249,126 @@ mod tests {
             "path": "/tmp",
             "filename": "album_art",
             "thumbnail_size": "1200",
+            "image_type": "back",
+            "release_group_fallback": false,
             "overwrite": true
         }"#;
         let params: MbCoverDownloadParams = serde_json::from_str(json).unwrap();
         assert_eq!(params.filename, "album_art");
         assert_eq!(params.thumbnail_size, "1200");
+        assert_eq!(params.image_type, "back");
+        assert!(!params.release_group_fallback);
         assert_eq!(params.overwrite, true);
     }
 
     #[test]
-    fn test_extension_detection() {
-        assert_eq!(
-            MbCoverDownloadTool::detect_extension("https://example.com/image.jpg"),
-            "jpg"
+    fn test_params_batch() {
+        let json = r#"{
+            "items": [
+                {"mbid": "65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c", "path": "/music/a"},
+                {"mbid": "b70e194e-29ba-4c2e-9f30-d8d2df6f5f42", "path": "/music/b", "filename": "folder"}
+            ],
+            "thumbnail_size": "1200"
+        }"#;
+        let params: MbCoverDownloadParams = serde_json::from_str(json).unwrap();
+        assert!(params.mbid.is_none());
+        let items = params.items.unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].filename, None);
+        assert_eq!(items[1].filename.as_deref(), Some("folder"));
+    }
+
+    #[test]
+    fn test_batch_and_single_are_mutually_exclusive() {
+        let json = r#"{
+            "mbid": "65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c",
+            "items": [{"mbid": "65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c", "path": "/tmp"}]
+        }"#;
+        let params: MbCoverDownloadParams = serde_json::from_str(json).unwrap();
+        let result = MbCoverDownloadTool::execute(&params, &Config::default());
+        assert!(result.is_error.unwrap_or(false));
+
+        let params: MbCoverDownloadParams = serde_json::from_str(r#"{"path": "/tmp"}"#).unwrap();
+        let result = MbCoverDownloadTool::execute(&params, &Config::default());
+        assert!(result.is_error.unwrap_or(false));
+    }
+
+    #[test]
+    fn test_batch_partial_failure_does_not_abort() {
+        use tempfile::TempDir;
+
+        // Both entries fail locally (invalid MBID, missing directory) without network access
+        let temp_dir = TempDir::new().unwrap();
+        let json = serde_json::json!({
+            "items": [
+                {"mbid": "not-an-mbid", "path": temp_dir.path()},
+                {"mbid": "65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c", "path": temp_dir.path().join("missing")}
+            ]
+        });
+        let params: MbCoverDownloadParams = serde_json::from_value(json).unwrap();
+        let result = MbCoverDownloadTool::execute(&params, &Config::default());
+
+        assert!(!result.is_error.unwrap_or(true));
+        let batch: CoverBatchResult =
+            serde_json::from_value(result.structured_content.unwrap()).unwrap();
+        assert_eq!(batch.total_count, 2);
+        assert_eq!(batch.failure_count, 2);
+        assert!(
+            batch
+                .results
+                .iter()
+                .all(|r| !r.success && r.error.is_some())
         );
+    }
+
+    #[test]
+    fn test_sniff_image_type() {
         assert_eq!(
-            MbCoverDownloadTool::detect_extension("https://example.com/image.jpeg"),
-            "jpg"
+            MbCoverDownloadTool::sniff_image_type(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00]),
+            Some(("image/jpeg", "jpg"))
         );
         assert_eq!(
-            MbCoverDownloadTool::detect_extension("https://example.com/image.png"),
-            "png"
+            MbCoverDownloadTool::sniff_image_type(b"\x89PNG\r\n\x1a\n\x00\x00"),
+            Some(("image/png", "png"))
         );
         assert_eq!(
-            MbCoverDownloadTool::detect_extension("https://example.com/image.gif"),
-            "gif"
+            MbCoverDownloadTool::sniff_image_type(b"GIF89a\x01\x00"),
+            Some(("image/gif", "gif"))
         );
         assert_eq!(
-            MbCoverDownloadTool::detect_extension("https://example.com/image.webp"),
-            "webp"
+            MbCoverDownloadTool::sniff_image_type(b"RIFF\x24\x00\x00\x00WEBPVP8 "),
+            Some(("image/webp", "webp"))
         );
         assert_eq!(
-            MbCoverDownloadTool::detect_extension("https://example.com/noext"),
-            "jpg"
-        ); // Fallback
+            MbCoverDownloadTool::sniff_image_type(b"<!DOCTYPE html><html>"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_validate_image() {
+        let jpeg = [0xFF, 0xD8, 0xFF, 0xE0, 0x00];
+
+        // Header matches (including the non-standard image/jpg alias)
+        assert!(MbCoverDownloadTool::validate_image(&jpeg, Some("image/jpeg")).is_ok());
+        assert!(
+            MbCoverDownloadTool::validate_image(&jpeg, Some("image/jpg; charset=binary")).is_ok()
+        );
+        assert!(MbCoverDownloadTool::validate_image(&jpeg, None).is_ok());
+
+        // HTML error page served with an HTML content type
+        let err = MbCoverDownloadTool::validate_image(b"<html>error</html>", Some("text/html"))
+            .unwrap_err();
+        assert!(err.contains("text/html"));
+
+        // HTML served with an image content type
+        assert!(
+            MbCoverDownloadTool::validate_image(b"<html>error</html>", Some("image/jpeg")).is_err()
+        );
+
+        // Header and bytes disagree
+        let err = MbCoverDownloadTool::validate_image(&jpeg, Some("image/png")).unwrap_err();
+        assert!(err.contains("does not match"));
     }
 
     #[test]
@@ -773,6 +1442,180 @@ mod tests {
         assert_eq!(size, "1200");
     }
 
+    fn fixture_image(id: &str, front: bool, back: bool, types: &[&str]) -> CoverartImage {
+        CoverartImage {
+            approved: true,
+            back,
+            comment: String::new(),
+            edit: 0,
+            front,
+            id: id.to_string(),
+            image: format!("http://example.com/{}.jpg", id),
+            thumbnails: Thumbnail {
+                small: None,
+                large: None,
+                res_250: None,
+                res_500: None,
+                res_1200: None,
+            },
+            types: types.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn mixed_coverart() -> Coverart {
+        Coverart {
+            images: vec![
+                fixture_image("1", false, false, &["Booklet"]),
+                fixture_image("2", false, true, &["Back", "Spine"]),
+                fixture_image("3", true, false, &["Front"]),
+            ],
+            release: None,
+        }
+    }
+
+    #[test]
+    fn test_select_image_by_type() {
+        let coverart = mixed_coverart();
+
+        let img = MbCoverDownloadTool::select_best_image(&coverart, "front").unwrap();
+        assert_eq!(img.id, "3");
+
+        let img = MbCoverDownloadTool::select_best_image(&coverart, "back").unwrap();
+        assert_eq!(img.id, "2");
+
+        let img = MbCoverDownloadTool::select_best_image(&coverart, "booklet").unwrap();
+        assert_eq!(img.id, "1");
+        assert_eq!(
+            MbCoverDownloadTool::describe_image_type(img, "booklet"),
+            "Booklet"
+        );
+
+        let img = MbCoverDownloadTool::select_best_image(&coverart, "any").unwrap();
+        assert_eq!(img.id, "3");
+    }
+
+    #[test]
+    fn test_select_missing_type_lists_available() {
+        let coverart = mixed_coverart();
+
+        let err = MbCoverDownloadTool::select_best_image(&coverart, "medium").unwrap_err();
+        assert!(err.contains("'medium'"));
+        assert!(err.contains("Booklet"));
+        assert!(err.contains("Back"));
+        assert!(err.contains("Spine"));
+        assert!(err.contains("Front"));
+    }
+
+    #[test]
+    fn test_select_any_falls_back_to_first_image() {
+        let coverart = Coverart {
+            images: vec![
+                fixture_image("1", false, false, &["Medium"]),
+                fixture_image("2", false, false, &["Booklet"]),
+            ],
+            release: None,
+        };
+
+        let img = MbCoverDownloadTool::select_best_image(&coverart, "any").unwrap();
+        assert_eq!(img.id, "1");
+        assert!(MbCoverDownloadTool::select_best_image(&coverart, "front").is_err());
+    }
+
+    #[test]
+    fn test_find_existing_cover() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        assert!(MbCoverDownloadTool::find_existing_cover(temp_dir.path(), "cover").is_none());
+
+        std::fs::write(temp_dir.path().join("cover.png"), b"x").unwrap();
+        assert_eq!(
+            MbCoverDownloadTool::find_existing_cover(temp_dir.path(), "cover"),
+            Some(temp_dir.path().join("cover.png"))
+        );
+    }
+
+    #[test]
+    fn test_filename_placeholders() {
+        assert_eq!(
+            MbCoverDownloadTool::filename_placeholders("cover").unwrap(),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            MbCoverDownloadTool::filename_placeholders("{artist} - {album} ({year}) - {type}")
+                .unwrap(),
+            vec!["artist", "album", "year", "type"]
+        );
+        // Braces that are not placeholders stay literal
+        assert_eq!(
+            MbCoverDownloadTool::filename_placeholders("{ cover } {} {mbid}").unwrap(),
+            vec!["mbid"]
+        );
+
+        let err = MbCoverDownloadTool::filename_placeholders("{artist} - {title}").unwrap_err();
+        assert!(err.contains("{title}"), "{}", err);
+    }
+
+    #[test]
+    fn test_render_filename() {
+        let values = HashMap::from([
+            ("artist", "Radiohead".to_string()),
+            ("album", "OK Computer".to_string()),
+            ("year", "1997".to_string()),
+            ("type", "front".to_string()),
+        ]);
+        assert_eq!(
+            MbCoverDownloadTool::render_filename("{artist} - {album} ({year}) - {type}", &values),
+            "Radiohead - OK Computer (1997) - front"
+        );
+        assert_eq!(
+            MbCoverDownloadTool::render_filename("{ {album} }", &values),
+            "{ OK Computer }"
+        );
+    }
+
+    #[test]
+    fn test_render_filename_sanitizes_values() {
+        let values = HashMap::from([
+            ("artist", "AC/DC".to_string()),
+            ("album", "What? <Live>: \"1979\" | \\ *".to_string()),
+            ("year", "..".to_string()),
+        ]);
+        assert_eq!(
+            MbCoverDownloadTool::render_filename("{artist} - {album}", &values),
+            "AC_DC - What_ _Live__ _1979_ _ _ _"
+        );
+        // A value cannot climb out of the target directory
+        assert_eq!(MbCoverDownloadTool::render_filename("{year}", &values), "");
+        assert_eq!(
+            MbCoverDownloadTool::sanitize_filename_component(" .hidden\n"),
+            "hidden"
+        );
+        assert_eq!(
+            MbCoverDownloadTool::sanitize_filename_component("Tab\there"),
+            "Tab_here"
+        );
+    }
+
+    #[test]
+    fn test_resolve_filename_without_release_placeholders() {
+        // {mbid} and {type} need no MusicBrainz request
+        let mbid = "65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c";
+        assert_eq!(
+            MbCoverDownloadTool::resolve_filename("{mbid}-{type}", mbid, "back").unwrap(),
+            "65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c-back"
+        );
+        assert_eq!(
+            MbCoverDownloadTool::resolve_filename("cover", mbid, "front").unwrap(),
+            "cover"
+        );
+        assert!(MbCoverDownloadTool::resolve_filename("{bogus}", mbid, "front").is_err());
+        for template in ["../cover", "art/cover", "..\\{type}", ".."] {
+            let err = MbCoverDownloadTool::resolve_filename(template, mbid, "front").unwrap_err();
+            assert!(err.contains("path separators"), "{}", err);
+        }
+    }
+
     // Network tests (require actual internet connection, run with --ignored)
     #[ignore]
     #[test]
@@ -784,10 +1627,13 @@ mod tests {
 
         let temp_dir = TempDir::new().unwrap();
         let params = MbCoverDownloadParams {
-            mbid: "65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c".to_string(),
-            path: temp_dir.path().to_string_lossy().to_string(),
+            mbid: Some("65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c".to_string()),
+            path: Some(temp_dir.path().to_string_lossy().to_string()),
             filename: "test_cover".to_string(),
             thumbnail_size: "250".to_string(),
+            items: None,
+            image_type: "front".to_string(),
+            release_group_fallback: true,
             overwrite: false,
         };
 
@@ -812,8 +1658,7 @@ mod tests {
 
         // Verify structured content
         if let Some(structured) = result.structured_content {
-            let cover_result: CoverDownloadResult =
-                serde_json::from_value(structured).unwrap();
+            let cover_result: CoverDownloadResult = serde_json::from_value(structured).unwrap();
             assert!(cover_result.success);
             assert!(cover_result.file_size_bytes > 0);
             assert!(cover_result.file_path.contains("test_cover"));
@@ -829,10 +1674,13 @@ mod tests {
 
         let temp_dir = TempDir::new().unwrap();
         let params = MbCoverDownloadParams {
-            mbid: "65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c".to_string(),
-            path: temp_dir.path().to_string_lossy().to_string(),
+            mbid: Some("65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c".to_string()),
+            path: Some(temp_dir.path().to_string_lossy().to_string()),
             filename: "original_cover".to_string(),
             thumbnail_size: "original".to_string(),
+            items: None,
+            image_type: "front".to_string(),
+            release_group_fallback: true,
             overwrite: false,
         };
 
@@ -855,10 +1703,13 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         // This MBID returns legacy format (small/large instead of res_250/res_500)
         let params = MbCoverDownloadParams {
-            mbid: "b70e194e-29ba-4c2e-9f30-d8d2df6f5f42".to_string(),
-            path: temp_dir.path().to_string_lossy().to_string(),
+            mbid: Some("b70e194e-29ba-4c2e-9f30-d8d2df6f5f42".to_string()),
+            path: Some(temp_dir.path().to_string_lossy().to_string()),
             filename: "legacy_cover".to_string(),
             thumbnail_size: "500".to_string(),
+            items: None,
+            image_type: "front".to_string(),
+            release_group_fallback: true,
             overwrite: false,
         };
 
@@ -868,16 +1719,19 @@ mod tests {
         if result.is_error.unwrap_or(true) {
             eprintln!("Error content: {:?}", result.content);
         }
-        assert!(!result.is_error.unwrap_or(true), "Expected success with legacy format");
+        assert!(
+            !result.is_error.unwrap_or(true),
+            "Expected success with legacy format"
+        );
 
         // Verify structured content
         if let Some(structured) = result.structured_content {
-            let cover_result: CoverDownloadResult =
-                serde_json::from_value(structured).unwrap();
+            let cover_result: CoverDownloadResult = serde_json::from_value(structured).unwrap();
             assert!(cover_result.success);
             assert!(cover_result.file_size_bytes > 0);
             assert_eq!(cover_result.thumbnail_size, "500");
             assert_eq!(cover_result.image_type, "Front");
+            assert_eq!(cover_result.source_entity, "release");
         } else {
             panic!("Expected structured content");
         }
@@ -0,0 +1,292 @@
+//! MusicBrainz Cover Art info tool.
+//!
+//! This tool lists the cover art available for a release in the Cover Art Archive
+//! (types, approval status, thumbnail resolutions) without downloading anything.
+
+use futures::FutureExt;
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Tool},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+
+use super::common::{error_result, extract_mbid, structured_result};
+use super::cover_download::{CoverartImage, MbCoverDownloadTool};
+use crate::core::config::Config;
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::timeout::with_timeout;
+use std::sync::Arc;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for cover art info operations.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MbCoverInfoParams {
+    /// MusicBrainz Release ID (UUID format).
+    #[schemars(description = "MusicBrainz Release ID (MBID) in UUID format")]
+    pub mbid: String,
+}
+
+// ============================================================================
+// Structured Output
+// ============================================================================
+
+/// Structured output for cover art info results.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CoverInfoResult {
+    pub release_mbid: String,
+    pub images: Vec<CoverImageInfo>,
+    pub total_count: usize,
+}
+
+/// A single image available in the Cover Art Archive.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CoverImageInfo {
+    pub id: String,
+    pub types: Vec<String>,
+    pub front: bool,
+    pub back: bool,
+    pub approved: bool,
+    pub comment: Option<String>,
+    pub image_url: String,
+    /// Available thumbnail resolutions (e.g. "250", "500", "1200").
+    pub thumbnail_sizes: Vec<String>,
+}
+
+impl From<&CoverartImage> for CoverImageInfo {
+    fn from(image: &CoverartImage) -> Self {
+        let thumbnails = &image.thumbnails;
+        let thumbnail_sizes = [
+            ("250", thumbnails.res_250.is_some() || thumbnails.small.is_some()),
+            ("500", thumbnails.res_500.is_some() || thumbnails.large.is_some()),
+            ("1200", thumbnails.res_1200.is_some()),
+        ]
+        .iter()
+        .filter(|(_, available)| *available)
+        .map(|(size, _)| size.to_string())
+        .collect();
+
+        Self {
+            id: image.id.clone(),
+            types: image.types.clone(),
+            front: image.front,
+            back: image.back,
+            approved: image.approved,
+            comment: Some(image.comment.clone()).filter(|c| !c.is_empty()),
+            image_url: image.image.clone(),
+            thumbnail_sizes,
+        }
+    }
+}
+
+// ============================================================================
+// Tool Implementation
+// ============================================================================
+
+/// MusicBrainz Cover Art Info Tool implementation.
+#[derive(Debug, Clone)]
+pub struct MbCoverInfoTool;
+
+impl MbCoverInfoTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "mb_cover_info";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "List the cover art available for a release in the Cover Art Archive without downloading it. \
+         Returns structured data for each image: id, types (Front, Back, Booklet, Medium...), \
+         approval status, comment, and available thumbnail resolutions. Read-only: nothing is written to disk.";
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    pub fn execute(params: &MbCoverInfoParams) -> CallToolResult {
+        info!("Cover info tool called for MBID: {}", params.mbid);
+
+        let Some(mbid) = extract_mbid(&params.mbid) else {
+            warn!("Invalid MBID format: {}", params.mbid);
+            return error_result("Invalid MBID format (expected UUID)");
+        };
+
+        let coverart = match MbCoverDownloadTool::fetch_coverart("release", &mbid) {
+            Ok(coverart) => coverart,
+            Err(e) => {
+                error!("Failed to fetch cover art: {}", e);
+                return error_result(&format!("Failed to fetch cover art: {}", e));
+            }
+        };
+
+        let images: Vec<CoverImageInfo> = coverart
+            .map(|c| c.images.iter().map(CoverImageInfo::from).collect())
+            .unwrap_or_default();
+
+        let summary = if images.is_empty() {
+            format!("No cover art available for release {}", mbid)
+        } else {
+            format!(
+                "Found {} cover art image(s) for release {}",
+                images.len(),
+                mbid
+            )
+        };
+
+        let result = CoverInfoResult {
+            release_mbid: mbid.clone(),
+            total_count: images.len(),
+            images,
+        };
+
+        structured_result(summary, result)
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(arguments: serde_json::Value) -> Result<serde_json::Value, String> {
+        let mbid = arguments
+            .get("mbid")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "Missing or invalid 'mbid' parameter".to_string())?
+            .to_string();
+
+        let params = MbCoverInfoParams { mbid };
+
+        // Use std::thread::spawn to avoid nested runtime panic.
+        // reqwest::blocking creates its own runtime.
+        let handle = std::thread::spawn(move || Self::execute(&params));
+
+        let result = handle
+            .join()
+            .map_err(|_| "Thread panicked during cover info lookup".to_string())?;
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        // Include structured_content if present
+        if let Some(structured) = result.structured_content {
+            response.as_object_mut().unwrap().insert(
+                "structuredContent".to_string(),
+                structured,
+            );
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<MbCoverInfoParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            async move {
+                let params: MbCoverInfoParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_on_thread(move || Self::execute(&params)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+impl Default for MbCoverInfoTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::cover_download::Thumbnail;
+
+    #[test]
+    fn test_image_info_from_coverart_image() {
+        let image = CoverartImage {
+            approved: false,
+            back: false,
+            comment: String::new(),
+            edit: 0,
+            front: true,
+            id: "12345".to_string(),
+            image: "http://example.com/image.jpg".to_string(),
+            thumbnails: Thumbnail {
+                small: Some("http://example.com/image-250.jpg".to_string()),
+                large: None,
+                res_250: None,
+                res_500: Some("http://example.com/image-500.jpg".to_string()),
+                res_1200: None,
+            },
+            types: vec!["Front".to_string(), "Booklet".to_string()],
+        };
+
+        let info = CoverImageInfo::from(&image);
+        assert_eq!(info.id, "12345");
+        assert_eq!(info.types, vec!["Front", "Booklet"]);
+        assert!(info.front);
+        assert!(!info.approved);
+        assert_eq!(info.comment, None);
+        assert_eq!(info.thumbnail_sizes, vec!["250", "500"]);
+    }
+
+    #[test]
+    fn test_invalid_mbid_rejected() {
+        let params = MbCoverInfoParams {
+            mbid: "not-an-mbid".to_string(),
+        };
+        let result = MbCoverInfoTool::execute(&params);
+        assert!(result.is_error.unwrap_or(false));
+    }
+
+    // Network test (requires internet connection, run with --ignored)
+    #[ignore]
+    #[test]
+    fn test_cover_info_real_release() {
+        std::thread::sleep(std::time::Duration::from_millis(1500));
+
+        let params = MbCoverInfoParams {
+            mbid: "65c70b9f-fdef-4bc0-a5b6-ac4e34252d3c".to_string(),
+        };
+        let result = MbCoverInfoTool::execute(&params);
+        assert!(!result.is_error.unwrap_or(true), "Expected success");
+
+        let structured = result.structured_content.expect("Expected structured content");
+        let info: CoverInfoResult = serde_json::from_value(structured).unwrap();
+        assert!(info.total_count > 0);
+    }
+}
@@ -0,0 +1,572 @@
+//! MusicBrainz artist discography tool.
+//!
+//! This tool builds the "what albums does X have" view of an artist: their
+//! release groups, deduplicated, grouped by type and sorted chronologically,
+//! each with a representative release that can be passed to other tools.
+
+use futures::FutureExt;
+use musicbrainz_rs::{
+    Browse,
+    entity::release::{Release, ReleaseStatus},
+    entity::release_group::{ReleaseGroup, ReleaseGroupPrimaryType, ReleaseGroupSecondaryType},
+};
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Tool},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, error, info, warn};
+
+use super::artist::MbArtistTool;
+use super::common::{
+    ExecuteWithRetry, MbRequestError, error_result, extract_year, secondary_type_names,
+    structured_result,
+};
+use crate::core::config::Config;
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::timeout::with_timeout;
+use std::sync::Arc;
+
+/// Page size for MusicBrainz browse requests (the API maximum).
+const PAGE_SIZE: u8 = 100;
+const DEFAULT_MAX_RELEASE_GROUPS: usize = 200;
+const MAX_RELEASE_GROUPS: usize = 1000;
+/// Pages of releases browsed to pick representative releases.
+const MAX_RELEASE_PAGES: usize = 5;
+
+/// Discography sections, in display order.
+const SECTIONS: [&str; 6] = ["Albums", "EPs", "Singles", "Live", "Compilations", "Other"];
+
+fn default_max_release_groups() -> usize {
+    DEFAULT_MAX_RELEASE_GROUPS
+}
+
+/// Parameters for the discography tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MbDiscographyParams {
+    /// Artist name or MBID.
+    #[schemars(description = "Artist name only (e.g., 'Radiohead') or artist MBID")]
+    pub artist: String,
+
+    /// Maximum number of release groups to fetch (default: 200, max: 1000).
+    #[schemars(
+        description = "Maximum number of release groups to fetch (default: 200, max: 1000)"
+    )]
+    #[serde(default = "default_max_release_groups")]
+    pub max_release_groups: usize,
+}
+
+/// Structured output for an artist discography.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DiscographyResult {
+    pub artist_name: String,
+    pub artist_mbid: String,
+    /// Non-empty sections in display order (Albums, EPs, Singles, Live, Compilations, Other)
+    pub sections: Vec<DiscographySection>,
+    pub total_count: usize,
+    /// True when the artist has more release groups than `max_release_groups`
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DiscographySection {
+    pub name: String,
+    pub release_groups: Vec<DiscographyEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DiscographyEntry {
+    pub title: String,
+    pub mbid: String,
+    pub first_release_year: Option<String>,
+    pub primary_type: Option<String>,
+    pub secondary_types: Vec<String>,
+    /// A release of this group (official and earliest when known)
+    pub representative_release_mbid: Option<String>,
+}
+
+/// MusicBrainz Discography Tool implementation.
+#[derive(Debug, Clone)]
+pub struct MbDiscographyTool;
+
+impl MbDiscographyTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "mb_discography";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Get an artist's discography from MusicBrainz: their release groups grouped into Albums, EPs, Singles, Live, Compilations and Other, deduplicated and sorted chronologically. Each entry has the first release year and a representative release MBID. Use this to answer 'what albums does X have'.";
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    pub fn execute(params: &MbDiscographyParams) -> CallToolResult {
+        let max_release_groups = params.max_release_groups.clamp(1, MAX_RELEASE_GROUPS);
+
+        Self::discography(&params.artist, max_release_groups)
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(arguments: serde_json::Value) -> Result<serde_json::Value, String> {
+        let artist = arguments
+            .get("artist")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "Missing or invalid 'artist' parameter".to_string())?
+            .to_string();
+
+        let max_release_groups = arguments
+            .get("max_release_groups")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_MAX_RELEASE_GROUPS, |v| v as usize);
+
+        let params = MbDiscographyParams {
+            artist,
+            max_release_groups,
+        };
+
+        // Use std::thread::spawn to avoid nested runtime panic.
+        // musicbrainz_rs uses reqwest::blocking which creates its own runtime.
+        let handle = std::thread::spawn(move || Self::execute(&params));
+
+        let result = handle
+            .join()
+            .map_err(|_| "Thread panicked during discography lookup".to_string())?;
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        // Include structured_content if present
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<MbDiscographyParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            async move {
+                let params: MbDiscographyParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_on_thread(move || Self::execute(&params)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+
+    /// Build the discography of an artist (name or MBID).
+    pub fn discography(artist: &str, max_release_groups: usize) -> CallToolResult {
+        info!("Building discography for artist: {}", artist);
+
+        let (artist_mbid, artist_name) = match MbArtistTool::resolve_artist(artist) {
+            Ok(artist) => artist,
+            Err(e) => return error_result(&e),
+        };
+
+        let (release_groups, available) =
+            match Self::browse_release_groups(&artist_mbid, max_release_groups) {
+                Ok(result) => result,
+                Err(e) => {
+                    error!("Release group browse failed: {:?}", e);
+                    return error_result(&format!("Release group browse failed: {}", e));
+                }
+            };
+
+        // Representative releases are a nice-to-have: keep the discography if this fails.
+        let wanted: HashSet<&str> = release_groups.iter().map(|rg| rg.id.as_str()).collect();
+        let representatives = match Self::browse_representatives(&artist_mbid, &wanted) {
+            Ok(representatives) => representatives,
+            Err(e) => {
+                warn!(
+                    "Release browse failed, omitting representative releases: {}",
+                    e
+                );
+                HashMap::new()
+            }
+        };
+
+        let sections = build_sections(release_groups, &representatives);
+        let total_count: usize = sections.iter().map(|s| s.release_groups.len()).sum();
+
+        let summary = if total_count == 0 {
+            format!("No release groups found for '{}'", artist_name)
+        } else {
+            let counts: Vec<String> = sections
+                .iter()
+                .map(|s| format!("{} {}", s.release_groups.len(), s.name))
+                .collect();
+            format!(
+                "Discography of '{}': {} release group(s) ({})",
+                artist_name,
+                total_count,
+                counts.join(", ")
+            )
+        };
+
+        let structured_data = DiscographyResult {
+            artist_name,
+            artist_mbid,
+            sections,
+            total_count,
+            truncated: available > max_release_groups,
+        };
+
+        structured_result(summary, structured_data)
+    }
+
+    /// Browse the artist's release groups page by page, up to `max`.
+    ///
+    /// Returns the release groups with the total the server reports.
+    fn browse_release_groups(
+        artist_mbid: &str,
+        max: usize,
+    ) -> Result<(Vec<ReleaseGroup>, usize), MbRequestError> {
+        let mut release_groups = Vec::new();
+        let mut available = 0;
+
+        while release_groups.len() < max {
+            let page = ReleaseGroup::browse()
+                .by_artist(artist_mbid)
+                .limit(PAGE_SIZE)
+                .offset(release_groups.len() as u16)
+                .execute_with_retry()?;
+            available = page.count.max(0) as usize;
+            debug!(
+                "Fetched {} release group(s) at offset {} of {}",
+                page.entities.len(),
+                page.offset,
+                available
+            );
+
+            let fetched = page.entities.len();
+            release_groups.extend(page.entities);
+            if fetched == 0 || release_groups.len() >= available {
+                break;
+            }
+        }
+
+        release_groups.truncate(max);
+        Ok((release_groups, available))
+    }
+
+    /// Browse the artist's releases to pick a representative release per wanted group.
+    fn browse_representatives(
+        artist_mbid: &str,
+        wanted: &HashSet<&str>,
+    ) -> Result<HashMap<String, String>, MbRequestError> {
+        let mut releases = Vec::new();
+
+        for page_index in 0..MAX_RELEASE_PAGES {
+            let page = Release::browse()
+                .by_artist(artist_mbid)
+                .with_release_groups()
+                .limit(PAGE_SIZE)
+                .offset((page_index * PAGE_SIZE as usize) as u16)
+                .execute_with_retry()?;
+
+            let fetched = page.entities.len();
+            releases.extend(page.entities);
+            if fetched < PAGE_SIZE as usize || releases.len() >= page.count.max(0) as usize {
+                break;
+            }
+            let covered = pick_representatives(&releases);
+            if wanted.iter().all(|id| covered.contains_key(*id)) {
+                break;
+            }
+        }
+
+        Ok(pick_representatives(&releases))
+    }
+}
+
+impl Default for MbDiscographyTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Section a release group belongs to. Live and compilation secondary types
+/// take precedence over the primary type.
+fn section_of(release_group: &ReleaseGroup) -> &'static str {
+    let secondary = &release_group.secondary_types;
+    if secondary.contains(&ReleaseGroupSecondaryType::Live) {
+        return "Live";
+    }
+    if secondary.contains(&ReleaseGroupSecondaryType::Compilation) {
+        return "Compilations";
+    }
+    match release_group.primary_type {
+        Some(ReleaseGroupPrimaryType::Album) => "Albums",
+        Some(ReleaseGroupPrimaryType::Ep) => "EPs",
+        Some(ReleaseGroupPrimaryType::Single) => "Singles",
+        _ => "Other",
+    }
+}
+
+/// Map release group MBIDs to a representative release: official releases
+/// first, then the earliest dated one.
+fn pick_representatives(releases: &[Release]) -> HashMap<String, String> {
+    let mut best: HashMap<String, &Release> = HashMap::new();
+
+    for release in releases {
+        let Some(group) = &release.release_group else {
+            continue;
+        };
+        let rank = |r: &Release| {
+            (
+                r.status != Some(ReleaseStatus::Official),
+                r.date
+                    .as_ref()
+                    .map(|d| d.0.clone())
+                    .unwrap_or_else(|| "9999".to_string()),
+            )
+        };
+        match best.get(&group.id) {
+            Some(current) if rank(current) <= rank(release) => {}
+            _ => {
+                best.insert(group.id.clone(), release);
+            }
+        }
+    }
+
+    best.into_iter()
+        .map(|(group_id, release)| (group_id, release.id.clone()))
+        .collect()
+}
+
+/// Deduplicate release groups, sort them chronologically and split them into sections.
+fn build_sections(
+    release_groups: Vec<ReleaseGroup>,
+    representatives: &HashMap<String, String>,
+) -> Vec<DiscographySection> {
+    let mut seen = HashSet::new();
+    let mut unique: Vec<ReleaseGroup> = release_groups
+        .into_iter()
+        .filter(|rg| seen.insert(rg.id.clone()))
+        .collect();
+
+    // Undated release groups go last.
+    unique.sort_by(|a, b| {
+        let date = |rg: &ReleaseGroup| {
+            rg.first_release_date
+                .as_ref()
+                .map(|d| d.0.clone())
+                .filter(|d| !d.is_empty())
+        };
+        (date(a).is_none(), date(a), &a.title).cmp(&(date(b).is_none(), date(b), &b.title))
+    });
+
+    SECTIONS
+        .iter()
+        .filter_map(|&name| {
+            let entries: Vec<DiscographyEntry> = unique
+                .iter()
+                .filter(|rg| section_of(rg) == name)
+                .map(|rg| DiscographyEntry {
+                    title: rg.title.clone(),
+                    mbid: rg.id.clone(),
+                    first_release_year: rg
+                        .first_release_date
+                        .as_ref()
+                        .and_then(|d| extract_year(&d.0)),
+                    primary_type: rg.primary_type.as_ref().map(|t| format!("{:?}", t)),
+                    secondary_types: secondary_type_names(&rg.secondary_types),
+                    representative_release_mbid: representatives.get(&rg.id).cloned(),
+                })
+                .collect();
+
+            (!entries.is_empty()).then(|| DiscographySection {
+                name: name.to_string(),
+                release_groups: entries,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::RawContent;
+
+    fn release_group(
+        id: &str,
+        title: &str,
+        date: &str,
+        primary: &str,
+        secondary: &[&str],
+    ) -> ReleaseGroup {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "title": title,
+            "first-release-date": date,
+            "primary-type": primary,
+            "secondary-type-ids": [],
+            "secondary-types": secondary,
+            "disambiguation": ""
+        }))
+        .unwrap()
+    }
+
+    fn release(id: &str, group_id: &str, status: &str, date: &str) -> Release {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "title": id,
+            "status": status,
+            "date": date,
+            "release-group": {
+                "id": group_id,
+                "title": group_id,
+                "secondary-type-ids": [],
+                "secondary-types": [],
+                "disambiguation": ""
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_discography_params_defaults() {
+        let params: MbDiscographyParams =
+            serde_json::from_str(r#"{"artist": "Radiohead"}"#).unwrap();
+        assert_eq!(params.artist, "Radiohead");
+        assert_eq!(params.max_release_groups, DEFAULT_MAX_RELEASE_GROUPS);
+    }
+
+    #[test]
+    fn test_section_of_prefers_secondary_types() {
+        assert_eq!(
+            section_of(&release_group("a", "A", "1997", "Album", &[])),
+            "Albums"
+        );
+        assert_eq!(
+            section_of(&release_group("b", "B", "1997", "EP", &[])),
+            "EPs"
+        );
+        assert_eq!(
+            section_of(&release_group("c", "C", "1997", "Single", &[])),
+            "Singles"
+        );
+        assert_eq!(
+            section_of(&release_group("d", "D", "2001", "Album", &["Live"])),
+            "Live"
+        );
+        assert_eq!(
+            section_of(&release_group("e", "E", "2008", "Album", &["Compilation"])),
+            "Compilations"
+        );
+        assert_eq!(
+            section_of(&release_group("f", "F", "2010", "Broadcast", &[])),
+            "Other"
+        );
+    }
+
+    #[test]
+    fn test_build_sections_dedupes_and_sorts() {
+        let groups = vec![
+            release_group("kid-a", "Kid A", "2000-10-02", "Album", &[]),
+            release_group("ok", "OK Computer", "1997-05-21", "Album", &[]),
+            release_group("undated", "Demo", "", "Album", &[]),
+            release_group("ok", "OK Computer", "1997-05-21", "Album", &[]),
+            release_group("creep", "Creep", "1992-09-21", "Single", &[]),
+        ];
+        let representatives = HashMap::from([("ok".to_string(), "ok-release".to_string())]);
+
+        let sections = build_sections(groups, &representatives);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].name, "Albums");
+        assert_eq!(sections[1].name, "Singles");
+
+        let titles: Vec<&str> = sections[0]
+            .release_groups
+            .iter()
+            .map(|e| e.title.as_str())
+            .collect();
+        assert_eq!(titles, ["OK Computer", "Kid A", "Demo"]);
+        assert_eq!(
+            sections[0].release_groups[0].first_release_year.as_deref(),
+            Some("1997")
+        );
+        assert_eq!(
+            sections[0].release_groups[0]
+                .representative_release_mbid
+                .as_deref(),
+            Some("ok-release")
+        );
+        assert_eq!(
+            sections[0].release_groups[1].representative_release_mbid,
+            None
+        );
+    }
+
+    #[test]
+    fn test_pick_representatives_prefers_official_then_earliest() {
+        let releases = vec![
+            release("bootleg", "ok", "Bootleg", "1996-01-01"),
+            release("reissue", "ok", "Official", "2009-03-24"),
+            release("original", "ok", "Official", "1997-05-21"),
+            release("single", "creep", "Official", "1992-09-21"),
+        ];
+
+        let representatives = pick_representatives(&releases);
+        assert_eq!(
+            representatives.get("ok").map(String::as_str),
+            Some("original")
+        );
+        assert_eq!(
+            representatives.get("creep").map(String::as_str),
+            Some("single")
+        );
+    }
+
+    // Integration tests (require network, run with: cargo test -- --ignored)
+    #[ignore]
+    #[test]
+    fn test_discography_radiohead() {
+        let result = MbDiscographyTool::discography("a74b1b7f-71a5-4011-9441-d0b5e4122711", 100);
+        assert!(
+            !result.is_error.unwrap_or(true),
+            "Expected success but got error"
+        );
+        if let RawContent::Text(text) = &result.content[0].raw {
+            assert!(text.text.contains("Albums"), "Expected an Albums section");
+        }
+    }
+}
@@ -0,0 +1,506 @@
+//! Batch audio identification for a whole directory.
+//!
+//! Scans a directory for audio files and identifies each one via
+//! AcoustID/Chromaprint, sharing a single HTTP client and rate limiter
+//! across the batch.
+
+use futures::FutureExt;
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, instrument, warn};
+
+use super::identify_record::{
+    IdentificationError, IdentificationResult, MatchOptions, MbIdentifyRecordTool, MetadataLevel,
+    default_fingerprint_length,
+};
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+use crate::domains::tools::timeout::with_timeout;
+
+// ============================================================================
+// Configuration & Constants
+// ============================================================================
+
+/// File extensions treated as audio files when scanning.
+pub(crate) const AUDIO_EXTENSIONS: &[&str] = &[
+    "mp3", "flac", "wav", "ogg", "oga", "opus", "m4a", "aac", "wma", "aiff", "aif", "ape", "wv",
+    "mpc",
+];
+
+/// Maximum number of audio files identified in a single call.
+pub(crate) const MAX_DIRECTORY_FILES: usize = 200;
+
+/// Minimum spacing between AcoustID lookups (the API allows 3 requests/second).
+const ACOUSTID_REQUEST_INTERVAL: Duration = Duration::from_millis(334);
+
+// ============================================================================
+// Structured Output Types
+// ============================================================================
+
+/// Structured output for directory identification.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DirectoryIdentificationResult {
+    pub directory: String,
+    pub recursive: bool,
+    pub results: Vec<FileIdentificationResult>,
+    pub total_count: usize,
+    pub identified_count: usize,
+    pub failure_count: usize,
+}
+
+/// Outcome of identifying a single file in the directory.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FileIdentificationResult {
+    pub file: String,
+    pub identified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<IdentificationResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the directory identification tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MbIdentifyDirectoryParams {
+    /// Path to the directory containing the audio files to identify
+    pub directory: String,
+
+    /// Also scan subdirectories (default: false)
+    #[serde(default)]
+    pub recursive: bool,
+
+    /// Maximum number of matches to return per file (default: 3, max: 10)
+    #[serde(default = "default_result_limit")]
+    pub limit: usize,
+
+    /// Metadata detail level (default: basic)
+    #[serde(default)]
+    pub metadata_level: MetadataLevel,
+
+    /// Drop matches whose confidence score (0.0-1.0) is below this value (default: keep all)
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
+
+    /// Drop recordings whose duration differs from the file's by more than this many seconds (default: keep all)
+    #[serde(default)]
+    pub max_duration_diff_secs: Option<u32>,
+
+    /// When AcoustID finds nothing for a file, search MusicBrainz using its title/artist tags (default: false)
+    #[serde(default)]
+    pub fallback_to_metadata: bool,
+
+    /// Seconds of audio to fingerprint from the start of each file (default: 120, max: 600)
+    #[serde(default = "default_fingerprint_length")]
+    pub max_fingerprint_length_secs: u32,
+}
+
+impl MbIdentifyDirectoryParams {
+    fn match_options(&self) -> MatchOptions {
+        MatchOptions {
+            limit: self.limit,
+            metadata_level: self.metadata_level,
+            meta_fields: None,
+            min_confidence: self.min_confidence,
+            max_duration_diff_secs: self.max_duration_diff_secs,
+            fallback_to_metadata: self.fallback_to_metadata,
+            fingerprint_length_secs: self.max_fingerprint_length_secs,
+        }
+    }
+}
+
+fn default_result_limit() -> usize {
+    3
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// MusicBrainz batch audio identification tool.
+pub struct MbIdentifyDirectoryTool;
+
+impl MbIdentifyDirectoryTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "mb_identify_directory";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Identify every audio file in a directory using acoustic fingerprinting via AcoustID/Chromaprint.\n\
+         \n\
+         Use this instead of calling mb_identify_record once per track, e.g. for an album of\n\
+         untagged files. Each file is fingerprinted and looked up with shared rate limiting;\n\
+         files that fail are reported individually and do not abort the batch.\n\
+         \n\
+         Returns a per-file result array (same shape as mb_identify_record) and an aggregate\n\
+         summary. Set recursive=true to include subdirectories.";
+
+    /// Create a new tool instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Execute the tool logic.
+    #[instrument(skip_all, fields(directory = %params.directory, recursive = params.recursive))]
+    pub async fn execute(params: &MbIdentifyDirectoryParams, config: &Config) -> CallToolResult {
+        info!("Starting directory identification");
+
+        match Self::identify_directory_internal(params, config).await {
+            Ok(data) => {
+                let summary = Self::build_text_summary(&data);
+                info!("{}", summary);
+                match serde_json::to_value(&data) {
+                    Ok(structured) => CallToolResult {
+                        content: vec![Content::text(summary)],
+                        structured_content: Some(structured),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(e) => {
+                        warn!("Failed to serialize structured content: {}", e);
+                        CallToolResult::success(vec![Content::text(summary)])
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Directory identification failed: {}", e);
+                CallToolResult::error(vec![Content::text(e.to_string())])
+            }
+        }
+    }
+
+    /// Identify all audio files in the directory sequentially.
+    ///
+    /// Per-file failures are recorded in the results; only errors that would
+    /// fail every file (missing fpcalc, invalid API key) abort the batch.
+    async fn identify_directory_internal(
+        params: &MbIdentifyDirectoryParams,
+        config: &Config,
+    ) -> Result<DirectoryIdentificationResult, IdentificationError> {
+        let directory = validate_path(&params.directory, config).map_err(|e| {
+            IdentificationError::FileNotFound(format!("Path security validation failed: {}", e))
+        })?;
+
+        if !directory.is_dir() {
+            return Err(IdentificationError::InvalidDirectory(format!(
+                "{} is not a directory",
+                params.directory
+            )));
+        }
+
+        let files = Self::collect_audio_files(&directory, params.recursive).map_err(|e| {
+            IdentificationError::InvalidDirectory(format!("Cannot read directory: {e}"))
+        })?;
+
+        if files.len() > MAX_DIRECTORY_FILES {
+            return Err(IdentificationError::InvalidDirectory(format!(
+                "Directory contains {} audio files (max {} per call); identify a subdirectory instead",
+                files.len(),
+                MAX_DIRECTORY_FILES
+            )));
+        }
+
+        debug!("Found {} audio file(s)", files.len());
+
+        if !files.is_empty() {
+            MbIdentifyRecordTool::ensure_fpcalc_installed(&config.tools).await?;
+        }
+
+        let api_key = config
+            .credentials
+            .acoustid_api_key
+            .as_deref()
+            .unwrap_or_default();
+        let client = MbIdentifyRecordTool::build_http_client()?;
+        let options = params.match_options();
+        let mut last_request: Option<Instant> = None;
+        let mut results = Vec::with_capacity(files.len());
+
+        for file in &files {
+            let file_path = file.to_string_lossy().into_owned();
+
+            if let Some(last) = last_request {
+                let elapsed = last.elapsed();
+                if elapsed < ACOUSTID_REQUEST_INTERVAL {
+                    tokio::time::sleep(ACOUSTID_REQUEST_INTERVAL - elapsed).await;
+                }
+            }
+            last_request = Some(Instant::now());
+
+            let outcome = match validate_path(&file_path, config) {
+                Ok(_) => {
+                    MbIdentifyRecordTool::identify_file(
+                        &client, api_key, &file_path, &options, config,
+                    )
+                    .await
+                }
+                Err(e) => Err(IdentificationError::FileNotFound(format!(
+                    "Path security validation failed: {}",
+                    e
+                ))),
+            };
+
+            match outcome {
+                Ok((_, result)) => results.push(FileIdentificationResult {
+                    file: file_path,
+                    identified: true,
+                    result: Some(result),
+                    error: None,
+                }),
+                Err(IdentificationError::InvalidApiKey) => {
+                    return Err(IdentificationError::InvalidApiKey);
+                }
+                Err(e) => {
+                    let message = match e {
+                        IdentificationError::NoMatches => {
+                            "No matches found in AcoustID database".to_string()
+                        }
+                        other => other.to_string(),
+                    };
+                    warn!("Failed to identify {}: {}", file_path, message);
+                    results.push(FileIdentificationResult {
+                        file: file_path,
+                        identified: false,
+                        result: None,
+                        error: Some(message),
+                    });
+                }
+            }
+        }
+
+        let identified_count = results.iter().filter(|r| r.identified).count();
+        Ok(DirectoryIdentificationResult {
+            directory: directory.to_string_lossy().into_owned(),
+            recursive: params.recursive,
+            total_count: results.len(),
+            failure_count: results.len() - identified_count,
+            identified_count,
+            results,
+        })
+    }
+
+    /// Collect audio files in `dir`, sorted by path.
+    ///
+    /// Symlinked directories are not followed to avoid traversal loops.
+    pub(crate) fn collect_audio_files(
+        dir: &Path,
+        recursive: bool,
+    ) -> std::io::Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+
+        while let Some(current) = pending.pop() {
+            for entry in std::fs::read_dir(&current)? {
+                let entry = entry?;
+                let file_type = entry.file_type()?;
+                let path = entry.path();
+
+                if file_type.is_dir() {
+                    if recursive {
+                        pending.push(path);
+                    }
+                } else if Self::is_audio_file(&path) {
+                    files.push(path);
+                }
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Check whether the path has a known audio extension.
+    pub(crate) fn is_audio_file(path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+    }
+
+    /// Build the aggregate summary line.
+    fn build_text_summary(data: &DirectoryIdentificationResult) -> String {
+        if data.total_count == 0 {
+            return format!("No audio files found in {}", data.directory);
+        }
+
+        format!(
+            "Identified {}/{} files in {} ({} failed)",
+            data.identified_count, data.total_count, data.directory, data.failure_count
+        )
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: MbIdentifyDirectoryParams =
+            serde_json::from_value(arguments).map_err(|e| format!("Invalid parameters: {}", e))?;
+
+        info!(
+            "Directory identification tool (HTTP) called for: {}",
+            params.directory
+        );
+
+        // Run on a dedicated thread with its own runtime: this handler is called
+        // synchronously from within the HTTP server's async context.
+        let handle = std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map(|runtime| runtime.block_on(Self::execute(&params, &config)))
+        });
+
+        let result = handle
+            .join()
+            .map_err(|_| "Identification thread panicked".to_string())?
+            .map_err(|e| format!("Failed to start identification runtime: {}", e))?;
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<MbIdentifyDirectoryParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: MbIdentifyDirectoryParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+                with_timeout(Self::NAME, timeout, async {
+                    Ok(Self::execute(&params, &config).await)
+                })
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+impl Default for MbIdentifyDirectoryTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_params_deserialization() {
+        let json = r#"{"directory": "/music/album"}"#;
+        let params: MbIdentifyDirectoryParams = serde_json::from_str(json).unwrap();
+        assert_eq!(params.directory, "/music/album");
+        assert!(!params.recursive);
+        assert_eq!(params.limit, 3);
+    }
+
+    #[test]
+    fn test_collect_audio_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let sub = temp_dir.path().join("disc2");
+        std::fs::create_dir(&sub).unwrap();
+        std::fs::write(temp_dir.path().join("02.flac"), b"").unwrap();
+        std::fs::write(temp_dir.path().join("01.MP3"), b"").unwrap();
+        std::fs::write(temp_dir.path().join("cover.jpg"), b"").unwrap();
+        std::fs::write(sub.join("01.ogg"), b"").unwrap();
+
+        let flat = MbIdentifyDirectoryTool::collect_audio_files(temp_dir.path(), false).unwrap();
+        let names: Vec<_> = flat
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["01.MP3", "02.flac"]);
+
+        let nested = MbIdentifyDirectoryTool::collect_audio_files(temp_dir.path(), true).unwrap();
+        assert_eq!(nested.len(), 3);
+        assert!(nested.contains(&sub.join("01.ogg")));
+    }
+
+    #[tokio::test]
+    async fn test_empty_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), b"").unwrap();
+
+        let params = MbIdentifyDirectoryParams {
+            directory: temp_dir.path().to_string_lossy().into_owned(),
+            recursive: false,
+            limit: 3,
+            metadata_level: MetadataLevel::Basic,
+            min_confidence: None,
+            max_duration_diff_secs: None,
+            fallback_to_metadata: false,
+            max_fingerprint_length_secs: 120,
+        };
+
+        let result = MbIdentifyDirectoryTool::execute(&params, &Config::default()).await;
+        assert!(!result.is_error.unwrap_or(false));
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["total_count"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_missing_directory() {
+        let params = MbIdentifyDirectoryParams {
+            directory: "/nonexistent/album".to_string(),
+            recursive: false,
+            limit: 3,
+            metadata_level: MetadataLevel::Basic,
+            min_confidence: None,
+            max_duration_diff_secs: None,
+            fallback_to_metadata: false,
+            max_fingerprint_length_secs: 120,
+        };
+
+        let result = MbIdentifyDirectoryTool::execute(&params, &Config::default()).await;
+        assert!(result.is_error.unwrap_or(false));
+    }
+}
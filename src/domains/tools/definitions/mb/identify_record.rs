@@ -4,6 +4,11 @@
 //! metadata is missing or incorrect (e.g., files downloaded from YouTube).
 
 use futures::FutureExt;
+use lofty::prelude::*;
+use musicbrainz_rs::{
+    Search,
+    entity::recording::{Recording, RecordingSearchQuery},
+};
 use rmcp::{
     ErrorData as McpError,
     handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
@@ -11,13 +16,17 @@ use rmcp::{
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
-use std::process::Command;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::process::Command;
 use tracing::{debug, error, info, instrument, warn};
 
-use crate::core::config::Config;
+use super::common::{ExecuteWithRetry, format_duration};
+use crate::core::config::{Config, ToolsConfig};
 use crate::core::security::validate_path;
+use crate::domains::tools::timeout::with_timeout;
 
 // ============================================================================
 // Configuration & Constants
@@ -28,6 +37,25 @@ const MAX_RETRIES: u32 = 3;
 const BASE_DELAY_MS: u64 = 1000;
 const REQUEST_TIMEOUT_SECS: u64 = 30;
 const MAX_RESULT_LIMIT: usize = 10;
+const FINGERPRINT_CACHE_CAPACITY: usize = 512;
+/// fpcalc's own default for `-length`.
+pub(super) const DEFAULT_FINGERPRINT_LENGTH_SECS: u32 = 120;
+const MAX_FINGERPRINT_LENGTH_SECS: u32 = 600;
+/// Largest difference between the decoded and the declared duration still treated as a match.
+const DURATION_MISMATCH_TOLERANCE_SECS: u32 = 5;
+/// Values accepted by the AcoustID lookup `meta` parameter.
+const ACOUSTID_META_FIELDS: &[&str] = &[
+    "recordings",
+    "recordingids",
+    "releases",
+    "releaseids",
+    "releasegroups",
+    "releasegroupids",
+    "tracks",
+    "compress",
+    "usermeta",
+    "sources",
+];
 
 // ============================================================================
 // Structured Output Types
@@ -37,13 +65,41 @@ const MAX_RESULT_LIMIT: usize = 10;
 #[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct IdentificationResult {
     pub file: String,
+    /// Metadata level used, or "custom" when `meta_fields` was given
     pub metadata_level: String,
+    /// AcoustID meta fields requested for this lookup
+    pub meta_fields: Vec<String>,
     pub matches: Vec<FingerprintMatch>,
     pub status: String,
+    /// Whether the fingerprint was reused from the cache instead of running fpcalc
+    pub fingerprint_cached: bool,
+    /// Number of matches dropped by `min_confidence` / `max_duration_diff_secs`
+    pub filtered_count: usize,
+    /// Where the matches came from: acoustic fingerprint or a tag-based search
+    pub match_source: MatchSource,
+    /// Duration of the audio as measured by fpcalc
+    pub audio_duration_secs: u32,
+    /// Duration declared by the file's headers/tags, as read by lofty
+    pub tag_duration_secs: Option<u32>,
+    /// Whether the two durations differ by more than a few seconds
+    pub duration_mismatch: bool,
+}
+
+/// Origin of identification matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchSource {
+    /// Matched by AcoustID from the audio fingerprint
+    Fingerprint,
+    /// Found by a MusicBrainz recording search using the file's title/artist tags
+    Metadata,
 }
 
 /// A single fingerprint match from AcoustID.
-#[derive(Debug, Clone, Serialize, JsonSchema)]
+///
+/// For metadata matches, `confidence` is 0.0 and `acoustid` is empty: they were
+/// not verified against the audio.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FingerprintMatch {
     pub rank: usize,
     pub confidence: f64,
@@ -52,7 +108,7 @@ pub struct FingerprintMatch {
 }
 
 /// Recording information from a match.
-#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RecordingMatch {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -63,10 +119,12 @@ pub struct RecordingMatch {
     pub artists: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub release_groups: Option<Vec<ReleaseGroupMatch>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub releases: Option<Vec<ReleaseMatch>>,
 }
 
 /// Release group information.
-#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReleaseGroupMatch {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
@@ -75,6 +133,35 @@ pub struct ReleaseGroupMatch {
     pub r#type: Option<String>,
 }
 
+/// A release containing the recording, with the track's position on it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReleaseMatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_group_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// Medium format (e.g. "CD", "Digital Media", "12\" Vinyl")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Disc number of the medium containing the track
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub medium_position: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub medium_count: Option<u32>,
+    /// Track number on the medium
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track_position: Option<u32>,
+    /// Number of tracks on the medium
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track_count: Option<u32>,
+}
+
 // ============================================================================
 // Tool Parameters
 // ============================================================================
@@ -101,14 +188,20 @@ impl MetadataLevel {
     /// Based on real API testing:
     /// - "recordingids" returns only IDs
     /// - "recordings" returns IDs + title + artists + duration
-    /// - "recordings releasegroups compress" returns everything + album info
+    /// - "recordings releasegroups releases tracks compress" returns everything + album info,
+    ///   with the releases and the position of the track on them
     fn as_api_param(self) -> &'static str {
         match self {
             Self::Minimal => "recordingids",
             Self::Basic => "recordings",
-            Self::Full => "recordings releasegroups compress",
+            Self::Full => "recordings releasegroups releases tracks compress",
         }
     }
+
+    /// The individual AcoustID meta fields requested by this level.
+    fn meta_fields(self) -> Vec<&'static str> {
+        self.as_api_param().split(' ').collect()
+    }
 }
 
 impl Default for MetadataLevel {
@@ -130,12 +223,116 @@ pub struct MbIdentifyRecordParams {
     /// Metadata detail level (default: basic)
     #[serde(default)]
     pub metadata_level: MetadataLevel,
+
+    /// Exact AcoustID meta fields to request, overriding metadata_level (recordings, recordingids, releases, releaseids, releasegroups, releasegroupids, tracks, compress, usermeta, sources)
+    #[serde(default)]
+    pub meta_fields: Vec<String>,
+
+    /// Drop matches whose confidence score (0.0-1.0) is below this value (default: keep all)
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
+
+    /// Drop recordings whose duration differs from the file's by more than this many seconds (default: keep all)
+    #[serde(default)]
+    pub max_duration_diff_secs: Option<u32>,
+
+    /// When AcoustID finds nothing, search MusicBrainz using the file's title/artist tags (default: false)
+    #[serde(default)]
+    pub fallback_to_metadata: bool,
+
+    /// Seconds of audio to fingerprint from the start of the file (default: 120, max: 600)
+    #[serde(default = "default_fingerprint_length")]
+    pub max_fingerprint_length_secs: u32,
+}
+
+impl MbIdentifyRecordParams {
+    fn match_options(&self) -> Result<MatchOptions, IdentificationError> {
+        let meta_fields = if self.meta_fields.is_empty() {
+            None
+        } else {
+            Some(parse_meta_fields(&self.meta_fields)?)
+        };
+
+        Ok(MatchOptions {
+            limit: self.limit,
+            metadata_level: self.metadata_level,
+            meta_fields,
+            min_confidence: self.min_confidence,
+            max_duration_diff_secs: self.max_duration_diff_secs,
+            fallback_to_metadata: self.fallback_to_metadata,
+            fingerprint_length_secs: self.max_fingerprint_length_secs,
+        })
+    }
+}
+
+/// Validate user-supplied meta fields against [`ACOUSTID_META_FIELDS`], dropping duplicates.
+fn parse_meta_fields(fields: &[String]) -> Result<Vec<&'static str>, IdentificationError> {
+    let mut parsed = Vec::with_capacity(fields.len());
+    for field in fields {
+        let field = field.trim().to_ascii_lowercase();
+        let Some(known) = ACOUSTID_META_FIELDS.iter().find(|f| **f == field) else {
+            return Err(IdentificationError::InvalidParameter(format!(
+                "Unknown meta field '{}' (use {})",
+                field,
+                ACOUSTID_META_FIELDS.join(", ")
+            )));
+        };
+        if !parsed.contains(known) {
+            parsed.push(*known);
+        }
+    }
+    Ok(parsed)
 }
 
 fn default_result_limit() -> usize {
     3
 }
 
+pub(super) fn default_fingerprint_length() -> u32 {
+    DEFAULT_FINGERPRINT_LENGTH_SECS
+}
+
+/// Per-call options for fingerprinting a file and turning AcoustID results into matches.
+#[derive(Debug, Clone)]
+pub(super) struct MatchOptions {
+    pub(super) limit: usize,
+    pub(super) metadata_level: MetadataLevel,
+    /// Explicit AcoustID meta fields, overriding `metadata_level` when set
+    pub(super) meta_fields: Option<Vec<&'static str>>,
+    pub(super) min_confidence: Option<f64>,
+    pub(super) max_duration_diff_secs: Option<u32>,
+    pub(super) fallback_to_metadata: bool,
+    pub(super) fingerprint_length_secs: u32,
+}
+
+impl MatchOptions {
+    /// AcoustID meta fields to request: the explicit list, or the level's fields.
+    fn requested_meta_fields(&self) -> Vec<&'static str> {
+        self.meta_fields
+            .clone()
+            .unwrap_or_else(|| self.metadata_level.meta_fields())
+    }
+
+    /// Level used when reading the response. Explicit meta fields keep
+    /// everything AcoustID returned, so they are read like `Full`.
+    fn extraction_level(&self) -> MetadataLevel {
+        if self.meta_fields.is_some() {
+            MetadataLevel::Full
+        } else {
+            self.metadata_level
+        }
+    }
+
+    /// Value reported as `metadata_level` in the structured result.
+    fn metadata_level_label(&self) -> String {
+        if self.meta_fields.is_some() {
+            "custom".to_string()
+        } else {
+            format!("{:?}", self.metadata_level).to_lowercase()
+        }
+    }
+}
+
 // ============================================================================
 // AcoustID API Response Structures
 // ============================================================================
@@ -165,7 +362,6 @@ struct AcoustIDRecording {
     duration: Option<f64>,
     #[serde(default)]
     artists: Vec<AcoustIDArtist>,
-    #[allow(dead_code)] // Used for deserialization but not read in current implementation
     #[serde(default)]
     releases: Vec<AcoustIDRelease>,
     #[serde(default)]
@@ -196,7 +392,6 @@ struct AcoustIDReleaseGroup {
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)] // Used for deserialization but not read in current implementation
 struct AcoustIDRelease {
     #[serde(default)]
     id: Option<String>,
@@ -215,7 +410,6 @@ struct AcoustIDRelease {
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)] // Used for deserialization but not read in current implementation
 struct AcoustIDMedium {
     #[serde(default)]
     position: Option<u32>,
@@ -232,7 +426,6 @@ struct AcoustIDTrack {
     #[allow(dead_code)]
     #[serde(default)]
     id: Option<String>,
-    #[allow(dead_code)]
     #[serde(default)]
     position: Option<u32>,
     #[serde(default)]
@@ -240,7 +433,7 @@ struct AcoustIDTrack {
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)] // Used for deserialization but not read in current implementation
+#[allow(dead_code)] // Only the year is currently surfaced
 struct AcoustIDDate {
     year: Option<u32>,
     month: Option<u32>,
@@ -256,10 +449,31 @@ struct AcoustIDError {
 // Helper Structures
 // ============================================================================
 
-#[derive(Debug)]
-struct FingerprintData {
-    duration: u32,
-    fingerprint: String,
+#[derive(Debug, Clone)]
+pub(super) struct FingerprintData {
+    pub(super) duration: u32,
+    pub(super) fingerprint: String,
+}
+
+/// Cached fpcalc output, valid while the file's size and mtime are unchanged.
+#[derive(Debug, Clone)]
+struct CachedFingerprint {
+    size: u64,
+    modified: Option<SystemTime>,
+    length_secs: u32,
+    data: FingerprintData,
+    last_used: Instant,
+}
+
+/// Process-wide fingerprint cache keyed by canonical file path.
+static FINGERPRINT_CACHE: LazyLock<Mutex<HashMap<PathBuf, CachedFingerprint>>> =
+    LazyLock::new(Default::default);
+
+/// Recordings found by searching MusicBrainz with a file's tags.
+struct TagSearchCandidates {
+    title: String,
+    artist: String,
+    recordings: Vec<Recording>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -273,10 +487,16 @@ struct FpcalcOutput {
 // ============================================================================
 
 #[derive(Debug, thiserror::Error)]
-enum IdentificationError {
+pub(super) enum IdentificationError {
     #[error("Audio file not found or inaccessible: {0}")]
     FileNotFound(String),
 
+    #[error("Invalid directory: {0}")]
+    InvalidDirectory(String),
+
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+
     #[error("Chromaprint (fpcalc) is not installed.\n{0}")]
     FpcalcNotFound(String),
 
@@ -339,7 +559,7 @@ impl MbIdentifyRecordTool {
 
     /// Execute the tool logic.
     #[instrument(skip_all, fields(file_path = %params.file_path, limit = params.limit))]
-    pub fn execute(params: &MbIdentifyRecordParams, config: &Config) -> CallToolResult {
+    pub async fn execute(params: &MbIdentifyRecordParams, config: &Config) -> CallToolResult {
         info!("Starting audio identification");
 
         // Get API key from config (always present due to default)
@@ -349,7 +569,7 @@ impl MbIdentifyRecordTool {
             .as_deref()
             .unwrap_or_default();
 
-        match Self::identify_audio_internal(params, api_key, config) {
+        match Self::identify_audio_internal(params, api_key, config).await {
             Ok((summary, structured_data)) => {
                 info!("Audio identification completed successfully");
                 match serde_json::to_value(&structured_data) {
@@ -374,11 +594,13 @@ impl MbIdentifyRecordTool {
     }
 
     /// Internal identification logic with proper error handling.
-    fn identify_audio_internal(
+    async fn identify_audio_internal(
         params: &MbIdentifyRecordParams,
         api_key: &str,
         config: &Config,
     ) -> Result<(String, IdentificationResult), IdentificationError> {
+        let options = params.match_options()?;
+
         // Validate path security first
         validate_path(&params.file_path, config).map_err(|e| {
             IdentificationError::FileNotFound(format!("Path security validation failed: {}", e))
@@ -387,21 +609,230 @@ impl MbIdentifyRecordTool {
         // Validate file exists and is accessible
         Self::validate_file(&params.file_path)?;
 
-        // Validate and clamp limit
-        let limit = params.limit.clamp(1, MAX_RESULT_LIMIT);
+        Self::ensure_fpcalc_installed(&config.tools).await?;
+        let client = Self::build_http_client()?;
 
+        Self::identify_file(&client, api_key, &params.file_path, &options, config).await
+    }
+
+    /// Fingerprint a validated file and look it up on AcoustID.
+    ///
+    /// Callers are responsible for path validation and for checking that
+    /// fpcalc is installed, so batch callers only pay for that once.
+    pub(super) async fn identify_file(
+        client: &reqwest::Client,
+        api_key: &str,
+        file_path: &str,
+        options: &MatchOptions,
+        config: &Config,
+    ) -> Result<(String, IdentificationResult), IdentificationError> {
         // Generate fingerprint
-        let fingerprint_data = Self::generate_fingerprint(&params.file_path)?;
+        let (fingerprint_data, fingerprint_cached) =
+            Self::fingerprint_with_cache(file_path, options.fingerprint_length_secs, &config.tools)
+                .await?;
 
         // Query API
-        let response = Self::query_acoustid(api_key, &fingerprint_data, params.metadata_level)?;
+        let meta = options.requested_meta_fields().join(" ");
+        let response = Self::query_acoustid(client, api_key, &fingerprint_data, &meta).await?;
 
         // Build structured result and summary
-        Self::build_results(&response, &params.file_path, limit, &params.metadata_level)
+        let (mut summary, mut data) = match Self::build_results(
+            &response,
+            file_path,
+            options,
+            fingerprint_data.duration,
+            fingerprint_cached,
+        ) {
+            Err(IdentificationError::NoMatches) if options.fallback_to_metadata => {
+                info!("No fingerprint match, falling back to tag-based search");
+                let owned_path = file_path.to_string();
+                let candidates =
+                    tokio::task::spawn_blocking(move || Self::search_by_tags(&owned_path))
+                        .await
+                        .map_err(|e| {
+                            IdentificationError::ApiError(format!("Metadata search failed: {}", e))
+                        })??;
+
+                Self::build_metadata_results(
+                    candidates,
+                    file_path,
+                    options,
+                    fingerprint_data.duration,
+                    fingerprint_cached,
+                )
+            }
+            other => other?,
+        };
+
+        let owned_path = file_path.to_string();
+        let tag_duration =
+            tokio::task::spawn_blocking(move || Self::read_tag_duration(&owned_path))
+                .await
+                .ok()
+                .flatten();
+        Self::apply_tag_duration(&mut summary, &mut data, tag_duration);
+
+        Ok((summary, data))
+    }
+
+    /// Duration declared by the file's headers/tags, or `None` if lofty can't read it.
+    ///
+    /// Blocking: reads the file with lofty.
+    fn read_tag_duration(file_path: &str) -> Option<u32> {
+        let tagged_file = lofty::read_from_path(file_path).ok()?;
+        let secs = tagged_file.properties().duration().as_secs_f64().round() as u32;
+        (secs > 0).then_some(secs)
+    }
+
+    /// Record the declared duration and flag it when it disagrees with fpcalc's.
+    fn apply_tag_duration(
+        summary: &mut String,
+        data: &mut IdentificationResult,
+        tag_duration: Option<u32>,
+    ) {
+        data.tag_duration_secs = tag_duration;
+        data.duration_mismatch = tag_duration.is_some_and(|tag| {
+            tag.abs_diff(data.audio_duration_secs) > DURATION_MISMATCH_TOLERANCE_SECS
+        });
+
+        if data.duration_mismatch
+            && let Some(tag) = tag_duration
+        {
+            summary.push_str(&format!(
+                " [duration mismatch: audio is {} but tags report {}]",
+                format_duration(u64::from(data.audio_duration_secs) * 1000),
+                format_duration(u64::from(tag) * 1000)
+            ));
+        }
+    }
+
+    /// Search MusicBrainz recordings using the file's title and artist tags.
+    ///
+    /// Blocking: reads the file with lofty and uses the blocking MusicBrainz client.
+    fn search_by_tags(file_path: &str) -> Result<TagSearchCandidates, IdentificationError> {
+        let tagged_file =
+            lofty::read_from_path(file_path).map_err(|_| IdentificationError::NoMatches)?;
+        let tag = tagged_file
+            .primary_tag()
+            .or_else(|| tagged_file.first_tag())
+            .ok_or(IdentificationError::NoMatches)?;
+
+        let title = tag
+            .title()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        let artist = tag
+            .artist()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        let (Some(title), Some(artist)) = (title, artist) else {
+            debug!("File has no title/artist tags, cannot fall back to metadata search");
+            return Err(IdentificationError::NoMatches);
+        };
+
+        let query = RecordingSearchQuery::query_builder()
+            .recording(&title)
+            .and()
+            .artist(&artist)
+            .build();
+
+        let recordings = Recording::search(query)
+            .execute_with_retry()
+            .map_err(|e| {
+                IdentificationError::ApiError(format!("MusicBrainz search failed: {}", e))
+            })?
+            .entities;
+
+        if recordings.is_empty() {
+            return Err(IdentificationError::NoMatches);
+        }
+
+        Ok(TagSearchCandidates {
+            title,
+            artist,
+            recordings,
+        })
+    }
+
+    /// Build results from a tag-based recording search.
+    fn build_metadata_results(
+        candidates: TagSearchCandidates,
+        file_path: &str,
+        options: &MatchOptions,
+        file_duration: u32,
+        fingerprint_cached: bool,
+    ) -> (String, IdentificationResult) {
+        let limit = options.limit.clamp(1, MAX_RESULT_LIMIT);
+        let mut matches = Vec::new();
+        let mut filtered_count = 0;
+
+        for recording in candidates.recordings {
+            let duration = recording.length.map(|ms| ms / 1000);
+            if !Self::duration_within(
+                duration.map(f64::from),
+                file_duration,
+                options.max_duration_diff_secs,
+            ) {
+                filtered_count += 1;
+                continue;
+            }
+
+            if matches.len() >= limit {
+                continue;
+            }
+
+            let (title, artists) = match options.extraction_level() {
+                MetadataLevel::Minimal => (None, None),
+                MetadataLevel::Basic | MetadataLevel::Full => (
+                    Some(recording.title),
+                    recording
+                        .artist_credit
+                        .map(|credits| credits.into_iter().map(|c| c.name).collect()),
+                ),
+            };
+
+            matches.push(FingerprintMatch {
+                rank: matches.len() + 1,
+                confidence: 0.0,
+                acoustid: String::new(),
+                recordings: vec![RecordingMatch {
+                    id: recording.id,
+                    title,
+                    duration,
+                    artists,
+                    release_groups: None,
+                    releases: None,
+                }],
+            });
+        }
+
+        let data = IdentificationResult {
+            file: file_path.to_string(),
+            metadata_level: options.metadata_level_label(),
+            meta_fields: Self::meta_field_names(options),
+            matches,
+            status: "success".to_string(),
+            fingerprint_cached,
+            filtered_count,
+            match_source: MatchSource::Metadata,
+            audio_duration_secs: file_duration,
+            tag_duration_secs: None,
+            duration_mismatch: false,
+        };
+
+        let summary = format!(
+            "No acoustic fingerprint match. Tag-based search for '{}' by {} found {} candidate(s); \
+             these come from the file's existing tags, not audio analysis, so verify before use",
+            candidates.title,
+            candidates.artist,
+            data.matches.len()
+        );
+
+        (summary, data)
     }
 
     /// Validate that the file exists and is accessible.
-    fn validate_file(file_path: &str) -> Result<(), IdentificationError> {
+    pub(super) fn validate_file(file_path: &str) -> Result<(), IdentificationError> {
         let path = Path::new(file_path);
 
         if !path.exists() {
@@ -424,29 +855,126 @@ impl MbIdentifyRecordTool {
         Ok(())
     }
 
-    /// Generate audio fingerprint using fpcalc command-line tool.
-    #[instrument(skip_all, fields(file = %file_path))]
-    fn generate_fingerprint(file_path: &str) -> Result<FingerprintData, IdentificationError> {
-        // Check if fpcalc is installed
-        if !Self::is_fpcalc_installed() {
-            return Err(IdentificationError::FpcalcNotFound(
-                "Installation instructions:\n\
-                 • Linux (Debian/Ubuntu): sudo apt-get install libchromaprint-tools\n\
-                 • Linux (Fedora/RHEL):   sudo dnf install chromaprint-tools\n\
-                 • macOS:                 brew install chromaprint\n\
-                 • Windows:               Download from https://acoustid.org/chromaprint\n\
-                 \nAfter installation, verify with: fpcalc -version"
-                    .to_string(),
-            ));
+    /// Return the fingerprint for a file, reusing a cached one when the file
+    /// is unchanged. The boolean is `true` on a cache hit.
+    ///
+    /// `length_secs` is passed to fpcalc as `-length`; it is clamped to
+    /// 1..=600 since AcoustID rejects overly long fingerprints.
+    pub(super) async fn fingerprint_with_cache(
+        file_path: &str,
+        length_secs: u32,
+        tools: &ToolsConfig,
+    ) -> Result<(FingerprintData, bool), IdentificationError> {
+        let length_secs = length_secs.clamp(1, MAX_FINGERPRINT_LENGTH_SECS);
+        let key = std::fs::canonicalize(file_path)
+            .ok()
+            .zip(std::fs::metadata(file_path).ok())
+            .map(|(path, meta)| (path, meta.len(), meta.modified().ok()));
+
+        if let Some((path, size, modified)) = &key
+            && let Some(data) = Self::cache_lookup(path, *size, *modified, length_secs)
+        {
+            debug!("Using cached fingerprint for {}", file_path);
+            return Ok((data, true));
         }
 
-        debug!("Running fpcalc on {}", file_path);
+        let timeout = Duration::from_secs(tools.fpcalc_timeout_secs);
+        let data =
+            Self::generate_fingerprint(tools.fpcalc_program(), file_path, length_secs, timeout)
+                .await?;
+        if let Some((path, size, modified)) = key {
+            Self::cache_store(path, size, modified, length_secs, data.clone());
+        }
+        Ok((data, false))
+    }
 
-        // Run fpcalc to generate fingerprint
-        let output = Command::new("fpcalc")
-            .arg("-json")
+    /// Look up a cached fingerprint, dropping it if the file has changed.
+    ///
+    /// An entry computed with a different `-length` is a miss but is kept
+    /// until replaced.
+    fn cache_lookup(
+        path: &Path,
+        size: u64,
+        modified: Option<SystemTime>,
+        length_secs: u32,
+    ) -> Option<FingerprintData> {
+        let mut cache = FINGERPRINT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = cache.get_mut(path)?;
+
+        if entry.size != size || entry.modified != modified || modified.is_none() {
+            cache.remove(path);
+            return None;
+        }
+
+        if entry.length_secs != length_secs {
+            return None;
+        }
+
+        entry.last_used = Instant::now();
+        Some(entry.data.clone())
+    }
+
+    /// Store a fingerprint, evicting the least recently used entry when full.
+    fn cache_store(
+        path: PathBuf,
+        size: u64,
+        modified: Option<SystemTime>,
+        length_secs: u32,
+        data: FingerprintData,
+    ) {
+        let mut cache = FINGERPRINT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+
+        if cache.len() >= FINGERPRINT_CACHE_CAPACITY
+            && !cache.contains_key(&path)
+            && let Some(oldest) = cache
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+        {
+            cache.remove(&oldest);
+        }
+
+        cache.insert(
+            path,
+            CachedFingerprint {
+                size,
+                modified,
+                length_secs,
+                data,
+                last_used: Instant::now(),
+            },
+        );
+    }
+
+    /// Generate audio fingerprint using fpcalc command-line tool.
+    ///
+    /// fpcalc is killed if it does not finish within `timeout` (e.g. when a
+    /// corrupt file makes it hang).
+    #[instrument(skip_all, fields(file = %file_path))]
+    async fn generate_fingerprint(
+        fpcalc: &Path,
+        file_path: &str,
+        length_secs: u32,
+        timeout: Duration,
+    ) -> Result<FingerprintData, IdentificationError> {
+        debug!("Running {} on {}", fpcalc.display(), file_path);
+
+        // Run fpcalc to generate fingerprint; the child is killed when the
+        // timed-out future is dropped.
+        let run = Command::new(fpcalc)
+            .args(Self::fpcalc_args(length_secs))
             .arg(file_path)
-            .output()
+            .kill_on_drop(true)
+            .output();
+
+        let output = tokio::time::timeout(timeout, run)
+            .await
+            .map_err(|_| {
+                IdentificationError::FingerprintFailed(format!(
+                    "fpcalc timed out after {}s (the file may be corrupt)",
+                    timeout.as_secs()
+                ))
+            })?
             .map_err(|e| {
                 IdentificationError::FingerprintFailed(format!("Failed to run fpcalc: {}", e))
             })?;
@@ -475,25 +1003,72 @@ impl MbIdentifyRecordTool {
         })
     }
 
-    /// Check if fpcalc is installed on the system.
-    fn is_fpcalc_installed() -> bool {
-        Command::new("fpcalc").arg("-version").output().is_ok()
+    /// Arguments passed to fpcalc before the file path.
+    fn fpcalc_args(length_secs: u32) -> [String; 3] {
+        [
+            "-json".to_string(),
+            "-length".to_string(),
+            length_secs.to_string(),
+        ]
     }
 
-    /// Query the AcoustID API with the fingerprint.
-    #[instrument(skip(fingerprint_data), fields(duration = fingerprint_data.duration, metadata_level = ?metadata_level))]
-    fn query_acoustid(
-        api_key: &str,
-        fingerprint_data: &FingerprintData,
-        metadata_level: MetadataLevel,
-    ) -> Result<AcoustIDResponse, IdentificationError> {
-        let client = reqwest::blocking::Client::builder()
-            .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+    /// Fail with installation instructions if fpcalc is missing.
+    pub(super) async fn ensure_fpcalc_installed(
+        tools: &ToolsConfig,
+    ) -> Result<(), IdentificationError> {
+        let fpcalc = tools.fpcalc_program();
+        if Self::is_fpcalc_installed(fpcalc).await {
+            return Ok(());
+        }
+
+        if tools.fpcalc_path.is_some() {
+            return Err(IdentificationError::FpcalcNotFound(format!(
+                "The configured fpcalc binary could not be run: {}\n\
+                 Check the MCP_FPCALC_PATH environment variable, or unset it to look up fpcalc on PATH.",
+                fpcalc.display()
+            )));
+        }
+
+        Err(IdentificationError::FpcalcNotFound(
+            "Installation instructions:\n\
+             • Linux (Debian/Ubuntu): sudo apt-get install libchromaprint-tools\n\
+             • Linux (Fedora/RHEL):   sudo dnf install chromaprint-tools\n\
+             • macOS:                 brew install chromaprint\n\
+             • Windows:               Download from https://acoustid.org/chromaprint\n\
+             \nAfter installation, verify with: fpcalc -version\n\
+             If fpcalc is installed outside PATH (e.g. NixOS, containers), set MCP_FPCALC_PATH to its location."
+                .to_string(),
+        ))
+    }
+
+    /// Check if the fpcalc binary can be run.
+    async fn is_fpcalc_installed(fpcalc: &Path) -> bool {
+        Command::new(fpcalc)
+            .arg("-version")
+            .kill_on_drop(true)
+            .output()
+            .await
+            .is_ok()
+    }
+
+    /// Build the HTTP client used for AcoustID lookups.
+    pub(super) fn build_http_client() -> Result<reqwest::Client, IdentificationError> {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
             .build()
             .map_err(|e| {
                 IdentificationError::ApiError(format!("Failed to create HTTP client: {}", e))
-            })?;
+            })
+    }
 
+    /// Query the AcoustID API with the fingerprint.
+    #[instrument(skip(client, fingerprint_data), fields(duration = fingerprint_data.duration, meta = %meta))]
+    async fn query_acoustid(
+        client: &reqwest::Client,
+        api_key: &str,
+        fingerprint_data: &FingerprintData,
+        meta: &str,
+    ) -> Result<AcoustIDResponse, IdentificationError> {
         let mut last_error = String::new();
 
         for attempt in 0..MAX_RETRIES {
@@ -505,10 +1080,10 @@ impl MbIdentifyRecordTool {
                     MAX_RETRIES,
                     delay
                 );
-                std::thread::sleep(std::time::Duration::from_millis(delay));
+                tokio::time::sleep(Duration::from_millis(delay)).await;
             }
 
-            match Self::try_api_request(&client, api_key, fingerprint_data, metadata_level) {
+            match Self::try_api_request(client, api_key, fingerprint_data, meta).await {
                 Ok(response) => return Ok(response),
                 Err(e) => {
                     // Don't retry on API key errors - fail fast
@@ -534,11 +1109,11 @@ impl MbIdentifyRecordTool {
     }
 
     /// Attempt a single API request.
-    fn try_api_request(
-        client: &reqwest::blocking::Client,
+    async fn try_api_request(
+        client: &reqwest::Client,
         api_key: &str,
         fingerprint_data: &FingerprintData,
-        metadata_level: MetadataLevel,
+        meta: &str,
     ) -> Result<AcoustIDResponse, IdentificationError> {
         // Pre-format duration string to avoid allocation in form builder
         let duration_str = fingerprint_data.duration.to_string();
@@ -549,9 +1124,10 @@ impl MbIdentifyRecordTool {
                 ("client", api_key),
                 ("duration", duration_str.as_str()),
                 ("fingerprint", fingerprint_data.fingerprint.as_str()),
-                ("meta", metadata_level.as_api_param()),
+                ("meta", meta),
             ])
             .send()
+            .await
             .map_err(|e| {
                 if e.is_timeout() {
                     IdentificationError::ApiError("Request timed out".to_string())
@@ -593,6 +1169,7 @@ impl MbIdentifyRecordTool {
         // Parse response directly from bytes for better performance
         let response_bytes = response
             .bytes()
+            .await
             .map_err(|e| IdentificationError::InvalidResponse(e.to_string()))?;
 
         debug!("API response received: {} bytes", response_bytes.len());
@@ -615,23 +1192,54 @@ impl MbIdentifyRecordTool {
 
 
     /// Build both structured results and text summary.
+    ///
+    /// Matches below `min_confidence`, or whose recordings all differ from the
+    /// fingerprinted duration by more than `max_duration_diff_secs`, are dropped
+    /// and counted in `filtered_count`. Recordings without a known duration are kept.
     fn build_results(
         response: &AcoustIDResponse,
         file_path: &str,
-        limit: usize,
-        metadata_level: &MetadataLevel,
+        options: &MatchOptions,
+        file_duration: u32,
+        fingerprint_cached: bool,
     ) -> Result<(String, IdentificationResult), IdentificationError> {
         if response.results.is_empty() {
             return Err(IdentificationError::NoMatches);
         }
 
+        let limit = options.limit.clamp(1, MAX_RESULT_LIMIT);
+        let metadata_level = &options.extraction_level();
+
         // Build structured data
         let mut matches = Vec::new();
+        let mut filtered_count = 0;
+
+        for result in &response.results {
+            if options.min_confidence.is_some_and(|min| result.score < min) {
+                filtered_count += 1;
+                continue;
+            }
+
+            let candidates: Vec<&AcoustIDRecording> = result
+                .recordings
+                .iter()
+                .filter(|r| {
+                    Self::duration_within(r.duration, file_duration, options.max_duration_diff_secs)
+                })
+                .collect();
+
+            if candidates.is_empty() && !result.recordings.is_empty() {
+                filtered_count += 1;
+                continue;
+            }
+
+            if matches.len() >= limit {
+                continue;
+            }
 
-        for (i, result) in response.results.iter().take(limit).enumerate() {
             let mut recordings = Vec::new();
 
-            for recording in &result.recordings {
+            for recording in candidates {
                 // Extract title and artists based on metadata level
                 let (title_opt, artists_opt) = match metadata_level {
                     MetadataLevel::Minimal => (None, None),
@@ -688,17 +1296,45 @@ impl MbIdentifyRecordTool {
                     None
                 };
 
+                // Extract releases with track positions for Full metadata level
+                let releases = if matches!(metadata_level, MetadataLevel::Full) {
+                    let releases: Vec<ReleaseMatch> = recording
+                        .releasegroups
+                        .iter()
+                        .flat_map(|rg| {
+                            rg.releases
+                                .iter()
+                                .map(move |release| Self::release_match(release, Some(rg)))
+                        })
+                        .chain(
+                            recording
+                                .releases
+                                .iter()
+                                .map(|release| Self::release_match(release, None)),
+                        )
+                        .collect();
+
+                    if releases.is_empty() {
+                        None
+                    } else {
+                        Some(releases)
+                    }
+                } else {
+                    None
+                };
+
                 recordings.push(RecordingMatch {
                     id: recording.id.clone(),
                     title: title_opt,
                     duration: recording.duration.map(|d| d as u32),
                     artists: artists_opt,
                     release_groups,
+                    releases,
                 });
             }
 
             matches.push(FingerprintMatch {
-                rank: i + 1,
+                rank: matches.len() + 1,
                 confidence: result.score,
                 acoustid: result.id.clone(),
                 recordings,
@@ -707,17 +1343,82 @@ impl MbIdentifyRecordTool {
 
         let structured_data = IdentificationResult {
             file: file_path.to_string(),
-            metadata_level: format!("{:?}", metadata_level).to_lowercase(),
+            metadata_level: options.metadata_level_label(),
+            meta_fields: Self::meta_field_names(options),
             matches,
             status: "success".to_string(),
+            fingerprint_cached,
+            filtered_count,
+            match_source: MatchSource::Fingerprint,
+            audio_duration_secs: file_duration,
+            tag_duration_secs: None,
+            duration_mismatch: false,
         };
 
         // Build text summary
-        let summary = Self::build_text_summary(&structured_data, metadata_level);
+        let mut summary = Self::build_text_summary(&structured_data, metadata_level);
+        if filtered_count > 0 {
+            summary.push_str(&format!(
+                " [{} match(es) filtered out by confidence/duration]",
+                filtered_count
+            ));
+        }
 
         Ok((summary, structured_data))
     }
 
+    /// Meta fields requested from AcoustID, as reported in the structured result.
+    fn meta_field_names(options: &MatchOptions) -> Vec<String> {
+        options
+            .requested_meta_fields()
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    /// Map an AcoustID release to a structured release match.
+    ///
+    /// AcoustID only returns the medium and track that contain the recording,
+    /// so the first medium/track are the relevant ones. With compressed
+    /// responses the release title may be omitted when it equals the group's.
+    fn release_match(
+        release: &AcoustIDRelease,
+        release_group: Option<&AcoustIDReleaseGroup>,
+    ) -> ReleaseMatch {
+        let medium = release.mediums.first();
+        let track = medium.and_then(|m| m.tracks.first());
+
+        ReleaseMatch {
+            id: release.id.clone(),
+            title: release
+                .title
+                .clone()
+                .or_else(|| release_group.and_then(|rg| rg.title.clone())),
+            release_group_id: release_group.and_then(|rg| rg.id.clone()),
+            year: release.date.as_ref().and_then(|d| d.year),
+            country: release.country.clone(),
+            format: medium.and_then(|m| m.format.clone()),
+            medium_position: medium.and_then(|m| m.position),
+            medium_count: release.medium_count,
+            track_position: track.and_then(|t| t.position),
+            track_count: medium.and_then(|m| m.track_count).or(release.track_count),
+        }
+    }
+
+    /// Whether a recording's duration is within `max_diff` seconds of the file's.
+    fn duration_within(
+        recording_duration: Option<f64>,
+        file_duration: u32,
+        max_diff: Option<u32>,
+    ) -> bool {
+        match (recording_duration, max_diff) {
+            (Some(duration), Some(max_diff)) => {
+                (duration - f64::from(file_duration)).abs() <= f64::from(max_diff)
+            }
+            _ => true,
+        }
+    }
+
     /// Build a concise text summary from structured data.
     fn build_text_summary(
         data: &IdentificationResult,
@@ -795,11 +1496,19 @@ impl MbIdentifyRecordTool {
             params.file_path
         );
 
-        let handle = std::thread::spawn(move || Self::execute(&params, &config));
+        // Run on a dedicated thread with its own runtime: this handler is called
+        // synchronously from within the HTTP server's async context.
+        let handle = std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map(|runtime| runtime.block_on(Self::execute(&params, &config)))
+        });
 
         let result = handle
             .join()
-            .map_err(|_| "Identification thread panicked".to_string())?;
+            .map_err(|_| "Identification thread panicked".to_string())?
+            .map_err(|e| format!("Failed to start identification runtime: {}", e))?;
 
         let mut response = serde_json::json!({
             "content": result.content,
@@ -836,6 +1545,7 @@ impl MbIdentifyRecordTool {
     where
         S: Send + Sync + 'static,
     {
+        let timeout = config.tools.timeout_for(Self::NAME);
         ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             let config = config.clone();
@@ -844,13 +1554,10 @@ impl MbIdentifyRecordTool {
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
-                let result = tokio::task::spawn_blocking(move || Self::execute(&params, &config))
-                    .await
-                    .map_err(|e| {
-                        McpError::internal_error(format!("Task execution failed: {}", e), None)
-                    })?;
-
-                Ok(result)
+                with_timeout(Self::NAME, timeout, async {
+                    Ok(Self::execute(&params, &config).await)
+                })
+                .await
             }
             .boxed()
         })
@@ -884,22 +1591,27 @@ mod tests {
         assert_eq!(params.limit, 3);
     }
 
-    #[test]
-    fn test_mb_identify_missing_file() {
+    #[tokio::test]
+    async fn test_mb_identify_missing_file() {
         let config = Config::default();
         let params = MbIdentifyRecordParams {
             file_path: "/nonexistent/file.mp3".to_string(),
             limit: 3,
             metadata_level: MetadataLevel::Basic,
+            meta_fields: Vec::new(),
+            min_confidence: None,
+            max_duration_diff_secs: None,
+            fallback_to_metadata: false,
+            max_fingerprint_length_secs: DEFAULT_FINGERPRINT_LENGTH_SECS,
         };
 
-        let result = MbIdentifyRecordTool::execute(&params, &config);
+        let result = MbIdentifyRecordTool::execute(&params, &config).await;
         assert!(result.is_error.unwrap_or(false));
     }
 
-    #[test]
+    #[tokio::test]
     #[ignore = "requires test audio file"]
-    fn test_mb_identify_integration() {
+    async fn test_mb_identify_integration() {
         let config = Config::default();
         let test_file = "test_audio.mp3";
 
@@ -912,12 +1624,379 @@ mod tests {
             file_path: test_file.to_string(),
             limit: 3,
             metadata_level: MetadataLevel::Basic,
+            meta_fields: Vec::new(),
+            min_confidence: None,
+            max_duration_diff_secs: None,
+            fallback_to_metadata: false,
+            max_fingerprint_length_secs: DEFAULT_FINGERPRINT_LENGTH_SECS,
         };
 
-        let result = MbIdentifyRecordTool::execute(&params, &config);
+        let result = MbIdentifyRecordTool::execute(&params, &config).await;
         assert!(!result.content.is_empty());
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_fingerprint_timeout_kills_fpcalc() {
+        // A FIFO with no writer blocks fpcalc on open, simulating a hang.
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let fifo = temp_dir.path().join("hang.mp3");
+        let status = std::process::Command::new("mkfifo").arg(&fifo).status();
+        if !matches!(status, Ok(s) if s.success())
+            || !MbIdentifyRecordTool::is_fpcalc_installed(Path::new("fpcalc")).await
+        {
+            println!("Skipping timeout test: mkfifo or fpcalc unavailable");
+            return;
+        }
+
+        let result = MbIdentifyRecordTool::generate_fingerprint(
+            Path::new("fpcalc"),
+            fifo.to_str().unwrap(),
+            DEFAULT_FINGERPRINT_LENGTH_SECS,
+            Duration::from_millis(200),
+        )
+        .await;
+
+        match result {
+            Err(IdentificationError::FingerprintFailed(msg)) => assert!(msg.contains("timed out")),
+            other => panic!("Expected timeout error, got {:?}", other),
+        }
+    }
+
+    fn filter_fixture() -> AcoustIDResponse {
+        serde_json::from_value(serde_json::json!({
+            "status": "ok",
+            "results": [
+                {"id": "a", "score": 0.95, "recordings": [
+                    {"id": "rec-good", "title": "Song", "duration": 241.0},
+                    {"id": "rec-edit", "title": "Song (Radio Edit)", "duration": 199.0}
+                ]},
+                {"id": "b", "score": 0.30, "recordings": [{"id": "rec-junk", "duration": 240.0}]},
+                {"id": "c", "score": 0.90, "recordings": [{"id": "rec-mix", "duration": 3600.0}]}
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn match_options(min_confidence: Option<f64>, max_diff: Option<u32>) -> MatchOptions {
+        MatchOptions {
+            limit: 10,
+            metadata_level: MetadataLevel::Basic,
+            meta_fields: None,
+            min_confidence,
+            max_duration_diff_secs: max_diff,
+            fallback_to_metadata: false,
+            fingerprint_length_secs: DEFAULT_FINGERPRINT_LENGTH_SECS,
+        }
+    }
+
+    #[test]
+    fn test_build_results_no_filters_keeps_everything() {
+        let (_, data) = MbIdentifyRecordTool::build_results(
+            &filter_fixture(),
+            "song.mp3",
+            &match_options(None, None),
+            240,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(data.matches.len(), 3);
+        assert_eq!(data.filtered_count, 0);
+    }
+
+    #[test]
+    fn test_build_results_filters_confidence_and_duration() {
+        let (summary, data) = MbIdentifyRecordTool::build_results(
+            &filter_fixture(),
+            "song.mp3",
+            &match_options(Some(0.5), Some(5)),
+            240,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(data.filtered_count, 2);
+        assert_eq!(data.matches.len(), 1);
+        assert_eq!(data.matches[0].rank, 1);
+        assert_eq!(data.matches[0].acoustid, "a");
+        // The radio edit is 41s shorter and is dropped from the kept match
+        let ids: Vec<_> = data.matches[0].recordings.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["rec-good"]);
+        assert!(summary.contains("2 match(es) filtered out"));
+    }
+
+    /// Trimmed AcoustID lookup response for `meta=recordings releasegroups releases tracks compress`.
+    const FULL_RESPONSE_FIXTURE: &str = r#"{
+        "status": "ok",
+        "results": [{
+            "id": "9ff43b6a-4f16-427c-93c2-92307ca505e0",
+            "score": 0.97,
+            "recordings": [{
+                "id": "b1a9c0e9-d987-4042-ae91-78d6a3267d69",
+                "title": "Paranoid Android",
+                "duration": 387,
+                "artists": [{"id": "a74b1b7f-71a5-4011-9441-d0b5e4122711", "name": "Radiohead"}],
+                "releasegroups": [{
+                    "id": "b1392450-e666-3926-a536-22c65f834433",
+                    "title": "OK Computer",
+                    "type": "Album",
+                    "releases": [
+                        {
+                            "id": "0b6b4ba0-d36f-47bd-b4ea-6a5b91842d29",
+                            "country": "GB",
+                            "date": {"year": 1997, "month": 6, "day": 16},
+                            "medium_count": 1,
+                            "track_count": 12,
+                            "mediums": [{
+                                "position": 1,
+                                "format": "CD",
+                                "track_count": 12,
+                                "tracks": [{"id": "t1", "position": 2, "title": "Paranoid Android"}]
+                            }]
+                        },
+                        {
+                            "id": "e7b2ae1b-7a4e-4b7b-8d4c-7f4b8b0b6a3d",
+                            "title": "OK Computer OKNOTOK 1997 2017",
+                            "country": "XW",
+                            "date": {"year": 2017},
+                            "medium_count": 2,
+                            "mediums": [{
+                                "position": 1,
+                                "format": "Digital Media",
+                                "track_count": 12,
+                                "tracks": [{"position": 2}]
+                            }]
+                        }
+                    ]
+                }]
+            }]
+        }]
+    }"#;
+
+    #[test]
+    fn test_build_results_full_releases() {
+        let response: AcoustIDResponse = serde_json::from_str(FULL_RESPONSE_FIXTURE).unwrap();
+        let mut options = match_options(None, None);
+        options.metadata_level = MetadataLevel::Full;
+
+        let (_, data) =
+            MbIdentifyRecordTool::build_results(&response, "song.flac", &options, 387, false)
+                .unwrap();
+
+        let recording = &data.matches[0].recordings[0];
+        let releases = recording.releases.as_ref().unwrap();
+        assert_eq!(releases.len(), 2);
+
+        // Compressed response: title inherited from the release group
+        let original = &releases[0];
+        assert_eq!(original.title.as_deref(), Some("OK Computer"));
+        assert_eq!(
+            original.release_group_id.as_deref(),
+            Some("b1392450-e666-3926-a536-22c65f834433")
+        );
+        assert_eq!(original.year, Some(1997));
+        assert_eq!(original.country.as_deref(), Some("GB"));
+        assert_eq!(original.format.as_deref(), Some("CD"));
+        assert_eq!(original.medium_position, Some(1));
+        assert_eq!(original.medium_count, Some(1));
+        assert_eq!(original.track_position, Some(2));
+        assert_eq!(original.track_count, Some(12));
+
+        let reissue = &releases[1];
+        assert_eq!(reissue.title.as_deref(), Some("OK Computer OKNOTOK 1997 2017"));
+        assert_eq!(reissue.year, Some(2017));
+        assert_eq!(reissue.format.as_deref(), Some("Digital Media"));
+        assert_eq!(reissue.medium_count, Some(2));
+    }
+
+    #[test]
+    fn test_build_results_basic_omits_releases() {
+        let response: AcoustIDResponse = serde_json::from_str(FULL_RESPONSE_FIXTURE).unwrap();
+
+        let (_, data) = MbIdentifyRecordTool::build_results(
+            &response,
+            "song.flac",
+            &match_options(None, None),
+            387,
+            false,
+        )
+        .unwrap();
+
+        let json = serde_json::to_value(&data).unwrap();
+        assert!(json["matches"][0]["recordings"][0].get("releases").is_none());
+    }
+
+    #[test]
+    fn test_meta_fields_override_metadata_level() {
+        let json = r#"{"file_path": "test.mp3", "meta_fields": ["Recordings", "releases", "tracks", "recordings"]}"#;
+        let params: MbIdentifyRecordParams = serde_json::from_str(json).unwrap();
+        let options = params.match_options().unwrap();
+        assert_eq!(
+            options.requested_meta_fields(),
+            vec!["recordings", "releases", "tracks"]
+        );
+        assert_eq!(options.metadata_level_label(), "custom");
+
+        let json = r#"{"file_path": "test.mp3", "metadata_level": "full"}"#;
+        let params: MbIdentifyRecordParams = serde_json::from_str(json).unwrap();
+        let options = params.match_options().unwrap();
+        assert_eq!(
+            options.requested_meta_fields(),
+            vec![
+                "recordings",
+                "releasegroups",
+                "releases",
+                "tracks",
+                "compress"
+            ]
+        );
+        assert_eq!(options.metadata_level_label(), "full");
+    }
+
+    #[test]
+    fn test_meta_fields_rejects_unknown_field() {
+        let json = r#"{"file_path": "test.mp3", "meta_fields": ["recordings", "lyrics"]}"#;
+        let params: MbIdentifyRecordParams = serde_json::from_str(json).unwrap();
+        match params.match_options() {
+            Err(IdentificationError::InvalidParameter(msg)) => assert!(msg.contains("'lyrics'")),
+            other => panic!("Expected invalid parameter error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_build_results_custom_meta_fields() {
+        let response: AcoustIDResponse = serde_json::from_str(FULL_RESPONSE_FIXTURE).unwrap();
+        let mut options = match_options(None, None);
+        options.meta_fields = Some(vec!["recordings", "releasegroups", "releases", "compress"]);
+
+        let (_, data) =
+            MbIdentifyRecordTool::build_results(&response, "song.flac", &options, 387, false)
+                .unwrap();
+
+        assert_eq!(data.metadata_level, "custom");
+        assert_eq!(
+            data.meta_fields,
+            vec!["recordings", "releasegroups", "releases", "compress"]
+        );
+        assert!(data.matches[0].recordings[0].releases.is_some());
+    }
+
+    #[test]
+    fn test_apply_tag_duration_flags_mismatch() {
+        let (summary, data) = MbIdentifyRecordTool::build_results(
+            &filter_fixture(),
+            "song.mp3",
+            &match_options(None, None),
+            422,
+            false,
+        )
+        .unwrap();
+        assert_eq!(data.audio_duration_secs, 422);
+
+        let (mut close_summary, mut close) = (summary.clone(), data.clone());
+        MbIdentifyRecordTool::apply_tag_duration(&mut close_summary, &mut close, Some(425));
+        assert_eq!(close.tag_duration_secs, Some(425));
+        assert!(!close.duration_mismatch);
+        assert_eq!(close_summary, summary);
+
+        let (mut far_summary, mut far) = (summary.clone(), data.clone());
+        MbIdentifyRecordTool::apply_tag_duration(&mut far_summary, &mut far, Some(225));
+        assert!(far.duration_mismatch);
+        assert!(far_summary.ends_with("[duration mismatch: audio is 7:02 but tags report 3:45]"));
+
+        let (mut unknown_summary, mut unknown) = (summary.clone(), data);
+        MbIdentifyRecordTool::apply_tag_duration(&mut unknown_summary, &mut unknown, None);
+        assert!(!unknown.duration_mismatch);
+    }
+
+    #[test]
+    fn test_build_metadata_results() {
+        let recordings: Vec<Recording> = serde_json::from_value(serde_json::json!([
+            {"id": "rec-1", "title": "Song", "length": 241000,
+             "artist-credit": [{"name": "Band", "joinphrase": "", "artist": {"id": "a1", "name": "Band", "sort-name": "Band"}}]},
+            {"id": "rec-2", "title": "Song (Live)", "length": 400000}
+        ]))
+        .unwrap();
+        let candidates = TagSearchCandidates {
+            title: "Song".to_string(),
+            artist: "Band".to_string(),
+            recordings,
+        };
+
+        let (summary, data) = MbIdentifyRecordTool::build_metadata_results(
+            candidates,
+            "song.mp3",
+            &match_options(None, Some(10)),
+            240,
+            false,
+        );
+
+        assert_eq!(data.match_source, MatchSource::Metadata);
+        assert_eq!(data.matches.len(), 1);
+        assert_eq!(data.filtered_count, 1);
+        assert_eq!(data.matches[0].recordings[0].id, "rec-1");
+        assert_eq!(
+            data.matches[0].recordings[0].artists,
+            Some(vec!["Band".to_string()])
+        );
+        assert!(summary.contains("not audio analysis"));
+    }
+
+    #[test]
+    fn test_fingerprint_cache_invalidated_on_change() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("track.flac");
+        let modified = Some(SystemTime::now());
+        let data = FingerprintData {
+            duration: 180,
+            fingerprint: "AQAAfake".to_string(),
+        };
+
+        MbIdentifyRecordTool::cache_store(path.clone(), 1024, modified, 120, data);
+
+        let hit = MbIdentifyRecordTool::cache_lookup(&path, 1024, modified, 120);
+        assert_eq!(hit.map(|d| d.fingerprint), Some("AQAAfake".to_string()));
+
+        // A different fpcalc -length is a miss
+        assert!(MbIdentifyRecordTool::cache_lookup(&path, 1024, modified, 300).is_none());
+
+        // A different size means the file changed: entry is dropped
+        assert!(MbIdentifyRecordTool::cache_lookup(&path, 2048, modified, 120).is_none());
+        assert!(MbIdentifyRecordTool::cache_lookup(&path, 1024, modified, 120).is_none());
+    }
+
+    #[test]
+    fn test_fpcalc_args_include_length() {
+        assert_eq!(
+            MbIdentifyRecordTool::fpcalc_args(300),
+            ["-json", "-length", "300"]
+        );
+    }
+
+    #[test]
+    fn test_default_fingerprint_length() {
+        let json = r#"{"file_path": "test.mp3"}"#;
+        let params: MbIdentifyRecordParams = serde_json::from_str(json).unwrap();
+        assert_eq!(params.max_fingerprint_length_secs, 120);
+    }
+
+    #[tokio::test]
+    async fn test_configured_fpcalc_path_missing() {
+        let tools = ToolsConfig {
+            fpcalc_path: Some(PathBuf::from("/nonexistent/bin/fpcalc")),
+            ..ToolsConfig::default()
+        };
+
+        match MbIdentifyRecordTool::ensure_fpcalc_installed(&tools).await {
+            Err(IdentificationError::FpcalcNotFound(msg)) => {
+                assert!(msg.contains("MCP_FPCALC_PATH"));
+                assert!(msg.contains("/nonexistent/bin/fpcalc"));
+            }
+            other => panic!("Expected FpcalcNotFound, got {:?}", other),
+        }
+    }
+
     #[cfg(feature = "http")]
     #[test]
     fn test_mb_identify_http_handler_invalid_params() {
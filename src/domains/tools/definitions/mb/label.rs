@@ -1,6 +1,7 @@
 //! MusicBrainz Label search tool.
 //!
-//! This tool provides functionality to search for labels (record labels/publishers).
+//! This tool provides functionality to search for labels (record labels/publishers)
+//! and to find releases by the catalog number printed on them.
 //! Labels represent the companies or organizations that publish music releases.
 
 use futures::FutureExt;
@@ -8,6 +9,7 @@ use futures::future::BoxFuture;
 use musicbrainz_rs::{
     Search,
     entity::label::{Label, LabelSearchQuery},
+    entity::release::{Release, ReleaseSearchQuery},
 };
 use rmcp::{
     ErrorData as McpError,
@@ -16,19 +18,42 @@ use rmcp::{
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
 use super::common::{
-    default_limit, error_result, structured_result, validate_limit,
+    best_match_suffix, default_limit, error_result, extract_year, get_artist_name,
+    search_with_scores, structured_result, validate_limit,
 };
+use crate::core::config::Config;
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::timeout::with_timeout;
+use std::sync::Arc;
+
+fn default_search_type() -> String {
+    "label".to_string()
+}
 
 /// Parameters for label search operations.
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct MbLabelParams {
-    /// The search query string (label name).
-    #[schemars(description = "Search query (label name)")]
+    /// The type of search to perform.
+    /// - "label": Search for labels by name
+    /// - "catalog_number": Search for releases by catalog number
+    #[schemars(description = "Search type: 'label' (default) or 'catalog_number'")]
+    #[serde(default = "default_search_type")]
+    pub search_type: String,
+
+    /// The search query string (label name or catalog number).
+    #[schemars(
+        description = "Search query: label name for 'label', catalog number (e.g., 'XLLP 781') for 'catalog_number'"
+    )]
     pub query: String,
 
+    /// Label name to narrow a catalog number search.
+    #[schemars(description = "Optional label name to narrow a 'catalog_number' search")]
+    #[serde(default)]
+    pub label: Option<String>,
+
     /// Maximum number of results to return (default: 10, max: 100).
     #[schemars(description = "Maximum number of results (default: 10, max: 100)")]
     #[serde(default = "default_limit")]
@@ -51,6 +76,39 @@ pub struct LabelInfo {
     pub country: Option<String>,
     pub disambiguation: Option<String>,
     pub label_code: Option<i32>,
+    /// MusicBrainz search relevance (0-100); null when fetched by MBID
+    pub score: Option<u8>,
+}
+
+/// Structured output for catalog number search results.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CatalogNumberResult {
+    pub releases: Vec<CatalogReleaseInfo>,
+    pub total_count: usize,
+    /// Catalog number as given
+    pub query: String,
+    /// Form of the catalog number that produced the results
+    pub matched_catalog_number: String,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CatalogReleaseInfo {
+    pub title: String,
+    pub mbid: String,
+    pub artist: String,
+    pub year: Option<String>,
+    pub country: Option<String>,
+    pub label_credits: Vec<LabelCredit>,
+    /// MusicBrainz search relevance (0-100)
+    pub score: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LabelCredit {
+    pub label: Option<String>,
+    pub label_mbid: Option<String>,
+    pub catalog_number: Option<String>,
 }
 
 /// MusicBrainz Label Search Tool implementation.
@@ -62,7 +120,7 @@ impl MbLabelTool {
     pub const NAME: &'static str = "mb_label_search";
 
     /// Tool description shown to clients.
-    pub const DESCRIPTION: &'static str = "Search for labels (record labels/publishers) in MusicBrainz. Labels represent the companies or organizations that publish music releases. Returns structured data with MBIDs, label types, countries, label codes, and disambiguation info.";
+    pub const DESCRIPTION: &'static str = "Search for labels (record labels/publishers) in MusicBrainz, or find releases by the catalog number printed on them (search_type 'catalog_number', e.g. 'XLLP 781', optionally narrowed by label name). Labels represent the companies or organizations that publish music releases. Returns structured data with MBIDs, label types, countries, label codes, and disambiguation info, or matching releases with their label credits.";
 
     pub fn new() -> Self {
         Self
@@ -73,12 +131,24 @@ impl MbLabelTool {
         let query = params.query.clone();
         let limit = validate_limit(params.limit);
 
-        Self::search_labels(&query, limit)
+        match params.search_type.as_str() {
+            "label" => Self::search_labels(&query, limit),
+            "catalog_number" => Self::search_catalog_number(&query, params.label.as_deref(), limit),
+            _ => error_result(&format!(
+                "Unknown search type: {}. Use 'label' or 'catalog_number'",
+                params.search_type
+            )),
+        }
     }
 
     /// HTTP handler for this tool (for HTTP transport).
     #[cfg(feature = "http")]
     pub fn http_handler(arguments: serde_json::Value) -> Result<serde_json::Value, String> {
+        let search_type = arguments
+            .get("search_type")
+            .and_then(|v| v.as_str())
+            .map_or_else(default_search_type, String::from);
+
         let query = arguments
             .get("query")
             .and_then(|v| v.as_str())
@@ -90,8 +160,15 @@ impl MbLabelTool {
             .and_then(|v| v.as_u64())
             .unwrap_or(10) as usize;
 
+        let label = arguments
+            .get("label")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
         let params = MbLabelParams {
+            search_type,
             query,
+            label,
             limit,
         };
 
@@ -134,27 +211,25 @@ impl MbLabelTool {
     }
 
     /// Create a ToolRoute for STDIO/TCP transport.
-    pub fn create_route<S>() -> ToolRoute<S>
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
     where
         S: Send + Sync + 'static,
     {
-        ToolRoute::new_dyn(Self::to_tool(), |ctx: ToolCallContext<'_, S>| {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             async move {
                 let params: MbLabelParams =
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
-                // Use std::thread::spawn to avoid nested runtime panic.
-                // musicbrainz_rs uses reqwest::blocking which creates its own runtime,
-                // so we need a completely separate OS thread.
-                let handle = std::thread::spawn(move || Self::execute(&params));
-
-                let result = handle
-                    .join()
-                    .map_err(|_| McpError::internal_error("Thread panicked".to_string(), None))?;
-
-                Ok(result)
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_on_thread(move || Self::execute(&params)),
+                )
+                .await
             }
             .boxed()
         })
@@ -164,10 +239,7 @@ impl MbLabelTool {
     #[deprecated(note = "Use http_handler() instead")]
     pub fn handle_http(params: MbLabelParams) -> BoxFuture<'static, CallToolResult> {
         Box::pin(async move {
-            let query = params.query.clone();
-            let limit = validate_limit(params.limit);
-
-            let result = std::thread::spawn(move || Self::search_labels(&query, limit))
+            let result = std::thread::spawn(move || Self::execute(&params))
                 .join()
                 .unwrap_or_else(|e| error_result(&format!("Thread panicked: {:?}", e)));
 
@@ -178,10 +250,7 @@ impl MbLabelTool {
     /// Main handler for STDIO/TCP transport.
     pub fn handle_stdio(params: MbLabelParams) -> BoxFuture<'static, CallToolResult> {
         Box::pin(async move {
-            let query = params.query.clone();
-            let limit = validate_limit(params.limit);
-
-            let result = tokio::task::spawn_blocking(move || Self::search_labels(&query, limit))
+            let result = tokio::task::spawn_blocking(move || Self::execute(&params))
                 .await
                 .unwrap_or_else(|e| error_result(&format!("Task failed: {:?}", e)));
 
@@ -194,25 +263,27 @@ impl MbLabelTool {
         info!("Searching for labels matching: {}", query);
 
         let search_query = LabelSearchQuery::query_builder().label(query).build();
-        let search_result = Label::search(search_query).execute();
+        let search_result = search_with_scores(&Label::search(search_query));
 
         match search_result {
-            Ok(result) => {
-                let labels: Vec<_> = result.entities.into_iter().take(limit).collect();
+            Ok(results) => {
+                let labels: Vec<_> = results.into_iter().take(limit).collect();
                 if labels.is_empty() {
                     return error_result(&format!("No labels found for query: {}", query));
                 }
 
                 let count = labels.len();
+                let best_score = labels.first().and_then(|(_, score)| *score);
                 let label_infos: Vec<LabelInfo> = labels
                     .into_iter()
-                    .map(|l| LabelInfo {
+                    .map(|(l, score)| LabelInfo {
                         name: l.name,
                         mbid: l.id,
                         label_type: l.label_type.map(|t| format!("{:?}", t)),
                         country: l.country,
                         disambiguation: l.disambiguation.filter(|d| !d.is_empty()),
                         label_code: l.label_code.map(|c| c as i32),
+                        score,
                     })
                     .collect();
 
@@ -222,7 +293,12 @@ impl MbLabelTool {
                     query: query.to_string(),
                 };
 
-                let summary = format!("Found {} label(s) matching '{}'", count, query);
+                let summary = format!(
+                    "Found {} label(s) matching '{}'{}",
+                    count,
+                    query,
+                    best_match_suffix(best_score)
+                );
                 structured_result(summary, structured_data)
             }
             Err(e) => {
@@ -231,6 +307,113 @@ impl MbLabelTool {
             }
         }
     }
+
+    /// Search for releases by catalog number, optionally narrowed by label name.
+    ///
+    /// When the catalog number as given has no hits, the spacing and
+    /// separator variants from `catalog_number_variants` are tried in turn.
+    pub fn search_catalog_number(
+        catalog_number: &str,
+        label: Option<&str>,
+        limit: usize,
+    ) -> CallToolResult {
+        info!(
+            "Searching for releases with catalog number: {}",
+            catalog_number
+        );
+
+        let label = label.map(str::trim).filter(|l| !l.is_empty());
+        for variant in catalog_number_variants(catalog_number) {
+            let mut builder = ReleaseSearchQuery::query_builder();
+            builder.catalog_number(&variant);
+            if let Some(label) = label {
+                builder.and().label(label);
+            }
+
+            let results = match search_with_scores(&Release::search(builder.build())) {
+                Ok(results) => results,
+                Err(e) => {
+                    error!("Catalog number search failed: {:?}", e);
+                    return error_result(&format!("Catalog number search failed: {}", e));
+                }
+            };
+            if results.is_empty() {
+                debug!("No releases for catalog number variant: {}", variant);
+                continue;
+            }
+
+            let releases: Vec<_> = results.into_iter().take(limit).collect();
+            let count = releases.len();
+            let best_score = releases.first().and_then(|(_, score)| *score);
+            let release_infos: Vec<CatalogReleaseInfo> = releases
+                .into_iter()
+                .map(|(r, score)| CatalogReleaseInfo {
+                    label_credits: r
+                        .label_info
+                        .iter()
+                        .flatten()
+                        .map(|info| LabelCredit {
+                            label: info.label.as_ref().map(|l| l.name.clone()),
+                            label_mbid: info.label.as_ref().map(|l| l.id.clone()),
+                            catalog_number: info.catalog_number.clone(),
+                        })
+                        .collect(),
+                    title: r.title,
+                    mbid: r.id,
+                    artist: get_artist_name(&r.artist_credit),
+                    year: r.date.as_ref().and_then(|d| extract_year(&d.0)),
+                    country: r.country,
+                    score,
+                })
+                .collect();
+
+            let summary = format!(
+                "Found {} release(s) with catalog number '{}'{}",
+                count,
+                variant,
+                best_match_suffix(best_score)
+            );
+            let structured_data = CatalogNumberResult {
+                releases: release_infos,
+                total_count: count,
+                query: catalog_number.to_string(),
+                matched_catalog_number: variant,
+                label: label.map(String::from),
+            };
+            return structured_result(summary, structured_data);
+        }
+
+        error_result(&format!(
+            "No releases found with catalog number: {}",
+            catalog_number
+        ))
+    }
+}
+
+/// Catalog number forms to try, the trimmed input first.
+///
+/// Catalog numbers are printed with varying separators ("XLLP 781",
+/// "XLLP-781", "XLLP781"), so the spaced, dashed and compact forms follow.
+fn catalog_number_variants(catalog_number: &str) -> Vec<String> {
+    let parts: Vec<&str> = catalog_number
+        .split(|c: char| c.is_whitespace() || matches!(c, '-' | '.' | '_' | '/'))
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    let candidates = [
+        catalog_number.trim().to_string(),
+        parts.join(" "),
+        parts.join("-"),
+        parts.concat(),
+    ];
+
+    let mut variants: Vec<String> = Vec::new();
+    for candidate in candidates {
+        if !candidate.is_empty() && !variants.contains(&candidate) {
+            variants.push(candidate);
+        }
+    }
+    variants
 }
 
 impl Default for MbLabelTool {
@@ -249,6 +432,33 @@ mod tests {
         let json = r#"{"query": "Sony Music"}"#;
         let params: MbLabelParams = serde_json::from_str(json).unwrap();
         assert_eq!(params.limit, 10);
+        assert_eq!(params.search_type, "label");
+        assert_eq!(params.label, None);
+    }
+
+    #[test]
+    fn test_catalog_number_variants() {
+        assert_eq!(
+            catalog_number_variants("XLLP 781"),
+            ["XLLP 781", "XLLP-781", "XLLP781"]
+        );
+        assert_eq!(
+            catalog_number_variants(" XLLP-781 "),
+            ["XLLP-781", "XLLP 781", "XLLP781"]
+        );
+        assert_eq!(
+            catalog_number_variants("CDSTUMM 300"),
+            ["CDSTUMM 300", "CDSTUMM-300", "CDSTUMM300"]
+        );
+        assert_eq!(catalog_number_variants("7243"), ["7243"]);
+    }
+
+    #[test]
+    fn test_unknown_search_type() {
+        let params: MbLabelParams =
+            serde_json::from_str(r#"{"search_type": "barcode", "query": "123"}"#).unwrap();
+        let result = MbLabelTool::execute(&params);
+        assert!(result.is_error.unwrap_or(false));
     }
 
     // Integration tests (require network, run with: cargo test -- --ignored)
@@ -268,4 +478,15 @@ mod tests {
             );
         }
     }
+
+    #[ignore]
+    #[test]
+    fn test_search_catalog_number() {
+        std::thread::sleep(std::time::Duration::from_millis(1500));
+        let result = MbLabelTool::search_catalog_number("XLLP781", Some("XL Recordings"), 5);
+        assert!(
+            !result.is_error.unwrap_or(true),
+            "Expected success but got error"
+        );
+    }
 }
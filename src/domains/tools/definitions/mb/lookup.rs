@@ -0,0 +1,511 @@
+//! MusicBrainz MBID lookup tool.
+//!
+//! This tool resolves a bare MBID without knowing its entity type. It probes
+//! the entity endpoints in turn and returns the first hit, tagged with the
+//! entity type. Resolved types and "not found" probes are cached so repeated
+//! lookups don't walk every endpoint again.
+
+use futures::FutureExt;
+use musicbrainz_rs::{
+    Error as MbError, Fetch,
+    entity::{
+        artist::Artist, label::Label, recording::Recording, release::Release,
+        release_group::ReleaseGroup, work::Work,
+    },
+};
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Tool},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info};
+
+use super::artist::ArtistSearchInfo;
+use super::common::{
+    ExecuteWithRetry, MbRequestError, error_result, extract_mbid, extract_year, format_duration,
+    get_artist_name, secondary_type_names, structured_result,
+};
+use super::label::LabelInfo;
+use super::recording::RecordingSearchInfo;
+use super::release::{ReleaseGroupSearchInfo, ReleaseSearchInfo};
+use super::work::WorkInfo;
+use crate::core::config::Config;
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::timeout::with_timeout;
+use std::sync::Arc;
+
+/// Maximum number of MBIDs kept in each cache before it is cleared.
+const LOOKUP_CACHE_CAPACITY: usize = 1024;
+/// How long a "not found" probe is trusted before the endpoint is tried again.
+const NEGATIVE_PROBE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Parameters for MBID lookup.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MbLookupParams {
+    /// The MusicBrainz ID to resolve.
+    #[schemars(
+        description = "MusicBrainz ID of any entity (artist, release, release group, recording, work or label)"
+    )]
+    pub mbid: String,
+}
+
+/// MusicBrainz entity types probed by the lookup, in probe order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    Recording,
+    Release,
+    Artist,
+    ReleaseGroup,
+    Work,
+    Label,
+}
+
+impl EntityType {
+    /// Probe order: recordings and releases are the MBIDs agents most often
+    /// hold (from fingerprinting or tags).
+    const ALL: [EntityType; 6] = [
+        EntityType::Recording,
+        EntityType::Release,
+        EntityType::Artist,
+        EntityType::ReleaseGroup,
+        EntityType::Work,
+        EntityType::Label,
+    ];
+}
+
+/// Details of the resolved entity, tagged with its type.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(tag = "entity_type", content = "details", rename_all = "snake_case")]
+pub enum LookupEntity {
+    Recording(RecordingSearchInfo),
+    Release(ReleaseSearchInfo),
+    Artist(ArtistSearchInfo),
+    ReleaseGroup(ReleaseGroupSearchInfo),
+    Work(WorkInfo),
+    Label(LabelInfo),
+}
+
+impl LookupEntity {
+    fn name(&self) -> &str {
+        match self {
+            LookupEntity::Recording(r) => &r.title,
+            LookupEntity::Release(r) => &r.title,
+            LookupEntity::Artist(a) => &a.name,
+            LookupEntity::ReleaseGroup(rg) => &rg.title,
+            LookupEntity::Work(w) => &w.title,
+            LookupEntity::Label(l) => &l.name,
+        }
+    }
+
+    fn entity_type(&self) -> EntityType {
+        match self {
+            LookupEntity::Recording(_) => EntityType::Recording,
+            LookupEntity::Release(_) => EntityType::Release,
+            LookupEntity::Artist(_) => EntityType::Artist,
+            LookupEntity::ReleaseGroup(_) => EntityType::ReleaseGroup,
+            LookupEntity::Work(_) => EntityType::Work,
+            LookupEntity::Label(_) => EntityType::Label,
+        }
+    }
+}
+
+/// Structured output for MBID lookup.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LookupResult {
+    /// The MBID that was looked up
+    pub mbid: String,
+    #[serde(flatten)]
+    pub entity: LookupEntity,
+    /// Number of MusicBrainz requests made for this lookup
+    pub probes: usize,
+}
+
+/// Resolved entity types and recent "not found" probes, keyed by MBID.
+#[derive(Debug, Default)]
+struct LookupCache {
+    resolved: HashMap<String, EntityType>,
+    not_found: HashMap<(String, EntityType), Instant>,
+}
+
+impl LookupCache {
+    /// Entity types to probe for an MBID: the cached type first, then every
+    /// type without a recent "not found" probe.
+    fn probe_order(&mut self, mbid: &str) -> Vec<EntityType> {
+        let now = Instant::now();
+        self.not_found
+            .retain(|_, probed_at| now.duration_since(*probed_at) < NEGATIVE_PROBE_TTL);
+
+        let resolved = self.resolved.get(mbid).copied();
+        resolved
+            .into_iter()
+            .chain(EntityType::ALL.into_iter().filter(|ty| {
+                Some(*ty) != resolved && !self.not_found.contains_key(&(mbid.to_string(), *ty))
+            }))
+            .collect()
+    }
+
+    fn record_found(&mut self, mbid: &str, entity_type: EntityType) {
+        if self.resolved.len() >= LOOKUP_CACHE_CAPACITY {
+            self.resolved.clear();
+        }
+        self.resolved.insert(mbid.to_string(), entity_type);
+    }
+
+    fn record_not_found(&mut self, mbid: &str, entity_type: EntityType) {
+        if self.resolved.get(mbid) == Some(&entity_type) {
+            self.resolved.remove(mbid);
+        }
+        if self.not_found.len() >= LOOKUP_CACHE_CAPACITY {
+            self.not_found.clear();
+        }
+        self.not_found
+            .insert((mbid.to_string(), entity_type), Instant::now());
+    }
+}
+
+static LOOKUP_CACHE: LazyLock<Mutex<LookupCache>> =
+    LazyLock::new(|| Mutex::new(LookupCache::default()));
+
+/// MusicBrainz MBID Lookup Tool implementation.
+#[derive(Debug, Clone)]
+pub struct MbLookupTool;
+
+impl MbLookupTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "mb_lookup";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Look up any MusicBrainz ID without knowing its entity type. Probes recording, release, artist, release group, work and label endpoints and returns structured data with the detected entity_type and its details. Use the dedicated search tools afterwards for track listings, releases or other related data.";
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    pub fn execute(params: &MbLookupParams) -> CallToolResult {
+        Self::lookup(params.mbid.trim())
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(arguments: serde_json::Value) -> Result<serde_json::Value, String> {
+        let params: MbLookupParams =
+            serde_json::from_value(arguments).map_err(|e| format!("Invalid parameters: {}", e))?;
+
+        // Use std::thread::spawn to avoid nested runtime panic.
+        // musicbrainz_rs uses reqwest::blocking which creates its own runtime.
+        let handle = std::thread::spawn(move || Self::execute(&params));
+
+        let result = handle
+            .join()
+            .map_err(|_| "Thread panicked during MBID lookup".to_string())?;
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        // Include structured_content if present
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<MbLookupParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            async move {
+                let params: MbLookupParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_on_thread(move || Self::execute(&params)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+
+    /// Resolve an MBID by probing each entity endpoint until one returns it.
+    pub fn lookup(query: &str) -> CallToolResult {
+        info!("Looking up MBID: {}", query);
+
+        let Some(mbid) = extract_mbid(query) else {
+            return error_result(&format!("'{}' is not a valid MusicBrainz ID", query));
+        };
+        let mbid = mbid.as_str();
+
+        let order = LOOKUP_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .probe_order(mbid);
+
+        let mut probes = 0;
+        for entity_type in order {
+            probes += 1;
+            debug!("Probing {:?} endpoint for {}", entity_type, mbid);
+
+            let outcome = Self::fetch(entity_type, mbid);
+            let mut cache = LOOKUP_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+            match outcome {
+                Ok(entity) => {
+                    cache.record_found(mbid, entity_type);
+                    return Self::build_result(mbid, entity, probes);
+                }
+                Err(MbRequestError::Api(MbError::NotFound(_))) => {
+                    cache.record_not_found(mbid, entity_type)
+                }
+                Err(e) => {
+                    error!("MBID lookup failed: {:?}", e);
+                    return error_result(&format!("MBID lookup failed: {}", e));
+                }
+            }
+        }
+
+        error_result(&format!(
+            "No MusicBrainz entity found for MBID: {} (tried artist, release, release group, recording, work and label)",
+            mbid
+        ))
+    }
+
+    /// Fetch a single entity type and convert it to its lookup details.
+    fn fetch(entity_type: EntityType, mbid: &str) -> Result<LookupEntity, MbRequestError> {
+        let entity = match entity_type {
+            EntityType::Recording => {
+                let r = Recording::fetch()
+                    .id(mbid)
+                    .with_artists()
+                    .execute_with_retry()?;
+                LookupEntity::Recording(RecordingSearchInfo {
+                    title: r.title,
+                    mbid: r.id,
+                    artist: get_artist_name(&r.artist_credit),
+                    duration: r.length.map(|l| format_duration(l as u64)),
+                    disambiguation: r.disambiguation.filter(|d| !d.is_empty()),
+                    score: None,
+                })
+            }
+            EntityType::Release => {
+                let r = Release::fetch()
+                    .id(mbid)
+                    .with_artists()
+                    .execute_with_retry()?;
+                LookupEntity::Release(ReleaseSearchInfo {
+                    has_cover_art: r.cover_art_archive.as_ref().map(|caa| caa.front),
+                    title: r.title,
+                    mbid: r.id,
+                    artist: get_artist_name(&r.artist_credit),
+                    year: r.date.as_ref().and_then(|d| extract_year(&d.0)),
+                    country: r.country,
+                    barcode: r.barcode.filter(|b| !b.is_empty()),
+                    score: None,
+                })
+            }
+            EntityType::Artist => {
+                let a = Artist::fetch().id(mbid).execute_with_retry()?;
+                LookupEntity::Artist(ArtistSearchInfo {
+                    name: a.name,
+                    mbid: a.id,
+                    country: a.country.filter(|c| !c.is_empty()),
+                    area: a.area.map(|area| area.name),
+                    disambiguation: Some(a.disambiguation).filter(|d| !d.is_empty()),
+                    score: None,
+                })
+            }
+            EntityType::ReleaseGroup => {
+                let rg = ReleaseGroup::fetch()
+                    .id(mbid)
+                    .with_artists()
+                    .execute_with_retry()?;
+                LookupEntity::ReleaseGroup(ReleaseGroupSearchInfo {
+                    title: rg.title,
+                    mbid: rg.id,
+                    artist: get_artist_name(&rg.artist_credit),
+                    first_release_year: rg
+                        .first_release_date
+                        .as_ref()
+                        .and_then(|d| extract_year(&d.0)),
+                    primary_type: rg.primary_type.map(|t| format!("{:?}", t)),
+                    secondary_types: secondary_type_names(&rg.secondary_types),
+                    score: None,
+                })
+            }
+            EntityType::Work => {
+                let w = Work::fetch().id(mbid).execute_with_retry()?;
+                LookupEntity::Work(WorkInfo {
+                    title: w.title,
+                    mbid: w.id,
+                    work_type: w.work_type.map(|t| format!("{:?}", t)),
+                    disambiguation: w.disambiguation.filter(|d| !d.is_empty()),
+                    language: w.language,
+                    score: None,
+                })
+            }
+            EntityType::Label => {
+                let l = Label::fetch().id(mbid).execute_with_retry()?;
+                LookupEntity::Label(LabelInfo {
+                    name: l.name,
+                    mbid: l.id,
+                    label_type: l.label_type.map(|t| format!("{:?}", t)),
+                    country: l.country,
+                    disambiguation: l.disambiguation.filter(|d| !d.is_empty()),
+                    label_code: l.label_code.map(|c| c as i32),
+                    score: None,
+                })
+            }
+        };
+
+        Ok(entity)
+    }
+
+    fn build_result(mbid: &str, entity: LookupEntity, probes: usize) -> CallToolResult {
+        let summary = format!(
+            "MBID {} resolves to {} '{}'",
+            mbid,
+            Self::type_label(entity.entity_type()),
+            entity.name()
+        );
+
+        let structured_data = LookupResult {
+            mbid: mbid.to_string(),
+            entity,
+            probes,
+        };
+
+        structured_result(summary, structured_data)
+    }
+
+    fn type_label(entity_type: EntityType) -> &'static str {
+        match entity_type {
+            EntityType::Recording => "recording",
+            EntityType::Release => "release",
+            EntityType::Artist => "artist",
+            EntityType::ReleaseGroup => "release group",
+            EntityType::Work => "work",
+            EntityType::Label => "label",
+        }
+    }
+}
+
+impl Default for MbLookupTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MBID: &str = "5b11f4ce-a62d-471e-81fc-a69a8278c7da";
+
+    #[test]
+    fn test_lookup_params() {
+        let json = format!(r#"{{"mbid": "{}"}}"#, MBID);
+        let params: MbLookupParams = serde_json::from_str(&json).unwrap();
+        assert_eq!(params.mbid, MBID);
+    }
+
+    #[test]
+    fn test_lookup_rejects_invalid_mbid() {
+        let result = MbLookupTool::lookup("Nirvana");
+        assert!(result.is_error.unwrap_or(false));
+        assert!(result.structured_content.is_none());
+    }
+
+    #[test]
+    fn test_probe_order_skips_negative_probes() {
+        let mut cache = LookupCache::default();
+        assert_eq!(cache.probe_order(MBID), EntityType::ALL.to_vec());
+
+        cache.record_not_found(MBID, EntityType::Recording);
+        cache.record_not_found(MBID, EntityType::Release);
+        assert_eq!(
+            cache.probe_order(MBID),
+            vec![
+                EntityType::Artist,
+                EntityType::ReleaseGroup,
+                EntityType::Work,
+                EntityType::Label
+            ]
+        );
+    }
+
+    #[test]
+    fn test_probe_order_prefers_resolved_type() {
+        let mut cache = LookupCache::default();
+        cache.record_found(MBID, EntityType::Label);
+        assert_eq!(cache.probe_order(MBID)[0], EntityType::Label);
+        assert_eq!(cache.probe_order(MBID).len(), EntityType::ALL.len());
+
+        // A resolved type that later 404s is forgotten
+        cache.record_not_found(MBID, EntityType::Label);
+        assert!(!cache.probe_order(MBID).contains(&EntityType::Label));
+    }
+
+    #[test]
+    fn test_lookup_result_is_tagged() {
+        let result = LookupResult {
+            mbid: MBID.to_string(),
+            entity: LookupEntity::Artist(ArtistSearchInfo {
+                name: "Nirvana".to_string(),
+                mbid: MBID.to_string(),
+                country: Some("US".to_string()),
+                area: None,
+                disambiguation: None,
+                score: None,
+            }),
+            probes: 3,
+        };
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["entity_type"], "artist");
+        assert_eq!(json["details"]["name"], "Nirvana");
+        assert_eq!(json["probes"], 3);
+    }
+
+    // Integration tests (require network, run with: cargo test -- --ignored)
+    #[ignore]
+    #[test]
+    fn test_lookup_artist_mbid() {
+        let result = MbLookupTool::lookup(MBID);
+        assert!(!result.is_error.unwrap_or(true));
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["entity_type"], "artist");
+    }
+}
@@ -0,0 +1,658 @@
+//! Directory-to-release track mapping tool.
+//!
+//! Once an album folder has been identified as a release, this tool works out
+//! which file is which track. Each file's duration and title tag are compared
+//! with the release tracklist, and files are assigned to tracks greedily, best
+//! match first. It is the glue step between identification and tagging.
+
+use futures::FutureExt;
+use lofty::prelude::*;
+use musicbrainz_rs::{
+    Fetch,
+    entity::release::{Media, Release},
+};
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Tool},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+
+use super::common::{
+    ExecuteWithRetry, error_result, extract_mbid, format_duration, get_artist_name,
+    structured_result,
+};
+use super::identify_directory::{MAX_DIRECTORY_FILES, MbIdentifyDirectoryTool};
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::timeout::with_timeout;
+
+/// Duration difference (in seconds) at which the duration score drops to zero.
+const DURATION_WINDOW_SECS: f64 = 15.0;
+/// Pairs scoring below this confidence are never assigned.
+const MIN_MATCH_CONFIDENCE: f64 = 0.5;
+
+/// Parameters for the track matching tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MbMatchTracksParams {
+    /// Directory containing the release's audio files.
+    #[schemars(description = "Path to the directory containing the album's audio files")]
+    pub directory: String,
+
+    /// Release the files belong to.
+    #[schemars(description = "MusicBrainz release ID (MBID or musicbrainz.org URL)")]
+    pub release_mbid: String,
+
+    /// Whether to scan subdirectories too.
+    #[schemars(description = "Also scan subdirectories, e.g. CD1/CD2 folders (default: false)")]
+    #[serde(default)]
+    pub recursive: bool,
+}
+
+/// Structured output for a file-to-track mapping.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TrackMatchResult {
+    pub release_title: String,
+    pub release_mbid: String,
+    pub artist: String,
+    pub directory: String,
+    /// Files assigned to a track, in tracklist order
+    pub matches: Vec<TrackMatch>,
+    /// Files that could not be matched to any track
+    pub unmatched_files: Vec<String>,
+    /// Tracks of the release with no matching file
+    pub missing_tracks: Vec<MissingTrack>,
+    pub file_count: usize,
+    pub track_count: usize,
+    pub matched_count: usize,
+}
+
+/// A file assigned to a release track.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TrackMatch {
+    pub file: String,
+    pub disc_number: usize,
+    /// Position of the track within its medium (disc)
+    pub position: usize,
+    /// Track number as printed on the release (e.g. "1", "A1")
+    pub track_number: String,
+    pub title: String,
+    pub recording_mbid: String,
+    /// Match confidence from 0.0 to 1.0
+    pub confidence: f64,
+    /// Difference between the file's and the track's duration, when both are known
+    pub duration_diff_secs: Option<u32>,
+    /// Similarity of the file's title tag to the track title (0.0-1.0), when tagged
+    pub title_similarity: Option<f64>,
+}
+
+/// A release track without a matching file.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MissingTrack {
+    pub disc_number: usize,
+    pub position: usize,
+    pub track_number: String,
+    pub title: String,
+    pub recording_mbid: String,
+    pub duration: Option<String>,
+}
+
+/// What is known about a local file before matching.
+#[derive(Debug, Clone)]
+pub(super) struct LocalFile {
+    path: String,
+    duration_secs: Option<u32>,
+    title: Option<String>,
+}
+
+/// One track of the release tracklist.
+#[derive(Debug, Clone)]
+pub(super) struct ReleaseTrack {
+    pub(super) disc_number: usize,
+    pub(super) position: usize,
+    track_number: String,
+    pub(super) title: String,
+    pub(super) recording_mbid: String,
+    duration_secs: Option<u32>,
+}
+
+/// How well a file matches a track.
+#[derive(Debug, Clone, Copy)]
+struct PairScore {
+    confidence: f64,
+    duration_diff_secs: Option<u32>,
+    title_similarity: Option<f64>,
+}
+
+/// MusicBrainz Track Matching Tool implementation.
+#[derive(Debug, Clone)]
+pub struct MbMatchTracksTool;
+
+impl MbMatchTracksTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "mb_match_tracks";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Map the audio files of a directory to the tracks of a MusicBrainz release. Compares each file's duration and title tag with the release tracklist and returns, for every file, its disc/track position, recording MBID and a confidence score, plus the files left unmatched and the tracks with no file. Use this after identifying the release of an album folder and before writing tags.";
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    pub fn execute(params: &MbMatchTracksParams, config: &Config) -> CallToolResult {
+        match Self::match_tracks(params, config) {
+            Ok(result) => {
+                let summary = Self::summarize(&result);
+                info!("{}", summary);
+                structured_result(summary, result)
+            }
+            Err(e) => error_result(&e),
+        }
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: MbMatchTracksParams =
+            serde_json::from_value(arguments).map_err(|e| format!("Invalid parameters: {}", e))?;
+
+        // Use std::thread::spawn to avoid nested runtime panic.
+        // musicbrainz_rs uses reqwest::blocking which creates its own runtime.
+        let handle = std::thread::spawn(move || Self::execute(&params, &config));
+
+        let result = handle
+            .join()
+            .map_err(|_| "Thread panicked during track matching".to_string())?;
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        // Include structured_content if present
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<MbMatchTracksParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: MbMatchTracksParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_on_thread(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+
+    /// Read the directory, fetch the release tracklist and map files to tracks.
+    fn match_tracks(
+        params: &MbMatchTracksParams,
+        config: &Config,
+    ) -> Result<TrackMatchResult, String> {
+        info!(
+            "Matching files in {} to release {}",
+            params.directory, params.release_mbid
+        );
+
+        let Some(release_mbid) = extract_mbid(&params.release_mbid) else {
+            return Err(format!(
+                "'{}' is not a valid MusicBrainz release ID",
+                params.release_mbid
+            ));
+        };
+
+        let directory = validate_path(&params.directory, config)
+            .map_err(|e| format!("Path security validation failed: {}", e))?;
+        if !directory.is_dir() {
+            return Err(format!("Path is not a directory: {}", params.directory));
+        }
+
+        let paths = MbIdentifyDirectoryTool::collect_audio_files(&directory, params.recursive)
+            .map_err(|e| format!("Cannot read directory: {}", e))?;
+        if paths.len() > MAX_DIRECTORY_FILES {
+            return Err(format!(
+                "Directory contains {} audio files (max {} per call)",
+                paths.len(),
+                MAX_DIRECTORY_FILES
+            ));
+        }
+
+        let files = Self::read_local_files(&paths, config);
+        debug!("Read {} audio file(s)", files.len());
+
+        let release = Release::fetch()
+            .id(&release_mbid)
+            .with_artists()
+            .with_recordings()
+            .execute_with_retry()
+            .map_err(|e| {
+                error!("Failed to fetch release tracklist: {:?}", e);
+                format!("Failed to fetch release tracklist: {}", e)
+            })?;
+        let tracks = release_tracks(release.media.as_deref().unwrap_or_default());
+        if tracks.is_empty() {
+            return Err(format!("Release '{}' has no tracks", release.title));
+        }
+
+        let mut result = build_result(&files, &tracks);
+        result.release_title = release.title;
+        result.release_mbid = release.id;
+        result.artist = get_artist_name(&release.artist_credit);
+        result.directory = directory.to_string_lossy().into_owned();
+        Ok(result)
+    }
+
+    /// Read the files that pass path validation, skipping the others.
+    pub(super) fn read_local_files(paths: &[PathBuf], config: &Config) -> Vec<LocalFile> {
+        paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .filter(|path| match validate_path(path, config) {
+                Ok(_) => true,
+                Err(e) => {
+                    warn!("Skipping {}: {}", path, e);
+                    false
+                }
+            })
+            .map(|path| Self::read_local_file(&path))
+            .collect()
+    }
+
+    /// Read the duration and title tag of a file; unreadable files yield neither.
+    fn read_local_file(path: &str) -> LocalFile {
+        let tagged_file = match lofty::read_from_path(path) {
+            Ok(file) => file,
+            Err(e) => {
+                warn!("Cannot read {}: {}", path, e);
+                return LocalFile {
+                    path: path.to_string(),
+                    duration_secs: None,
+                    title: None,
+                };
+            }
+        };
+
+        let secs = tagged_file.properties().duration().as_secs_f64().round() as u32;
+        let title = tagged_file
+            .primary_tag()
+            .or_else(|| tagged_file.first_tag())
+            .and_then(|tag| tag.title().map(|t| t.trim().to_string()))
+            .filter(|t| !t.is_empty());
+
+        LocalFile {
+            path: path.to_string(),
+            duration_secs: (secs > 0).then_some(secs),
+            title,
+        }
+    }
+
+    /// One-line summary such as "Matched 11/12 file(s) to 'OK Computer' (1 unmatched file(s), 1 missing track(s))".
+    fn summarize(result: &TrackMatchResult) -> String {
+        format!(
+            "Matched {}/{} file(s) to '{}' ({} unmatched file(s), {} missing track(s))",
+            result.matched_count,
+            result.file_count,
+            result.release_title,
+            result.unmatched_files.len(),
+            result.missing_tracks.len()
+        )
+    }
+}
+
+impl Default for MbMatchTracksTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Flatten the release media into a tracklist, skipping tracks without a recording.
+pub(super) fn release_tracks(media: &[Media]) -> Vec<ReleaseTrack> {
+    media
+        .iter()
+        .enumerate()
+        .flat_map(|(disc_idx, medium)| {
+            let disc_number = medium.position.map_or(disc_idx + 1, |p| p as usize);
+            medium.tracks.iter().flatten().filter_map(move |track| {
+                let recording = track.recording.as_ref()?;
+                Some(ReleaseTrack {
+                    disc_number,
+                    position: track.position as usize,
+                    track_number: track.number.clone(),
+                    title: track.title.clone(),
+                    recording_mbid: recording.id.clone(),
+                    duration_secs: track
+                        .length
+                        .or(recording.length)
+                        .map(|ms| (f64::from(ms) / 1000.0).round() as u32),
+                })
+            })
+        })
+        .collect()
+}
+
+/// Assign files to tracks and split out unmatched files and missing tracks.
+///
+/// Every file/track pair is scored, then pairs are taken best first while
+/// neither side is already assigned. Ties keep directory and tracklist order.
+pub(super) fn build_result(files: &[LocalFile], tracks: &[ReleaseTrack]) -> TrackMatchResult {
+    let mut candidates = Vec::new();
+    for (file_idx, file) in files.iter().enumerate() {
+        for (track_idx, track) in tracks.iter().enumerate() {
+            let score = score_pair(file, track);
+            if score.confidence >= MIN_MATCH_CONFIDENCE {
+                candidates.push((file_idx, track_idx, score));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.2.confidence.total_cmp(&a.2.confidence));
+
+    let mut file_matched = vec![false; files.len()];
+    let mut track_matched = vec![false; tracks.len()];
+    let mut assigned = Vec::new();
+    for (file_idx, track_idx, score) in candidates {
+        if file_matched[file_idx] || track_matched[track_idx] {
+            continue;
+        }
+        file_matched[file_idx] = true;
+        track_matched[track_idx] = true;
+        assigned.push((file_idx, track_idx, score));
+    }
+    assigned.sort_by_key(|(_, track_idx, _)| *track_idx);
+
+    let matches: Vec<TrackMatch> = assigned
+        .into_iter()
+        .map(|(file_idx, track_idx, score)| {
+            let track = &tracks[track_idx];
+            TrackMatch {
+                file: files[file_idx].path.clone(),
+                disc_number: track.disc_number,
+                position: track.position,
+                track_number: track.track_number.clone(),
+                title: track.title.clone(),
+                recording_mbid: track.recording_mbid.clone(),
+                confidence: round2(score.confidence),
+                duration_diff_secs: score.duration_diff_secs,
+                title_similarity: score.title_similarity.map(round2),
+            }
+        })
+        .collect();
+
+    let unmatched_files = files
+        .iter()
+        .zip(&file_matched)
+        .filter(|(_, matched)| !**matched)
+        .map(|(file, _)| file.path.clone())
+        .collect();
+
+    let missing_tracks = tracks
+        .iter()
+        .zip(&track_matched)
+        .filter(|(_, matched)| !**matched)
+        .map(|(track, _)| MissingTrack {
+            disc_number: track.disc_number,
+            position: track.position,
+            track_number: track.track_number.clone(),
+            title: track.title.clone(),
+            recording_mbid: track.recording_mbid.clone(),
+            duration: track
+                .duration_secs
+                .map(|secs| format_duration(u64::from(secs) * 1000)),
+        })
+        .collect();
+
+    TrackMatchResult {
+        release_title: String::new(),
+        release_mbid: String::new(),
+        artist: String::new(),
+        directory: String::new(),
+        file_count: files.len(),
+        track_count: tracks.len(),
+        matched_count: matches.len(),
+        matches,
+        unmatched_files,
+        missing_tracks,
+    }
+}
+
+/// Score a file against a track from duration proximity and title similarity.
+///
+/// When only one signal is available it decides alone; with both, they are
+/// averaged. A file with neither scores zero and is never matched.
+fn score_pair(file: &LocalFile, track: &ReleaseTrack) -> PairScore {
+    let duration_diff_secs = file
+        .duration_secs
+        .zip(track.duration_secs)
+        .map(|(a, b)| a.abs_diff(b));
+    let duration_score =
+        duration_diff_secs.map(|diff| (1.0 - f64::from(diff) / DURATION_WINDOW_SECS).max(0.0));
+    let title_similarity = file
+        .title
+        .as_deref()
+        .and_then(|title| title_similarity(title, &track.title));
+
+    let confidence = match (duration_score, title_similarity) {
+        (Some(d), Some(t)) => (d + t) / 2.0,
+        (Some(d), None) => d,
+        (None, Some(t)) => t,
+        (None, None) => 0.0,
+    };
+
+    PairScore {
+        confidence,
+        duration_diff_secs,
+        title_similarity,
+    }
+}
+
+/// Similarity of two titles from 0.0 to 1.0, ignoring case and punctuation.
+///
+/// Based on the edit distance between the normalized titles; `None` when
+/// either title has no letters or digits.
+fn title_similarity(a: &str, b: &str) -> Option<f64> {
+    let a: Vec<char> = normalize_title(a).chars().collect();
+    let b: Vec<char> = normalize_title(b).chars().collect();
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+
+    // Levenshtein distance, one row at a time
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    let distance = previous[b.len()] as f64;
+    Some(1.0 - distance / a.len().max(b.len()) as f64)
+}
+
+/// Lowercase a title and reduce punctuation and whitespace runs to single spaces.
+fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, duration_secs: Option<u32>, title: Option<&str>) -> LocalFile {
+        LocalFile {
+            path: path.to_string(),
+            duration_secs,
+            title: title.map(String::from),
+        }
+    }
+
+    fn track(position: usize, title: &str, duration_secs: Option<u32>) -> ReleaseTrack {
+        ReleaseTrack {
+            disc_number: 1,
+            position,
+            track_number: position.to_string(),
+            title: title.to_string(),
+            recording_mbid: format!("rec-{}", position),
+            duration_secs,
+        }
+    }
+
+    #[test]
+    fn test_match_tracks_params_defaults() {
+        let json = r#"{"directory": "/music/album", "release_mbid": "b1392450-e666-3926-a536-22c65f834433"}"#;
+        let params: MbMatchTracksParams = serde_json::from_str(json).unwrap();
+        assert_eq!(params.directory, "/music/album");
+        assert!(!params.recursive);
+    }
+
+    #[test]
+    fn test_title_similarity() {
+        assert_eq!(
+            title_similarity("Paranoid Android", "paranoid android"),
+            Some(1.0)
+        );
+        assert_eq!(title_similarity("Let Down!", "Let Down"), Some(1.0));
+        let close = title_similarity("Karma Police", "Karma Polic").unwrap();
+        assert!(close > 0.9 && close < 1.0);
+        assert!(title_similarity("Airbag", "Lucky").unwrap() < 0.5);
+        assert_eq!(title_similarity("???", "Airbag"), None);
+    }
+
+    #[test]
+    fn test_score_pair_combines_duration_and_title() {
+        let t = track(1, "Airbag", Some(284));
+
+        let exact = score_pair(&file("a.flac", Some(284), Some("Airbag")), &t);
+        assert_eq!(exact.confidence, 1.0);
+        assert_eq!(exact.duration_diff_secs, Some(0));
+
+        let untagged = score_pair(&file("a.flac", Some(287), None), &t);
+        assert!((untagged.confidence - 0.8).abs() < 1e-9);
+        assert_eq!(untagged.title_similarity, None);
+
+        let unknown = score_pair(&file("a.flac", None, None), &t);
+        assert_eq!(unknown.confidence, 0.0);
+    }
+
+    #[test]
+    fn test_build_result_maps_files_to_tracks() {
+        let tracks = vec![
+            track(1, "Airbag", Some(284)),
+            track(2, "Paranoid Android", Some(383)),
+            track(3, "Subterranean Homesick Alien", Some(267)),
+        ];
+        let files = vec![
+            file("02.flac", Some(384), Some("Paranoid Android")),
+            // Untagged, matched on duration alone
+            file("01.flac", Some(285), None),
+            // Bonus file with no plausible track
+            file("bonus.flac", Some(600), Some("Hidden Track")),
+        ];
+
+        let result = build_result(&files, &tracks);
+
+        assert_eq!(result.file_count, 3);
+        assert_eq!(result.track_count, 3);
+        assert_eq!(result.matched_count, 2);
+        assert_eq!(result.matches[0].file, "01.flac");
+        assert_eq!(result.matches[0].position, 1);
+        assert_eq!(result.matches[1].file, "02.flac");
+        assert_eq!(result.matches[1].recording_mbid, "rec-2");
+        assert_eq!(result.matches[1].title_similarity, Some(1.0));
+        assert_eq!(result.unmatched_files, vec!["bonus.flac"]);
+        assert_eq!(result.missing_tracks.len(), 1);
+        assert_eq!(
+            result.missing_tracks[0].title,
+            "Subterranean Homesick Alien"
+        );
+        assert_eq!(result.missing_tracks[0].duration.as_deref(), Some("4:27"));
+    }
+
+    #[test]
+    fn test_build_result_prefers_best_pair() {
+        // Both files fit track 1 on duration; the title decides
+        let tracks = vec![
+            track(1, "Lucky", Some(259)),
+            track(2, "The Tourist", Some(324)),
+        ];
+        let files = vec![
+            file("a.flac", Some(259), Some("The Tourist")),
+            file("b.flac", Some(260), Some("Lucky")),
+        ];
+
+        let result = build_result(&files, &tracks);
+
+        assert_eq!(result.matches[0].file, "b.flac");
+        assert_eq!(result.matches[0].position, 1);
+        // The title alone is just enough to place the mistimed file
+        assert_eq!(result.matches[1].file, "a.flac");
+        assert_eq!(result.matches[1].position, 2);
+        assert_eq!(result.matches[1].duration_diff_secs, Some(65));
+    }
+
+    #[test]
+    fn test_match_tracks_rejects_invalid_mbid() {
+        let params = MbMatchTracksParams {
+            directory: ".".to_string(),
+            release_mbid: "OK Computer".to_string(),
+            recursive: false,
+        };
+        let result = MbMatchTracksTool::execute(&params, &Config::default());
+        assert!(result.is_error.unwrap_or(false));
+    }
+}
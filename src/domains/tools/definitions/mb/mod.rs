@@ -1,30 +1,54 @@
 //! MusicBrainz tools module.
 //!
 //! This module provides domain-specific tools for searching the MusicBrainz database:
+//! - `advanced_search`: Search any entity type through a single tool
 //! - `artist`: Search for artists and their releases
+//! - `discography`: An artist's release groups, grouped by type
 //! - `release`: Search for releases, release groups, tracks, and versions
 //! - `recording`: Search for recordings and find where they appear
 //! - `work`: Search for works (musical compositions)
 //! - `label`: Search for labels (record labels/publishers)
+//! - `lookup`: Resolve any MBID without knowing its entity type
 //! - `identify_record`: Audio fingerprinting via AcoustID
+//! - `identify_directory`: Batch audio fingerprinting for a directory
+//! - `match_tracks`: Map a directory's files to the tracks of a release
+//! - `tag_from_release`: Write a release's tags into a file or album folder
+//! - `acoustid_submit`: Submit fingerprints back to AcoustID
 //! - `cover_download`: Download cover art images from Cover Art Archive
+//! - `cover_info`: List available cover art without downloading
 //!
 //! Each tool has handlers for both HTTP and STDIO/TCP transports.
 
+pub mod acoustid_submit;
+pub mod advanced_search;
 pub mod artist;
 pub mod common;
 pub mod cover_download;
+pub mod cover_info;
+pub mod discography;
+pub mod identify_directory;
 pub mod identify_record;
 pub mod label;
+pub mod lookup;
+pub mod match_tracks;
 pub mod recording;
 pub mod release;
+pub mod tag_from_release;
 pub mod work;
 
 // Re-export domain-specific tools
+pub use acoustid_submit::{MbAcoustidSubmitParams, MbAcoustidSubmitTool};
+pub use advanced_search::{MbAdvancedSearchParams, MbAdvancedSearchTool};
 pub use artist::{MbArtistParams, MbArtistTool};
 pub use cover_download::{MbCoverDownloadParams, MbCoverDownloadTool};
+pub use cover_info::{MbCoverInfoParams, MbCoverInfoTool};
+pub use discography::{MbDiscographyParams, MbDiscographyTool};
+pub use identify_directory::{MbIdentifyDirectoryParams, MbIdentifyDirectoryTool};
 pub use identify_record::MbIdentifyRecordTool;
 pub use label::{MbLabelParams, MbLabelTool};
+pub use lookup::{MbLookupParams, MbLookupTool};
+pub use match_tracks::{MbMatchTracksParams, MbMatchTracksTool};
 pub use recording::{MbRecordingParams, MbRecordingTool};
 pub use release::{MbReleaseParams, MbReleaseTool};
+pub use tag_from_release::{TagFromReleaseParams, TagFromReleaseTool};
 pub use work::{MbWorkParams, MbWorkTool};
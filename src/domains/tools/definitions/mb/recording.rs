@@ -7,7 +7,10 @@ use futures::FutureExt;
 use futures::future::BoxFuture;
 use musicbrainz_rs::{
     Fetch, Search,
-    entity::recording::{Recording, RecordingSearchQuery},
+    entity::{
+        recording::{Recording, RecordingSearchQuery},
+        relations::{Relation, RelationContent},
+    },
 };
 use rmcp::{
     ErrorData as McpError,
@@ -19,9 +22,13 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info};
 
 use super::common::{
-    default_limit, error_result, extract_year, format_duration, get_artist_name, is_mbid,
-    structured_result, validate_limit,
+    ExecuteWithRetry, best_match_suffix, default_limit, error_result, extract_mbid, extract_year,
+    format_duration, get_artist_name, search_with_scores, structured_result, validate_limit,
 };
+use crate::core::config::Config;
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::timeout::with_timeout;
+use std::sync::Arc;
 
 /// Parameters for recording search operations.
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
@@ -56,6 +63,13 @@ pub struct MbRecordingParams {
     #[schemars(description = "Maximum number of results (default: 10, max: 100)")]
     #[serde(default = "default_limit")]
     pub limit: usize,
+
+    /// Include producer, engineer and performer credits when fetching by MBID.
+    #[schemars(
+        description = "Include relationship credits (producer, mix, engineer, vocal, instrument...) when the query is a recording MBID. Enlarges the response; default: false"
+    )]
+    #[serde(default)]
+    pub include_credits: bool,
 }
 
 /// Structured output for recording search results.
@@ -73,6 +87,8 @@ pub struct RecordingSearchInfo {
     pub artist: String,
     pub duration: Option<String>,
     pub disambiguation: Option<String>,
+    /// MusicBrainz search relevance (0-100); null when fetched by MBID
+    pub score: Option<u8>,
 }
 
 /// Structured output for single recording details (by MBID).
@@ -86,6 +102,20 @@ pub struct RecordingDetails {
     pub artist_mbids: Vec<ArtistMbid>,
     pub releases: Vec<RecordingReleaseInfo>,
     pub genres: Vec<String>,
+    /// Artist relationship credits; empty unless `include_credits` is set
+    pub credits: Vec<RecordingCredit>,
+}
+
+/// An artist credited on a recording through a relationship.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RecordingCredit {
+    /// Relationship type, e.g. "producer", "mix", "engineer", "vocal", "instrument"
+    pub role: String,
+    /// Artist name as credited on this recording
+    pub name: String,
+    pub mbid: String,
+    /// Relationship attributes, e.g. "piano" for an instrument or "lead vocals"
+    pub attributes: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, JsonSchema)]
@@ -143,9 +173,10 @@ impl MbRecordingTool {
         let search_type = params.search_type.clone();
         let query = params.query.clone();
         let limit = validate_limit(params.limit);
+        let include_credits = params.include_credits;
 
         match search_type.as_str() {
-            "recording" => Self::search_recordings(&query, limit),
+            "recording" => Self::search_recordings(&query, limit, include_credits),
             "recording_releases" => Self::search_recording_releases(&query, limit),
             _ => error_result(&format!(
                 "Unknown search type: {}. Use 'recording' or 'recording_releases'",
@@ -174,10 +205,16 @@ impl MbRecordingTool {
             .and_then(|v| v.as_u64())
             .unwrap_or(10) as usize;
 
+        let include_credits = arguments
+            .get("include_credits")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
         let params = MbRecordingParams {
             search_type,
             query,
             limit,
+            include_credits,
         };
 
         // Use std::thread::spawn to avoid nested runtime panic.
@@ -219,27 +256,25 @@ impl MbRecordingTool {
     }
 
     /// Create a ToolRoute for STDIO/TCP transport.
-    pub fn create_route<S>() -> ToolRoute<S>
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
     where
         S: Send + Sync + 'static,
     {
-        ToolRoute::new_dyn(Self::to_tool(), |ctx: ToolCallContext<'_, S>| {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             async move {
                 let params: MbRecordingParams =
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
-                // Use std::thread::spawn to avoid nested runtime panic.
-                // musicbrainz_rs uses reqwest::blocking which creates its own runtime,
-                // so we need a completely separate OS thread.
-                let handle = std::thread::spawn(move || Self::execute(&params));
-
-                let result = handle
-                    .join()
-                    .map_err(|_| McpError::internal_error("Thread panicked".to_string(), None))?;
-
-                Ok(result)
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_on_thread(move || Self::execute(&params)),
+                )
+                .await
             }
             .boxed()
         })
@@ -252,9 +287,10 @@ impl MbRecordingTool {
             let search_type = params.search_type.clone();
             let query = params.query.clone();
             let limit = validate_limit(params.limit);
+            let include_credits = params.include_credits;
 
             let result = std::thread::spawn(move || match search_type.as_str() {
-                "recording" => Self::search_recordings(&query, limit),
+                "recording" => Self::search_recordings(&query, limit, include_credits),
                 "recording_releases" => Self::search_recording_releases(&query, limit),
                 _ => error_result(&format!(
                     "Unknown search type: {}. Use 'recording' or 'recording_releases'",
@@ -274,9 +310,10 @@ impl MbRecordingTool {
             let search_type = params.search_type.clone();
             let query = params.query.clone();
             let limit = validate_limit(params.limit);
+            let include_credits = params.include_credits;
 
             let result = tokio::task::spawn_blocking(move || match search_type.as_str() {
-                "recording" => Self::search_recordings(&query, limit),
+                "recording" => Self::search_recordings(&query, limit, include_credits),
                 "recording_releases" => Self::search_recording_releases(&query, limit),
                 _ => error_result(&format!(
                     "Unknown search type: {}. Use 'recording' or 'recording_releases'",
@@ -291,26 +328,28 @@ impl MbRecordingTool {
     }
 
     /// Search for recordings by title or MBID.
-    pub fn search_recordings(query: &str, limit: usize) -> CallToolResult {
+    ///
+    /// `include_credits` only applies to MBID queries.
+    pub fn search_recordings(query: &str, limit: usize, include_credits: bool) -> CallToolResult {
         info!("Searching for recordings matching: {}", query);
 
         // If the query is a MusicBrainz ID (MBID), fetch the recording directly.
-        if is_mbid(query) {
-            Self::fetch_recording_by_id(query)
+        if let Some(mbid) = extract_mbid(query) {
+            Self::fetch_recording_by_id(&mbid, include_credits)
         } else {
             Self::search_recordings_by_title(query, limit)
         }
     }
 
     /// Fetch a recording by its MBID with full details.
-    fn fetch_recording_by_id(mbid: &str) -> CallToolResult {
-        match Recording::fetch()
-            .id(mbid)
-            .with_artists()
-            .with_releases()
-            .with_genres()
-            .execute()
-        {
+    fn fetch_recording_by_id(mbid: &str, include_credits: bool) -> CallToolResult {
+        let mut fetch = Recording::fetch();
+        fetch.id(mbid).with_artists().with_releases().with_genres();
+        if include_credits {
+            fetch.with_artist_relations();
+        }
+
+        match fetch.execute_with_retry() {
             Ok(recording) => {
                 let artist = get_artist_name(&recording.artist_credit);
                 let duration = recording.length.map(|l| format_duration(l as u64));
@@ -353,6 +392,13 @@ impl MbRecordingTool {
                     .map(|gs| gs.iter().map(|g| g.name.clone()).collect())
                     .unwrap_or_default();
 
+                let credits = recording
+                    .relations
+                    .as_deref()
+                    .map(relationship_credits)
+                    .unwrap_or_default();
+                let producers = producer_names(&credits).join(", ");
+
                 let structured_data = RecordingDetails {
                     title: recording.title.clone(),
                     mbid: recording.id,
@@ -364,10 +410,11 @@ impl MbRecordingTool {
                     artist_mbids,
                     releases: releases.clone(),
                     genres: genres.clone(),
+                    credits,
                 };
 
                 // Build summary
-                let summary = if releases.is_empty() {
+                let mut summary = if releases.is_empty() {
                     format!("'{}' by {} ({})", recording.title, artist, duration.unwrap_or_else(|| "unknown duration".to_string()))
                 } else {
                     format!(
@@ -378,6 +425,9 @@ impl MbRecordingTool {
                         releases.len()
                     )
                 };
+                if !producers.is_empty() {
+                    summary.push_str(&format!(", produced by {}", producers));
+                }
 
                 structured_result(summary, structured_data)
             }
@@ -394,24 +444,26 @@ impl MbRecordingTool {
             .recording(query)
             .build();
 
-        let search_result = Recording::search(search_query).execute();
+        let search_result = search_with_scores(&Recording::search(search_query));
 
         match search_result {
-            Ok(result) => {
-                let recordings: Vec<_> = result.entities.into_iter().take(limit).collect();
+            Ok(results) => {
+                let recordings: Vec<_> = results.into_iter().take(limit).collect();
                 if recordings.is_empty() {
                     return error_result(&format!("No recordings found for query: {}", query));
                 }
 
                 let count = recordings.len();
+                let best_score = recordings.first().and_then(|(_, score)| *score);
                 let recording_infos: Vec<RecordingSearchInfo> = recordings
                     .into_iter()
-                    .map(|r| RecordingSearchInfo {
+                    .map(|(r, score)| RecordingSearchInfo {
                         title: r.title,
                         mbid: r.id,
                         artist: get_artist_name(&r.artist_credit),
                         duration: r.length.map(|l| format_duration(l as u64)),
                         disambiguation: r.disambiguation.filter(|d| !d.is_empty()),
+                        score,
                     })
                     .collect();
 
@@ -421,7 +473,12 @@ impl MbRecordingTool {
                     query: query.to_string(),
                 };
 
-                let summary = format!("Found {} recording(s) matching '{}'", count, query);
+                let summary = format!(
+                    "Found {} recording(s) matching '{}'{}",
+                    count,
+                    query,
+                    best_match_suffix(best_score)
+                );
                 structured_result(summary, structured_data)
             }
             Err(e) => {
@@ -436,14 +493,14 @@ impl MbRecordingTool {
         info!("Finding releases containing recording: {}", query);
 
         // Get the recording MBID
-        let recording_id = if is_mbid(query) {
-            query.to_string()
+        let recording_id = if let Some(mbid) = extract_mbid(query) {
+            mbid
         } else {
             // Search for recording first
             let search_query = RecordingSearchQuery::query_builder()
                 .recording(query)
                 .build();
-            match Recording::search(search_query).execute() {
+            match Recording::search(search_query).execute_with_retry() {
                 Ok(result) => {
                     if let Some(recording) = result.entities.first() {
                         debug!("Found recording: {} ({})", recording.title, recording.id);
@@ -464,7 +521,7 @@ impl MbRecordingTool {
             .id(&recording_id)
             .with_releases()
             .with_artists()
-            .execute()
+            .execute_with_retry()
         {
             Ok(recording) => {
                 let artist = get_artist_name(&recording.artist_credit);
@@ -523,6 +580,39 @@ impl Default for MbRecordingTool {
     }
 }
 
+/// Credits from the artist relationships of a recording.
+///
+/// Other relationship targets (works, URLs...) are skipped.
+fn relationship_credits(relations: &[Relation]) -> Vec<RecordingCredit> {
+    relations
+        .iter()
+        .filter_map(|relation| match &relation.content {
+            RelationContent::Artist(artist) => Some(RecordingCredit {
+                role: relation.relation_type.clone(),
+                name: relation
+                    .target_credit
+                    .clone()
+                    .filter(|credit| !credit.is_empty())
+                    .unwrap_or_else(|| artist.name.clone()),
+                mbid: artist.id.clone(),
+                attributes: relation.attributes.clone().unwrap_or_default(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Names of the credited producers, without duplicates.
+fn producer_names(credits: &[RecordingCredit]) -> Vec<&str> {
+    let mut names: Vec<&str> = Vec::new();
+    for credit in credits.iter().filter(|c| c.role == "producer") {
+        if !names.contains(&credit.name.as_str()) {
+            names.push(&credit.name);
+        }
+    }
+    names
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -533,13 +623,71 @@ mod tests {
         let json = r#"{"search_type": "recording", "query": "Smells Like Teen Spirit"}"#;
         let params: MbRecordingParams = serde_json::from_str(json).unwrap();
         assert_eq!(params.limit, 10);
+        assert!(!params.include_credits);
+    }
+
+    fn artist_relation(
+        relation_type: &str,
+        name: &str,
+        credit: &str,
+        attributes: &[&str],
+    ) -> Relation {
+        serde_json::from_value(serde_json::json!({
+            "type": relation_type,
+            "type-id": "00000000-0000-0000-0000-000000000000",
+            "direction": "backward",
+            "target-type": "artist",
+            "target-credit": credit,
+            "attributes": attributes,
+            "artist": {
+                "id": format!("{}-mbid", name),
+                "name": name,
+                "sort-name": name,
+                "disambiguation": ""
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_relationship_credits() {
+        let relations = vec![
+            artist_relation("producer", "Nigel Godrich", "", &[]),
+            artist_relation("instrument", "Jonny Greenwood", "Jonny", &["piano"]),
+            artist_relation("vocal", "Thom Yorke", "", &["lead vocals"]),
+        ];
+
+        let credits = relationship_credits(&relations);
+        assert_eq!(credits.len(), 3);
+        assert_eq!(credits[0].role, "producer");
+        assert_eq!(credits[0].name, "Nigel Godrich");
+        assert_eq!(credits[0].mbid, "Nigel Godrich-mbid");
+        assert!(credits[0].attributes.is_empty());
+        // The credited name wins over the artist name
+        assert_eq!(credits[1].name, "Jonny");
+        assert_eq!(credits[1].attributes, vec!["piano"]);
+        assert_eq!(credits[2].attributes, vec!["lead vocals"]);
+    }
+
+    #[test]
+    fn test_producer_names() {
+        let relations = vec![
+            artist_relation("producer", "Nigel Godrich", "", &[]),
+            artist_relation("mix", "Nigel Godrich", "", &[]),
+            artist_relation("producer", "Radiohead", "", &[]),
+            artist_relation("producer", "Nigel Godrich", "", &["co"]),
+        ];
+
+        let credits = relationship_credits(&relations);
+        assert_eq!(producer_names(&credits), vec!["Nigel Godrich", "Radiohead"]);
+        assert!(producer_names(&[]).is_empty());
     }
 
     // Integration tests (require network, run with: cargo test -- --ignored)
     #[ignore]
     #[test]
     fn test_search_recordings() {
-        let result = MbRecordingTool::search_recordings("Paranoid Android", 5);
+        let result = MbRecordingTool::search_recordings("Paranoid Android", 5, false);
         assert!(
             !result.is_error.unwrap_or(true),
             "Expected success but got error"
@@ -558,7 +706,8 @@ mod tests {
     fn test_search_recordings_by_id() {
         std::thread::sleep(std::time::Duration::from_millis(1500));
         // Specific recording MBID
-        let result = MbRecordingTool::search_recordings("3a909079-a42a-4642-b06f-398bf91f34f4", 5);
+        let result =
+            MbRecordingTool::search_recordings("3a909079-a42a-4642-b06f-398bf91f34f4", 5, false);
         assert!(
             !result.is_error.unwrap_or(true),
             "Expected success but got error"
@@ -7,8 +7,9 @@ use futures::FutureExt;
 use futures::future::BoxFuture;
 use musicbrainz_rs::{
     Fetch, Search,
-    entity::release::{Release, ReleaseSearchQuery},
+    entity::release::{Media, Release, ReleaseSearchQuery},
     entity::release_group::{ReleaseGroup, ReleaseGroupSearchQuery},
+    entity::tag::Tag,
 };
 use rmcp::{
     ErrorData as McpError,
@@ -17,12 +18,19 @@ use rmcp::{
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
 use tracing::{debug, error, info};
 
 use super::common::{
-    default_limit, error_result, extract_year, format_duration, get_artist_name, is_mbid,
-    structured_result, validate_limit,
+    ExecuteWithRetry, best_match_suffix, default_limit, error_result, extract_mbid, extract_year,
+    format_duration, get_artist_name, search_with_scores, secondary_type_names, structured_result,
+    validate_limit,
 };
+use super::cover_download::MbCoverDownloadTool;
+use crate::core::config::Config;
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::timeout::with_timeout;
+use std::sync::Arc;
 
 /// Structured output for release search results.
 #[derive(Debug, Clone, Serialize, JsonSchema)]
@@ -40,6 +48,10 @@ pub struct ReleaseSearchInfo {
     pub year: Option<String>,
     pub country: Option<String>,
     pub barcode: Option<String>,
+    /// MusicBrainz search relevance (0-100); null when fetched by MBID
+    pub score: Option<u8>,
+    /// Whether the Cover Art Archive has a front cover; null unless `check_cover_art` is set
+    pub has_cover_art: Option<bool>,
 }
 
 /// Structured output for release recordings (track listing).
@@ -49,19 +61,33 @@ pub struct ReleaseRecordingsResult {
     pub release_mbid: String,
     pub artist: String,
     pub media: Vec<Medium>,
+    /// Number of tracks on the release, including any beyond `limit`
     pub total_tracks: usize,
+    /// True when `limit` cut the listing short
+    pub truncated: bool,
+    /// Running time of the whole release (M:SS)
+    pub total_duration: Option<String>,
+    /// Running time of the whole release in milliseconds
+    pub total_duration_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct Medium {
     pub disc_number: usize,
     pub disc_title: Option<String>,
+    /// Medium format (e.g. "CD", "12\" Vinyl", "Digital Media")
+    pub format: Option<String>,
+    /// Number of tracks on this medium, including any beyond `limit`
+    pub track_count: usize,
     pub tracks: Vec<TrackInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct TrackInfo {
+    /// Position of the track within its medium (disc)
     pub position: usize,
+    /// Track number as printed on the release (e.g. "1", "A1")
+    pub track_number: String,
     pub title: String,
     pub duration: Option<String>,
     pub recording_mbid: String,
@@ -83,6 +109,30 @@ pub struct ReleaseGroupSearchInfo {
     pub artist: String,
     pub first_release_year: Option<String>,
     pub primary_type: Option<String>,
+    /// Secondary types such as "Live", "Compilation" or "Remix"
+    pub secondary_types: Vec<String>,
+    /// MusicBrainz search relevance (0-100); null when fetched by MBID
+    pub score: Option<u8>,
+}
+
+/// Structured output for a single release group fetched by MBID.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ReleaseGroupDetails {
+    pub title: String,
+    pub mbid: String,
+    pub artist: String,
+    pub disambiguation: Option<String>,
+    pub first_release_year: Option<String>,
+    pub primary_type: Option<String>,
+    /// Secondary types such as "Live", "Compilation" or "Remix"
+    pub secondary_types: Vec<String>,
+    /// Genres, most voted first
+    pub genres: Vec<String>,
+    /// Folksonomy tags, most voted first
+    pub tags: Vec<String>,
+    /// Average user rating (0-5)
+    pub rating: Option<f32>,
+    pub rating_votes: Option<u32>,
 }
 
 /// Structured output for release group releases (all versions).
@@ -101,6 +151,8 @@ pub struct ReleaseVersionInfo {
     pub mbid: String,
     pub date: Option<String>,
     pub country: Option<String>,
+    /// Whether the Cover Art Archive has a front cover; null unless `check_cover_art` is set
+    pub has_cover_art: Option<bool>,
 }
 
 /// Parameters for release search operations.
@@ -141,6 +193,13 @@ pub struct MbReleaseParams {
     #[schemars(description = "Maximum number of results (default: 10, max: 100)")]
     #[serde(default = "default_limit")]
     pub limit: usize,
+
+    /// Check the Cover Art Archive for a front cover on each release.
+    #[schemars(
+        description = "Report whether each release has front cover art ('release' and 'release_group_releases' only). Adds one request per release; default: false"
+    )]
+    #[serde(default)]
+    pub check_cover_art: bool,
 }
 
 /// MusicBrainz Release Search Tool implementation.
@@ -163,12 +222,15 @@ impl MbReleaseTool {
         let search_type = params.search_type.clone();
         let query = params.query.clone();
         let limit = validate_limit(params.limit);
+        let check_cover_art = params.check_cover_art;
 
         match search_type.as_str() {
-            "release" => Self::search_releases(&query, limit),
+            "release" => Self::search_releases(&query, limit, check_cover_art),
             "release_group" => Self::search_release_groups(&query, limit),
             "release_recordings" => Self::search_release_recordings(&query, limit),
-            "release_group_releases" => Self::search_release_group_releases(&query, limit),
+            "release_group_releases" => {
+                Self::search_release_group_releases(&query, limit, check_cover_art)
+            }
             _ => error_result(&format!(
                 "Unknown search type: {}. Use 'release', 'release_group', 'release_recordings', or 'release_group_releases'",
                 search_type
@@ -196,10 +258,16 @@ impl MbReleaseTool {
             .and_then(|v| v.as_u64())
             .unwrap_or(10) as usize;
 
+        let check_cover_art = arguments
+            .get("check_cover_art")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
         let params = MbReleaseParams {
             search_type,
             query,
             limit,
+            check_cover_art,
         };
 
         // Use std::thread::spawn to avoid nested runtime panic.
@@ -241,27 +309,25 @@ impl MbReleaseTool {
     }
 
     /// Create a ToolRoute for STDIO/TCP transport.
-    pub fn create_route<S>() -> ToolRoute<S>
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
     where
         S: Send + Sync + 'static,
     {
-        ToolRoute::new_dyn(Self::to_tool(), |ctx: ToolCallContext<'_, S>| {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             async move {
                 let params: MbReleaseParams =
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
-                // Use std::thread::spawn to avoid nested runtime panic.
-                // musicbrainz_rs uses reqwest::blocking which creates its own runtime,
-                // so we need a completely separate OS thread.
-                let handle = std::thread::spawn(move || Self::execute(&params));
-
-                let result = handle
-                    .join()
-                    .map_err(|_| McpError::internal_error("Thread panicked".to_string(), None))?;
-
-                Ok(result)
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_on_thread(move || Self::execute(&params)),
+                )
+                .await
             }
             .boxed()
         })
@@ -274,13 +340,16 @@ impl MbReleaseTool {
             let search_type = params.search_type.clone();
             let query = params.query.clone();
             let limit = validate_limit(params.limit);
+            let check_cover_art = params.check_cover_art;
 
             let result = std::thread::spawn(move || {
                 match search_type.as_str() {
-                    "release" => Self::search_releases(&query, limit),
+                    "release" => Self::search_releases(&query, limit, check_cover_art),
                     "release_group" => Self::search_release_groups(&query, limit),
                     "release_recordings" => Self::search_release_recordings(&query, limit),
-                    "release_group_releases" => Self::search_release_group_releases(&query, limit),
+                    "release_group_releases" => {
+                Self::search_release_group_releases(&query, limit, check_cover_art)
+            }
                     _ => error_result(&format!(
                         "Unknown search type: {}. Use 'release', 'release_group', 'release_recordings', or 'release_group_releases'",
                         search_type
@@ -300,13 +369,16 @@ impl MbReleaseTool {
             let search_type = params.search_type.clone();
             let query = params.query.clone();
             let limit = validate_limit(params.limit);
+            let check_cover_art = params.check_cover_art;
 
             let result = tokio::task::spawn_blocking(move || {
                 match search_type.as_str() {
-                    "release" => Self::search_releases(&query, limit),
+                    "release" => Self::search_releases(&query, limit, check_cover_art),
                     "release_group" => Self::search_release_groups(&query, limit),
                     "release_recordings" => Self::search_release_recordings(&query, limit),
-                    "release_group_releases" => Self::search_release_group_releases(&query, limit),
+                    "release_group_releases" => {
+                Self::search_release_group_releases(&query, limit, check_cover_art)
+            }
                     _ => error_result(&format!(
                         "Unknown search type: {}. Use 'release', 'release_group', 'release_recordings', or 'release_group_releases'",
                         search_type
@@ -321,13 +393,14 @@ impl MbReleaseTool {
     }
 
     /// Search for releases by title or fetch by MBID.
-    pub fn search_releases(query: &str, limit: usize) -> CallToolResult {
+    pub fn search_releases(query: &str, limit: usize, check_cover_art: bool) -> CallToolResult {
         info!("Searching for releases matching: {}", query);
 
         // If query is an MBID, fetch directly
-        if is_mbid(query) {
-            match Release::fetch().id(query).execute() {
+        if let Some(mbid) = extract_mbid(query) {
+            match Release::fetch().id(&mbid).execute_with_retry() {
                 Ok(release) => {
+                    let has_cover_art = check_cover_art.then(|| has_front_cover(&release));
                     let release_info = ReleaseSearchInfo {
                         title: release.title.clone(),
                         mbid: release.id.clone(),
@@ -335,6 +408,8 @@ impl MbReleaseTool {
                         year: release.date.as_ref().and_then(|d| extract_year(&d.0)),
                         country: release.country,
                         barcode: release.barcode.filter(|b| !b.is_empty()),
+                        score: None,
+                        has_cover_art,
                     };
 
                     let structured_data = ReleaseSearchResult {
@@ -355,25 +430,28 @@ impl MbReleaseTool {
             // Search by title
             let search_query = ReleaseSearchQuery::query_builder().release(query).build();
 
-            let search_result = Release::search(search_query).execute();
+            let search_result = search_with_scores(&Release::search(search_query));
 
             match search_result {
-                Ok(result) => {
-                    let releases: Vec<_> = result.entities.into_iter().take(limit).collect();
+                Ok(results) => {
+                    let releases: Vec<_> = results.into_iter().take(limit).collect();
                     if releases.is_empty() {
                         return error_result(&format!("No releases found for query: {}", query));
                     }
 
                     let count = releases.len();
+                    let best_score = releases.first().and_then(|(_, score)| *score);
                     let release_infos: Vec<ReleaseSearchInfo> = releases
                         .into_iter()
-                        .map(|r| ReleaseSearchInfo {
+                        .map(|(r, score)| ReleaseSearchInfo {
+                            has_cover_art: check_cover_art.then(|| has_front_cover(&r)),
                             title: r.title,
                             mbid: r.id,
                             artist: get_artist_name(&r.artist_credit),
                             year: r.date.as_ref().and_then(|d| extract_year(&d.0)),
                             country: r.country,
                             barcode: r.barcode.filter(|b| !b.is_empty()),
+                            score,
                         })
                         .collect();
 
@@ -383,7 +461,12 @@ impl MbReleaseTool {
                         query: query.to_string(),
                     };
 
-                    let summary = format!("Found {} release(s) matching '{}'", count, query);
+                    let summary = format!(
+                        "Found {} release(s) matching '{}'{}",
+                        count,
+                        query,
+                        best_match_suffix(best_score)
+                    );
                     structured_result(summary, structured_data)
                 }
                 Err(e) => {
@@ -399,27 +482,51 @@ impl MbReleaseTool {
         info!("Searching for release groups matching: {}", query);
 
         // If query is an MBID, fetch directly
-        if is_mbid(query) {
-            match ReleaseGroup::fetch().id(query).execute() {
+        if let Some(mbid) = extract_mbid(query) {
+            match ReleaseGroup::fetch()
+                .id(&mbid)
+                .with_artists()
+                .with_genres()
+                .with_tags()
+                .with_ratings()
+                .execute_with_retry()
+            {
                 Ok(release_group) => {
-                    let group_info = ReleaseGroupSearchInfo {
+                    let rating = release_group.rating.as_ref();
+                    let structured_data = ReleaseGroupDetails {
                         title: release_group.title.clone(),
                         mbid: release_group.id.clone(),
                         artist: get_artist_name(&release_group.artist_credit),
+                        disambiguation: Some(release_group.disambiguation.clone())
+                            .filter(|d| !d.is_empty()),
                         first_release_year: release_group
                             .first_release_date
                             .as_ref()
                             .and_then(|d| extract_year(&d.0)),
-                        primary_type: release_group.primary_type.map(|t| format!("{:?}", t)),
-                    };
-
-                    let structured_data = ReleaseGroupSearchResult {
-                        release_groups: vec![group_info],
-                        total_count: 1,
-                        query: query.to_string(),
+                        primary_type: release_group
+                            .primary_type
+                            .as_ref()
+                            .map(|t| format!("{:?}", t)),
+                        secondary_types: secondary_type_names(&release_group.secondary_types),
+                        genres: release_group
+                            .genres
+                            .as_ref()
+                            .map(|genres| {
+                                let mut genres: Vec<_> = genres.iter().collect();
+                                genres.sort_by_key(|g| Reverse(g.count));
+                                genres.into_iter().map(|g| g.name.clone()).collect()
+                            })
+                            .unwrap_or_default(),
+                        tags: release_group
+                            .tags
+                            .as_deref()
+                            .map(tag_names_by_votes)
+                            .unwrap_or_default(),
+                        rating: rating.and_then(|r| r.value),
+                        rating_votes: rating.and_then(|r| r.vote_count),
                     };
 
-                    let summary = format!("Found release group: '{}'", release_group.title);
+                    let summary = release_group_summary(&structured_data);
                     structured_result(summary, structured_data)
                 }
                 Err(e) => {
@@ -433,19 +540,20 @@ impl MbReleaseTool {
                 .release_group(query)
                 .build();
 
-            let search_result = ReleaseGroup::search(search_query).execute();
+            let search_result = search_with_scores(&ReleaseGroup::search(search_query));
 
             match search_result {
-                Ok(result) => {
-                    let groups: Vec<_> = result.entities.into_iter().take(limit).collect();
+                Ok(results) => {
+                    let groups: Vec<_> = results.into_iter().take(limit).collect();
                     if groups.is_empty() {
                         return error_result(&format!("No release groups found for query: {}", query));
                     }
 
                     let count = groups.len();
+                    let best_score = groups.first().and_then(|(_, score)| *score);
                     let group_infos: Vec<ReleaseGroupSearchInfo> = groups
                         .into_iter()
-                        .map(|rg| ReleaseGroupSearchInfo {
+                        .map(|(rg, score)| ReleaseGroupSearchInfo {
                             title: rg.title,
                             mbid: rg.id,
                             artist: get_artist_name(&rg.artist_credit),
@@ -454,6 +562,8 @@ impl MbReleaseTool {
                                 .as_ref()
                                 .and_then(|d| extract_year(&d.0)),
                             primary_type: rg.primary_type.map(|t| format!("{:?}", t)),
+                            secondary_types: secondary_type_names(&rg.secondary_types),
+                            score,
                         })
                         .collect();
 
@@ -463,7 +573,12 @@ impl MbReleaseTool {
                         query: query.to_string(),
                     };
 
-                    let summary = format!("Found {} release group(s) matching '{}'", count, query);
+                    let summary = format!(
+                        "Found {} release group(s) matching '{}'{}",
+                        count,
+                        query,
+                        best_match_suffix(best_score)
+                    );
                     structured_result(summary, structured_data)
                 }
                 Err(e) => {
@@ -479,12 +594,12 @@ impl MbReleaseTool {
         info!("Getting recordings for release: {}", query);
 
         // Get the release MBID
-        let release_id = if is_mbid(query) {
-            query.to_string()
+        let release_id = if let Some(mbid) = extract_mbid(query) {
+            mbid
         } else {
             // Search for release first
             let search_query = ReleaseSearchQuery::query_builder().release(query).build();
-            match Release::search(search_query).execute() {
+            match Release::search(search_query).execute_with_retry() {
                 Ok(result) => {
                     if let Some(release) = result.entities.first() {
                         debug!("Found release: {} ({})", release.title, release.id);
@@ -501,64 +616,23 @@ impl MbReleaseTool {
         };
 
         // Fetch release with recordings (media->tracks)
-        match Release::fetch().id(&release_id).with_recordings().execute() {
+        match Release::fetch().id(&release_id).with_recordings().execute_with_retry() {
             Ok(release) => {
                 let artist = get_artist_name(&release.artist_credit);
-                let mut total_tracks = 0;
-                let mut media_list = Vec::new();
-
-                if let Some(media) = &release.media {
-                    for (disc_idx, medium) in media.iter().enumerate() {
-                        let mut tracks = Vec::new();
-
-                        if let Some(medium_tracks) = &medium.tracks {
-                            for track in medium_tracks.iter().take(limit) {
-                                if let Some(ref recording) = track.recording {
-                                    total_tracks += 1;
-                                    let track_artist = get_artist_name(&recording.artist_credit);
-
-                                    tracks.push(TrackInfo {
-                                        position: total_tracks,
-                                        title: recording.title.clone(),
-                                        duration: recording
-                                            .length
-                                            .map(|l| format_duration(l as u64)),
-                                        recording_mbid: recording.id.clone(),
-                                        artist: if track_artist != artist
-                                            && track_artist != "Unknown Artist"
-                                        {
-                                            Some(track_artist)
-                                        } else {
-                                            None
-                                        },
-                                    });
-                                }
-                            }
-                        }
-
-                        media_list.push(Medium {
-                            disc_number: disc_idx + 1,
-                            disc_title: medium.title.clone(),
-                            tracks,
-                        });
-                    }
-                }
+                let listing =
+                    build_media(release.media.as_deref().unwrap_or_default(), &artist, limit);
+                let summary = listing_summary(&release.title, &listing);
+                let returned_tracks: usize = listing.media.iter().map(|m| m.tracks.len()).sum();
 
                 let structured_data = ReleaseRecordingsResult {
                     release_title: release.title.clone(),
                     release_mbid: release.id.clone(),
                     artist: artist.clone(),
-                    media: media_list,
-                    total_tracks,
-                };
-
-                let summary = if total_tracks > 0 {
-                    format!(
-                        "Track listing for '{}' by {} ({} track(s))",
-                        release.title, artist, total_tracks
-                    )
-                } else {
-                    format!("No tracks available for '{}'", release.title)
+                    total_tracks: listing.total_tracks,
+                    truncated: returned_tracks < listing.total_tracks,
+                    total_duration: listing.total_duration_ms.map(format_duration),
+                    total_duration_ms: listing.total_duration_ms,
+                    media: listing.media,
                 };
 
                 structured_result(summary, structured_data)
@@ -571,18 +645,22 @@ impl MbReleaseTool {
     }
 
     /// Get all releases/versions of a release group.
-    pub fn search_release_group_releases(query: &str, limit: usize) -> CallToolResult {
+    pub fn search_release_group_releases(
+        query: &str,
+        limit: usize,
+        check_cover_art: bool,
+    ) -> CallToolResult {
         info!("Getting all versions of release group: {}", query);
 
         // Get the release group MBID
-        let release_group_id = if is_mbid(query) {
-            query.to_string()
+        let release_group_id = if let Some(mbid) = extract_mbid(query) {
+            mbid
         } else {
             // Search for release group first
             let search_query = ReleaseGroupSearchQuery::query_builder()
                 .release_group(query)
                 .build();
-            match ReleaseGroup::search(search_query).execute() {
+            match ReleaseGroup::search(search_query).execute_with_retry() {
                 Ok(result) => {
                     if let Some(rg) = result.entities.first() {
                         debug!("Found release group: {} ({})", rg.title, rg.id);
@@ -605,7 +683,7 @@ impl MbReleaseTool {
         match ReleaseGroup::fetch()
             .id(&release_group_id)
             .with_releases()
-            .execute()
+            .execute_with_retry()
         {
             Ok(release_group) => {
                 let artist = get_artist_name(&release_group.artist_credit);
@@ -621,6 +699,7 @@ impl MbReleaseTool {
                             mbid: r.id.clone(),
                             date: r.date.as_ref().map(|d| d.0.clone()),
                             country: r.country.clone(),
+                            has_cover_art: check_cover_art.then(|| has_front_cover(r)),
                         })
                         .collect()
                 } else {
@@ -655,29 +734,338 @@ impl MbReleaseTool {
     }
 }
 
+/// Whether a release has a front cover in the Cover Art Archive.
+///
+/// Uses the `cover-art-archive` block when MusicBrainz included it (release
+/// lookups do, searches don't) and otherwise asks the archive directly.
+fn has_front_cover(release: &Release) -> bool {
+    match &release.cover_art_archive {
+        Some(caa) => caa.front,
+        None => MbCoverDownloadTool::has_front_cover(&release.id),
+    }
+}
+
+/// Track listing of a release, before it is wrapped into `ReleaseRecordingsResult`.
+struct TrackListing {
+    media: Vec<Medium>,
+    total_tracks: usize,
+    total_duration_ms: Option<u64>,
+}
+
+/// Build the per-medium track listing. Totals cover the whole release, while
+/// `limit` caps the number of tracks listed across all media, not per medium.
+fn build_media(media: &[Media], release_artist: &str, limit: usize) -> TrackListing {
+    let total_tracks = media.iter().map(|m| m.track_count as usize).sum();
+    let lengths: Vec<u64> = media
+        .iter()
+        .flat_map(|m| m.tracks.iter().flatten())
+        .filter_map(|t| {
+            t.length
+                .or_else(|| t.recording.as_ref().and_then(|r| r.length))
+        })
+        .map(u64::from)
+        .collect();
+    let total_duration_ms = (!lengths.is_empty()).then(|| lengths.iter().sum());
+
+    let mut remaining = limit;
+    let mut media_list = Vec::new();
+
+    for (disc_idx, medium) in media.iter().enumerate() {
+        let mut tracks = Vec::new();
+
+        for track in medium.tracks.iter().flatten() {
+            if remaining == 0 {
+                break;
+            }
+            if let Some(ref recording) = track.recording {
+                remaining -= 1;
+                let track_artist = get_artist_name(&recording.artist_credit);
+
+                tracks.push(TrackInfo {
+                    position: track.position as usize,
+                    track_number: track.number.clone(),
+                    title: recording.title.clone(),
+                    duration: recording.length.map(|l| format_duration(l as u64)),
+                    recording_mbid: recording.id.clone(),
+                    artist: if track_artist != release_artist && track_artist != "Unknown Artist" {
+                        Some(track_artist)
+                    } else {
+                        None
+                    },
+                });
+            }
+        }
+
+        if tracks.is_empty() && remaining == 0 {
+            break;
+        }
+
+        media_list.push(Medium {
+            disc_number: medium.position.map_or(disc_idx + 1, |p| p as usize),
+            disc_title: medium.title.clone(),
+            format: medium.format.clone(),
+            track_count: medium.track_count as usize,
+            tracks,
+        });
+    }
+
+    TrackListing {
+        media: media_list,
+        total_tracks,
+        total_duration_ms,
+    }
+}
+
+/// Describe the media formats of a listing, e.g. "CD", "2×CD" or "CD + DVD-Video".
+fn describe_formats(media: &[Medium]) -> Option<String> {
+    let formats: Vec<&str> = media.iter().filter_map(|m| m.format.as_deref()).collect();
+    match formats.as_slice() {
+        [] => None,
+        [single] => Some(single.to_string()),
+        [first, rest @ ..] if rest.iter().all(|f| f == first) => {
+            Some(format!("{}×{}", formats.len(), first))
+        }
+        _ => Some(formats.join(" + ")),
+    }
+}
+
+/// One-line summary such as "Track listing for 'OK Computer' (12 tracks, 53:21, CD)".
+fn listing_summary(title: &str, listing: &TrackListing) -> String {
+    if listing.total_tracks == 0 {
+        return format!("No tracks available for '{}'", title);
+    }
+
+    let returned: usize = listing.media.iter().map(|m| m.tracks.len()).sum();
+    let mut details = vec![if returned < listing.total_tracks {
+        format!("showing {} of {} tracks", returned, listing.total_tracks)
+    } else {
+        format!("{} tracks", listing.total_tracks)
+    }];
+    details.extend(listing.total_duration_ms.map(format_duration));
+    details.extend(describe_formats(&listing.media));
+
+    format!("Track listing for '{}' ({})", title, details.join(", "))
+}
+
 impl Default for MbReleaseTool {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Tag names, most voted first.
+fn tag_names_by_votes(tags: &[Tag]) -> Vec<String> {
+    let mut tags: Vec<&Tag> = tags.iter().collect();
+    tags.sort_by_key(|t| Reverse(t.count));
+    tags.into_iter().map(|t| t.name.clone()).collect()
+}
+
+/// Summary of a release group, e.g.
+/// "Release group 'Live at X' by Y (Album + Live, 2008), rated 4.2/5 (12 votes)".
+fn release_group_summary(details: &ReleaseGroupDetails) -> String {
+    let mut kinds: Vec<&str> = details.primary_type.iter().map(String::as_str).collect();
+    kinds.extend(details.secondary_types.iter().map(String::as_str));
+    let mut kind = kinds.join(" + ");
+    if let Some(year) = &details.first_release_year {
+        if !kind.is_empty() {
+            kind.push_str(", ");
+        }
+        kind.push_str(year);
+    }
+
+    let mut summary = format!("Release group '{}' by {}", details.title, details.artist);
+    if !kind.is_empty() {
+        summary.push_str(&format!(" ({})", kind));
+    }
+    if let (Some(rating), Some(votes)) = (details.rating, details.rating_votes) {
+        summary.push_str(&format!(", rated {:.1}/5 ({} votes)", rating, votes));
+    }
+    summary
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rmcp::model::RawContent;
 
+    fn sample_media() -> Vec<Media> {
+        let track = |pos: u32, number: &str, title: &str| {
+            serde_json::json!({
+                "id": format!("track-{}", title),
+                "title": title,
+                "number": number,
+                "position": pos,
+                "length": 180000,
+                "recording": {"id": format!("rec-{}", title), "title": title, "length": 180000}
+            })
+        };
+        serde_json::from_value(serde_json::json!([
+            {
+                "position": 1,
+                "title": null,
+                "format": "12\" Vinyl",
+                "track-count": 2,
+                "tracks": [track(1, "A1", "One"), track(2, "A2", "Two")]
+            },
+            {
+                "position": 2,
+                "title": "Bonus",
+                "format": "12\" Vinyl",
+                "track-count": 2,
+                "tracks": [track(1, "B1", "Three"), track(2, "B2", "Four")]
+            }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_media_numbers_tracks_per_medium() {
+        let TrackListing {
+            media,
+            total_tracks,
+            ..
+        } = build_media(&sample_media(), "Artist", 10);
+        assert_eq!(total_tracks, 4);
+        assert_eq!(media.len(), 2);
+        assert_eq!(media[1].disc_number, 2);
+        assert_eq!(media[1].tracks[0].position, 1);
+        assert_eq!(media[1].tracks[0].track_number, "B1");
+        assert_eq!(media[1].tracks[0].title, "Three");
+    }
+
+    #[test]
+    fn test_build_media_limit_applies_to_whole_release() {
+        let listing = build_media(&sample_media(), "Artist", 3);
+        assert_eq!(listing.total_tracks, 4);
+        let returned: usize = listing.media.iter().map(|m| m.tracks.len()).sum();
+        assert_eq!(returned, 3);
+        assert_eq!(listing.media[1].tracks.len(), 1);
+        assert_eq!(listing.media[1].track_count, 2);
+
+        let listing = build_media(&sample_media(), "Artist", 2);
+        assert_eq!(listing.media.len(), 1);
+    }
+
+    #[test]
+    fn test_build_media_totals_cover_whole_release() {
+        let listing = build_media(&sample_media(), "Artist", 1);
+        assert_eq!(listing.total_duration_ms, Some(4 * 180_000));
+        assert_eq!(listing.media[0].format.as_deref(), Some("12\" Vinyl"));
+        assert_eq!(
+            listing_summary("Album", &listing),
+            "Track listing for 'Album' (showing 1 of 4 tracks, 12:00, 12\" Vinyl)"
+        );
+
+        let listing = build_media(&sample_media(), "Artist", 10);
+        assert_eq!(
+            listing_summary("Album", &listing),
+            "Track listing for 'Album' (4 tracks, 12:00, 2×12\" Vinyl)"
+        );
+    }
+
+    #[test]
+    fn test_has_front_cover_uses_embedded_archive_info() {
+        let release: Release = serde_json::from_value(serde_json::json!({
+            "id": "b84ee12a-09ef-421b-82de-0441a926375b",
+            "title": "OK Computer",
+            "cover-art-archive": {
+                "artwork": true,
+                "back": false,
+                "count": 1,
+                "darkened": false,
+                "front": false
+            }
+        }))
+        .unwrap();
+        assert!(!has_front_cover(&release));
+    }
+
+    fn sample_release_group_details() -> ReleaseGroupDetails {
+        ReleaseGroupDetails {
+            title: "I Might Be Wrong".to_string(),
+            mbid: "rg-mbid".to_string(),
+            artist: "Radiohead".to_string(),
+            disambiguation: None,
+            first_release_year: Some("2001".to_string()),
+            primary_type: Some("Album".to_string()),
+            secondary_types: vec!["Live".to_string()],
+            genres: vec![],
+            tags: vec![],
+            rating: Some(4.2),
+            rating_votes: Some(12),
+        }
+    }
+
+    #[test]
+    fn test_release_group_summary() {
+        let details = sample_release_group_details();
+        assert_eq!(
+            release_group_summary(&details),
+            "Release group 'I Might Be Wrong' by Radiohead (Album + Live, 2001), rated 4.2/5 (12 votes)"
+        );
+
+        let unrated = ReleaseGroupDetails {
+            primary_type: None,
+            secondary_types: vec![],
+            first_release_year: None,
+            rating: None,
+            rating_votes: None,
+            ..details
+        };
+        assert_eq!(
+            release_group_summary(&unrated),
+            "Release group 'I Might Be Wrong' by Radiohead"
+        );
+    }
+
+    #[test]
+    fn test_tag_names_by_votes() {
+        let tags: Vec<Tag> = serde_json::from_value(serde_json::json!([
+            {"name": "live", "count": 2},
+            {"name": "alternative rock", "count": 7},
+            {"name": "british"}
+        ]))
+        .unwrap();
+        assert_eq!(
+            tag_names_by_votes(&tags),
+            vec!["alternative rock", "live", "british"]
+        );
+    }
+
+    #[test]
+    fn test_describe_formats() {
+        let medium = |format: Option<&str>| Medium {
+            disc_number: 1,
+            disc_title: None,
+            format: format.map(String::from),
+            track_count: 0,
+            tracks: Vec::new(),
+        };
+        assert_eq!(describe_formats(&[]), None);
+        assert_eq!(
+            describe_formats(&[medium(Some("CD"))]).as_deref(),
+            Some("CD")
+        );
+        assert_eq!(
+            describe_formats(&[medium(Some("CD")), medium(Some("DVD-Video"))]).as_deref(),
+            Some("CD + DVD-Video")
+        );
+        assert_eq!(describe_formats(&[medium(None)]), None);
+    }
+
     #[test]
     fn test_release_params_default_limit() {
         let json = r#"{"search_type": "release", "query": "Nevermind"}"#;
         let params: MbReleaseParams = serde_json::from_str(json).unwrap();
         assert_eq!(params.limit, 10);
+        assert!(!params.check_cover_art);
     }
 
     // Integration tests (require network, run with: cargo test -- --ignored)
     #[ignore]
     #[test]
     fn test_search_releases() {
-        let result = MbReleaseTool::search_releases("Nevermind", 5);
+        let result = MbReleaseTool::search_releases("Nevermind", 5, false);
         assert!(
             !result.is_error.unwrap_or(true),
             "Expected success but got error"
@@ -719,6 +1107,7 @@ mod tests {
         let result = MbReleaseTool::search_release_group_releases(
             "18079f7b-78c3-3980-b16e-c5db63cc10a5",
             10,
+            false,
         );
         assert!(
             !result.is_error.unwrap_or(true),
@@ -0,0 +1,612 @@
+//! Release-based tagging tool.
+//!
+//! Writes the tags of a MusicBrainz release into an audio file or an album
+//! folder in one call: the release is fetched with its recordings and artist
+//! credits, files are placed on the tracklist (by the given position, or
+//! matched by duration and title like `mb_match_tracks`), and each file is
+//! written through write_metadata.
+
+use futures::FutureExt;
+use musicbrainz_rs::{Fetch, entity::release::Release};
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Tool},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use super::common::{
+    ExecuteWithRetry, error_result, extract_mbid, format_artist_credit, get_artist_name,
+    structured_result,
+};
+use super::identify_directory::{MAX_DIRECTORY_FILES, MbIdentifyDirectoryTool};
+use super::match_tracks::{MbMatchTracksTool, MissingTrack, build_result, release_tracks};
+use crate::core::audit;
+use crate::core::config::Config;
+use crate::core::security::{AccessKind, validate_path_for};
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::definitions::metadata::write::{
+    MetadataWriteResult, WriteMetadataParams, WriteMetadataTool,
+};
+use crate::domains::tools::timeout::with_audited_timeout;
+
+/// Parameters for the release tagging tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct TagFromReleaseParams {
+    /// Audio file or album directory to tag.
+    #[schemars(
+        description = "Path to an audio file, or to a directory whose audio files are matched to the release"
+    )]
+    pub path: String,
+
+    /// Release whose tags are written.
+    #[schemars(description = "MusicBrainz release ID (MBID or musicbrainz.org URL)")]
+    pub release_mbid: String,
+
+    /// Track position of the file on its disc.
+    #[schemars(
+        description = "Position of the track on its disc, for a single file (default: matched by duration and title)"
+    )]
+    #[serde(default)]
+    pub track: Option<u32>,
+
+    /// Disc of the track.
+    #[schemars(description = "Disc number of the track, used with track (default: 1)")]
+    #[serde(default)]
+    pub disc: Option<u32>,
+
+    /// Whether to scan subdirectories too.
+    #[schemars(
+        description = "Also tag files in subdirectories, e.g. CD1/CD2 folders (default: false)"
+    )]
+    #[serde(default)]
+    pub recursive: bool,
+
+    /// Compute the changes without writing.
+    #[schemars(
+        description = "Return the per-file changes without writing any file (default: false)"
+    )]
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Back up each file before writing.
+    #[schemars(
+        description = "Copy each file to <name>.bak before writing (default: MCP_METADATA_BACKUP_ON_WRITE)"
+    )]
+    #[serde(default)]
+    pub backup: Option<bool>,
+}
+
+/// Structured output for release tagging.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ReleaseTaggingResult {
+    pub release_title: String,
+    pub release_mbid: String,
+    pub artist: String,
+    /// False for a dry run: nothing was written
+    pub applied: bool,
+    /// One entry per file placed on the tracklist, in tracklist order
+    pub results: Vec<FileTagResult>,
+    /// Files that could not be matched to any track
+    pub unmatched_files: Vec<String>,
+    /// Tracks of the release with no matching file (directory mode only)
+    pub missing_tracks: Vec<MissingTrack>,
+    pub success_count: usize,
+    pub failure_count: usize,
+}
+
+/// Outcome of tagging one file.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FileTagResult {
+    pub file: String,
+    pub disc_number: usize,
+    pub position: usize,
+    pub title: String,
+    pub recording_mbid: String,
+    /// Match confidence from 0.0 to 1.0; absent when the track position was given
+    pub confidence: Option<f64>,
+    pub success: bool,
+    /// Same shape as write_metadata output, including the `changes` diff
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<MetadataWriteResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// MusicBrainz release tagging tool implementation.
+#[derive(Debug, Clone)]
+pub struct TagFromReleaseTool;
+
+impl TagFromReleaseTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "tag_from_release";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Tag an audio file or an album folder from a MusicBrainz release. Fetches the release with its recordings and artist credits, places each file on the tracklist (at the given track/disc for a single file, otherwise matched by duration and title like mb_match_tracks) and writes title, artist, album, album artist, year, track/total, disc/total and MusicBrainz IDs. Use dry_run to review the per-file changes first.";
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
+    pub fn execute(params: &TagFromReleaseParams, config: &Config) -> CallToolResult {
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
+
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &TagFromReleaseParams) -> Vec<&str> {
+        vec![params.path.as_str()]
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &TagFromReleaseParams, config: &Config) -> CallToolResult {
+        match Self::tag_from_release(params, config) {
+            Ok(result) => {
+                let summary = Self::summarize(&result);
+                info!("{}", summary);
+                structured_result(summary, result)
+            }
+            Err(e) => error_result(&e),
+        }
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: TagFromReleaseParams =
+            serde_json::from_value(arguments).map_err(|e| format!("Invalid parameters: {}", e))?;
+
+        // Use std::thread::spawn to avoid nested runtime panic.
+        // musicbrainz_rs uses reqwest::blocking which creates its own runtime.
+        let handle = std::thread::spawn(move || Self::execute(&params, &config));
+
+        let result = handle
+            .join()
+            .map_err(|_| "Thread panicked during release tagging".to_string())?;
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        // Include structured_content if present
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<TagFromReleaseParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: TagFromReleaseParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_on_thread(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+
+    /// Fetch the release, place the files on its tracklist and write their tags.
+    fn tag_from_release(
+        params: &TagFromReleaseParams,
+        config: &Config,
+    ) -> Result<ReleaseTaggingResult, String> {
+        info!(
+            "Tagging {} from release {}",
+            params.path, params.release_mbid
+        );
+
+        let Some(release_mbid) = extract_mbid(&params.release_mbid) else {
+            return Err(format!(
+                "'{}' is not a valid MusicBrainz release ID",
+                params.release_mbid
+            ));
+        };
+        if params.disc.is_some() && params.track.is_none() {
+            return Err("disc can only be given together with track".to_string());
+        }
+
+        let path = validate_path_for(&params.path, AccessKind::Write, config)
+            .map_err(|e| format!("Path security validation failed: {}", e))?;
+        let paths = if path.is_dir() {
+            if params.track.is_some() {
+                return Err("track can only be given when path is a file".to_string());
+            }
+            let paths = MbIdentifyDirectoryTool::collect_audio_files(&path, params.recursive)
+                .map_err(|e| format!("Cannot read directory: {}", e))?;
+            if paths.len() > MAX_DIRECTORY_FILES {
+                return Err(format!(
+                    "Directory contains {} audio files (max {} per call)",
+                    paths.len(),
+                    MAX_DIRECTORY_FILES
+                ));
+            }
+            paths
+        } else if path.is_file() {
+            vec![path.clone()]
+        } else {
+            return Err(format!("Path is not a file or directory: {}", params.path));
+        };
+
+        let release = Release::fetch()
+            .id(&release_mbid)
+            .with_artists()
+            .with_artist_credits()
+            .with_recordings()
+            .with_release_groups()
+            .execute_with_retry()
+            .map_err(|e| {
+                error!("Failed to fetch release: {:?}", e);
+                format!("Failed to fetch release: {}", e)
+            })?;
+        let tracks = release_tracks(release.media.as_deref().unwrap_or_default());
+        if tracks.is_empty() {
+            return Err(format!("Release '{}' has no tracks", release.title));
+        }
+
+        // Place the files on the tracklist
+        let mut results = Vec::new();
+        let mut unmatched_files = Vec::new();
+        let mut missing_tracks = Vec::new();
+        if let Some(position) = params.track {
+            let disc = params.disc.unwrap_or(1);
+            let track = tracks
+                .iter()
+                .find(|t| t.disc_number == disc as usize && t.position == position as usize)
+                .ok_or_else(|| {
+                    format!(
+                        "Release '{}' has no track {} on disc {}",
+                        release.title, position, disc
+                    )
+                })?;
+            results.push(FileTagResult {
+                file: path.to_string_lossy().into_owned(),
+                disc_number: track.disc_number,
+                position: track.position,
+                title: track.title.clone(),
+                recording_mbid: track.recording_mbid.clone(),
+                confidence: None,
+                success: false,
+                result: None,
+                error: None,
+            });
+        } else {
+            let files = MbMatchTracksTool::read_local_files(&paths, config);
+            let matched = build_result(&files, &tracks);
+            results = matched
+                .matches
+                .into_iter()
+                .map(|m| FileTagResult {
+                    file: m.file,
+                    disc_number: m.disc_number,
+                    position: m.position,
+                    title: m.title,
+                    recording_mbid: m.recording_mbid,
+                    confidence: Some(m.confidence),
+                    success: false,
+                    result: None,
+                    error: None,
+                })
+                .collect();
+            unmatched_files = matched.unmatched_files;
+            if path.is_dir() {
+                missing_tracks = matched.missing_tracks;
+            }
+        }
+
+        // Write each file, recording failures without aborting
+        for file_result in &mut results {
+            let outcome = release_fields(&release, file_result.disc_number, file_result.position)
+                .ok_or_else(|| "Track not found in the release".to_string())
+                .and_then(|mut fields| {
+                    fields.insert("path".to_string(), Value::from(file_result.file.clone()));
+                    fields.insert("dry_run".to_string(), Value::from(params.dry_run));
+                    if let Some(backup) = params.backup {
+                        fields.insert("backup".to_string(), Value::from(backup));
+                    }
+                    serde_json::from_value::<WriteMetadataParams>(Value::Object(fields))
+                        .map_err(|e| format!("Invalid parameters: {}", e))
+                })
+                .and_then(|write_params| WriteMetadataTool::write_file(&write_params, config));
+            match outcome {
+                Ok(result) => {
+                    file_result.success = true;
+                    file_result.result = Some(result);
+                }
+                Err(e) => {
+                    warn!("Failed to tag {}: {}", file_result.file, e);
+                    file_result.error = Some(e);
+                }
+            }
+        }
+
+        let success_count = results.iter().filter(|r| r.success).count();
+        Ok(ReleaseTaggingResult {
+            release_title: release.title.clone(),
+            release_mbid: release.id.clone(),
+            artist: get_artist_name(&release.artist_credit),
+            applied: !params.dry_run,
+            failure_count: results.len() - success_count,
+            success_count,
+            results,
+            unmatched_files,
+            missing_tracks,
+        })
+    }
+
+    /// One-line summary such as "Tagged 11/11 file(s) from 'OK Computer' (0 failed, 1 unmatched file(s), 1 missing track(s))".
+    fn summarize(result: &ReleaseTaggingResult) -> String {
+        let counts = format!(
+            "({} failed, {} unmatched file(s), {} missing track(s))",
+            result.failure_count,
+            result.unmatched_files.len(),
+            result.missing_tracks.len()
+        );
+        if result.applied {
+            format!(
+                "Tagged {}/{} file(s) from '{}' {}",
+                result.success_count,
+                result.results.len(),
+                result.release_title,
+                counts
+            )
+        } else {
+            let changed = result
+                .results
+                .iter()
+                .filter_map(|r| r.result.as_ref())
+                .filter(|r| !r.changes.is_empty())
+                .count();
+            format!(
+                "DRY RUN: {}/{} file(s) would change from '{}' {}; nothing was written",
+                changed,
+                result.results.len(),
+                result.release_title,
+                counts
+            )
+        }
+    }
+}
+
+impl Default for TagFromReleaseTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The write_metadata fields of one release track, found by disc number and position.
+///
+/// Discs are numbered like [`release_tracks`]: by medium position, else by order.
+fn release_fields(
+    release: &Release,
+    disc_number: usize,
+    position: usize,
+) -> Option<Map<String, Value>> {
+    let media = release.media.as_deref().unwrap_or_default();
+    let (medium, track) = media
+        .iter()
+        .enumerate()
+        .filter(|(disc_idx, medium)| {
+            medium.position.map_or(disc_idx + 1, |p| p as usize) == disc_number
+        })
+        .find_map(|(_, medium)| {
+            let track = medium
+                .tracks
+                .iter()
+                .flatten()
+                .find(|t| t.position as usize == position)?;
+            Some((medium, track))
+        })?;
+    let recording = track.recording.as_ref()?;
+
+    // Track credits fall back to the recording's, then to the release's
+    let track_credit = [&track.artist_credit, &recording.artist_credit]
+        .into_iter()
+        .find(|credit| credit.as_ref().is_some_and(|c| !c.is_empty()))
+        .unwrap_or(&release.artist_credit);
+
+    let mut fields = Map::new();
+    fields.insert("title".to_string(), Value::from(track.title.clone()));
+    if let Some(artist) = format_artist_credit(track_credit) {
+        fields.insert("artist".to_string(), Value::from(artist));
+    }
+    fields.insert("album".to_string(), Value::from(release.title.clone()));
+    if let Some(album_artist) = format_artist_credit(&release.artist_credit) {
+        fields.insert("album_artist".to_string(), Value::from(album_artist));
+    }
+    if let Some(year) = release
+        .date
+        .as_ref()
+        .and_then(|date| date.0.get(..4))
+        .and_then(|year| year.parse::<u32>().ok())
+    {
+        fields.insert("year".to_string(), Value::from(year));
+    }
+    fields.insert("track".to_string(), Value::from(position));
+    fields.insert("track_total".to_string(), Value::from(medium.track_count));
+    fields.insert("disc".to_string(), Value::from(disc_number));
+    fields.insert("disc_total".to_string(), Value::from(media.len()));
+    fields.insert(
+        "mb_recording_id".to_string(),
+        Value::from(recording.id.clone()),
+    );
+    fields.insert("mb_release_id".to_string(), Value::from(release.id.clone()));
+    if let Some(artist) = track_credit.as_ref().and_then(|credit| credit.first()) {
+        fields.insert(
+            "mb_artist_id".to_string(),
+            Value::from(artist.artist.id.clone()),
+        );
+    }
+    if let Some(release_group) = &release.release_group {
+        fields.insert(
+            "mb_release_group_id".to_string(),
+            Value::from(release_group.id.clone()),
+        );
+    }
+    Some(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release() -> Release {
+        let credit = |id: &str, name: &str, joinphrase: &str| {
+            serde_json::json!({
+                "name": name,
+                "joinphrase": joinphrase,
+                "artist": {"id": id, "name": name, "sort-name": name}
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "id": "b1392450-e666-3926-a536-22c65f834433",
+            "title": "The Album",
+            "date": "1997-05-21",
+            "artist-credit": [credit("a1", "Band", "")],
+            "release-group": {"id": "rg-1", "title": "The Album"},
+            "media": [
+                {"position": 1, "track-count": 2, "tracks": [
+                    {"id": "t1", "position": 1, "number": "1", "title": "Opener",
+                     "recording": {"id": "rec-1", "title": "Opener"}},
+                    {"id": "t2", "position": 2, "number": "2", "title": "Duet",
+                     "artist-credit": [credit("a1", "Band", " & "), credit("a2", "Guest", "")],
+                     "recording": {"id": "rec-2", "title": "Duet"}}
+                ]},
+                {"position": 2, "track-count": 1, "tracks": [
+                    {"id": "t3", "position": 1, "number": "1", "title": "Bonus",
+                     "recording": {"id": "rec-3", "title": "Bonus"}}
+                ]}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tag_from_release_params_defaults() {
+        let json =
+            r#"{"path": "/music/album", "release_mbid": "b1392450-e666-3926-a536-22c65f834433"}"#;
+        let params: TagFromReleaseParams = serde_json::from_str(json).unwrap();
+        assert_eq!(params.track, None);
+        assert!(!params.recursive);
+        assert!(!params.dry_run);
+        assert_eq!(params.backup, None);
+    }
+
+    #[test]
+    fn test_release_fields() {
+        let release = release();
+
+        let fields = release_fields(&release, 1, 2).unwrap();
+        assert_eq!(fields["title"], "Duet");
+        assert_eq!(fields["artist"], "Band & Guest");
+        assert_eq!(fields["album"], "The Album");
+        assert_eq!(fields["album_artist"], "Band");
+        assert_eq!(fields["year"], 1997);
+        assert_eq!(fields["track"], 2);
+        assert_eq!(fields["track_total"], 2);
+        assert_eq!(fields["disc"], 1);
+        assert_eq!(fields["disc_total"], 2);
+        assert_eq!(fields["mb_recording_id"], "rec-2");
+        assert_eq!(fields["mb_release_id"], release.id);
+        assert_eq!(fields["mb_artist_id"], "a1");
+        assert_eq!(fields["mb_release_group_id"], "rg-1");
+
+        // Tracks without their own credit use the release's
+        let fields = release_fields(&release, 2, 1).unwrap();
+        assert_eq!(fields["title"], "Bonus");
+        assert_eq!(fields["artist"], "Band");
+        assert_eq!(fields["track_total"], 1);
+
+        assert!(release_fields(&release, 2, 2).is_none());
+        assert!(release_fields(&release, 3, 1).is_none());
+    }
+
+    #[test]
+    fn test_release_fields_are_write_metadata_params() {
+        let mut fields = release_fields(&release(), 1, 1).unwrap();
+        fields.insert("path".to_string(), Value::from("/music/01.flac"));
+        let params: WriteMetadataParams = serde_json::from_value(Value::Object(fields)).unwrap();
+        assert_eq!(params.title.as_deref(), Some("Opener"));
+        assert_eq!(params.disc_total, Some(2));
+    }
+
+    #[test]
+    fn test_tag_from_release_rejects_invalid_params() {
+        let params: TagFromReleaseParams = serde_json::from_value(serde_json::json!({
+            "path": ".",
+            "release_mbid": "The Album"
+        }))
+        .unwrap();
+        let result = TagFromReleaseTool::execute(&params, &Config::default());
+        assert!(result.is_error.unwrap_or(false));
+
+        // A disc without a track, and a track for a directory
+        for args in [
+            serde_json::json!({"disc": 2}),
+            serde_json::json!({"track": 1}),
+        ] {
+            let mut params = serde_json::json!({
+                "path": ".",
+                "release_mbid": "b1392450-e666-3926-a536-22c65f834433"
+            });
+            params
+                .as_object_mut()
+                .unwrap()
+                .extend(args.as_object().unwrap().clone());
+            let params: TagFromReleaseParams = serde_json::from_value(params).unwrap();
+            let result = TagFromReleaseTool::execute(&params, &Config::default());
+            assert!(result.is_error.unwrap_or(false));
+        }
+    }
+}
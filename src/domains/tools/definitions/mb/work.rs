@@ -19,8 +19,13 @@ use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 
 use super::common::{
-    default_limit, error_result, structured_result, validate_limit,
+    best_match_suffix, default_limit, error_result, search_with_scores, structured_result,
+    validate_limit,
 };
+use crate::core::config::Config;
+use crate::domains::tools::blocking::run_on_thread;
+use crate::domains::tools::timeout::with_timeout;
+use std::sync::Arc;
 
 /// Parameters for work search operations.
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
@@ -50,6 +55,8 @@ pub struct WorkInfo {
     pub work_type: Option<String>,
     pub disambiguation: Option<String>,
     pub language: Option<String>,
+    /// MusicBrainz search relevance (0-100); null when fetched by MBID
+    pub score: Option<u8>,
 }
 
 /// MusicBrainz Work Search Tool implementation.
@@ -133,27 +140,25 @@ impl MbWorkTool {
     }
 
     /// Create a ToolRoute for STDIO/TCP transport.
-    pub fn create_route<S>() -> ToolRoute<S>
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
     where
         S: Send + Sync + 'static,
     {
-        ToolRoute::new_dyn(Self::to_tool(), |ctx: ToolCallContext<'_, S>| {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             async move {
                 let params: MbWorkParams =
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
-                // Use std::thread::spawn to avoid nested runtime panic.
-                // musicbrainz_rs uses reqwest::blocking which creates its own runtime,
-                // so we need a completely separate OS thread.
-                let handle = std::thread::spawn(move || Self::execute(&params));
-
-                let result = handle
-                    .join()
-                    .map_err(|_| McpError::internal_error("Thread panicked".to_string(), None))?;
-
-                Ok(result)
+                // A dedicated thread: musicbrainz_rs and reqwest::blocking start their own runtime
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_on_thread(move || Self::execute(&params)),
+                )
+                .await
             }
             .boxed()
         })
@@ -193,24 +198,26 @@ impl MbWorkTool {
         info!("Searching for works matching: {}", query);
 
         let search_query = WorkSearchQuery::query_builder().work(query).build();
-        let search_result = Work::search(search_query).execute();
+        let search_result = search_with_scores(&Work::search(search_query));
 
         match search_result {
-            Ok(result) => {
-                let works: Vec<_> = result.entities.into_iter().take(limit).collect();
+            Ok(results) => {
+                let works: Vec<_> = results.into_iter().take(limit).collect();
                 if works.is_empty() {
                     return error_result(&format!("No works found for query: {}", query));
                 }
 
                 let count = works.len();
+                let best_score = works.first().and_then(|(_, score)| *score);
                 let work_infos: Vec<WorkInfo> = works
                     .into_iter()
-                    .map(|w| WorkInfo {
+                    .map(|(w, score)| WorkInfo {
                         title: w.title,
                         mbid: w.id,
                         work_type: w.work_type.map(|t| format!("{:?}", t)),
                         disambiguation: w.disambiguation.filter(|d| !d.is_empty()),
                         language: w.language,
+                        score,
                     })
                     .collect();
 
@@ -220,7 +227,12 @@ impl MbWorkTool {
                     query: query.to_string(),
                 };
 
-                let summary = format!("Found {} work(s) matching '{}'", count, query);
+                let summary = format!(
+                    "Found {} work(s) matching '{}'{}",
+                    count,
+                    query,
+                    best_match_suffix(best_score)
+                );
                 structured_result(summary, structured_data)
             }
             Err(e) => {
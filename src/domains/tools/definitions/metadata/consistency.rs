@@ -0,0 +1,691 @@
+//! Album consistency check tool definition.
+//!
+//! Reads the tags of every audio file in an album folder and reports the disagreements
+//! that make players split or misorder an album (different album tags, duplicate track
+//! numbers, ...) before a tagging run.
+
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool},
+};
+
+use futures::FutureExt;
+use lofty::prelude::*;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::definitions::mb::identify_directory::{
+    MAX_DIRECTORY_FILES, MbIdentifyDirectoryTool,
+};
+use crate::domains::tools::timeout::with_timeout;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the album consistency check tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct CheckAlbumConsistencyParams {
+    /// Album directory whose audio files are checked
+    pub directory: String,
+
+    /// Also check audio files in subdirectories, e.g. CD1/CD2 folders (default: false)
+    #[serde(default)]
+    pub recursive: bool,
+}
+
+// ============================================================================
+// Structured Output Types
+// ============================================================================
+
+/// Structured output for an album consistency check.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AlbumConsistencyReport {
+    pub directory: String,
+    pub recursive: bool,
+    pub file_count: usize,
+    /// True when there is no error (warnings allowed)
+    pub consistent: bool,
+    pub error_count: usize,
+    pub warning_count: usize,
+    /// Most common album tag
+    pub album: Option<String>,
+    /// Most common album artist tag
+    pub album_artist: Option<String>,
+    /// Most common year
+    pub year: Option<u32>,
+    /// Violations, errors first
+    pub issues: Vec<ConsistencyIssue>,
+}
+
+/// One violation found in the folder.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ConsistencyIssue {
+    pub category: IssueCategory,
+    pub severity: IssueSeverity,
+    pub message: String,
+    /// Offending files, relative to the directory (empty when no file is at fault, e.g. a
+    /// missing track)
+    pub files: Vec<String>,
+}
+
+/// Kind of violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueCategory {
+    /// The file's tags could not be read
+    Unreadable,
+    /// Files have different album tags
+    AlbumMismatch,
+    /// Files have different album artist tags
+    AlbumArtistMismatch,
+    /// Files have no track number
+    MissingTrackNumber,
+    /// Several files have the same disc and track number
+    DuplicateTrackNumber,
+    /// Track numbers of a disc are not contiguous from 1
+    TrackGap,
+    /// Files of a disc disagree on the track total, or exceed it
+    TrackTotalMismatch,
+    /// Files disagree on the disc total, or exceed it
+    DiscTotalMismatch,
+    /// Files have different years
+    YearMismatch,
+}
+
+/// How serious a violation is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    /// Breaks album grouping or track order in players
+    Error,
+    /// Incomplete or inconsistent, but the album still displays as one
+    Warning,
+}
+
+/// Tags of one file relevant to album consistency.
+#[derive(Debug, Clone, Default)]
+struct AlbumFileTags {
+    file: String,
+    album: Option<String>,
+    album_artist: Option<String>,
+    year: Option<u32>,
+    track: Option<u32>,
+    track_total: Option<u32>,
+    disc: Option<u32>,
+    disc_total: Option<u32>,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Album consistency check tool - reports tag disagreements within an album folder.
+pub struct CheckAlbumConsistencyTool;
+
+impl CheckAlbumConsistencyTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "check_album_consistency";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Check that the audio files of an album folder agree with each other before tagging.\n\
+         \n\
+         Reads every audio file's tags and reports, per category, the files that break the\n\
+         album: different album or album artist tags, missing or duplicate track numbers\n\
+         (errors), and track number gaps, track/disc total disagreements or different years\n\
+         (warnings). Errors make players split or misorder the album; warnings are worth\n\
+         reviewing. Set recursive=true for multi-disc folders (CD1/CD2).";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    #[instrument(skip_all, fields(directory = %params.directory))]
+    pub fn execute(params: &CheckAlbumConsistencyParams, config: &Config) -> CallToolResult {
+        info!("Album consistency check called");
+
+        match Self::check_directory(params, config) {
+            Ok(data) => {
+                let summary = Self::build_text_summary(&data);
+                info!(
+                    "{} error(s), {} warning(s) in {}",
+                    data.error_count, data.warning_count, data.directory
+                );
+                match serde_json::to_value(&data) {
+                    Ok(structured) => CallToolResult {
+                        content: vec![Content::text(summary)],
+                        structured_content: Some(structured),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(e) => {
+                        warn!("Failed to serialize structured content: {}", e);
+                        CallToolResult::success(vec![Content::text(summary)])
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Album consistency check failed: {}", e);
+                CallToolResult::error(vec![Content::text(e)])
+            }
+        }
+    }
+
+    /// Read every audio file of the directory and check them together.
+    fn check_directory(
+        params: &CheckAlbumConsistencyParams,
+        config: &Config,
+    ) -> Result<AlbumConsistencyReport, String> {
+        let directory = validate_path(&params.directory, config)
+            .map_err(|e| format!("Path security validation failed: {}", e))?;
+        if !directory.is_dir() {
+            return Err(format!("Path is not a directory: {}", params.directory));
+        }
+
+        let paths = MbIdentifyDirectoryTool::collect_audio_files(&directory, params.recursive)
+            .map_err(|e| format!("Cannot read directory: {}", e))?;
+        if paths.len() > MAX_DIRECTORY_FILES {
+            return Err(format!(
+                "Directory contains {} audio files (max {} per call); check a subdirectory instead",
+                paths.len(),
+                MAX_DIRECTORY_FILES
+            ));
+        }
+
+        let mut files = Vec::with_capacity(paths.len());
+        let mut unreadable = Vec::new();
+        for path in &paths {
+            let file = path
+                .strip_prefix(&directory)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .into_owned();
+            match Self::read_file_tags(path, config) {
+                Ok(tags) => files.push(AlbumFileTags { file, ..tags }),
+                Err(e) => {
+                    warn!("Cannot read {}: {}", path.display(), e);
+                    unreadable.push(file);
+                }
+            }
+        }
+
+        let mut issues = check_album(&files);
+        if !unreadable.is_empty() {
+            issues.push(ConsistencyIssue {
+                category: IssueCategory::Unreadable,
+                severity: IssueSeverity::Error,
+                message: format!("{} file(s) could not be read", unreadable.len()),
+                files: unreadable,
+            });
+        }
+        issues.sort_by_key(|issue| (issue.severity, issue.category));
+
+        let error_count = issues
+            .iter()
+            .filter(|issue| issue.severity == IssueSeverity::Error)
+            .count();
+        Ok(AlbumConsistencyReport {
+            directory: directory.to_string_lossy().into_owned(),
+            recursive: params.recursive,
+            file_count: paths.len(),
+            consistent: error_count == 0,
+            error_count,
+            warning_count: issues.len() - error_count,
+            album: most_common(files.iter().map(|f| f.album.clone())),
+            album_artist: most_common(files.iter().map(|f| f.album_artist.clone())),
+            year: most_common(files.iter().map(|f| f.year)),
+            issues,
+        })
+    }
+
+    /// Read the album-level tags of a file, from its primary tag or else its first tag.
+    fn read_file_tags(path: &Path, config: &Config) -> Result<AlbumFileTags, String> {
+        let path = validate_path(&path.to_string_lossy(), config)
+            .map_err(|e| format!("Path security validation failed: {}", e))?;
+        let tagged_file = lofty::read_from_path(&path)
+            .map_err(|e| format!("Failed to read audio file: {}", e))?;
+        let Some(tag) = tagged_file
+            .primary_tag()
+            .or_else(|| tagged_file.first_tag())
+        else {
+            return Ok(AlbumFileTags::default());
+        };
+
+        let text = |value: Option<std::borrow::Cow<'_, str>>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Ok(AlbumFileTags {
+            file: String::new(),
+            album: text(tag.album()),
+            album_artist: text(tag.get_string(&ItemKey::AlbumArtist).map(Into::into)),
+            year: tag.year(),
+            track: tag.track(),
+            track_total: tag.track_total(),
+            disc: tag.disk(),
+            disc_total: tag.disk_total(),
+        })
+    }
+
+    /// Build the summary line followed by one line per issue.
+    fn build_text_summary(data: &AlbumConsistencyReport) -> String {
+        if data.file_count == 0 {
+            return format!("No audio files found in {}", data.directory);
+        }
+
+        let mut summary = if data.issues.is_empty() {
+            format!(
+                "{} is consistent ({} file(s))",
+                data.directory, data.file_count
+            )
+        } else {
+            format!(
+                "{}: {} error(s), {} warning(s) across {} file(s)",
+                data.directory, data.error_count, data.warning_count, data.file_count
+            )
+        };
+        for issue in &data.issues {
+            let severity = match issue.severity {
+                IssueSeverity::Error => "error",
+                IssueSeverity::Warning => "warning",
+            };
+            summary.push_str(&format!("\n[{}] {}", severity, issue.message));
+            if !issue.files.is_empty() {
+                summary.push_str(&format!(" ({})", issue.files.join(", ")));
+            }
+        }
+        summary
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: CheckAlbumConsistencyParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Album consistency check (HTTP) called");
+
+        let result = Self::execute(&params, &config);
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<CheckAlbumConsistencyParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: CheckAlbumConsistencyParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// Run every check over the files of one album.
+///
+/// Files without a disc number count as disc 1.
+fn check_album(files: &[AlbumFileTags]) -> Vec<ConsistencyIssue> {
+    let mut issues = Vec::new();
+
+    issues.extend(mismatch(
+        files,
+        IssueCategory::AlbumMismatch,
+        IssueSeverity::Error,
+        "Album",
+        |f| f.album.clone(),
+    ));
+    issues.extend(mismatch(
+        files,
+        IssueCategory::AlbumArtistMismatch,
+        IssueSeverity::Error,
+        "Album artist",
+        |f| f.album_artist.clone(),
+    ));
+    issues.extend(mismatch(
+        files,
+        IssueCategory::YearMismatch,
+        IssueSeverity::Warning,
+        "Year",
+        |f| f.year.map(|y| y.to_string()),
+    ));
+    issues.extend(mismatch(
+        files,
+        IssueCategory::DiscTotalMismatch,
+        IssueSeverity::Warning,
+        "Disc total",
+        |f| f.disc_total.map(|t| t.to_string()),
+    ));
+
+    let beyond_disc_total: Vec<String> = files
+        .iter()
+        .filter(|f| f.disc.zip(f.disc_total).is_some_and(|(d, t)| d > t))
+        .map(|f| f.file.clone())
+        .collect();
+    if !beyond_disc_total.is_empty() {
+        issues.push(ConsistencyIssue {
+            category: IssueCategory::DiscTotalMismatch,
+            severity: IssueSeverity::Warning,
+            message: "Disc number is greater than the disc total".to_string(),
+            files: beyond_disc_total,
+        });
+    }
+
+    let missing_track: Vec<String> = files
+        .iter()
+        .filter(|f| f.track.is_none())
+        .map(|f| f.file.clone())
+        .collect();
+    if !missing_track.is_empty() {
+        issues.push(ConsistencyIssue {
+            category: IssueCategory::MissingTrackNumber,
+            severity: IssueSeverity::Error,
+            message: format!("{} file(s) have no track number", missing_track.len()),
+            files: missing_track,
+        });
+    }
+
+    // Track numbers, per disc
+    let mut discs: BTreeMap<u32, Vec<&AlbumFileTags>> = BTreeMap::new();
+    for file in files {
+        discs.entry(file.disc.unwrap_or(1)).or_default().push(file);
+    }
+    for (disc, disc_files) in &discs {
+        let mut by_track: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+        for file in disc_files {
+            if let Some(track) = file.track {
+                by_track.entry(track).or_default().push(file.file.clone());
+            }
+        }
+
+        for (track, track_files) in &by_track {
+            if track_files.len() > 1 {
+                issues.push(ConsistencyIssue {
+                    category: IssueCategory::DuplicateTrackNumber,
+                    severity: IssueSeverity::Error,
+                    message: format!(
+                        "Disc {} track {} is used by {} files",
+                        disc,
+                        track,
+                        track_files.len()
+                    ),
+                    files: track_files.clone(),
+                });
+            }
+        }
+
+        let disc_files: Vec<AlbumFileTags> = disc_files.iter().map(|f| (*f).clone()).collect();
+        issues.extend(mismatch(
+            &disc_files,
+            IssueCategory::TrackTotalMismatch,
+            IssueSeverity::Warning,
+            &format!("Disc {} track total", disc),
+            |f| f.track_total.map(|t| t.to_string()),
+        ));
+
+        let beyond_track_total: Vec<String> = disc_files
+            .iter()
+            .filter(|f| f.track.zip(f.track_total).is_some_and(|(n, t)| n > t))
+            .map(|f| f.file.clone())
+            .collect();
+        if !beyond_track_total.is_empty() {
+            issues.push(ConsistencyIssue {
+                category: IssueCategory::TrackTotalMismatch,
+                severity: IssueSeverity::Warning,
+                message: format!("Disc {} track number is greater than the track total", disc),
+                files: beyond_track_total,
+            });
+        }
+
+        // Expect 1..=N, N being the highest track number or the track total
+        let last = by_track
+            .keys()
+            .copied()
+            .chain(most_common(disc_files.iter().map(|f| f.track_total)))
+            .max()
+            .unwrap_or(0);
+        let present: BTreeSet<u32> = by_track.keys().copied().collect();
+        let missing: Vec<String> = (1..=last)
+            .filter(|n| !present.contains(n))
+            .map(|n| n.to_string())
+            .collect();
+        if !missing.is_empty() {
+            issues.push(ConsistencyIssue {
+                category: IssueCategory::TrackGap,
+                severity: IssueSeverity::Warning,
+                message: format!(
+                    "Disc {} has no file for track(s) {}",
+                    disc,
+                    missing.join(", ")
+                ),
+                files: Vec::new(),
+            });
+        }
+    }
+
+    issues
+}
+
+/// Report a field whose value differs between files, listing the files that differ from
+/// the most common value. A field missing from every file is not a mismatch.
+fn mismatch(
+    files: &[AlbumFileTags],
+    category: IssueCategory,
+    severity: IssueSeverity,
+    label: &str,
+    value: impl Fn(&AlbumFileTags) -> Option<String>,
+) -> Option<ConsistencyIssue> {
+    let mut groups: BTreeMap<Option<String>, Vec<String>> = BTreeMap::new();
+    for file in files {
+        groups
+            .entry(value(file))
+            .or_default()
+            .push(file.file.clone());
+    }
+    if groups.len() < 2 {
+        return None;
+    }
+
+    let majority = most_common(files.iter().map(&value));
+    let values: Vec<String> = groups
+        .iter()
+        .map(|(value, group)| match value {
+            Some(value) => format!("'{}' ({})", value, group.len()),
+            None => format!("missing ({})", group.len()),
+        })
+        .collect();
+    let files = groups
+        .into_iter()
+        .filter(|(value, _)| *value != majority)
+        .flat_map(|(_, group)| group)
+        .collect();
+
+    Some(ConsistencyIssue {
+        category,
+        severity,
+        message: format!("{} differs between files: {}", label, values.join(", ")),
+        files,
+    })
+}
+
+/// The most frequent present value, the first in order on ties.
+fn most_common<T: Ord>(values: impl Iterator<Item = Option<T>>) -> Option<T> {
+    let mut counts: BTreeMap<T, usize> = BTreeMap::new();
+    for value in values.flatten() {
+        *counts.entry(value).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(value, _)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, album: &str, disc: Option<u32>, track: Option<u32>) -> AlbumFileTags {
+        AlbumFileTags {
+            file: name.to_string(),
+            album: Some(album.to_string()),
+            album_artist: Some("Artist".to_string()),
+            year: Some(1959),
+            track,
+            track_total: Some(3),
+            disc,
+            disc_total: None,
+        }
+    }
+
+    fn categories(issues: &[ConsistencyIssue]) -> Vec<IssueCategory> {
+        issues.iter().map(|issue| issue.category).collect()
+    }
+
+    #[test]
+    fn test_check_album_consistent() {
+        let files = vec![
+            file("01.flac", "Album", None, Some(1)),
+            file("02.flac", "Album", None, Some(2)),
+            file("03.flac", "Album", None, Some(3)),
+        ];
+        assert!(check_album(&files).is_empty());
+    }
+
+    #[test]
+    fn test_check_album_reports_offending_files() {
+        let mut files = vec![
+            file("01.flac", "Album", None, Some(1)),
+            file("02.flac", "Album", None, Some(1)),
+            file("03.flac", "Album (Remaster)", None, None),
+        ];
+        files[1].year = Some(2009);
+
+        let issues = check_album(&files);
+        assert_eq!(
+            categories(&issues),
+            vec![
+                IssueCategory::AlbumMismatch,
+                IssueCategory::YearMismatch,
+                IssueCategory::MissingTrackNumber,
+                IssueCategory::DuplicateTrackNumber,
+                IssueCategory::TrackGap,
+            ]
+        );
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+        assert_eq!(issues[0].files, vec!["03.flac"]);
+        assert_eq!(
+            issues[0].message,
+            "Album differs between files: 'Album' (2), 'Album (Remaster)' (1)"
+        );
+        assert_eq!(issues[1].severity, IssueSeverity::Warning);
+        assert_eq!(issues[1].files, vec!["02.flac"]);
+        assert_eq!(issues[3].files, vec!["01.flac", "02.flac"]);
+        assert_eq!(issues[4].message, "Disc 1 has no file for track(s) 2, 3");
+    }
+
+    #[test]
+    fn test_check_album_discs_and_totals() {
+        let mut files = vec![
+            file("CD1/01.flac", "Album", Some(1), Some(1)),
+            file("CD2/01.flac", "Album", Some(2), Some(1)),
+            file("CD2/04.flac", "Album", Some(2), Some(4)),
+        ];
+        for f in &mut files {
+            f.track_total = Some(1);
+            f.disc_total = Some(2);
+        }
+        files[2].track_total = None;
+        files[2].disc_total = Some(1);
+
+        let issues = check_album(&files);
+        assert_eq!(
+            categories(&issues),
+            vec![
+                IssueCategory::DiscTotalMismatch,
+                IssueCategory::DiscTotalMismatch,
+                IssueCategory::TrackTotalMismatch,
+                IssueCategory::TrackGap,
+            ]
+        );
+        // Track 1 on both discs is not a duplicate
+        assert!(
+            issues
+                .iter()
+                .all(|issue| issue.files != ["CD1/01.flac", "CD2/01.flac"])
+        );
+        assert_eq!(issues[0].files, vec!["CD2/04.flac"]);
+        assert_eq!(
+            issues[1].message,
+            "Disc number is greater than the disc total"
+        );
+        assert_eq!(issues[3].message, "Disc 2 has no file for track(s) 2, 3");
+    }
+
+    #[test]
+    fn test_most_common() {
+        assert_eq!(
+            most_common([Some(2), None, Some(1), Some(2)].into_iter()),
+            Some(2)
+        );
+        assert_eq!(most_common([Some("b"), Some("a")].into_iter()), Some("a"));
+        assert_eq!(most_common([None::<u32>, None].into_iter()), None);
+    }
+
+    #[test]
+    fn test_check_album_consistency_not_a_directory() {
+        let params: CheckAlbumConsistencyParams =
+            serde_json::from_value(serde_json::json!({ "directory": "/nonexistent/album" }))
+                .unwrap();
+        let result = CheckAlbumConsistencyTool::execute(&params, &Config::default());
+        assert!(result.is_error.unwrap_or(false));
+    }
+}
@@ -0,0 +1,530 @@
+//! Metadata diff tool definition.
+//!
+//! Compares the tags and audio properties of two audio files field by field, e.g. two
+//! copies of a track, to decide which tags to keep.
+
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool},
+};
+
+use futures::FutureExt;
+use lofty::prelude::*;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+
+use super::read::{AudioMetadata, AudioProperties, audio_metadata, audio_properties};
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_timeout;
+
+/// Metadata fields left out of the comparison: derived from other fields.
+const SKIPPED_TAG_FIELDS: &[&str] = &["has_lyrics", "total_tags", "custom_tags"];
+
+/// List fields that repeat the single-value field when the tag stores one value.
+const MULTI_VALUE_FIELDS: &[&str] = &["artists", "genres"];
+
+/// Audio properties compared; the other ones are derived from these.
+const PROPERTY_FIELDS: &[&str] = &[
+    "file_type",
+    "duration_ms",
+    "bitrate_kbps",
+    "overall_bitrate_kbps",
+    "sample_rate_hz",
+    "channels",
+    "bit_depth",
+];
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the metadata diff tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MetadataDiffParams {
+    /// First audio file ("A")
+    pub path_a: String,
+
+    /// Second audio file ("B")
+    pub path_b: String,
+}
+
+// ============================================================================
+// Structured Output Types
+// ============================================================================
+
+/// Structured output for a metadata diff.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MetadataDiffResult {
+    pub path_a: String,
+    pub path_b: String,
+    /// Tag fields, by read_metadata field name or, for other tags, by their name in the file
+    pub tags: FieldDiff,
+    /// Audio properties (file type, duration, bitrates, sample rate, channels, bit depth)
+    pub properties: FieldDiff,
+}
+
+/// Field-by-field comparison, each list sorted by field name.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct FieldDiff {
+    pub equal: Vec<FieldValue>,
+    pub only_in_a: Vec<FieldValue>,
+    pub only_in_b: Vec<FieldValue>,
+    pub different: Vec<FieldDifference>,
+}
+
+/// A field present in one file, or equal in both.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FieldValue {
+    pub field: String,
+    pub value: Value,
+}
+
+/// A field with a different value in each file.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FieldDifference {
+    pub field: String,
+    pub a: Value,
+    pub b: Value,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Metadata diff tool - compares the tags and properties of two audio files.
+pub struct MetadataDiffTool;
+
+impl MetadataDiffTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "metadata_diff";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Compare the tags and audio properties of two audio files field by field.\n\
+         \n\
+         Returns, for the tags and for the audio properties (duration, bitrate, sample rate,\n\
+         channels, bit depth), the fields that are equal, only in A, only in B, and that\n\
+         differ, with both values. Use it to choose between two copies of a track or to\n\
+         check a re-encode kept its tags.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    #[instrument(skip_all, fields(path_a = %params.path_a, path_b = %params.path_b))]
+    pub fn execute(params: &MetadataDiffParams, config: &Config) -> CallToolResult {
+        info!("Metadata diff called");
+
+        match Self::diff(params, config) {
+            Ok(data) => {
+                let summary = Self::build_text_summary(&data);
+                info!("{}", summary);
+                match serde_json::to_value(&data) {
+                    Ok(structured) => CallToolResult {
+                        content: vec![Content::text(summary)],
+                        structured_content: Some(structured),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(e) => {
+                        warn!("Failed to serialize structured content: {}", e);
+                        CallToolResult::success(vec![Content::text(summary)])
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Metadata diff failed: {}", e);
+                CallToolResult::error(vec![Content::text(e)])
+            }
+        }
+    }
+
+    /// Read both files and compare them.
+    fn diff(params: &MetadataDiffParams, config: &Config) -> Result<MetadataDiffResult, String> {
+        let (metadata_a, properties_a) = Self::read_file(&params.path_a, config)?;
+        let (metadata_b, properties_b) = Self::read_file(&params.path_b, config)?;
+
+        Ok(MetadataDiffResult {
+            path_a: params.path_a.clone(),
+            path_b: params.path_b.clone(),
+            tags: compare(
+                &tag_fields(metadata_a.as_ref()),
+                &tag_fields(metadata_b.as_ref()),
+            ),
+            properties: compare(
+                &property_fields(&properties_a),
+                &property_fields(&properties_b),
+            ),
+        })
+    }
+
+    /// Read the primary tag and the audio properties of a file, like read_metadata.
+    fn read_file(
+        path: &str,
+        config: &Config,
+    ) -> Result<(Option<AudioMetadata>, AudioProperties), String> {
+        let validated = validate_path(path, config)
+            .map_err(|e| format!("Path security validation failed: {}", e))?;
+        if !validated.is_file() {
+            return Err(format!("Path is not a file: {}", path));
+        }
+
+        let tagged_file = lofty::read_from_path(&validated)
+            .map_err(|e| format!("Failed to read audio file {}: {}", path, e))?;
+        Ok((
+            tagged_file.primary_tag().map(audio_metadata),
+            audio_properties(&tagged_file),
+        ))
+    }
+
+    /// Build e.g. "7 fields differ (2 only in A, 1 only in B), 12 equal; B has higher
+    /// bitrate (320 vs 192 kbps)".
+    fn build_text_summary(data: &MetadataDiffResult) -> String {
+        let tags = &data.tags;
+        let differing = tags.different.len() + tags.only_in_a.len() + tags.only_in_b.len();
+        let mut summary = if differing == 0 {
+            format!("Tags are identical ({} fields)", tags.equal.len())
+        } else {
+            format!(
+                "{} field(s) differ ({} only in A, {} only in B), {} equal",
+                differing,
+                tags.only_in_a.len(),
+                tags.only_in_b.len(),
+                tags.equal.len()
+            )
+        };
+
+        let properties_a = property_map(&data.properties, true);
+        let properties_b = property_map(&data.properties, false);
+        let bitrate = |properties: &BTreeMap<&str, &Value>| {
+            properties
+                .get("bitrate_kbps")
+                .or_else(|| properties.get("overall_bitrate_kbps"))
+                .and_then(|value| value.as_u64())
+        };
+        match (bitrate(&properties_a), bitrate(&properties_b)) {
+            (Some(a), Some(b)) if a > b => {
+                summary.push_str(&format!("; A has higher bitrate ({} vs {} kbps)", a, b));
+            }
+            (Some(a), Some(b)) if b > a => {
+                summary.push_str(&format!("; B has higher bitrate ({} vs {} kbps)", b, a));
+            }
+            (Some(a), Some(_)) => summary.push_str(&format!("; same bitrate ({} kbps)", a)),
+            _ => {}
+        }
+
+        let duration = |properties: &BTreeMap<&str, &Value>| {
+            properties
+                .get("duration_ms")
+                .and_then(|value| value.as_u64())
+        };
+        if let (Some(a), Some(b)) = (duration(&properties_a), duration(&properties_b))
+            && a.abs_diff(b) >= 1000
+        {
+            summary.push_str(&format!(
+                "; durations differ by {:.1}s",
+                a.abs_diff(b) as f64 / 1000.0
+            ));
+        }
+
+        let file_types = (
+            properties_a.get("file_type").and_then(|v| v.as_str()),
+            properties_b.get("file_type").and_then(|v| v.as_str()),
+        );
+        if let (Some(a), Some(b)) = file_types
+            && a != b
+        {
+            summary.push_str(&format!("; A is {}, B is {}", a, b));
+        }
+
+        let name = |path: &str| {
+            Path::new(path)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.to_string())
+        };
+        format!(
+            "{}\nA: {}\nB: {}",
+            summary,
+            name(&data.path_a),
+            name(&data.path_b)
+        )
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: MetadataDiffParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Metadata diff (HTTP) called");
+
+        let result = Self::execute(&params, &config);
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<MetadataDiffParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: MetadataDiffParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// Flatten the metadata of a file into its present fields: read_metadata fields, plus
+/// the custom tags by their name.
+fn tag_fields(metadata: Option<&AudioMetadata>) -> BTreeMap<String, Value> {
+    let mut fields = BTreeMap::new();
+    let Some(metadata) = metadata else {
+        return fields;
+    };
+
+    if let Ok(Value::Object(map)) = serde_json::to_value(metadata) {
+        for (field, value) in map {
+            let single = MULTI_VALUE_FIELDS.contains(&field.as_str())
+                && value.as_array().is_some_and(|values| values.len() < 2);
+            if single || SKIPPED_TAG_FIELDS.contains(&field.as_str()) || is_empty(&value) {
+                continue;
+            }
+            fields.insert(field, value);
+        }
+    }
+    for (name, value) in &metadata.custom_tags {
+        fields
+            .entry(name.clone())
+            .or_insert_with(|| Value::String(value.clone()));
+    }
+    fields
+}
+
+/// The compared audio properties of a file that have a value.
+fn property_fields(properties: &AudioProperties) -> BTreeMap<String, Value> {
+    let Ok(Value::Object(map)) = serde_json::to_value(properties) else {
+        return BTreeMap::new();
+    };
+    map.into_iter()
+        .filter(|(field, value)| PROPERTY_FIELDS.contains(&field.as_str()) && !is_empty(value))
+        .collect()
+}
+
+/// Absent values: null, empty string or empty list.
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(text) => text.is_empty(),
+        Value::Array(values) => values.is_empty(),
+        _ => false,
+    }
+}
+
+/// Split the fields of two files into equal, one-sided and different ones.
+fn compare(a: &BTreeMap<String, Value>, b: &BTreeMap<String, Value>) -> FieldDiff {
+    let mut diff = FieldDiff::default();
+    for (field, value_a) in a {
+        match b.get(field) {
+            Some(value_b) if value_a == value_b => diff.equal.push(FieldValue {
+                field: field.clone(),
+                value: value_a.clone(),
+            }),
+            Some(value_b) => diff.different.push(FieldDifference {
+                field: field.clone(),
+                a: value_a.clone(),
+                b: value_b.clone(),
+            }),
+            None => diff.only_in_a.push(FieldValue {
+                field: field.clone(),
+                value: value_a.clone(),
+            }),
+        }
+    }
+    for (field, value_b) in b {
+        if !a.contains_key(field) {
+            diff.only_in_b.push(FieldValue {
+                field: field.clone(),
+                value: value_b.clone(),
+            });
+        }
+    }
+    diff
+}
+
+/// The values of one side of a comparison, by field name.
+fn property_map(diff: &FieldDiff, side_a: bool) -> BTreeMap<&str, &Value> {
+    let one_sided = if side_a {
+        &diff.only_in_a
+    } else {
+        &diff.only_in_b
+    };
+    diff.equal
+        .iter()
+        .chain(one_sided)
+        .map(|field| (field.field.as_str(), &field.value))
+        .chain(diff.different.iter().map(|field| {
+            let value = if side_a { &field.a } else { &field.b };
+            (field.field.as_str(), value)
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lofty::config::WriteOptions;
+    use lofty::tag::{Tag, TagType};
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    /// Minimal FLAC file: signature, a STREAMINFO block (44.1kHz, stereo, 16-bit, no frames)
+    /// and a final PADDING block, tagged with the given title and album.
+    fn write_flac_fixture(path: &Path, title: &str, album: Option<&str>) {
+        let mut bytes = b"fLaC".to_vec();
+        bytes.extend_from_slice(&[0x00, 0, 0, 34]);
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&[0; 6]);
+        bytes.extend_from_slice(&[0x0A, 0xC4, 0x42, 0xF0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&[0x81, 0, 0, 16]);
+        bytes.extend_from_slice(&[0; 16]);
+        std::fs::write(path, bytes).unwrap();
+
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.set_title(title.to_string());
+        tag.set_artist("Artist".to_string());
+        if let Some(album) = album {
+            tag.set_album(album.to_string());
+        }
+        tag.save_to_path(path, WriteOptions::default()).unwrap();
+    }
+
+    fn fields(value: Value) -> BTreeMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn names(values: &[FieldValue]) -> Vec<&str> {
+        values.iter().map(|v| v.field.as_str()).collect()
+    }
+
+    #[test]
+    fn test_compare() {
+        let diff = compare(
+            &fields(json!({ "title": "Song", "artist": "A", "year": 1999 })),
+            &fields(json!({ "title": "Song", "artist": "B", "ISRC": "X" })),
+        );
+        assert_eq!(names(&diff.equal), vec!["title"]);
+        assert_eq!(names(&diff.only_in_a), vec!["year"]);
+        assert_eq!(names(&diff.only_in_b), vec!["ISRC"]);
+        assert_eq!(diff.different.len(), 1);
+        assert_eq!(diff.different[0].field, "artist");
+        assert_eq!(diff.different[0].a, json!("A"));
+        assert_eq!(diff.different[0].b, json!("B"));
+    }
+
+    #[test]
+    fn test_summary_bitrate_and_duration() {
+        let properties = compare(
+            &fields(json!({ "bitrate_kbps": 192, "duration_ms": 200_000, "file_type": "Mpeg" })),
+            &fields(json!({ "bitrate_kbps": 320, "duration_ms": 202_300, "file_type": "Mpeg" })),
+        );
+        let data = MetadataDiffResult {
+            path_a: "/music/a.mp3".to_string(),
+            path_b: "/music/b.mp3".to_string(),
+            tags: compare(
+                &fields(json!({ "title": "Song", "album": "X" })),
+                &fields(json!({ "title": "Song" })),
+            ),
+            properties,
+        };
+        assert_eq!(
+            MetadataDiffTool::build_text_summary(&data),
+            "1 field(s) differ (1 only in A, 0 only in B), 1 equal; B has higher bitrate \
+             (320 vs 192 kbps); durations differ by 2.3s\nA: a.mp3\nB: b.mp3"
+        );
+    }
+
+    #[test]
+    fn test_metadata_diff_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.flac");
+        let b = temp_dir.path().join("b.flac");
+        write_flac_fixture(&a, "Song", Some("Album"));
+        write_flac_fixture(&b, "Song (Remastered)", None);
+
+        let params = MetadataDiffParams {
+            path_a: a.to_string_lossy().into_owned(),
+            path_b: b.to_string_lossy().into_owned(),
+        };
+        let data = MetadataDiffTool::diff(&params, &test_config()).unwrap();
+        assert_eq!(names(&data.tags.equal), vec!["artist"]);
+        assert_eq!(names(&data.tags.only_in_a), vec!["album"]);
+        assert!(data.tags.only_in_b.is_empty());
+        assert_eq!(data.tags.different[0].field, "title");
+        assert!(names(&data.properties.equal).contains(&"sample_rate_hz"));
+    }
+
+    #[test]
+    fn test_metadata_diff_missing_file() {
+        let params = MetadataDiffParams {
+            path_a: "/nonexistent/a.flac".to_string(),
+            path_b: "/nonexistent/b.flac".to_string(),
+        };
+        let result = MetadataDiffTool::execute(&params, &test_config());
+        assert!(result.is_error.unwrap_or(false));
+    }
+}
@@ -0,0 +1,608 @@
+//! Duplicate finder tool definition.
+//!
+//! Scans a library directory tree for audio files with identical contents. Files are
+//! grouped by size first, then by a hash of their first bytes, and only the files still
+//! sharing a group are hashed in full, so most files are never read. Nothing is deleted:
+//! the groups are returned so the caller can decide which copy to keep.
+
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool, ToolAnnotations},
+};
+
+use futures::FutureExt;
+use lofty::prelude::*;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+use crate::domains::tools::definitions::fs::checksum::{Algorithm, hash_file};
+
+use super::report::collect_files;
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_timeout;
+
+/// Default number of directory levels scanned below `directory`.
+const DEFAULT_MAX_DEPTH: usize = 10;
+
+/// Upper bound for `max_depth`.
+const MAX_DEPTH_LIMIT: usize = 32;
+
+/// Default number of audio files compared.
+const DEFAULT_MAX_FILES: usize = 5000;
+
+/// Upper bound for `max_files`.
+const MAX_FILES_LIMIT: usize = 50000;
+
+/// Number of bytes hashed at the start of each file before hashing it in full.
+const PARTIAL_HASH_BYTES: u64 = 64 * 1024;
+
+/// Maximum number of groups listed in the text summary.
+const MAX_SUMMARY_GROUPS: usize = 20;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the duplicate finder tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct FindDuplicatesParams {
+    /// Library directory to scan
+    pub directory: String,
+
+    /// Directory levels scanned below `directory`, 0 for the directory alone
+    /// (default: 10, max: 32)
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+
+    /// Maximum number of audio files compared (default: 5000, max: 50000)
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+
+    /// Also read the title and artist tags of every duplicate (default: false)
+    #[serde(default)]
+    pub include_tags: bool,
+}
+
+fn default_max_depth() -> usize {
+    DEFAULT_MAX_DEPTH
+}
+
+fn default_max_files() -> usize {
+    DEFAULT_MAX_FILES
+}
+
+// ============================================================================
+// Structured Output Types
+// ============================================================================
+
+/// Structured output of the duplicate finder.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DuplicatesReport {
+    pub directory: String,
+    pub max_depth: usize,
+    /// Audio files compared
+    pub file_count: usize,
+    /// True if more than `max_files` audio files were found; only the first were compared
+    pub truncated: bool,
+    /// True if subdirectories deeper than `max_depth` were skipped
+    pub depth_limited: bool,
+    /// Files hashed in full, after grouping by size and partial hash
+    pub hashed_count: usize,
+    pub group_count: usize,
+    /// Files that are extra copies: every file of each group but one
+    pub duplicate_count: usize,
+    /// Bytes used by the extra copies
+    pub wasted_bytes: u64,
+    /// Groups of identical files, most wasted bytes first
+    pub groups: Vec<DuplicateGroup>,
+    /// Files that could not be compared
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Files with identical contents.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DuplicateGroup {
+    /// Size of each file in bytes
+    pub size_bytes: u64,
+    /// SHA-256 of the contents, lowercase hexadecimal
+    pub sha256: String,
+    /// The identical files, in path order
+    pub files: Vec<DuplicateFile>,
+}
+
+/// One file of a duplicate group.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DuplicateFile {
+    /// Path relative to the directory
+    pub path: String,
+    /// With include_tags, the title tag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// With include_tags, the artist tag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Duplicate finder tool - groups audio files with identical contents.
+pub struct FindDuplicatesTool;
+
+impl FindDuplicatesTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "find_duplicates";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Find audio files with identical contents in a music library.\n\
+         \n\
+         Scans the directory tree (up to max_depth levels, max_files audio files) and groups\n\
+         files by size, then by a hash of their first 64 KiB, then by a full SHA-256, so only\n\
+         likely duplicates are read in full. Returns each group of identical files with its\n\
+         size, most wasted space first; include_tags adds the title and artist of each file to\n\
+         help choose which copy to keep. Nothing is deleted: use fs_delete on the copies to\n\
+         remove. Files with the same music but different tags are not identical and are not\n\
+         reported.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    #[instrument(skip_all, fields(directory = %params.directory))]
+    pub fn execute(params: &FindDuplicatesParams, config: &Config) -> CallToolResult {
+        info!("Find duplicates called");
+
+        match Self::find_duplicates(params, config) {
+            Ok(data) => {
+                let summary = Self::build_text_summary(&data);
+                info!(
+                    "{} duplicate group(s) among {} file(s) in {}",
+                    data.group_count, data.file_count, data.directory
+                );
+                match serde_json::to_value(&data) {
+                    Ok(structured) => CallToolResult {
+                        content: vec![Content::text(summary)],
+                        structured_content: Some(structured),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(e) => {
+                        warn!("Failed to serialize structured content: {}", e);
+                        CallToolResult::success(vec![Content::text(summary)])
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Find duplicates failed: {}", e);
+                CallToolResult::error(vec![Content::text(e)])
+            }
+        }
+    }
+
+    /// Scan the directory and narrow its files down to groups of identical contents.
+    fn find_duplicates(
+        params: &FindDuplicatesParams,
+        config: &Config,
+    ) -> Result<DuplicatesReport, String> {
+        let directory = validate_path(&params.directory, config)
+            .map_err(|e| format!("Path security validation failed: {}", e))?;
+        if !directory.is_dir() {
+            return Err(format!("Path is not a directory: {}", params.directory));
+        }
+
+        let max_depth = params.max_depth.min(MAX_DEPTH_LIMIT);
+        let max_files = params.max_files.clamp(1, MAX_FILES_LIMIT);
+        let scan = collect_files(&directory, max_depth, max_files)
+            .map_err(|e| format!("Cannot read directory: {}", e))?;
+        let mut warnings = Vec::new();
+        let relative = |path: &Path| -> String {
+            path.strip_prefix(&directory)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .into_owned()
+        };
+
+        // Stage 1: group by size. The same file reached twice (symlink, hard link) is
+        // kept once, as it takes no extra space.
+        let mut by_size: BTreeMap<u64, Vec<PathBuf>> = BTreeMap::new();
+        let mut seen: HashSet<PathBuf> = HashSet::new();
+        #[cfg(unix)]
+        let mut seen_inodes: HashSet<(u64, u64)> = HashSet::new();
+        for path in &scan.files {
+            let canonical = match validate_path(&path.to_string_lossy(), config) {
+                Ok(canonical) => canonical,
+                Err(e) => {
+                    warnings.push(format!("{}: {}", relative(path), e));
+                    continue;
+                }
+            };
+            let metadata = match std::fs::metadata(&canonical) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    warnings.push(format!("{}: {}", relative(path), e));
+                    continue;
+                }
+            };
+            if !seen.insert(canonical) {
+                continue;
+            }
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::MetadataExt;
+                if !seen_inodes.insert((metadata.dev(), metadata.ino())) {
+                    continue;
+                }
+            }
+            // Empty files are all identical, and of no use to report
+            if metadata.len() > 0 {
+                by_size
+                    .entry(metadata.len())
+                    .or_default()
+                    .push(path.clone());
+            }
+        }
+
+        // Stage 2: within each size, group by a hash of the first bytes. Stage 3: hash the
+        // files still grouped in full, unless the partial hash already covered them.
+        let mut groups = Vec::new();
+        let mut hashed_count = 0;
+        for (size, paths) in by_size {
+            if paths.len() < 2 {
+                continue;
+            }
+            for (digest, paths) in group_by(paths, partial_hash, &mut warnings, &relative) {
+                hashed_count += paths.len();
+                let full_groups = if size <= PARTIAL_HASH_BYTES {
+                    vec![(digest, paths)]
+                } else {
+                    group_by(paths, full_hash, &mut warnings, &relative)
+                };
+                for (sha256, paths) in full_groups {
+                    let files = paths
+                        .iter()
+                        .map(|path| {
+                            let (title, artist) = if params.include_tags {
+                                read_title_artist(path)
+                            } else {
+                                (None, None)
+                            };
+                            DuplicateFile {
+                                path: relative(path),
+                                title,
+                                artist,
+                            }
+                        })
+                        .collect();
+                    groups.push(DuplicateGroup {
+                        size_bytes: size,
+                        sha256,
+                        files,
+                    });
+                }
+            }
+        }
+        groups.sort_by_key(|group| {
+            (
+                Reverse(group.size_bytes * (group.files.len() as u64 - 1)),
+                group.files[0].path.clone(),
+            )
+        });
+
+        let duplicate_count = groups.iter().map(|group| group.files.len() - 1).sum();
+        let wasted_bytes = groups
+            .iter()
+            .map(|group| group.size_bytes * (group.files.len() as u64 - 1))
+            .sum();
+        Ok(DuplicatesReport {
+            directory: directory.to_string_lossy().into_owned(),
+            max_depth,
+            file_count: scan.files.len(),
+            truncated: scan.truncated,
+            depth_limited: scan.depth_limited,
+            hashed_count,
+            group_count: groups.len(),
+            duplicate_count,
+            wasted_bytes,
+            groups,
+            warnings,
+        })
+    }
+
+    /// Build the summary line followed by one line per group.
+    fn build_text_summary(data: &DuplicatesReport) -> String {
+        if data.file_count == 0 {
+            return format!("No audio files found in {}", data.directory);
+        }
+
+        let mut summary = if data.groups.is_empty() {
+            format!(
+                "{}: no duplicates among {} file(s)",
+                data.directory, data.file_count
+            )
+        } else {
+            format!(
+                "{}: {} group(s) of identical files among {} file(s), {} extra cop(ies) using {} bytes",
+                data.directory,
+                data.group_count,
+                data.file_count,
+                data.duplicate_count,
+                data.wasted_bytes
+            )
+        };
+        if data.truncated {
+            summary.push_str(&format!(
+                "; only the first {} files were compared, raise max_files or scan a subdirectory",
+                data.file_count
+            ));
+        }
+        if data.depth_limited {
+            summary.push_str(&format!(
+                "; folders deeper than {} level(s) were skipped",
+                data.max_depth
+            ));
+        }
+        if !data.warnings.is_empty() {
+            summary.push_str(&format!(
+                "; {} file(s) could not be compared",
+                data.warnings.len()
+            ));
+        }
+        for group in data.groups.iter().take(MAX_SUMMARY_GROUPS) {
+            let paths: Vec<&str> = group.files.iter().map(|f| f.path.as_str()).collect();
+            summary.push_str(&format!(
+                "\n{} bytes: {}",
+                group.size_bytes,
+                paths.join(", ")
+            ));
+        }
+        if data.groups.len() > MAX_SUMMARY_GROUPS {
+            summary.push_str(&format!(
+                "\n... and {} more group(s)",
+                data.groups.len() - MAX_SUMMARY_GROUPS
+            ));
+        }
+        summary
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: FindDuplicatesParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Find duplicates (HTTP) called");
+
+        let result = Self::execute(&params, &config);
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<FindDuplicatesParams>(),
+            annotations: Some(ToolAnnotations::new().read_only(true)),
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: FindDuplicatesParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// Group files by `hash`, keeping the groups of two files or more, in hash order.
+///
+/// Files that cannot be read are left out with a warning.
+fn group_by(
+    paths: Vec<PathBuf>,
+    hash: fn(&Path) -> io::Result<String>,
+    warnings: &mut Vec<String>,
+    relative: &dyn Fn(&Path) -> String,
+) -> Vec<(String, Vec<PathBuf>)> {
+    let mut groups: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for path in paths {
+        match hash(&path) {
+            Ok(digest) => groups.entry(digest).or_default().push(path),
+            Err(e) => {
+                warn!("Cannot hash {}: {}", path.display(), e);
+                warnings.push(format!("{}: {}", relative(&path), e));
+            }
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .collect()
+}
+
+/// SHA-256 of the first [`PARTIAL_HASH_BYTES`] bytes of a file, in hexadecimal.
+fn partial_hash(path: &Path) -> io::Result<String> {
+    let mut file = std::fs::File::open(path)?.take(PARTIAL_HASH_BYTES);
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer)?;
+    Ok(format!("{:x}", Sha256::digest(&buffer)))
+}
+
+/// SHA-256 of a whole file, read in chunks, in hexadecimal.
+fn full_hash(path: &Path) -> io::Result<String> {
+    let (_, mut digests) = hash_file(path, &[Algorithm::Sha256])?;
+    Ok(digests.remove(0))
+}
+
+/// Title and artist of a file's primary tag, or else its first tag.
+fn read_title_artist(path: &Path) -> (Option<String>, Option<String>) {
+    let tagged_file = match lofty::read_from_path(path) {
+        Ok(tagged_file) => tagged_file,
+        Err(e) => {
+            warn!("Cannot read tags of {}: {}", path.display(), e);
+            return (None, None);
+        }
+    };
+    let Some(tag) = tagged_file
+        .primary_tag()
+        .or_else(|| tagged_file.first_tag())
+    else {
+        return (None, None);
+    };
+    let text = |key: &ItemKey| {
+        tag.get_string(key)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    (text(&ItemKey::TrackTitle), text(&ItemKey::TrackArtist))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn params(directory: &Path) -> FindDuplicatesParams {
+        FindDuplicatesParams {
+            directory: directory.to_string_lossy().into_owned(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_files: DEFAULT_MAX_FILES,
+            include_tags: false,
+        }
+    }
+
+    fn group_paths(report: &DuplicatesReport) -> Vec<Vec<&str>> {
+        report
+            .groups
+            .iter()
+            .map(|group| group.files.iter().map(|f| f.path.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("A")).unwrap();
+        std::fs::create_dir_all(root.join("B")).unwrap();
+        // Small files, fully covered by the partial hash
+        std::fs::write(root.join("A/01.flac"), b"same audio").unwrap();
+        std::fs::write(root.join("B/01.flac"), b"same audio").unwrap();
+        std::fs::write(root.join("B/02.flac"), b"diff audio").unwrap();
+        // Large files identical at the start but not at the end
+        let large = vec![7u8; PARTIAL_HASH_BYTES as usize + 10];
+        let mut other = large.clone();
+        *other.last_mut().unwrap() = 8;
+        std::fs::write(root.join("A/02.mp3"), &large).unwrap();
+        std::fs::write(root.join("B/03.mp3"), &large).unwrap();
+        std::fs::write(root.join("B/04.mp3"), &other).unwrap();
+        // Not audio, and empty files
+        std::fs::write(root.join("A/cover.jpg"), b"same audio").unwrap();
+        std::fs::write(root.join("A/05.flac"), b"").unwrap();
+        std::fs::write(root.join("B/05.flac"), b"").unwrap();
+
+        let report =
+            FindDuplicatesTool::find_duplicates(&params(root), &Config::default()).unwrap();
+        assert_eq!(report.file_count, 8);
+        assert_eq!(
+            group_paths(&report),
+            vec![vec!["A/02.mp3", "B/03.mp3"], vec!["A/01.flac", "B/01.flac"]]
+        );
+        assert_eq!(report.groups[1].size_bytes, 10);
+        assert_eq!(
+            report.groups[1].sha256,
+            format!("{:x}", Sha256::digest(b"same audio"))
+        );
+        assert_eq!(report.duplicate_count, 2);
+        assert_eq!(report.wasted_bytes, large.len() as u64 + 10);
+        // The three large files and the two small duplicates
+        assert_eq!(report.hashed_count, 5);
+        // Nothing is deleted
+        assert!(root.join("B/03.mp3").exists());
+    }
+
+    #[test]
+    fn test_find_duplicates_same_file_twice() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("01.flac"), b"audio").unwrap();
+        std::fs::hard_link(root.join("01.flac"), root.join("02.flac")).unwrap();
+
+        let report =
+            FindDuplicatesTool::find_duplicates(&params(root), &Config::default()).unwrap();
+        assert_eq!(report.file_count, 2);
+        #[cfg(unix)]
+        assert!(report.groups.is_empty());
+    }
+
+    #[test]
+    fn test_find_duplicates_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("A")).unwrap();
+        std::fs::write(root.join("01.flac"), b"audio").unwrap();
+        std::fs::write(root.join("02.flac"), b"audio").unwrap();
+        std::fs::write(root.join("A/01.flac"), b"audio").unwrap();
+
+        let mut params = params(root);
+        params.max_files = 2;
+        let report = FindDuplicatesTool::find_duplicates(&params, &Config::default()).unwrap();
+        assert!(report.truncated);
+        assert_eq!(group_paths(&report), vec![vec!["01.flac", "02.flac"]]);
+
+        params.max_files = DEFAULT_MAX_FILES;
+        params.max_depth = 0;
+        let report = FindDuplicatesTool::find_duplicates(&params, &Config::default()).unwrap();
+        assert!(report.depth_limited);
+        assert_eq!(report.file_count, 2);
+
+        params.directory = root.join("01.flac").to_string_lossy().into_owned();
+        let err = FindDuplicatesTool::find_duplicates(&params, &Config::default()).unwrap_err();
+        assert!(err.starts_with("Path is not a directory"));
+    }
+}
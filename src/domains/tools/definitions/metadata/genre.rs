@@ -0,0 +1,140 @@
+//! Genre normalization.
+//!
+//! Maps the many spellings of a genre ("Hip Hop", "hiphop", "Rap/Hip Hop") to one
+//! canonical name. The mapping comes from the JSON file named by
+//! `MCP_METADATA_GENRE_MAP`, or else from a built-in list of common aliases.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::core::config::MetadataConfig;
+
+/// Built-in canonical genres and their aliases, used when no genre map is configured.
+///
+/// Case, spaces and punctuation are ignored when matching, so "hip hop" and "Hip-Hop"
+/// need no alias of their own.
+const DEFAULT_GENRES: &[(&str, &[&str])] = &[
+    ("Hip-Hop", &["Rap/Hip Hop", "Hip Hop/Rap", "Hip-Hop/Rap"]),
+    ("R&B", &["RnB", "R and B", "Rhythm and Blues"]),
+    ("Rock & Roll", &["Rock and Roll", "Rock 'n' Roll"]),
+    ("Drum & Bass", &["Drum and Bass", "Drum 'n' Bass", "DnB"]),
+    ("Singer-Songwriter", &[]),
+    ("Trip-Hop", &[]),
+    ("Post-Rock", &[]),
+    ("Synth-Pop", &[]),
+    ("Lo-Fi", &[]),
+    ("K-Pop", &[]),
+    ("J-Pop", &[]),
+    ("Electronic", &["Electronica"]),
+    ("Soundtrack", &["OST", "Original Soundtrack", "Soundtracks"]),
+];
+
+/// Case-insensitive many-to-one mapping from genre spellings to canonical genres.
+#[derive(Debug, Clone)]
+pub struct GenreMap {
+    /// Canonical genre by matching key of each canonical name and alias
+    canonical: HashMap<String, String>,
+}
+
+impl GenreMap {
+    /// The built-in list of common aliases.
+    pub fn builtin() -> Self {
+        Self::from_entries(DEFAULT_GENRES.iter().map(|(genre, aliases)| {
+            (
+                genre.to_string(),
+                aliases.iter().map(|a| a.to_string()).collect(),
+            )
+        }))
+    }
+
+    /// The configured genre map, or the built-in one when none is configured.
+    pub fn load(config: &MetadataConfig) -> Result<Self, String> {
+        match &config.genre_map_path {
+            Some(path) => Self::from_file(path),
+            None => Ok(Self::builtin()),
+        }
+    }
+
+    /// Read a JSON object whose keys are canonical genres and whose values list their
+    /// aliases, e.g. `{"Hip-Hop": ["Hip Hop", "Rap/Hip Hop"]}`.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read genre map {}: {}", path.display(), e))?;
+        let entries: HashMap<String, Vec<String>> = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid genre map {}: {}", path.display(), e))?;
+        Ok(Self::from_entries(entries))
+    }
+
+    fn from_entries(entries: impl IntoIterator<Item = (String, Vec<String>)>) -> Self {
+        let mut canonical = HashMap::new();
+        for (genre, aliases) in entries {
+            let genre = genre.trim().to_string();
+            for name in aliases.iter().map(String::as_str).chain([genre.as_str()]) {
+                let key = genre_key(name);
+                if !key.is_empty() {
+                    canonical.insert(key, genre.clone());
+                }
+            }
+        }
+        Self { canonical }
+    }
+
+    /// The canonical genre a spelling belongs to, if the map knows it.
+    pub fn canonical(&self, genre: &str) -> Option<&str> {
+        self.canonical.get(&genre_key(genre)).map(String::as_str)
+    }
+
+    /// The canonical spelling of a genre, or the genre trimmed when the map does not know it.
+    pub fn normalize(&self, genre: &str) -> String {
+        self.canonical(genre)
+            .map(str::to_string)
+            .unwrap_or_else(|| genre.trim().to_string())
+    }
+}
+
+/// Key two spellings of the same genre share: lowercase letters and digits only.
+pub fn genre_key(genre: &str) -> String {
+    genre
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_builtin_normalize() {
+        let map = GenreMap::builtin();
+        for spelling in ["Hip Hop", "Hip-Hop", "hiphop", "Rap/Hip Hop", " HIP HOP "] {
+            assert_eq!(map.normalize(spelling), "Hip-Hop", "{}", spelling);
+        }
+        assert_eq!(map.normalize("rnb"), "R&B");
+        assert_eq!(map.normalize(" Shoegaze "), "Shoegaze");
+        assert_eq!(map.canonical("Shoegaze"), None);
+    }
+
+    #[test]
+    fn test_genre_map_from_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("genres.json");
+        std::fs::write(&path, r#"{"Hip Hop": ["Rap"], "Shoegaze": ["Dream Pop"]}"#).unwrap();
+
+        let map = GenreMap::from_file(&path).unwrap();
+        assert_eq!(map.normalize("hip-hop"), "Hip Hop");
+        assert_eq!(map.normalize("RAP"), "Hip Hop");
+        assert_eq!(map.normalize("dream pop"), "Shoegaze");
+        // The configured map replaces the built-in one
+        assert_eq!(map.normalize("rnb"), "rnb");
+
+        std::fs::write(&path, r#"["Hip Hop"]"#).unwrap();
+        assert!(
+            GenreMap::from_file(&path)
+                .unwrap_err()
+                .contains("Invalid genre map")
+        );
+    }
+}
@@ -1,5 +1,22 @@
+pub mod consistency;
+pub mod diff;
+pub mod duplicates;
+pub mod genre;
+pub mod organize;
 pub mod read;
+pub mod rename;
+pub mod report;
+pub mod strip;
 pub mod write;
+pub mod write_batch;
 
+pub use consistency::CheckAlbumConsistencyTool;
+pub use diff::MetadataDiffTool;
+pub use duplicates::FindDuplicatesTool;
+pub use organize::OrganizeLibraryTool;
 pub use read::ReadMetadataTool;
+pub use rename::RenameFromMetadataTool;
+pub use report::MetadataReportTool;
+pub use strip::StripMetadataTool;
 pub use write::WriteMetadataTool;
+pub use write_batch::WriteMetadataBatchTool;
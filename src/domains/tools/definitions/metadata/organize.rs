@@ -0,0 +1,858 @@
+//! Organize library tool definition.
+//!
+//! Moves audio files into an `{Album Artist}/{Album} ({Year})/` hierarchy under a target
+//! root, driven by their tags, optionally with the covers, lyrics and cue sheets next to
+//! them.
+
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool},
+};
+
+use futures::FutureExt;
+use lofty::prelude::*;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::audit;
+use crate::core::config::Config;
+use crate::core::security::{AccessKind, validate_path_for, validate_write_target};
+use crate::domains::tools::definitions::fs::rename::{FsRenameParams, FsRenameTool};
+use crate::domains::tools::definitions::mb::identify_directory::{
+    MAX_DIRECTORY_FILES, MbIdentifyDirectoryTool,
+};
+
+use super::read::is_compilation;
+use super::rename::sanitize_file_name;
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_audited_timeout;
+
+/// Artist folder of compilations.
+pub(super) const VARIOUS_ARTISTS: &str = "Various Artists";
+
+/// Extensions of the files moved along with the audio files when `include_sidecars` is set.
+const SIDECAR_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp", "lrc", "cue"];
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the organize library tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct OrganizeLibraryParams {
+    /// Directory containing the audio files to organize
+    pub source: String,
+
+    /// Existing library root the `{Album Artist}/{Album} ({Year})/` folders are created in
+    pub target_root: String,
+
+    /// Also organize audio files in subdirectories of `source` (default: false)
+    #[serde(default)]
+    pub recursive: bool,
+
+    /// Also move covers and cue sheets of each source folder, and lyrics (.lrc) named
+    /// after an audio file (default: false)
+    #[serde(default)]
+    pub include_sidecars: bool,
+
+    /// If true, return the planned moves without moving or creating anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// ============================================================================
+// Structured Output Types
+// ============================================================================
+
+/// Structured output for an organize library call.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct OrganizeLibraryResult {
+    pub source: String,
+    pub target_root: String,
+    /// False for a dry run
+    pub applied: bool,
+    pub total_count: usize,
+    /// Planned moves for a dry run
+    pub moved_count: usize,
+    pub unchanged_count: usize,
+    pub skipped_count: usize,
+    pub failure_count: usize,
+    /// One entry per audio file
+    pub results: Vec<FileMoveResult>,
+    /// Sidecar files moved (or planned, or skipped) along with the audio files
+    pub sidecars: Vec<FileMoveResult>,
+}
+
+/// Outcome for one file.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FileMoveResult {
+    pub from: String,
+    /// Destination (planned destination for a dry run)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    pub status: MoveStatus,
+    /// Why the file was skipped or failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// What happened to a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MoveStatus {
+    /// Moved (or would be, for a dry run)
+    Moved,
+    /// The file is already at its destination
+    Unchanged,
+    /// Missing tags or destination taken; the file was left in place
+    Skipped,
+    /// The file could not be read or moved
+    Failed,
+}
+
+/// Tags that decide where an album goes.
+#[derive(Debug, Clone, Default)]
+struct AlbumTags {
+    album_artist: Option<String>,
+    artist: Option<String>,
+    album: Option<String>,
+    year: Option<u32>,
+    compilation: bool,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Organize library tool - moves audio files into Artist/Album folders.
+pub struct OrganizeLibraryTool;
+
+impl OrganizeLibraryTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "organize_library";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Move the audio files of a directory into an \"{Album Artist}/{Album} ({Year})/\" hierarchy under a library root, from their tags.\n\
+         \n\
+         Folders are created as needed and file names are kept. Compilations (compilation\n\
+         flag, or album artist \"Various Artists\") go under \"Various Artists\"; without an\n\
+         album artist the track artist is used. Files without album or artist tags, or\n\
+         whose destination exists, are skipped with the reason. Set include_sidecars=true\n\
+         to move covers, cue sheets and .lrc lyrics along. Use dry_run=true to get the\n\
+         planned moves first.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
+    #[instrument(skip_all, fields(source = %params.source, target_root = %params.target_root))]
+    pub fn execute(params: &OrganizeLibraryParams, config: &Config) -> CallToolResult {
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
+
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &OrganizeLibraryParams) -> Vec<&str> {
+        vec![params.source.as_str(), params.target_root.as_str()]
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &OrganizeLibraryParams, config: &Config) -> CallToolResult {
+        info!("Organize library called");
+
+        match Self::organize(params, config) {
+            Ok(data) => {
+                let summary = Self::build_text_summary(&data);
+                info!(
+                    "{} of {} file(s) moved ({} skipped, {} failed)",
+                    data.moved_count, data.total_count, data.skipped_count, data.failure_count
+                );
+                match serde_json::to_value(&data) {
+                    Ok(structured) => CallToolResult {
+                        content: vec![Content::text(summary)],
+                        structured_content: Some(structured),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(e) => {
+                        warn!("Failed to serialize structured content: {}", e);
+                        CallToolResult::success(vec![Content::text(summary)])
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Organize library failed: {}", e);
+                CallToolResult::error(vec![Content::text(e)])
+            }
+        }
+    }
+
+    /// Plan and perform every move sequentially, audio files first, then sidecars.
+    ///
+    /// Per-file failures are recorded in the results; only invalid parameters abort.
+    fn organize(
+        params: &OrganizeLibraryParams,
+        config: &Config,
+    ) -> Result<OrganizeLibraryResult, String> {
+        let source = validate_path_for(&params.source, AccessKind::Write, config)
+            .map_err(|e| format!("Source path security validation failed: {}", e))?;
+        if !source.is_dir() {
+            return Err(format!("{} is not a directory", source.display()));
+        }
+        let target_root = validate_path_for(&params.target_root, AccessKind::Write, config)
+            .map_err(|e| format!("Target root security validation failed: {}", e))?;
+        if !target_root.is_dir() {
+            return Err(format!("{} is not a directory", target_root.display()));
+        }
+
+        let paths = MbIdentifyDirectoryTool::collect_audio_files(&source, params.recursive)
+            .map_err(|e| format!("Cannot read directory: {}", e))?;
+        if paths.len() > MAX_DIRECTORY_FILES {
+            return Err(format!(
+                "Directory contains {} audio files (max {} per call); organize a subdirectory instead",
+                paths.len(),
+                MAX_DIRECTORY_FILES
+            ));
+        }
+
+        // Destinations given out in this call, so two files never get the same one
+        let mut claimed: HashSet<PathBuf> = HashSet::new();
+        // Destination folders of the audio files of each source folder
+        let mut destinations: BTreeMap<PathBuf, BTreeSet<PathBuf>> = BTreeMap::new();
+        let mut results = Vec::with_capacity(paths.len());
+        for path in &paths {
+            let planned = Self::read_album_tags(path).and_then(|tags| album_dir(&tags));
+            let result = match planned {
+                Ok(dir) => {
+                    let destination = target_root.join(&dir);
+                    if let Some(parent) = path.parent() {
+                        destinations
+                            .entry(parent.to_path_buf())
+                            .or_default()
+                            .insert(destination.clone());
+                    }
+                    Self::move_file(path, &destination, &mut claimed, params.dry_run, config)
+                }
+                Err(PlanError::Skip(reason)) => FileMoveResult {
+                    from: path.to_string_lossy().into_owned(),
+                    to: None,
+                    status: MoveStatus::Skipped,
+                    reason: Some(reason),
+                },
+                Err(PlanError::Fail(e)) => FileMoveResult {
+                    from: path.to_string_lossy().into_owned(),
+                    to: None,
+                    status: MoveStatus::Failed,
+                    reason: Some(e),
+                },
+            };
+            if let (MoveStatus::Failed, Some(reason)) = (result.status, &result.reason) {
+                warn!("Failed to organize {}: {}", result.from, reason);
+            }
+            results.push(result);
+        }
+
+        let mut sidecars = Vec::new();
+        if params.include_sidecars {
+            for (dir, album_dirs) in &destinations {
+                sidecars.extend(Self::move_sidecars(
+                    dir,
+                    album_dirs,
+                    &paths,
+                    &results,
+                    &mut claimed,
+                    params.dry_run,
+                    config,
+                ));
+            }
+        }
+
+        let count = |status: MoveStatus| results.iter().filter(|r| r.status == status).count();
+        Ok(OrganizeLibraryResult {
+            source: source.to_string_lossy().into_owned(),
+            target_root: target_root.to_string_lossy().into_owned(),
+            applied: !params.dry_run,
+            total_count: results.len(),
+            moved_count: count(MoveStatus::Moved),
+            unchanged_count: count(MoveStatus::Unchanged),
+            skipped_count: count(MoveStatus::Skipped),
+            failure_count: count(MoveStatus::Failed),
+            results,
+            sidecars,
+        })
+    }
+
+    /// Read the tags deciding the album folder, from the primary tag or else the first tag.
+    fn read_album_tags(path: &Path) -> Result<AlbumTags, PlanError> {
+        let tagged_file = lofty::read_from_path(path)
+            .map_err(|e| PlanError::Fail(format!("Failed to read audio file: {}", e)))?;
+        let Some(tag) = tagged_file
+            .primary_tag()
+            .or_else(|| tagged_file.first_tag())
+        else {
+            return Ok(AlbumTags::default());
+        };
+
+        let text = |value: Option<std::borrow::Cow<'_, str>>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Ok(AlbumTags {
+            album_artist: text(tag.get_string(&ItemKey::AlbumArtist).map(Into::into)),
+            artist: text(tag.artist()),
+            album: text(tag.album()),
+            year: tag.year(),
+            compilation: is_compilation(tag),
+        })
+    }
+
+    /// Move one file into `dir`, keeping its name, unless the destination is taken.
+    fn move_file(
+        path: &Path,
+        dir: &Path,
+        claimed: &mut HashSet<PathBuf>,
+        dry_run: bool,
+        config: &Config,
+    ) -> FileMoveResult {
+        let from = path.to_string_lossy().into_owned();
+        let Some(file_name) = path.file_name() else {
+            return FileMoveResult {
+                from,
+                to: None,
+                status: MoveStatus::Failed,
+                reason: Some("Path has no file name".to_string()),
+            };
+        };
+        let destination = dir.join(file_name);
+        let to = Some(destination.to_string_lossy().into_owned());
+
+        if destination == path {
+            claimed.insert(destination);
+            return FileMoveResult {
+                from,
+                to,
+                status: MoveStatus::Unchanged,
+                reason: None,
+            };
+        }
+        if claimed.contains(&destination) {
+            return FileMoveResult {
+                from,
+                to,
+                status: MoveStatus::Skipped,
+                reason: Some("Another file of this call goes to the same destination".to_string()),
+            };
+        }
+        if destination.exists() {
+            return FileMoveResult {
+                from,
+                to,
+                status: MoveStatus::Skipped,
+                reason: Some("Destination already exists".to_string()),
+            };
+        }
+        claimed.insert(destination.clone());
+
+        let moved = if dry_run {
+            Ok(())
+        } else {
+            Self::create_dir(dir, config).and_then(|created| {
+                let to = destination.to_string_lossy();
+                let moved =
+                    FsRenameTool::rename_path(&from, &to, &FsRenameParams::default(), config);
+                // Leave no empty folder behind when the file stays where it was
+                if moved.is_err() {
+                    for created in created.iter().rev() {
+                        let _ = std::fs::remove_dir(created);
+                    }
+                }
+                moved.map(|_| ())
+            })
+        };
+        match moved {
+            Ok(()) => FileMoveResult {
+                from,
+                to,
+                status: MoveStatus::Moved,
+                reason: None,
+            },
+            Err(e) => FileMoveResult {
+                from,
+                to,
+                status: MoveStatus::Failed,
+                reason: Some(e),
+            },
+        }
+    }
+
+    /// Create `dir` once it passed the path security checks, returning the folders created,
+    /// outermost first.
+    fn create_dir(dir: &Path, config: &Config) -> Result<Vec<PathBuf>, String> {
+        let dir = validate_write_target(&dir.to_string_lossy(), config)
+            .map_err(|e| format!("Path security validation failed: {}", e))?;
+        let mut created: Vec<PathBuf> = dir
+            .ancestors()
+            .take_while(|ancestor| !ancestor.exists())
+            .map(Path::to_path_buf)
+            .collect();
+        created.reverse();
+        for (index, folder) in created.iter().enumerate() {
+            if let Err(e) = std::fs::create_dir(folder) {
+                for created in created[..index].iter().rev() {
+                    let _ = std::fs::remove_dir(created);
+                }
+                return Err(format!("Cannot create {}: {}", dir.display(), e));
+            }
+        }
+        Ok(created)
+    }
+
+    /// Move the sidecar files of one source folder.
+    ///
+    /// Lyrics follow the audio file they are named after; covers and cue sheets follow the
+    /// folder's audio files, as long as these all went to the same album folder.
+    fn move_sidecars(
+        dir: &Path,
+        album_dirs: &BTreeSet<PathBuf>,
+        paths: &[PathBuf],
+        results: &[FileMoveResult],
+        claimed: &mut HashSet<PathBuf>,
+        dry_run: bool,
+        config: &Config,
+    ) -> Vec<FileMoveResult> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut sidecar_paths: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && is_sidecar(path))
+            .collect();
+        sidecar_paths.sort();
+
+        let mut sidecars = Vec::new();
+        for sidecar in sidecar_paths {
+            let is_lyrics = sidecar
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("lrc"));
+            let destination = if is_lyrics {
+                // Folder of the audio file with the same name
+                paths
+                    .iter()
+                    .zip(results)
+                    .find(|(path, result)| {
+                        path.with_extension("") == sidecar.with_extension("")
+                            && matches!(result.status, MoveStatus::Moved | MoveStatus::Unchanged)
+                    })
+                    .and_then(|(_, result)| result.to.as_ref())
+                    .and_then(|to| Path::new(to).parent().map(Path::to_path_buf))
+            } else if album_dirs.len() == 1 {
+                album_dirs.first().cloned()
+            } else {
+                sidecars.push(FileMoveResult {
+                    from: sidecar.to_string_lossy().into_owned(),
+                    to: None,
+                    status: MoveStatus::Skipped,
+                    reason: Some(format!(
+                        "The audio files of this folder go to {} albums",
+                        album_dirs.len()
+                    )),
+                });
+                continue;
+            };
+
+            let Some(destination) = destination else {
+                continue;
+            };
+            let result = Self::move_file(&sidecar, &destination, claimed, dry_run, config);
+            if result.status != MoveStatus::Unchanged {
+                sidecars.push(result);
+            }
+        }
+        sidecars
+    }
+
+    /// Build the summary line followed by one line per moved, skipped or failed file.
+    fn build_text_summary(data: &OrganizeLibraryResult) -> String {
+        if data.total_count == 0 {
+            return format!("No audio files found in {}", data.source);
+        }
+
+        let counts = format!(
+            "{} unchanged, {} skipped, {} failed; {} sidecar file(s)",
+            data.unchanged_count,
+            data.skipped_count,
+            data.failure_count,
+            data.sidecars
+                .iter()
+                .filter(|s| s.status == MoveStatus::Moved)
+                .count()
+        );
+        let mut summary = if data.applied {
+            format!(
+                "Moved {}/{} file(s) into {} ({})",
+                data.moved_count, data.total_count, data.target_root, counts
+            )
+        } else {
+            format!(
+                "DRY RUN: {}/{} file(s) would be moved into {} ({}); nothing was moved",
+                data.moved_count, data.total_count, data.target_root, counts
+            )
+        };
+
+        let relative = |path: &str, root: &str| {
+            Path::new(path)
+                .strip_prefix(root)
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_else(|_| path.to_string())
+        };
+        for result in data.results.iter().chain(&data.sidecars) {
+            let from = relative(&result.from, &data.source);
+            let line = match (result.status, &result.to, &result.reason) {
+                (MoveStatus::Moved, Some(to), _) => {
+                    format!("{} -> {}", from, relative(to, &data.target_root))
+                }
+                (MoveStatus::Skipped, _, Some(reason)) => format!("skipped {}: {}", from, reason),
+                (MoveStatus::Failed, _, Some(reason)) => format!("failed {}: {}", from, reason),
+                _ => continue,
+            };
+            summary.push('\n');
+            summary.push_str(&line);
+        }
+        summary
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: OrganizeLibraryParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Organize library (HTTP) called");
+
+        let result = Self::execute(&params, &config);
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<OrganizeLibraryParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: OrganizeLibraryParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// Why a file has no destination.
+enum PlanError {
+    /// Missing tags: the file is left in place
+    Skip(String),
+    /// The file could not be read
+    Fail(String),
+}
+
+/// The `{Album Artist}/{Album} ({Year})` folder of a file, relative to the target root.
+///
+/// Compilations go under "Various Artists"; without an album artist the track artist is
+/// used. The year suffix is left out when there is no year.
+fn album_dir(tags: &AlbumTags) -> Result<PathBuf, PlanError> {
+    let various = tags.compilation
+        || tags
+            .album_artist
+            .as_deref()
+            .is_some_and(|artist| artist.eq_ignore_ascii_case(VARIOUS_ARTISTS));
+    let artist = if various {
+        VARIOUS_ARTISTS.to_string()
+    } else {
+        match tags.album_artist.as_ref().or(tags.artist.as_ref()) {
+            Some(artist) => sanitize_file_name(artist),
+            None => {
+                return Err(PlanError::Skip(
+                    "Missing album artist and artist tags".to_string(),
+                ));
+            }
+        }
+    };
+    let album = match &tags.album {
+        Some(album) => match tags.year {
+            Some(year) => sanitize_file_name(&format!("{} ({})", album, year)),
+            None => sanitize_file_name(album),
+        },
+        None => return Err(PlanError::Skip("Missing album tag".to_string())),
+    };
+
+    if artist.is_empty() || album.is_empty() {
+        return Err(PlanError::Skip(
+            "Album or artist tag has no usable characters for a folder name".to_string(),
+        ));
+    }
+    Ok(Path::new(&artist).join(album))
+}
+
+/// Check whether the path is a cover, cue sheet or lyrics file.
+fn is_sidecar(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SIDECAR_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lofty::config::WriteOptions;
+    use lofty::tag::{Tag, TagType};
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    /// Minimal FLAC file: signature, a STREAMINFO block (44.1kHz, stereo, 16-bit, no frames)
+    /// and a final PADDING block, tagged with the given artist and album.
+    fn write_flac_fixture(path: &Path, artist: &str, album: Option<&str>) {
+        let mut bytes = b"fLaC".to_vec();
+        bytes.extend_from_slice(&[0x00, 0, 0, 34]);
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&[0; 6]);
+        bytes.extend_from_slice(&[0x0A, 0xC4, 0x42, 0xF0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&[0x81, 0, 0, 16]);
+        bytes.extend_from_slice(&[0; 16]);
+        std::fs::write(path, bytes).unwrap();
+
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.set_artist(artist.to_string());
+        if let Some(album) = album {
+            tag.set_album(album.to_string());
+        }
+        tag.set_year(1997);
+        tag.save_to_path(path, WriteOptions::default()).unwrap();
+    }
+
+    fn tags(album_artist: Option<&str>, artist: Option<&str>, album: Option<&str>) -> AlbumTags {
+        AlbumTags {
+            album_artist: album_artist.map(str::to_string),
+            artist: artist.map(str::to_string),
+            album: album.map(str::to_string),
+            year: None,
+            compilation: false,
+        }
+    }
+
+    fn skip_reason(result: Result<PathBuf, PlanError>) -> String {
+        match result {
+            Err(PlanError::Skip(reason)) => reason,
+            _ => panic!("expected a skipped file"),
+        }
+    }
+
+    #[test]
+    fn test_album_dir() {
+        let mut album = tags(Some("AC/DC"), Some("Bon Scott"), Some("Highway to Hell"));
+        album.year = Some(1979);
+        assert_eq!(
+            album_dir(&album).ok(),
+            Some(PathBuf::from("AC_DC/Highway to Hell (1979)"))
+        );
+
+        // Falls back to the track artist, without a year suffix
+        let album = tags(None, Some("Radiohead"), Some("OK Computer"));
+        assert_eq!(
+            album_dir(&album).ok(),
+            Some(PathBuf::from("Radiohead/OK Computer"))
+        );
+
+        let mut compilation = tags(None, Some("Blur"), Some("Now 34"));
+        compilation.compilation = true;
+        assert_eq!(
+            album_dir(&compilation).ok(),
+            Some(PathBuf::from("Various Artists/Now 34"))
+        );
+        let various = tags(Some("various artists"), Some("Blur"), Some("Now 34"));
+        assert_eq!(
+            album_dir(&various).ok(),
+            Some(PathBuf::from("Various Artists/Now 34"))
+        );
+
+        assert_eq!(
+            skip_reason(album_dir(&tags(None, Some("Blur"), None))),
+            "Missing album tag"
+        );
+        assert_eq!(
+            skip_reason(album_dir(&tags(None, None, Some("Album")))),
+            "Missing album artist and artist tags"
+        );
+        assert!(skip_reason(album_dir(&tags(None, Some("..."), Some("Album")))).contains("usable"));
+    }
+
+    #[test]
+    fn test_organize_dry_run_then_apply() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("incoming");
+        let library = temp_dir.path().join("library");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::create_dir_all(&library).unwrap();
+        write_flac_fixture(&source.join("01.flac"), "Radiohead", Some("OK Computer"));
+        write_flac_fixture(&source.join("02.flac"), "Radiohead", None);
+        std::fs::write(source.join("cover.jpg"), b"jpeg").unwrap();
+        std::fs::write(source.join("01.lrc"), b"[00:00.00]").unwrap();
+        std::fs::write(source.join("02.lrc"), b"[00:00.00]").unwrap();
+
+        let dry_run: OrganizeLibraryParams = serde_json::from_value(serde_json::json!({
+            "source": source.to_string_lossy(),
+            "target_root": library.to_string_lossy(),
+            "include_sidecars": true,
+            "dry_run": true
+        }))
+        .unwrap();
+        let data = OrganizeLibraryTool::organize(&dry_run, &test_config()).unwrap();
+        assert!(!data.applied);
+        assert_eq!(data.moved_count, 1);
+        assert_eq!(data.skipped_count, 1);
+        assert_eq!(data.results[1].reason.as_deref(), Some("Missing album tag"));
+        // 02.lrc stays with its skipped audio file
+        assert_eq!(data.sidecars.len(), 2);
+        assert!(!library.join("Radiohead").exists());
+
+        let apply = OrganizeLibraryParams {
+            dry_run: false,
+            ..dry_run
+        };
+        let data = OrganizeLibraryTool::organize(&apply, &test_config()).unwrap();
+        assert!(data.applied);
+        let album = library.join("Radiohead/OK Computer (1997)");
+        assert!(album.join("01.flac").exists());
+        assert!(album.join("01.lrc").exists());
+        assert!(album.join("cover.jpg").exists());
+        assert!(source.join("02.flac").exists());
+        assert!(source.join("02.lrc").exists());
+    }
+
+    #[test]
+    fn test_organize_skips_taken_destination() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("incoming");
+        let album = temp_dir.path().join("library/Blur/Blur (1997)");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::create_dir_all(&album).unwrap();
+        write_flac_fixture(&source.join("song.flac"), "Blur", Some("Blur"));
+        std::fs::write(album.join("song.flac"), b"existing").unwrap();
+
+        let params: OrganizeLibraryParams = serde_json::from_value(serde_json::json!({
+            "source": source.to_string_lossy(),
+            "target_root": temp_dir.path().join("library").to_string_lossy()
+        }))
+        .unwrap();
+        let result = OrganizeLibraryTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false));
+
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["skipped_count"], 1);
+        assert_eq!(
+            structured["results"][0]["reason"],
+            "Destination already exists"
+        );
+        assert_eq!(std::fs::read(album.join("song.flac")).unwrap(), b"existing");
+    }
+
+    #[test]
+    fn test_organize_checks_folders_before_creating_them() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("incoming");
+        let library = temp_dir.path().join("library");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::create_dir_all(&library).unwrap();
+        write_flac_fixture(&source.join("01.flac"), "Radiohead", Some("OK Computer"));
+
+        let params: OrganizeLibraryParams = serde_json::from_value(serde_json::json!({
+            "source": source.to_string_lossy(),
+            "target_root": library.to_string_lossy()
+        }))
+        .unwrap();
+
+        // A denied album folder is never created
+        let mut config = test_config();
+        config.security.deny_patterns = vec!["Radiohead/*".to_string()];
+        let data = OrganizeLibraryTool::organize(&params, &config).unwrap();
+        assert_eq!(data.results[0].status, MoveStatus::Failed);
+        assert!(!library.join("Radiohead").exists());
+
+        // Folders created for a move that fails are removed
+        config.security.deny_patterns = vec!["OK Computer*/*.flac".to_string()];
+        let data = OrganizeLibraryTool::organize(&params, &config).unwrap();
+        assert_eq!(data.results[0].status, MoveStatus::Failed);
+        assert!(!library.join("Radiohead").exists());
+        assert!(source.join("01.flac").exists());
+    }
+
+    #[test]
+    fn test_organize_requires_existing_target_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let params: OrganizeLibraryParams = serde_json::from_value(serde_json::json!({
+            "source": temp_dir.path().to_string_lossy(),
+            "target_root": "/nonexistent/library"
+        }))
+        .unwrap();
+        let result = OrganizeLibraryTool::execute(&params, &test_config());
+        assert!(result.is_error.unwrap_or(false));
+    }
+}
@@ -1,6 +1,7 @@
 //! Read metadata tool definition.
 //!
-//! A tool that reads audio file metadata (ID3 tags, etc.) using lofty.
+//! A tool that reads audio file metadata (ID3 tags, etc.) using lofty, for a single file
+//! or as a compact table for every audio file in a directory.
 
 use rmcp::{
     ErrorData as McpError,
@@ -9,30 +10,109 @@ use rmcp::{
 };
 
 use futures::FutureExt;
+use lofty::file::TaggedFile;
+use lofty::picture::{MimeType, PictureInformation, PictureType};
 use lofty::prelude::*;
+use lofty::tag::{ItemKey, ItemValue, Tag};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
 use std::sync::Arc;
 use tracing::{info, instrument, warn};
 
+use crate::core::audit;
 use crate::core::config::Config;
-use crate::core::security::validate_path;
+use crate::core::security::{validate_path, validate_write_target};
+use crate::domains::tools::definitions::fs::files::{is_identical_file, write_atomic};
+use crate::domains::tools::definitions::mb::identify_directory::MbIdentifyDirectoryTool;
+
+use super::write::{MP4_FREEFORM_PREFIX, release_country_key};
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::{with_audited_timeout, with_timeout};
 
 // ============================================================================
 // Tool Parameters
 // ============================================================================
 
 /// Parameters for the read metadata tool.
-#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ReadMetadataParams {
-    /// Path to the audio file to read.
+    /// Path to the audio file to read, or to a directory to read every audio file in it
+    /// as a compact table.
     pub path: String,
 
-    /// Include technical audio properties (bitrate, sample rate, duration)
+    /// When `path` is a directory, also read its subdirectories (default: false)
     #[serde(default)]
-    pub include_properties: bool,
+    pub recursive: bool,
+
+    /// When `path` is a directory, read at most this many files (default: 200, max: 1000)
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+
+    /// Write the embedded front cover to this file or directory (`cover.<ext>` in a directory);
+    /// the extension is set from the image type
+    pub extract_cover_to: Option<String>,
+
+    /// Also return every item of every tag in the file under `raw_tags`, including fields
+    /// without a dedicated output field
+    #[serde(default)]
+    pub include_all_tags: bool,
+}
+
+fn default_max_files() -> usize {
+    DEFAULT_MAX_FILES
 }
 
+/// Default number of files read in directory mode.
+const DEFAULT_MAX_FILES: usize = 200;
+
+/// Upper bound for `max_files`.
+const MAX_FILES_LIMIT: usize = 1000;
+
+/// Maximum number of characters of lyrics included in `lyrics_preview`.
+const LYRICS_PREVIEW_CHARS: usize = 200;
+
+/// Number of leading bytes shown in hex for binary values in `raw_tags`.
+const BINARY_PREVIEW_BYTES: usize = 16;
+
+/// Keys reported by dedicated `AudioMetadata` fields, left out of `custom_tags`.
+const DEDICATED_KEYS: &[ItemKey] = &[
+    ItemKey::TrackTitle,
+    ItemKey::TrackArtist,
+    ItemKey::AlbumTitle,
+    ItemKey::AlbumArtist,
+    ItemKey::FlagCompilation,
+    ItemKey::Year,
+    ItemKey::OriginalReleaseDate,
+    ItemKey::OriginalMediaType,
+    ItemKey::TrackNumber,
+    ItemKey::TrackTotal,
+    ItemKey::DiscNumber,
+    ItemKey::DiscTotal,
+    ItemKey::Genre,
+    ItemKey::Comment,
+    ItemKey::Composer,
+    ItemKey::Conductor,
+    ItemKey::Lyricist,
+    ItemKey::Remixer,
+    ItemKey::Performer,
+    ItemKey::TrackArtistSortOrder,
+    ItemKey::AlbumArtistSortOrder,
+    ItemKey::AlbumTitleSortOrder,
+    ItemKey::Bpm,
+    ItemKey::IntegerBpm,
+    ItemKey::ReplayGainTrackGain,
+    ItemKey::ReplayGainTrackPeak,
+    ItemKey::ReplayGainAlbumGain,
+    ItemKey::ReplayGainAlbumPeak,
+    ItemKey::Lyrics,
+    ItemKey::MusicBrainzRecordingId,
+    ItemKey::MusicBrainzReleaseId,
+    ItemKey::MusicBrainzArtistId,
+    ItemKey::MusicBrainzReleaseGroupId,
+];
+
 // ============================================================================
 // Structured Output Types
 // ============================================================================
@@ -43,7 +123,87 @@ pub struct MetadataReadResult {
     pub file: String,
     pub format: String,
     pub metadata: Option<AudioMetadata>,
-    pub properties: Option<AudioProperties>,
+    /// Technical properties of the audio stream
+    pub audio_properties: AudioProperties,
+    /// Cover art written by `extract_cover_to`
+    pub extracted_cover: Option<ExtractedCover>,
+    /// Every tag item in the file, only set with `include_all_tags`
+    pub raw_tags: Option<Vec<RawTagItem>>,
+}
+
+/// A single tag item as stored in the file.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RawTagItem {
+    /// Tag format holding the item, e.g. "Id3v2", "VorbisComments"
+    pub tag_type: String,
+    /// Item name in that format, e.g. "TSRC" or "ISRC"; pictures are "PICTURE:<type>"
+    pub key: String,
+    /// Text value, or a size and hex preview for binary data
+    pub value: String,
+}
+
+/// Embedded picture extracted to a file.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ExtractedCover {
+    pub path: String,
+    pub mime_type: String,
+    /// Picture type in the tag, e.g. "CoverFront"
+    pub picture_type: String,
+    pub size_bytes: usize,
+    /// Dimensions, when they can be read from the image (JPEG, PNG, GIF, BMP)
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// Structured output when `path` is a directory.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DirectoryReadResult {
+    pub directory: String,
+    pub recursive: bool,
+    /// One row per file read, sorted by path
+    pub files: Vec<MetadataRow>,
+    /// Audio files found, including those beyond `max_files`
+    pub total_count: usize,
+    pub read_count: usize,
+    pub error_count: usize,
+    /// True if more than `max_files` audio files were found
+    pub truncated: bool,
+    pub stats: DirectoryStats,
+}
+
+/// Compact summary of one file in a directory read.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MetadataRow {
+    /// Path relative to the directory
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_formatted: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitrate_kbps: Option<u32>,
+    /// Why the file could not be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Aggregate figures over the files read.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DirectoryStats {
+    pub total_duration_seconds: u64,
+    pub total_duration_formatted: String,
+    /// Distinct album names, sorted
+    pub albums: Vec<String>,
+    /// Distinct artist names, sorted
+    pub artists: Vec<String>,
 }
 
 /// Audio metadata tags.
@@ -51,21 +211,73 @@ pub struct MetadataReadResult {
 pub struct AudioMetadata {
     pub title: Option<String>,
     pub artist: Option<String>,
+    /// All artist values, when the tag stores several
+    pub artists: Vec<String>,
     pub album: Option<String>,
     pub album_artist: Option<String>,
+    /// Compilation flag (COMPILATION / TCMP / cpil), when present
+    pub compilation: Option<bool>,
     pub year: Option<u32>,
+    /// Release date of the original edition (ORIGINALDATE / TDOR)
+    pub original_date: Option<String>,
+    /// Country of the release (RELEASECOUNTRY)
+    pub release_country: Option<String>,
+    /// Medium format, e.g. "CD" (MEDIA / TMED)
+    pub media: Option<String>,
     pub track: Option<u32>,
+    pub track_total: Option<u32>,
+    pub disc: Option<u32>,
+    pub disc_total: Option<u32>,
     pub genre: Option<String>,
+    /// All genre values, when the tag stores several
+    pub genres: Vec<String>,
     pub comment: Option<String>,
+    pub composer: Option<String>,
+    pub conductor: Option<String>,
+    pub lyricist: Option<String>,
+    pub remixer: Option<String>,
+    pub performers: Vec<String>,
+    /// Artist name used for sorting (ARTISTSORT / TSOP)
+    pub artist_sort: Option<String>,
+    /// Album artist name used for sorting (ALBUMARTISTSORT / TSO2)
+    pub album_artist_sort: Option<String>,
+    /// Album title used for sorting (ALBUMSORT / TSOA)
+    pub album_sort: Option<String>,
+    /// Beats per minute (BPM / TBPM), rounded
+    pub bpm: Option<u32>,
+    /// ReplayGain values as stored, e.g. "-6.50 dB" (REPLAYGAIN_TRACK_GAIN)
+    pub replaygain_track_gain: Option<String>,
+    pub replaygain_track_peak: Option<String>,
+    pub replaygain_album_gain: Option<String>,
+    pub replaygain_album_peak: Option<String>,
+    pub has_lyrics: bool,
+    /// First lines of the unsynchronized lyrics, truncated
+    pub lyrics_preview: Option<String>,
+    /// MusicBrainz recording ID (MUSICBRAINZ_TRACKID)
+    pub mb_recording_id: Option<String>,
+    /// MusicBrainz release ID (MUSICBRAINZ_ALBUMID)
+    pub mb_release_id: Option<String>,
+    /// MusicBrainz artist ID (MUSICBRAINZ_ARTISTID)
+    pub mb_artist_id: Option<String>,
+    /// MusicBrainz release group ID (MUSICBRAINZ_RELEASEGROUPID)
+    pub mb_release_group_id: Option<String>,
+    /// All other text tags by their name in the file's tag format (several values joined with "; ")
+    pub custom_tags: BTreeMap<String, String>,
     pub total_tags: u32,
 }
 
 /// Audio technical properties.
 #[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct AudioProperties {
+    /// Detected file type, e.g. "Flac", "Mpeg"
+    pub file_type: String,
     pub duration_seconds: Option<u64>,
+    pub duration_ms: u64,
     pub duration_formatted: Option<String>,
+    /// Audio stream bitrate
     pub bitrate_kbps: Option<u32>,
+    /// Bitrate of the whole file, including tags and container overhead
+    pub overall_bitrate_kbps: Option<u32>,
     pub sample_rate_hz: Option<u32>,
     pub channels: Option<u8>,
     pub channel_description: Option<String>,
@@ -84,11 +296,31 @@ impl ReadMetadataTool {
     pub const NAME: &'static str = "read_metadata";
 
     /// Tool description shown to clients.
-    pub const DESCRIPTION: &'static str = "Read metadata from audio files (MP3, FLAC, M4A, etc.). Returns tags like artist, album, title, year, and optionally technical properties.";
+    pub const DESCRIPTION: &'static str = "Read metadata from audio files (MP3, FLAC, M4A, etc.). Returns tags like artist, album, title, year, and technical properties. Pass a directory as path to get one compact row per audio file (title, artist, album, track, duration, bitrate) with total duration and distinct albums/artists.";
 
     /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    ///
+    /// A call extracting the cover art writes a file, so it is recorded in the audit log,
+    /// if enabled, before its result is returned.
     #[instrument(skip_all, fields(path = %params.path))]
     pub fn execute(params: &ReadMetadataParams, config: &Config) -> CallToolResult {
+        let result = Self::run(params, config);
+        if params.extract_cover_to.is_some() {
+            let paths = Self::audited_paths(params);
+            audit::record(config, Self::NAME, params, &paths, &result);
+        }
+        result
+    }
+
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &ReadMetadataParams) -> Vec<&str> {
+        std::iter::once(params.path.as_str())
+            .chain(params.extract_cover_to.as_deref())
+            .collect()
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &ReadMetadataParams, config: &Config) -> CallToolResult {
         info!("Read metadata tool called for path: {}", params.path);
 
         // Validate path security first
@@ -103,6 +335,10 @@ impl ReadMetadataTool {
             }
         };
 
+        if path.is_dir() {
+            return Self::execute_directory(params, &path, config);
+        }
+
         // Validate it's a file
         if !path.is_file() {
             warn!("Path is not a file: {}", params.path);
@@ -127,75 +363,55 @@ impl ReadMetadataTool {
         let format_str = format!("{:?}", tagged_file.file_type());
 
         // Build metadata structure
-        let metadata = tagged_file.primary_tag().map(|tag| {
-            AudioMetadata {
-                title: tag.title().map(|s| s.to_string()),
-                artist: tag.artist().map(|s| s.to_string()),
-                album: tag.album().map(|s| s.to_string()),
-                album_artist: tag.get_string(&lofty::tag::ItemKey::AlbumArtist).map(|s| s.to_string()),
-                year: tag.year(),
-                track: tag.track(),
-                genre: tag.genre().map(|s| s.to_string()),
-                comment: tag.comment().map(|s| s.to_string()),
-                total_tags: tag.item_count(),
-            }
-        });
+        let metadata = tagged_file.primary_tag().map(audio_metadata);
 
-        // Build properties structure if requested
-        let properties = if params.include_properties {
-            let props = tagged_file.properties();
-            let duration_secs = props.duration().as_secs();
-            let duration_formatted = if duration_secs > 0 {
-                let minutes = duration_secs / 60;
-                let seconds = duration_secs % 60;
-                Some(format!("{}:{:02}", minutes, seconds))
-            } else {
-                None
-            };
-
-            let channel_desc = props.channels().map(|ch| match ch {
-                1 => "Mono".to_string(),
-                2 => "Stereo".to_string(),
-                _ => "Multi-channel".to_string(),
-            });
+        // Properties come with the tags from the same read, so they are always included
+        let audio_properties = audio_properties(&tagged_file);
 
-            Some(AudioProperties {
-                duration_seconds: Some(duration_secs),
-                duration_formatted,
-                bitrate_kbps: props.audio_bitrate(),
-                sample_rate_hz: props.sample_rate(),
-                channels: props.channels(),
-                channel_description: channel_desc,
-                bit_depth: props.bit_depth(),
-            })
-        } else {
-            None
+        // Extract the cover art if requested
+        let extracted_cover = match params
+            .extract_cover_to
+            .as_deref()
+            .map(|output| Self::extract_cover(&tagged_file, output, config))
+            .transpose()
+        {
+            Ok(extracted_cover) => extracted_cover,
+            Err(e) => {
+                warn!("Failed to extract cover art from {}: {}", params.path, e);
+                return CallToolResult::error(vec![Content::text(format!(
+                    "Failed to extract cover art: {}",
+                    e
+                ))]);
+            }
         };
 
+        let raw_tags = params.include_all_tags.then(|| raw_tags(&tagged_file));
+
         // Build structured result
         let structured_data = MetadataReadResult {
             file: params.path.clone(),
             format: format_str,
             metadata: metadata.clone(),
-            properties: properties.clone(),
+            audio_properties: audio_properties.clone(),
+            extracted_cover: extracted_cover.clone(),
+            raw_tags,
         };
 
         // Build text summary
-        let summary = if let Some(ref meta) = metadata {
+        let mut summary = if let Some(ref meta) = metadata {
             let title = meta.title.as_deref().unwrap_or("Unknown");
             let artist = meta.artist.as_deref().unwrap_or("Unknown Artist");
-            if let Some(ref props) = properties {
-                if let Some(ref duration) = props.duration_formatted {
-                    format!("'{}' by {} ({}, {} tags)", title, artist, duration, meta.total_tags)
-                } else {
-                    format!("'{}' by {} ({} tags)", title, artist, meta.total_tags)
-                }
+            if let Some(ref duration) = audio_properties.duration_formatted {
+                format!("'{}' by {} ({}, {} tags)", title, artist, duration, meta.total_tags)
             } else {
                 format!("'{}' by {} ({} tags)", title, artist, meta.total_tags)
             }
         } else {
             format!("No metadata found in '{}'", params.path)
         };
+        if let Some(cover) = &extracted_cover {
+            summary.push_str(&format!("; cover art extracted to {}", cover.path));
+        }
 
         info!("Successfully read metadata from {}", params.path);
 
@@ -215,6 +431,252 @@ impl ReadMetadataTool {
         }
     }
 
+    /// Read every audio file in `directory` into a compact table.
+    ///
+    /// Files that cannot be read get a row with an error instead of failing the call.
+    fn execute_directory(
+        params: &ReadMetadataParams,
+        directory: &Path,
+        config: &Config,
+    ) -> CallToolResult {
+        if params.extract_cover_to.is_some() {
+            return CallToolResult::error(vec![Content::text(
+                "extract_cover_to requires path to be a file, not a directory".to_string(),
+            )]);
+        }
+
+        let files = match MbIdentifyDirectoryTool::collect_audio_files(directory, params.recursive)
+        {
+            Ok(files) => files,
+            Err(e) => {
+                warn!("Cannot read directory {}: {}", params.path, e);
+                return CallToolResult::error(vec![Content::text(format!(
+                    "Cannot read directory: {}",
+                    e
+                ))]);
+            }
+        };
+
+        let max_files = params.max_files.clamp(1, MAX_FILES_LIMIT);
+        let rows: Vec<MetadataRow> = files
+            .iter()
+            .take(max_files)
+            .map(|file| Self::read_row(file, directory, config))
+            .collect();
+
+        let mut total_duration_seconds = 0;
+        let mut albums = BTreeSet::new();
+        let mut artists = BTreeSet::new();
+        for row in &rows {
+            total_duration_seconds += row.duration_seconds.unwrap_or(0);
+            albums.extend(row.album.clone());
+            artists.extend(row.artist.clone());
+        }
+
+        let error_count = rows.iter().filter(|row| row.error.is_some()).count();
+        let structured_data = DirectoryReadResult {
+            directory: directory.to_string_lossy().into_owned(),
+            recursive: params.recursive,
+            total_count: files.len(),
+            read_count: rows.len() - error_count,
+            error_count,
+            truncated: files.len() > rows.len(),
+            stats: DirectoryStats {
+                total_duration_seconds,
+                total_duration_formatted: format_duration(total_duration_seconds),
+                albums: albums.into_iter().collect(),
+                artists: artists.into_iter().collect(),
+            },
+            files: rows,
+        };
+
+        let summary = Self::build_directory_summary(&structured_data);
+        info!(
+            "Read metadata from {}/{} files in {}",
+            structured_data.read_count, structured_data.total_count, params.path
+        );
+
+        match serde_json::to_value(&structured_data) {
+            Ok(structured) => CallToolResult {
+                content: vec![Content::text(summary)],
+                structured_content: Some(structured),
+                is_error: Some(false),
+                meta: None,
+            },
+            Err(e) => {
+                warn!("Failed to serialize structured content: {}", e);
+                CallToolResult::success(vec![Content::text(summary)])
+            }
+        }
+    }
+
+    /// Read the compact row of one file in a directory read.
+    fn read_row(file: &Path, directory: &Path, config: &Config) -> MetadataRow {
+        let mut row = MetadataRow {
+            file: file
+                .strip_prefix(directory)
+                .unwrap_or(file)
+                .to_string_lossy()
+                .into_owned(),
+            title: None,
+            artist: None,
+            album: None,
+            track: None,
+            duration_seconds: None,
+            duration_formatted: None,
+            bitrate_kbps: None,
+            error: None,
+        };
+
+        let tagged_file = validate_path(&file.to_string_lossy(), config)
+            .map_err(|e| format!("Path security validation failed: {}", e))
+            .and_then(|path| {
+                lofty::read_from_path(path).map_err(|e| format!("Failed to read audio file: {}", e))
+            });
+        let tagged_file = match tagged_file {
+            Ok(tagged_file) => tagged_file,
+            Err(e) => {
+                warn!("{}: {}", file.display(), e);
+                row.error = Some(e);
+                return row;
+            }
+        };
+
+        if let Some(tag) = tagged_file.primary_tag() {
+            row.title = tag.title().map(|s| s.to_string());
+            row.artist = tag.artist().map(|s| s.to_string());
+            row.album = tag.album().map(|s| s.to_string());
+            row.track = tag.track();
+        }
+        let properties = audio_properties(&tagged_file);
+        row.duration_seconds = properties.duration_seconds;
+        row.duration_formatted = properties.duration_formatted;
+        row.bitrate_kbps = properties.bitrate_kbps;
+        row
+    }
+
+    /// Build the summary line followed by one line per file.
+    fn build_directory_summary(data: &DirectoryReadResult) -> String {
+        if data.total_count == 0 {
+            return format!("No audio files found in {}", data.directory);
+        }
+
+        let mut summary = format!(
+            "Read {}/{} files in {} ({} failed): {} total, {} album(s), {} artist(s)",
+            data.read_count,
+            data.total_count,
+            data.directory,
+            data.error_count,
+            data.stats.total_duration_formatted,
+            data.stats.albums.len(),
+            data.stats.artists.len()
+        );
+        if data.truncated {
+            summary.push_str(&format!(
+                "; only the first {} files were read, raise max_files or read a subdirectory",
+                data.files.len()
+            ));
+        }
+
+        for row in &data.files {
+            let line = match &row.error {
+                Some(error) => format!("{} | error: {}", row.file, error),
+                None => [
+                    row.file.clone(),
+                    row.track.map(|t| t.to_string()).unwrap_or_default(),
+                    row.title.clone().unwrap_or_default(),
+                    row.artist.clone().unwrap_or_default(),
+                    row.album.clone().unwrap_or_default(),
+                    row.duration_formatted.clone().unwrap_or_default(),
+                    row.bitrate_kbps
+                        .map(|b| format!("{} kbps", b))
+                        .unwrap_or_default(),
+                ]
+                .join(" | "),
+            };
+            summary.push('\n');
+            summary.push_str(&line);
+        }
+        summary
+    }
+
+    /// Write the front cover, or the first picture if there is none, to `output`.
+    ///
+    /// `output` is either an existing directory, which receives `cover.<ext>`, or a file path
+    /// in an existing directory, whose extension is replaced to match the image type. An
+    /// identical existing file is kept; any other existing file is never overwritten.
+    fn extract_cover(
+        tagged_file: &TaggedFile,
+        output: &str,
+        config: &Config,
+    ) -> Result<ExtractedCover, String> {
+        let pictures: Vec<_> = tagged_file
+            .tags()
+            .iter()
+            .flat_map(|tag| tag.pictures())
+            .collect();
+        let picture = pictures
+            .iter()
+            .find(|picture| picture.pic_type() == PictureType::CoverFront)
+            .or_else(|| pictures.first())
+            .ok_or_else(|| "File has no embedded pictures".to_string())?;
+
+        let extension = match picture.mime_type() {
+            Some(MimeType::Jpeg) => "jpg",
+            Some(MimeType::Png) => "png",
+            Some(MimeType::Gif) => "gif",
+            Some(MimeType::Bmp) => "bmp",
+            Some(MimeType::Tiff) => "tiff",
+            other => {
+                return Err(format!(
+                    "Unsupported embedded image type: {}",
+                    other.map_or("unknown", |mime| mime.as_str())
+                ));
+            }
+        };
+
+        let requested = Path::new(output);
+        let output_path = if requested.is_dir() {
+            requested.join(format!("cover.{}", extension))
+        } else {
+            if requested.file_name().is_none() {
+                return Err(format!("Invalid cover output path: {}", output));
+            }
+            requested.with_extension(extension)
+        };
+        // The output file itself is validated, so deny patterns see the name it is given
+        let output_path = validate_write_target(&output_path.to_string_lossy(), config)
+            .map_err(|e| format!("Cover output path security validation failed: {}", e))?;
+
+        let data = picture.data();
+        if output_path.exists() {
+            if !is_identical_file(&output_path, data) {
+                return Err(format!("File already exists: {}", output_path.display()));
+            }
+            info!("Existing cover is identical: {}", output_path.display());
+        } else {
+            write_atomic(&output_path, data)
+                .map_err(|e| format!("Failed to write {}: {}", output_path.display(), e))?;
+            info!("Extracted cover art to {}", output_path.display());
+        }
+
+        let (width, height) = match PictureInformation::from_picture(picture) {
+            Ok(info) if info.width > 0 && info.height > 0 => (Some(info.width), Some(info.height)),
+            _ => (None, None),
+        };
+        Ok(ExtractedCover {
+            path: output_path.to_string_lossy().into_owned(),
+            mime_type: picture
+                .mime_type()
+                .map_or("unknown", |mime| mime.as_str())
+                .to_string(),
+            picture_type: format!("{:?}", picture.pic_type()),
+            size_bytes: data.len(),
+            width,
+            height,
+        })
+    }
+
     /// HTTP handler for this tool (for HTTP transport).
     #[cfg(feature = "http")]
     pub fn http_handler(
@@ -227,8 +689,23 @@ impl ReadMetadataTool {
             .ok_or_else(|| "Missing or invalid 'path' parameter".to_string())?
             .to_string();
 
-        let include_properties = arguments
-            .get("include_properties")
+        let recursive = arguments
+            .get("recursive")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let max_files = arguments
+            .get("max_files")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_MAX_FILES, |n| n as usize);
+
+        let extract_cover_to = arguments
+            .get("extract_cover_to")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let include_all_tags = arguments
+            .get("include_all_tags")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
@@ -236,7 +713,10 @@ impl ReadMetadataTool {
 
         let params = ReadMetadataParams {
             path,
-            include_properties,
+            recursive,
+            max_files,
+            extract_cover_to,
+            include_all_tags,
         };
 
         let result = Self::execute(&params, &config);
@@ -276,6 +756,7 @@ impl ReadMetadataTool {
     where
         S: Send + Sync + 'static,
     {
+        let timeout = config.tools.timeout_for(Self::NAME);
         ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             let config = config.clone();
@@ -283,13 +764,255 @@ impl ReadMetadataTool {
                 let params: ReadMetadataParams =
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
-                Ok(Self::execute(&params, &config))
+                // Only a call extracting the cover art is recorded in the audit log
+                if params.extract_cover_to.is_none() {
+                    return with_timeout(
+                        Self::NAME,
+                        timeout,
+                        run_blocking(move || Self::execute(&params, &config)),
+                    )
+                    .await;
+                }
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
             }
             .boxed()
         })
     }
 }
 
+/// Build the metadata fields of a tag.
+pub(super) fn audio_metadata(tag: &Tag) -> AudioMetadata {
+    let lyrics = tag
+        .get_string(&ItemKey::Lyrics)
+        .filter(|l| !l.trim().is_empty());
+    AudioMetadata {
+        title: tag.title().map(|s| s.to_string()),
+        artist: tag.artist().map(|s| s.to_string()),
+        artists: tag
+            .get_strings(&ItemKey::TrackArtist)
+            .map(|s| s.to_string())
+            .collect(),
+        album: tag.album().map(|s| s.to_string()),
+        album_artist: tag.get_string(&ItemKey::AlbumArtist).map(|s| s.to_string()),
+        compilation: tag
+            .get_string(&ItemKey::FlagCompilation)
+            .map(|_| is_compilation(tag)),
+        year: tag.year(),
+        original_date: tag
+            .get_string(&ItemKey::OriginalReleaseDate)
+            .map(|s| s.to_string()),
+        release_country: release_country_key(tag.tag_type())
+            .and_then(|key| tag.get_string(&key))
+            .map(|s| s.to_string()),
+        media: tag
+            .get_string(&ItemKey::OriginalMediaType)
+            .map(|s| s.to_string()),
+        track: tag.track(),
+        track_total: tag.track_total(),
+        disc: tag.disk(),
+        disc_total: tag.disk_total(),
+        genre: tag.genre().map(|s| s.to_string()),
+        genres: tag
+            .get_strings(&ItemKey::Genre)
+            .map(|s| s.to_string())
+            .collect(),
+        comment: tag.comment().map(|s| s.to_string()),
+        composer: tag.get_string(&ItemKey::Composer).map(|s| s.to_string()),
+        conductor: tag.get_string(&ItemKey::Conductor).map(|s| s.to_string()),
+        lyricist: tag.get_string(&ItemKey::Lyricist).map(|s| s.to_string()),
+        remixer: tag.get_string(&ItemKey::Remixer).map(|s| s.to_string()),
+        performers: tag
+            .get_strings(&ItemKey::Performer)
+            .map(|s| s.to_string())
+            .collect(),
+        artist_sort: tag
+            .get_string(&ItemKey::TrackArtistSortOrder)
+            .map(|s| s.to_string()),
+        album_artist_sort: tag
+            .get_string(&ItemKey::AlbumArtistSortOrder)
+            .map(|s| s.to_string()),
+        album_sort: tag
+            .get_string(&ItemKey::AlbumTitleSortOrder)
+            .map(|s| s.to_string()),
+        bpm: tag
+            .get_string(&ItemKey::Bpm)
+            .or_else(|| tag.get_string(&ItemKey::IntegerBpm))
+            .and_then(|bpm| bpm.trim().parse::<f64>().ok())
+            .filter(|bpm| bpm.is_finite() && *bpm >= 0.0)
+            .map(|bpm| bpm.round() as u32),
+        replaygain_track_gain: tag
+            .get_string(&ItemKey::ReplayGainTrackGain)
+            .map(|s| s.to_string()),
+        replaygain_track_peak: tag
+            .get_string(&ItemKey::ReplayGainTrackPeak)
+            .map(|s| s.to_string()),
+        replaygain_album_gain: tag
+            .get_string(&ItemKey::ReplayGainAlbumGain)
+            .map(|s| s.to_string()),
+        replaygain_album_peak: tag
+            .get_string(&ItemKey::ReplayGainAlbumPeak)
+            .map(|s| s.to_string()),
+        has_lyrics: lyrics.is_some(),
+        lyrics_preview: lyrics.map(lyrics_preview),
+        mb_recording_id: tag
+            .get_string(&ItemKey::MusicBrainzRecordingId)
+            .map(|s| s.to_string()),
+        mb_release_id: tag
+            .get_string(&ItemKey::MusicBrainzReleaseId)
+            .map(|s| s.to_string()),
+        mb_artist_id: tag
+            .get_string(&ItemKey::MusicBrainzArtistId)
+            .map(|s| s.to_string()),
+        mb_release_group_id: tag
+            .get_string(&ItemKey::MusicBrainzReleaseGroupId)
+            .map(|s| s.to_string()),
+        custom_tags: custom_tags(tag),
+        total_tags: tag.item_count(),
+    }
+}
+
+/// Whether the tag's compilation flag is set ("1", or "true" as some taggers write it).
+pub(super) fn is_compilation(tag: &Tag) -> bool {
+    tag.get_string(&ItemKey::FlagCompilation)
+        .is_some_and(|flag| flag.trim() == "1" || flag.trim().eq_ignore_ascii_case("true"))
+}
+
+/// Collect the text tags not covered by a dedicated field, by format-specific name.
+///
+/// MP4 freeform names are reported without their `----:com.apple.iTunes:` prefix, matching
+/// the names accepted by write_metadata's `custom_tags`.
+pub(super) fn custom_tags(tag: &Tag) -> BTreeMap<String, String> {
+    let mut custom_tags: BTreeMap<String, String> = BTreeMap::new();
+    for item in tag.items() {
+        let ItemValue::Text(value) = item.value() else {
+            continue;
+        };
+        if value.is_empty()
+            || DEDICATED_KEYS.contains(item.key())
+            || release_country_key(tag.tag_type()).as_ref() == Some(item.key())
+        {
+            continue;
+        }
+        let Some(name) = item.key().map_key(tag.tag_type(), true) else {
+            continue;
+        };
+        let name = name.strip_prefix(MP4_FREEFORM_PREFIX).unwrap_or(name);
+        custom_tags
+            .entry(name.to_string())
+            .and_modify(|existing| {
+                existing.push_str("; ");
+                existing.push_str(value);
+            })
+            .or_insert_with(|| value.clone());
+    }
+    custom_tags
+}
+
+/// List every item and picture of every tag in the file, in file order.
+fn raw_tags(tagged_file: &TaggedFile) -> Vec<RawTagItem> {
+    let mut raw_tags = Vec::new();
+    for tag in tagged_file.tags() {
+        let tag_type = format!("{:?}", tag.tag_type());
+        for item in tag.items() {
+            let key = match item.key().map_key(tag.tag_type(), true) {
+                Some(name) => name.to_string(),
+                None => format!("{:?}", item.key()),
+            };
+            let value = match item.value() {
+                ItemValue::Text(text) | ItemValue::Locator(text) => text.clone(),
+                ItemValue::Binary(data) => binary_preview(data),
+            };
+            raw_tags.push(RawTagItem {
+                tag_type: tag_type.clone(),
+                key,
+                value,
+            });
+        }
+        for picture in tag.pictures() {
+            raw_tags.push(RawTagItem {
+                tag_type: tag_type.clone(),
+                key: format!("PICTURE:{:?}", picture.pic_type()),
+                value: format!(
+                    "{} {}",
+                    picture.mime_type().map_or("unknown", |mime| mime.as_str()),
+                    binary_preview(picture.data())
+                ),
+            });
+        }
+    }
+    raw_tags
+}
+
+/// Describe binary data by its size and its first [`BINARY_PREVIEW_BYTES`] bytes in hex.
+fn binary_preview(data: &[u8]) -> String {
+    let hex: String = data
+        .iter()
+        .take(BINARY_PREVIEW_BYTES)
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    let ellipsis = if data.len() > BINARY_PREVIEW_BYTES {
+        "…"
+    } else {
+        ""
+    };
+    format!("<{} bytes: {}{}>", data.len(), hex, ellipsis)
+}
+
+/// Collect the technical properties lofty read along with the tags.
+pub(super) fn audio_properties(tagged_file: &TaggedFile) -> AudioProperties {
+    let props = tagged_file.properties();
+    let duration = props.duration();
+    let duration_secs = duration.as_secs();
+    let duration_formatted = (duration_secs > 0).then(|| format_duration(duration_secs));
+
+    let channel_desc = props.channels().map(|ch| match ch {
+        1 => "Mono".to_string(),
+        2 => "Stereo".to_string(),
+        _ => "Multi-channel".to_string(),
+    });
+
+    AudioProperties {
+        file_type: format!("{:?}", tagged_file.file_type()),
+        duration_seconds: Some(duration_secs),
+        duration_ms: duration.as_millis() as u64,
+        duration_formatted,
+        bitrate_kbps: props.audio_bitrate(),
+        overall_bitrate_kbps: props.overall_bitrate(),
+        sample_rate_hz: props.sample_rate(),
+        channels: props.channels(),
+        channel_description: channel_desc,
+        bit_depth: props.bit_depth(),
+    }
+}
+
+/// Format a duration as `m:ss`, or `h:mm:ss` from one hour.
+fn format_duration(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// Truncate lyrics to [`LYRICS_PREVIEW_CHARS`] characters, marking the cut with an ellipsis.
+fn lyrics_preview(lyrics: &str) -> String {
+    let lyrics = lyrics.trim();
+    match lyrics.char_indices().nth(LYRICS_PREVIEW_CHARS) {
+        Some((cut, _)) => format!("{}…", lyrics[..cut].trim_end()),
+        None => lyrics.to_string(),
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -302,11 +1025,40 @@ mod tests {
         Config::default()
     }
 
+    /// Minimal FLAC file: STREAMINFO (44.1kHz, stereo, 16-bit, no frames) and PADDING
+    fn write_flac_fixture(path: &Path) {
+        let mut bytes = b"fLaC".to_vec();
+        bytes.extend_from_slice(&[0x00, 0, 0, 34]);
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&[0; 6]);
+        bytes.extend_from_slice(&[0x0A, 0xC4, 0x42, 0xF0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&[0x81, 0, 0, 16]);
+        bytes.extend_from_slice(&[0; 16]);
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    /// A 2x3 PNG header, enough for the image type and dimensions to be read.
+    fn png_bytes() -> Vec<u8> {
+        let mut bytes = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        bytes.extend_from_slice(&[0, 0, 0, 13]);
+        bytes.extend_from_slice(b"IHDR");
+        bytes.extend_from_slice(&2u32.to_be_bytes());
+        bytes.extend_from_slice(&3u32.to_be_bytes());
+        bytes.extend_from_slice(&[8, 2, 0, 0, 0]);
+        bytes.extend_from_slice(&[0, 0, 0, 0]);
+        bytes
+    }
+
     #[test]
     fn test_read_metadata_nonexistent() {
         let params = ReadMetadataParams {
             path: "/nonexistent/audio/file.mp3".to_string(),
-            include_properties: false,
+            recursive: false,
+            max_files: DEFAULT_MAX_FILES,
+            extract_cover_to: None,
+            include_all_tags: false,
         };
 
         let config = test_config();
@@ -314,11 +1066,295 @@ mod tests {
         assert!(result.is_error.unwrap_or(false));
     }
 
+    #[test]
+    fn test_read_metadata_extract_cover_is_audited() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = test_config();
+        let log_path = temp_dir.path().join("audit.jsonl");
+        config.audit.path = Some(log_path.clone());
+        let mut params = ReadMetadataParams {
+            path: "/nonexistent/audio/file.mp3".to_string(),
+            recursive: false,
+            max_files: DEFAULT_MAX_FILES,
+            extract_cover_to: None,
+            include_all_tags: false,
+        };
+
+        // A plain read writes nothing, so it is not recorded
+        ReadMetadataTool::execute(&params, &config);
+        assert!(!log_path.exists());
+
+        params.extract_cover_to = Some(temp_dir.path().to_string_lossy().into_owned());
+        ReadMetadataTool::execute(&params, &config);
+        let log = std::fs::read_to_string(&log_path).unwrap();
+        let entry: serde_json::Value = serde_json::from_str(log.trim()).unwrap();
+        assert_eq!(entry["tool"], "read_metadata");
+        assert_eq!(entry["outcome"], "error");
+        assert_eq!(entry["paths"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_read_metadata_audio_properties() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+
+        let params = ReadMetadataParams {
+            path: audio_path.to_string_lossy().to_string(),
+            recursive: false,
+            max_files: DEFAULT_MAX_FILES,
+            extract_cover_to: None,
+            include_all_tags: false,
+        };
+        let result = ReadMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+
+        let data = result.structured_content.unwrap();
+        let properties = &data["audio_properties"];
+        assert_eq!(properties["file_type"], "Flac");
+        assert_eq!(properties["sample_rate_hz"], 44100);
+        assert_eq!(properties["channels"], 2);
+        assert_eq!(properties["channel_description"], "Stereo");
+        assert_eq!(properties["bit_depth"], 16);
+        assert_eq!(properties["duration_ms"], 0);
+        assert!(properties["duration_formatted"].is_null());
+        // The legacy field stays opt-in
+        assert!(data["properties"].is_null());
+        assert!(data["raw_tags"].is_null());
+    }
+
+    #[test]
+    fn test_read_metadata_extract_cover() {
+        use crate::domains::tools::definitions::metadata::write::{
+            WriteMetadataParams, WriteMetadataTool,
+        };
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let path = audio_path.to_string_lossy().to_string();
+        let read_params = ReadMetadataParams {
+            path: path.clone(),
+            recursive: false,
+            max_files: DEFAULT_MAX_FILES,
+            extract_cover_to: Some(temp_dir.path().to_string_lossy().to_string()),
+            include_all_tags: false,
+        };
+
+        // Nothing to extract yet
+        let result = ReadMetadataTool::execute(&read_params, &test_config());
+        let text = format!("{:?}", result.content);
+        assert!(result.is_error.unwrap_or(false));
+        assert!(text.contains("File has no embedded pictures"), "{}", text);
+
+        let cover_path = temp_dir.path().join("cover.img");
+        std::fs::write(&cover_path, png_bytes()).unwrap();
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": path,
+            "cover_art_path": cover_path.to_string_lossy()
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+
+        // Into a directory, then to a file path whose extension is corrected
+        let result = ReadMetadataTool::execute(&read_params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+        let cover = &result.structured_content.unwrap()["extracted_cover"];
+        let extracted = temp_dir.path().join("cover.png");
+        assert_eq!(cover["path"], extracted.to_string_lossy().as_ref());
+        assert_eq!(cover["mime_type"], "image/png");
+        assert_eq!(cover["width"], 2);
+        assert_eq!(cover["height"], 3);
+        assert_eq!(std::fs::read(&extracted).unwrap(), png_bytes());
+
+        let read_params = ReadMetadataParams {
+            extract_cover_to: Some(
+                temp_dir
+                    .path()
+                    .join("folder.jpg")
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            ..read_params
+        };
+        let result = ReadMetadataTool::execute(&read_params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+        assert!(temp_dir.path().join("folder.png").exists());
+
+        // A different existing file is not overwritten
+        std::fs::write(&extracted, b"other").unwrap();
+        let read_params = ReadMetadataParams {
+            extract_cover_to: Some(temp_dir.path().to_string_lossy().to_string()),
+            ..read_params
+        };
+        let result = ReadMetadataTool::execute(&read_params, &test_config());
+        assert!(result.is_error.unwrap_or(false));
+        assert_eq!(std::fs::read(&extracted).unwrap(), b"other");
+    }
+
+    #[test]
+    fn test_read_metadata_include_all_tags() {
+        use lofty::config::WriteOptions;
+        use lofty::picture::Picture;
+        use lofty::tag::{TagItem, TagType};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        let mut bytes = b"fLaC".to_vec();
+        bytes.extend_from_slice(&[0x00, 0, 0, 34]);
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&[0; 6]);
+        bytes.extend_from_slice(&[0x0A, 0xC4, 0x42, 0xF0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&[0x81, 0, 0, 16]);
+        bytes.extend_from_slice(&[0; 16]);
+        std::fs::write(&audio_path, bytes).unwrap();
+
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.set_title("Title".to_string());
+        tag.insert_text(ItemKey::Isrc, "GBAYE0601498".to_string());
+        tag.insert_unchecked(TagItem::new(
+            ItemKey::Unknown("MY_TAG".to_string()),
+            ItemValue::Text("value".to_string()),
+        ));
+        tag.push_picture(Picture::new_unchecked(
+            PictureType::CoverFront,
+            Some(MimeType::Png),
+            None,
+            vec![0x89; 20],
+        ));
+        tag.save_to_path(&audio_path, WriteOptions::default())
+            .unwrap();
+
+        let params: ReadMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "include_all_tags": true
+        }))
+        .unwrap();
+        let result = ReadMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+
+        let raw_tags = result.structured_content.unwrap()["raw_tags"].clone();
+        let raw_tags = raw_tags.as_array().unwrap();
+        let find = |key: &str| {
+            raw_tags
+                .iter()
+                .find(|item| item["key"] == key)
+                .unwrap_or_else(|| panic!("{} missing from {:?}", key, raw_tags))
+        };
+        assert_eq!(find("TITLE")["value"], "Title");
+        assert_eq!(find("ISRC")["value"], "GBAYE0601498");
+        assert_eq!(find("MY_TAG")["value"], "value");
+        assert_eq!(find("MY_TAG")["tag_type"], "VorbisComments");
+        assert_eq!(
+            find("PICTURE:CoverFront")["value"],
+            format!(
+                "image/png <20 bytes: {}…>",
+                "89".repeat(BINARY_PREVIEW_BYTES)
+            )
+        );
+    }
+
+    #[test]
+    fn test_read_metadata_directory() {
+        use lofty::config::WriteOptions;
+        use lofty::tag::TagType;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let disc_dir = temp_dir.path().join("CD2");
+        std::fs::create_dir(&disc_dir).unwrap();
+        let mut bytes = b"fLaC".to_vec();
+        bytes.extend_from_slice(&[0x00, 0, 0, 34]);
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&[0; 6]);
+        bytes.extend_from_slice(&[0x0A, 0xC4, 0x42, 0xF0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&[0x81, 0, 0, 16]);
+        bytes.extend_from_slice(&[0; 16]);
+        for (name, title) in [("01.flac", "One"), ("CD2/01.flac", "Two")] {
+            let audio_path = temp_dir.path().join(name);
+            std::fs::write(&audio_path, &bytes).unwrap();
+            let mut tag = Tag::new(TagType::VorbisComments);
+            tag.set_title(title.to_string());
+            tag.set_artist("Artist".to_string());
+            tag.set_album("Album".to_string());
+            tag.set_track(1);
+            tag.save_to_path(&audio_path, WriteOptions::default())
+                .unwrap();
+        }
+        std::fs::write(temp_dir.path().join("02.mp3"), b"not audio").unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), b"not listed").unwrap();
+
+        let path = temp_dir.path().to_string_lossy().to_string();
+        let params: ReadMetadataParams =
+            serde_json::from_value(serde_json::json!({ "path": path })).unwrap();
+        let result = ReadMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+        let data = result.structured_content.unwrap();
+        assert_eq!(data["total_count"], 2);
+        assert_eq!(data["read_count"], 1);
+        assert_eq!(data["error_count"], 1);
+        assert_eq!(data["files"][0]["file"], "01.flac");
+        assert_eq!(data["files"][0]["title"], "One");
+        assert_eq!(data["files"][0]["track"], 1);
+        assert!(data["files"][1]["error"].is_string());
+        assert_eq!(data["stats"]["albums"], serde_json::json!(["Album"]));
+        assert_eq!(data["stats"]["artists"], serde_json::json!(["Artist"]));
+
+        // Subdirectories and the file cap
+        let params: ReadMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": path,
+            "recursive": true,
+            "max_files": 2
+        }))
+        .unwrap();
+        let result = ReadMetadataTool::execute(&params, &test_config());
+        let data = result.structured_content.unwrap();
+        assert_eq!(data["total_count"], 3);
+        assert_eq!(data["files"].as_array().unwrap().len(), 2);
+        assert_eq!(data["truncated"], true);
+        let files: Vec<_> = data["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["file"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(files, vec!["01.flac", "02.mp3"]);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(5), "0:05");
+        assert_eq!(format_duration(245), "4:05");
+        assert_eq!(format_duration(3725), "1:02:05");
+    }
+
+    #[test]
+    fn test_binary_preview() {
+        assert_eq!(binary_preview(&[]), "<0 bytes: >");
+        assert_eq!(binary_preview(&[0x00, 0xff, 0x10]), "<3 bytes: 00ff10>");
+        let long = vec![0xab; BINARY_PREVIEW_BYTES + 1];
+        assert!(binary_preview(&long).ends_with("…>"));
+    }
+
+    #[test]
+    fn test_lyrics_preview() {
+        assert_eq!(lyrics_preview("  Short lyrics\n"), "Short lyrics");
+
+        let long = "é".repeat(LYRICS_PREVIEW_CHARS + 10);
+        let preview = lyrics_preview(&long);
+        assert_eq!(preview.chars().count(), LYRICS_PREVIEW_CHARS + 1);
+        assert!(preview.ends_with('…'));
+    }
+
     #[cfg(feature = "http")]
     #[test]
     fn test_read_metadata_http_handler_missing_param() {
         let args = serde_json::json!({
-            "include_properties": true
+            "recursive": true
         });
 
         let config = Arc::new(test_config());
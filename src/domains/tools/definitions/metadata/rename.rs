@@ -0,0 +1,918 @@
+//! Rename from metadata tool definition.
+//!
+//! Renames audio files after their tags using a template such as
+//! `{track:02} - {title}.{ext}`, through the same path validation as fs_rename.
+
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool},
+};
+
+use futures::FutureExt;
+use lofty::prelude::*;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::audit;
+use crate::core::config::Config;
+use crate::core::security::{AccessKind, validate_path_for};
+use crate::domains::tools::definitions::fs::rename::{FsRenameParams, FsRenameTool};
+use crate::domains::tools::definitions::mb::identify_directory::{
+    MAX_DIRECTORY_FILES, MbIdentifyDirectoryTool,
+};
+
+use super::organize::VARIOUS_ARTISTS;
+use super::read::is_compilation;
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_audited_timeout;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the rename from metadata tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct RenameFromMetadataParams {
+    /// File name template, e.g. "{track:02} - {title}.{ext}". Placeholders: artist, album,
+    /// albumartist, year, track, disc, title, ext; numbers accept a zero-padded width
+    /// ({track:02}). The original extension is appended when the template has no {ext}
+    pub template: String,
+
+    /// Directory whose audio files are renamed (use either directory or files)
+    #[serde(default)]
+    pub directory: Option<String>,
+
+    /// Also include audio files in subdirectories of `directory` (default: false)
+    #[serde(default)]
+    pub recursive: bool,
+
+    /// Audio files to rename
+    #[serde(default)]
+    pub files: Vec<String>,
+
+    /// If true, return the old -> new names without renaming anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// ============================================================================
+// Structured Output Types
+// ============================================================================
+
+/// Structured output for a rename from metadata call.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RenameFromMetadataResult {
+    pub template: String,
+    /// Present when `directory` was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory: Option<String>,
+    /// False for a dry run
+    pub applied: bool,
+    pub total_count: usize,
+    pub renamed_count: usize,
+    pub unchanged_count: usize,
+    pub skipped_count: usize,
+    pub failure_count: usize,
+    pub results: Vec<FileRenameResult>,
+}
+
+/// Outcome for one file.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FileRenameResult {
+    pub from: String,
+    /// New path (planned path for a dry run)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    pub status: RenameStatus,
+    /// True when a number was appended because the name was already taken
+    pub numbered: bool,
+    /// Why the file was skipped or failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// What happened to a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RenameStatus {
+    /// Renamed (or would be, for a dry run)
+    Renamed,
+    /// The file already has the templated name
+    Unchanged,
+    /// A placeholder has no value in the file's tags
+    Skipped,
+    /// The file could not be read or renamed
+    Failed,
+}
+
+/// A template placeholder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Artist,
+    Album,
+    AlbumArtist,
+    Year,
+    Track,
+    Disc,
+    Title,
+    Ext,
+}
+
+impl Placeholder {
+    const ALL: [Placeholder; 8] = [
+        Placeholder::Artist,
+        Placeholder::Album,
+        Placeholder::AlbumArtist,
+        Placeholder::Year,
+        Placeholder::Track,
+        Placeholder::Disc,
+        Placeholder::Title,
+        Placeholder::Ext,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Placeholder::Artist => "artist",
+            Placeholder::Album => "album",
+            Placeholder::AlbumArtist => "albumartist",
+            Placeholder::Year => "year",
+            Placeholder::Track => "track",
+            Placeholder::Disc => "disc",
+            Placeholder::Title => "title",
+            Placeholder::Ext => "ext",
+        }
+    }
+
+    fn is_number(self) -> bool {
+        matches!(
+            self,
+            Placeholder::Year | Placeholder::Track | Placeholder::Disc
+        )
+    }
+}
+
+/// A parsed template piece.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field {
+        placeholder: Placeholder,
+        /// Zero-padded width, for numbers
+        width: usize,
+    },
+}
+
+/// Tag values a template can use.
+#[derive(Debug, Clone, Default)]
+struct TemplateValues {
+    artist: Option<String>,
+    album: Option<String>,
+    album_artist: Option<String>,
+    year: Option<u32>,
+    track: Option<u32>,
+    disc: Option<u32>,
+    title: Option<String>,
+    ext: Option<String>,
+}
+
+impl TemplateValues {
+    fn get(&self, placeholder: Placeholder, width: usize) -> Option<String> {
+        let number = |value: Option<u32>| value.map(|n| format!("{:0width$}", n, width = width));
+        match placeholder {
+            Placeholder::Artist => self.artist.clone(),
+            Placeholder::Album => self.album.clone(),
+            Placeholder::AlbumArtist => self.album_artist.clone(),
+            Placeholder::Year => number(self.year),
+            Placeholder::Track => number(self.track),
+            Placeholder::Disc => number(self.disc),
+            Placeholder::Title => self.title.clone(),
+            Placeholder::Ext => self.ext.clone(),
+        }
+    }
+}
+
+/// New name of a file, or why it has none.
+enum NamePlan {
+    Name(String),
+    Skip(String),
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Rename from metadata tool - renames audio files after their tags.
+pub struct RenameFromMetadataTool;
+
+impl RenameFromMetadataTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "rename_from_metadata";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Rename audio files after their tags using a template, e.g. \"{track:02} - {title}.{ext}\".\n\
+         \n\
+         Placeholders: artist, album, albumartist, year, track, disc, title, ext; numbers take\n\
+         a zero-padded width ({track:02}). Give a directory or a list of files. Files stay in\n\
+         their folder; characters that are illegal in file names are replaced by '_'. Files\n\
+         missing a placeholder's tag are skipped, and a taken name gets a number appended\n\
+         (\"Intro (2).flac\"). Use dry_run=true to get the old -> new names first.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
+    #[instrument(skip_all, fields(template = %params.template))]
+    pub fn execute(params: &RenameFromMetadataParams, config: &Config) -> CallToolResult {
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
+
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &RenameFromMetadataParams) -> Vec<&str> {
+        params
+            .directory
+            .iter()
+            .chain(&params.files)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &RenameFromMetadataParams, config: &Config) -> CallToolResult {
+        info!("Rename from metadata called");
+
+        match Self::rename_files(params, config) {
+            Ok(data) => {
+                let summary = Self::build_text_summary(&data);
+                info!(
+                    "{} of {} file(s) renamed ({} skipped, {} failed)",
+                    data.renamed_count, data.total_count, data.skipped_count, data.failure_count
+                );
+                match serde_json::to_value(&data) {
+                    Ok(structured) => CallToolResult {
+                        content: vec![Content::text(summary)],
+                        structured_content: Some(structured),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(e) => {
+                        warn!("Failed to serialize structured content: {}", e);
+                        CallToolResult::success(vec![Content::text(summary)])
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Rename from metadata failed: {}", e);
+                CallToolResult::error(vec![Content::text(e)])
+            }
+        }
+    }
+
+    /// Rename every file sequentially.
+    ///
+    /// Per-file failures are recorded in the results; only invalid parameters abort.
+    fn rename_files(
+        params: &RenameFromMetadataParams,
+        config: &Config,
+    ) -> Result<RenameFromMetadataResult, String> {
+        let segments = parse_template(&params.template)?;
+
+        let directory = match (&params.directory, params.files.is_empty()) {
+            (Some(_), false) => {
+                return Err("Provide either directory or files, not both".to_string());
+            }
+            (None, true) => {
+                return Err("Provide a directory or a list of files".to_string());
+            }
+            (Some(directory), true) => {
+                let directory = validate_path_for(directory, AccessKind::Write, config)
+                    .map_err(|e| format!("Path security validation failed: {}", e))?;
+                if !directory.is_dir() {
+                    return Err(format!("{} is not a directory", directory.display()));
+                }
+                Some(directory)
+            }
+            (None, false) => None,
+        };
+
+        let paths: Vec<PathBuf> = match &directory {
+            Some(directory) => {
+                MbIdentifyDirectoryTool::collect_audio_files(directory, params.recursive)
+                    .map_err(|e| format!("Cannot read directory: {}", e))?
+            }
+            None => params.files.iter().map(PathBuf::from).collect(),
+        };
+        if paths.len() > MAX_DIRECTORY_FILES {
+            return Err(format!(
+                "{} audio files to rename (max {} per call); split them into smaller calls",
+                paths.len(),
+                MAX_DIRECTORY_FILES
+            ));
+        }
+
+        // Names given out in this call, so two files never get the same one
+        let mut claimed: HashSet<PathBuf> = HashSet::new();
+        let mut results = Vec::with_capacity(paths.len());
+        for path in &paths {
+            let from = path.to_string_lossy().into_owned();
+            let plan = validate_path_for(&from, AccessKind::Write, config)
+                .map_err(|e| format!("Path security validation failed: {}", e))
+                .and_then(|path| Self::plan_name(&path, &segments));
+
+            let result = match plan {
+                Ok(NamePlan::Name(name)) => {
+                    let target = path.with_file_name(name);
+                    let (to, numbered) = free_name(path, &target, &claimed);
+                    claimed.insert(to.clone());
+                    if &to == path {
+                        FileRenameResult {
+                            from,
+                            to: Some(to.to_string_lossy().into_owned()),
+                            status: RenameStatus::Unchanged,
+                            numbered,
+                            reason: None,
+                        }
+                    } else {
+                        let to = to.to_string_lossy().into_owned();
+                        let renamed = if params.dry_run {
+                            Ok(())
+                        } else {
+                            let rename = FsRenameParams::default();
+                            FsRenameTool::rename_path(&from, &to, &rename, config).map(|_| ())
+                        };
+                        match renamed {
+                            Ok(()) => FileRenameResult {
+                                from,
+                                to: Some(to),
+                                status: RenameStatus::Renamed,
+                                numbered,
+                                reason: None,
+                            },
+                            Err(e) => FileRenameResult {
+                                from,
+                                to: Some(to),
+                                status: RenameStatus::Failed,
+                                numbered,
+                                reason: Some(e),
+                            },
+                        }
+                    }
+                }
+                Ok(NamePlan::Skip(reason)) => FileRenameResult {
+                    from,
+                    to: None,
+                    status: RenameStatus::Skipped,
+                    numbered: false,
+                    reason: Some(reason),
+                },
+                Err(e) => FileRenameResult {
+                    from,
+                    to: None,
+                    status: RenameStatus::Failed,
+                    numbered: false,
+                    reason: Some(e),
+                },
+            };
+            if let (RenameStatus::Failed, Some(reason)) = (result.status, &result.reason) {
+                warn!("Failed to rename {}: {}", result.from, reason);
+            }
+            results.push(result);
+        }
+
+        let count = |status: RenameStatus| results.iter().filter(|r| r.status == status).count();
+        Ok(RenameFromMetadataResult {
+            template: params.template.clone(),
+            directory: directory.map(|directory| directory.to_string_lossy().into_owned()),
+            applied: !params.dry_run,
+            total_count: results.len(),
+            renamed_count: count(RenameStatus::Renamed),
+            unchanged_count: count(RenameStatus::Unchanged),
+            skipped_count: count(RenameStatus::Skipped),
+            failure_count: count(RenameStatus::Failed),
+            results,
+        })
+    }
+
+    /// Read the file's tags and render its new file name.
+    fn plan_name(path: &Path, segments: &[Segment]) -> Result<NamePlan, String> {
+        let tagged_file =
+            lofty::read_from_path(path).map_err(|e| format!("Failed to read audio file: {}", e))?;
+        let text = |value: Option<std::borrow::Cow<'_, str>>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let mut values = TemplateValues {
+            ext: path
+                .extension()
+                .map(|ext| ext.to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        if let Some(tag) = tagged_file
+            .primary_tag()
+            .or_else(|| tagged_file.first_tag())
+        {
+            values.artist = text(tag.artist());
+            values.album = text(tag.album());
+            values.album_artist = text(tag.get_string(&ItemKey::AlbumArtist).map(Into::into));
+            // Compilations are filed under one artist, as organize_library does
+            if is_compilation(tag) {
+                values.album_artist = Some(VARIOUS_ARTISTS.to_string());
+            }
+            values.year = tag.year();
+            values.track = tag.track();
+            values.disc = tag.disk();
+            values.title = text(tag.title());
+        }
+
+        let name = match render_template(segments, &values) {
+            Ok(name) => name,
+            Err(missing) => {
+                return Ok(NamePlan::Skip(format!(
+                    "Missing tag(s) for {}",
+                    missing.join(", ")
+                )));
+            }
+        };
+        let has_ext = segments.iter().any(|segment| {
+            matches!(
+                segment,
+                Segment::Field {
+                    placeholder: Placeholder::Ext,
+                    ..
+                }
+            )
+        });
+        let name = match (&values.ext, has_ext) {
+            (Some(ext), false) => format!("{}.{}", name, ext),
+            _ => name,
+        };
+
+        let name = sanitize_file_name(&name);
+        if name.is_empty() || name == "." || name == ".." {
+            return Ok(NamePlan::Skip(
+                "Template produced an empty file name".to_string(),
+            ));
+        }
+        Ok(NamePlan::Name(name))
+    }
+
+    /// Build the summary line followed by one line per renamed, skipped or failed file.
+    fn build_text_summary(data: &RenameFromMetadataResult) -> String {
+        if data.total_count == 0 {
+            return match &data.directory {
+                Some(directory) => format!("No audio files found in {}", directory),
+                None => "No files to rename".to_string(),
+            };
+        }
+
+        let counts = format!(
+            "{} unchanged, {} skipped, {} failed",
+            data.unchanged_count, data.skipped_count, data.failure_count
+        );
+        let mut summary = if data.applied {
+            format!(
+                "Renamed {}/{} file(s) ({})",
+                data.renamed_count, data.total_count, counts
+            )
+        } else {
+            format!(
+                "DRY RUN: {}/{} file(s) would be renamed ({}); nothing was renamed",
+                data.renamed_count, data.total_count, counts
+            )
+        };
+
+        let file_name = |path: &str| {
+            Path::new(path)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.to_string())
+        };
+        for result in &data.results {
+            let from = file_name(&result.from);
+            let line = match (result.status, &result.to, &result.reason) {
+                (RenameStatus::Renamed, Some(to), _) => format!("{} -> {}", from, file_name(to)),
+                (RenameStatus::Skipped, _, Some(reason)) => {
+                    format!("skipped {}: {}", from, reason)
+                }
+                (RenameStatus::Failed, _, Some(reason)) => format!("failed {}: {}", from, reason),
+                _ => continue,
+            };
+            summary.push('\n');
+            summary.push_str(&line);
+        }
+        summary
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: RenameFromMetadataParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Rename from metadata (HTTP) called");
+
+        let result = Self::execute(&params, &config);
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<RenameFromMetadataParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: RenameFromMetadataParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// Parse a file name template into literals and placeholders.
+fn parse_template(template: &str) -> Result<Vec<Segment>, String> {
+    if template.trim().is_empty() {
+        return Err("template cannot be empty".to_string());
+    }
+
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while !rest.is_empty() {
+        let Some(start) = rest.find(['{', '}']) else {
+            segments.push(Segment::Literal(rest.to_string()));
+            break;
+        };
+        if rest[start..].starts_with('}') {
+            return Err(format!("Unmatched '}}' in template '{}'", template));
+        }
+        if start > 0 {
+            segments.push(Segment::Literal(rest[..start].to_string()));
+        }
+
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            return Err(format!("Unclosed '{{' in template '{}'", template));
+        };
+        let inner = &rest[start + 1..end];
+        let (name, width) = inner.split_once(':').unwrap_or((inner, ""));
+        let placeholder = Placeholder::ALL
+            .into_iter()
+            .find(|placeholder| placeholder.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Placeholder::ALL.iter().map(|p| p.name()).collect();
+                format!(
+                    "Unknown placeholder '{{{}}}' (expected one of: {})",
+                    name,
+                    names.join(", ")
+                )
+            })?;
+        let width = if inner.contains(':') {
+            if !placeholder.is_number() {
+                return Err(format!(
+                    "'{{{}}}': only year, track and disc take a width",
+                    inner
+                ));
+            }
+            width
+                .parse()
+                .map_err(|_| format!("'{{{}}}': width must be a number, e.g. 02", inner))?
+        } else {
+            0
+        };
+        segments.push(Segment::Field { placeholder, width });
+        rest = &rest[end + 1..];
+    }
+
+    let separator = segments
+        .iter()
+        .any(|segment| matches!(segment, Segment::Literal(text) if text.contains(['/', '\\'])));
+    if separator {
+        return Err("template is a file name and cannot contain '/' or '\\'".to_string());
+    }
+    Ok(segments)
+}
+
+/// Render a template, or list the placeholders without a value.
+fn render_template(
+    segments: &[Segment],
+    values: &TemplateValues,
+) -> Result<String, Vec<&'static str>> {
+    let mut name = String::new();
+    let mut missing = Vec::new();
+    for segment in segments {
+        match segment {
+            Segment::Literal(text) => name.push_str(text),
+            Segment::Field { placeholder, width } => match values.get(*placeholder, *width) {
+                Some(value) => name.push_str(&value),
+                None if !missing.contains(&placeholder.name()) => missing.push(placeholder.name()),
+                None => {}
+            },
+        }
+    }
+    if missing.is_empty() {
+        Ok(name)
+    } else {
+        Err(missing)
+    }
+}
+
+/// Replace characters that are illegal in file names on common filesystems by '_', and
+/// drop the leading spaces and trailing spaces and dots Windows does not allow.
+pub(super) fn sanitize_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    name.trim_start().trim_end_matches([' ', '.']).to_string()
+}
+
+/// The first of `target`, `target (2)`, `target (3)`... that is neither on disk nor
+/// claimed by another file, and whether a number was appended.
+///
+/// `source` itself never counts as taken, so an already well-named file is unchanged.
+fn free_name(source: &Path, target: &Path, claimed: &HashSet<PathBuf>) -> (PathBuf, bool) {
+    let stem = target
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = target
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+
+    let mut candidate = target.to_path_buf();
+    let mut number = 1;
+    loop {
+        let taken = claimed.contains(&candidate) || (candidate != source && candidate.exists());
+        if !taken {
+            return (candidate, number > 1);
+        }
+        number += 1;
+        candidate = target.with_file_name(format!("{} ({}){}", stem, number, ext));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lofty::config::WriteOptions;
+    use lofty::tag::{Tag, TagType};
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    /// Minimal FLAC file: signature, a STREAMINFO block (44.1kHz, stereo, 16-bit, no frames)
+    /// and a final PADDING block, tagged with the given title and track.
+    fn write_flac_fixture(path: &Path, title: Option<&str>, track: u32) {
+        let mut bytes = b"fLaC".to_vec();
+        bytes.extend_from_slice(&[0x00, 0, 0, 34]);
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&[0; 6]);
+        bytes.extend_from_slice(&[0x0A, 0xC4, 0x42, 0xF0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&[0x81, 0, 0, 16]);
+        bytes.extend_from_slice(&[0; 16]);
+        std::fs::write(path, bytes).unwrap();
+
+        let mut tag = Tag::new(TagType::VorbisComments);
+        if let Some(title) = title {
+            tag.set_title(title.to_string());
+        }
+        tag.set_track(track);
+        tag.save_to_path(path, WriteOptions::default()).unwrap();
+    }
+
+    fn params(value: serde_json::Value) -> RenameFromMetadataParams {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_template() {
+        let segments = parse_template("{track:02} - {title}").unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                Segment::Field {
+                    placeholder: Placeholder::Track,
+                    width: 2
+                },
+                Segment::Literal(" - ".to_string()),
+                Segment::Field {
+                    placeholder: Placeholder::Title,
+                    width: 0
+                },
+            ]
+        );
+
+        assert!(
+            parse_template("{genre}")
+                .unwrap_err()
+                .contains("Unknown placeholder")
+        );
+        assert!(parse_template("{title:02}").unwrap_err().contains("width"));
+        assert!(parse_template("{track").unwrap_err().contains("Unclosed"));
+        assert!(parse_template("track}").unwrap_err().contains("Unmatched"));
+        assert!(parse_template("{album}/{title}").is_err());
+        assert!(parse_template(" ").is_err());
+    }
+
+    #[test]
+    fn test_render_template() {
+        let values = TemplateValues {
+            artist: Some("AC/DC".to_string()),
+            track: Some(3),
+            ext: Some("mp3".to_string()),
+            ..Default::default()
+        };
+        let segments = parse_template("{artist} - {track:02}.{ext}").unwrap();
+        assert_eq!(
+            render_template(&segments, &values).unwrap(),
+            "AC/DC - 03.mp3"
+        );
+
+        let segments = parse_template("{track} {title} {album} {title}").unwrap();
+        assert_eq!(
+            render_template(&segments, &values).unwrap_err(),
+            vec!["title", "album"]
+        );
+    }
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("AC/DC - What?.mp3"), "AC_DC - What_.mp3");
+        assert_eq!(sanitize_file_name(" Intro..."), "Intro");
+        assert_eq!(sanitize_file_name("a\tb<c>|d"), "a_b_c__d");
+    }
+
+    #[test]
+    fn test_rename_compilation_album_artist() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("a.flac");
+        write_flac_fixture(&path, Some("Song"), 1);
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.set_title("Song".to_string());
+        tag.insert_text(ItemKey::AlbumArtist, "Blur".to_string());
+        tag.insert_text(ItemKey::FlagCompilation, "1".to_string());
+        tag.save_to_path(&path, WriteOptions::default()).unwrap();
+
+        let segments = parse_template("{albumartist} - {title}").unwrap();
+        match RenameFromMetadataTool::plan_name(&path, &segments).unwrap() {
+            NamePlan::Name(name) => assert_eq!(name, "Various Artists - Song.flac"),
+            NamePlan::Skip(reason) => panic!("skipped: {}", reason),
+        }
+    }
+
+    #[test]
+    fn test_rename_directory_dry_run_then_apply() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write_flac_fixture(&dir.join("a.flac"), Some("Intro: Part 1"), 1);
+        write_flac_fixture(&dir.join("b.flac"), Some("Song"), 2);
+        write_flac_fixture(&dir.join("c.flac"), Some("Song"), 2);
+        write_flac_fixture(&dir.join("d.flac"), None, 4);
+
+        let dry_run = params(serde_json::json!({
+            "template": "{track:02} - {title}",
+            "directory": dir.to_string_lossy(),
+            "dry_run": true
+        }));
+        let data = RenameFromMetadataTool::rename_files(&dry_run, &test_config()).unwrap();
+        assert!(!data.applied);
+        assert_eq!(data.renamed_count, 3);
+        assert_eq!(data.skipped_count, 1);
+        assert!(dir.join("a.flac").exists());
+
+        let to: Vec<Option<String>> = data
+            .results
+            .iter()
+            .map(|r| {
+                r.to.as_ref().map(|to| {
+                    Path::new(to)
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned()
+                })
+            })
+            .collect();
+        assert_eq!(
+            to,
+            vec![
+                Some("01 - Intro_ Part 1.flac".to_string()),
+                Some("02 - Song.flac".to_string()),
+                Some("02 - Song (2).flac".to_string()),
+                None,
+            ]
+        );
+        assert!(data.results[2].numbered);
+        assert_eq!(
+            data.results[3].reason.as_deref(),
+            Some("Missing tag(s) for title")
+        );
+
+        let apply = RenameFromMetadataParams {
+            dry_run: false,
+            ..dry_run
+        };
+        let data = RenameFromMetadataTool::rename_files(&apply, &test_config()).unwrap();
+        assert!(data.applied);
+        assert_eq!(data.renamed_count, 3);
+        assert!(dir.join("01 - Intro_ Part 1.flac").exists());
+        assert!(dir.join("02 - Song (2).flac").exists());
+        assert!(dir.join("d.flac").exists());
+
+        // Running again leaves the renamed files alone
+        let data = RenameFromMetadataTool::rename_files(&apply, &test_config()).unwrap();
+        assert_eq!(data.renamed_count, 0);
+        assert_eq!(data.unchanged_count, 3);
+    }
+
+    #[test]
+    fn test_rename_files_list_with_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("track.flac");
+        write_flac_fixture(&file, Some("Title"), 7);
+
+        let params = params(serde_json::json!({
+            "template": "{track} {title}.{ext}",
+            "files": [file.to_string_lossy(), "/nonexistent/file.flac"]
+        }));
+        let result = RenameFromMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false));
+        assert!(temp_dir.path().join("7 Title.flac").exists());
+
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["renamed_count"], 1);
+        assert_eq!(structured["failure_count"], 1);
+        assert_eq!(structured["results"][1]["status"], "failed");
+    }
+
+    #[test]
+    fn test_rename_requires_directory_or_files() {
+        let result = RenameFromMetadataTool::execute(
+            &params(serde_json::json!({ "template": "{title}" })),
+            &test_config(),
+        );
+        assert!(result.is_error.unwrap_or(false));
+    }
+}
@@ -0,0 +1,975 @@
+//! Metadata report tool definition.
+//!
+//! Scans a library directory tree and summarizes what its tags are missing or get wrong
+//! (no title, no cover art, "Track 01" titles, ...), as counts with a few example files
+//! per check and a per-album rollup, so a whole library fits in one response. The genre
+//! report lists every genre spelling found, grouped by the canonical genre it stands for.
+
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool},
+};
+
+use futures::FutureExt;
+use lofty::prelude::*;
+use lofty::tag::Tag;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::config::Config;
+use crate::core::security::validate_path;
+use crate::domains::tools::definitions::mb::identify_directory::MbIdentifyDirectoryTool;
+
+use super::genre::{GenreMap, genre_key};
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_timeout;
+
+/// Default number of directory levels scanned below `directory`.
+const DEFAULT_MAX_DEPTH: usize = 10;
+
+/// Upper bound for `max_depth`.
+const MAX_DEPTH_LIMIT: usize = 32;
+
+/// Default number of audio files read.
+const DEFAULT_MAX_FILES: usize = 2000;
+
+/// Upper bound for `max_files`.
+const MAX_FILES_LIMIT: usize = 20000;
+
+/// Default number of example files listed per check.
+const DEFAULT_MAX_EXAMPLES: usize = 5;
+
+/// Upper bound for `max_examples`.
+const MAX_EXAMPLES_LIMIT: usize = 50;
+
+/// Maximum number of albums listed in the rollup.
+const MAX_ALBUMS: usize = 50;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the metadata report tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MetadataReportParams {
+    /// Library directory to scan
+    pub directory: String,
+
+    /// Directory levels scanned below `directory`, 0 for the directory alone
+    /// (default: 10, max: 32)
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+
+    /// Maximum number of audio files read (default: 2000, max: 20000)
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+
+    /// Example files listed per check (default: 5, max: 50)
+    #[serde(default = "default_max_examples")]
+    pub max_examples: usize,
+
+    /// Also list every distinct genre spelling with the canonical genre suggested for it
+    /// (default: false)
+    #[serde(default)]
+    pub genre_report: bool,
+}
+
+fn default_max_depth() -> usize {
+    DEFAULT_MAX_DEPTH
+}
+
+fn default_max_files() -> usize {
+    DEFAULT_MAX_FILES
+}
+
+fn default_max_examples() -> usize {
+    DEFAULT_MAX_EXAMPLES
+}
+
+// ============================================================================
+// Structured Output Types
+// ============================================================================
+
+/// Structured output for a metadata report.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MetadataReport {
+    pub directory: String,
+    pub max_depth: usize,
+    /// Audio files read
+    pub file_count: usize,
+    /// True if more than `max_files` audio files were found; only the first were read
+    pub truncated: bool,
+    /// True if subdirectories deeper than `max_depth` were skipped
+    pub depth_limited: bool,
+    /// Files with at least one missing or suspicious value
+    pub files_with_issues: usize,
+    /// Checks failed by at least one file, in check order
+    pub checks: Vec<CheckSummary>,
+    pub album_count: usize,
+    pub albums_with_issues: usize,
+    /// Albums with issues, most affected first (at most 50)
+    pub albums: Vec<AlbumSummary>,
+    /// With genre_report, the genres found, most used first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genres: Option<Vec<GenreGroup>>,
+}
+
+/// Spellings of one genre found in the library.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GenreGroup {
+    /// Spelling to use: the canonical genre from the genre map, else the most used spelling
+    pub suggested: String,
+    /// Whether `suggested` comes from the genre map
+    pub canonical: bool,
+    /// Files tagged with any of the spellings
+    pub file_count: usize,
+    /// Spellings found, most used first
+    pub spellings: Vec<GenreSpelling>,
+}
+
+/// One genre spelling and the number of files using it.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GenreSpelling {
+    pub genre: String,
+    pub file_count: usize,
+}
+
+/// Files failing one check.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CheckSummary {
+    pub check: ReportCheck,
+    pub count: usize,
+    /// First files failing the check, relative to the directory (at most `max_examples`)
+    pub examples: Vec<String>,
+}
+
+/// Issues of the files of one album.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AlbumSummary {
+    /// Album tag, or null for files without one
+    pub album: Option<String>,
+    /// Album artist, or the track artist when there is none
+    pub artist: Option<String>,
+    pub file_count: usize,
+    pub files_with_issues: usize,
+    pub checks: Vec<CheckCount>,
+}
+
+/// Number of files of an album failing one check.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CheckCount {
+    pub check: ReportCheck,
+    pub count: usize,
+}
+
+/// Missing or suspicious value looked for in every file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportCheck {
+    /// The file's tags could not be read
+    Unreadable,
+    MissingTitle,
+    MissingArtist,
+    MissingAlbum,
+    MissingYear,
+    MissingTrack,
+    /// No embedded picture
+    MissingCoverArt,
+    /// No MusicBrainz recording or release ID
+    MissingMbIds,
+    /// Year tag set to 0
+    YearZero,
+    /// Title left by a ripper, e.g. "Track 01" or "Unknown"
+    PlaceholderTitle,
+    /// Artist left by a ripper, e.g. "Unknown Artist"
+    PlaceholderArtist,
+    /// Genre that says nothing, e.g. "Other"
+    GenericGenre,
+}
+
+impl ReportCheck {
+    /// Name used in the text summary, as serialized.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Unreadable => "unreadable",
+            Self::MissingTitle => "missing_title",
+            Self::MissingArtist => "missing_artist",
+            Self::MissingAlbum => "missing_album",
+            Self::MissingYear => "missing_year",
+            Self::MissingTrack => "missing_track",
+            Self::MissingCoverArt => "missing_cover_art",
+            Self::MissingMbIds => "missing_mb_ids",
+            Self::YearZero => "year_zero",
+            Self::PlaceholderTitle => "placeholder_title",
+            Self::PlaceholderArtist => "placeholder_artist",
+            Self::GenericGenre => "generic_genre",
+        }
+    }
+}
+
+/// Album a file belongs to, its genres and the checks it fails.
+#[derive(Debug, Clone, Default)]
+struct FileChecks {
+    album: Option<String>,
+    artist: Option<String>,
+    genres: Vec<String>,
+    checks: Vec<ReportCheck>,
+}
+
+/// Counts of one album while scanning.
+#[derive(Debug, Default)]
+struct AlbumTally {
+    file_count: usize,
+    files_with_issues: usize,
+    checks: BTreeMap<ReportCheck, usize>,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Metadata report tool - summarizes missing and suspicious tags across a library.
+pub struct MetadataReportTool;
+
+impl MetadataReportTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "metadata_report";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Summarize the missing and suspicious tags of a whole music library.\n\
+         \n\
+         Scans the directory tree (up to max_depth levels, max_files audio files), reads\n\
+         every file's tags and returns, per check, how many files fail it with a few example\n\
+         paths: missing title, artist, album, year, track, cover art or MusicBrainz IDs, year\n\
+         0, placeholder titles/artists (\"Track 01\", \"Unknown Artist\") and generic genres\n\
+         (\"Other\"). Albums with issues are listed, most affected first. Use it to decide\n\
+         where to start tagging; read_metadata and check_album_consistency give the details.\n\
+         \n\
+         With genre_report, every genre spelling found is listed, grouped under the canonical\n\
+         genre suggested for it (\"Hip Hop\", \"hiphop\" -> \"Hip-Hop\"); write_metadata with\n\
+         normalize_genre applies the same mapping.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    #[instrument(skip_all, fields(directory = %params.directory))]
+    pub fn execute(params: &MetadataReportParams, config: &Config) -> CallToolResult {
+        info!("Metadata report called");
+
+        match Self::build_report(params, config) {
+            Ok(data) => {
+                let summary = Self::build_text_summary(&data);
+                info!(
+                    "{} of {} file(s) with issues in {}",
+                    data.files_with_issues, data.file_count, data.directory
+                );
+                match serde_json::to_value(&data) {
+                    Ok(structured) => CallToolResult {
+                        content: vec![Content::text(summary)],
+                        structured_content: Some(structured),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(e) => {
+                        warn!("Failed to serialize structured content: {}", e);
+                        CallToolResult::success(vec![Content::text(summary)])
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Metadata report failed: {}", e);
+                CallToolResult::error(vec![Content::text(e)])
+            }
+        }
+    }
+
+    /// Scan the directory and aggregate the checks of every file.
+    fn build_report(
+        params: &MetadataReportParams,
+        config: &Config,
+    ) -> Result<MetadataReport, String> {
+        let directory = validate_path(&params.directory, config)
+            .map_err(|e| format!("Path security validation failed: {}", e))?;
+        if !directory.is_dir() {
+            return Err(format!("Path is not a directory: {}", params.directory));
+        }
+
+        let max_depth = params.max_depth.min(MAX_DEPTH_LIMIT);
+        let max_files = params.max_files.clamp(1, MAX_FILES_LIMIT);
+        let max_examples = params.max_examples.min(MAX_EXAMPLES_LIMIT);
+        let genre_map = params
+            .genre_report
+            .then(|| GenreMap::load(&config.metadata))
+            .transpose()?;
+        let scan = collect_files(&directory, max_depth, max_files)
+            .map_err(|e| format!("Cannot read directory: {}", e))?;
+
+        let mut checks: BTreeMap<ReportCheck, CheckSummary> = BTreeMap::new();
+        let mut albums: BTreeMap<(Option<String>, Option<String>), AlbumTally> = BTreeMap::new();
+        let mut genres: BTreeMap<String, usize> = BTreeMap::new();
+        let mut files_with_issues = 0;
+        for path in &scan.files {
+            let file = path
+                .strip_prefix(&directory)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .into_owned();
+            let file_checks = match Self::read_file_checks(path, config) {
+                Ok(file_checks) => file_checks,
+                Err(e) => {
+                    warn!("Cannot read {}: {}", path.display(), e);
+                    FileChecks {
+                        checks: vec![ReportCheck::Unreadable],
+                        ..FileChecks::default()
+                    }
+                }
+            };
+
+            if !file_checks.checks.is_empty() {
+                files_with_issues += 1;
+            }
+            for check in &file_checks.checks {
+                let summary = checks.entry(*check).or_insert_with(|| CheckSummary {
+                    check: *check,
+                    count: 0,
+                    examples: Vec::new(),
+                });
+                summary.count += 1;
+                if summary.examples.len() < max_examples {
+                    summary.examples.push(file.clone());
+                }
+            }
+
+            // Unreadable files belong to no known album
+            if file_checks.checks == [ReportCheck::Unreadable] {
+                continue;
+            }
+            for genre in file_checks.genres {
+                *genres.entry(genre).or_default() += 1;
+            }
+            let tally = albums
+                .entry((file_checks.artist, file_checks.album))
+                .or_default();
+            tally.file_count += 1;
+            if !file_checks.checks.is_empty() {
+                tally.files_with_issues += 1;
+            }
+            for check in file_checks.checks {
+                *tally.checks.entry(check).or_default() += 1;
+            }
+        }
+
+        let album_count = albums.len();
+        let mut albums: Vec<AlbumSummary> = albums
+            .into_iter()
+            .filter(|(_, tally)| tally.files_with_issues > 0)
+            .map(|((artist, album), tally)| AlbumSummary {
+                album,
+                artist,
+                file_count: tally.file_count,
+                files_with_issues: tally.files_with_issues,
+                checks: tally
+                    .checks
+                    .into_iter()
+                    .map(|(check, count)| CheckCount { check, count })
+                    .collect(),
+            })
+            .collect();
+        let albums_with_issues = albums.len();
+        albums.sort_by_key(|album| Reverse(album.files_with_issues));
+        albums.truncate(MAX_ALBUMS);
+
+        Ok(MetadataReport {
+            directory: directory.to_string_lossy().into_owned(),
+            max_depth,
+            file_count: scan.files.len(),
+            truncated: scan.truncated,
+            depth_limited: scan.depth_limited,
+            files_with_issues,
+            checks: checks.into_values().collect(),
+            album_count,
+            albums_with_issues,
+            albums,
+            genres: genre_map.map(|genre_map| genre_groups(&genres, &genre_map)),
+        })
+    }
+
+    /// Read a file's primary tag, or else its first tag, and run every check on it.
+    fn read_file_checks(path: &Path, config: &Config) -> Result<FileChecks, String> {
+        let path = validate_path(&path.to_string_lossy(), config)
+            .map_err(|e| format!("Path security validation failed: {}", e))?;
+        let tagged_file = lofty::read_from_path(&path)
+            .map_err(|e| format!("Failed to read audio file: {}", e))?;
+        let tag = tagged_file
+            .primary_tag()
+            .or_else(|| tagged_file.first_tag());
+        Ok(file_checks(tag))
+    }
+
+    /// Build the summary line followed by one line per check.
+    fn build_text_summary(data: &MetadataReport) -> String {
+        if data.file_count == 0 {
+            return format!("No audio files found in {}", data.directory);
+        }
+
+        let mut summary = format!(
+            "{}: {} of {} file(s) with issues, {} of {} album(s)",
+            data.directory,
+            data.files_with_issues,
+            data.file_count,
+            data.albums_with_issues,
+            data.album_count
+        );
+        if data.truncated {
+            summary.push_str(&format!(
+                "; only the first {} files were read, raise max_files or report a subdirectory",
+                data.file_count
+            ));
+        }
+        if data.depth_limited {
+            summary.push_str(&format!(
+                "; folders deeper than {} level(s) were skipped",
+                data.max_depth
+            ));
+        }
+        for check in &data.checks {
+            summary.push_str(&format!("\n{}: {}", check.check.label(), check.count));
+            if !check.examples.is_empty() {
+                summary.push_str(&format!(" (e.g. {})", check.examples.join(", ")));
+            }
+        }
+        if let Some(genres) = &data.genres {
+            let spellings: usize = genres.iter().map(|group| group.spellings.len()).sum();
+            summary.push_str(&format!(
+                "\ngenres: {} spelling(s) of {} genre(s)",
+                spellings,
+                genres.len()
+            ));
+            // Only the genres with a spelling to change
+            for group in genres {
+                if group.spellings.iter().all(|s| s.genre == group.suggested) {
+                    continue;
+                }
+                let spellings: Vec<String> = group
+                    .spellings
+                    .iter()
+                    .map(|s| format!("{} ({})", s.genre, s.file_count))
+                    .collect();
+                summary.push_str(&format!(
+                    "\n{} <- {}",
+                    group.suggested,
+                    spellings.join(", ")
+                ));
+            }
+        }
+        summary
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: MetadataReportParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Metadata report (HTTP) called");
+
+        let result = Self::execute(&params, &config);
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<MetadataReportParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: MetadataReportParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                with_timeout(
+                    Self::NAME,
+                    timeout,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// Audio files found by [`collect_files`].
+#[derive(Debug, Default)]
+pub(super) struct ScanResult {
+    pub(super) files: Vec<PathBuf>,
+    pub(super) truncated: bool,
+    pub(super) depth_limited: bool,
+}
+
+/// Collect up to `max_files` audio files, each folder's files before its subfolders, in
+/// name order, descending at most `max_depth` levels.
+///
+/// Symlinked directories are not followed; unreadable subdirectories are skipped.
+pub(super) fn collect_files(
+    dir: &Path,
+    max_depth: usize,
+    max_files: usize,
+) -> std::io::Result<ScanResult> {
+    let mut scan = ScanResult::default();
+    let mut pending = vec![(dir.to_path_buf(), 0)];
+
+    while let Some((current, depth)) = pending.pop() {
+        let entries = match std::fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(e) if depth > 0 => {
+                warn!("Skipping {}: {}", current.display(), e);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let mut entries: Vec<_> = entries.filter_map(Result::ok).collect();
+        entries.sort_by_key(|entry| entry.file_name());
+
+        let mut subdirs = Vec::new();
+        for entry in entries {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                if depth < max_depth {
+                    subdirs.push(path);
+                } else {
+                    scan.depth_limited = true;
+                }
+            } else if MbIdentifyDirectoryTool::is_audio_file(&path) {
+                if scan.files.len() == max_files {
+                    scan.truncated = true;
+                    return Ok(scan);
+                }
+                scan.files.push(path);
+            }
+        }
+        // Reversed so the first subdirectory is scanned next
+        pending.extend(subdirs.into_iter().rev().map(|subdir| (subdir, depth + 1)));
+    }
+
+    Ok(scan)
+}
+
+/// Run every check on a file's tag (`None` for a file without tags).
+fn file_checks(tag: Option<&Tag>) -> FileChecks {
+    let text = |key: &ItemKey| {
+        tag.and_then(|tag| tag.get_string(key))
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let title = text(&ItemKey::TrackTitle);
+    let artist = text(&ItemKey::TrackArtist);
+    let album = text(&ItemKey::AlbumTitle);
+    let genre = text(&ItemKey::Genre);
+    let year = text(&ItemKey::Year).or_else(|| text(&ItemKey::RecordingDate));
+
+    let mut checks = Vec::new();
+    let results = [
+        (ReportCheck::MissingTitle, title.is_none()),
+        (ReportCheck::MissingArtist, artist.is_none()),
+        (ReportCheck::MissingAlbum, album.is_none()),
+        (ReportCheck::MissingYear, year.is_none()),
+        (
+            ReportCheck::MissingTrack,
+            tag.and_then(|tag| tag.track()).is_none(),
+        ),
+        (
+            ReportCheck::MissingCoverArt,
+            tag.is_none_or(|tag| tag.pictures().is_empty()),
+        ),
+        (
+            ReportCheck::MissingMbIds,
+            text(&ItemKey::MusicBrainzRecordingId).is_none()
+                || text(&ItemKey::MusicBrainzReleaseId).is_none(),
+        ),
+        (
+            ReportCheck::YearZero,
+            year.as_deref().is_some_and(is_zero_year),
+        ),
+        (
+            ReportCheck::PlaceholderTitle,
+            title.as_deref().is_some_and(is_placeholder_title),
+        ),
+        (
+            ReportCheck::PlaceholderArtist,
+            artist.as_deref().is_some_and(|artist| {
+                matches!(
+                    artist.to_lowercase().as_str(),
+                    "unknown" | "unknown artist" | "artist"
+                )
+            }),
+        ),
+        (
+            ReportCheck::GenericGenre,
+            genre.as_deref().is_some_and(|genre| {
+                matches!(
+                    genre.to_lowercase().as_str(),
+                    "other" | "unknown" | "misc" | "genre"
+                )
+            }),
+        ),
+    ];
+    for (check, failed) in results {
+        if failed {
+            checks.push(check);
+        }
+    }
+
+    let mut genres: Vec<String> = Vec::new();
+    let values = tag
+        .into_iter()
+        .flat_map(|tag| tag.get_strings(&ItemKey::Genre));
+    for genre in values.map(str::trim).filter(|genre| !genre.is_empty()) {
+        if !genres.iter().any(|g| g == genre) {
+            genres.push(genre.to_string());
+        }
+    }
+
+    let album_artist = text(&ItemKey::AlbumArtist);
+    FileChecks {
+        album,
+        artist: album_artist.or(artist),
+        genres,
+        checks,
+    }
+}
+
+/// Group genre spellings (with the number of files using each) by the genre they stand for:
+/// the genre map's canonical genre, or else the spellings equal but for case and punctuation.
+fn genre_groups(spellings: &BTreeMap<String, usize>, genre_map: &GenreMap) -> Vec<GenreGroup> {
+    let mut groups: BTreeMap<String, Vec<GenreSpelling>> = BTreeMap::new();
+    for (genre, &file_count) in spellings {
+        let canonical = genre_map.canonical(genre).unwrap_or(genre);
+        groups
+            .entry(genre_key(canonical))
+            .or_default()
+            .push(GenreSpelling {
+                genre: genre.clone(),
+                file_count,
+            });
+    }
+
+    let mut groups: Vec<GenreGroup> = groups
+        .into_values()
+        .map(|mut spellings| {
+            spellings.sort_by_key(|s| Reverse(s.file_count));
+            let canonical = genre_map.canonical(&spellings[0].genre);
+            GenreGroup {
+                suggested: canonical.unwrap_or(&spellings[0].genre).to_string(),
+                canonical: canonical.is_some(),
+                file_count: spellings.iter().map(|s| s.file_count).sum(),
+                spellings,
+            }
+        })
+        .collect();
+    groups.sort_by_key(|group| Reverse(group.file_count));
+    groups
+}
+
+/// Whether a year or date is all zeros: "0", "0000", "0000-00-00".
+fn is_zero_year(year: &str) -> bool {
+    year.starts_with('0')
+        && year
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .all(|c| c == '0')
+}
+
+/// Whether a title was left by a ripper: "Track 01", "Track 1", "Unknown", "Untitled".
+fn is_placeholder_title(title: &str) -> bool {
+    let title = title.to_lowercase();
+    let number = title.strip_prefix("track").map(str::trim_start);
+    number.is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        || matches!(title.as_str(), "unknown" | "unknown title" | "untitled")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lofty::config::WriteOptions;
+    use lofty::tag::TagType;
+    use tempfile::TempDir;
+
+    fn write_flac_fixture(path: &Path, tag: &Tag) {
+        let mut bytes = b"fLaC".to_vec();
+        bytes.extend_from_slice(&[0x00, 0, 0, 34]);
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&[0; 6]);
+        bytes.extend_from_slice(&[0x0A, 0xC4, 0x42, 0xF0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&[0x81, 0, 0, 16]);
+        bytes.extend_from_slice(&[0; 16]);
+        std::fs::write(path, bytes).unwrap();
+        tag.save_to_path(path, WriteOptions::default()).unwrap();
+    }
+
+    fn album_tag(album: &str, title: &str, track: u32) -> Tag {
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.set_title(title.to_string());
+        tag.set_artist("Low".to_string());
+        tag.set_album(album.to_string());
+        tag.set_year(1997);
+        tag.set_track(track);
+        tag
+    }
+
+    #[test]
+    fn test_file_checks() {
+        let checks = file_checks(None);
+        assert_eq!(
+            checks.checks,
+            vec![
+                ReportCheck::MissingTitle,
+                ReportCheck::MissingArtist,
+                ReportCheck::MissingAlbum,
+                ReportCheck::MissingYear,
+                ReportCheck::MissingTrack,
+                ReportCheck::MissingCoverArt,
+                ReportCheck::MissingMbIds,
+            ]
+        );
+
+        let mut tag = album_tag("Album", "Track 03", 3);
+        tag.set_year(0);
+        tag.set_genre("Other".to_string());
+        tag.insert_text(ItemKey::AlbumArtist, "Album Artist".to_string());
+        let checks = file_checks(Some(&tag));
+        assert_eq!(
+            checks.checks,
+            vec![
+                ReportCheck::MissingCoverArt,
+                ReportCheck::MissingMbIds,
+                ReportCheck::YearZero,
+                ReportCheck::PlaceholderTitle,
+                ReportCheck::GenericGenre,
+            ]
+        );
+        assert_eq!(checks.artist.as_deref(), Some("Album Artist"));
+        assert_eq!(checks.album.as_deref(), Some("Album"));
+    }
+
+    #[test]
+    fn test_is_placeholder_title() {
+        assert!(is_placeholder_title("Track 01"));
+        assert!(is_placeholder_title("track1"));
+        assert!(is_placeholder_title("Untitled"));
+        assert!(!is_placeholder_title("Track"));
+        assert!(!is_placeholder_title("Track 01 (Reprise)"));
+        assert!(!is_placeholder_title("Trackdown"));
+    }
+
+    #[test]
+    fn test_collect_files_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("A/CD1")).unwrap();
+        std::fs::create_dir_all(root.join("B")).unwrap();
+        for file in [
+            "01.flac",
+            "A/01.mp3",
+            "A/CD1/01.flac",
+            "B/01.flac",
+            "B/cover.jpg",
+        ] {
+            std::fs::write(root.join(file), b"").unwrap();
+        }
+
+        let relative = |scan: &ScanResult| -> Vec<String> {
+            scan.files
+                .iter()
+                .map(|f| f.strip_prefix(root).unwrap().to_string_lossy().into_owned())
+                .collect()
+        };
+
+        let scan = collect_files(root, 10, 100).unwrap();
+        assert_eq!(
+            relative(&scan),
+            vec!["01.flac", "A/01.mp3", "A/CD1/01.flac", "B/01.flac"]
+        );
+        assert!(!scan.truncated && !scan.depth_limited);
+
+        let scan = collect_files(root, 1, 100).unwrap();
+        assert_eq!(relative(&scan), vec!["01.flac", "A/01.mp3", "B/01.flac"]);
+        assert!(scan.depth_limited);
+
+        let scan = collect_files(root, 10, 2).unwrap();
+        assert_eq!(relative(&scan), vec!["01.flac", "A/01.mp3"]);
+        assert!(scan.truncated);
+    }
+
+    #[test]
+    fn test_metadata_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("Good")).unwrap();
+        std::fs::create_dir_all(root.join("Rip")).unwrap();
+
+        let mut good = album_tag("Good", "Song", 1);
+        good.insert_text(ItemKey::MusicBrainzRecordingId, "id".to_string());
+        good.insert_text(ItemKey::MusicBrainzReleaseId, "id".to_string());
+        good.push_picture(lofty::picture::Picture::new_unchecked(
+            lofty::picture::PictureType::CoverFront,
+            Some(lofty::picture::MimeType::Png),
+            None,
+            vec![0x89, b'P', b'N', b'G'],
+        ));
+        write_flac_fixture(&root.join("Good/01.flac"), &good);
+        for track in 1..=3 {
+            let tag = album_tag("Rip", &format!("Track {:02}", track), track);
+            write_flac_fixture(&root.join(format!("Rip/{:02}.flac", track)), &tag);
+        }
+        std::fs::write(root.join("Rip/broken.flac"), b"not audio").unwrap();
+
+        let params: MetadataReportParams = serde_json::from_value(serde_json::json!({
+            "directory": root.to_string_lossy(),
+            "max_examples": 2
+        }))
+        .unwrap();
+        let data = MetadataReportTool::build_report(&params, &Config::default()).unwrap();
+        assert_eq!(data.file_count, 5);
+        assert_eq!(data.files_with_issues, 4);
+        assert_eq!(data.album_count, 2);
+        assert_eq!(data.albums_with_issues, 1);
+
+        let checks: Vec<(ReportCheck, usize)> =
+            data.checks.iter().map(|c| (c.check, c.count)).collect();
+        assert_eq!(
+            checks,
+            vec![
+                (ReportCheck::Unreadable, 1),
+                (ReportCheck::MissingCoverArt, 3),
+                (ReportCheck::MissingMbIds, 3),
+                (ReportCheck::PlaceholderTitle, 3),
+            ]
+        );
+        assert_eq!(data.checks[1].examples, vec!["Rip/01.flac", "Rip/02.flac"]);
+        assert_eq!(data.albums[0].album.as_deref(), Some("Rip"));
+        assert_eq!(data.albums[0].files_with_issues, 3);
+
+        let summary = MetadataReportTool::build_text_summary(&data);
+        assert!(
+            summary.contains("placeholder_title: 3 (e.g. Rip/01.flac, Rip/02.flac)"),
+            "{}",
+            summary
+        );
+    }
+
+    #[test]
+    fn test_genre_groups() {
+        let spellings = BTreeMap::from([
+            ("Hip Hop".to_string(), 3),
+            ("hiphop".to_string(), 1),
+            ("Rap/Hip Hop".to_string(), 2),
+            ("Shoegaze".to_string(), 1),
+            ("shoegaze".to_string(), 2),
+            ("Jazz".to_string(), 1),
+        ]);
+        let groups = genre_groups(&spellings, &GenreMap::builtin());
+        let summary: Vec<(&str, bool, usize, usize)> = groups
+            .iter()
+            .map(|g| {
+                let spellings = g.spellings.len();
+                (g.suggested.as_str(), g.canonical, g.file_count, spellings)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Hip-Hop", true, 6, 3),
+                ("shoegaze", false, 3, 2),
+                ("Jazz", false, 1, 1),
+            ]
+        );
+        assert_eq!(groups[0].spellings[0].genre, "Hip Hop");
+    }
+
+    #[test]
+    fn test_metadata_report_genre_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for (track, genre) in [(1, "Hip Hop"), (2, "hiphop"), (3, "Hip-Hop")] {
+            let mut tag = album_tag("Album", "Song", track);
+            tag.set_genre(genre.to_string());
+            write_flac_fixture(&root.join(format!("{:02}.flac", track)), &tag);
+        }
+
+        let params: MetadataReportParams = serde_json::from_value(serde_json::json!({
+            "directory": root.to_string_lossy()
+        }))
+        .unwrap();
+        let data = MetadataReportTool::build_report(&params, &Config::default()).unwrap();
+        assert!(data.genres.is_none());
+
+        let params = MetadataReportParams {
+            genre_report: true,
+            ..params
+        };
+        let data = MetadataReportTool::build_report(&params, &Config::default()).unwrap();
+        let genres = data.genres.as_ref().unwrap();
+        assert_eq!(genres.len(), 1);
+        assert_eq!(genres[0].suggested, "Hip-Hop");
+        assert_eq!(genres[0].file_count, 3);
+
+        let summary = MetadataReportTool::build_text_summary(&data);
+        assert!(
+            summary.contains("genres: 3 spelling(s) of 1 genre(s)\nHip-Hop <- "),
+            "{}",
+            summary
+        );
+    }
+
+    #[test]
+    fn test_metadata_report_not_a_directory() {
+        let params: MetadataReportParams =
+            serde_json::from_value(serde_json::json!({ "directory": "/nonexistent/library" }))
+                .unwrap();
+        let result = MetadataReportTool::execute(&params, &Config::default());
+        assert!(result.is_error.unwrap_or(false));
+    }
+}
@@ -0,0 +1,612 @@
+//! Strip metadata tool definition.
+//!
+//! Removes every tag (ID3v1, ID3v2, APE, Vorbis comments, ...) and embedded picture from
+//! audio files, optionally keeping a whitelist of fields such as ReplayGain, before sharing
+//! files or re-tagging them from scratch.
+
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool},
+};
+
+use futures::FutureExt;
+use lofty::config::WriteOptions;
+use lofty::prelude::*;
+use lofty::tag::{Tag, TagType};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::audit;
+use crate::core::config::{Config, Id3Version};
+use crate::core::security::{AccessKind, validate_path_for};
+use crate::domains::tools::definitions::mb::identify_directory::{
+    MAX_DIRECTORY_FILES, MbIdentifyDirectoryTool,
+};
+
+use super::write::{WriteMetadataTool, copied_field_name, copy_tag, same_field};
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_audited_timeout;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the strip metadata tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct StripMetadataParams {
+    /// Audio file, or directory whose audio files are all stripped
+    pub path: String,
+
+    /// Also strip audio files in subdirectories of a directory (default: false)
+    #[serde(default)]
+    pub recursive: bool,
+
+    /// Fields kept and rewritten into a fresh tag: standard names as in write_metadata
+    /// (e.g. "title", "bpm"), format-specific keys (e.g. "CATALOGNUMBER"),
+    /// "replaygain" for every ReplayGain field and "pictures" for embedded pictures
+    #[serde(default)]
+    pub keep_fields: Vec<String>,
+
+    /// If true, report what would be removed without modifying any file
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Copy each file to `<name>.bak` before stripping (defaults to MCP_METADATA_BACKUP_ON_WRITE)
+    #[serde(default)]
+    pub backup: Option<bool>,
+}
+
+// ============================================================================
+// Structured Output Types
+// ============================================================================
+
+/// Structured output for a strip operation.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct StripMetadataResult {
+    pub path: String,
+    /// False for a dry run: nothing was written
+    pub applied: bool,
+    pub results: Vec<FileStripResult>,
+    pub total_count: usize,
+    /// Files that had tags to remove
+    pub stripped_count: usize,
+    pub failure_count: usize,
+}
+
+/// Outcome of stripping a single file.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FileStripResult {
+    pub file: String,
+    pub success: bool,
+    /// Tag types found in the file and removed, e.g. ["Id3v2", "Ape"]
+    pub removed_tag_types: Vec<String>,
+    /// Embedded pictures removed
+    pub removed_pictures: usize,
+    /// Fields kept from `keep_fields` and written back
+    pub kept_fields: Vec<String>,
+    /// Kept fields the file's tag format cannot store, so they were lost
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unsupported_fields: Vec<String>,
+    /// Copy of the file taken before stripping, if a backup was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Strip metadata tool - removes every tag and picture from audio files.
+pub struct StripMetadataTool;
+
+impl StripMetadataTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "strip_metadata";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Remove every tag and embedded picture from audio files.\n\
+         \n\
+         Unlike write_metadata's clear_existing, which only empties the primary tag, this\n\
+         removes all tag types of the file (ID3v1, ID3v2, APE, Vorbis comments, ...) and\n\
+         reports which ones were found. Use keep_fields to write a whitelist back into a\n\
+         fresh tag, e.g. [\"replaygain\"] to keep loudness data or [\"pictures\"] to keep\n\
+         cover art. `path` is a file or a directory (recursive for subfolders).\n\
+         \n\
+         Run with dry_run: true first; backup copies each file before stripping it.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
+    #[instrument(skip_all, fields(path = %params.path, dry_run = params.dry_run))]
+    pub fn execute(params: &StripMetadataParams, config: &Config) -> CallToolResult {
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
+
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &StripMetadataParams) -> Vec<&str> {
+        vec![params.path.as_str()]
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &StripMetadataParams, config: &Config) -> CallToolResult {
+        info!("Strip metadata tool called");
+
+        match Self::strip(params, config) {
+            Ok(data) => {
+                let summary = Self::build_text_summary(&data);
+                info!("{}", summary);
+                match serde_json::to_value(&data) {
+                    Ok(structured) => CallToolResult {
+                        content: vec![Content::text(summary)],
+                        structured_content: Some(structured),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(e) => {
+                        warn!("Failed to serialize structured content: {}", e);
+                        CallToolResult::success(vec![Content::text(summary)])
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Strip metadata failed: {}", e);
+                CallToolResult::error(vec![Content::text(e)])
+            }
+        }
+    }
+
+    /// Strip the file, or every audio file of the directory, sequentially.
+    ///
+    /// Per-file failures are recorded in the results; only an invalid path aborts.
+    fn strip(params: &StripMetadataParams, config: &Config) -> Result<StripMetadataResult, String> {
+        let path = validate_path_for(&params.path, AccessKind::Write, config)
+            .map_err(|e| format!("Path security validation failed: {}", e))?;
+
+        let files = if path.is_dir() {
+            let files = MbIdentifyDirectoryTool::collect_audio_files(&path, params.recursive)
+                .map_err(|e| format!("Cannot read directory: {}", e))?;
+            if files.len() > MAX_DIRECTORY_FILES {
+                return Err(format!(
+                    "Directory contains {} audio files (max {} per call); strip subdirectories separately",
+                    files.len(),
+                    MAX_DIRECTORY_FILES
+                ));
+            }
+            files
+        } else if path.is_file() {
+            vec![path.clone()]
+        } else {
+            return Err(format!("Path is not a file or directory: {}", params.path));
+        };
+
+        let backup = params.backup.unwrap_or(config.metadata.backup_on_write);
+        let mut results = Vec::with_capacity(files.len());
+        for file in files {
+            let mut result = FileStripResult {
+                file: file.to_string_lossy().into_owned(),
+                success: true,
+                removed_tag_types: Vec::new(),
+                removed_pictures: 0,
+                kept_fields: Vec::new(),
+                unsupported_fields: Vec::new(),
+                backup_path: None,
+                error: None,
+            };
+            if let Err(e) = Self::strip_file(&file, params, backup, config, &mut result) {
+                warn!("Failed to strip {}: {}", file.display(), e);
+                result.success = false;
+                result.error = Some(e);
+            }
+            results.push(result);
+        }
+
+        let failure_count = results.iter().filter(|r| !r.success).count();
+        let stripped_count = results
+            .iter()
+            .filter(|r| r.success && !r.removed_tag_types.is_empty())
+            .count();
+        Ok(StripMetadataResult {
+            path: path.to_string_lossy().into_owned(),
+            applied: !params.dry_run,
+            total_count: results.len(),
+            stripped_count,
+            failure_count,
+            results,
+        })
+    }
+
+    /// Remove every tag of one file, then write the kept fields back into a fresh tag of
+    /// the file's primary type.
+    fn strip_file(
+        path: &Path,
+        params: &StripMetadataParams,
+        backup: bool,
+        config: &Config,
+        result: &mut FileStripResult,
+    ) -> Result<(), String> {
+        let tagged_file =
+            lofty::read_from_path(path).map_err(|e| format!("Failed to read audio file: {}", e))?;
+
+        // Empty tags (e.g. a bare ID3v1 header) are not worth reporting
+        let tag_types: Vec<TagType> = tagged_file
+            .tags()
+            .iter()
+            .filter(|tag| !tag.is_empty())
+            .map(Tag::tag_type)
+            .collect();
+        let picture_count: usize = tagged_file.tags().iter().map(|t| t.pictures().len()).sum();
+
+        let mut kept = Tag::new(tagged_file.primary_tag_type());
+        if let Some(source) = tagged_file
+            .primary_tag()
+            .or_else(|| tagged_file.first_tag())
+        {
+            let keep_pictures = params
+                .keep_fields
+                .iter()
+                .any(|field| field.eq_ignore_ascii_case("pictures"));
+            let (copied, unsupported) = copy_tag(
+                &mut kept,
+                &kept_items(source, &params.keep_fields),
+                keep_pictures,
+            );
+            result.kept_fields = copied;
+            result.unsupported_fields = unsupported;
+        }
+
+        result.removed_tag_types = tag_types.iter().map(|t| format!("{:?}", t)).collect();
+        result.removed_pictures = picture_count - kept.pictures().len();
+        if tag_types.is_empty() || params.dry_run {
+            return Ok(());
+        }
+
+        if backup {
+            let backup_path = WriteMetadataTool::create_backup(path, config)
+                .map_err(|e| format!("Failed to create backup, file not modified: {}", e))?;
+            result.backup_path = Some(backup_path.to_string_lossy().into_owned());
+        }
+
+        for tag_type in &tag_types {
+            tag_type
+                .remove_from_path(path)
+                .map_err(|e| format!("Failed to remove {:?} tag: {}", tag_type, e))?;
+        }
+        if !kept.is_empty() {
+            let write_options =
+                WriteOptions::default().use_id3v23(config.metadata.id3_version == Id3Version::V23);
+            kept.save_to_path(path, write_options)
+                .map_err(|e| format!("Failed to write kept fields: {}", e))?;
+        }
+
+        info!(
+            "Stripped {} from {}",
+            result.removed_tag_types.join(", "),
+            path.display()
+        );
+        Ok(())
+    }
+
+    /// Build the summary line, naming the removed tag types for a single file.
+    fn build_text_summary(data: &StripMetadataResult) -> String {
+        if data.total_count == 0 {
+            return format!("No audio files found in {}", data.path);
+        }
+
+        let prefix = if data.applied { "" } else { "DRY RUN: " };
+        let mut summary = match data.results.as_slice() {
+            [result] if !result.success => {
+                return format!(
+                    "Failed to strip {}: {}",
+                    result.file,
+                    result.error.as_deref().unwrap_or_default()
+                );
+            }
+            [result] if result.removed_tag_types.is_empty() => {
+                return format!("{} has no tags to strip", result.file);
+            }
+            [result] => format!(
+                "{}{} {} ({} picture(s)) from {}",
+                prefix,
+                if data.applied {
+                    "Stripped"
+                } else {
+                    "Would strip"
+                },
+                result.removed_tag_types.join(", "),
+                result.removed_pictures,
+                result.file
+            ),
+            _ => format!(
+                "{}{} tags from {}/{} file(s) ({} failed)",
+                prefix,
+                if data.applied {
+                    "Stripped"
+                } else {
+                    "Would strip"
+                },
+                data.stripped_count,
+                data.total_count,
+                data.failure_count
+            ),
+        };
+
+        if let [result] = data.results.as_slice()
+            && !result.kept_fields.is_empty()
+        {
+            summary.push_str(&format!("; kept {}", result.kept_fields.join(", ")));
+        }
+        if !data.applied {
+            summary.push_str("; nothing was written");
+        }
+        summary
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: StripMetadataParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Strip metadata tool (HTTP) called");
+
+        let result = Self::execute(&params, &config);
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<StripMetadataParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: StripMetadataParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// Copy of `source` holding only the items named in `keep_fields`.
+///
+/// Items match by the name write_metadata's `copy_tags_from` reports for them; "replaygain"
+/// matches the four ReplayGain keys.
+fn kept_items(source: &Tag, keep_fields: &[String]) -> Tag {
+    let mut kept = source.clone();
+    kept.retain(|item| {
+        let name = copied_field_name(item.key(), source.tag_type());
+        keep_fields.iter().any(|field| {
+            same_field(field, &name)
+                || (field.eq_ignore_ascii_case("replaygain")
+                    && matches!(
+                        item.key(),
+                        ItemKey::ReplayGainAlbumGain
+                            | ItemKey::ReplayGainAlbumPeak
+                            | ItemKey::ReplayGainTrackGain
+                            | ItemKey::ReplayGainTrackPeak
+                    ))
+        })
+    });
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lofty::picture::{MimeType, Picture, PictureType};
+    use lofty::tag::{ItemValue, TagItem};
+    use tempfile::TempDir;
+
+    fn write_flac_fixture(path: &Path) {
+        let mut bytes = b"fLaC".to_vec();
+        bytes.extend_from_slice(&[0x00, 0, 0, 34]);
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&[0; 6]);
+        bytes.extend_from_slice(&[0x0A, 0xC4, 0x42, 0xF0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&[0x81, 0, 0, 16]);
+        bytes.extend_from_slice(&[0; 16]);
+        std::fs::write(path, bytes).unwrap();
+
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.set_title("Title".to_string());
+        tag.set_artist("Artist".to_string());
+        tag.insert_text(ItemKey::ReplayGainTrackGain, "-6.50 dB".to_string());
+        tag.insert_text(ItemKey::ReplayGainAlbumGain, "-7.10 dB".to_string());
+        tag.push_picture(Picture::new_unchecked(
+            PictureType::CoverFront,
+            Some(MimeType::Png),
+            None,
+            vec![0x89, b'P', b'N', b'G'],
+        ));
+        tag.save_to_path(path, WriteOptions::default()).unwrap();
+    }
+
+    fn strip_params(path: &Path, keep_fields: &[&str], dry_run: bool) -> StripMetadataParams {
+        serde_json::from_value(serde_json::json!({
+            "path": path.to_string_lossy(),
+            "keep_fields": keep_fields,
+            "dry_run": dry_run,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_kept_items() {
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.set_title("Title".to_string());
+        tag.set_artist("Artist".to_string());
+        tag.insert_text(ItemKey::ReplayGainTrackPeak, "0.98".to_string());
+        tag.insert_unchecked(TagItem::new(
+            ItemKey::Unknown("CATALOGNUMBER".to_string()),
+            ItemValue::Text("X-1".to_string()),
+        ));
+
+        let kept = kept_items(&tag, &["replaygain".to_string(), "Artist".to_string()]);
+        assert_eq!(kept.item_count(), 2);
+        assert_eq!(kept.artist().as_deref(), Some("Artist"));
+
+        let kept = kept_items(&tag, &["catalognumber".to_string()]);
+        assert_eq!(kept.item_count(), 1);
+        assert!(kept_items(&tag, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_strip_metadata_dry_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&path);
+        let before = std::fs::read(&path).unwrap();
+
+        let data =
+            StripMetadataTool::strip(&strip_params(&path, &[], true), &Config::default()).unwrap();
+        assert!(!data.applied);
+        assert_eq!(data.stripped_count, 1);
+        assert_eq!(data.results[0].removed_tag_types, vec!["VorbisComments"]);
+        assert_eq!(data.results[0].removed_pictures, 1);
+        assert_eq!(std::fs::read(&path).unwrap(), before);
+    }
+
+    #[test]
+    fn test_strip_metadata_keeps_whitelist() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&path);
+
+        let params = strip_params(&path, &["replaygain"], false);
+        let data = StripMetadataTool::strip(&params, &Config::default()).unwrap();
+        assert!(data.results[0].success, "{:?}", data.results[0].error);
+        assert_eq!(data.results[0].removed_pictures, 1);
+
+        let tagged_file = lofty::read_from_path(&path).unwrap();
+        let tag = tagged_file.primary_tag().unwrap();
+        assert_eq!(tag.title(), None);
+        assert!(tag.pictures().is_empty());
+        assert_eq!(
+            tag.get_string(&ItemKey::ReplayGainTrackGain),
+            Some("-6.50 dB")
+        );
+        assert_eq!(tag.artist(), None);
+
+        let summary = StripMetadataTool::build_text_summary(&data);
+        assert!(
+            summary.starts_with("Stripped VorbisComments (1 picture(s))"),
+            "{}",
+            summary
+        );
+    }
+
+    #[test]
+    fn test_strip_metadata_removes_every_tag_type() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("track.mp3");
+        let mut bytes = Vec::new();
+        for _ in 0..4 {
+            bytes.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x00]);
+            bytes.extend_from_slice(&[0; 413]);
+        }
+        std::fs::write(&path, bytes).unwrap();
+        for tag_type in [TagType::Id3v2, TagType::Ape] {
+            let mut tag = Tag::new(tag_type);
+            tag.set_title("Title".to_string());
+            tag.save_to_path(&path, WriteOptions::default()).unwrap();
+        }
+
+        let params = strip_params(&path, &["title"], false);
+        let data = StripMetadataTool::strip(&params, &Config::default()).unwrap();
+        assert_eq!(data.results[0].removed_tag_types, vec!["Id3v2", "Ape"]);
+        assert_eq!(data.results[0].kept_fields, vec!["title"]);
+
+        let tagged_file = lofty::read_from_path(&path).unwrap();
+        let tag_types: Vec<TagType> = tagged_file.tags().iter().map(Tag::tag_type).collect();
+        assert_eq!(tag_types, vec![TagType::Id3v2]);
+        assert_eq!(
+            tagged_file.primary_tag().unwrap().title().as_deref(),
+            Some("Title")
+        );
+    }
+
+    #[test]
+    fn test_strip_metadata_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        write_flac_fixture(&temp_dir.path().join("01.flac"));
+        write_flac_fixture(&temp_dir.path().join("02.flac"));
+        std::fs::write(temp_dir.path().join("03.flac"), b"not audio").unwrap();
+
+        let params = strip_params(temp_dir.path(), &[], false);
+        let data = StripMetadataTool::strip(&params, &Config::default()).unwrap();
+        assert_eq!(data.total_count, 3);
+        assert_eq!(data.stripped_count, 2);
+        assert_eq!(data.failure_count, 1);
+
+        let tagged_file = lofty::read_from_path(temp_dir.path().join("01.flac")).unwrap();
+        assert!(tagged_file.tags().iter().all(|tag| tag.is_empty()));
+
+        // Nothing left to strip the second time
+        let data = StripMetadataTool::strip(&params, &Config::default()).unwrap();
+        assert_eq!(data.stripped_count, 0);
+    }
+
+    #[test]
+    fn test_strip_metadata_nonexistent() {
+        let params = strip_params(Path::new("/nonexistent/track.flac"), &[], false);
+        let result = StripMetadataTool::execute(&params, &Config::default());
+        assert!(result.is_error.unwrap_or(false));
+    }
+}
@@ -9,22 +9,35 @@ use rmcp::{
 };
 
 use futures::FutureExt;
+use lofty::picture::{Picture, PictureInformation, PictureType};
 use lofty::prelude::*;
+use lofty::tag::{ItemKey, ItemValue, TagItem, TagType};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
+use std::time::SystemTime;
 use tracing::{info, instrument, warn};
 
-use crate::core::config::Config;
-use crate::core::security::validate_path;
+use crate::core::audit;
+use crate::core::config::{Config, Id3Version};
+use crate::core::security::{AccessKind, validate_path, validate_path_for, validate_write_target};
+use crate::domains::tools::definitions::fs::files::write_atomic;
+use crate::domains::tools::definitions::mb::common::extract_mbid;
+use crate::domains::tools::definitions::mb::identify_record::FingerprintMatch;
+
+use super::genre::GenreMap;
+use super::read::{custom_tags, is_compilation};
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_audited_timeout;
 
 // ============================================================================
 // Tool Parameters
 // ============================================================================
 
 /// Parameters for the write metadata tool.
-#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct WriteMetadataParams {
     /// Path to the audio file to modify.
     pub path: String,
@@ -37,6 +50,10 @@ pub struct WriteMetadataParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub artist: Option<String>,
 
+    /// Several artists, written as separate values where the tag format allows it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artists: Option<Vec<String>>,
+
     /// Album name
     #[serde(skip_serializing_if = "Option::is_none")]
     pub album: Option<String>,
@@ -45,10 +62,44 @@ pub struct WriteMetadataParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub album_artist: Option<String>,
 
+    /// Mark the album as a compilation of various artists (COMPILATION / TCMP / cpil), so
+    /// players keep its tracks together instead of splitting it per artist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compilation: Option<bool>,
+
+    /// Artist name used for sorting, e.g. "Beatles, The" (ARTISTSORT / TSOP)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artist_sort: Option<String>,
+
+    /// Album artist name used for sorting (ALBUMARTISTSORT / TSO2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album_artist_sort: Option<String>,
+
+    /// Album title used for sorting (ALBUMSORT / TSOA)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album_sort: Option<String>,
+
+    /// Derive the sort fields not given from the artist, album artist and album:
+    /// "The X" becomes "X, The" and a two-word artist "First Last" becomes "Last, First"
+    #[serde(default)]
+    pub auto_sort_names: bool,
+
     /// Year of release
     #[serde(skip_serializing_if = "Option::is_none")]
     pub year: Option<u32>,
 
+    /// Release date of the original edition, as YYYY or YYYY-MM-DD (ORIGINALDATE / TDOR)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original_date: Option<String>,
+
+    /// Country of the release, e.g. "GB" or "XW" for worldwide (RELEASECOUNTRY)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_country: Option<String>,
+
+    /// Medium format, e.g. "CD", "Digital Media", "12\" Vinyl" (MEDIA / TMED)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<String>,
+
     /// Track number
     #[serde(skip_serializing_if = "Option::is_none")]
     pub track: Option<u32>,
@@ -57,30 +108,205 @@ pub struct WriteMetadataParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub track_total: Option<u32>,
 
+    /// Disc number (starting at 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disc: Option<u32>,
+
+    /// Total discs in album
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disc_total: Option<u32>,
+
     /// Genre
     #[serde(skip_serializing_if = "Option::is_none")]
     pub genre: Option<String>,
 
+    /// Several genres, written as separate values where the tag format allows it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genres: Option<Vec<String>>,
+
+    /// Replace genre or genres with their canonical spelling from the genre map before
+    /// writing, e.g. "hiphop" and "Rap/Hip Hop" become "Hip-Hop" (default: false)
+    #[serde(default)]
+    pub normalize_genre: bool,
+
     /// Comment
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
 
-    /// If true, clear all existing tags before writing new ones
+    /// Composer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub composer: Option<String>,
+
+    /// Conductor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conductor: Option<String>,
+
+    /// Lyricist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lyricist: Option<String>,
+
+    /// Remixer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remixer: Option<String>,
+
+    /// Performers (e.g. "Miles Davis (trumpet)"), replacing existing performer credits
+    #[serde(default)]
+    pub performers: Vec<String>,
+
+    /// Unsynchronized lyrics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lyrics: Option<String>,
+
+    /// Path to a .txt or .lrc file to embed as unsynchronized lyrics (LRC timestamps are stripped)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lyrics_file: Option<String>,
+
+    /// Beats per minute (BPM / TBPM)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bpm: Option<u32>,
+
+    /// ReplayGain track gain, e.g. "-6.50 dB" (REPLAYGAIN_TRACK_GAIN)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaygain_track_gain: Option<String>,
+
+    /// ReplayGain track peak, e.g. "0.988547" (REPLAYGAIN_TRACK_PEAK)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaygain_track_peak: Option<String>,
+
+    /// ReplayGain album gain, e.g. "-7.10 dB" (REPLAYGAIN_ALBUM_GAIN)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaygain_album_gain: Option<String>,
+
+    /// ReplayGain album peak, e.g. "1.000000" (REPLAYGAIN_ALBUM_PEAK)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaygain_album_peak: Option<String>,
+
+    /// MusicBrainz recording ID (MUSICBRAINZ_TRACKID)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mb_recording_id: Option<String>,
+
+    /// MusicBrainz release ID (MUSICBRAINZ_ALBUMID)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mb_release_id: Option<String>,
+
+    /// MusicBrainz artist ID (MUSICBRAINZ_ARTISTID)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mb_artist_id: Option<String>,
+
+    /// MusicBrainz release group ID (MUSICBRAINZ_RELEASEGROUPID)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mb_release_group_id: Option<String>,
+
+    /// Other tags by name, e.g. {"CATALOGNUMBER": "XL 123", "MEDIA": "CD"}; unknown names are
+    /// stored as user-defined tags (TXXX in ID3v2, freeform atoms in MP4)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_tags: Option<HashMap<String, String>>,
+
+    /// Path to an image file (JPEG, PNG, GIF, BMP, TIFF) to embed as the front cover, replacing existing front covers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_art_path: Option<String>,
+
+    /// Audio file whose primary tag is copied first (every text tag, e.g. from the FLAC a
+    /// file was encoded from); explicit fields in the same call override copied values
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copy_tags_from: Option<String>,
+
+    /// With copy_tags_from, also copy the embedded pictures (default: false)
+    #[serde(default)]
+    pub include_pictures: bool,
+
+    /// Structured output of mb_identify_record; the title, artists, recording ID and release
+    /// group of the selected match fill the fields not given explicitly
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_identification: Option<serde_json::Value>,
+
+    /// With from_identification, rank of the match to apply (default: the top match)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identification_rank: Option<usize>,
+
+    /// Fields to remove before applying updates: title, artist, album, album_artist, compilation, year,
+    /// original_date, release_country, media, track,
+    /// track_total, disc, disc_total, genre, comment, composer, conductor, lyricist, remixer,
+    /// performers, artist_sort, album_artist_sort, album_sort, lyrics, bpm, replaygain_track_gain,
+    /// replaygain_track_peak, replaygain_album_gain, replaygain_album_peak, mb_recording_id, mb_release_id, mb_artist_id,
+    /// mb_release_group_id, or a format-specific key (e.g. "TCOM", "COMPOSER")
+    #[serde(default)]
+    pub remove_fields: Vec<String>,
+
+    /// If true, clear the primary tag before writing new ones (other tag types, e.g. APE
+    /// on an MP3, are kept; use strip_metadata to remove them). BPM and ReplayGain values are
+    /// kept unless listed in remove_fields
     #[serde(default)]
     pub clear_existing: bool,
+
+    /// If true, validate and compute the changes without writing the file
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Copy the file to `<name>.bak` before writing (defaults to MCP_METADATA_BACKUP_ON_WRITE)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<bool>,
+
+    /// Keep the file's modification time unchanged (defaults to MCP_METADATA_PRESERVE_MTIME)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preserve_mtime: Option<bool>,
+
+    /// ID3v2 version for MP3 files, "2.3" or "2.4" (defaults to MCP_METADATA_ID3_VERSION);
+    /// ignored for other formats
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id3_version: Option<Id3Version>,
 }
 
 // ============================================================================
 // Structured Output Types
 // ============================================================================
 
+/// How a list of values (artists, genres) was stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiValueStrategy {
+    /// One tag value per entry (Vorbis Comments, ID3v2.4, MP4)
+    MultipleValues,
+    /// A single value joined with "; " (formats without multi-value support)
+    Joined,
+}
+
+/// Old and new value of a field changed by a write.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct FieldChange {
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
 /// Structured output for metadata write results.
 #[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct MetadataWriteResult {
     pub file: String,
+    /// False for a dry run: nothing was written
+    pub applied: bool,
     pub clear_existing: bool,
     pub fields_updated: usize,
     pub updated_fields: HashMap<String, String>,
+    /// Source file of `copy_tags_from`
+    pub copied_from: Option<String>,
+    /// Fields taken from `copy_tags_from` and not overridden or removed in the same call
+    pub copied_fields: Vec<String>,
+    /// Rank of the `from_identification` match that was applied
+    pub identification_rank: Option<usize>,
+    /// Requested fields that were present and removed
+    pub removed_fields: Vec<String>,
+    /// How `artists` and `genres` were written, if given
+    pub multi_value_strategy: Option<MultiValueStrategy>,
+    /// Requested fields the file's tag format cannot store (e.g. performers in ID3v2)
+    pub unsupported_fields: Vec<String>,
+    /// Every field whose value changes, including fields dropped by clear_existing
+    pub changes: BTreeMap<String, FieldChange>,
+    /// Copy of the file taken before writing, if a backup was requested
+    pub backup_path: Option<String>,
+    /// True when the file's modification time was restored after writing
+    pub mtime_preserved: bool,
+    /// ID3v2 version of the tag written, for MP3 files
+    pub id3_version: Option<Id3Version>,
 }
 
 // ============================================================================
@@ -100,72 +326,346 @@ impl WriteMetadataTool {
          Only provided fields will be updated.";
 
     /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
     #[instrument(skip_all, fields(path = %params.path))]
     pub fn execute(params: &WriteMetadataParams, config: &Config) -> CallToolResult {
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
+
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &WriteMetadataParams) -> Vec<&str> {
+        vec![params.path.as_str()]
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &WriteMetadataParams, config: &Config) -> CallToolResult {
         info!("Write metadata tool called for path: {}", params.path);
 
+        match Self::write_file(params, config) {
+            Ok(data) => {
+                let summary = Self::build_text_summary(&data);
+                match serde_json::to_value(&data) {
+                    Ok(structured) => CallToolResult {
+                        content: vec![Content::text(summary)],
+                        structured_content: Some(structured),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(e) => {
+                        warn!("Failed to serialize structured content: {}", e);
+                        // Fallback to text-only
+                        CallToolResult::success(vec![Content::text(summary)])
+                    }
+                }
+            }
+            Err(e) => CallToolResult::error(vec![Content::text(e)]),
+        }
+    }
+
+    /// Validate the parameters and write the tags of a single file.
+    ///
+    /// Parameters are checked before the file is touched; errors are returned as the message
+    /// to show the client.
+    pub(crate) fn write_file(
+        params: &WriteMetadataParams,
+        config: &Config,
+    ) -> Result<MetadataWriteResult, String> {
         // Validate path security first
-        let path = match validate_path(&params.path, config) {
+        let path = match validate_path_for(&params.path, AccessKind::Write, config) {
             Ok(p) => p,
             Err(e) => {
                 warn!("Path security validation failed: {}", e);
-                return CallToolResult::error(vec![Content::text(format!(
-                    "Path security validation failed: {}",
-                    e
-                ))]);
+                return Err(format!("Path security validation failed: {}", e));
             }
         };
 
         // Validate it's a file
         if !path.is_file() {
             warn!("Path is not a file: {}", params.path);
-            return CallToolResult::error(vec![Content::text(format!(
-                "Path is not a file: {}",
-                params.path
-            ))]);
+            return Err(format!("Path is not a file: {}", params.path));
         }
 
+        // Identification data only fills the fields left unset
+        let identified = params
+            .from_identification
+            .as_ref()
+            .map(|identification| Self::apply_identification(params, identification))
+            .transpose()?;
+        let (params, identification_rank) = match &identified {
+            Some((identified, rank)) => (identified, Some(*rank)),
+            None => (params, None),
+        };
+
+        Self::validate_fields(params)?;
+
+        // Canonical genre spellings replace the given ones
+        let normalized;
+        let has_genre = params.genre.is_some() || params.genres.is_some();
+        let params = if params.normalize_genre && has_genre {
+            normalized = Self::normalize_genres(params, &GenreMap::load(&config.metadata)?);
+            &normalized
+        } else {
+            params
+        };
+
+        // Validate MusicBrainz IDs before touching the audio file
+        let mb_ids = match Self::mb_id_updates(params) {
+            Ok(ids) => ids,
+            Err(e) => {
+                warn!("{}", e);
+                return Err(e);
+            }
+        };
+
+        // Load the lyrics before touching the audio file
+        let lyrics = match Self::load_lyrics(params, config) {
+            Ok(lyrics) => lyrics,
+            Err(e) => {
+                warn!("Failed to load lyrics: {}", e);
+                return Err(e);
+            }
+        };
+
+        // Load the cover art before touching the audio file
+        let cover_art = match params
+            .cover_art_path
+            .as_deref()
+            .map(|cover_path| Self::load_cover_art(cover_path, config))
+            .transpose()
+        {
+            Ok(cover_art) => cover_art,
+            Err(e) => {
+                warn!("Failed to load cover art: {}", e);
+                return Err(e);
+            }
+        };
+
+        // Load the tag to copy before touching the audio file
+        let source_tag = match params
+            .copy_tags_from
+            .as_deref()
+            .map(|source| Self::load_source_tag(source, config))
+            .transpose()
+        {
+            Ok(source_tag) => source_tag,
+            Err(e) => {
+                warn!("Failed to load tags to copy: {}", e);
+                return Err(e);
+            }
+        };
+
         // Read the audio file
         let mut tagged_file = match lofty::read_from_path(&path) {
             Ok(file) => file,
             Err(e) => {
                 warn!("Failed to read audio file: {}", e);
-                return CallToolResult::error(vec![Content::text(format!(
-                    "Failed to read audio file: {}",
-                    e
-                ))]);
+                return Err(format!("Failed to read audio file: {}", e));
             }
         };
 
-        // Get or create primary tag
-        let tag = if params.clear_existing {
-            // Clear existing and create new tag
-            tagged_file.clear();
-            match tagged_file.primary_tag_mut() {
-                Some(t) => t,
-                None => {
-                    // If no tag exists, we need to create one
-                    let tag_type = tagged_file.primary_tag_type();
-                    let new_tag = lofty::tag::Tag::new(tag_type);
-                    tagged_file.insert_tag(new_tag);
-                    tagged_file.primary_tag_mut().expect("Just inserted tag")
-                }
-            }
+        // Keep the current tag to report what changes
+        let before = tagged_file.primary_tag().cloned();
+
+        let tag = Self::prepare_tag(&mut tagged_file, before.as_ref(), params.clear_existing);
+
+        let mut unsupported_fields = Vec::new();
+
+        // Copy the source tag first, so removals and explicit fields apply on top of it
+        let mut copied_fields = Vec::new();
+        if let Some(source_tag) = &source_tag {
+            let (copied, unsupported) = copy_tag(tag, source_tag, params.include_pictures);
+            copied_fields = copied;
+            unsupported_fields.extend(unsupported);
+        }
+
+        // Remove fields before applying updates, so a field can be both removed and rewritten
+        let removed_fields = Self::remove_fields(tag, &params.remove_fields);
+
+        let mut updated_fields = HashMap::new();
+
+        let multi_value_strategy =
+            Self::write_standard_fields(tag, params, &mut updated_fields, &mut unsupported_fields);
+        Self::write_extended_fields(
+            tag,
+            params,
+            lyrics,
+            &mut updated_fields,
+            &mut unsupported_fields,
+        );
+        Self::write_mb_ids(tag, mb_ids, &mut updated_fields, &mut unsupported_fields);
+
+        Self::write_cover_art(tag, cover_art, &mut updated_fields);
+
+        // Report as copied only the fields the copy still decides
+        copied_fields.retain(|copied| {
+            !updated_fields
+                .keys()
+                .chain(&removed_fields)
+                .any(|field| same_field(field, copied))
+        });
+
+        let changes = diff_tags(before.as_ref(), tag);
+
+        // Only MP3 files get the configured ID3v2 version
+        let id3_version = (tagged_file.file_type() == lofty::file::FileType::Mpeg)
+            .then(|| params.id3_version.unwrap_or(config.metadata.id3_version));
+
+        let (backup_path, mtime_preserved) =
+            Self::save(&tagged_file, &path, params, config, id3_version)?;
+
+        if params.dry_run {
+            info!(
+                "Dry run for {}: {} field(s) would change",
+                params.path,
+                changes.len()
+            );
         } else {
-            match tagged_file.primary_tag_mut() {
-                Some(t) => t,
-                None => {
-                    // Create new tag if none exists
-                    let tag_type = tagged_file.primary_tag_type();
-                    let new_tag = lofty::tag::Tag::new(tag_type);
-                    tagged_file.insert_tag(new_tag);
-                    tagged_file.primary_tag_mut().expect("Just inserted tag")
-                }
+            info!(
+                "Successfully wrote metadata to {} ({} fields updated, {} removed)",
+                params.path,
+                updated_fields.len(),
+                removed_fields.len()
+            );
+        }
+
+        // Build structured result
+        Ok(MetadataWriteResult {
+            file: params.path.clone(),
+            applied: !params.dry_run,
+            clear_existing: params.clear_existing,
+            fields_updated: updated_fields.len(),
+            updated_fields,
+            copied_from: params.copy_tags_from.clone(),
+            copied_fields,
+            identification_rank,
+            removed_fields,
+            multi_value_strategy,
+            unsupported_fields,
+            changes,
+            backup_path,
+            mtime_preserved,
+            id3_version,
+        })
+    }
+
+    /// Check the field values, before the file is touched.
+    fn validate_fields(params: &WriteMetadataParams) -> Result<(), String> {
+        if params.artist.is_some() && params.artists.is_some() {
+            return Err("Provide either artist or artists, not both".to_string());
+        }
+        if params.genre.is_some() && params.genres.is_some() {
+            return Err("Provide either genre or genres, not both".to_string());
+        }
+
+        if let Some(custom_tags) = &params.custom_tags
+            && let Some(key) = custom_tags.keys().find(|key| !is_valid_custom_key(key))
+        {
+            return Err(format!(
+                "Invalid custom tag name '{}': use letters, digits, spaces and _ - . : only",
+                key
+            ));
+        }
+
+        // Disc numbers start at 1
+        if params.disc == Some(0) || params.disc_total == Some(0) {
+            warn!("Invalid disc number for {}", params.path);
+            return Err("Disc number and disc total must be at least 1".to_string());
+        }
+
+        if let Some(date) = &params.original_date
+            && !is_valid_date(date)
+        {
+            return Err(format!(
+                "Invalid original date '{}': use YYYY or YYYY-MM-DD",
+                date
+            ));
+        }
+
+        // Players parse ReplayGain values, so reject what they could not read
+        let gains = [
+            ("replaygain_track_gain", &params.replaygain_track_gain),
+            ("replaygain_album_gain", &params.replaygain_album_gain),
+        ];
+        for (field, gain) in gains {
+            if let Some(gain) = gain
+                && !is_valid_gain(gain)
+            {
+                return Err(format!(
+                    "Invalid {} '{}': use a number of decibels, e.g. \"-6.50 dB\"",
+                    field, gain
+                ));
             }
-        };
+        }
+        let peaks = [
+            ("replaygain_track_peak", &params.replaygain_track_peak),
+            ("replaygain_album_peak", &params.replaygain_album_peak),
+        ];
+        for (field, peak) in peaks {
+            if let Some(peak) = peak
+                && !is_valid_peak(peak)
+            {
+                return Err(format!(
+                    "Invalid {} '{}': use a non-negative number, e.g. \"0.988547\"",
+                    field, peak
+                ));
+            }
+        }
+        Ok(())
+    }
 
-        let mut updated_fields = HashMap::new();
+    /// Clear the tag if requested and create it if the file has none.
+    fn prepare_tag<'a>(
+        tagged_file: &'a mut lofty::file::TaggedFile,
+        before: Option<&lofty::tag::Tag>,
+        clear_existing: bool,
+    ) -> &'a mut lofty::tag::Tag {
+        if clear_existing {
+            tagged_file.clear();
+        }
+        if tagged_file.primary_tag().is_none() {
+            let tag_type = tagged_file.primary_tag_type();
+            tagged_file.insert_tag(lofty::tag::Tag::new(tag_type));
+        }
+        let tag = tagged_file.primary_tag_mut().expect("Primary tag exists");
+
+        // Values measured from the audio survive clear_existing; remove_fields still applies
+        if clear_existing && let Some(before) = before {
+            for item in before
+                .items()
+                .filter(|item| MEASURED_KEYS.contains(item.key()))
+            {
+                tag.push(item.clone());
+            }
+        }
+        tag
+    }
+
+    /// Remove the named fields, returning those the tag held.
+    fn remove_fields(tag: &mut lofty::tag::Tag, names: &[String]) -> Vec<String> {
+        let mut removed_fields = Vec::new();
+        for name in names {
+            let name = name.trim();
+            if name.is_empty() || removed_fields.iter().any(|f: &String| f == name) {
+                continue;
+            }
+            if Self::remove_field(tag, name) {
+                removed_fields.push(name.to_string());
+            }
+        }
+        removed_fields
+    }
 
+    /// Write the common fields, returning how multiple artists or genres were stored.
+    fn write_standard_fields(
+        tag: &mut lofty::tag::Tag,
+        params: &WriteMetadataParams,
+        updated_fields: &mut HashMap<String, String>,
+        unsupported_fields: &mut Vec<String>,
+    ) -> Option<MultiValueStrategy> {
         // Update title
         if let Some(title) = &params.title {
             tag.set_title(title.clone());
@@ -186,10 +686,19 @@ impl WriteMetadataTool {
 
         // Update album artist
         if let Some(album_artist) = &params.album_artist {
-            tag.insert_text(lofty::tag::ItemKey::AlbumArtist, album_artist.clone());
+            tag.insert_text(ItemKey::AlbumArtist, album_artist.clone());
             updated_fields.insert("album_artist".to_string(), album_artist.clone());
         }
 
+        // Update compilation flag
+        if let Some(compilation) = params.compilation {
+            if Self::write_compilation(tag, compilation) {
+                updated_fields.insert("compilation".to_string(), compilation.to_string());
+            } else {
+                unsupported_fields.push("compilation".to_string());
+            }
+        }
+
         // Update year
         if let Some(year) = params.year {
             tag.set_year(year);
@@ -208,86 +717,771 @@ impl WriteMetadataTool {
             updated_fields.insert("track_total".to_string(), track_total.to_string());
         }
 
+        // Update disc number
+        if let Some(disc) = params.disc {
+            tag.set_disk(disc);
+            updated_fields.insert("disc".to_string(), disc.to_string());
+        }
+
+        // Update disc total
+        if let Some(disc_total) = params.disc_total {
+            tag.set_disk_total(disc_total);
+            updated_fields.insert("disc_total".to_string(), disc_total.to_string());
+        }
+
         // Update genre
         if let Some(genre) = &params.genre {
             tag.set_genre(genre.clone());
             updated_fields.insert("genre".to_string(), genre.clone());
         }
 
+        // Update artist and genre lists
+        let mut multi_value_strategy = None;
+        let lists = [
+            ("artists", ItemKey::TrackArtist, &params.artists),
+            ("genres", ItemKey::Genre, &params.genres),
+        ];
+        for (field, key, values) in lists {
+            let values: Vec<&str> = values
+                .iter()
+                .flatten()
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .collect();
+            if values.is_empty() {
+                continue;
+            }
+            multi_value_strategy = Some(Self::write_values(tag, key, &values));
+            updated_fields.insert(field.to_string(), values.join("; "));
+        }
+
         // Update comment
         if let Some(comment) = &params.comment {
             tag.set_comment(comment.clone());
             updated_fields.insert("comment".to_string(), comment.clone());
         }
+        multi_value_strategy
+    }
+
+    /// Write the credits, release details, lyrics and custom tags.
+    fn write_extended_fields(
+        tag: &mut lofty::tag::Tag,
+        params: &WriteMetadataParams,
+        lyrics: Option<String>,
+        updated_fields: &mut HashMap<String, String>,
+        unsupported_fields: &mut Vec<String>,
+    ) {
+        // Update credits, release details and ReplayGain, which not every tag format can store
+        let credits = [
+            (
+                "original_date",
+                ItemKey::OriginalReleaseDate,
+                &params.original_date,
+            ),
+            ("media", ItemKey::OriginalMediaType, &params.media),
+            ("composer", ItemKey::Composer, &params.composer),
+            ("conductor", ItemKey::Conductor, &params.conductor),
+            ("lyricist", ItemKey::Lyricist, &params.lyricist),
+            ("remixer", ItemKey::Remixer, &params.remixer),
+            (
+                "replaygain_track_gain",
+                ItemKey::ReplayGainTrackGain,
+                &params.replaygain_track_gain,
+            ),
+            (
+                "replaygain_track_peak",
+                ItemKey::ReplayGainTrackPeak,
+                &params.replaygain_track_peak,
+            ),
+            (
+                "replaygain_album_gain",
+                ItemKey::ReplayGainAlbumGain,
+                &params.replaygain_album_gain,
+            ),
+            (
+                "replaygain_album_peak",
+                ItemKey::ReplayGainAlbumPeak,
+                &params.replaygain_album_peak,
+            ),
+        ];
+        for (field, key, value) in credits {
+            if let Some(value) = value {
+                if tag.insert_text(key, value.clone()) {
+                    updated_fields.insert(field.to_string(), value.clone());
+                } else {
+                    unsupported_fields.push(field.to_string());
+                }
+            }
+        }
+
+        // BPM is a text item in Vorbis comments and MP4 freeform atoms, an integer frame (TBPM) in ID3v2
+        if let Some(bpm) = params.bpm {
+            let bpm = bpm.to_string();
+            tag.remove_key(&ItemKey::Bpm);
+            tag.remove_key(&ItemKey::IntegerBpm);
+            if tag.insert_text(ItemKey::Bpm, bpm.clone())
+                || tag.insert_text(ItemKey::IntegerBpm, bpm.clone())
+            {
+                updated_fields.insert("bpm".to_string(), bpm);
+            } else {
+                unsupported_fields.push("bpm".to_string());
+            }
+        }
+
+        // lofty has no generic key for the release country
+        if let Some(country) = &params.release_country {
+            match release_country_key(tag.tag_type()) {
+                Some(key) => {
+                    tag.remove_key(&key);
+                    tag.insert_unchecked(TagItem::new(key, ItemValue::Text(country.clone())));
+                    updated_fields.insert("release_country".to_string(), country.clone());
+                }
+                None => unsupported_fields.push("release_country".to_string()),
+            }
+        }
+
+        // Update sort names, derived from the updated tag with auto_sort_names
+        let sort_names = [
+            (
+                "artist_sort",
+                ItemKey::TrackArtistSortOrder,
+                &params.artist_sort,
+            ),
+            (
+                "album_artist_sort",
+                ItemKey::AlbumArtistSortOrder,
+                &params.album_artist_sort,
+            ),
+            (
+                "album_sort",
+                ItemKey::AlbumTitleSortOrder,
+                &params.album_sort,
+            ),
+        ];
+        for (field, key, value) in sort_names {
+            let value = match value {
+                Some(value) => Some(value.clone()),
+                None if params.auto_sort_names => derived_sort_name(tag, &key),
+                None => None,
+            };
+            let Some(value) = value else {
+                continue;
+            };
+            if tag.insert_text(key, value.clone()) {
+                updated_fields.insert(field.to_string(), value);
+            } else {
+                unsupported_fields.push(field.to_string());
+            }
+        }
+
+        // Replace performers
+        let performers: Vec<&str> = params
+            .performers
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if !performers.is_empty() {
+            tag.remove_key(&ItemKey::Performer);
+            let written = performers.iter().all(|performer| {
+                tag.push(TagItem::new(
+                    ItemKey::Performer,
+                    ItemValue::Text(performer.to_string()),
+                ))
+            });
+            if written {
+                updated_fields.insert("performers".to_string(), performers.join("; "));
+            } else {
+                unsupported_fields.push("performers".to_string());
+            }
+        }
+
+        // Update lyrics
+        if let Some(lyrics) = lyrics {
+            let description = describe_lyrics(&lyrics);
+            if tag.insert_text(ItemKey::Lyrics, lyrics) {
+                updated_fields.insert("lyrics".to_string(), description);
+            } else {
+                unsupported_fields.push("lyrics".to_string());
+            }
+        }
+
+        // Update custom tags, in name order for a stable result
+        if let Some(custom_tags) = &params.custom_tags {
+            let mut custom_tags: Vec<_> = custom_tags.iter().collect();
+            custom_tags.sort();
+            for (name, value) in custom_tags {
+                if Self::write_custom_tag(tag, name, value) {
+                    updated_fields.insert(name.clone(), value.clone());
+                } else {
+                    unsupported_fields.push(name.clone());
+                }
+            }
+        }
+    }
+
+    /// Replace the front cover with the loaded picture.
+    fn write_cover_art(
+        tag: &mut lofty::tag::Tag,
+        cover_art: Option<(Picture, String)>,
+        updated_fields: &mut HashMap<String, String>,
+    ) {
+        if let Some((picture, description)) = cover_art {
+            tag.remove_picture_type(PictureType::CoverFront);
+            tag.push_picture(picture);
+            updated_fields.insert("cover_art".to_string(), description);
+        }
+    }
+
+    /// Back up and save the file, returning the backup path and whether the mtime was kept.
+    fn save(
+        tagged_file: &lofty::file::TaggedFile,
+        path: &Path,
+        params: &WriteMetadataParams,
+        config: &Config,
+        id3_version: Option<Id3Version>,
+    ) -> Result<(Option<String>, bool), String> {
+        // Back up the original file, never writing without the requested backup
+        let backup = params.backup.unwrap_or(config.metadata.backup_on_write);
+        let backup_path = if backup && !params.dry_run {
+            match Self::create_backup(path, config) {
+                Ok(backup_path) => Some(backup_path.to_string_lossy().to_string()),
+                Err(e) => {
+                    warn!("Failed to back up {}: {}", params.path, e);
+                    return Err(format!("Failed to create backup, file not modified: {}", e));
+                }
+            }
+        } else {
+            None
+        };
+
+        // Record the modification time to restore after saving
+        let preserve_mtime = params
+            .preserve_mtime
+            .unwrap_or(config.metadata.preserve_mtime);
+        let modified = if preserve_mtime && !params.dry_run {
+            std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .map_err(|e| warn!("Cannot read modification time of {}: {}", params.path, e))
+                .ok()
+        } else {
+            None
+        };
 
         // Save changes to file
-        let write_options = lofty::config::WriteOptions::default();
+        if !params.dry_run {
+            let write_options = lofty::config::WriteOptions::default()
+                .use_id3v23(id3_version == Some(Id3Version::V23));
 
-        if let Err(e) = tagged_file.save_to_path(&path, write_options) {
-            warn!("Failed to save metadata: {}", e);
-            return CallToolResult::error(vec![Content::text(format!(
-                "Failed to save metadata: {}",
-                e
-            ))]);
+            if let Err(e) = tagged_file.save_to_path(path, write_options) {
+                warn!("Failed to save metadata: {}", e);
+                return Err(format!("Failed to save metadata: {}", e));
+            }
         }
 
-        // Build structured result
-        let fields_count = updated_fields.len();
-        let structured_data = MetadataWriteResult {
-            file: params.path.clone(),
-            clear_existing: params.clear_existing,
-            fields_updated: fields_count,
-            updated_fields: updated_fields.clone(),
+        // A failure here leaves the tags written, so it is reported rather than returned
+        let mtime_preserved = match modified {
+            Some(modified) => match Self::restore_mtime(path, modified) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!(
+                        "Failed to restore modification time of {}: {}",
+                        params.path, e
+                    );
+                    false
+                }
+            },
+            None => false,
         };
 
-        // Build concise text summary
-        let summary = if fields_count == 0 {
-            format!("No fields updated for '{}'", params.path)
+        Ok((backup_path, mtime_preserved))
+    }
+
+    /// Build the text summary for a single-file write.
+    fn build_text_summary(data: &MetadataWriteResult) -> String {
+        let mut summary = if !data.applied {
+            if data.changes.is_empty() {
+                format!("DRY RUN: no changes for '{}'", data.file)
+            } else {
+                let field_names: Vec<&str> = data.changes.keys().map(|k| k.as_str()).collect();
+                format!(
+                    "DRY RUN: {} field(s) would change in '{}': {}; nothing was written",
+                    data.changes.len(),
+                    data.file,
+                    field_names.join(", ")
+                )
+            }
+        } else if let Some(source) = data
+            .copied_from
+            .as_ref()
+            .filter(|_| !data.copied_fields.is_empty())
+        {
+            let mut summary = format!(
+                "Copied {} field(s) from '{}' to '{}': {}",
+                data.copied_fields.len(),
+                source,
+                data.file,
+                data.copied_fields.join(", ")
+            );
+            if data.fields_updated > 0 {
+                let field_names: Vec<&str> =
+                    data.updated_fields.keys().map(|k| k.as_str()).collect();
+                summary.push_str(&format!(
+                    "; updated {} field(s): {}",
+                    data.fields_updated,
+                    field_names.join(", ")
+                ));
+            }
+            summary
+        } else if data.fields_updated == 0 && !data.removed_fields.is_empty() {
+            format!(
+                "Removed {} field(s) in '{}': {}",
+                data.removed_fields.len(),
+                data.file,
+                data.removed_fields.join(", ")
+            )
+        } else if data.fields_updated == 0 {
+            format!("No fields updated for '{}'", data.file)
         } else {
-            let field_names: Vec<&str> = updated_fields.keys().map(|k| k.as_str()).collect();
-            if params.clear_existing {
+            let field_names: Vec<&str> = data.updated_fields.keys().map(|k| k.as_str()).collect();
+            if data.clear_existing {
                 format!(
                     "Cleared and updated {} field(s) in '{}': {}",
-                    fields_count,
-                    params.path,
+                    data.fields_updated,
+                    data.file,
                     field_names.join(", ")
                 )
             } else {
                 format!(
                     "Updated {} field(s) in '{}': {}",
-                    fields_count,
-                    params.path,
+                    data.fields_updated,
+                    data.file,
                     field_names.join(", ")
                 )
             }
         };
-
-        info!(
-            "Successfully wrote metadata to {} ({} fields updated)",
-            params.path,
-            fields_count
-        );
-
-        // Return structured result
-        match serde_json::to_value(&structured_data) {
-            Ok(structured) => CallToolResult {
-                content: vec![Content::text(summary)],
-                structured_content: Some(structured),
-                is_error: Some(false),
-                meta: None,
-            },
-            Err(e) => {
-                warn!("Failed to serialize structured content: {}", e);
-                // Fallback to text-only
-                CallToolResult::success(vec![Content::text(summary)])
-            }
+        if data.applied
+            && (data.fields_updated > 0 || !data.copied_fields.is_empty())
+            && !data.removed_fields.is_empty()
+        {
+            summary.push_str(&format!(
+                "; removed {} field(s): {}",
+                data.removed_fields.len(),
+                data.removed_fields.join(", ")
+            ));
+        }
+        if let Some(rank) = data.identification_rank {
+            summary.push_str(&format!(" (identification match #{})", rank));
+        }
+        if !data.unsupported_fields.is_empty() {
+            summary.push_str(&format!(
+                " (not supported by this file's tag format: {})",
+                data.unsupported_fields.join(", ")
+            ));
+        }
+        if let Some(backup_path) = &data.backup_path {
+            summary.push_str(&format!(" (backup: {})", backup_path));
         }
+        summary
     }
 
-    /// HTTP handler for this tool (for HTTP transport).
-    #[cfg(feature = "http")]
-    pub fn http_handler(
-        arguments: serde_json::Value,
+    /// Resolve the lyrics to embed from `lyrics` or `lyrics_file`.
+    ///
+    /// Lyrics files are path-validated and must be UTF-8; timestamps are stripped from `.lrc`
+    /// files. Both sources are capped at `tools.max_lyrics_bytes`.
+    fn load_lyrics(
+        params: &WriteMetadataParams,
+        config: &Config,
+    ) -> Result<Option<String>, String> {
+        let max_bytes = config.tools.max_lyrics_bytes;
+        let lyrics = match (&params.lyrics, &params.lyrics_file) {
+            (Some(_), Some(_)) => {
+                return Err("Provide either lyrics or lyrics_file, not both".to_string());
+            }
+            (Some(lyrics), None) => lyrics.clone(),
+            (None, Some(lyrics_file)) => {
+                let path = validate_path(lyrics_file, config)
+                    .map_err(|e| format!("Lyrics path security validation failed: {}", e))?;
+                if !path.is_file() {
+                    return Err(format!("Lyrics path is not a file: {}", lyrics_file));
+                }
+                let size = std::fs::metadata(&path)
+                    .map_err(|e| format!("Failed to read lyrics file: {}", e))?
+                    .len();
+                if size > max_bytes as u64 {
+                    return Err(format!(
+                        "Lyrics file is {} bytes (max {} bytes, see MCP_MAX_LYRICS_BYTES)",
+                        size, max_bytes
+                    ));
+                }
+                let text = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read lyrics file: {}", e))?;
+                let is_lrc = path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("lrc"));
+                if is_lrc {
+                    strip_lrc_timestamps(&text)
+                } else {
+                    text
+                }
+            }
+            (None, None) => return Ok(None),
+        };
+
+        let lyrics = lyrics.trim().to_string();
+        if lyrics.len() > max_bytes {
+            return Err(format!(
+                "Lyrics are {} bytes (max {} bytes, see MCP_MAX_LYRICS_BYTES)",
+                lyrics.len(),
+                max_bytes
+            ));
+        }
+        Ok(Some(lyrics))
+    }
+
+    /// Collect the MusicBrainz ID parameters as (field, key, canonical MBID) updates.
+    ///
+    /// IDs may be bare UUIDs or musicbrainz.org URLs; anything else is rejected.
+    fn mb_id_updates(
+        params: &WriteMetadataParams,
+    ) -> Result<Vec<(&'static str, ItemKey, String)>, String> {
+        let ids = [
+            (
+                "mb_recording_id",
+                ItemKey::MusicBrainzRecordingId,
+                &params.mb_recording_id,
+            ),
+            (
+                "mb_release_id",
+                ItemKey::MusicBrainzReleaseId,
+                &params.mb_release_id,
+            ),
+            (
+                "mb_artist_id",
+                ItemKey::MusicBrainzArtistId,
+                &params.mb_artist_id,
+            ),
+            (
+                "mb_release_group_id",
+                ItemKey::MusicBrainzReleaseGroupId,
+                &params.mb_release_group_id,
+            ),
+        ];
+
+        let mut updates = Vec::new();
+        for (field, key, value) in ids {
+            if let Some(value) = value {
+                let mbid = extract_mbid(value).ok_or_else(|| {
+                    format!("'{}' is not a valid MusicBrainz ID for {}", value, field)
+                })?;
+                updates.push((field, key, mbid));
+            }
+        }
+        Ok(updates)
+    }
+
+    /// Write the MusicBrainz IDs from [`Self::mb_id_updates`].
+    ///
+    /// lofty maps each key to the tag format, e.g. UFID for ID3v2 recordings; an ID the
+    /// format cannot store is reported as unsupported.
+    fn write_mb_ids(
+        tag: &mut lofty::tag::Tag,
+        mb_ids: Vec<(&'static str, ItemKey, String)>,
+        updated_fields: &mut HashMap<String, String>,
+        unsupported_fields: &mut Vec<String>,
+    ) {
+        for (field, key, mbid) in mb_ids {
+            if tag.insert_text(key, mbid.clone()) {
+                updated_fields.insert(field.to_string(), mbid);
+            } else {
+                unsupported_fields.push(field.to_string());
+            }
+        }
+    }
+
+    /// Replace all values of `key` with `values`.
+    ///
+    /// Formats with multi-value support get one item per value; others get a single
+    /// value joined with "; ".
+    fn write_values(
+        tag: &mut lofty::tag::Tag,
+        key: ItemKey,
+        values: &[&str],
+    ) -> MultiValueStrategy {
+        tag.remove_key(&key);
+        match tag.tag_type() {
+            TagType::VorbisComments | TagType::Id3v2 | TagType::Mp4Ilst => {
+                for value in values {
+                    tag.push(TagItem::new(
+                        key.clone(),
+                        ItemValue::Text(value.to_string()),
+                    ));
+                }
+                MultiValueStrategy::MultipleValues
+            }
+            _ => {
+                tag.insert_text(key, values.join("; "));
+                MultiValueStrategy::Joined
+            }
+        }
+    }
+
+    /// Write a tag by name, returning false if the tag format cannot store it.
+    ///
+    /// The name is looked up as a key of the file's tag format, then as a Vorbis Comment
+    /// field name (the most common naming, e.g. CATALOGNUMBER, ORIGINALDATE), so the same
+    /// name works across formats. Unknown names become user-defined tags where the format
+    /// has them.
+    fn write_custom_tag(tag: &mut lofty::tag::Tag, name: &str, value: &str) -> bool {
+        let tag_type = tag.tag_type();
+        let known_key = [
+            ItemKey::from_key(tag_type, name),
+            ItemKey::from_key(TagType::VorbisComments, &name.to_uppercase()),
+        ]
+        .into_iter()
+        .find(|key| key.map_key(tag_type, false).is_some());
+
+        match known_key {
+            Some(key) => tag.insert_text(key, value.to_string()),
+            None => {
+                let key = match tag_type {
+                    TagType::VorbisComments => ItemKey::Unknown(name.to_uppercase()),
+                    TagType::Mp4Ilst => {
+                        ItemKey::Unknown(format!("{}{}", MP4_FREEFORM_PREFIX, name))
+                    }
+                    // A 4-character name would be taken as an ID3v2 frame ID
+                    TagType::Id3v2 if name.len() != 4 => ItemKey::Unknown(name.to_string()),
+                    TagType::Ape => ItemKey::Unknown(name.to_string()),
+                    _ => return false,
+                };
+                tag.remove_key(&key);
+                tag.insert_unchecked(TagItem::new(key, ItemValue::Text(value.to_string())));
+                true
+            }
+        }
+    }
+
+    /// Set the compilation flag, as "1" or "0" (lofty turns it into a boolean atom in MP4).
+    fn write_compilation(tag: &mut lofty::tag::Tag, compilation: bool) -> bool {
+        let flag = if compilation { "1" } else { "0" };
+        tag.insert_text(ItemKey::FlagCompilation, flag.to_string())
+    }
+
+    /// Remove a field from the tag by name.
+    ///
+    /// Standard field names map to their generic keys; any other name is looked up as a
+    /// format-specific key of the tag. Returns whether anything was removed.
+    fn remove_field(tag: &mut lofty::tag::Tag, name: &str) -> bool {
+        let items_before = tag.len();
+        let field = name.to_lowercase();
+        if field == "year" {
+            tag.remove_year();
+        } else if field == "release_country" {
+            if let Some(key) = release_country_key(tag.tag_type()) {
+                tag.remove_key(&key);
+            }
+        } else if field == "bpm" {
+            tag.remove_key(&ItemKey::Bpm);
+            tag.remove_key(&ItemKey::IntegerBpm);
+        } else {
+            let key = field_key(&field).unwrap_or_else(|| ItemKey::from_key(tag.tag_type(), name));
+            tag.remove_key(&key);
+        }
+        tag.len() < items_before
+    }
+
+    /// Copy the file to its backup location, returning the backup path.
+    ///
+    /// Backups are written next to the file as `<name>.bak`, or under `metadata.backup_dir`
+    /// keeping the file's directory relative to the allowed root holding it (its full path
+    /// when no root holds it). An existing backup is left as is, so it keeps the file as it
+    /// was before the first write.
+    pub(super) fn create_backup(path: &Path, config: &Config) -> Result<PathBuf, String> {
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| format!("Invalid file path: {}", path.display()))?;
+        let backup_name = format!("{}.bak", file_name.to_string_lossy());
+        let parent = path.parent().unwrap_or(Path::new(""));
+
+        let backup_path = match &config.metadata.backup_dir {
+            // Next to the file, where the backup is subject to the same checks as the file
+            None => validate_write_target(&parent.join(backup_name).to_string_lossy(), config)
+                .map_err(|e| format!("Path security validation failed: {}", e))?,
+            Some(backup_dir) => {
+                let relative_dir = config
+                    .security
+                    .roots_for(AccessKind::Write)
+                    .iter()
+                    .find_map(|root| {
+                        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+                        parent.strip_prefix(&root).ok().map(Path::to_path_buf)
+                    })
+                    // Files with the same name in different directories get distinct backups
+                    .unwrap_or_else(|| {
+                        parent
+                            .components()
+                            .filter(|component| matches!(component, Component::Normal(_)))
+                            .collect()
+                    });
+                backup_dir.join(relative_dir).join(backup_name)
+            }
+        };
+
+        if backup_path.exists() {
+            info!("Keeping existing backup {}", backup_path.display());
+            return Ok(backup_path);
+        }
+        let original = std::fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        if let Some(parent) = backup_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                format!(
+                    "Failed to create backup directory {}: {}",
+                    parent.display(),
+                    e
+                )
+            })?;
+        }
+        write_atomic(&backup_path, &original)
+            .map_err(|e| format!("Failed to write {}: {}", backup_path.display(), e))?;
+
+        info!("Backed up {} to {}", path.display(), backup_path.display());
+        Ok(backup_path)
+    }
+
+    /// Set the file's modification time back to `modified`.
+    fn restore_mtime(path: &Path, modified: SystemTime) -> std::io::Result<()> {
+        let file = std::fs::OpenOptions::new().write(true).open(path)?;
+        file.set_times(std::fs::FileTimes::new().set_modified(modified))
+    }
+
+    /// Copy of the parameters with genre and genres replaced by their canonical spelling;
+    /// genres that end up spelled the same are written once.
+    fn normalize_genres(params: &WriteMetadataParams, genre_map: &GenreMap) -> WriteMetadataParams {
+        let mut normalized = params.clone();
+        normalized.genre = params
+            .genre
+            .as_deref()
+            .map(|genre| genre_map.normalize(genre));
+        normalized.genres = params.genres.as_ref().map(|genres| {
+            let mut canonical: Vec<String> = Vec::new();
+            for genre in genres.iter().map(|genre| genre_map.normalize(genre)) {
+                if !canonical.contains(&genre) {
+                    canonical.push(genre);
+                }
+            }
+            canonical
+        });
+        normalized
+    }
+
+    /// Fill the fields not given explicitly from an `mb_identify_record` result.
+    ///
+    /// Uses the first recording of the match ranked `identification_rank` (the top match by
+    /// default) and the first of its release groups. Returns the merged parameters and the rank
+    /// of the match applied.
+    fn apply_identification(
+        params: &WriteMetadataParams,
+        identification: &serde_json::Value,
+    ) -> Result<(WriteMetadataParams, usize), String> {
+        let matches: Vec<FingerprintMatch> = identification
+            .get("matches")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| format!("Invalid from_identification matches: {}", e))?
+            .ok_or_else(|| {
+                "from_identification must be the structured output of mb_identify_record"
+                    .to_string()
+            })?;
+
+        let selected = match params.identification_rank {
+            Some(rank) => matches.iter().find(|m| m.rank == rank).ok_or_else(|| {
+                format!(
+                    "No match with rank {} in from_identification ({} match(es))",
+                    rank,
+                    matches.len()
+                )
+            })?,
+            None => matches
+                .iter()
+                .min_by_key(|m| m.rank)
+                .ok_or("from_identification has no matches")?,
+        };
+        let recording = selected
+            .recordings
+            .first()
+            .ok_or_else(|| format!("Identification match #{} has no recordings", selected.rank))?;
+
+        let mut resolved = params.clone();
+        if resolved.title.is_none() {
+            resolved.title = recording.title.clone();
+        }
+        if resolved.artist.is_none() && resolved.artists.is_none() {
+            match recording.artists.as_deref() {
+                Some([artist]) => resolved.artist = Some(artist.clone()),
+                Some(artists) if !artists.is_empty() => resolved.artists = Some(artists.to_vec()),
+                _ => {}
+            }
+        }
+        if resolved.mb_recording_id.is_none() && !recording.id.is_empty() {
+            resolved.mb_recording_id = Some(recording.id.clone());
+        }
+        if let Some(release_group) = recording
+            .release_groups
+            .as_ref()
+            .and_then(|groups| groups.first())
+        {
+            if resolved.album.is_none() {
+                resolved.album = Some(release_group.name.clone());
+            }
+            if resolved.mb_release_group_id.is_none() {
+                resolved.mb_release_group_id = release_group.id.clone();
+            }
+        }
+
+        Ok((resolved, selected.rank))
+    }
+
+    /// Read the primary tag of the `copy_tags_from` file.
+    fn load_source_tag(source: &str, config: &Config) -> Result<lofty::tag::Tag, String> {
+        let source_path = validate_path(source, config)
+            .map_err(|e| format!("copy_tags_from path security validation failed: {}", e))?;
+        if !source_path.is_file() {
+            return Err(format!("copy_tags_from is not a file: {}", source));
+        }
+        let tagged_file = lofty::read_from_path(&source_path)
+            .map_err(|e| format!("Failed to read copy_tags_from file: {}", e))?;
+        tagged_file
+            .primary_tag()
+            .cloned()
+            .ok_or_else(|| format!("No tags to copy in {}", source))
+    }
+
+    /// Read an image file as a front cover picture.
+    ///
+    /// Returns the picture and a description of it, e.g. "image/jpeg, 500x500, 45231 bytes".
+    /// Dimensions are only known for JPEG and PNG images.
+    fn load_cover_art(cover_path: &str, config: &Config) -> Result<(Picture, String), String> {
+        let path = validate_path(cover_path, config)
+            .map_err(|e| format!("Cover art path security validation failed: {}", e))?;
+        if !path.is_file() {
+            return Err(format!("Cover art path is not a file: {}", cover_path));
+        }
+
+        let mut file =
+            std::fs::File::open(&path).map_err(|e| format!("Failed to read cover art: {}", e))?;
+        let mut picture = Picture::from_reader(&mut file)
+            .map_err(|e| format!("Cover art is not a supported image: {}", e))?;
+        picture.set_pic_type(PictureType::CoverFront);
+
+        let description = describe_picture(&picture);
+        Ok((picture, description))
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
         config: Arc<Config>,
     ) -> Result<serde_json::Value, String> {
         let path = arguments
@@ -338,6 +1532,7 @@ impl WriteMetadataTool {
     where
         S: Send + Sync + 'static,
     {
+        let timeout = config.tools.timeout_for(Self::NAME);
         ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
             let args = ctx.arguments.clone().unwrap_or_default();
             let config = config.clone();
@@ -345,13 +1540,420 @@ impl WriteMetadataTool {
                 let params: WriteMetadataParams =
                     serde_json::from_value(serde_json::Value::Object(args))
                         .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
-                Ok(Self::execute(&params, &config))
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
             }
             .boxed()
         })
     }
 }
 
+/// Keys measured from the audio rather than describing the recording, kept by clear_existing.
+const MEASURED_KEYS: &[ItemKey] = &[
+    ItemKey::Bpm,
+    ItemKey::IntegerBpm,
+    ItemKey::ReplayGainTrackGain,
+    ItemKey::ReplayGainTrackPeak,
+    ItemKey::ReplayGainAlbumGain,
+    ItemKey::ReplayGainAlbumPeak,
+];
+
+/// Standard fields compared by [`diff_tags`], besides custom tags.
+const DIFF_FIELDS: &[&str] = &[
+    "title",
+    "artist",
+    "album",
+    "album_artist",
+    "compilation",
+    "year",
+    "original_date",
+    "release_country",
+    "media",
+    "track",
+    "track_total",
+    "disc",
+    "disc_total",
+    "genre",
+    "comment",
+    "composer",
+    "conductor",
+    "lyricist",
+    "remixer",
+    "performers",
+    "artist_sort",
+    "album_artist_sort",
+    "album_sort",
+    "bpm",
+    "replaygain_track_gain",
+    "replaygain_track_peak",
+    "replaygain_album_gain",
+    "replaygain_album_peak",
+    "lyrics",
+    "mb_recording_id",
+    "mb_release_id",
+    "mb_artist_id",
+    "mb_release_group_id",
+    "cover_art",
+];
+
+/// The generic key behind a standard field name (year and cover_art have none).
+fn field_key(field: &str) -> Option<ItemKey> {
+    let key = match field {
+        "title" => ItemKey::TrackTitle,
+        "artist" | "artists" => ItemKey::TrackArtist,
+        "album" => ItemKey::AlbumTitle,
+        "album_artist" => ItemKey::AlbumArtist,
+        "compilation" => ItemKey::FlagCompilation,
+        "original_date" => ItemKey::OriginalReleaseDate,
+        "media" => ItemKey::OriginalMediaType,
+        "track" => ItemKey::TrackNumber,
+        "track_total" => ItemKey::TrackTotal,
+        "disc" => ItemKey::DiscNumber,
+        "disc_total" => ItemKey::DiscTotal,
+        "genre" | "genres" => ItemKey::Genre,
+        "comment" => ItemKey::Comment,
+        "composer" => ItemKey::Composer,
+        "conductor" => ItemKey::Conductor,
+        "lyricist" => ItemKey::Lyricist,
+        "remixer" => ItemKey::Remixer,
+        "performers" => ItemKey::Performer,
+        "artist_sort" => ItemKey::TrackArtistSortOrder,
+        "album_artist_sort" => ItemKey::AlbumArtistSortOrder,
+        "album_sort" => ItemKey::AlbumTitleSortOrder,
+        "bpm" => ItemKey::Bpm,
+        "replaygain_track_gain" => ItemKey::ReplayGainTrackGain,
+        "replaygain_track_peak" => ItemKey::ReplayGainTrackPeak,
+        "replaygain_album_gain" => ItemKey::ReplayGainAlbumGain,
+        "replaygain_album_peak" => ItemKey::ReplayGainAlbumPeak,
+        "lyrics" => ItemKey::Lyrics,
+        "mb_recording_id" => ItemKey::MusicBrainzRecordingId,
+        "mb_release_id" => ItemKey::MusicBrainzReleaseId,
+        "mb_artist_id" => ItemKey::MusicBrainzArtistId,
+        "mb_release_group_id" => ItemKey::MusicBrainzReleaseGroupId,
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Current value of a standard field, several values joined with "; ".
+///
+/// Lyrics and cover art are summarized, see [`describe_lyrics`] and [`describe_picture`].
+fn field_value(tag: &lofty::tag::Tag, field: &str) -> Option<String> {
+    match field {
+        "year" => tag.year().map(|year| year.to_string()),
+        "release_country" => release_country_key(tag.tag_type())
+            .and_then(|key| tag.get_string(&key))
+            .map(|country| country.to_string()),
+        "compilation" => tag
+            .get_string(&ItemKey::FlagCompilation)
+            .map(|_| is_compilation(tag).to_string()),
+        "cover_art" => front_cover(tag).map(describe_picture),
+        "lyrics" => tag.get_string(&ItemKey::Lyrics).map(describe_lyrics),
+        "bpm" => tag
+            .get_string(&ItemKey::Bpm)
+            .or_else(|| tag.get_string(&ItemKey::IntegerBpm))
+            .map(|bpm| bpm.to_string()),
+        _ => {
+            let key = field_key(field)?;
+            let values: Vec<&str> = tag.get_strings(&key).collect();
+            (!values.is_empty()).then(|| values.join("; "))
+        }
+    }
+}
+
+fn front_cover(tag: &lofty::tag::Tag) -> Option<&Picture> {
+    tag.pictures()
+        .iter()
+        .find(|picture| picture.pic_type() == PictureType::CoverFront)
+}
+
+/// Compare two versions of a tag, field by field.
+///
+/// Standard fields use their parameter names; other tags use their name in the tag format,
+/// as read_metadata reports them in `custom_tags`.
+fn diff_tags(
+    before: Option<&lofty::tag::Tag>,
+    after: &lofty::tag::Tag,
+) -> BTreeMap<String, FieldChange> {
+    let mut changes = BTreeMap::new();
+
+    for field in DIFF_FIELDS {
+        let old = before.and_then(|t| field_value(t, field));
+        let new = field_value(after, field);
+        // Summaries of lyrics and cover art can match for different content
+        let changed = match *field {
+            "lyrics" => {
+                before.and_then(|t| t.get_string(&ItemKey::Lyrics))
+                    != after.get_string(&ItemKey::Lyrics)
+            }
+            "cover_art" => {
+                before.and_then(front_cover).map(|p| p.data())
+                    != front_cover(after).map(|p| p.data())
+            }
+            _ => old != new,
+        };
+        if changed {
+            changes.insert(field.to_string(), FieldChange { old, new });
+        }
+    }
+
+    let custom_before = before.map(custom_tags).unwrap_or_default();
+    let custom_after = custom_tags(after);
+    for name in custom_before.keys().chain(custom_after.keys()) {
+        let old = custom_before.get(name).cloned();
+        let new = custom_after.get(name).cloned();
+        if old != new {
+            changes.insert(name.clone(), FieldChange { old, new });
+        }
+    }
+
+    changes
+}
+
+/// Copy every text item and, optionally, every picture of `source` into `tag`.
+///
+/// Each copied key replaces the tag's values for that key; keys without a generic meaning
+/// are written like custom tags, so they carry over between tag formats. Returns the copied
+/// field names (standard fields by parameter name, others by their name in the source
+/// format) and those the tag format cannot store.
+pub(super) fn copy_tag(
+    tag: &mut lofty::tag::Tag,
+    source: &lofty::tag::Tag,
+    include_pictures: bool,
+) -> (Vec<String>, Vec<String>) {
+    // Values by key, in source order
+    let mut items: Vec<(&ItemKey, Vec<&ItemValue>)> = Vec::new();
+    for item in source.items() {
+        if matches!(item.value(), ItemValue::Binary(_)) {
+            continue;
+        }
+        match items.iter_mut().find(|(key, _)| *key == item.key()) {
+            Some((_, values)) => values.push(item.value()),
+            None => items.push((item.key(), vec![item.value()])),
+        }
+    }
+
+    let mut copied = Vec::new();
+    let mut unsupported = Vec::new();
+    for (key, values) in items {
+        let name = copied_field_name(key, source.tag_type());
+        let written = match key {
+            ItemKey::Unknown(_) => {
+                let joined: Vec<&str> = values.iter().filter_map(|v| v.text()).collect();
+                WriteMetadataTool::write_custom_tag(tag, &name, &joined.join("; "))
+            }
+            _ => {
+                tag.remove_key(key);
+                values
+                    .into_iter()
+                    .all(|value| tag.push(TagItem::new(key.clone(), value.clone())))
+            }
+        };
+        let names = if written {
+            &mut copied
+        } else {
+            &mut unsupported
+        };
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    if include_pictures && !source.pictures().is_empty() {
+        for picture in source.pictures() {
+            tag.remove_picture_type(picture.pic_type());
+        }
+        for picture in source.pictures() {
+            tag.push_picture(picture.clone());
+        }
+        copied.push("pictures".to_string());
+    }
+
+    (copied, unsupported)
+}
+
+/// Name of a copied key: the standard field name, else its name in the source format.
+pub(super) fn copied_field_name(key: &ItemKey, tag_type: TagType) -> String {
+    if matches!(key, ItemKey::Year | ItemKey::RecordingDate) {
+        return "year".to_string();
+    }
+    if matches!(key, ItemKey::Bpm | ItemKey::IntegerBpm) {
+        return "bpm".to_string();
+    }
+    if release_country_key(tag_type).as_ref() == Some(key) {
+        return "release_country".to_string();
+    }
+    if let Some(field) = DIFF_FIELDS
+        .iter()
+        .find(|field| field_key(field).as_ref() == Some(key))
+    {
+        return field.to_string();
+    }
+    match key.map_key(tag_type, true) {
+        Some(name) => name
+            .strip_prefix(MP4_FREEFORM_PREFIX)
+            .unwrap_or(name)
+            .to_string(),
+        None => format!("{:?}", key),
+    }
+}
+
+/// Whether two field names designate the same field, e.g. "artists" and "artist".
+pub(super) fn same_field(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b) || field_key(a).is_some_and(|key| field_key(b) == Some(key))
+}
+
+/// Sort name for a sort key, derived from the name it orders (see [`sort_name`]).
+fn derived_sort_name(tag: &lofty::tag::Tag, sort_key: &ItemKey) -> Option<String> {
+    let (name_key, swap_names) = match sort_key {
+        ItemKey::TrackArtistSortOrder => (ItemKey::TrackArtist, true),
+        ItemKey::AlbumArtistSortOrder => (ItemKey::AlbumArtist, true),
+        ItemKey::AlbumTitleSortOrder => (ItemKey::AlbumTitle, false),
+        _ => return None,
+    };
+    tag.get_string(&name_key)
+        .map(|name| sort_name(name, swap_names))
+}
+
+/// Sort form of a name: "The Beatles" becomes "Beatles, The" and, with `swap_names`, a
+/// two-word name is inverted ("John Coltrane" becomes "Coltrane, John"). Other names are
+/// returned unchanged.
+fn sort_name(name: &str, swap_names: bool) -> String {
+    let name = name.trim();
+    if let Some(article) = name.get(..4).filter(|a| a.eq_ignore_ascii_case("the "))
+        && !name[4..].trim().is_empty()
+    {
+        return format!("{}, {}", name[4..].trim(), article.trim_end());
+    }
+    if swap_names
+        && !name.contains(',')
+        && let Some((first, last)) = name.split_once(' ')
+        && !first.is_empty()
+        && !last.is_empty()
+        && !last.contains(' ')
+    {
+        return format!("{}, {}", last, first);
+    }
+    name.to_string()
+}
+
+/// Summarize lyrics as their size, e.g. "32 line(s), 1204 bytes".
+fn describe_lyrics(lyrics: &str) -> String {
+    format!("{} line(s), {} bytes", lyrics.lines().count(), lyrics.len())
+}
+
+/// Summarize a picture, e.g. "image/jpeg, 500x500, 45231 bytes".
+///
+/// Dimensions are only known for JPEG and PNG images.
+fn describe_picture(picture: &Picture) -> String {
+    let mime_type = picture.mime_type().map_or("unknown", |mime| mime.as_str());
+    let byte_size = picture.data().len();
+    match PictureInformation::from_picture(picture) {
+        Ok(info) if info.width > 0 && info.height > 0 => format!(
+            "{}, {}x{}, {} bytes",
+            mime_type, info.width, info.height, byte_size
+        ),
+        _ => format!("{}, {} bytes", mime_type, byte_size),
+    }
+}
+
+/// Prefix of MP4 freeform atoms written for custom tags.
+pub(super) const MP4_FREEFORM_PREFIX: &str = "----:com.apple.iTunes:";
+
+/// Key of the release country in a tag format, named as Picard writes it.
+///
+/// lofty has no generic key for it; formats without user-defined tags cannot store it.
+pub(super) fn release_country_key(tag_type: TagType) -> Option<ItemKey> {
+    let name = match tag_type {
+        TagType::VorbisComments | TagType::Ape => "RELEASECOUNTRY",
+        TagType::Id3v2 => "MusicBrainz Album Release Country",
+        TagType::Mp4Ilst => "----:com.apple.iTunes:MusicBrainz Album Release Country",
+        _ => return None,
+    };
+    Some(ItemKey::Unknown(name.to_string()))
+}
+
+/// Check that a date is `YYYY` or `YYYY-MM-DD`.
+fn is_valid_date(date: &str) -> bool {
+    let digits =
+        |part: &str, len: usize| part.len() == len && part.chars().all(|c| c.is_ascii_digit());
+    match date.split('-').collect::<Vec<_>>().as_slice() {
+        [year] => digits(year, 4),
+        [year, month, day] => {
+            digits(year, 4)
+                && digits(month, 2)
+                && digits(day, 2)
+                && (1..=12).contains(&month.parse::<u32>().unwrap_or(0))
+                && (1..=31).contains(&day.parse::<u32>().unwrap_or(0))
+        }
+        _ => false,
+    }
+}
+
+/// Check a ReplayGain gain: a number of decibels, with or without its "dB" unit.
+fn is_valid_gain(gain: &str) -> bool {
+    let gain = gain.trim();
+    let gain = gain.strip_suffix("dB").unwrap_or(gain).trim_end();
+    gain.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+/// Check a ReplayGain peak: a sample amplitude, 1.0 being full scale.
+fn is_valid_peak(peak: &str) -> bool {
+    peak.trim()
+        .parse::<f64>()
+        .is_ok_and(|peak| peak.is_finite() && peak >= 0.0)
+}
+
+/// Check that a custom tag name is safe to store in every tag format.
+fn is_valid_custom_key(name: &str) -> bool {
+    !name.trim().is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.' | ':'))
+}
+
+/// Convert LRC lyrics to plain text.
+///
+/// Removes `[mm:ss.xx]` timestamps at the start of lines and drops ID tag lines such as
+/// `[ar:Artist]`; other lines are kept as they are.
+fn strip_lrc_timestamps(lrc: &str) -> String {
+    fn is_timestamp(tag: &str) -> bool {
+        tag.contains(':')
+            && tag
+                .chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, ':' | '.'))
+    }
+
+    let mut lines = Vec::new();
+    for line in lrc.lines() {
+        let mut rest = line.trim_start();
+        let mut had_timestamp = false;
+        while let Some((tag, after)) = rest.strip_prefix('[').and_then(|r| r.split_once(']')) {
+            if !is_timestamp(tag) {
+                break;
+            }
+            had_timestamp = true;
+            rest = after;
+        }
+        let is_id_tag =
+            !had_timestamp && rest.starts_with('[') && rest.ends_with(']') && rest.contains(':');
+        if !is_id_tag {
+            lines.push(rest.trim());
+        }
+    }
+    lines.join("\n")
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -371,14 +1973,54 @@ mod tests {
             path: "/nonexistent/audio/file.mp3".to_string(),
             title: Some("Test".to_string()),
             artist: None,
+            artists: None,
             album: None,
             album_artist: None,
+            compilation: None,
             year: None,
+            original_date: None,
+            release_country: None,
+            media: None,
             track: None,
             track_total: None,
+            disc: None,
+            disc_total: None,
             genre: None,
+            genres: None,
+            normalize_genre: false,
             comment: None,
+            artist_sort: None,
+            album_artist_sort: None,
+            album_sort: None,
+            auto_sort_names: false,
+            composer: None,
+            conductor: None,
+            lyricist: None,
+            remixer: None,
+            performers: vec![],
+            custom_tags: None,
+            lyrics: None,
+            lyrics_file: None,
+            bpm: None,
+            replaygain_track_gain: None,
+            replaygain_track_peak: None,
+            replaygain_album_gain: None,
+            replaygain_album_peak: None,
+            mb_recording_id: None,
+            mb_release_id: None,
+            mb_artist_id: None,
+            mb_release_group_id: None,
+            cover_art_path: None,
+            copy_tags_from: None,
+            include_pictures: false,
+            from_identification: None,
+            identification_rank: None,
+            remove_fields: vec![],
             clear_existing: false,
+            dry_run: false,
+            backup: None,
+            preserve_mtime: None,
+            id3_version: None,
         };
 
         let config = test_config();
@@ -394,14 +2036,54 @@ mod tests {
             path: temp_dir.path().to_string_lossy().to_string(),
             title: Some("Test".to_string()),
             artist: None,
+            artists: None,
             album: None,
             album_artist: None,
+            compilation: None,
             year: None,
+            original_date: None,
+            release_country: None,
+            media: None,
             track: None,
             track_total: None,
+            disc: None,
+            disc_total: None,
             genre: None,
+            genres: None,
+            normalize_genre: false,
             comment: None,
+            artist_sort: None,
+            album_artist_sort: None,
+            album_sort: None,
+            auto_sort_names: false,
+            composer: None,
+            conductor: None,
+            lyricist: None,
+            remixer: None,
+            performers: vec![],
+            custom_tags: None,
+            lyrics: None,
+            lyrics_file: None,
+            bpm: None,
+            replaygain_track_gain: None,
+            replaygain_track_peak: None,
+            replaygain_album_gain: None,
+            replaygain_album_peak: None,
+            mb_recording_id: None,
+            mb_release_id: None,
+            mb_artist_id: None,
+            mb_release_group_id: None,
+            cover_art_path: None,
+            copy_tags_from: None,
+            include_pictures: false,
+            from_identification: None,
+            identification_rank: None,
+            remove_fields: vec![],
             clear_existing: false,
+            dry_run: false,
+            backup: None,
+            preserve_mtime: None,
+            id3_version: None,
         };
 
         let config = test_config();
@@ -409,6 +2091,1363 @@ mod tests {
         assert!(result.is_error.unwrap_or(false));
     }
 
+    /// Minimal FLAC file: signature, a STREAMINFO block (44.1kHz, stereo, 16-bit, no frames)
+    /// and a final PADDING block.
+    fn write_flac_fixture(path: &std::path::Path) {
+        let mut bytes = b"fLaC".to_vec();
+        bytes.extend_from_slice(&[0x00, 0, 0, 34]);
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&[0; 6]);
+        bytes.extend_from_slice(&[0x0A, 0xC4, 0x42, 0xF0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&[0x81, 0, 0, 16]);
+        bytes.extend_from_slice(&[0; 16]);
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_write_metadata_credits_round_trip() {
+        use crate::domains::tools::definitions::metadata::read::{
+            ReadMetadataParams, ReadMetadataTool,
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let path = audio_path.to_string_lossy().to_string();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": path,
+            "composer": "Johann Sebastian Bach",
+            "conductor": "Karl Richter",
+            "lyricist": "Christian Friedrich Henrici",
+            "remixer": "Nobody",
+            "performers": ["Münchener Bach-Orchester", " ", "Dietrich Fischer-Dieskau (baritone)"]
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+        let written = result.structured_content.unwrap();
+        assert_eq!(written["fields_updated"], 5);
+        assert_eq!(
+            written["updated_fields"]["performers"],
+            "Münchener Bach-Orchester; Dietrich Fischer-Dieskau (baritone)"
+        );
+        assert_eq!(written["unsupported_fields"], serde_json::json!([]));
+
+        let read_params = ReadMetadataParams {
+            path,
+            recursive: false,
+            max_files: 200,
+            extract_cover_to: None,
+            include_all_tags: false,
+        };
+        let result = ReadMetadataTool::execute(&read_params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+        let metadata = &result.structured_content.unwrap()["metadata"];
+        assert_eq!(metadata["composer"], "Johann Sebastian Bach");
+        assert_eq!(metadata["conductor"], "Karl Richter");
+        assert_eq!(metadata["lyricist"], "Christian Friedrich Henrici");
+        assert_eq!(metadata["remixer"], "Nobody");
+        assert_eq!(
+            metadata["performers"],
+            serde_json::json!([
+                "Münchener Bach-Orchester",
+                "Dietrich Fischer-Dieskau (baritone)"
+            ])
+        );
+    }
+
+    #[test]
+    fn test_sort_name() {
+        assert_eq!(sort_name("The Beatles", true), "Beatles, The");
+        assert_eq!(sort_name("the the", false), "the, the");
+        assert_eq!(sort_name("John Coltrane", true), "Coltrane, John");
+        assert_eq!(sort_name("John Coltrane", false), "John Coltrane");
+        assert_eq!(sort_name("Earth, Wind & Fire", true), "Earth, Wind & Fire");
+        assert_eq!(sort_name("Sun Ra Arkestra", true), "Sun Ra Arkestra");
+        assert_eq!(sort_name("Radiohead", true), "Radiohead");
+        assert_eq!(sort_name("Theory", true), "Theory");
+    }
+
+    #[test]
+    fn test_write_metadata_sort_names_round_trip() {
+        use crate::domains::tools::definitions::metadata::read::{
+            ReadMetadataParams, ReadMetadataTool,
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let path = audio_path.to_string_lossy().to_string();
+
+        // Explicit sort names win over derived ones
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": path,
+            "artist": "John Coltrane",
+            "album_artist": "The John Coltrane Quartet",
+            "album": "The Gentle Side",
+            "album_sort": "Gentle Side (Coltrane)",
+            "auto_sort_names": true
+        }))
+        .unwrap();
+        let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+        assert_eq!(data.updated_fields["artist_sort"], "Coltrane, John");
+        assert_eq!(
+            data.updated_fields["album_artist_sort"],
+            "John Coltrane Quartet, The"
+        );
+        assert_eq!(data.updated_fields["album_sort"], "Gentle Side (Coltrane)");
+        assert_eq!(
+            data.changes["artist_sort"].new.as_deref(),
+            Some("Coltrane, John")
+        );
+
+        let read_params = ReadMetadataParams {
+            path: path.clone(),
+            recursive: false,
+            max_files: 200,
+            extract_cover_to: None,
+            include_all_tags: false,
+        };
+        let result = ReadMetadataTool::execute(&read_params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+        let metadata = &result.structured_content.unwrap()["metadata"];
+        assert_eq!(metadata["artist_sort"], "Coltrane, John");
+        assert_eq!(metadata["album_artist_sort"], "John Coltrane Quartet, The");
+        assert_eq!(metadata["album_sort"], "Gentle Side (Coltrane)");
+        assert_eq!(metadata["custom_tags"], serde_json::json!({}));
+
+        // Existing names are used when only auto_sort_names is given
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": path,
+            "remove_fields": ["album_sort"],
+            "auto_sort_names": true
+        }))
+        .unwrap();
+        let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+        assert_eq!(data.removed_fields, vec!["album_sort"]);
+        assert_eq!(data.updated_fields["album_sort"], "Gentle Side, The");
+    }
+
+    #[test]
+    fn test_write_metadata_lyrics_round_trip() {
+        use crate::domains::tools::definitions::metadata::read::{
+            ReadMetadataParams, ReadMetadataTool,
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let path = audio_path.to_string_lossy().to_string();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": path,
+            "lyrics": "Ticking away\nthe moments"
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+        assert_eq!(
+            result.structured_content.unwrap()["updated_fields"]["lyrics"],
+            "2 line(s), 24 bytes"
+        );
+
+        let read_params = ReadMetadataParams {
+            path,
+            recursive: false,
+            max_files: 200,
+            extract_cover_to: None,
+            include_all_tags: false,
+        };
+        let result = ReadMetadataTool::execute(&read_params, &test_config());
+        let metadata = &result.structured_content.unwrap()["metadata"];
+        assert_eq!(metadata["has_lyrics"], true);
+        assert_eq!(metadata["lyrics_preview"], "Ticking away\nthe moments");
+    }
+
+    #[test]
+    fn test_write_values() {
+        use lofty::tag::Tag;
+
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.set_artist("A feat. B".to_string());
+        let strategy = WriteMetadataTool::write_values(&mut tag, ItemKey::TrackArtist, &["A", "B"]);
+        assert_eq!(strategy, MultiValueStrategy::MultipleValues);
+        assert_eq!(
+            tag.get_strings(&ItemKey::TrackArtist).collect::<Vec<_>>(),
+            vec!["A", "B"]
+        );
+
+        let mut tag = Tag::new(TagType::RiffInfo);
+        let strategy = WriteMetadataTool::write_values(&mut tag, ItemKey::Genre, &["Jazz", "Funk"]);
+        assert_eq!(strategy, MultiValueStrategy::Joined);
+        assert_eq!(
+            tag.get_strings(&ItemKey::Genre).collect::<Vec<_>>(),
+            vec!["Jazz; Funk"]
+        );
+    }
+
+    #[test]
+    fn test_write_metadata_multi_value_round_trip() {
+        use crate::domains::tools::definitions::metadata::read::{
+            ReadMetadataParams, ReadMetadataTool,
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let path = audio_path.to_string_lossy().to_string();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": path,
+            "artists": ["Herbie Hancock", "Wayne Shorter"],
+            "genres": ["Jazz", " ", "Fusion"]
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+        let written = result.structured_content.unwrap();
+        assert_eq!(written["multi_value_strategy"], "multiple_values");
+        assert_eq!(written["updated_fields"]["genres"], "Jazz; Fusion");
+
+        let read_params = ReadMetadataParams {
+            path,
+            recursive: false,
+            max_files: 200,
+            extract_cover_to: None,
+            include_all_tags: false,
+        };
+        let result = ReadMetadataTool::execute(&read_params, &test_config());
+        let metadata = &result.structured_content.unwrap()["metadata"];
+        assert_eq!(metadata["artist"], "Herbie Hancock");
+        assert_eq!(
+            metadata["artists"],
+            serde_json::json!(["Herbie Hancock", "Wayne Shorter"])
+        );
+        assert_eq!(metadata["genres"], serde_json::json!(["Jazz", "Fusion"]));
+    }
+
+    #[test]
+    fn test_is_valid_custom_key() {
+        assert!(is_valid_custom_key("CATALOGNUMBER"));
+        assert!(is_valid_custom_key("MusicBrainz Album Type"));
+        assert!(is_valid_custom_key("release.country"));
+        assert!(!is_valid_custom_key(""));
+        assert!(!is_valid_custom_key("KEY=VALUE"));
+        assert!(!is_valid_custom_key("NEW\nLINE"));
+        assert!(!is_valid_custom_key(&"A".repeat(65)));
+    }
+
+    #[test]
+    fn test_write_custom_tag() {
+        use lofty::tag::Tag;
+
+        // Vorbis Comment names resolve to the matching key of other formats
+        let mut tag = Tag::new(TagType::Mp4Ilst);
+        assert!(WriteMetadataTool::write_custom_tag(
+            &mut tag,
+            "catalognumber",
+            "XL 123"
+        ));
+        assert_eq!(tag.get_string(&ItemKey::CatalogNumber), Some("XL 123"));
+
+        // Unknown names become user-defined tags
+        assert!(WriteMetadataTool::write_custom_tag(
+            &mut tag, "MY TAG", "value"
+        ));
+        let key = ItemKey::Unknown("----:com.apple.iTunes:MY TAG".to_string());
+        assert_eq!(tag.get_string(&key), Some("value"));
+
+        let mut tag = Tag::new(TagType::Id3v2);
+        assert!(WriteMetadataTool::write_custom_tag(
+            &mut tag, "MY_TAG", "one"
+        ));
+        assert!(WriteMetadataTool::write_custom_tag(
+            &mut tag, "MY_TAG", "two"
+        ));
+        let key = ItemKey::Unknown("MY_TAG".to_string());
+        assert_eq!(tag.get_strings(&key).collect::<Vec<_>>(), vec!["two"]);
+
+        // RIFF INFO only has fixed keys
+        let mut tag = Tag::new(TagType::RiffInfo);
+        assert!(!WriteMetadataTool::write_custom_tag(
+            &mut tag, "MY_TAG", "value"
+        ));
+    }
+
+    #[test]
+    fn test_write_metadata_custom_tags_round_trip() {
+        use crate::domains::tools::definitions::metadata::read::{
+            ReadMetadataParams, ReadMetadataTool,
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let path = audio_path.to_string_lossy().to_string();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": path,
+            "title": "Title",
+            "custom_tags": {
+                "CATALOGNUMBER": "XL 123",
+                "releasecountry": "GB",
+                "MY_TAG": "value"
+            }
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+        let written = result.structured_content.unwrap();
+        assert_eq!(written["fields_updated"], 4);
+        assert_eq!(written["updated_fields"]["releasecountry"], "GB");
+
+        let read_params = ReadMetadataParams {
+            path,
+            recursive: false,
+            max_files: 200,
+            extract_cover_to: None,
+            include_all_tags: false,
+        };
+        let result = ReadMetadataTool::execute(&read_params, &test_config());
+        let metadata = &result.structured_content.unwrap()["metadata"];
+        assert_eq!(
+            metadata["custom_tags"],
+            serde_json::json!({
+                "CATALOGNUMBER": "XL 123",
+                "MY_TAG": "value"
+            })
+        );
+        // Reported by its dedicated field
+        assert_eq!(metadata["release_country"], "GB");
+    }
+
+    #[test]
+    fn test_write_metadata_release_details_round_trip() {
+        use crate::domains::tools::definitions::metadata::read::{
+            ReadMetadataParams, ReadMetadataTool,
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["track.flac", "track.mp3"] {
+            let audio_path = temp_dir.path().join(name);
+            if name.ends_with(".flac") {
+                write_flac_fixture(&audio_path);
+            } else {
+                write_mp3_fixture(&audio_path);
+            }
+            let path = audio_path.to_string_lossy().to_string();
+
+            let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+                "path": path,
+                "original_date": "1967-06-01",
+                "release_country": "GB",
+                "media": "CD"
+            }))
+            .unwrap();
+            let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+            assert_eq!(data.updated_fields["original_date"], "1967-06-01");
+            assert_eq!(data.updated_fields["release_country"], "GB");
+            assert_eq!(data.changes["media"].new.as_deref(), Some("CD"));
+            assert!(data.unsupported_fields.is_empty(), "{}", name);
+
+            let read_params = ReadMetadataParams {
+                path: path.clone(),
+                recursive: false,
+                max_files: 200,
+                extract_cover_to: None,
+                include_all_tags: false,
+            };
+            let result = ReadMetadataTool::execute(&read_params, &test_config());
+            let metadata = &result.structured_content.unwrap()["metadata"];
+            assert_eq!(metadata["original_date"], "1967-06-01", "{}", name);
+            assert_eq!(metadata["release_country"], "GB", "{}", name);
+            assert_eq!(metadata["media"], "CD", "{}", name);
+            assert_eq!(metadata["custom_tags"], serde_json::json!({}), "{}", name);
+
+            let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+                "path": path,
+                "remove_fields": ["release_country", "original_date"]
+            }))
+            .unwrap();
+            let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+            assert_eq!(
+                data.removed_fields,
+                vec!["release_country", "original_date"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_metadata_rejects_invalid_original_date() {
+        assert!(is_valid_date("1967"));
+        assert!(is_valid_date("1967-06-01"));
+        assert!(!is_valid_date("67"));
+        assert!(!is_valid_date("1967-06"));
+        assert!(!is_valid_date("1967-13-01"));
+        assert!(!is_valid_date("1967-6-1"));
+        assert!(!is_valid_date("June 1967"));
+
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "original_date": "01/06/1967"
+        }))
+        .unwrap();
+        let error = WriteMetadataTool::write_file(&params, &test_config()).unwrap_err();
+        assert!(error.contains("YYYY or YYYY-MM-DD"), "{}", error);
+    }
+
+    #[test]
+    fn test_write_metadata_replaygain_and_bpm_round_trip() {
+        use crate::domains::tools::definitions::metadata::read::{
+            ReadMetadataParams, ReadMetadataTool,
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["track.flac", "track.mp3"] {
+            let audio_path = temp_dir.path().join(name);
+            if name.ends_with(".flac") {
+                write_flac_fixture(&audio_path);
+            } else {
+                write_mp3_fixture(&audio_path);
+            }
+            let path = audio_path.to_string_lossy().to_string();
+
+            let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+                "path": path,
+                "bpm": 128,
+                "replaygain_track_gain": "-6.50 dB",
+                "replaygain_track_peak": "0.988547",
+                "replaygain_album_gain": "-7.10 dB",
+                "replaygain_album_peak": "1.000000"
+            }))
+            .unwrap();
+            let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+            assert_eq!(data.updated_fields["bpm"], "128", "{}", name);
+            assert!(data.unsupported_fields.is_empty(), "{}", name);
+
+            // clear_existing keeps the measured values
+            let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+                "path": path,
+                "title": "Title",
+                "clear_existing": true
+            }))
+            .unwrap();
+            WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+
+            let read_params = ReadMetadataParams {
+                path: path.clone(),
+                recursive: false,
+                max_files: 200,
+                extract_cover_to: None,
+                include_all_tags: false,
+            };
+            let result = ReadMetadataTool::execute(&read_params, &test_config());
+            let metadata = &result.structured_content.unwrap()["metadata"];
+            assert_eq!(metadata["bpm"], 128, "{}", name);
+            assert_eq!(metadata["replaygain_track_gain"], "-6.50 dB", "{}", name);
+            assert_eq!(metadata["replaygain_track_peak"], "0.988547", "{}", name);
+            assert_eq!(metadata["replaygain_album_gain"], "-7.10 dB", "{}", name);
+            assert_eq!(metadata["replaygain_album_peak"], "1.000000", "{}", name);
+
+            let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+                "path": path,
+                "clear_existing": true,
+                "remove_fields": ["bpm", "replaygain_track_gain"]
+            }))
+            .unwrap();
+            let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+            assert_eq!(data.removed_fields, vec!["bpm", "replaygain_track_gain"]);
+            assert_eq!(data.changes["bpm"].new, None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_write_compilation_representations() {
+        use lofty::id3::v2::{FrameId, Id3v2Tag};
+        use lofty::mp4::{AtomData, AtomIdent, Ilst};
+        use lofty::ogg::VorbisComments;
+
+        let flagged = |tag_type: TagType, compilation: bool| {
+            let mut tag = lofty::tag::Tag::new(tag_type);
+            assert!(WriteMetadataTool::write_compilation(&mut tag, compilation));
+            tag
+        };
+
+        let vorbis: VorbisComments = flagged(TagType::VorbisComments, true).into();
+        assert_eq!(vorbis.get("COMPILATION"), Some("1"));
+
+        let id3v2: Id3v2Tag = flagged(TagType::Id3v2, true).into();
+        assert_eq!(id3v2.get_text(&FrameId::new("TCMP").unwrap()), Some("1"));
+
+        for compilation in [true, false] {
+            let ilst: Ilst = flagged(TagType::Mp4Ilst, compilation).into();
+            let cpil = ilst.get(&AtomIdent::Fourcc(*b"cpil")).unwrap();
+            assert_eq!(cpil.data().next(), Some(&AtomData::Bool(compilation)));
+        }
+    }
+
+    #[test]
+    fn test_write_metadata_compilation_round_trip() {
+        use crate::domains::tools::definitions::metadata::read::{
+            ReadMetadataParams, ReadMetadataTool,
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["track.flac", "track.mp3"] {
+            let audio_path = temp_dir.path().join(name);
+            if name.ends_with(".flac") {
+                write_flac_fixture(&audio_path);
+            } else {
+                write_mp3_fixture(&audio_path);
+            }
+            let path = audio_path.to_string_lossy().to_string();
+
+            let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+                "path": path,
+                "compilation": true
+            }))
+            .unwrap();
+            let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+            assert_eq!(data.updated_fields["compilation"], "true", "{}", name);
+            assert_eq!(data.changes["compilation"].new.as_deref(), Some("true"));
+
+            let read_params = ReadMetadataParams {
+                path: path.clone(),
+                recursive: false,
+                max_files: 200,
+                extract_cover_to: None,
+                include_all_tags: false,
+            };
+            let result = ReadMetadataTool::execute(&read_params, &test_config());
+            let metadata = &result.structured_content.unwrap()["metadata"];
+            assert_eq!(metadata["compilation"], true, "{}", name);
+            assert!(metadata["custom_tags"].get("COMPILATION").is_none());
+
+            let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+                "path": path,
+                "remove_fields": ["compilation"]
+            }))
+            .unwrap();
+            let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+            assert_eq!(data.removed_fields, vec!["compilation"], "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_write_metadata_normalize_genre() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let write = |config: &Config, normalize_genre: bool| {
+            let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+                "path": audio_path.to_string_lossy(),
+                "genre": "Rap/Hip Hop",
+                "normalize_genre": normalize_genre
+            }))
+            .unwrap();
+            WriteMetadataTool::write_file(&params, config)
+        };
+
+        let data = write(&test_config(), false).unwrap();
+        assert_eq!(data.updated_fields["genre"], "Rap/Hip Hop");
+        let data = write(&test_config(), true).unwrap();
+        assert_eq!(data.updated_fields["genre"], "Hip-Hop");
+
+        let genre_map = temp_dir.path().join("genres.json");
+        std::fs::write(&genre_map, r#"{"Hip Hop": ["Rap/Hip Hop"]}"#).unwrap();
+        let mut config = test_config();
+        config.metadata.genre_map_path = Some(genre_map.clone());
+        let data = write(&config, true).unwrap();
+        assert_eq!(data.updated_fields["genre"], "Hip Hop");
+
+        // A broken genre map fails the call before the file is written
+        std::fs::write(&genre_map, "not json").unwrap();
+        let err = write(&config, true).unwrap_err();
+        assert!(err.contains("Invalid genre map"), "{}", err);
+    }
+
+    #[test]
+    fn test_write_metadata_rejects_invalid_replaygain() {
+        assert!(is_valid_gain("-6.50 dB"));
+        assert!(is_valid_gain("+2.1dB"));
+        assert!(is_valid_gain("0"));
+        assert!(!is_valid_gain("loud"));
+        assert!(is_valid_peak("0.988547"));
+        assert!(!is_valid_peak("-0.5"));
+
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "replaygain_album_gain": "-7 LUFS"
+        }))
+        .unwrap();
+        let error = WriteMetadataTool::write_file(&params, &test_config()).unwrap_err();
+        assert!(error.contains("replaygain_album_gain"), "{}", error);
+    }
+
+    #[test]
+    fn test_write_metadata_rejects_invalid_custom_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.mp3");
+        std::fs::write(&audio_path, b"not really audio").unwrap();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "custom_tags": { "BAD=KEY": "value" }
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        let text = format!("{:?}", result.content);
+        assert!(result.is_error.unwrap_or(false));
+        assert!(
+            text.contains("Invalid custom tag name 'BAD=KEY'"),
+            "{}",
+            text
+        );
+        assert_eq!(std::fs::read(&audio_path).unwrap(), b"not really audio");
+    }
+
+    #[test]
+    fn test_diff_tags() {
+        use lofty::tag::Tag;
+
+        let mut before = Tag::new(TagType::VorbisComments);
+        before.set_title("Old Title".to_string());
+        before.set_comment("Ripped by XYZ".to_string());
+        before.insert_text(ItemKey::CatalogNumber, "XL 123".to_string());
+
+        let mut after = before.clone();
+        after.set_title("New Title".to_string());
+        after.remove_comment();
+        after.set_year(1997);
+
+        let changes = diff_tags(Some(&before), &after);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(
+            changes["title"],
+            FieldChange {
+                old: Some("Old Title".to_string()),
+                new: Some("New Title".to_string()),
+            }
+        );
+        assert_eq!(changes["comment"].new, None);
+        assert_eq!(changes["year"].old, None);
+
+        // Without a previous tag, everything is new
+        let changes = diff_tags(None, &after);
+        assert_eq!(changes["CATALOGNUMBER"].new.as_deref(), Some("XL 123"));
+    }
+
+    #[test]
+    fn test_write_metadata_dry_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let original = std::fs::read(&audio_path).unwrap();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "title": "New Title",
+            "year": 1997,
+            "dry_run": true
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+        let text = format!("{:?}", result.content);
+        assert!(
+            text.contains("DRY RUN: 2 field(s) would change"),
+            "{}",
+            text
+        );
+
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["applied"], false);
+        assert_eq!(
+            structured["changes"],
+            serde_json::json!({
+                "title": { "old": null, "new": "New Title" },
+                "year": { "old": null, "new": "1997" }
+            })
+        );
+        assert_eq!(std::fs::read(&audio_path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_create_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let album_dir = temp_dir.path().join("Artist").join("Album");
+        std::fs::create_dir_all(&album_dir).unwrap();
+        let audio_path = album_dir.join("01.flac");
+        std::fs::write(&audio_path, b"original").unwrap();
+
+        // Next to the file by default
+        let mut config = test_config();
+        let backup_path = WriteMetadataTool::create_backup(&audio_path, &config).unwrap();
+        assert_eq!(backup_path, album_dir.join("01.flac.bak"));
+        assert_eq!(std::fs::read(&backup_path).unwrap(), b"original");
+
+        // An existing backup keeps the original
+        std::fs::write(&audio_path, b"changed").unwrap();
+        WriteMetadataTool::create_backup(&audio_path, &config).unwrap();
+        assert_eq!(std::fs::read(&backup_path).unwrap(), b"original");
+
+        // Under the backup directory, relative to the root path
+        let backup_dir = temp_dir.path().join("backups");
+        config.security.allowed_paths = vec![temp_dir.path().to_path_buf()];
+        config.metadata.backup_dir = Some(backup_dir.clone());
+        let backup_path = WriteMetadataTool::create_backup(&audio_path, &config).unwrap();
+        assert!(backup_path.ends_with("backups/Artist/Album/01.flac.bak"));
+        assert!(backup_path.starts_with(&backup_dir));
+        assert_eq!(std::fs::read(&backup_path).unwrap(), b"changed");
+    }
+
+    #[test]
+    fn test_create_backup_without_roots_keeps_directories_apart() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("A").join("01 - Intro.flac");
+        let second = temp_dir.path().join("B").join("01 - Intro.flac");
+        for (path, content) in [(&first, b"first"), (&second, b"other")] {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        let mut config = test_config();
+        config.metadata.backup_dir = Some(temp_dir.path().join("backups"));
+        let first_backup = WriteMetadataTool::create_backup(&first, &config).unwrap();
+        let second_backup = WriteMetadataTool::create_backup(&second, &config).unwrap();
+
+        assert_ne!(first_backup, second_backup);
+        assert!(first_backup.ends_with("A/01 - Intro.flac.bak"));
+        assert_eq!(std::fs::read(&first_backup).unwrap(), b"first");
+        assert_eq!(std::fs::read(&second_backup).unwrap(), b"other");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_backup_validates_backup_path() {
+        let root = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let audio_path = root.path().join("song.flac");
+        std::fs::write(&audio_path, b"original").unwrap();
+        let mut config = test_config();
+        config.security.allowed_paths = vec![root.path().to_path_buf()];
+
+        // A backup symlink leading outside the roots is not written through
+        let target = outside.path().join("victim");
+        std::fs::write(&target, b"outside").unwrap();
+        std::os::unix::fs::symlink(&target, root.path().join("song.flac.bak")).unwrap();
+        let err = WriteMetadataTool::create_backup(&audio_path, &config).unwrap_err();
+        assert!(err.contains("security validation failed"), "{}", err);
+        assert_eq!(std::fs::read(&target).unwrap(), b"outside");
+
+        // A backup name matching a deny pattern is refused
+        std::fs::remove_file(root.path().join("song.flac.bak")).unwrap();
+        config.security.deny_patterns = vec!["*.bak".to_string()];
+        let err = WriteMetadataTool::create_backup(&audio_path, &config).unwrap_err();
+        assert!(err.contains("security validation failed"), "{}", err);
+        assert!(!root.path().join("song.flac.bak").exists());
+    }
+
+    #[test]
+    fn test_write_metadata_backup_failure_aborts_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let original = std::fs::read(&audio_path).unwrap();
+        // The backup directory cannot be created below a regular file
+        let not_a_dir = temp_dir.path().join("not_a_dir");
+        std::fs::write(&not_a_dir, b"").unwrap();
+
+        let mut config = test_config();
+        config.metadata.backup_on_write = true;
+        config.metadata.backup_dir = Some(not_a_dir.join("backups"));
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "title": "New Title"
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &config);
+        assert!(result.is_error.unwrap_or(false));
+        let text = format!("{:?}", result.content);
+        assert!(
+            text.contains("Failed to create backup, file not modified"),
+            "{}",
+            text
+        );
+        assert_eq!(std::fs::read(&audio_path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_write_metadata_with_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let original = std::fs::read(&audio_path).unwrap();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "title": "New Title",
+            "backup": true
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+
+        let backup_path = temp_dir.path().join("track.flac.bak");
+        assert_eq!(
+            result.structured_content.unwrap()["backup_path"],
+            backup_path.to_string_lossy().as_ref()
+        );
+        assert_eq!(std::fs::read(&backup_path).unwrap(), original);
+        assert_ne!(std::fs::read(&audio_path).unwrap(), original);
+    }
+
+    /// A minimal MP3 file: a few silent MPEG-1 Layer III frames (128 kbps, 44.1 kHz).
+    fn write_mp3_fixture(path: &Path) {
+        let mut bytes = Vec::new();
+        for _ in 0..4 {
+            bytes.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x00]);
+            bytes.extend_from_slice(&[0; 413]);
+        }
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_write_metadata_id3_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let mp3_path = temp_dir.path().join("track.mp3");
+        let flac_path = temp_dir.path().join("track.flac");
+        write_mp3_fixture(&mp3_path);
+        write_flac_fixture(&flac_path);
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": mp3_path.to_string_lossy(),
+            "title": "Title",
+            "id3_version": "2.3"
+        }))
+        .unwrap();
+        let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+        assert_eq!(data.id3_version, Some(Id3Version::V23));
+        let bytes = std::fs::read(&mp3_path).unwrap();
+        assert_eq!(&bytes[..4], b"ID3\x03");
+        let tagged_file = lofty::read_from_path(&mp3_path).unwrap();
+        assert_eq!(
+            tagged_file.primary_tag().unwrap().title().as_deref(),
+            Some("Title")
+        );
+
+        // The configured version applies when the call doesn't choose one
+        let mut config = test_config();
+        config.metadata.id3_version = Id3Version::V24;
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": mp3_path.to_string_lossy(),
+            "title": "Title 2"
+        }))
+        .unwrap();
+        let data = WriteMetadataTool::write_file(&params, &config).unwrap();
+        assert_eq!(data.id3_version, Some(Id3Version::V24));
+        assert_eq!(&std::fs::read(&mp3_path).unwrap()[..4], b"ID3\x04");
+
+        // Ignored for other formats
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": flac_path.to_string_lossy(),
+            "title": "Title",
+            "id3_version": "2.3"
+        }))
+        .unwrap();
+        let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+        assert_eq!(data.id3_version, None);
+    }
+
+    #[test]
+    fn test_write_metadata_preserve_mtime() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let modified = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+        WriteMetadataTool::restore_mtime(&audio_path, modified).unwrap();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "title": "New Title",
+            "preserve_mtime": true
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+        assert_eq!(result.structured_content.unwrap()["mtime_preserved"], true);
+
+        let metadata = std::fs::metadata(&audio_path).unwrap();
+        assert_eq!(metadata.modified().unwrap(), modified);
+
+        // The tags were written all the same
+        let tagged_file = lofty::read_from_path(&audio_path).unwrap();
+        assert_eq!(
+            tagged_file.primary_tag().unwrap().title().as_deref(),
+            Some("New Title")
+        );
+
+        // Without the option, the write updates the modification time
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "title": "Other Title"
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert_eq!(result.structured_content.unwrap()["mtime_preserved"], false);
+        let metadata = std::fs::metadata(&audio_path).unwrap();
+        assert_ne!(metadata.modified().unwrap(), modified);
+    }
+
+    #[test]
+    fn test_strip_lrc_timestamps() {
+        let lrc = "[ar:Pink Floyd]\n[ti:Time]\n[00:12.34]Ticking away\n[00:15.00][01:20.50]the moments\n\n[00:18]that make up a dull day";
+        assert_eq!(
+            strip_lrc_timestamps(lrc),
+            "Ticking away\nthe moments\n\nthat make up a dull day"
+        );
+        // Plain text passes through
+        assert_eq!(strip_lrc_timestamps("[Chorus]\nLa la"), "[Chorus]\nLa la");
+    }
+
+    #[test]
+    fn test_load_lyrics() {
+        let temp_dir = TempDir::new().unwrap();
+        let lrc_path = temp_dir.path().join("track.LRC");
+        std::fs::write(
+            &lrc_path,
+            "[ti:Song]\n[00:01.00]First line\n[00:02.00]Second line\n",
+        )
+        .unwrap();
+        let mut config = test_config();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": "/music/track.flac",
+            "lyrics_file": lrc_path.to_string_lossy()
+        }))
+        .unwrap();
+        assert_eq!(
+            WriteMetadataTool::load_lyrics(&params, &config)
+                .unwrap()
+                .as_deref(),
+            Some("First line\nSecond line")
+        );
+
+        config.tools.max_lyrics_bytes = 10;
+        let err = WriteMetadataTool::load_lyrics(&params, &config).unwrap_err();
+        assert!(err.contains("max 10 bytes"), "{}", err);
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": "/music/track.flac",
+            "lyrics": "Short",
+            "lyrics_file": lrc_path.to_string_lossy()
+        }))
+        .unwrap();
+        let err = WriteMetadataTool::load_lyrics(&params, &config).unwrap_err();
+        assert!(err.contains("not both"), "{}", err);
+    }
+
+    #[test]
+    fn test_remove_field() {
+        use lofty::tag::Tag;
+
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.set_title("Title".to_string());
+        tag.set_comment("Ripped by XYZ".to_string());
+        tag.set_year(1997);
+        tag.insert_text(ItemKey::AlbumArtist, "Wrong Artist".to_string());
+        tag.insert_text(ItemKey::Composer, "Composer".to_string());
+
+        assert!(WriteMetadataTool::remove_field(&mut tag, "comment"));
+        assert!(WriteMetadataTool::remove_field(&mut tag, "Album_Artist"));
+        assert!(WriteMetadataTool::remove_field(&mut tag, "year"));
+        assert!(WriteMetadataTool::remove_field(&mut tag, "COMPOSER"));
+        assert!(tag.comment().is_none());
+        assert!(tag.get_string(&ItemKey::AlbumArtist).is_none());
+        assert!(tag.year().is_none());
+        assert!(tag.get_string(&ItemKey::Composer).is_none());
+
+        // Missing fields are not an error
+        assert!(!WriteMetadataTool::remove_field(&mut tag, "genre"));
+        assert!(!WriteMetadataTool::remove_field(&mut tag, "NOT_A_KEY"));
+        assert_eq!(tag.title().as_deref(), Some("Title"));
+    }
+
+    /// Minimal PNG: signature plus a 2x3 IHDR chunk.
+    #[test]
+    fn test_write_metadata_copy_tags_from() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("source.flac");
+        let audio_path = temp_dir.path().join("track.flac");
+        let cover_path = temp_dir.path().join("cover.png");
+        write_flac_fixture(&source_path);
+        write_flac_fixture(&audio_path);
+        std::fs::write(&cover_path, png_bytes()).unwrap();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": source_path.to_string_lossy(),
+            "title": "Source Title",
+            "artist": "Source Artist",
+            "genre": "Jazz",
+            "custom_tags": { "CATALOGNUMBER": "XL 123" },
+            "cover_art_path": cover_path.to_string_lossy()
+        }))
+        .unwrap();
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+
+        // Copied first, then genre removed and title overridden
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "copy_tags_from": source_path.to_string_lossy(),
+            "include_pictures": true,
+            "remove_fields": ["genre"],
+            "title": "Own Title"
+        }))
+        .unwrap();
+        let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+        for field in ["artist", "CATALOGNUMBER", "pictures"] {
+            assert!(data.copied_fields.iter().any(|f| f == field), "{}", field);
+        }
+        assert!(
+            !data
+                .copied_fields
+                .iter()
+                .any(|f| f == "title" || f == "genre")
+        );
+        assert_eq!(
+            data.updated_fields.keys().collect::<Vec<_>>(),
+            vec!["title"]
+        );
+        assert_eq!(data.removed_fields, vec!["genre"]);
+
+        let tagged_file = lofty::read_from_path(&audio_path).unwrap();
+        let tag = tagged_file.primary_tag().unwrap();
+        assert_eq!(tag.title().as_deref(), Some("Own Title"));
+        assert_eq!(tag.artist().as_deref(), Some("Source Artist"));
+        assert!(tag.genre().is_none());
+        assert_eq!(custom_tags(tag)["CATALOGNUMBER"], "XL 123");
+        assert_eq!(tag.pictures().len(), 1);
+    }
+
+    #[test]
+    fn test_write_metadata_copy_tags_from_missing_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+        let original = std::fs::read(&audio_path).unwrap();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "copy_tags_from": temp_dir.path().join("missing.flac").to_string_lossy(),
+            "title": "Title"
+        }))
+        .unwrap();
+        let error = WriteMetadataTool::write_file(&params, &test_config()).unwrap_err();
+        assert!(error.contains("copy_tags_from"), "{}", error);
+        assert_eq!(std::fs::read(&audio_path).unwrap(), original);
+    }
+
+    fn identification_json() -> serde_json::Value {
+        serde_json::json!({
+            "file": "/music/track.flac",
+            "status": "success",
+            "matches": [
+                {
+                    "rank": 1,
+                    "confidence": 0.97,
+                    "acoustid": "9ff43b6a-4f16-427c-93c2-92307ca505e0",
+                    "recordings": [{
+                        "id": "b9ad642e-b012-41c7-b72a-42cf4911f9ff",
+                        "title": "Karma Police",
+                        "artists": ["Radiohead"],
+                        "release_groups": [{
+                            "id": "b1392450-e666-3926-a536-22c65f834433",
+                            "name": "OK Computer",
+                            "type": "Album"
+                        }]
+                    }]
+                },
+                {
+                    "rank": 2,
+                    "confidence": 0.41,
+                    "acoustid": "2f1b5a4e-0d2c-4f4e-8a0b-6f1f5e3c2d1a",
+                    "recordings": [{
+                        "id": "0c2e1a5b-8f3d-4b6a-9e7c-1d2f3a4b5c6d",
+                        "title": "Karma Police (live)",
+                        "artists": ["Radiohead", "Guest"]
+                    }]
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_write_metadata_from_identification() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+
+        // Top match by default; the explicit title wins
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "from_identification": identification_json(),
+            "title": "My Title"
+        }))
+        .unwrap();
+        let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+        assert_eq!(data.identification_rank, Some(1));
+        assert_eq!(data.updated_fields["title"], "My Title");
+        assert_eq!(data.updated_fields["artist"], "Radiohead");
+        assert_eq!(data.updated_fields["album"], "OK Computer");
+        assert_eq!(
+            data.updated_fields["mb_recording_id"],
+            "b9ad642e-b012-41c7-b72a-42cf4911f9ff"
+        );
+        assert_eq!(
+            data.updated_fields["mb_release_group_id"],
+            "b1392450-e666-3926-a536-22c65f834433"
+        );
+        assert!(
+            WriteMetadataTool::build_text_summary(&data).ends_with("(identification match #1)")
+        );
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "from_identification": identification_json(),
+            "identification_rank": 2
+        }))
+        .unwrap();
+        let data = WriteMetadataTool::write_file(&params, &test_config()).unwrap();
+        assert_eq!(data.identification_rank, Some(2));
+        assert_eq!(data.updated_fields["title"], "Karma Police (live)");
+        assert!(data.updated_fields.contains_key("artists"));
+
+        let tagged_file = lofty::read_from_path(&audio_path).unwrap();
+        let tag = tagged_file.primary_tag().unwrap();
+        assert_eq!(tag.title().as_deref(), Some("Karma Police (live)"));
+        assert_eq!(tag.get_strings(&ItemKey::TrackArtist).count(), 2);
+        // Not in match #2, so left from the first write
+        assert_eq!(tag.album().as_deref(), Some("OK Computer"));
+    }
+
+    #[test]
+    fn test_write_metadata_from_identification_invalid() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.flac");
+        write_flac_fixture(&audio_path);
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "from_identification": identification_json(),
+            "identification_rank": 5
+        }))
+        .unwrap();
+        let error = WriteMetadataTool::write_file(&params, &test_config()).unwrap_err();
+        assert!(error.contains("No match with rank 5"), "{}", error);
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "from_identification": { "title": "Not an identification" }
+        }))
+        .unwrap();
+        let error = WriteMetadataTool::write_file(&params, &test_config()).unwrap_err();
+        assert!(error.contains("mb_identify_record"), "{}", error);
+    }
+
+    fn png_bytes() -> Vec<u8> {
+        let mut bytes = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        bytes.extend_from_slice(&[0, 0, 0, 13]);
+        bytes.extend_from_slice(b"IHDR");
+        bytes.extend_from_slice(&2u32.to_be_bytes());
+        bytes.extend_from_slice(&3u32.to_be_bytes());
+        bytes.extend_from_slice(&[8, 2, 0, 0, 0]);
+        bytes.extend_from_slice(&[0, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn test_load_cover_art() {
+        let temp_dir = TempDir::new().unwrap();
+        let cover_path = temp_dir.path().join("cover.png");
+        std::fs::write(&cover_path, png_bytes()).unwrap();
+
+        let (picture, description) =
+            WriteMetadataTool::load_cover_art(cover_path.to_str().unwrap(), &test_config())
+                .unwrap();
+        assert_eq!(picture.pic_type(), PictureType::CoverFront);
+        assert_eq!(description, "image/png, 2x3, 33 bytes");
+    }
+
+    #[test]
+    fn test_write_metadata_rejects_invalid_cover_art() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.mp3");
+        std::fs::write(&audio_path, b"not really audio").unwrap();
+        let cover_path = temp_dir.path().join("cover.txt");
+        std::fs::write(&cover_path, b"plain text, not an image").unwrap();
+
+        let params = WriteMetadataParams {
+            path: audio_path.to_string_lossy().to_string(),
+            title: None,
+            artist: None,
+            artists: None,
+            album: None,
+            album_artist: None,
+            compilation: None,
+            year: None,
+            original_date: None,
+            release_country: None,
+            media: None,
+            track: None,
+            track_total: None,
+            disc: None,
+            disc_total: None,
+            genre: None,
+            genres: None,
+            normalize_genre: false,
+            comment: None,
+            artist_sort: None,
+            album_artist_sort: None,
+            album_sort: None,
+            auto_sort_names: false,
+            composer: None,
+            conductor: None,
+            lyricist: None,
+            remixer: None,
+            performers: vec![],
+            custom_tags: None,
+            lyrics: None,
+            lyrics_file: None,
+            bpm: None,
+            replaygain_track_gain: None,
+            replaygain_track_peak: None,
+            replaygain_album_gain: None,
+            replaygain_album_peak: None,
+            mb_recording_id: None,
+            mb_release_id: None,
+            mb_artist_id: None,
+            mb_release_group_id: None,
+            cover_art_path: Some(cover_path.to_string_lossy().to_string()),
+            copy_tags_from: None,
+            include_pictures: false,
+            from_identification: None,
+            identification_rank: None,
+            remove_fields: vec![],
+            clear_existing: false,
+            dry_run: false,
+            backup: None,
+            preserve_mtime: None,
+            id3_version: None,
+        };
+
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(result.is_error.unwrap_or(false));
+        let text = format!("{:?}", result.content);
+        assert!(text.contains("not a supported image"));
+        // The audio file is left untouched
+        assert_eq!(std::fs::read(&audio_path).unwrap(), b"not really audio");
+    }
+
+    #[test]
+    fn test_write_metadata_rejects_disc_zero() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.mp3");
+        std::fs::write(&audio_path, b"not really audio").unwrap();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "disc": 0,
+            "disc_total": 2
+        }))
+        .unwrap();
+
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(result.is_error.unwrap_or(false));
+        let text = format!("{:?}", result.content);
+        assert!(text.contains("Disc number and disc total must be at least 1"));
+        assert_eq!(std::fs::read(&audio_path).unwrap(), b"not really audio");
+    }
+
+    #[test]
+    fn test_mb_id_updates() {
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": "/music/track.flac",
+            "mb_release_id": "https://musicbrainz.org/release/52709206-8816-3C12-9FF6-F957F2F1EECF",
+            "mb_artist_id": "a74b1b7f-71a5-4011-9441-d0b5e4122711"
+        }))
+        .unwrap();
+
+        let updates = WriteMetadataTool::mb_id_updates(&params).unwrap();
+        assert_eq!(
+            updates,
+            vec![
+                (
+                    "mb_release_id",
+                    ItemKey::MusicBrainzReleaseId,
+                    "52709206-8816-3c12-9ff6-f957f2f1eecf".to_string()
+                ),
+                (
+                    "mb_artist_id",
+                    ItemKey::MusicBrainzArtistId,
+                    "a74b1b7f-71a5-4011-9441-d0b5e4122711".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_mb_ids_unsupported() {
+        let mb_ids = vec![(
+            "mb_recording_id",
+            ItemKey::MusicBrainzRecordingId,
+            "a74b1b7f-71a5-4011-9441-d0b5e4122711".to_string(),
+        )];
+        for (tag_type, supported) in [(TagType::VorbisComments, true), (TagType::RiffInfo, false)] {
+            let mut tag = lofty::tag::Tag::new(tag_type);
+            let mut updated_fields = HashMap::new();
+            let mut unsupported_fields = Vec::new();
+            WriteMetadataTool::write_mb_ids(
+                &mut tag,
+                mb_ids.clone(),
+                &mut updated_fields,
+                &mut unsupported_fields,
+            );
+            assert_eq!(updated_fields.contains_key("mb_recording_id"), supported);
+            assert_eq!(unsupported_fields.is_empty(), supported, "{:?}", tag_type);
+        }
+    }
+
+    #[test]
+    fn test_write_metadata_rejects_invalid_mbid() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio_path = temp_dir.path().join("track.mp3");
+        std::fs::write(&audio_path, b"not really audio").unwrap();
+
+        let params: WriteMetadataParams = serde_json::from_value(serde_json::json!({
+            "path": audio_path.to_string_lossy(),
+            "title": "Title",
+            "mb_recording_id": "not-an-mbid"
+        }))
+        .unwrap();
+
+        let result = WriteMetadataTool::execute(&params, &test_config());
+        assert!(result.is_error.unwrap_or(false));
+        let text = format!("{:?}", result.content);
+        assert!(text.contains("'not-an-mbid' is not a valid MusicBrainz ID for mb_recording_id"));
+        assert_eq!(std::fs::read(&audio_path).unwrap(), b"not really audio");
+    }
+
     #[cfg(feature = "http")]
     #[test]
     fn test_write_metadata_http_handler_missing_path() {
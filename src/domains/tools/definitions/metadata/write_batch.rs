@@ -0,0 +1,568 @@
+//! Batch write metadata tool definition.
+//!
+//! Applies write_metadata to several files in one call: shared fields for every file,
+//! with optional per-file overrides (e.g. title and track number of each album track).
+
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::tool::{ToolCallContext, ToolRoute, schema_for_type},
+    model::{CallToolResult, Content, Tool},
+};
+
+use futures::FutureExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::core::audit;
+use crate::core::config::Config;
+use crate::core::security::{AccessKind, validate_path_for};
+use crate::domains::tools::definitions::mb::identify_directory::{
+    MAX_DIRECTORY_FILES, MbIdentifyDirectoryTool,
+};
+
+use super::write::{MetadataWriteResult, WriteMetadataParams, WriteMetadataTool};
+use crate::domains::tools::blocking::run_blocking;
+use crate::domains::tools::timeout::with_audited_timeout;
+
+// ============================================================================
+// Tool Parameters
+// ============================================================================
+
+/// Parameters for the batch write metadata tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct WriteMetadataBatchParams {
+    /// Directory whose audio files all receive `fields` (use either directory or files)
+    #[serde(default)]
+    pub directory: Option<String>,
+
+    /// Also include audio files in subdirectories of `directory` (default: false)
+    #[serde(default)]
+    pub recursive: bool,
+
+    /// Files to write: each entry has a `path` plus any write_metadata parameters, which
+    /// override `fields` for that file, e.g. {"path": "/music/album/01.flac", "title": "Intro", "track": 1}
+    #[serde(default)]
+    pub files: Vec<Map<String, Value>>,
+
+    /// write_metadata parameters applied to every file, e.g. {"album": "Kind of Blue", "year": 1959}
+    #[serde(default)]
+    pub fields: Map<String, Value>,
+
+    /// If true, validate and compute the changes without writing any file
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Copy each file to `<name>.bak` before writing (defaults to MCP_METADATA_BACKUP_ON_WRITE)
+    #[serde(default)]
+    pub backup: Option<bool>,
+}
+
+// ============================================================================
+// Structured Output Types
+// ============================================================================
+
+/// Structured output for a batch metadata write.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BatchWriteResult {
+    /// Scanned directory, when files were collected from a directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory: Option<String>,
+    /// False for a dry run: nothing was written
+    pub applied: bool,
+    pub results: Vec<FileWriteResult>,
+    pub total_count: usize,
+    pub success_count: usize,
+    pub failure_count: usize,
+}
+
+/// Outcome of writing a single file of the batch.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FileWriteResult {
+    pub file: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<MetadataWriteResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// Tool Definition
+// ============================================================================
+
+/// Batch write metadata tool - writes the same or per-file tags to several audio files.
+pub struct WriteMetadataBatchTool;
+
+impl WriteMetadataBatchTool {
+    /// Tool name as registered in MCP.
+    pub const NAME: &'static str = "write_metadata_batch";
+
+    /// Tool description shown to clients.
+    pub const DESCRIPTION: &'static str = "Write metadata tags to several audio files in one call.\n\
+         \n\
+         Use this instead of calling write_metadata once per track, e.g. to tag a whole album.\n\
+         `fields` takes any write_metadata parameters and applies them to every file. Either give\n\
+         a `directory` to apply them to each audio file in it, or a `files` list whose entries hold\n\
+         a `path` plus per-file overrides (title, track, ...).\n\
+         \n\
+         Files are written independently: failures are reported per file and do not abort the\n\
+         batch. dry_run and backup apply to every file.";
+
+    /// Execute the tool logic (for STDIO/TCP transport via rmcp).
+    ///
+    /// Every call is recorded in the audit log, if enabled, before its result is returned.
+    #[instrument(skip_all, fields(directory = ?params.directory, files = params.files.len()))]
+    pub fn execute(params: &WriteMetadataBatchParams, config: &Config) -> CallToolResult {
+        let result = Self::run(params, config);
+        let paths = Self::audited_paths(params);
+        audit::record(config, Self::NAME, params, &paths, &result);
+        result
+    }
+
+    /// Paths given in the parameters, recorded in the audit log.
+    pub(crate) fn audited_paths(params: &WriteMetadataBatchParams) -> Vec<&str> {
+        params
+            .directory
+            .iter()
+            .map(String::as_str)
+            .chain(
+                params
+                    .files
+                    .iter()
+                    .filter_map(|entry| entry.get("path").and_then(Value::as_str)),
+            )
+            .collect()
+    }
+
+    /// Run the tool logic for [`Self::execute`].
+    fn run(params: &WriteMetadataBatchParams, config: &Config) -> CallToolResult {
+        info!("Batch write metadata tool called");
+
+        match Self::write_batch(params, config) {
+            Ok(data) => {
+                let summary = Self::build_text_summary(&data);
+                info!("{}", summary);
+                match serde_json::to_value(&data) {
+                    Ok(structured) => CallToolResult {
+                        content: vec![Content::text(summary)],
+                        structured_content: Some(structured),
+                        is_error: Some(false),
+                        meta: None,
+                    },
+                    Err(e) => {
+                        warn!("Failed to serialize structured content: {}", e);
+                        CallToolResult::success(vec![Content::text(summary)])
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Batch write failed: {}", e);
+                CallToolResult::error(vec![Content::text(e)])
+            }
+        }
+    }
+
+    /// Write every file of the batch sequentially.
+    ///
+    /// Per-file failures are recorded in the results; only invalid batch parameters abort.
+    fn write_batch(
+        params: &WriteMetadataBatchParams,
+        config: &Config,
+    ) -> Result<BatchWriteResult, String> {
+        if params.fields.contains_key("path") {
+            return Err("fields cannot contain path; set it in each files entry".to_string());
+        }
+
+        let directory = match (&params.directory, params.files.is_empty()) {
+            (Some(_), false) => {
+                return Err("Provide either directory or files, not both".to_string());
+            }
+            (None, true) => {
+                return Err("Provide a directory or a list of files".to_string());
+            }
+            (Some(directory), true) => {
+                let directory = validate_path_for(directory, AccessKind::Write, config)
+                    .map_err(|e| format!("Path security validation failed: {}", e))?;
+                if !directory.is_dir() {
+                    return Err(format!("{} is not a directory", directory.display()));
+                }
+                Some(directory)
+            }
+            (None, false) => None,
+        };
+
+        // One (path, overrides) pair per file
+        let empty = Map::new();
+        let entries: Vec<(Option<String>, &Map<String, Value>)> = match &directory {
+            Some(directory) => {
+                MbIdentifyDirectoryTool::collect_audio_files(directory, params.recursive)
+                    .map_err(|e| format!("Cannot read directory: {}", e))?
+                    .iter()
+                    .map(|file| (Some(file.to_string_lossy().into_owned()), &empty))
+                    .collect()
+            }
+            None => params
+                .files
+                .iter()
+                .map(|entry| {
+                    let path = entry.get("path").and_then(Value::as_str);
+                    (path.map(str::to_string), entry)
+                })
+                .collect(),
+        };
+
+        if entries.len() > MAX_DIRECTORY_FILES {
+            return Err(format!(
+                "Batch contains {} audio files (max {} per call); split it into smaller batches",
+                entries.len(),
+                MAX_DIRECTORY_FILES
+            ));
+        }
+
+        let mut results = Vec::with_capacity(entries.len());
+        for (path, overrides) in entries {
+            let outcome = match &path {
+                Some(path) => Self::file_params(params, path, overrides)
+                    .and_then(|file_params| WriteMetadataTool::write_file(&file_params, config)),
+                None => Err("Missing or invalid 'path' in files entry".to_string()),
+            };
+            let file = path.unwrap_or_default();
+
+            match outcome {
+                Ok(result) => results.push(FileWriteResult {
+                    file,
+                    success: true,
+                    result: Some(result),
+                    error: None,
+                }),
+                Err(e) => {
+                    warn!("Failed to write metadata to {}: {}", file, e);
+                    results.push(FileWriteResult {
+                        file,
+                        success: false,
+                        result: None,
+                        error: Some(e),
+                    });
+                }
+            }
+        }
+
+        let success_count = results.iter().filter(|r| r.success).count();
+        Ok(BatchWriteResult {
+            directory: directory.map(|directory| directory.to_string_lossy().into_owned()),
+            applied: !params.dry_run,
+            total_count: results.len(),
+            failure_count: results.len() - success_count,
+            success_count,
+            results,
+        })
+    }
+
+    /// Build the write_metadata parameters of one file.
+    ///
+    /// Per-file overrides take precedence over the shared fields; the batch's dry_run and
+    /// backup options take precedence over both.
+    fn file_params(
+        params: &WriteMetadataBatchParams,
+        path: &str,
+        overrides: &Map<String, Value>,
+    ) -> Result<WriteMetadataParams, String> {
+        let mut arguments = params.fields.clone();
+        arguments.extend(overrides.clone());
+        arguments.insert("path".to_string(), Value::from(path));
+        arguments.insert("dry_run".to_string(), Value::from(params.dry_run));
+        match params.backup {
+            Some(backup) => arguments.insert("backup".to_string(), Value::from(backup)),
+            None => arguments.remove("backup"),
+        };
+
+        serde_json::from_value(Value::Object(arguments))
+            .map_err(|e| format!("Invalid parameters: {}", e))
+    }
+
+    /// Build the aggregate summary line.
+    fn build_text_summary(data: &BatchWriteResult) -> String {
+        if data.total_count == 0 {
+            return match &data.directory {
+                Some(directory) => format!("No audio files found in {}", directory),
+                None => "No files to write".to_string(),
+            };
+        }
+
+        if !data.applied {
+            let changed = data
+                .results
+                .iter()
+                .filter(|r| {
+                    r.result
+                        .as_ref()
+                        .is_some_and(|result| !result.changes.is_empty())
+                })
+                .count();
+            return format!(
+                "DRY RUN: {}/{} file(s) would change ({} failed); nothing was written",
+                changed, data.total_count, data.failure_count
+            );
+        }
+
+        format!(
+            "Wrote metadata to {}/{} file(s) ({} failed)",
+            data.success_count, data.total_count, data.failure_count
+        )
+    }
+
+    /// HTTP handler for this tool (for HTTP transport).
+    #[cfg(feature = "http")]
+    pub fn http_handler(
+        arguments: serde_json::Value,
+        config: Arc<Config>,
+    ) -> Result<serde_json::Value, String> {
+        let params: WriteMetadataBatchParams = serde_json::from_value(arguments)
+            .map_err(|e| format!("Failed to parse parameters: {}", e))?;
+
+        info!("Batch write metadata tool (HTTP) called");
+
+        let result = Self::execute(&params, &config);
+
+        let mut response = serde_json::json!({
+            "content": result.content,
+            "isError": result.is_error.unwrap_or(false)
+        });
+
+        if let Some(structured) = result.structured_content {
+            response
+                .as_object_mut()
+                .unwrap()
+                .insert("structuredContent".to_string(), structured);
+        }
+
+        Ok(response)
+    }
+
+    /// Create a Tool model for this tool (metadata).
+    pub fn to_tool() -> Tool {
+        Tool {
+            name: Self::NAME.into(),
+            description: Some(Self::DESCRIPTION.into()),
+            input_schema: schema_for_type::<WriteMetadataBatchParams>(),
+            annotations: None,
+            output_schema: None,
+            icons: None,
+            meta: None,
+            title: None,
+        }
+    }
+
+    /// Create a ToolRoute for STDIO/TCP transport.
+    pub fn create_route<S>(config: Arc<Config>) -> ToolRoute<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        let timeout = config.tools.timeout_for(Self::NAME);
+        ToolRoute::new_dyn(Self::to_tool(), move |ctx: ToolCallContext<'_, S>| {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let config = config.clone();
+            async move {
+                let params: WriteMetadataBatchParams =
+                    serde_json::from_value(serde_json::Value::Object(args))
+                        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                // Kept to record a timeout, the originals moving to the execution
+                let (audited, audit_config) = (params.clone(), config.clone());
+                with_audited_timeout(
+                    Self::NAME,
+                    timeout,
+                    &audit_config,
+                    &audited,
+                    Self::audited_paths,
+                    run_blocking(move || Self::execute(&params, &config)),
+                )
+                .await
+            }
+            .boxed()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    /// Minimal FLAC file: signature, a STREAMINFO block (44.1kHz, stereo, 16-bit, no frames)
+    /// and a final PADDING block.
+    fn write_flac_fixture(path: &std::path::Path) {
+        let mut bytes = b"fLaC".to_vec();
+        bytes.extend_from_slice(&[0x00, 0, 0, 34]);
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&[0; 6]);
+        bytes.extend_from_slice(&[0x0A, 0xC4, 0x42, 0xF0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&[0x81, 0, 0, 16]);
+        bytes.extend_from_slice(&[0; 16]);
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    fn read_title(path: &std::path::Path) -> Option<String> {
+        use lofty::prelude::*;
+        let tagged_file = lofty::read_from_path(path).unwrap();
+        tagged_file
+            .primary_tag()
+            .and_then(|tag| tag.title().map(|title| title.to_string()))
+    }
+
+    #[test]
+    fn test_batch_requires_directory_or_files() {
+        let params: WriteMetadataBatchParams =
+            serde_json::from_value(json!({ "fields": { "album": "Album" } })).unwrap();
+        let result = WriteMetadataBatchTool::execute(&params, &test_config());
+        assert!(result.is_error.unwrap_or(false));
+
+        let params: WriteMetadataBatchParams = serde_json::from_value(json!({
+            "directory": "/music",
+            "files": [{ "path": "/music/01.flac" }]
+        }))
+        .unwrap();
+        let result = WriteMetadataBatchTool::execute(&params, &test_config());
+        let text = format!("{:?}", result.content);
+        assert!(result.is_error.unwrap_or(false));
+        assert!(text.contains("not both"), "{}", text);
+    }
+
+    #[test]
+    fn test_file_params_precedence() {
+        let params: WriteMetadataBatchParams = serde_json::from_value(json!({
+            "files": [],
+            "fields": { "album": "Album", "title": "Shared", "backup": true },
+            "dry_run": true
+        }))
+        .unwrap();
+        let overrides = json!({ "title": "Own", "track": 2, "dry_run": false });
+        let file_params = WriteMetadataBatchTool::file_params(
+            &params,
+            "/music/02.flac",
+            overrides.as_object().unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(file_params.path, "/music/02.flac");
+        assert_eq!(file_params.album.as_deref(), Some("Album"));
+        assert_eq!(file_params.title.as_deref(), Some("Own"));
+        assert_eq!(file_params.track, Some(2));
+        assert!(file_params.dry_run);
+        // Without a batch-level backup option, the config default applies
+        assert_eq!(file_params.backup, None);
+    }
+
+    #[test]
+    fn test_batch_files_with_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("01.flac");
+        let second = temp_dir.path().join("02.flac");
+        write_flac_fixture(&first);
+        write_flac_fixture(&second);
+
+        let params: WriteMetadataBatchParams = serde_json::from_value(json!({
+            "fields": { "album": "Album", "year": 2001 },
+            "files": [
+                { "path": first.to_string_lossy(), "title": "One", "track": 1 },
+                { "path": second.to_string_lossy(), "title": "Two", "track": 2 },
+                { "path": temp_dir.path().join("missing.flac").to_string_lossy() },
+                { "title": "No path" }
+            ]
+        }))
+        .unwrap();
+        let result = WriteMetadataBatchTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+
+        let data = result.structured_content.unwrap();
+        assert_eq!(data["total_count"], 4);
+        assert_eq!(data["success_count"], 2);
+        assert_eq!(data["failure_count"], 2);
+        assert_eq!(
+            data["results"][0]["result"]["updated_fields"]["album"],
+            "Album"
+        );
+        assert_eq!(
+            data["results"][1]["result"]["updated_fields"]["title"],
+            "Two"
+        );
+        assert!(data["results"][2]["error"].is_string());
+        assert!(data["results"][3]["error"].is_string());
+
+        assert_eq!(read_title(&first).as_deref(), Some("One"));
+        assert_eq!(read_title(&second).as_deref(), Some("Two"));
+    }
+
+    #[test]
+    fn test_batch_directory_dry_run_with_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        write_flac_fixture(&temp_dir.path().join("01.flac"));
+        write_flac_fixture(&temp_dir.path().join("02.flac"));
+        std::fs::write(temp_dir.path().join("cover.jpg"), b"not audio").unwrap();
+
+        let params: WriteMetadataBatchParams = serde_json::from_value(json!({
+            "directory": temp_dir.path().to_string_lossy(),
+            "fields": { "title": "Same" },
+            "dry_run": true,
+            "backup": true
+        }))
+        .unwrap();
+        let result = WriteMetadataBatchTool::execute(&params, &test_config());
+        assert!(!result.is_error.unwrap_or(false), "{:?}", result.content);
+
+        let data = result.structured_content.unwrap();
+        assert_eq!(data["applied"], false);
+        assert_eq!(data["total_count"], 2);
+        assert_eq!(data["results"][0]["result"]["applied"], false);
+        assert_eq!(
+            data["results"][0]["result"]["changes"]["title"]["new"],
+            "Same"
+        );
+
+        // Nothing is written or backed up on a dry run
+        assert_eq!(read_title(&temp_dir.path().join("01.flac")), None);
+        assert!(!temp_dir.path().join("01.flac.bak").exists());
+    }
+
+    #[test]
+    fn test_batch_normalize_genre() {
+        let temp_dir = TempDir::new().unwrap();
+        write_flac_fixture(&temp_dir.path().join("01.flac"));
+        write_flac_fixture(&temp_dir.path().join("02.flac"));
+
+        let params: WriteMetadataBatchParams = serde_json::from_value(json!({
+            "directory": temp_dir.path().to_string_lossy(),
+            "fields": { "genres": ["Hip Hop", "hiphop", "jazz"], "normalize_genre": true }
+        }))
+        .unwrap();
+        let result = WriteMetadataBatchTool::execute(&params, &test_config());
+        let data = result.structured_content.unwrap();
+        assert_eq!(data["success_count"], 2);
+        for file in 0..2 {
+            assert_eq!(
+                data["results"][file]["result"]["updated_fields"]["genres"],
+                "Hip-Hop; jazz"
+            );
+        }
+    }
+
+    #[test]
+    fn test_batch_rejects_shared_path() {
+        let params: WriteMetadataBatchParams = serde_json::from_value(json!({
+            "directory": "/music",
+            "fields": { "path": "/music/01.flac" }
+        }))
+        .unwrap();
+        let result = WriteMetadataBatchTool::execute(&params, &test_config());
+        assert!(result.is_error.unwrap_or(false));
+    }
+}
@@ -7,10 +7,11 @@ pub mod fs;
 pub mod mb;
 pub mod metadata;
 
-pub use fs::{FsDeleteTool, FsListDirTool, FsRenameTool};
+pub use fs::{FsChecksumTool, FsCopyTool, FsDeleteTool, FsDiskUsageTool, FsEmptyDirsTool, FsFindTool, FsListDirTool, FsReadFileTool, FsRenameTool, FsStatTool, FsWriteFileTool};
 pub use mb::{
-    MbArtistParams, MbArtistTool, MbCoverDownloadParams, MbCoverDownloadTool,
-    MbIdentifyRecordTool, MbLabelParams, MbLabelTool, MbRecordingParams, MbRecordingTool,
-    MbReleaseParams, MbReleaseTool, MbWorkParams, MbWorkTool,
+    MbAcoustidSubmitParams, MbAcoustidSubmitTool, MbAdvancedSearchParams, MbAdvancedSearchTool, MbArtistParams, MbArtistTool, MbCoverDownloadParams, MbCoverDownloadTool, MbCoverInfoParams,
+    MbCoverInfoTool, MbDiscographyParams, MbDiscographyTool, MbIdentifyDirectoryParams, MbIdentifyDirectoryTool, MbIdentifyRecordTool,
+    MbLabelParams, MbLabelTool, MbLookupParams, MbLookupTool, MbMatchTracksParams, MbMatchTracksTool, MbRecordingParams, MbRecordingTool, MbReleaseParams, MbReleaseTool,
+    MbWorkParams, MbWorkTool, TagFromReleaseParams, TagFromReleaseTool,
 };
-pub use metadata::{ReadMetadataTool, WriteMetadataTool};
+pub use metadata::{CheckAlbumConsistencyTool, FindDuplicatesTool, MetadataDiffTool, MetadataReportTool, OrganizeLibraryTool, ReadMetadataTool, RenameFromMetadataTool, StripMetadataTool, WriteMetadataBatchTool, WriteMetadataTool};
@@ -1,5 +1,6 @@
 //! Tool-specific error types.
 
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur during tool operations.
@@ -18,8 +19,11 @@ pub enum ToolError {
     ExecutionFailed(String),
 
     /// The tool timed out during execution.
-    #[error("Tool execution timed out")]
-    Timeout,
+    #[error(
+        "Tool '{tool}' timed out after {:.1}s; it may still complete in the background",
+        elapsed.as_secs_f64()
+    )]
+    Timeout { tool: String, elapsed: Duration },
 
     /// An internal error occurred.
     #[error("Internal error: {0}")]
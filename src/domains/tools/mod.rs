@@ -9,6 +9,8 @@
 //! - `definitions/` - Individual tool implementations (one file per tool)
 //! - `router.rs` - Dynamic ToolRouter builder for STDIO/TCP transport
 //! - `registry.rs` - Central tool registry and HTTP dispatch
+//! - `blocking.rs` - Blocking execution of the tool calls, off the async executor
+//! - `timeout.rs` - Execution timeouts of the tool calls
 //! - `error.rs` - Tool-specific error types
 //!
 //! ## Adding a New Tool
@@ -22,11 +24,13 @@
 //!
 //! **No need to modify `server.rs`!** The router is built dynamically.
 
+pub(crate) mod blocking;
 pub mod definitions;
 mod error;
 mod handlers;
 mod registry;
 pub mod router;
+pub(crate) mod timeout;
 
 pub use error::ToolError;
 pub use handlers::*;
@@ -7,16 +7,31 @@
 
 use std::sync::Arc;
 #[cfg(feature = "http")]
+use std::time::Instant;
+#[cfg(feature = "http")]
 use tracing::warn;
 
 use rmcp::model::Tool;
 
+#[cfg(feature = "http")]
+use crate::core::audit;
 use crate::core::config::Config;
-use crate::domains::tools::definitions::MbIdentifyRecordTool;
+#[cfg(feature = "http")]
+use crate::domains::tools::timeout::timeout_result;
+#[cfg(feature = "http")]
+use rmcp::model::CallToolResult;
+#[cfg(feature = "http")]
+use serde::{Serialize, de::DeserializeOwned};
 
 use super::definitions::{
-    FsDeleteTool, FsListDirTool, FsRenameTool, MbArtistTool, MbCoverDownloadTool, MbLabelTool,
-    MbRecordingTool, MbReleaseTool, MbWorkTool, ReadMetadataTool, WriteMetadataTool,
+    CheckAlbumConsistencyTool, FindDuplicatesTool, FsChecksumTool, FsCopyTool, FsDeleteTool,
+    FsDiskUsageTool, FsEmptyDirsTool, FsFindTool, FsListDirTool, FsReadFileTool, FsRenameTool,
+    FsStatTool, FsWriteFileTool, MbAcoustidSubmitTool, MbAdvancedSearchTool, MbArtistTool,
+    MbCoverDownloadTool, MbCoverInfoTool, MbDiscographyTool, MbIdentifyDirectoryTool,
+    MbIdentifyRecordTool, MbLabelTool, MbLookupTool, MbMatchTracksTool, MbRecordingTool,
+    MbReleaseTool, MbWorkTool, MetadataDiffTool, MetadataReportTool, OrganizeLibraryTool,
+    ReadMetadataTool, RenameFromMetadataTool, StripMetadataTool, TagFromReleaseTool,
+    WriteMetadataBatchTool, WriteMetadataTool,
 };
 
 // ============================================================================
@@ -41,18 +56,42 @@ impl ToolRegistry {
     /// Get all tool names.
     pub fn tool_names(&self) -> Vec<&'static str> {
         vec![
+            FsCopyTool::NAME,
             FsDeleteTool::NAME,
             FsListDirTool::NAME,
+            FsReadFileTool::NAME,
+            FsWriteFileTool::NAME,
+            FsStatTool::NAME,
+            FsFindTool::NAME,
+            FsDiskUsageTool::NAME,
+            FsChecksumTool::NAME,
+            FsEmptyDirsTool::NAME,
             FsRenameTool::NAME,
             ReadMetadataTool::NAME,
             WriteMetadataTool::NAME,
+            WriteMetadataBatchTool::NAME,
+            CheckAlbumConsistencyTool::NAME,
+            RenameFromMetadataTool::NAME,
+            OrganizeLibraryTool::NAME,
+            MetadataDiffTool::NAME,
+            MetadataReportTool::NAME,
+            FindDuplicatesTool::NAME,
+            StripMetadataTool::NAME,
+            MbAcoustidSubmitTool::NAME,
+            MbAdvancedSearchTool::NAME,
             MbArtistTool::NAME,
             MbCoverDownloadTool::NAME,
+            MbCoverInfoTool::NAME,
+            MbDiscographyTool::NAME,
+            MbIdentifyDirectoryTool::NAME,
             MbIdentifyRecordTool::NAME,
             MbLabelTool::NAME,
+            MbLookupTool::NAME,
+            MbMatchTracksTool::NAME,
             MbRecordingTool::NAME,
             MbReleaseTool::NAME,
             MbWorkTool::NAME,
+            TagFromReleaseTool::NAME,
         ]
     }
 
@@ -62,47 +101,184 @@ impl ToolRegistry {
     /// Both HTTP and STDIO/TCP transports use this to get tool metadata.
     pub fn get_all_tools() -> Vec<Tool> {
         vec![
+            FsCopyTool::to_tool(),
             FsDeleteTool::to_tool(),
             FsListDirTool::to_tool(),
+            FsReadFileTool::to_tool(),
+            FsWriteFileTool::to_tool(),
+            FsStatTool::to_tool(),
+            FsFindTool::to_tool(),
+            FsDiskUsageTool::to_tool(),
+            FsChecksumTool::to_tool(),
+            FsEmptyDirsTool::to_tool(),
             FsRenameTool::to_tool(),
+            MbAcoustidSubmitTool::to_tool(),
+            MbAdvancedSearchTool::to_tool(),
             MbArtistTool::to_tool(),
             MbCoverDownloadTool::to_tool(),
+            MbCoverInfoTool::to_tool(),
+            MbDiscographyTool::to_tool(),
+            MbIdentifyDirectoryTool::to_tool(),
             MbIdentifyRecordTool::to_tool(),
             MbLabelTool::to_tool(),
+            MbLookupTool::to_tool(),
+            MbMatchTracksTool::to_tool(),
             MbRecordingTool::to_tool(),
             MbReleaseTool::to_tool(),
             MbWorkTool::to_tool(),
             ReadMetadataTool::to_tool(),
+            TagFromReleaseTool::to_tool(),
             WriteMetadataTool::to_tool(),
+            WriteMetadataBatchTool::to_tool(),
+            CheckAlbumConsistencyTool::to_tool(),
+            RenameFromMetadataTool::to_tool(),
+            OrganizeLibraryTool::to_tool(),
+            MetadataDiffTool::to_tool(),
+            MetadataReportTool::to_tool(),
+            FindDuplicatesTool::to_tool(),
+            StripMetadataTool::to_tool(),
         ]
     }
 
     /// Dispatch an HTTP tool call to the appropriate handler.
     ///
-    /// This is used by the HTTP transport to call tools.
+    /// This is used by the HTTP transport to call tools. The handlers block, so they run
+    /// on tokio's blocking thread pool, for at most the tool's timeout; a handler still
+    /// running then is left detached and the call is answered with a timeout error, which
+    /// is recorded in the audit log for the tools that modify files.
     #[cfg(feature = "http")]
-    pub fn call_tool(
+    pub async fn call_tool(
         &self,
         name: &str,
         arguments: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let timeout = self.config.tools.timeout_for(name);
+        let start = Instant::now();
+        let tool = name.to_string();
+        let config = self.config.clone();
+        let audited = arguments.clone();
+        let dispatch =
+            tokio::task::spawn_blocking(move || Self::dispatch(&tool, arguments, config));
+
+        match tokio::time::timeout(timeout, dispatch).await {
+            Ok(result) => result.map_err(|e| format!("Tool task failed: {}", e))?,
+            Err(_) => {
+                let result = timeout_result(name, start.elapsed(), timeout);
+                Self::record_timeout(name, audited, &self.config, &result);
+                serde_json::to_value(result).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    /// Record a call answered with a timeout in the audit log, if the tool modifies
+    /// files. Its handler carries on and records the call again once it completes.
+    #[cfg(feature = "http")]
+    fn record_timeout(
+        name: &str,
+        args: serde_json::Value,
+        config: &Config,
+        result: &CallToolResult,
+    ) {
+        /// The parameters of a call as the tool records them, and its paths.
+        fn parse<P: Serialize + DeserializeOwned>(
+            args: serde_json::Value,
+            paths: fn(&P) -> Vec<&str>,
+        ) -> (serde_json::Value, Vec<String>) {
+            // The handlers parse their arguments leniently, so keep them as given if
+            // they do not parse strictly
+            match serde_json::from_value::<P>(args.clone()) {
+                Ok(params) => (
+                    serde_json::to_value(&params).unwrap_or(args),
+                    paths(&params).into_iter().map(String::from).collect(),
+                ),
+                Err(_) => (args, Vec::new()),
+            }
+        }
+
+        let (params, paths) = match name {
+            FsCopyTool::NAME => parse(args, FsCopyTool::audited_paths),
+            FsDeleteTool::NAME => parse(args, FsDeleteTool::audited_paths),
+            FsEmptyDirsTool::NAME => parse(args, FsEmptyDirsTool::audited_paths),
+            FsRenameTool::NAME => parse(args, FsRenameTool::audited_paths),
+            FsWriteFileTool::NAME => parse(args, FsWriteFileTool::audited_paths),
+            MbCoverDownloadTool::NAME => parse(args, MbCoverDownloadTool::audited_paths),
+            OrganizeLibraryTool::NAME => parse(args, OrganizeLibraryTool::audited_paths),
+            ReadMetadataTool::NAME
+                if args.get("extract_cover_to").is_some_and(|v| !v.is_null()) =>
+            {
+                parse(args, ReadMetadataTool::audited_paths)
+            }
+            RenameFromMetadataTool::NAME => parse(args, RenameFromMetadataTool::audited_paths),
+            StripMetadataTool::NAME => parse(args, StripMetadataTool::audited_paths),
+            TagFromReleaseTool::NAME => parse(args, TagFromReleaseTool::audited_paths),
+            WriteMetadataBatchTool::NAME => parse(args, WriteMetadataBatchTool::audited_paths),
+            WriteMetadataTool::NAME => parse(args, WriteMetadataTool::audited_paths),
+            _ => return,
+        };
+        let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+        audit::record_timeout(config, name, &params, &paths, result);
+    }
+
+    /// Call the HTTP handler of a tool.
+    #[cfg(feature = "http")]
+    fn dispatch(
+        name: &str,
+        arguments: serde_json::Value,
+        config: Arc<Config>,
     ) -> Result<serde_json::Value, String> {
         match name {
-            FsDeleteTool::NAME => FsDeleteTool::http_handler(arguments, self.config.clone()),
-            FsListDirTool::NAME => FsListDirTool::http_handler(arguments, self.config.clone()),
-            FsRenameTool::NAME => FsRenameTool::http_handler(arguments, self.config.clone()),
+            FsCopyTool::NAME => FsCopyTool::http_handler(arguments, config.clone()),
+            FsDeleteTool::NAME => FsDeleteTool::http_handler(arguments, config.clone()),
+            FsListDirTool::NAME => FsListDirTool::http_handler(arguments, config.clone()),
+            FsReadFileTool::NAME => FsReadFileTool::http_handler(arguments, config.clone()),
+            FsWriteFileTool::NAME => FsWriteFileTool::http_handler(arguments, config.clone()),
+            FsStatTool::NAME => FsStatTool::http_handler(arguments, config.clone()),
+            FsFindTool::NAME => FsFindTool::http_handler(arguments, config.clone()),
+            FsDiskUsageTool::NAME => FsDiskUsageTool::http_handler(arguments, config.clone()),
+            FsChecksumTool::NAME => FsChecksumTool::http_handler(arguments, config.clone()),
+            FsEmptyDirsTool::NAME => FsEmptyDirsTool::http_handler(arguments, config.clone()),
+            FsRenameTool::NAME => FsRenameTool::http_handler(arguments, config.clone()),
+            MbAcoustidSubmitTool::NAME => {
+                MbAcoustidSubmitTool::http_handler(arguments, config.clone())
+            }
+            MbAdvancedSearchTool::NAME => MbAdvancedSearchTool::http_handler(arguments),
             MbArtistTool::NAME => MbArtistTool::http_handler(arguments),
             MbCoverDownloadTool::NAME => {
-                MbCoverDownloadTool::http_handler(arguments, self.config.clone())
+                MbCoverDownloadTool::http_handler(arguments, config.clone())
+            }
+            MbCoverInfoTool::NAME => MbCoverInfoTool::http_handler(arguments),
+            MbDiscographyTool::NAME => MbDiscographyTool::http_handler(arguments),
+            MbIdentifyDirectoryTool::NAME => {
+                MbIdentifyDirectoryTool::http_handler(arguments, config.clone())
             }
             MbIdentifyRecordTool::NAME => {
-                MbIdentifyRecordTool::http_handler(arguments, self.config.clone())
+                MbIdentifyRecordTool::http_handler(arguments, config.clone())
             }
             MbLabelTool::NAME => MbLabelTool::http_handler(arguments),
+            MbLookupTool::NAME => MbLookupTool::http_handler(arguments),
+            MbMatchTracksTool::NAME => MbMatchTracksTool::http_handler(arguments, config.clone()),
             MbRecordingTool::NAME => MbRecordingTool::http_handler(arguments),
             MbReleaseTool::NAME => MbReleaseTool::http_handler(arguments),
             MbWorkTool::NAME => MbWorkTool::http_handler(arguments),
-            ReadMetadataTool::NAME => ReadMetadataTool::http_handler(arguments, self.config.clone()),
-            WriteMetadataTool::NAME => WriteMetadataTool::http_handler(arguments, self.config.clone()),
+            TagFromReleaseTool::NAME => TagFromReleaseTool::http_handler(arguments, config.clone()),
+            ReadMetadataTool::NAME => ReadMetadataTool::http_handler(arguments, config.clone()),
+            WriteMetadataTool::NAME => WriteMetadataTool::http_handler(arguments, config.clone()),
+            WriteMetadataBatchTool::NAME => {
+                WriteMetadataBatchTool::http_handler(arguments, config.clone())
+            }
+            CheckAlbumConsistencyTool::NAME => {
+                CheckAlbumConsistencyTool::http_handler(arguments, config.clone())
+            }
+            RenameFromMetadataTool::NAME => {
+                RenameFromMetadataTool::http_handler(arguments, config.clone())
+            }
+            OrganizeLibraryTool::NAME => {
+                OrganizeLibraryTool::http_handler(arguments, config.clone())
+            }
+            MetadataDiffTool::NAME => MetadataDiffTool::http_handler(arguments, config.clone()),
+            MetadataReportTool::NAME => MetadataReportTool::http_handler(arguments, config.clone()),
+            FindDuplicatesTool::NAME => FindDuplicatesTool::http_handler(arguments, config.clone()),
+            StripMetadataTool::NAME => StripMetadataTool::http_handler(arguments, config.clone()),
             _ => {
                 warn!("Unknown tool requested: {}", name);
                 Err(format!("Unknown tool: {}", name))
@@ -123,34 +299,94 @@ mod tests {
     fn test_registry_tool_names() {
         let registry = ToolRegistry::new(test_config());
         let names = registry.tool_names();
-        assert_eq!(names.len(), 12);
+        assert_eq!(names.len(), 36);
         assert!(names.contains(&"fs_delete"));
         assert!(names.contains(&"fs_list_dir"));
         assert!(names.contains(&"fs_rename"));
+        assert!(names.contains(&"fs_copy"));
+        assert!(names.contains(&"fs_read_file"));
+        assert!(names.contains(&"fs_write_file"));
+        assert!(names.contains(&"fs_stat"));
+        assert!(names.contains(&"fs_find"));
+        assert!(names.contains(&"fs_disk_usage"));
+        assert!(names.contains(&"fs_checksum"));
+        assert!(names.contains(&"fs_empty_dirs"));
+        assert!(names.contains(&"mb_acoustid_submit"));
+        assert!(names.contains(&"mb_advanced_search"));
         assert!(names.contains(&"mb_artist_search"));
         assert!(names.contains(&"mb_cover_download"));
+        assert!(names.contains(&"mb_cover_info"));
+        assert!(names.contains(&"mb_discography"));
+        assert!(names.contains(&"mb_identify_directory"));
         assert!(names.contains(&"mb_identify_record"));
         assert!(names.contains(&"mb_label_search"));
+        assert!(names.contains(&"mb_lookup"));
+        assert!(names.contains(&"mb_match_tracks"));
         assert!(names.contains(&"mb_recording_search"));
         assert!(names.contains(&"mb_release_search"));
         assert!(names.contains(&"mb_work_search"));
         assert!(names.contains(&"read_metadata"));
         assert!(names.contains(&"write_metadata"));
+        assert!(names.contains(&"write_metadata_batch"));
+        assert!(names.contains(&"tag_from_release"));
+        assert!(names.contains(&"check_album_consistency"));
+        assert!(names.contains(&"rename_from_metadata"));
+        assert!(names.contains(&"organize_library"));
+        assert!(names.contains(&"metadata_diff"));
+        assert!(names.contains(&"metadata_report"));
+        assert!(names.contains(&"find_duplicates"));
+        assert!(names.contains(&"strip_metadata"));
     }
 
     #[cfg(feature = "http")]
-    #[test]
-    fn test_registry_call_echo() {
+    #[tokio::test]
+    async fn test_registry_call_echo() {
         let registry = ToolRegistry::new(test_config());
-        let result = registry.call_tool("fs_list_dir", serde_json::json!({ "path": "test" }));
+        let result = registry
+            .call_tool("fs_list_dir", serde_json::json!({ "path": "test" }))
+            .await;
         assert!(result.is_ok());
     }
 
     #[cfg(feature = "http")]
-    #[test]
-    fn test_registry_call_unknown() {
+    #[tokio::test]
+    async fn test_registry_call_unknown() {
         let registry = ToolRegistry::new(test_config());
-        let result = registry.call_tool("unknown", serde_json::json!({}));
+        let result = registry.call_tool("unknown", serde_json::json!({})).await;
         assert!(result.is_err());
     }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_record_timeout() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.audit.path = Some(temp_dir.path().join("audit.jsonl"));
+        let result = CallToolResult::error(vec![]);
+
+        ToolRegistry::record_timeout(
+            "fs_delete",
+            serde_json::json!({ "path": "Old" }),
+            &config,
+            &result,
+        );
+        // Only the tools that modify files are recorded
+        ToolRegistry::record_timeout(
+            "fs_stat",
+            serde_json::json!({ "path": "Old" }),
+            &config,
+            &result,
+        );
+
+        let log = std::fs::read_to_string(config.audit.path.as_ref().unwrap()).unwrap();
+        let entries: Vec<serde_json::Value> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["tool"], "fs_delete");
+        assert_eq!(entries[0]["outcome"], "timeout");
+        assert_eq!(entries[0]["params"]["recursive"], false);
+        assert_eq!(entries[0]["paths"].as_array().unwrap().len(), 1);
+    }
 }
@@ -11,8 +11,10 @@ use crate::core::config::Config;
 use crate::domains::tools::definitions::MbIdentifyRecordTool;
 
 use super::definitions::{
-    FsDeleteTool, FsListDirTool, FsRenameTool, MbArtistTool, MbCoverDownloadTool, MbLabelTool,
-    MbRecordingTool, MbReleaseTool, MbWorkTool, ReadMetadataTool, WriteMetadataTool,
+    FsCopyTool, FsDeleteTool, FsListDirTool, FsReadFileTool, FsRenameTool, FsStatTool, FsFindTool, FsDiskUsageTool, FsChecksumTool, FsEmptyDirsTool, FsWriteFileTool, MbAcoustidSubmitTool, MbAdvancedSearchTool, MbArtistTool, MbCoverDownloadTool, MbCoverInfoTool,
+    MbDiscographyTool, MbIdentifyDirectoryTool, MbLabelTool, MbLookupTool, MbMatchTracksTool, MbRecordingTool, MbReleaseTool, MbWorkTool, ReadMetadataTool, TagFromReleaseTool, WriteMetadataBatchTool, WriteMetadataTool,
+    CheckAlbumConsistencyTool, FindDuplicatesTool, MetadataDiffTool, MetadataReportTool, OrganizeLibraryTool, RenameFromMetadataTool,
+    StripMetadataTool,
 };
 
 /// Build the tool router with all registered tools.
@@ -21,18 +23,42 @@ where
     S: Send + Sync + 'static,
 {
     ToolRouter::new()
+        .with_route(FsCopyTool::create_route(config.clone()))
         .with_route(FsDeleteTool::create_route(config.clone()))
         .with_route(FsListDirTool::create_route(config.clone()))
+        .with_route(FsReadFileTool::create_route(config.clone()))
+        .with_route(FsWriteFileTool::create_route(config.clone()))
+        .with_route(FsStatTool::create_route(config.clone()))
+        .with_route(FsFindTool::create_route(config.clone()))
+        .with_route(FsDiskUsageTool::create_route(config.clone()))
+        .with_route(FsChecksumTool::create_route(config.clone()))
+        .with_route(FsEmptyDirsTool::create_route(config.clone()))
         .with_route(FsRenameTool::create_route(config.clone()))
-        .with_route(MbArtistTool::create_route())
+        .with_route(MbAcoustidSubmitTool::create_route(config.clone()))
+        .with_route(MbAdvancedSearchTool::create_route(config.clone()))
+        .with_route(MbArtistTool::create_route(config.clone()))
         .with_route(MbCoverDownloadTool::create_route(config.clone()))
+        .with_route(MbCoverInfoTool::create_route(config.clone()))
+        .with_route(MbDiscographyTool::create_route(config.clone()))
+        .with_route(MbIdentifyDirectoryTool::create_route(config.clone()))
         .with_route(MbIdentifyRecordTool::create_route(config.clone()))
-        .with_route(MbLabelTool::create_route())
-        .with_route(MbRecordingTool::create_route())
-        .with_route(MbReleaseTool::create_route())
-        .with_route(MbWorkTool::create_route())
+        .with_route(MbLabelTool::create_route(config.clone()))
+        .with_route(MbLookupTool::create_route(config.clone()))
+        .with_route(MbMatchTracksTool::create_route(config.clone()))
+        .with_route(MbRecordingTool::create_route(config.clone()))
+        .with_route(MbReleaseTool::create_route(config.clone()))
+        .with_route(MbWorkTool::create_route(config.clone()))
         .with_route(ReadMetadataTool::create_route(config.clone()))
-        .with_route(WriteMetadataTool::create_route(config))
+        .with_route(TagFromReleaseTool::create_route(config.clone()))
+        .with_route(WriteMetadataTool::create_route(config.clone()))
+        .with_route(WriteMetadataBatchTool::create_route(config.clone()))
+        .with_route(CheckAlbumConsistencyTool::create_route(config.clone()))
+        .with_route(RenameFromMetadataTool::create_route(config.clone()))
+        .with_route(OrganizeLibraryTool::create_route(config.clone()))
+        .with_route(MetadataDiffTool::create_route(config.clone()))
+        .with_route(MetadataReportTool::create_route(config.clone()))
+        .with_route(FindDuplicatesTool::create_route(config.clone()))
+        .with_route(StripMetadataTool::create_route(config))
 }
 
 #[cfg(test)]
@@ -50,18 +76,30 @@ mod tests {
     fn test_build_router() {
         let router: ToolRouter<TestServer> = build_tool_router(test_config());
         let tools = router.list_all();
-        assert_eq!(tools.len(), 12);
+        assert_eq!(tools.len(), 36);
 
         let names: Vec<_> = tools.iter().map(|t| t.name.as_ref()).collect();
         assert!(names.contains(&"fs_delete"));
         assert!(names.contains(&"fs_list_dir"));
+        assert!(names.contains(&"mb_acoustid_submit"));
+        assert!(names.contains(&"mb_advanced_search"));
         assert!(names.contains(&"mb_artist_search"));
         assert!(names.contains(&"mb_cover_download"));
+        assert!(names.contains(&"mb_cover_info"));
+        assert!(names.contains(&"mb_discography"));
         assert!(names.contains(&"mb_release_search"));
         assert!(names.contains(&"mb_recording_search"));
         assert!(names.contains(&"mb_label_search"));
+        assert!(names.contains(&"mb_lookup"));
+        assert!(names.contains(&"mb_match_tracks"));
         assert!(names.contains(&"mb_work_search"));
+        assert!(names.contains(&"mb_identify_directory"));
         assert!(names.contains(&"mb_identify_record"));
+        assert!(names.contains(&"tag_from_release"));
+        assert!(names.contains(&"check_album_consistency"));
+        assert!(names.contains(&"rename_from_metadata"));
+        assert!(names.contains(&"organize_library"));
+        assert!(names.contains(&"metadata_diff"));
     }
 
     #[test]
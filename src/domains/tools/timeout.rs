@@ -0,0 +1,177 @@
+//! Execution timeouts of the tool calls.
+//!
+//! Every call is bounded by `tools.default_timeout_secs`, or the tool's entry in
+//! `tools.timeouts`. A call still running when its timeout expires is answered with a
+//! timeout error. Blocking filesystem and network calls cannot be interrupted, so its
+//! work carries on detached and its result is dropped. For a tool that modifies files,
+//! the timeout is recorded in the audit log, where the work is recorded again once it
+//! completes.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+};
+use serde::Serialize;
+use tracing::warn;
+
+use super::error::ToolError;
+use crate::core::audit;
+use crate::core::config::Config;
+
+/// Structured content of a timeout error.
+#[derive(Debug, Serialize)]
+struct TimeoutError<'a> {
+    /// Always "timeout"
+    error: &'static str,
+    /// Name of the tool called
+    tool: &'a str,
+    /// Time the call ran before it was answered
+    elapsed_secs: f64,
+    /// Timeout of the tool
+    timeout_secs: u64,
+}
+
+/// Await a tool's execution for at most `timeout`.
+pub async fn with_timeout<F>(
+    tool: &str,
+    timeout: Duration,
+    execution: F,
+) -> Result<CallToolResult, McpError>
+where
+    F: Future<Output = Result<CallToolResult, McpError>>,
+{
+    let start = Instant::now();
+    match tokio::time::timeout(timeout, execution).await {
+        Ok(result) => result,
+        Err(_) => Ok(timeout_result(tool, start.elapsed(), timeout)),
+    }
+}
+
+/// [`with_timeout`] for a tool whose calls are recorded in the audit log: a timeout is
+/// recorded too, with the call's parameters and the paths `paths` takes from them.
+pub async fn with_audited_timeout<P, F>(
+    tool: &str,
+    timeout: Duration,
+    config: &Config,
+    params: &P,
+    paths: fn(&P) -> Vec<&str>,
+    execution: F,
+) -> Result<CallToolResult, McpError>
+where
+    P: Serialize,
+    F: Future<Output = Result<CallToolResult, McpError>>,
+{
+    let start = Instant::now();
+    match tokio::time::timeout(timeout, execution).await {
+        Ok(result) => result,
+        Err(_) => {
+            let result = timeout_result(tool, start.elapsed(), timeout);
+            audit::record_timeout(config, tool, params, &paths(params), &result);
+            Ok(result)
+        }
+    }
+}
+
+/// The error answering a call that ran past its timeout, naming the tool and the time
+/// it ran.
+pub fn timeout_result(tool: &str, elapsed: Duration, timeout: Duration) -> CallToolResult {
+    let error = ToolError::Timeout {
+        tool: tool.to_string(),
+        elapsed,
+    };
+    warn!("{}", error);
+
+    let structured = TimeoutError {
+        error: "timeout",
+        tool,
+        elapsed_secs: (elapsed.as_secs_f64() * 10.0).round() / 10.0,
+        timeout_secs: timeout.as_secs(),
+    };
+    CallToolResult {
+        content: vec![Content::text(error.to_string())],
+        structured_content: Some(serde_json::to_value(&structured).unwrap()),
+        is_error: Some(true),
+        meta: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::tools::blocking::{run_blocking, run_on_thread};
+
+    #[tokio::test]
+    async fn test_with_timeout() {
+        let result = with_timeout(
+            "fs_stat",
+            Duration::from_secs(5),
+            run_blocking(|| CallToolResult::success(vec![Content::text("done")])),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.is_error, Some(false));
+
+        // A hung execution is answered when the timeout expires, not when it returns
+        let start = Instant::now();
+        let result = with_timeout(
+            "mb_identify_record",
+            Duration::from_millis(100),
+            run_on_thread(|| {
+                std::thread::sleep(Duration::from_secs(2));
+                CallToolResult::success(vec![])
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(result.is_error, Some(true));
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["error"], "timeout");
+        assert_eq!(structured["tool"], "mb_identify_record");
+        assert!(structured["elapsed_secs"].as_f64().unwrap() >= 0.1);
+        let text = match &result.content[0].raw {
+            rmcp::model::RawContent::Text(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+        assert!(
+            text.starts_with("Tool 'mb_identify_record' timed out after"),
+            "{}",
+            text
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_audited_timeout() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.audit.path = Some(temp_dir.path().join("audit.jsonl"));
+        let params = serde_json::json!({ "path": "Old" });
+        fn paths(params: &serde_json::Value) -> Vec<&str> {
+            vec![params["path"].as_str().unwrap()]
+        }
+
+        let result = with_audited_timeout(
+            "fs_delete",
+            Duration::from_millis(100),
+            &config,
+            &params,
+            paths,
+            run_on_thread(|| {
+                std::thread::sleep(Duration::from_secs(1));
+                CallToolResult::success(vec![])
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let log = std::fs::read_to_string(config.audit.path.as_ref().unwrap()).unwrap();
+        let entry: serde_json::Value = serde_json::from_str(log.trim()).unwrap();
+        assert_eq!(entry["tool"], "fs_delete");
+        assert_eq!(entry["outcome"], "timeout");
+        assert_eq!(entry["params"], params);
+    }
+}
@@ -3,16 +3,26 @@
 //! This is the main entry point for the MCP server. It initializes logging,
 //! loads configuration, and starts the server with the configured transport.
 
-use anyhow::Result;
+use anyhow::{Result, bail};
+use std::path::PathBuf;
 use tracing::{Level, info};
 use tracing_subscriber::{EnvFilter, fmt};
 
 use music_mcp_server::core::{Config, McpServer, TransportService};
+use music_mcp_server::domains::tools::ToolRegistry;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Load configuration from environment
-    let config = Config::from_env();
+    // Load configuration from the --config file or MCP_CONFIG_FILE, and the environment
+    let config = Config::load(config_path_arg()?.as_deref())?;
+
+    // Refuse to start with a broken configuration, listing every problem
+    let tools = ToolRegistry::get_all_tools();
+    let tool_names: Vec<&str> = tools.iter().map(|tool| tool.name.as_ref()).collect();
+    if let Err(problems) = config.validate(&tool_names) {
+        eprintln!("{}", problems);
+        std::process::exit(1);
+    }
 
     // Initialize logging
     init_logging(&config.logging.level);
@@ -33,6 +43,23 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Path given with `--config <path>` or `--config=<path>`, if any.
+fn config_path_arg() -> Result<Option<PathBuf>> {
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            let Some(path) = args.next() else {
+                bail!("--config requires a path to a TOML configuration file");
+            };
+            return Ok(Some(PathBuf::from(path)));
+        }
+        if let Some(path) = arg.to_str().and_then(|a| a.strip_prefix("--config=")) {
+            return Ok(Some(PathBuf::from(path)));
+        }
+    }
+    Ok(None)
+}
+
 /// Initialize the logging subsystem.
 ///
 /// Configures tracing with the specified log level and format.